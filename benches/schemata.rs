@@ -0,0 +1,696 @@
+//! Benchmarks of schema construction and contract validation.
+//!
+//! Run with `cargo bench`; a single schema can be selected with e.g. `cargo bench -- nia`.
+
+use amplify::confinement::SmallBlob;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::containers::{Contract, ValidConsignment};
+use rgbstd::contract::{ContractBuilder, IssuerWrapper};
+use rgbstd::invoice::Precision;
+use rgbstd::stl::{AssetSpec, Name, ProofOfReserves, TokenData};
+use rgbstd::validation::ValidationConfig;
+use rgbstd::{Allocation, Amount, ContractId, Outpoint, TokenIndex};
+use schemata::height::block_height;
+use schemata::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+    DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+    GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+    NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+    ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+    SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+    TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+    VerifiableCredential, VestingAsset, WrappedBtcAsset,
+};
+use schemata_tools::testing::{builder, issue, seal, spec, terms, txid, MockResolver, CHAIN_NET};
+
+fn nia() -> ContractBuilder {
+    builder::<NonInflatableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn cfa() -> ContractBuilder {
+    builder::<CollectibleFungibleAsset>()
+        .add_global_state("name", Name::from("Test asset"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn pubkey() -> CompressedPublicKey {
+    CompressedPublicKey::from_slice(&[
+        2, 199, 163, 211, 116, 75, 108, 119, 241, 66, 54, 236, 233, 189, 142, 108, 37, 135, 56,
+        128, 200, 176, 199, 9, 117, 132, 72, 200, 167, 185, 4, 64, 53,
+    ])
+    .unwrap()
+}
+
+fn pfa() -> ContractBuilder {
+    builder::<PermissionedFungibleAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn ifa() -> ContractBuilder {
+    builder::<InflatableFungibleAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(1500u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), 500u64)
+        .unwrap()
+        .add_rights("replaceRight", seal(3))
+        .unwrap()
+}
+
+fn ria() -> ContractBuilder {
+    builder::<ReissuableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("reissueRight", seal(2))
+        .unwrap()
+}
+
+fn sba() -> ContractBuilder {
+    builder::<SoulboundAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("revokeRight", seal(2))
+        .unwrap()
+}
+
+fn uda() -> ContractBuilder {
+    let index = TokenIndex::from(2);
+    builder::<UniqueDigitalAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test uda", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(index, 1))
+        .unwrap()
+}
+
+fn fua() -> ContractBuilder {
+    let index = TokenIndex::from(2);
+    builder::<FractionalUniqueAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test fua", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(100u64))
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(index, 60))
+        .unwrap()
+        .add_data("assetOwner", seal(2), Allocation::with(index, 40))
+        .unwrap()
+}
+
+fn collection() -> ContractBuilder {
+    let mut builder = builder::<UniqueDigitalCollection>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test collection", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    // genesis validation matches every token against every allocation
+    for no in 0..32u32 {
+        let index = TokenIndex::from(no);
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", seal(no + 1), Allocation::with(index, 1))
+            .unwrap();
+    }
+    builder
+}
+
+fn sfa() -> ContractBuilder {
+    let mut builder = builder::<SemiFungibleAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    // genesis validation looks every allocation up among the tokens
+    for no in 0..32u32 {
+        let index = TokenIndex::from(no);
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", seal(no + 1), Allocation::with(index, 1000))
+            .unwrap();
+    }
+    builder
+}
+
+fn stablecoin() -> ContractBuilder {
+    builder::<StablecoinAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(1500u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), 500u64)
+        .unwrap()
+}
+
+fn bond() -> ContractBuilder {
+    builder::<BondAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("faceValue", Amount::from(100_000u64))
+        .unwrap()
+        .add_global_state("couponRate", Amount::from(450u64))
+        .unwrap()
+        .add_global_state("maturity", block_height(900_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn governance() -> ContractBuilder {
+    builder::<GovernanceAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("votingUnit", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("voteRight", seal(1))
+        .unwrap()
+}
+
+fn ticket() -> ContractBuilder {
+    let mut builder = builder::<TicketAsset>()
+        .add_global_state("spec", AssetSpec::new("TKT", "Test tickets", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for no in 0..32u32 {
+        let index = TokenIndex::from(no);
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", seal(no + 1), Allocation::with(index, 1))
+            .unwrap();
+    }
+    builder
+}
+
+fn vesting() -> ContractBuilder {
+    builder::<VestingAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("vestedSupply", Amount::from(600u64))
+        .unwrap()
+        .add_global_state("vestingCliff", block_height(850_000))
+        .unwrap()
+        .add_global_state("vestingEnd", block_height(900_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 400u64)
+        .unwrap()
+        .add_fungible_state("lockedAsset", seal(2), 600u64)
+        .unwrap()
+}
+
+fn timelock() -> ContractBuilder {
+    builder::<TimelockAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("lockHeight", block_height(900_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn pfan() -> ContractBuilder {
+    builder::<MultiKeyPermissionedAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn freezable() -> ContractBuilder {
+    builder::<FreezableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("freezeRight", seal(2))
+        .unwrap()
+}
+
+fn tifa() -> ContractBuilder {
+    builder::<ThresholdInflatableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(1500u64))
+        .unwrap()
+        .add_global_state("inflationThreshold", Amount::from(1u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), 500u64)
+        .unwrap()
+        .add_data("inflationApproval", seal(3), Allocation::with(TokenIndex::from(0), 0))
+        .unwrap()
+}
+
+fn wbtc() -> ContractBuilder {
+    let proof = SmallBlob::try_from_iter(vec![0x52; 64]).unwrap();
+    let reserves = ProofOfReserves::new(Outpoint::new(txid(), 0), proof);
+    builder::<WrappedBtcAsset>()
+        .add_metadata("reserveProof", reserves.clone())
+        .unwrap()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("reserves", reserves)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("mintRight", seal(2))
+        .unwrap()
+}
+
+fn fee() -> ContractBuilder {
+    builder::<TransferFeeAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("feeRate", Amount::from(25u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn dividend() -> ContractBuilder {
+    builder::<DividendAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("distributeRight", seal(2))
+        .unwrap()
+}
+
+fn escrow() -> ContractBuilder {
+    builder::<EscrowAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn swap() -> ContractBuilder {
+    builder::<SwapOrder>()
+        .add_global_state("offeredAsset", SwapOrder::asset_ref(ContractId::from([0x11; 32])))
+        .unwrap()
+        .add_global_state("offeredAmount", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("counterAsset", SwapOrder::asset_ref(ContractId::from([0x22; 32])))
+        .unwrap()
+        .add_global_state("price", Amount::from(2500u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_rights("orderRight", seal(1))
+        .unwrap()
+}
+
+fn carbon() -> ContractBuilder {
+    builder::<CarbonCreditAsset>()
+        .add_global_state("name", Name::from("Test asset"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn provenance() -> ContractBuilder {
+    let index = TokenIndex::from(2);
+    builder::<ProvenanceAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test provenance", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(index, 1))
+        .unwrap()
+}
+
+fn subscription() -> ContractBuilder {
+    let index = TokenIndex::from(2);
+    builder::<SubscriptionAsset>()
+        .add_global_state(
+            "spec",
+            AssetSpec::new("TEST", "Test subscription", Precision::Indivisible),
+        )
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_global_state("expiry", block_height(900_000))
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(index, 1))
+        .unwrap()
+}
+
+fn edition() -> ContractBuilder {
+    builder::<LimitedEditionAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test editions", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("maxEditions", Amount::from(100u64))
+        .unwrap()
+        .add_rights("mintRight", seal(1))
+        .unwrap()
+}
+
+fn eifa() -> ContractBuilder {
+    builder::<EpochInflatableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(1500u64))
+        .unwrap()
+        .add_global_state("epochLength", Amount::from(100u64))
+        .unwrap()
+        .add_global_state("epochCap", Amount::from(200u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), 500u64)
+        .unwrap()
+        .add_data("epochIssuance", seal(3), Allocation::with(TokenIndex::from(0), 0))
+        .unwrap()
+}
+
+fn claim() -> ContractBuilder {
+    builder::<ClaimableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn registry() -> ContractBuilder {
+    builder::<NameRegistry>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test names", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_rights("registerRight", seal(1))
+        .unwrap()
+}
+
+fn pausable() -> ContractBuilder {
+    builder::<PausableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("pauseRight", seal(2))
+        .unwrap()
+}
+
+fn rental() -> ContractBuilder {
+    let index = TokenIndex::from(2);
+    builder::<RentalAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test rental", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(index, 1))
+        .unwrap()
+}
+
+fn option() -> ContractBuilder {
+    builder::<OptionAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("underlyingAsset", OptionAsset::asset_ref(ContractId::from([0x33; 32])))
+        .unwrap()
+        .add_global_state("strike", Amount::from(3000u64))
+        .unwrap()
+        .add_global_state("expiry", block_height(890_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn credential() -> ContractBuilder {
+    builder::<VerifiableCredential>()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_rights("assetOwner", seal(1))
+        .unwrap()
+        .add_rights("revokeRight", seal(2))
+        .unwrap()
+}
+
+fn burnable() -> ContractBuilder {
+    builder::<BurnableAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+}
+
+fn rebasing() -> ContractBuilder {
+    builder::<RebasingAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1000u64)
+        .unwrap()
+        .add_rights("rebaseRight", seal(2))
+        .unwrap()
+}
+
+fn bench_schema<I: IssuerWrapper>(c: &mut Criterion, name: &str, builder: fn() -> ContractBuilder) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("schema", |b| b.iter(I::schema));
+    group.bench_function("scripts", |b| b.iter(I::scripts));
+    group.bench_function("types", |b| b.iter(I::types));
+
+    let contract: Contract = issue(builder()).into_consignment();
+    let config = ValidationConfig {
+        chain_net: CHAIN_NET,
+        trusted_typesystem: I::types(),
+        ..Default::default()
+    };
+    let resolver = MockResolver::new();
+    group.bench_function("validate", |b| {
+        b.iter_batched(
+            || contract.clone(),
+            |contract| -> ValidConsignment<false> {
+                contract.validate(&resolver, &config).unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn schemata(c: &mut Criterion) {
+    bench_schema::<NonInflatableAsset>(c, "nia", nia);
+    bench_schema::<CollectibleFungibleAsset>(c, "cfa", cfa);
+    bench_schema::<PermissionedFungibleAsset>(c, "pfa", pfa);
+    bench_schema::<InflatableFungibleAsset>(c, "ifa", ifa);
+    bench_schema::<ReissuableAsset>(c, "ria", ria);
+    bench_schema::<SoulboundAsset>(c, "sba", sba);
+    bench_schema::<UniqueDigitalAsset>(c, "uda", uda);
+    bench_schema::<FractionalUniqueAsset>(c, "fua", fua);
+    bench_schema::<UniqueDigitalCollection>(c, "collection", collection);
+    bench_schema::<SemiFungibleAsset>(c, "sfa", sfa);
+    bench_schema::<StablecoinAsset>(c, "stablecoin", stablecoin);
+    bench_schema::<BondAsset>(c, "bond", bond);
+    bench_schema::<GovernanceAsset>(c, "governance", governance);
+    bench_schema::<TicketAsset>(c, "ticket", ticket);
+    bench_schema::<VestingAsset>(c, "vesting", vesting);
+    bench_schema::<TimelockAsset>(c, "timelock", timelock);
+    bench_schema::<MultiKeyPermissionedAsset>(c, "pfan", pfan);
+    bench_schema::<FreezableAsset>(c, "freezable", freezable);
+    bench_schema::<ThresholdInflatableAsset>(c, "tifa", tifa);
+    bench_schema::<WrappedBtcAsset>(c, "wbtc", wbtc);
+    bench_schema::<TransferFeeAsset>(c, "fee", fee);
+    bench_schema::<DividendAsset>(c, "dividend", dividend);
+    bench_schema::<EscrowAsset>(c, "escrow", escrow);
+    bench_schema::<SwapOrder>(c, "swap", swap);
+    bench_schema::<CarbonCreditAsset>(c, "carbon", carbon);
+    bench_schema::<ProvenanceAsset>(c, "provenance", provenance);
+    bench_schema::<SubscriptionAsset>(c, "subscription", subscription);
+    bench_schema::<LimitedEditionAsset>(c, "edition", edition);
+    bench_schema::<EpochInflatableAsset>(c, "eifa", eifa);
+    bench_schema::<ClaimableAsset>(c, "claim", claim);
+    bench_schema::<NameRegistry>(c, "registry", registry);
+    bench_schema::<PausableAsset>(c, "pausable", pausable);
+    bench_schema::<RentalAsset>(c, "rental", rental);
+    bench_schema::<OptionAsset>(c, "option", option);
+    bench_schema::<VerifiableCredential>(c, "credential", credential);
+    bench_schema::<BurnableAsset>(c, "burnable", burnable);
+    bench_schema::<RebasingAsset>(c, "rebasing", rebasing);
+}
+
+criterion_group!(benches, schemata);
+criterion_main!(benches);
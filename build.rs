@@ -0,0 +1,44 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2026 by
+//     RGB schemas contributors
+//
+// Copyright (C) 2026 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sets the `any_schema` cfg when at least one schema feature is enabled, sparing the library
+//! from repeating the list of all the schema features wherever code is unused without them, and
+//! the `all_schemas` cfg when all of them are enabled through the `default` or `all` features,
+//! gating the tests which need the fixtures of every schema.
+
+/// Features which don't enable a schema, including the implicit features of optional dependencies.
+const NON_SCHEMA_FEATURES: &[&str] = &["ALL", "DEFAULT", "LOG", "SERDE", "TRACING"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let any_schema = std::env::vars().any(|(var, _)| {
+        var.strip_prefix("CARGO_FEATURE_")
+            .is_some_and(|feature| !NON_SCHEMA_FEATURES.contains(&feature))
+    });
+    if any_schema {
+        println!("cargo:rustc-cfg=any_schema");
+    }
+    if std::env::var_os("CARGO_FEATURE_DEFAULT").is_some()
+        || std::env::var_os("CARGO_FEATURE_ALL").is_some()
+    {
+        println!("cargo:rustc-cfg=all_schemas");
+    }
+}
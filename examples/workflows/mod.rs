@@ -0,0 +1,297 @@
+//! Issuance workflows shared by the examples and the `examples` integration test, which runs them
+//! against a temporary directory.
+//!
+//! Each workflow imports the schema kit from `schemata/` into a new in-memory stock, issues a
+//! contract with a single beneficiary, saves it (both binary and armored) as
+//! `<dir>/<schema>-example.rgb(a)` and imports it into the stock.
+
+// Every example includes all the workflows but enables the features of its own schema only
+#![allow(dead_code, unused_imports)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use amplify::confinement::SmallBlob;
+use amplify::{Bytes, Wrapper};
+use rgbstd::bitcoin::CompressedPublicKey;
+use schemata::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const ISSUED_SUPPLY: u64 = 100000;
+pub const MAX_SUPPLY: u64 = 150000;
+
+fn beneficiary(vout: u32) -> GenesisSeal {
+    let beneficiary_txid =
+        Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5").unwrap();
+    GenesisSeal::new_random(beneficiary_txid, vout)
+}
+
+fn terms() -> ContractTerms {
+    ContractTerms {
+        text: RicardianContract::default(),
+        media: None,
+    }
+}
+
+/// Chain network the examples issue on, set with the `RGB_CHAIN_NET` environment variable to the
+/// network prefix used in invoices, like `bcrt` for regtest or `sb` for signet; the default is
+/// testnet4.
+pub fn chain_net() -> ChainNet {
+    match std::env::var("RGB_CHAIN_NET") {
+        Ok(prefix) => ChainNet::from_str(&prefix).expect("invalid RGB_CHAIN_NET"),
+        Err(_) => ChainNet::BitcoinTestnet4,
+    }
+}
+
+fn manifest_path(path: &str) -> PathBuf { PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path) }
+
+/// Imports the kit of `I` into a new stock, issues the contract defined by `build`, saves it to
+/// `dir` and imports it into the stock.
+fn issue<I: IssuerWrapper>(
+    kit: &str,
+    name: &str,
+    dir: &Path,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+    build: impl FnOnce(ContractBuilder) -> ContractBuilder,
+) -> (Stock, ContractId) {
+    let mut stock = Stock::in_memory();
+    let kit = Kit::load_file(manifest_path(&format!("schemata/{kit}.rgb")))
+        .unwrap()
+        .validate()
+        .unwrap();
+    stock.import_kit(kit).expect("invalid issuer kit");
+
+    let builder = stock
+        .contract_builder("ssi:anonymous", I::schema().schema_id(), chain_net)
+        .unwrap();
+    let contract = build(builder)
+        .issue_contract()
+        .expect("contract doesn't fit schema requirements");
+
+    let contract_id = contract.contract_id();
+
+    eprintln!("{contract}");
+    contract
+        .save_file(dir.join(format!("{name}-example.rgb")))
+        .expect("unable to save contract");
+    contract
+        .save_armored(dir.join(format!("{name}-example.rgba")))
+        .expect("unable to save armored contract");
+
+    stock.import_contract(contract, resolver).unwrap();
+
+    (stock, contract_id)
+}
+
+#[cfg(feature = "nia")]
+pub fn issue_nia(
+    dir: &Path,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+) -> (Stock, ContractId) {
+    let spec = AssetSpec::new("TEST", "Test asset", Precision::CentiMicro);
+    let issued_supply = Amount::from(ISSUED_SUPPLY);
+
+    issue::<NonInflatableAsset>("NonInflatableAsset", "nia", dir, chain_net, resolver, |builder| {
+        builder
+            .add_global_state("spec", spec)
+            .expect("invalid spec")
+            .add_global_state("terms", terms())
+            .expect("invalid contract terms")
+            .add_global_state("issuedSupply", issued_supply)
+            .expect("invalid issued supply")
+            .add_fungible_state("assetOwner", beneficiary(1), ISSUED_SUPPLY)
+            .expect("invalid fungible state")
+    })
+}
+
+#[cfg(feature = "cfa")]
+pub fn issue_cfa(
+    dir: &Path,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+) -> (Stock, ContractId) {
+    let name = Name::from("Test asset");
+    let precision = Precision::CentiMicro;
+    let issued_supply = Amount::from(ISSUED_SUPPLY);
+
+    issue::<CollectibleFungibleAsset>(
+        "CollectibleFungibleAsset",
+        "cfa",
+        dir,
+        chain_net,
+        resolver,
+        |builder| {
+            builder
+                .add_global_state("name", name)
+                .expect("invalid name")
+                .add_global_state("precision", precision)
+                .expect("invalid precision")
+                .add_global_state("terms", terms())
+                .expect("invalid contract terms")
+                .add_global_state("issuedSupply", issued_supply)
+                .expect("invalid issued supply")
+                .add_fungible_state("assetOwner", beneficiary(1), ISSUED_SUPPLY)
+                .expect("invalid fungible state")
+        },
+    )
+}
+
+#[cfg(feature = "pfa")]
+pub fn issue_pfa(
+    dir: &Path,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+) -> (Stock, ContractId) {
+    let spec = AssetSpec::new("TEST", "Test asset", Precision::CentiMicro);
+    let issued_supply = Amount::from(ISSUED_SUPPLY);
+    let pubkey = CompressedPublicKey::from_slice(&[
+        2, 199, 163, 211, 116, 75, 108, 119, 241, 66, 54, 236, 233, 189, 142, 108, 37, 135, 56,
+        128, 200, 176, 199, 9, 117, 132, 72, 200, 167, 185, 4, 64, 53,
+    ])
+    .unwrap();
+
+    issue::<PermissionedFungibleAsset>(
+        "PermissionedFungibleAsset",
+        "pfa",
+        dir,
+        chain_net,
+        resolver,
+        |builder| {
+            builder
+                .add_global_state("spec", spec)
+                .expect("invalid spec")
+                .add_global_state("terms", terms())
+                .expect("invalid contract terms")
+                .add_global_state("issuedSupply", issued_supply)
+                .expect("invalid issued supply")
+                .add_fungible_state("assetOwner", beneficiary(1), ISSUED_SUPPLY)
+                .expect("invalid fungible state")
+                .add_global_state("pubkey", pubkey)
+                .expect("invalid pubkey")
+        },
+    )
+}
+
+#[cfg(feature = "ifa")]
+pub fn issue_ifa(
+    dir: &Path,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+) -> (Stock, ContractId) {
+    let spec = AssetSpec::new("TEST", "Test asset", Precision::CentiMicro);
+    let issued_supply = Amount::from(ISSUED_SUPPLY);
+    let max_supply = Amount::from(MAX_SUPPLY);
+    let reject_list_url = RejectListUrl::from("example.xyz/reject");
+
+    issue::<InflatableFungibleAsset>(
+        "InflatableFungibleAsset",
+        "ifa",
+        dir,
+        chain_net,
+        resolver,
+        |builder| {
+            builder
+                .add_global_state("spec", spec)
+                .expect("invalid spec")
+                .add_global_state("terms", terms())
+                .expect("invalid contract terms")
+                .add_global_state("issuedSupply", issued_supply)
+                .expect("invalid issued supply")
+                .add_global_state("maxSupply", max_supply)
+                .expect("invalid max supply")
+                .add_global_state("rejectListUrl", reject_list_url)
+                .expect("invalid reject list url")
+                .add_fungible_state("assetOwner", beneficiary(1), ISSUED_SUPPLY)
+                .expect("invalid fungible state")
+                .add_fungible_state(
+                    "inflationAllowance",
+                    beneficiary(2),
+                    MAX_SUPPLY - ISSUED_SUPPLY,
+                )
+                .expect("invalid fungible state")
+                .add_rights("replaceRight", beneficiary(3))
+                .expect("invalid void state")
+        },
+    )
+}
+
+#[cfg(feature = "uda")]
+pub fn issue_uda(
+    dir: &Path,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+) -> (Stock, ContractId) {
+    let spec = AssetSpec::new("TEST", "Test uda", Precision::Indivisible);
+
+    let file_bytes = fs::read(manifest_path("README.md")).unwrap();
+    let mut hasher = Sha256::new();
+    hasher.update(file_bytes);
+    let file_hash = hasher.finalize();
+    let terms = ContractTerms {
+        text: RicardianContract::default(),
+        media: Some(Attachment {
+            ty: MediaType::with("text/*"),
+            digest: Bytes::from_byte_array(file_hash),
+        }),
+    };
+
+    let index = TokenIndex::from_inner(2);
+    let preview = EmbeddedMedia {
+        ty: MediaType::with("image/*"),
+        data: SmallBlob::try_from_iter(vec![0, 0]).expect("invalid data"),
+    };
+    let token_data = TokenData {
+        index,
+        preview: Some(preview),
+        ..Default::default()
+    };
+
+    let allocation = Allocation::with(index, 1);
+
+    issue::<UniqueDigitalAsset>("UniqueDigitalAsset", "uda", dir, chain_net, resolver, |builder| {
+        builder
+            .add_global_state("spec", spec)
+            .expect("invalid spec")
+            .add_global_state("terms", terms)
+            .expect("invalid contract terms")
+            .add_global_state("tokens", token_data)
+            .expect("invalid token data")
+            .add_data("assetOwner", beneficiary(1), allocation)
+            .expect("invalid asset blob")
+    })
+}
+
+pub fn print_fungible(allocations: impl Iterator<Item = FungibleAllocation>) {
+    for FungibleAllocation {
+        seal,
+        state,
+        witness,
+        ..
+    } in allocations
+    {
+        let witness = witness
+            .as_ref()
+            .map(Txid::to_string)
+            .unwrap_or("~".to_owned());
+        eprintln!("amount={}, owner={seal}, witness={witness}", state.value());
+    }
+}
+
+pub fn print_data(allocations: impl Iterator<Item = DataAllocation>) {
+    for DataAllocation {
+        seal,
+        state,
+        witness,
+        ..
+    } in allocations
+    {
+        let witness = witness
+            .as_ref()
+            .map(Txid::to_string)
+            .unwrap_or("~".to_owned());
+        eprintln!("state={state}, owner={seal}, witness={witness}");
+    }
+}
@@ -0,0 +1,19 @@
+//! ASCII-armored kits and consignments.
+
+#![no_main]
+
+use std::str::FromStr;
+
+use libfuzzer_sys::fuzz_target;
+use rgbstd::containers::{Contract, Kit, Transfer};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(kit) = Kit::from_str(s) {
+        let _ = kit.validate();
+    }
+    let _ = Contract::from_str(s);
+    let _ = Transfer::from_str(s);
+});
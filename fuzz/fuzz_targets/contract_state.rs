@@ -0,0 +1,513 @@
+//! Binary contract consignments which, once validated and imported into a stock, are read back
+//! through the schema wrappers.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rgbstd::containers::{ConsignmentExt, Contract, FileContent};
+use rgbstd::contract::{FilterIncludeAll, IssuerWrapper};
+use rgbstd::persistence::Stock;
+use rgbstd::validation::ValidationConfig;
+use schemata::dumb::MockResolver;
+use schemata::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset, ContractWrapper, DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset, GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset, NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset,
+    PermissionedFungibleAsset, ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset, SoulboundAsset,
+    StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset, TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection, VerifiableCredential, VestingAsset, WrappedBtcAsset, BOND_SCHEMA_ID, BURNABLE_SCHEMA_ID, CARBON_SCHEMA_ID, CFA_SCHEMA_ID, CLAIM_SCHEMA_ID, COLLECTION_SCHEMA_ID, CREDENTIAL_SCHEMA_ID,
+    DIVIDEND_SCHEMA_ID, EDITION_SCHEMA_ID, EIFA_SCHEMA_ID, ESCROW_SCHEMA_ID, FEE_SCHEMA_ID, FREEZABLE_SCHEMA_ID, FUA_SCHEMA_ID, GOVERNANCE_SCHEMA_ID, IFA_SCHEMA_ID, NIA_SCHEMA_ID, OPTION_SCHEMA_ID, PAUSABLE_SCHEMA_ID, PFAN_SCHEMA_ID, PFA_SCHEMA_ID, PROVENANCE_SCHEMA_ID, REBASING_SCHEMA_ID, REGISTRY_SCHEMA_ID, RENTAL_SCHEMA_ID, RIA_SCHEMA_ID, SBA_SCHEMA_ID,
+    SFA_SCHEMA_ID, STABLECOIN_SCHEMA_ID, SUBSCRIPTION_SCHEMA_ID, SWAP_SCHEMA_ID, TICKET_SCHEMA_ID, TIFA_SCHEMA_ID, TIMELOCK_SCHEMA_ID, UDA_SCHEMA_ID, VESTING_SCHEMA_ID, WBTC_SCHEMA_ID,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(contract) = Contract::load(data) else {
+        return;
+    };
+    // wallets trust the type system of the schemata they know, not the one from the consignment
+    let trusted_typesystem = match contract.schema_id() {
+        NIA_SCHEMA_ID => NonInflatableAsset::types(),
+        CFA_SCHEMA_ID => CollectibleFungibleAsset::types(),
+        PFA_SCHEMA_ID => PermissionedFungibleAsset::types(),
+        IFA_SCHEMA_ID => InflatableFungibleAsset::types(),
+        RIA_SCHEMA_ID => ReissuableAsset::types(),
+        SBA_SCHEMA_ID => SoulboundAsset::types(),
+        UDA_SCHEMA_ID => UniqueDigitalAsset::types(),
+        FUA_SCHEMA_ID => FractionalUniqueAsset::types(),
+        COLLECTION_SCHEMA_ID => UniqueDigitalCollection::types(),
+        SFA_SCHEMA_ID => SemiFungibleAsset::types(),
+        STABLECOIN_SCHEMA_ID => StablecoinAsset::types(),
+        BOND_SCHEMA_ID => BondAsset::types(),
+        GOVERNANCE_SCHEMA_ID => GovernanceAsset::types(),
+        TICKET_SCHEMA_ID => TicketAsset::types(),
+        VESTING_SCHEMA_ID => VestingAsset::types(),
+        TIMELOCK_SCHEMA_ID => TimelockAsset::types(),
+        PFAN_SCHEMA_ID => MultiKeyPermissionedAsset::types(),
+        FREEZABLE_SCHEMA_ID => FreezableAsset::types(),
+        TIFA_SCHEMA_ID => ThresholdInflatableAsset::types(),
+        WBTC_SCHEMA_ID => WrappedBtcAsset::types(),
+        FEE_SCHEMA_ID => TransferFeeAsset::types(),
+        DIVIDEND_SCHEMA_ID => DividendAsset::types(),
+        ESCROW_SCHEMA_ID => EscrowAsset::types(),
+        SWAP_SCHEMA_ID => SwapOrder::types(),
+        CARBON_SCHEMA_ID => CarbonCreditAsset::types(),
+        PROVENANCE_SCHEMA_ID => ProvenanceAsset::types(),
+        SUBSCRIPTION_SCHEMA_ID => SubscriptionAsset::types(),
+        EDITION_SCHEMA_ID => LimitedEditionAsset::types(),
+        EIFA_SCHEMA_ID => EpochInflatableAsset::types(),
+        CLAIM_SCHEMA_ID => ClaimableAsset::types(),
+        REGISTRY_SCHEMA_ID => NameRegistry::types(),
+        PAUSABLE_SCHEMA_ID => PausableAsset::types(),
+        RENTAL_SCHEMA_ID => RentalAsset::types(),
+        OPTION_SCHEMA_ID => OptionAsset::types(),
+        CREDENTIAL_SCHEMA_ID => VerifiableCredential::types(),
+        BURNABLE_SCHEMA_ID => BurnableAsset::types(),
+        REBASING_SCHEMA_ID => RebasingAsset::types(),
+        _ => return,
+    };
+    let config = ValidationConfig {
+        chain_net: contract.genesis.chain_net,
+        trusted_typesystem,
+        ..Default::default()
+    };
+    let resolver = MockResolver::new();
+    let Ok(contract) = contract.validate(&resolver, &config) else {
+        return;
+    };
+    let contract_id = contract.contract_id();
+    let schema_id = contract.schema_id();
+
+    let mut stock = Stock::in_memory();
+    if stock.import_contract(contract, &resolver).is_err() {
+        return;
+    }
+
+    match schema_id {
+        NIA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<NonInflatableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        CFA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<CollectibleFungibleAsset>(contract_id)
+            else {
+                return;
+            };
+            let _ = wrapper.name();
+            let _ = wrapper.details();
+            let _ = wrapper.precision();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        PFA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<PermissionedFungibleAsset>(contract_id)
+            else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        IFA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<InflatableFungibleAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.reject_list_url();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.issuance_amounts();
+            let _ = wrapper.max_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.inflation_allocations(&FilterIncludeAll).count();
+            let _ = wrapper.replace_rights(&FilterIncludeAll).count();
+        }
+        RIA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<ReissuableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.burned_supply();
+            let _ = wrapper.reissue_allowance();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.reissue_rights(&FilterIncludeAll).count();
+        }
+        SBA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<SoulboundAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.revocations();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.revoke_rights(&FilterIncludeAll).count();
+        }
+        UDA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<UniqueDigitalAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.token_data();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        FUA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<FractionalUniqueAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.token_data();
+            let _ = wrapper.total_fractions();
+            let _ = wrapper.holder_shares(&FilterIncludeAll);
+        }
+        COLLECTION_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<UniqueDigitalCollection>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.tokens();
+            let _ = wrapper.allocations_by_token(&FilterIncludeAll);
+        }
+        SFA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<SemiFungibleAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.tokens();
+            let _ = wrapper.balances(&FilterIncludeAll);
+        }
+        STABLECOIN_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<StablecoinAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.pubkey();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.redeemed_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.inflation_allocations(&FilterIncludeAll).count();
+        }
+        BOND_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<BondAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_maturity();
+            let _ = wrapper.coupon_rate();
+            let _ = wrapper.outstanding_principal();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        GOVERNANCE_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<GovernanceAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_vote_rights();
+            let _ = wrapper.tally();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.vote_rights(&FilterIncludeAll).count();
+        }
+        TICKET_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<TicketAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.redeemed_tickets();
+            let _ = wrapper.outstanding_tickets();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        VESTING_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<VestingAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_schedule();
+            let _ = wrapper.try_unlocked_supply(u32::MAX);
+            let _ = wrapper.locked_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.locked_allocations(&FilterIncludeAll).count();
+        }
+        TIMELOCK_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<TimelockAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_lock_height();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        PFAN_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<MultiKeyPermissionedAsset>(contract_id)
+            else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_pubkeys();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        FREEZABLE_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<FreezableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_frozen();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.freeze_rights(&FilterIncludeAll).count();
+        }
+        TIFA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<ThresholdInflatableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.issuance_amounts();
+            let _ = wrapper.max_supply();
+            let _ = wrapper.try_inflation_threshold();
+            let _ = wrapper.try_pubkeys();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.inflation_allocations(&FilterIncludeAll).count();
+            let _ = wrapper.try_approvals(&FilterIncludeAll);
+            let _ = wrapper.try_pending_approvers(&FilterIncludeAll);
+        }
+        WBTC_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<WrappedBtcAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.burned_supply();
+            let _ = wrapper.circulating_supply();
+            let _ = wrapper.try_attestations();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.mint_rights(&FilterIncludeAll).count();
+        }
+        FEE_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<TransferFeeAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_fee_rate();
+            let _ = wrapper.try_fee(rgbstd::Amount::from(u64::MAX));
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.fee_allocations(&FilterIncludeAll).count();
+        }
+        DIVIDEND_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<DividendAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_dividends();
+            let snapshot = wrapper.snapshot(&FilterIncludeAll);
+            let _ = wrapper.try_claimable(&snapshot, 0);
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.distribute_rights(&FilterIncludeAll).count();
+        }
+        ESCROW_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<EscrowAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.escrows(&FilterIncludeAll).count();
+            let _ = wrapper.release_rights(&FilterIncludeAll).count();
+            let _ = wrapper.refund_rights(&FilterIncludeAll).count();
+        }
+        SWAP_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<SwapOrder>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.try_offered_asset();
+            let _ = wrapper.try_counter_asset();
+            let _ = wrapper.try_fills();
+            let _ = wrapper.try_remaining_amount();
+            let _ = wrapper.try_cost(rgbstd::Amount::from(u64::MAX));
+            let _ = wrapper.order_rights(&FilterIncludeAll).count();
+        }
+        CARBON_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<CarbonCreditAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.name();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_retired_amount();
+            let _ = wrapper.try_retirements();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        PROVENANCE_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<ProvenanceAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.token_data();
+            let _ = wrapper.try_provenance();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        SUBSCRIPTION_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<SubscriptionAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.token_data();
+            let _ = wrapper.try_pubkey();
+            let _ = wrapper.try_valid_until();
+            let _ = wrapper.try_is_active(u32::MAX);
+            let _ = wrapper.try_renewals();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        EDITION_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<LimitedEditionAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.try_max_editions();
+            let _ = wrapper.try_editions();
+            let _ = wrapper.try_remaining_editions();
+            let _ = wrapper.try_next_edition();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.mint_rights(&FilterIncludeAll).count();
+        }
+        EIFA_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<EpochInflatableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.issuance_amounts();
+            let _ = wrapper.max_supply();
+            let _ = wrapper.try_epoch_length();
+            let _ = wrapper.try_epoch_cap();
+            let _ = wrapper.try_epoch_of(u32::MAX);
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.inflation_allocations(&FilterIncludeAll).count();
+        }
+        CLAIM_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<ClaimableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_pubkey();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.claims(&FilterIncludeAll).count();
+            let _ = wrapper.try_claimed_amount(&FilterIncludeAll);
+        }
+        REGISTRY_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<NameRegistry>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let names = wrapper.try_names().unwrap_or_default();
+            for name in names {
+                let _ = wrapper.try_token_of(&name);
+                let _ = wrapper.try_resolve(&name, &FilterIncludeAll);
+            }
+            let _ = wrapper.try_next_token();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.register_rights(&FilterIncludeAll).count();
+        }
+        PAUSABLE_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<PausableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_is_paused();
+            let _ = wrapper.try_toggles();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.pause_rights(&FilterIncludeAll).count();
+        }
+        RENTAL_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<RentalAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_token_data();
+            let _ = wrapper.try_leases();
+            let _ = wrapper.try_lease_expiry();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.lease_rights(&FilterIncludeAll).count();
+        }
+        OPTION_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<OptionAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_option_terms();
+            let _ = wrapper.try_exercised_amount();
+            let _ = wrapper.try_lapsed_amount();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        CREDENTIAL_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<VerifiableCredential>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.try_pubkey();
+            let _ = wrapper.try_claims();
+            let _ = wrapper.try_revocation_reason();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.revoke_rights(&FilterIncludeAll).count();
+        }
+        BURNABLE_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<BurnableAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_total_burned();
+            let _ = wrapper.try_circulating_supply();
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+        }
+        REBASING_SCHEMA_ID => {
+            let Ok(wrapper) = stock.contract_wrapper::<RebasingAsset>(contract_id) else {
+                return;
+            };
+            let _ = wrapper.spec();
+            let _ = wrapper.contract_terms();
+            let _ = wrapper.total_issued_supply();
+            let _ = wrapper.try_pubkey();
+            let _ = wrapper.try_rebase_factors();
+            let _ = wrapper.try_effective_balance(&FilterIncludeAll);
+            let _ = wrapper.allocations(&FilterIncludeAll).count();
+            let _ = wrapper.rebase_rights(&FilterIncludeAll).count();
+        }
+        _ => {}
+    }
+});
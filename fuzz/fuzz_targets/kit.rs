@@ -0,0 +1,12 @@
+//! Binary issuer kits, as received from peers or loaded from `schemata/*.rgb` files.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rgbstd::containers::{FileContent, Kit};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(kit) = Kit::load(data) {
+        let _ = kit.validate();
+    }
+});
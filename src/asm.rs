@@ -0,0 +1,260 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assembler helpers laying out AluVM libraries from labeled subroutines.
+//!
+//! Subroutine offsets are computed from the layout instead of being maintained by hand, so jumps
+//! and the `FN_*` entry point constants referenced from schema `LibSite`s can't drift from the
+//! code. Libraries are embedded as precompiled constants, hence these helpers are only used by
+//! unit tests regenerating and verifying them.
+
+// Each helper is used by the libraries of some schemata only
+#![cfg_attr(not(all_schemas), allow(dead_code))]
+
+use std::collections::BTreeMap;
+
+use aluvm::isa::Instr;
+use aluvm::library::Lib;
+use aluvm::reg::Reg16;
+use amplify::num::u4;
+use rgbstd::persistence::MemContract;
+use rgbstd::vm::{ContractOp, RgbIsa};
+use rgbstd::AssignmentType;
+
+use crate::ValidationErrno;
+
+pub(crate) type RgbInstr = Instr<RgbIsa<MemContract>>;
+
+// Error numbers as `u8` constants, as `rgbasm!` only accepts identifiers and literals
+pub(crate) const ERRNO_NON_EQUAL_IN_OUT: u8 = ValidationErrno::NonEqualInOut.errno();
+pub(crate) const ERRNO_ISSUED_MISMATCH: u8 = ValidationErrno::IssuedMismatch.errno();
+pub(crate) const ERRNO_NON_FRACTIONAL: u8 = ValidationErrno::NonFractional.errno();
+pub(crate) const ERRNO_UNSORTED_TOKENS: u8 = ValidationErrno::UnsortedTokens.errno();
+pub(crate) const ERRNO_UNKNOWN_TOKEN: u8 = ValidationErrno::UnknownToken.errno();
+pub(crate) const ERRNO_TICKET_MISMATCH: u8 = ValidationErrno::TicketMismatch.errno();
+pub(crate) const ERRNO_EDITION_MISMATCH: u8 = ValidationErrno::EditionMismatch.errno();
+pub(crate) const ERRNO_EDITION_CAP_EXCEEDED: u8 = ValidationErrno::EditionCapExceeded.errno();
+pub(crate) const ERRNO_NAME_TAKEN: u8 = ValidationErrno::NameTaken.errno();
+pub(crate) const ERRNO_UNNORMALIZED_NAME: u8 = ValidationErrno::UnnormalizedName.errno();
+pub(crate) const ERRNO_REGISTRATION_MISMATCH: u8 = ValidationErrno::RegistrationMismatch.errno();
+pub(crate) const ERRNO_TOKEN_REINDEXED: u8 = ValidationErrno::TokenReindexed.errno();
+pub(crate) const ERRNO_EPOCH_CAP_EXCEEDED: u8 = ValidationErrno::EpochCapExceeded.errno();
+pub(crate) const ERRNO_EPOCH_REGRESSED: u8 = ValidationErrno::EpochRegressed.errno();
+pub(crate) const ERRNO_EPOCH_ISSUANCE_MISMATCH: u8 = ValidationErrno::EpochIssuanceMismatch.errno();
+pub(crate) const ERRNO_MISSING_PUBKEY: u8 = ValidationErrno::MissingPubkey.errno();
+pub(crate) const ERRNO_INVALID_SIGNATURE: u8 = ValidationErrno::InvalidSignature.errno();
+pub(crate) const ERRNO_UNAUTHORIZED_SIGNER: u8 = ValidationErrno::UnauthorizedSigner.errno();
+pub(crate) const ERRNO_INFLATION_MISMATCH: u8 = ValidationErrno::InflationMismatch.errno();
+pub(crate) const ERRNO_INFLATION_EXCEEDS_ALLOWANCE: u8 =
+    ValidationErrno::InflationExceedsAllowance.errno();
+pub(crate) const ERRNO_INVALID_THRESHOLD: u8 = ValidationErrno::InvalidThreshold.errno();
+pub(crate) const ERRNO_APPROVAL_MISMATCH: u8 = ValidationErrno::ApprovalMismatch.errno();
+pub(crate) const ERRNO_INSUFFICIENT_APPROVALS: u8 = ValidationErrno::InsufficientApprovals.errno();
+pub(crate) const ERRNO_REPLACE_NO_INPUT: u8 = ValidationErrno::ReplaceNoInput.errno();
+pub(crate) const ERRNO_REPLACE_HIDDEN_BURN: u8 = ValidationErrno::ReplaceHiddenBurn.errno();
+pub(crate) const ERRNO_BURN_MISMATCH: u8 = ValidationErrno::BurnMismatch.errno();
+pub(crate) const ERRNO_REISSUE_EXCEEDS_BURNS: u8 = ValidationErrno::ReissueExceedsBurns.errno();
+pub(crate) const ERRNO_REDEEM_MISMATCH: u8 = ValidationErrno::RedeemMismatch.errno();
+pub(crate) const ERRNO_REDEEM_LOCKED: u8 = ValidationErrno::RedeemLocked.errno();
+pub(crate) const ERRNO_SOULBOUND: u8 = ValidationErrno::Soulbound.errno();
+pub(crate) const ERRNO_REVOCATION_MISMATCH: u8 = ValidationErrno::RevocationMismatch.errno();
+pub(crate) const ERRNO_VOTE_RIGHTS_MISMATCH: u8 = ValidationErrno::VoteRightsMismatch.errno();
+pub(crate) const ERRNO_INVALID_SCHEDULE: u8 = ValidationErrno::InvalidSchedule.errno();
+pub(crate) const ERRNO_RELEASE_MISMATCH: u8 = ValidationErrno::ReleaseMismatch.errno();
+pub(crate) const ERRNO_TRANSFER_LOCKED: u8 = ValidationErrno::TransferLocked.errno();
+pub(crate) const ERRNO_FROZEN_SPEND: u8 = ValidationErrno::FrozenSpend.errno();
+pub(crate) const ERRNO_ALREADY_FROZEN: u8 = ValidationErrno::AlreadyFrozen.errno();
+pub(crate) const ERRNO_RESERVE_MISMATCH: u8 = ValidationErrno::ReserveMismatch.errno();
+pub(crate) const ERRNO_INVALID_FEE_RATE: u8 = ValidationErrno::InvalidFeeRate.errno();
+pub(crate) const ERRNO_FEE_MISMATCH: u8 = ValidationErrno::FeeMismatch.errno();
+pub(crate) const ERRNO_FILL_EXCEEDS_OFFER: u8 = ValidationErrno::FillExceedsOffer.errno();
+pub(crate) const ERRNO_EXPIRY_NOT_EXTENDED: u8 = ValidationErrno::ExpiryNotExtended.errno();
+pub(crate) const ERRNO_PAUSED: u8 = ValidationErrno::Paused.errno();
+pub(crate) const ERRNO_PAUSE_NOT_TOGGLED: u8 = ValidationErrno::PauseNotToggled.errno();
+pub(crate) const ERRNO_LEASE_ACTIVE: u8 = ValidationErrno::LeaseActive.errno();
+pub(crate) const ERRNO_LEASE_MISMATCH: u8 = ValidationErrno::LeaseMismatch.errno();
+pub(crate) const ERRNO_CREDENTIAL_REVOKED: u8 = ValidationErrno::CredentialRevoked.errno();
+pub(crate) const ERRNO_ZERO_REBASE_FACTOR: u8 = ValidationErrno::ZeroRebaseFactor.errno();
+pub(crate) const ERRNO_LEASE_NOT_EXPIRED: u8 = ValidationErrno::LeaseNotExpired.errno();
+pub(crate) const ERRNO_OPTION_EXPIRED: u8 = ValidationErrno::OptionExpired.errno();
+pub(crate) const ERRNO_OPTION_NOT_EXPIRED: u8 = ValidationErrno::OptionNotExpired.errno();
+pub(crate) const ERRNO_RELEASE_LOCKED: u8 = ValidationErrno::ReleaseLocked.errno();
+pub(crate) const ERRNO_UNAPPROVED_INFLATION: u8 = ValidationErrno::UnapprovedInflation.errno();
+
+/// `ldf ty,a16[idx],a64[dst]`, loading into `a64[dst]` the amount of the fungible output
+/// assignment of type `ty` at the index in `a16[idx]`.
+///
+/// `rgbasm!` doesn't know the opcode, so subroutines using it prepend or append the instruction
+/// to the assembled code.
+pub(crate) fn ldf(ty: AssignmentType, idx: u8, dst: u8) -> RgbInstr {
+    Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::LdF(
+        ty,
+        Reg16::from(u4::with(idx)),
+        Reg16::from(u4::with(dst)),
+    )))
+}
+
+/// Offsets of the labeled subroutines of a library.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Labels(Option<BTreeMap<&'static str, u16>>);
+
+impl Labels {
+    /// Offset of the subroutine with the given label.
+    ///
+    /// While the layout is being measured all offsets are zero.
+    pub fn offset(&self, label: &str) -> u16 {
+        match &self.0 {
+            None => 0,
+            Some(labels) => *labels
+                .get(label)
+                .unwrap_or_else(|| panic!("unknown subroutine label {label}")),
+        }
+    }
+}
+
+/// Library assembled from labeled subroutines, along with the offset of each label.
+pub(crate) struct Assembled {
+    pub lib: Lib,
+    pub labels: Labels,
+}
+
+impl Assembled {
+    /// Checks the entry point constants, named after their labels, against the computed layout,
+    /// printing the up-to-date constants on mismatch.
+    pub fn verify_offsets(&self, consts: &[(&str, u16)]) {
+        let labels = self.labels.0.as_ref().expect("assembled layout");
+        for label in labels.keys() {
+            assert!(
+                consts.iter().any(|(name, _)| name == label),
+                "subroutine {label} has no entry point constant"
+            );
+        }
+        let outdated = consts
+            .iter()
+            .filter(|(label, offset)| self.labels.offset(label) != *offset)
+            .map(|(label, _)| {
+                format!("pub(super) const {label}: u16 = {};", self.labels.offset(label))
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            outdated.is_empty(),
+            "entry point constants are outdated, replace them with:\n\n{}\n",
+            outdated.join("\n")
+        );
+    }
+}
+
+/// Assembles a library out of subroutines laid out one after another.
+///
+/// `layout` returns the labeled subroutines in their layout order and may use
+/// [`Labels::offset`] for jump targets. The subroutines are first measured with all offsets set to
+/// zero and then measured again with the final offsets, which must be unaffected by the jump
+/// targets.
+pub(crate) fn assemble(
+    name: &str,
+    layout: impl Fn(&Labels) -> Vec<(&'static str, Vec<RgbInstr>)>,
+) -> Assembled {
+    let assemble = |code: &[RgbInstr]| {
+        Lib::assemble(code).unwrap_or_else(|err| panic!("wrong {name} script: {err}"))
+    };
+    // Instructions can't be cloned, so each subroutine is measured by assembling a fresh copy of
+    // the code preceding it
+    let measure = |labels: &Labels| {
+        let subroutines = layout(labels);
+        let mut offsets = BTreeMap::new();
+        for (no, (label, _)) in subroutines.iter().enumerate() {
+            let code = layout(labels)
+                .into_iter()
+                .take(no)
+                .flat_map(|(_, subroutine)| subroutine)
+                .collect::<Vec<_>>();
+            let offset = u16::try_from(assemble(&code).code_segment().len())
+                .expect("library code exceeds 64kB");
+            assert!(
+                offsets.insert(*label, offset).is_none(),
+                "duplicated subroutine label {label}"
+            );
+        }
+        Labels(Some(offsets))
+    };
+
+    let labels = measure(&Labels(None));
+    assert_eq!(measure(&labels), labels, "{name} layout depends on jump targets");
+
+    let code = layout(&labels)
+        .into_iter()
+        .flat_map(|(_, subroutine)| subroutine)
+        .collect::<Vec<_>>();
+    let lib = assemble(&code);
+    Assembled { lib, labels }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::rgbasm;
+
+    use super::*;
+
+    fn layout(labels: &Labels) -> Vec<(&'static str, Vec<RgbInstr>)> {
+        let end = labels.offset("END");
+        vec![
+            ("START", rgbasm! {
+                put     a8[0],1;
+                jmp     end;
+            }),
+            ("MIDDLE", rgbasm! {
+                fail;
+            }),
+            ("END", rgbasm! {
+                ret;
+            }),
+        ]
+    }
+
+    #[test]
+    fn offsets() {
+        let assembled = assemble("test", layout);
+        assert_eq!(assembled.labels.offset("START"), 0);
+        assert_eq!(assembled.labels.offset("MIDDLE"), 4 + 3);
+        assert_eq!(assembled.labels.offset("END"), 4 + 3 + 1);
+        assembled.verify_offsets(&[("START", 0), ("MIDDLE", 7), ("END", 8)]);
+        assert_eq!(&assembled.lib.code_segment()[5..7], &8u16.to_le_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "pub(super) const END: u16 = 8;")]
+    fn outdated_offset() {
+        assemble("test", layout).verify_offsets(&[("START", 0), ("MIDDLE", 7), ("END", 9)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "subroutine MIDDLE has no entry point constant")]
+    fn missing_offset() { assemble("test", layout).verify_offsets(&[("START", 0), ("END", 8)]); }
+
+    #[test]
+    #[should_panic(expected = "unknown subroutine label")]
+    fn unknown_label() {
+        assemble("test", |labels| {
+            let none = labels.offset("NONE");
+            vec![("START", rgbasm! { jmp none; })]
+        });
+    }
+}
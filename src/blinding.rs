@@ -0,0 +1,133 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic blinding of seals.
+//!
+//! Seals are normally blinded with random factors, so a contract issued twice from the same data
+//! gets a different contract id each time. A [`SealBlinder`] derives the blinding factors from a
+//! seed instead, making issuances reproducible: test vectors can be checked against a fixed
+//! contract id, and the participants of a multi-party issuance can each rebuild the genesis
+//! before signing off on it. Each participant may derive its own blinder from the shared one with
+//! [`SealBlinder::derive`].
+//!
+//! Anyone knowing the seed can unblind the seals, so it must be kept as private as the seals.
+
+use rgbstd::bitcoin::hashes::{sha256, Hash, HashEngine};
+use rgbstd::txout::BlindSeal;
+use rgbstd::{GenesisSeal, GraphSeal, Txid};
+
+const TAG: &[u8] = b"urn:lnp-bp:rgb:schemata:seal-blinding#2024-04-16";
+
+/// Source of deterministic seal blinding factors.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SealBlinder {
+    /// Factors obtained by adding the index of the seal to a base value, as in the test vectors
+    /// of this crate.
+    Offset(u64),
+    /// Factors obtained by hashing a seed with the index of the seal.
+    Seed([u8; 32]),
+}
+
+impl SealBlinder {
+    /// Blinder hashing the seed, which should have enough entropy to make the blinding factors
+    /// unpredictable.
+    pub const fn with_seed(seed: [u8; 32]) -> Self { SealBlinder::Seed(seed) }
+
+    /// Derives the blinder of a participant or of a part of an issuance from this one.
+    pub fn derive(&self, label: &str) -> Self {
+        let mut engine = tagged_engine();
+        match self {
+            SealBlinder::Offset(base) => engine.input(&base.to_le_bytes()),
+            SealBlinder::Seed(seed) => engine.input(seed),
+        }
+        engine.input(label.as_bytes());
+        SealBlinder::Seed(sha256::Hash::from_engine(engine).to_byte_array())
+    }
+
+    /// Returns the blinding factor of the seal with the given index.
+    pub fn blinding(&self, index: u64) -> u64 {
+        match self {
+            SealBlinder::Offset(base) => base.wrapping_add(index),
+            SealBlinder::Seed(seed) => {
+                let mut engine = tagged_engine();
+                engine.input(seed);
+                engine.input(&index.to_le_bytes());
+                let hash = sha256::Hash::from_engine(engine).to_byte_array();
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&hash[..8]);
+                u64::from_le_bytes(bytes)
+            }
+        }
+    }
+
+    /// Genesis seal on the output `vout` of `txid`, blinded with the factor of index `vout`.
+    pub fn genesis_seal(&self, txid: Txid, vout: u32) -> GenesisSeal {
+        GenesisSeal::from(BlindSeal::with_blinding(txid, vout, self.blinding(vout as u64)))
+    }
+
+    /// Seal on the output `vout` of the witness transaction, blinded with the factor of index
+    /// `vout`.
+    pub fn graph_seal(&self, vout: u32) -> GraphSeal {
+        GraphSeal::with_blinded_vout(vout, self.blinding(vout as u64))
+    }
+}
+
+/// Hash engine committing to [`TAG`] as in BIP-340 tagged hashes.
+fn tagged_engine() -> sha256::HashEngine {
+    let tag = sha256::Hash::hash(TAG);
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag.as_ref());
+    engine.input(tag.as_ref());
+    engine
+}
+
+#[cfg(test)]
+mod test {
+    use schemata_tools::testing::{self, txid};
+
+    use super::*;
+
+    #[test]
+    fn offset() {
+        let blinder = SealBlinder::Offset(654321);
+        assert_eq!(blinder.blinding(2), 654323);
+        assert_eq!(blinder.genesis_seal(txid(), 3), testing::seal(3));
+        assert_eq!(blinder.graph_seal(3), testing::graph_seal(3));
+    }
+
+    #[test]
+    fn seeded() {
+        let blinder = SealBlinder::with_seed([7; 32]);
+        assert_eq!(blinder.blinding(1), SealBlinder::with_seed([7; 32]).blinding(1));
+        assert_ne!(blinder.blinding(1), blinder.blinding(2));
+        assert_ne!(blinder.blinding(1), SealBlinder::with_seed([8; 32]).blinding(1));
+        assert_eq!(blinder.genesis_seal(txid(), 1), blinder.genesis_seal(txid(), 1));
+    }
+
+    #[test]
+    fn derived() {
+        let blinder = SealBlinder::with_seed([7; 32]);
+        assert_eq!(blinder.derive("alice"), blinder.derive("alice"));
+        assert_ne!(blinder.derive("alice"), blinder.derive("bob"));
+        assert_ne!(blinder.derive("alice").blinding(0), blinder.blinding(0));
+        assert_ne!(SealBlinder::Offset(1).derive("alice"), SealBlinder::Offset(2).derive("alice"));
+    }
+}
@@ -0,0 +1,554 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bond schema.
+//!
+//! Each unit of the asset is a bond paying back its face value at maturity, with the coupon rate
+//! declared in the genesis. Holders redeem their bonds by spending their allocations without
+//! assigning any, recording the redeemed amount in the global state like the stablecoin redeems.
+//!
+//! The redeem validation checks that the redeemed supply matches the bonds spent, and that the
+//! lock time committed by the redeem keeps its witness transaction from being mined before the
+//! maturity height. The lock time of the witness itself is not checked, see [`crate::height`]:
+//! redeems mined before the maturity height are still valid, and the `audit` module of
+//! `rgb-schemata-tools` reports them. Face value and coupon rate are recorded as amounts, since
+//! the standard types have no dedicated ones.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::height::height;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, BOND_REDEEM, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_COUPON_RATE,
+    GS_FACE_VALUE, GS_ISSUED_SUPPLY, GS_LOCK_TIME, GS_MATURITY, GS_NOMINAL, GS_REDEEMED_SUPPLY,
+    GS_TERMS, OS_ASSET, TS_REDEEM, TS_TRANSFER,
+};
+
+pub const BOND_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xb7, 0xde, 0xf5, 0x90, 0x31, 0x4f, 0xbd, 0x37, 0xd8, 0xdb, 0x07, 0x6b, 0x8f, 0x6f, 0x62, 0x75,
+    0xaf, 0xae, 0x50, 0x13, 0xb1, 0xf1, 0x8d, 0xf6, 0xea, 0x86, 0x82, 0x67, 0xf6, 0x54, 0x1a, 0x28,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn bond_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    BOND_REDEEM.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("BondAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_REDEEMED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("redeemedSupply"),
+            },
+            GS_FACE_VALUE => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("faceValue"),
+            },
+            GS_COUPON_RATE => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("couponRate"),
+            },
+            GS_MATURITY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("maturity"),
+            },
+            GS_LOCK_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("lockTime"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_FACE_VALUE => Occurrences::Once,
+                GS_COUPON_RATE => Occurrences::Once,
+                GS_MATURITY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_REDEEM => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_REDEEMED_SUPPLY => Occurrences::Once,
+                        GS_LOCK_TIME => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                    },
+                    assignments: none!(),
+                    validator: Some(BOND_REDEEM.lib_site())
+                },
+                name: fname!("redeem"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn bond_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, BOND_REDEEM])
+}
+
+#[derive(Default)]
+pub struct BondAsset;
+
+impl IssuerWrapper for BondAsset {
+    type Wrapper<S: ContractStateRead> = BondWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(bond_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(bond_scripts).clone()
+    }
+}
+
+impl BondAsset {
+    /// First revision of the bond schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "BondAsset",
+        schema_id: BOND_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fixed supply of bonds with a face value, coupon rate and maturity height, \
+                        redeemed by burning them.",
+    };
+}
+
+impl IssuerInfo for BondAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for BondAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct BondWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for BondWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for BondWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<BondAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> BondWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the bond schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<BondAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the bond schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the amount paid back at maturity for each unit of the asset, in the units of the
+    /// currency of the bond defined by the contract terms.
+    pub fn face_value(&self) -> Amount { or_panic(self.try_face_value()) }
+
+    pub fn try_face_value(&self) -> Result<Amount, Error> { global(&self.0, GS_FACE_VALUE) }
+
+    /// Returns the yearly coupon rate, in basis points of the face value.
+    pub fn coupon_rate(&self) -> u64 { or_panic(self.try_coupon_rate()) }
+
+    pub fn try_coupon_rate(&self) -> Result<u64, Error> {
+        global::<_, Amount>(&self.0, GS_COUPON_RATE).map(Amount::value)
+    }
+
+    /// Returns the block height from which the bonds can be redeemed.
+    pub fn maturity(&self) -> u32 { or_panic(self.try_maturity()) }
+
+    pub fn try_maturity(&self) -> Result<u32, Error> { height(&self.0, GS_MATURITY) }
+
+    /// Checks whether the bonds can be redeemed in a block at the given height.
+    pub fn is_mature(&self, height: u32) -> bool { or_panic(self.try_is_mature(height)) }
+
+    pub fn try_is_mature(&self, height: u32) -> Result<bool, Error> {
+        Ok(height >= self.try_maturity()?)
+    }
+
+    /// Returns the lowest lock time a redeem can commit to, which keeps its witness transaction
+    /// from being mined before the maturity height.
+    pub fn min_lock_time(&self) -> u32 { or_panic(self.try_min_lock_time()) }
+
+    pub fn try_min_lock_time(&self) -> Result<u32, Error> {
+        Ok(self.try_maturity()?.saturating_sub(1))
+    }
+
+    /// Returns the supply redeemed over the contract history.
+    pub fn redeemed_supply(&self) -> Amount { or_panic(self.try_redeemed_supply()) }
+
+    pub fn try_redeemed_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_REDEEMED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts retired by each redeem, from the latest one.
+    pub fn redeem_amounts(&self) -> Vec<Amount> { or_panic(self.try_redeem_amounts()) }
+
+    pub fn try_redeem_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_REDEEMED_SUPPLY)?.collect()
+    }
+
+    /// Returns the number of bonds issued and not redeemed yet.
+    pub fn outstanding_supply(&self) -> Amount { or_panic(self.try_outstanding_supply()) }
+
+    pub fn try_outstanding_supply(&self) -> Result<Amount, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        let redeemed = self.try_redeemed_supply()?.value();
+        Ok(Amount::from(issued.saturating_sub(redeemed)))
+    }
+
+    /// Returns the principal still owed by the issuer: the face value of the outstanding bonds.
+    pub fn outstanding_principal(&self) -> u128 { or_panic(self.try_outstanding_principal()) }
+
+    pub fn try_outstanding_principal(&self) -> Result<u128, Error> {
+        let outstanding = self.try_outstanding_supply()?.value() as u128;
+        Ok(outstanding * self.try_face_value()?.value() as u128)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for BondWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { BondWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        BondWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        BondWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        BondWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::height::block_height;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = bond_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(BOND_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<BondAsset>(fixtures::contract_id("bond"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.face_value(), Amount::from(fixtures::FACE_VALUE));
+        assert_eq!(wrapper.coupon_rate(), fixtures::COUPON_RATE);
+        assert_eq!(wrapper.maturity(), fixtures::MATURITY);
+        assert!(!wrapper.is_mature(fixtures::MATURITY - 1));
+        assert!(wrapper.is_mature(fixtures::MATURITY));
+        assert_eq!(wrapper.redeemed_supply(), Amount::ZERO);
+        assert_eq!(wrapper.redeem_amounts(), vec![]);
+        assert_eq!(wrapper.outstanding_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(
+            wrapper.outstanding_principal(),
+            fixtures::ISSUED_SUPPLY as u128 * fixtures::FACE_VALUE as u128
+        );
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+    }
+
+    // Redemption of bonds.
+    //
+    // The holder redeems all the bonds issued by the genesis in a mined witness TX, committing to
+    // the lowest lock time allowed by the maturity. The scripts don't check the lock time of the
+    // witness, so the redeem is accepted even if it is mined before maturity, and it is the audit
+    // which must report it as premature until the witness is mined at the maturity height. The
+    // redeem scripts are run over the consumed history, which must be rejected if the redeemed
+    // supply doesn't match the spent bonds or if the redeem commits to a lock time letting its
+    // witness be mined before maturity.
+
+    /// Bond maturing at height `maturity`, with a face value of 100 000 and a coupon rate of 450
+    /// basis points.
+    fn bond(issued: u64, allocated: u64, maturity: u32) -> ContractBuilder {
+        testing::asset::<BondAsset>(issued, allocated)
+            .add_global_state("faceValue", Amount::from(100_000u64))
+            .unwrap()
+            .add_global_state("couponRate", Amount::from(450u64))
+            .unwrap()
+            .add_global_state("maturity", block_height(maturity))
+            .unwrap()
+    }
+
+    const MATURITY: u32 = 100;
+
+    /// Redeems all the bonds allocated by the genesis, recording `recorded` as the redeemed supply
+    /// and committing to `lock_time`.
+    fn redeem(
+        stock: &Stock,
+        contract_id: ContractId,
+        genesis_id: OpId,
+        recorded: u64,
+        lock_time: u32,
+    ) -> Transition {
+        stock
+            .transition_builder(contract_id, "redeem")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_global_state("redeemedSupply", Amount::from(recorded))
+            .unwrap()
+            .add_global_state("lockTime", block_height(lock_time))
+            .unwrap()
+            .complete_transition()
+            .unwrap()
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn redeem_at_maturity() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(bond(1000, 1000, MATURITY));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock.contract_wrapper::<BondAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.maturity(), MATURITY);
+        assert_eq!(wrapper.coupon_rate(), 450);
+        assert_eq!(wrapper.outstanding_principal(), 1000 * 100_000);
+
+        let transition = redeem(&stock, contract_id, genesis_id, 1000, wrapper.min_lock_time());
+        let opid = transition.id();
+
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 1)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(MATURITY - 1));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        testing::validate_history::<BondAsset>(&stock, &resolver, contract_id, &[opid]).unwrap();
+
+        let wrapper = stock.contract_wrapper::<BondAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.redeemed_supply(), Amount::from(1000u64));
+        assert_eq!(wrapper.redeem_amounts(), vec![Amount::from(1000u64)]);
+        assert_eq!(wrapper.outstanding_supply(), Amount::ZERO);
+        assert_eq!(wrapper.outstanding_principal(), 0);
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::PrematureRedeem {
+            op: opid,
+            height: MATURITY - 1,
+            maturity: MATURITY,
+        }]);
+        assert_eq!((report.issued, report.burned, report.allocated), (1000, 1000, 0));
+
+        resolver.set_witness_ord(witness_id, testing::mined_at(MATURITY));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn redeem_mismatch_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(bond(1000, 1000, MATURITY));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = redeem(&stock, contract_id, genesis_id, 999, MATURITY - 1);
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            MATURITY,
+        );
+
+        let err = testing::validate_history::<BondAsset>(&stock, &resolver, contract_id, &[opid])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::RedeemMismatch));
+    }
+
+    #[test]
+    fn early_redeem_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(bond(1000, 1000, MATURITY));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        // a lock time of `MATURITY - 2` lets the witness be mined one block before maturity
+        let transition = redeem(&stock, contract_id, genesis_id, 1000, MATURITY - 2);
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            MATURITY,
+        );
+
+        let err = testing::validate_history::<BondAsset>(&stock, &resolver, contract_id, &[opid])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::RedeemLocked));
+    }
+}
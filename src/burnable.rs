@@ -0,0 +1,454 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Burnable assets schema.
+//!
+//! A NIA variant whose holders can provably reduce the supply: a `burn` transition spends asset
+//! allocations without assigning any and records the burned amount in the global state. The
+//! supply can't be reissued afterwards, so the circulating supply only ever decreases, and burns
+//! of part of an allocation go through a transfer splitting it first.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER, RIA_BURN};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_BURNED_SUPPLY,
+    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS, OS_ASSET, TS_BURN, TS_TRANSFER,
+};
+
+pub const BURNABLE_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x24, 0x4a, 0x4a, 0xf0, 0xc3, 0xdc, 0x97, 0xa2, 0xb0, 0x5f, 0xbb, 0xb0, 0x6f, 0xab, 0x58, 0x5e,
+    0x76, 0x29, 0x9d, 0x0b, 0x9c, 0xf2, 0xf0, 0xda, 0x86, 0x36, 0x3e, 0x82, 0x4f, 0x32, 0x73, 0x84,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn burnable_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    RIA_BURN.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("BurnableAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_BURNED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("burnedSupply"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_BURN => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_BURNED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                    },
+                    assignments: none!(),
+                    validator: Some(RIA_BURN.lib_site())
+                },
+                name: fname!("burn"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn burnable_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, RIA_BURN])
+}
+
+#[derive(Default)]
+pub struct BurnableAsset;
+
+impl IssuerWrapper for BurnableAsset {
+    type Wrapper<S: ContractStateRead> = BurnableWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(burnable_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(burnable_scripts).clone()
+    }
+}
+
+impl BurnableAsset {
+    /// First revision of the burnable asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "BurnableAsset",
+        schema_id: BURNABLE_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset with a fixed supply issued at genesis, which holders can \
+                        reduce by burning their allocations.",
+    };
+}
+
+impl IssuerInfo for BurnableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for BurnableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct BurnableWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for BurnableWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for BurnableWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<BurnableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> BurnableWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the burnable asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<BurnableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the burnable asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the supply burned over the contract history.
+    pub fn total_burned(&self) -> Amount { or_panic(self.try_total_burned()) }
+
+    pub fn try_total_burned(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_BURNED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts burned by each burn, from the latest one.
+    pub fn burn_amounts(&self) -> Vec<Amount> { or_panic(self.try_burn_amounts()) }
+
+    pub fn try_burn_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_BURNED_SUPPLY)?.collect()
+    }
+
+    /// Returns the supply issued at genesis which isn't burned yet.
+    pub fn circulating_supply(&self) -> Amount { or_panic(self.try_circulating_supply()) }
+
+    pub fn try_circulating_supply(&self) -> Result<Amount, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        let burned = self.try_total_burned()?.value();
+        // Burns are checked against the spent allocations, so they never exceed the issuance
+        Ok(Amount::from(issued.saturating_sub(burned)))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for BurnableWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        BurnableWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        BurnableWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        BurnableWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        BurnableWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::audit_onchain;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = burnable_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(BURNABLE_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<BurnableAsset>(fixtures::contract_id("burnable"))
+            .unwrap();
+        assert_eq!(wrapper.version(), BurnableAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.total_burned(), Amount::ZERO);
+        assert_eq!(wrapper.burn_amounts(), vec![]);
+        assert_eq!(wrapper.circulating_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+    }
+
+    // Burning part of the supply of a burnable asset.
+    //
+    // The holder splits the issued supply with a transfer and burns one of the resulting
+    // allocations, each operation in its own mined witness TX; the wrapper must report the
+    // burned amount out of the circulating supply, and the audit must account it as burned. A
+    // burn recording another amount than the burned allocations must be rejected by the
+    // validation of the history.
+
+    /// Burnable asset allocated to `seal(1)`.
+    fn burnable(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::asset::<BurnableAsset>(issued, allocated)
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn burn_part_of_supply() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(burnable(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 600u64)
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(2), 400u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 1)], 2);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let transfer_witness = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(101));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<BurnableAsset>(contract_id)
+            .unwrap();
+        let burned = wrapper
+            .allocations(&FilterIncludeAll)
+            .find(|a| a.state.value() == 400)
+            .unwrap();
+        assert_eq!(burned.seal, OutputSeal::new(Outpoint::new(transfer_witness, 2)));
+        let transition = stock
+            .transition_builder(contract_id, "burn")
+            .unwrap()
+            .add_input(burned.opout, AllocatedState::Amount(RevealedValue::from(400u64)))
+            .unwrap()
+            .add_global_state("burnedSupply", Amount::from(400u64))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(transfer_witness, 2)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        resolver.add_witness(witness, testing::mined_at(102));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        testing::validate_history::<BurnableAsset>(&stock, &resolver, contract_id, &[opid])
+            .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<BurnableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1000u64));
+        assert_eq!(wrapper.total_burned(), Amount::from(400u64));
+        assert_eq!(wrapper.burn_amounts(), vec![Amount::from(400u64)]);
+        assert_eq!(wrapper.circulating_supply(), Amount::from(600u64));
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!((report.issued, report.burned, report.allocated), (1000, 400, 600));
+    }
+
+    #[test]
+    fn burn_mismatch_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(burnable(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = stock
+            .transition_builder(contract_id, "burn")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_global_state("burnedSupply", Amount::from(999u64))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            101,
+        );
+
+        let err =
+            testing::validate_history::<BurnableAsset>(&stock, &resolver, contract_id, &[opid])
+                .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::BurnMismatch));
+    }
+}
@@ -0,0 +1,746 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operations supported by the contracts of a schema.
+//!
+//! Wallets handling contracts of schemata they don't know, e.g. distributed as kits by third
+//! parties, can't rely on the schema wrappers of this crate to know which actions to offer.
+//! [`SchemaCapabilities`] derives them from the schema itself: the operations are recognized by
+//! the standard names of their transitions, shared by the schemata of this crate, and the
+//! transitions requiring a signature by the validation scripts verifying one.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use aluvm::isa::{Bytecode, Instr};
+use aluvm::library::{Cursor, Lib, LibId, Read};
+use rgbstd::containers::Kit;
+use rgbstd::persistence::MemContract;
+use rgbstd::schema::{OwnedStateSchema, Schema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::{ContractOp, RgbIsa};
+use rgbstd::{SchemaId, TransitionType};
+
+/// Operation which contracts of a schema may support.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum Capability {
+    /// Transfer of the owned state to new owners.
+    Transfer,
+    /// Issuance of new units of the asset.
+    Inflate,
+    /// Destruction of units of the asset.
+    Burn,
+    /// Replacement of allocations by the issuer.
+    Replace,
+    /// Issuance of new units of the asset in place of burned ones.
+    Reissue,
+    /// Revocation of allocations by the issuer.
+    Revoke,
+    /// Destruction of units of the asset redeemed off-chain, like stablecoins, matured bonds or
+    /// used tickets.
+    Redeem,
+    /// Casting of votes by spending vote rights.
+    Cast,
+    /// Release of locked units of the asset, following a vesting schedule or paying escrowed
+    /// units out to the beneficiary.
+    Release,
+    /// Freezing of allocations by the issuer.
+    Freeze,
+    /// Approval of an inflation by one of the keys committed in the global state.
+    Approve,
+    /// Issuance of new units of the asset by the holders of mint rights, like wrapped BTC
+    /// attesting the reserves backing them or the editions of a limited series.
+    Mint,
+    /// Collection of transfer fees or of claims on the issuer, turning their allocations into
+    /// units of the asset.
+    Collect,
+    /// Recording of the dividends distributed to the holders by the issuer.
+    Distribute,
+    /// Locking of units of the asset in escrow until they are released or refunded.
+    Escrow,
+    /// Return of escrowed units of the asset to the sender.
+    Refund,
+    /// Filling of a swap order, in part or in full.
+    Fill,
+    /// Cancellation of a swap order by its maker.
+    Cancel,
+    /// Retirement of units of the asset, destroying them on behalf of a beneficiary.
+    Retire,
+    /// Extension of the validity of a subscription by the issuer.
+    Renew,
+    /// Registration of a new name by the holders of register rights.
+    Register,
+    /// Pausing or resuming of all the transfers of the asset by the issuer.
+    Pause,
+    /// Leasing of the usage of a token by its owner up to an expiry height.
+    Lease,
+    /// Ending of an expired lease by the owner of the token.
+    Reclaim,
+    /// Exercise of options by their holders up to the expiry.
+    Exercise,
+    /// Destruction of the options left unexercised after the expiry.
+    Lapse,
+    /// Recording of claims about the holder of a credential, signed by its issuer.
+    Attest,
+    /// Scaling of all the balances by a rebase factor published by the issuer.
+    Rebase,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 28] = [
+        Capability::Transfer,
+        Capability::Inflate,
+        Capability::Burn,
+        Capability::Replace,
+        Capability::Reissue,
+        Capability::Revoke,
+        Capability::Redeem,
+        Capability::Cast,
+        Capability::Release,
+        Capability::Freeze,
+        Capability::Approve,
+        Capability::Mint,
+        Capability::Collect,
+        Capability::Distribute,
+        Capability::Escrow,
+        Capability::Refund,
+        Capability::Fill,
+        Capability::Cancel,
+        Capability::Retire,
+        Capability::Renew,
+        Capability::Register,
+        Capability::Pause,
+        Capability::Lease,
+        Capability::Reclaim,
+        Capability::Exercise,
+        Capability::Lapse,
+        Capability::Attest,
+        Capability::Rebase,
+    ];
+
+    /// Name of the transition performing the operation.
+    pub const fn transition_name(self) -> &'static str {
+        match self {
+            Capability::Transfer => "transfer",
+            Capability::Inflate => "inflate",
+            Capability::Burn => "burn",
+            Capability::Replace => "replace",
+            Capability::Reissue => "reissue",
+            Capability::Revoke => "revoke",
+            Capability::Redeem => "redeem",
+            Capability::Cast => "cast",
+            Capability::Release => "release",
+            Capability::Freeze => "freeze",
+            Capability::Approve => "approve",
+            Capability::Mint => "mint",
+            Capability::Collect => "collect",
+            Capability::Distribute => "distribute",
+            Capability::Escrow => "escrow",
+            Capability::Refund => "refund",
+            Capability::Fill => "fill",
+            Capability::Cancel => "cancel",
+            Capability::Retire => "retire",
+            Capability::Renew => "renew",
+            Capability::Register => "register",
+            Capability::Pause => "togglePause",
+            Capability::Lease => "lease",
+            Capability::Reclaim => "reclaim",
+            Capability::Exercise => "exercise",
+            Capability::Lapse => "lapse",
+            Capability::Attest => "attest",
+            Capability::Rebase => "rebase",
+        }
+    }
+}
+
+/// Operations and kinds of state of the contracts of a schema.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct SchemaCapabilities {
+    /// Transitions performing the supported operations.
+    pub operations: BTreeMap<Capability, TransitionType>,
+    /// Transitions whose validation verifies a signature, known only if the schema is analyzed
+    /// together with its scripts.
+    pub signed: BTreeSet<TransitionType>,
+    /// Whether the schema has fungible owned state.
+    pub fungible: bool,
+    /// Whether the schema has structured owned state, like the token allocations of unique assets.
+    pub structured: bool,
+}
+
+impl SchemaCapabilities {
+    /// Derives the operations of a schema from the names of its transitions.
+    ///
+    /// The scripts of the schema are not inspected, so [`SchemaCapabilities::signed`] is empty;
+    /// use [`SchemaCapabilities::analyze_with`] to detect signature-gated transitions.
+    pub fn analyze(schema: &Schema) -> Self {
+        let operations = Capability::ALL
+            .into_iter()
+            .filter_map(|capability| {
+                schema
+                    .transitions
+                    .iter()
+                    .find(|(_, details)| details.name.as_str() == capability.transition_name())
+                    .map(|(ty, _)| (capability, *ty))
+            })
+            .collect();
+        let states = schema
+            .owned_types
+            .values()
+            .map(|details| &details.owned_state_schema);
+        SchemaCapabilities {
+            operations,
+            signed: bset! {},
+            fungible: states
+                .clone()
+                .any(|state| matches!(state, OwnedStateSchema::Fungible(_))),
+            structured: states
+                .clone()
+                .any(|state| matches!(state, OwnedStateSchema::Structured(_))),
+        }
+    }
+
+    /// Derives the operations of a schema, also detecting the transitions whose validation
+    /// verifies a signature.
+    ///
+    /// A transition is considered signature-gated if its validation library, or any library it
+    /// calls, contains a signature verification. Libraries shared by several transitions may thus
+    /// mark as signature-gated transitions which don't execute the verification.
+    pub fn analyze_with(schema: &Schema, scripts: &Scripts) -> Self {
+        Self::analyze_libs(schema, |id| scripts.get(&id))
+    }
+
+    /// Analyzes each schema of a kit with the scripts of the kit, see
+    /// [`SchemaCapabilities::analyze_with`].
+    pub fn analyze_kit(kit: &Kit) -> BTreeMap<SchemaId, Self> {
+        let libs = kit
+            .scripts
+            .iter()
+            .map(|lib| (lib.id(), lib))
+            .collect::<BTreeMap<_, _>>();
+        kit.schemata
+            .iter()
+            .map(|schema| {
+                let capabilities = Self::analyze_libs(schema, |id| libs.get(&id).copied());
+                (schema.schema_id(), capabilities)
+            })
+            .collect()
+    }
+
+    fn analyze_libs<'l>(schema: &Schema, libs: impl Fn(LibId) -> Option<&'l Lib>) -> Self {
+        let mut capabilities = Self::analyze(schema);
+        capabilities.signed = schema
+            .transitions
+            .iter()
+            .filter(|(_, details)| {
+                details
+                    .transition_schema
+                    .validator
+                    .is_some_and(|site| verifies_signature(site.lib, &libs, &mut bset! {}))
+            })
+            .map(|(ty, _)| *ty)
+            .collect();
+        capabilities
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.operations.contains_key(&capability)
+    }
+
+    /// Returns the transition type performing the operation, if the schema supports it.
+    pub fn transition_type(&self, capability: Capability) -> Option<TransitionType> {
+        self.operations.get(&capability).copied()
+    }
+
+    /// Checks whether the operation is supported and requires a signature.
+    pub fn is_signed(&self, capability: Capability) -> bool {
+        self.transition_type(capability)
+            .is_some_and(|ty| self.signed.contains(&ty))
+    }
+}
+
+/// Checks whether the library, or a library it calls, verifies a signature. Libraries missing from
+/// the scripts or failing to disassemble are considered not to.
+fn verifies_signature<'l>(
+    id: LibId,
+    libs: &impl Fn(LibId) -> Option<&'l Lib>,
+    visited: &mut BTreeSet<LibId>,
+) -> bool {
+    if !visited.insert(id) {
+        return false;
+    }
+    let Some(lib) = libs(id) else {
+        return false;
+    };
+    has_vts(lib)
+        || lib
+            .libs
+            .iter()
+            .any(|callee| verifies_signature(*callee, libs, visited))
+}
+
+/// Checks whether the library code contains a signature verification.
+///
+/// The code is decoded instruction by instruction rather than with [`Lib::disassemble`], which
+/// fails on the padding following a `vts` instruction at the end of the code.
+fn has_vts(lib: &Lib) -> bool {
+    let mut reader = Cursor::with(&lib.code, &lib.data, &lib.libs);
+    while !reader.is_eof() {
+        match Instr::<RgbIsa<MemContract>>::decode(&mut reader) {
+            Ok(Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::Vts(_)))) => return true,
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+#[cfg_attr(not(all_schemas), allow(dead_code, unused_imports))]
+mod test {
+    use rgbstd::contract::IssuerWrapper;
+
+    use super::*;
+    use crate::*;
+
+    fn analyze<I: IssuerWrapper>() -> SchemaCapabilities {
+        SchemaCapabilities::analyze_with(&I::schema(), &I::scripts())
+    }
+
+    #[test]
+    #[cfg(feature = "nia")]
+    fn nia() {
+        let capabilities = analyze::<NonInflatableAsset>();
+        assert_eq!(capabilities.operations, bmap! { Capability::Transfer => TS_TRANSFER });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+        assert!(!capabilities.is_signed(Capability::Transfer));
+    }
+
+    #[test]
+    #[cfg(feature = "pfa")]
+    fn pfa() {
+        let capabilities = analyze::<PermissionedFungibleAsset>();
+        assert!(capabilities.supports(Capability::Transfer));
+        assert!(!capabilities.supports(Capability::Burn));
+        assert!(capabilities.is_signed(Capability::Transfer));
+
+        let unscripted = SchemaCapabilities::analyze(&PermissionedFungibleAsset::schema());
+        assert!(!unscripted.is_signed(Capability::Transfer));
+
+        let kit = crate::kit::kit::<PermissionedFungibleAsset>();
+        assert_eq!(SchemaCapabilities::analyze_kit(&kit), bmap! { PFA_SCHEMA_ID => capabilities });
+    }
+
+    #[test]
+    #[cfg(feature = "ifa")]
+    fn ifa() {
+        let capabilities = analyze::<InflatableFungibleAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Inflate => TS_INFLATION,
+            Capability::Burn => TS_BURN,
+            Capability::Replace => TS_REPLACE,
+        });
+        assert!(capabilities.signed.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "ria")]
+    fn ria() {
+        let capabilities = analyze::<ReissuableAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Burn => TS_BURN,
+            Capability::Reissue => TS_REISSUE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "sba")]
+    fn sba() {
+        let capabilities = analyze::<SoulboundAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Burn => TS_BURN,
+            Capability::Revoke => TS_REVOKE,
+        });
+        assert!(capabilities.signed.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "stablecoin")]
+    fn stablecoin() {
+        let capabilities = analyze::<StablecoinAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Inflate => TS_INFLATION,
+            Capability::Redeem => TS_REDEEM,
+        });
+        assert_eq!(capabilities.signed, bset! { TS_INFLATION, TS_REDEEM });
+        assert!(!capabilities.is_signed(Capability::Transfer));
+    }
+
+    #[test]
+    #[cfg(feature = "bond")]
+    fn bond() {
+        let capabilities = analyze::<BondAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Redeem => TS_REDEEM,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "governance")]
+    fn governance() {
+        let capabilities = analyze::<GovernanceAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Cast => TS_CAST,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "ticket")]
+    fn ticket() {
+        let capabilities = analyze::<TicketAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Redeem => TS_REDEEM,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "vesting")]
+    fn vesting() {
+        let capabilities = analyze::<VestingAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Release => TS_RELEASE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "timelock")]
+    fn timelock() {
+        let capabilities = analyze::<TimelockAsset>();
+        assert_eq!(capabilities.operations, bmap! { Capability::Transfer => TS_TRANSFER });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "pfan")]
+    fn pfan() {
+        let capabilities = analyze::<MultiKeyPermissionedAsset>();
+        assert_eq!(capabilities.operations, bmap! { Capability::Transfer => TS_TRANSFER });
+        assert_eq!(capabilities.signed, bset! { TS_TRANSFER });
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "freezable")]
+    fn freezable() {
+        let capabilities = analyze::<FreezableAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Freeze => TS_FREEZE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "pausable")]
+    fn pausable() {
+        let capabilities = analyze::<PausableAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Pause => TS_TOGGLE_PAUSE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+    }
+
+    #[test]
+    #[cfg(feature = "tifa")]
+    fn tifa() {
+        let capabilities = analyze::<ThresholdInflatableAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Inflate => TS_INFLATION,
+            Capability::Approve => TS_APPROVE,
+        });
+        // The inflation shares the library verifying the approval signatures
+        assert_eq!(capabilities.signed, bset! { TS_INFLATION, TS_APPROVE });
+        assert!(capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "wbtc")]
+    fn wbtc() {
+        let capabilities = analyze::<WrappedBtcAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Burn => TS_BURN,
+            Capability::Mint => TS_MINT,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "burnable")]
+    fn burnable() {
+        let capabilities = analyze::<BurnableAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Burn => TS_BURN,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "rebasing")]
+    fn rebasing() {
+        let capabilities = analyze::<RebasingAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Rebase => TS_REBASE,
+        });
+        assert_eq!(capabilities.signed, bset! { TS_REBASE });
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "fee")]
+    fn fee() {
+        let capabilities = analyze::<TransferFeeAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Collect => TS_COLLECT,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "dividend")]
+    fn dividend() {
+        let capabilities = analyze::<DividendAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Distribute => TS_DISTRIBUTE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "escrow")]
+    fn escrow() {
+        let capabilities = analyze::<EscrowAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Release => TS_RELEASE,
+            Capability::Escrow => TS_ESCROW,
+            Capability::Refund => TS_REFUND,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "swap")]
+    fn swap() {
+        let capabilities = analyze::<SwapOrder>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Fill => TS_FILL,
+            Capability::Cancel => TS_CANCEL,
+        });
+        assert_eq!(capabilities.signed, bset! { TS_FILL, TS_CANCEL });
+        assert!(!capabilities.supports(Capability::Transfer));
+        assert!(!capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "carbon")]
+    fn carbon() {
+        let capabilities = analyze::<CarbonCreditAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Retire => TS_RETIRE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "provenance")]
+    fn provenance() {
+        let capabilities = analyze::<ProvenanceAsset>();
+        // Checkpoints move the token too, but aren't an operation of their own
+        assert_eq!(capabilities.operations, bmap! { Capability::Transfer => TS_TRANSFER });
+        assert!(capabilities.signed.is_empty());
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "subscription")]
+    fn subscription() {
+        let capabilities = analyze::<SubscriptionAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Renew => TS_RENEW,
+        });
+        assert_eq!(capabilities.signed, bset! { TS_RENEW });
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "rental")]
+    fn rental() {
+        let capabilities = analyze::<RentalAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Lease => TS_LEASE,
+            Capability::Reclaim => TS_RECLAIM,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "option")]
+    fn option() {
+        let capabilities = analyze::<OptionAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Exercise => TS_EXERCISE,
+            Capability::Lapse => TS_LAPSE,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "credential")]
+    fn credential() {
+        let capabilities = analyze::<VerifiableCredential>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Revoke => TS_REVOKE,
+            Capability::Attest => TS_ATTEST,
+        });
+        assert_eq!(capabilities.signed, bset! { TS_ATTEST });
+        assert!(!capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "edition")]
+    fn edition() {
+        let capabilities = analyze::<LimitedEditionAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Mint => TS_MINT,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "eifa")]
+    fn eifa() {
+        let capabilities = analyze::<EpochInflatableAsset>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Inflate => TS_INFLATION,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "claim")]
+    fn claim() {
+        let capabilities = analyze::<ClaimableAsset>();
+        // Claims are handed to the issuer, but aren't a capability of their own
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Collect => TS_COLLECT,
+        });
+        // The claim shares the library verifying the issuer signature of the collection
+        assert_eq!(capabilities.signed, bset! { TS_CLAIM, TS_COLLECT });
+        assert!(capabilities.fungible);
+        assert!(!capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "registry")]
+    fn registry() {
+        let capabilities = analyze::<NameRegistry>();
+        assert_eq!(capabilities.operations, bmap! {
+            Capability::Transfer => TS_TRANSFER,
+            Capability::Register => TS_REGISTER,
+        });
+        assert!(capabilities.signed.is_empty());
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+
+    #[test]
+    #[cfg(feature = "uda")]
+    fn uda() {
+        let capabilities = analyze::<UniqueDigitalAsset>();
+        assert_eq!(capabilities.transition_type(Capability::Transfer), Some(TS_TRANSFER));
+        assert!(!capabilities.fungible);
+        assert!(capabilities.structured);
+    }
+}
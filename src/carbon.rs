@@ -0,0 +1,538 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Carbon credit schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the CFA schema for carbon credits, whose holders offset their emissions by
+//! retiring the credits they own with a `retire` transition. The retirement destroys the credits
+//! it doesn't assign back as change and records in the global state the retired supply, the
+//! beneficiary of the offset and its purpose, so that [`CarbonWrapper::retirements`] can list
+//! every retirement made over the contract history. The validation scripts require the retired
+//! supply to match the credits destroyed.
+//!
+//! Credits can't be issued after the genesis, so the supply issued minus the retired one is the
+//! amount of credits still in circulation.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, Details, Name};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, Precision, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, CARBON_RETIRE, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_DETAILS,
+    GS_ISSUED_SUPPLY, GS_NAME, GS_PRECISION, GS_RETIRED_SUPPLY, GS_RETIREMENT_BENEFICIARY,
+    GS_RETIREMENT_PURPOSE, GS_TERMS, OS_ASSET, TS_RETIRE, TS_TRANSFER,
+};
+
+pub const CARBON_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x93, 0xf1, 0x01, 0xbb, 0xbc, 0xba, 0xd2, 0x4c, 0x4a, 0x14, 0x20, 0xdc, 0xc5, 0x95, 0x62, 0x36,
+    0x3e, 0x1f, 0x62, 0xb8, 0x95, 0x46, 0xf4, 0x29, 0xe6, 0xe0, 0x23, 0xfe, 0x0c, 0x9f, 0xf6, 0x6d,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn carbon_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    CARBON_RETIRE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("CarbonCreditAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NAME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Name")),
+                name: fname!("name"),
+            },
+            GS_DETAILS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Details")),
+                name: fname!("details"),
+            },
+            GS_PRECISION => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Precision")),
+                name: fname!("precision"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_RETIRED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("retiredSupply"),
+            },
+            GS_RETIREMENT_BENEFICIARY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Details")),
+                name: fname!("retirementBeneficiary"),
+            },
+            GS_RETIREMENT_PURPOSE => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Details")),
+                name: fname!("retirementPurpose"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NAME => Occurrences::Once,
+                GS_DETAILS => Occurrences::NoneOrOnce,
+                GS_PRECISION => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_RETIRE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_RETIRED_SUPPLY => Occurrences::Once,
+                        GS_RETIREMENT_BENEFICIARY => Occurrences::Once,
+                        GS_RETIREMENT_PURPOSE => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore
+                    },
+                    validator: Some(CARBON_RETIRE.lib_site())
+                },
+                name: fname!("retire"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn carbon_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, CARBON_RETIRE])
+}
+
+#[derive(Default)]
+pub struct CarbonCreditAsset;
+
+impl IssuerWrapper for CarbonCreditAsset {
+    type Wrapper<S: ContractStateRead> = CarbonWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(carbon_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(carbon_scripts).clone()
+    }
+}
+
+impl CarbonCreditAsset {
+    /// First revision of the carbon credit schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "CarbonCreditAsset",
+        schema_id: CARBON_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "CFA variant for carbon credits, retired by their holders with a record of \
+                        the beneficiary and the purpose of each retirement.",
+    };
+}
+
+impl IssuerInfo for CarbonCreditAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for CarbonCreditAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+/// Retirement of carbon credits, as recorded in the global state by a `retire` transition.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Retirement {
+    /// Credits destroyed by the retirement.
+    pub amount: Amount,
+    /// Party on whose behalf the credits are retired, claiming the offset.
+    pub beneficiary: Details,
+    /// Purpose of the retirement, like the emissions it offsets.
+    pub purpose: Details,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct CarbonWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for CarbonWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for CarbonWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<CarbonCreditAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> CarbonWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the carbon credit schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<CarbonCreditAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the carbon credit schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn name(&self) -> Name { or_panic(self.try_name()) }
+
+    pub fn try_name(&self) -> Result<Name, Error> { global(&self.0, GS_NAME) }
+
+    pub fn details(&self) -> Option<Details> { or_panic(self.try_details()) }
+
+    pub fn try_details(&self) -> Result<Option<Details>, Error> {
+        globals(&self.0, GS_DETAILS)?.next().transpose()
+    }
+
+    pub fn precision(&self) -> Precision { or_panic(self.try_precision()) }
+
+    pub fn try_precision(&self) -> Result<Precision, Error> { global(&self.0, GS_PRECISION) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the credits retired over the contract history.
+    pub fn retired_amount(&self) -> Amount { or_panic(self.try_retired_amount()) }
+
+    pub fn try_retired_amount(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_RETIRED_SUPPLY)?.sum()
+    }
+
+    /// Returns the retirements in the order they were made.
+    pub fn retirements(&self) -> Vec<Retirement> { or_panic(self.try_retirements()) }
+
+    pub fn try_retirements(&self) -> Result<Vec<Retirement>, Error> {
+        // Each retirement records one entry of each global state, so their entries match one to
+        // one
+        let amounts = globals(&self.0, GS_RETIRED_SUPPLY)?.collect::<Result<Vec<Amount>, _>>()?;
+        let beneficiaries =
+            globals(&self.0, GS_RETIREMENT_BENEFICIARY)?.collect::<Result<Vec<Details>, _>>()?;
+        let purposes =
+            globals(&self.0, GS_RETIREMENT_PURPOSE)?.collect::<Result<Vec<Details>, _>>()?;
+        if beneficiaries.len() != amounts.len() {
+            return Err(Error::MissingGlobal(GS_RETIREMENT_BENEFICIARY));
+        }
+        if purposes.len() != amounts.len() {
+            return Err(Error::MissingGlobal(GS_RETIREMENT_PURPOSE));
+        }
+        let mut retirements = amounts
+            .into_iter()
+            .zip(beneficiaries)
+            .zip(purposes)
+            .map(|((amount, beneficiary), purpose)| Retirement {
+                amount,
+                beneficiary,
+                purpose,
+            })
+            .collect::<Vec<_>>();
+        // Global state lists the latest entries first
+        retirements.reverse();
+        Ok(retirements)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for CarbonWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { Ok(None) }
+
+    fn try_name(&self) -> Result<Name, Error> { CarbonWrapper::try_name(self) }
+
+    fn try_precision(&self) -> Result<Precision, Error> { CarbonWrapper::try_precision(self) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        CarbonWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        CarbonWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        CarbonWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::Details;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = carbon_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(CARBON_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<CarbonCreditAsset>(fixtures::contract_id("carbon"))
+            .unwrap();
+        assert_eq!(wrapper.version(), CarbonCreditAsset::V1);
+        assert_eq!(wrapper.name(), Name::from("Test asset"));
+        assert_eq!(wrapper.details(), Some(fixtures::details()));
+        assert_eq!(wrapper.precision(), Precision::CentiMicro);
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.retired_amount(), Amount::ZERO);
+        assert!(wrapper.retirements().is_empty());
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+    }
+
+    // Retirements of carbon credits.
+    //
+    // A holder retires part of their credits, keeping the change, and then retires the change on
+    // behalf of another beneficiary; the wrapper lists both retirements in the order they were
+    // made. A retirement recording another amount than the retired credits must be rejected by
+    // the validation of the history.
+
+    /// Carbon credits allocated to `seal(1)`.
+    fn carbon(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::builder::<CarbonCreditAsset>()
+            .add_global_state("name", Name::from("Test asset"))
+            .unwrap()
+            .add_global_state("precision", Precision::CentiMicro)
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("issuedSupply", Amount::from(issued))
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::seal(1), allocated)
+            .unwrap()
+    }
+
+    #[test]
+    fn retire() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(carbon(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 1);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+        let retirement = |amount: u64, beneficiary: &str, purpose: &str| Retirement {
+            amount: Amount::from(amount),
+            beneficiary: Details::from_str(beneficiary).unwrap(),
+            purpose: Details::from_str(purpose).unwrap(),
+        };
+
+        let mut owned =
+            (Opout::new(genesis_id, OS_ASSET, 0), Outpoint::new(testing::txid(), 1), 1000u64);
+        let mut expected = vec![];
+        for (height, retired) in [
+            (101, retirement(300, "ACME Corp", "Offset of the 2025 emissions")),
+            (102, retirement(700, "Jane Doe", "Offset of a transatlantic flight")),
+        ] {
+            let change = owned.2 - retired.amount.value();
+            let mut builder = stock
+                .transition_builder(contract_id, "retire")
+                .unwrap()
+                .add_input(owned.0, AllocatedState::Amount(RevealedValue::from(owned.2)))
+                .unwrap()
+                .add_global_state("retiredSupply", retired.amount)
+                .unwrap()
+                .add_global_state("retirementBeneficiary", retired.beneficiary.clone())
+                .unwrap()
+                .add_global_state("retirementPurpose", retired.purpose.clone())
+                .unwrap();
+            if change > 0 {
+                builder = builder
+                    .add_fungible_state("assetOwner", testing::graph_seal(1), change)
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            let opid = transition.id();
+            let witness_id = consume(&mut stock, transition, &[owned.1], height);
+            owned = (Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness_id, 1), change);
+            expected.push(retired);
+
+            let wrapper = stock
+                .contract_wrapper::<CarbonCreditAsset>(contract_id)
+                .unwrap();
+            assert_eq!(wrapper.retirements(), expected);
+        }
+
+        testing::validate_history::<CarbonCreditAsset>(&stock, &resolver, contract_id, &[owned
+            .0
+            .op])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<CarbonCreditAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.retired_amount(), Amount::from(1000u64));
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1000u64));
+    }
+
+    #[test]
+    fn retire_mismatch_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(carbon(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = stock
+            .transition_builder(contract_id, "retire")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_global_state("retiredSupply", Amount::from(999u64))
+            .unwrap()
+            .add_global_state("retirementBeneficiary", Details::from_str("ACME Corp").unwrap())
+            .unwrap()
+            .add_global_state("retirementPurpose", Details::from_str("Offset").unwrap())
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            101,
+        );
+
+        let err =
+            testing::validate_history::<CarbonCreditAsset>(&stock, &resolver, contract_id, &[opid])
+                .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::BurnMismatch));
+    }
+}
@@ -21,8 +21,9 @@
 
 //! Collectible Fungible Assets (CFA) schema.
 
-use aluvm::library::LibSite;
-use amplify::confinement::Confined;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
 use rgbstd::contract::{
     AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
 };
@@ -31,14 +32,18 @@ use rgbstd::schema::{
     AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
     Schema, TransitionDetails, TransitionSchema,
 };
-use rgbstd::stl::{rgb_contract_stl, ContractTerms, Details, Name, StandardTypes};
+use rgbstd::stl::{AssetSpec, ContractTerms, Details, Name};
 use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
 use rgbstd::{Amount, OwnedStateSchema, Precision, SchemaId};
 use strict_types::TypeSystem;
 
-use crate::nia::{nia_lib, FN_NIA_GENESIS_OFFSET, FN_NIA_TRANSFER_OFFSET};
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
 use crate::{
-    GS_ART, GS_DETAILS, GS_ISSUED_SUPPLY, GS_NAME, GS_PRECISION, GS_TERMS, OS_ASSET, TS_TRANSFER,
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ART, GS_DETAILS,
+    GS_ISSUED_SUPPLY, GS_NAME, GS_PRECISION, GS_TERMS, OS_ASSET, TS_TRANSFER,
 };
 
 pub const CFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
@@ -46,12 +51,12 @@ pub const CFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
     0xe8, 0x8b, 0x4d, 0xc0, 0x39, 0x72, 0xc5, 0x02, 0x9c, 0xbc, 0xef, 0x68, 0xa4, 0xd3, 0xac, 0xd6,
 ]);
 
-fn cfa_standard_types() -> StandardTypes { StandardTypes::with(rgb_contract_stl()) }
-
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
 pub fn cfa_schema() -> Schema {
-    let types = cfa_standard_types();
+    let types = standard_types();
 
-    let nia_id = nia_lib().id();
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
 
     Schema {
         ffv: zero!(),
@@ -103,7 +108,7 @@ pub fn cfa_schema() -> Schema {
             assignments: tiny_bmap! {
                 OS_ASSET => Occurrences::OnceOrMore,
             },
-            validator: Some(LibSite::with(FN_NIA_GENESIS_OFFSET, nia_id)),
+            validator: Some(NIA_GENESIS.lib_site()),
         },
         transitions: tiny_bmap! {
             TS_TRANSFER => TransitionDetails {
@@ -116,7 +121,7 @@ pub fn cfa_schema() -> Schema {
                     assignments: tiny_bmap! {
                         OS_ASSET => Occurrences::OnceOrMore
                     },
-                    validator: Some(LibSite::with(FN_NIA_TRANSFER_OFFSET, nia_id))
+                    validator: Some(NIA_TRANSFER.lib_site())
                 },
                 name: fname!("transfer"),
             }
@@ -125,6 +130,8 @@ pub fn cfa_schema() -> Schema {
     }
 }
 
+fn cfa_scripts() -> Scripts { SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER]) }
+
 #[derive(Default)]
 pub struct CollectibleFungibleAsset;
 
@@ -134,78 +141,147 @@ pub struct CfaWrapper<S: ContractStateRead>(ContractData<S>);
 impl IssuerWrapper for CollectibleFungibleAsset {
     type Wrapper<S: ContractStateRead> = CfaWrapper<S>;
 
-    fn schema() -> Schema { cfa_schema() }
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(cfa_schema).clone()
+    }
 
-    fn types() -> TypeSystem { cfa_standard_types().type_system(cfa_schema()) }
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
 
     fn scripts() -> Scripts {
-        let lib = nia_lib();
-        Confined::from_checked(bmap! { lib.id() => lib })
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(cfa_scripts).clone()
     }
 }
 
+impl CollectibleFungibleAsset {
+    /// First revision of the CFA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "CollectibleFungibleAsset",
+        schema_id: CFA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fungible asset with a fixed supply issued at genesis, described by a \
+                        name, optional details and precision instead of a ticker; reuses the NIA \
+                        validation scripts.",
+    };
+}
+
+impl IssuerInfo for CollectibleFungibleAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for CollectibleFungibleAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
 impl<S: ContractStateRead> SchemaWrapper<S> for CfaWrapper<S> {
-    fn with(data: ContractData<S>) -> Self {
-        if data.schema.schema_id() != CFA_SCHEMA_ID {
-            panic!("the provided schema is not CFA");
-        }
-        Self(data)
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for CfaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<CollectibleFungibleAsset, _>(&self.0)?)
     }
 }
 
 impl<S: ContractStateRead> CfaWrapper<S> {
-    pub fn name(&self) -> Name {
-        let strict_val = &self
-            .0
-            .global("name")
-            .next()
-            .expect("CFA requires global state `name` to have at least one item");
-        Name::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, failing if it doesn't belong to the CFA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<CollectibleFungibleAsset, _>(&data)?;
+        Ok(Self(data))
     }
 
-    pub fn details(&self) -> Option<Details> {
-        self.0
-            .global("details")
-            .next()
-            .map(|strict_val| Details::from_strict_val_unchecked(&strict_val))
-    }
+    /// Wraps the contract data, panicking if it doesn't belong to the CFA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
 
-    pub fn precision(&self) -> Precision {
-        let strict_val = &self
-            .0
-            .global("precision")
-            .next()
-            .expect("CFA requires global state `precision` to have at least one item");
-        Precision::from_strict_val_unchecked(strict_val)
-    }
+    pub fn name(&self) -> Name { or_panic(self.try_name()) }
 
-    pub fn total_issued_supply(&self) -> Amount {
-        self.0
-            .global("issuedSupply")
-            .map(|amount| Amount::from_strict_val_unchecked(&amount))
-            .sum()
+    pub fn try_name(&self) -> Result<Name, Error> { global(&self.0, GS_NAME) }
+
+    pub fn details(&self) -> Option<Details> { or_panic(self.try_details()) }
+
+    pub fn try_details(&self) -> Result<Option<Details>, Error> {
+        globals(&self.0, GS_DETAILS)?.next().transpose()
     }
 
-    pub fn contract_terms(&self) -> ContractTerms {
-        let strict_val = &self
-            .0
-            .global("terms")
-            .next()
-            .expect("CFA requires global state `terms` to have at least one item");
-        ContractTerms::from_strict_val_unchecked(strict_val)
+    pub fn precision(&self) -> Precision { or_panic(self.try_precision()) }
+
+    pub fn try_precision(&self) -> Result<Precision, Error> { global(&self.0, GS_PRECISION) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
     }
 
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
     pub fn allocations<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = FungibleAllocation> + 'c {
-        self.0.fungible_raw(OS_ASSET, filter).unwrap()
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for CfaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { Ok(None) }
+
+    fn try_name(&self) -> Result<Name, Error> { CfaWrapper::try_name(self) }
+
+    fn try_precision(&self) -> Result<Precision, Error> { CfaWrapper::try_precision(self) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        CfaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        CfaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        CfaWrapper::try_allocations(self, filter)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
+    use rgbstd::containers::{BuilderSeal, ConsignmentExt};
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::*;
+    use rgbstd::txout::BlindSeal;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
     use super::*;
+    use crate::fixtures::{self, fixture_seal};
 
     #[test]
     fn schema_id() {
@@ -213,4 +289,65 @@ mod test {
         eprintln!("{:#04x?}", schema_id.to_byte_array());
         assert_eq!(CFA_SCHEMA_ID, schema_id);
     }
+
+    #[test]
+    fn deterministic_contract_id() {
+        let created_at = 1713261744;
+        let terms = ContractTerms {
+            text: RicardianContract::default(),
+            media: None,
+        };
+        let issued_supply = 999u64;
+        let seal: BlindSeal<Txid> = testing::BLINDER.genesis_seal(
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap(),
+            0,
+        );
+
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            CollectibleFungibleAsset::schema(),
+            CollectibleFungibleAsset::types(),
+            CollectibleFungibleAsset::scripts(),
+            ChainNet::BitcoinTestnet4,
+        )
+        .add_global_state("name", Name::from("NAME"))
+        .unwrap()
+        .add_global_state("precision", Precision::try_from(2).unwrap())
+        .unwrap()
+        .add_global_state("terms", terms)
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", BuilderSeal::from(seal), issued_supply)
+        .unwrap();
+
+        let contract = builder.issue_contract_raw(created_at).unwrap();
+
+        assert_eq!(
+            contract.contract_id().to_string(),
+            s!("rgb:nFFAz7DF-jNfCu0X-Gq1~rAj-_LBTVdy-U~PQb26-FT2frQQ")
+        );
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        for name in ["cfa", "cfa-full"] {
+            let wrapper = stock
+                .contract_wrapper::<CollectibleFungibleAsset>(fixtures::contract_id(name))
+                .unwrap();
+            assert_eq!(wrapper.name(), Name::from("Test asset"));
+            assert_eq!(wrapper.precision(), Precision::CentiMicro);
+            assert_eq!(wrapper.contract_terms(), testing::terms());
+            assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+            let allocations = wrapper
+                .allocations(&FilterIncludeAll)
+                .map(|a| (a.seal, a.state.value()))
+                .collect::<Vec<_>>();
+            assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+            let details = (name == "cfa-full").then(fixtures::details);
+            assert_eq!(wrapper.details(), details);
+        }
+    }
 }
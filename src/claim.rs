@@ -0,0 +1,368 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Claimable asset schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A fungible asset whose holders can hand units back to the issuer for buy-back or redemption.
+//! A `claim` transition moves units into a claim on the issuer, assigning any change back to the
+//! holder; the claim can then be spent only by a `collect` transition signed by the issuer key
+//! committed in the genesis, which turns the claimed units into units of the asset again. The
+//! validation scripts require the units to be preserved by both steps.
+//!
+//! Scripts can't see the seals of the outputs, so the issuer is expected to collect claims to
+//! outputs it controls; the claims themselves are meant to be assigned to an output agreed with
+//! the issuer, whose spending commits to the collection. The payment for the claimed units is
+//! settled outside of the contract.
+//!
+//! The `claim_transition` and `collect_transition` functions of the `workflow` module of
+//! `rgb-schemata-tools` build the transitions of the holder and of the issuer respectively.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, CLAIM_COLLECT, CLAIM_LOCK, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_PUBKEY, GS_TERMS, OS_ASSET, OS_CLAIM, TS_CLAIM, TS_COLLECT, TS_TRANSFER,
+};
+
+pub const CLAIM_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x35, 0x05, 0xad, 0x55, 0x8b, 0x77, 0x14, 0x7b, 0xea, 0x61, 0x92, 0x24, 0xaf, 0x9d, 0x23, 0x56,
+    0xe3, 0x94, 0xaf, 0x34, 0xab, 0x91, 0xf9, 0x46, 0x18, 0xb7, 0x07, 0x06, 0x45, 0x16, 0x36, 0x3c,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn claim_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    CLAIM_LOCK.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    CLAIM_COLLECT.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("ClaimableAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_CLAIM => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("issuerClaim"),
+                default_transition: TS_COLLECT,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_PUBKEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_CLAIM => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_CLAIM => Occurrences::Once
+                    },
+                    validator: Some(CLAIM_LOCK.lib_site())
+                },
+                name: fname!("claim"),
+            },
+            TS_COLLECT => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_CLAIM => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    // The issuer signature is checked after the claimed sum
+                    validator: Some(CLAIM_COLLECT.lib_site())
+                },
+                name: fname!("collect"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn claim_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, CLAIM_LOCK, CLAIM_COLLECT])
+}
+
+#[derive(Default)]
+pub struct ClaimableAsset;
+
+impl IssuerWrapper for ClaimableAsset {
+    type Wrapper<S: ContractStateRead> = ClaimWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(claim_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(claim_scripts).clone()
+    }
+}
+
+impl ClaimableAsset {
+    /// First revision of the claimable asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "ClaimableAsset",
+        schema_id: CLAIM_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset whose holders can hand units back to the issuer with a \
+                        claim, which only a collection signed by the issuer can spend.",
+    };
+}
+
+impl IssuerInfo for ClaimableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for ClaimableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct ClaimWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for ClaimWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for ClaimWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<ClaimableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> ClaimWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the claimable asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<ClaimableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the claimable asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the public key of the issuer, which signs the collections of claims.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the claims on the issuer, which only the issuer can collect.
+    pub fn claims<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_claims(filter))
+    }
+
+    pub fn try_claims<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_CLAIM, filter)?)
+    }
+
+    /// Returns the units of the asset claimed on the issuer by the claims passing the filter, like
+    /// the ones assigned to the outputs the issuer is yet to collect.
+    pub fn claimed_amount(&self, filter: impl AssignmentsFilter) -> Amount {
+        or_panic(self.try_claimed_amount(filter))
+    }
+
+    pub fn try_claimed_amount(&self, filter: impl AssignmentsFilter) -> Result<Amount, Error> {
+        Ok(self.try_claims(filter)?.map(|claim| claim.state).sum())
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for ClaimWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        ClaimWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        ClaimWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        ClaimWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        ClaimWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::*;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = claim_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(CLAIM_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<ClaimableAsset>(fixtures::contract_id("claim"))
+            .unwrap();
+        assert_eq!(wrapper.version(), ClaimableAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.pubkey(), fixtures::pubkey());
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        assert_eq!(wrapper.claims(&FilterIncludeAll).count(), 0);
+        assert_eq!(wrapper.claimed_amount(&FilterIncludeAll), Amount::ZERO);
+    }
+}
@@ -0,0 +1,374 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unique digital collection (UDC) schema.
+//!
+//! Extends UDA to a whole collection of unique tokens issued in a single genesis: the contract
+//! defines any number of tokens, up to 255, and each [`Allocation`] owns one of them. The genesis
+//! must declare the tokens sorted by strictly increasing index and allocate each of them exactly
+//! once, while transfers may move any set of tokens as long as each input token is assigned to
+//! exactly one output. Tokens are never split into fractions.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::{
+    Allocation, GlobalDetails, OwnedStateSchema, SchemaId, TokenIndex, TransitionDetails,
+};
+use strict_types::{StrictDeserialize, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, UDC_GENESIS, UDC_MATCH, UDC_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_ATTACH, GS_NOMINAL, GS_TERMS,
+    GS_TOKENS, OS_ASSET, TS_TRANSFER,
+};
+
+pub const COLLECTION_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x74, 0xce, 0xe4, 0x67, 0x51, 0x2b, 0x9a, 0x25, 0x3e, 0xe3, 0x21, 0xc9, 0x4e, 0x6e, 0x60, 0xe3,
+    0xc5, 0xa3, 0x4d, 0x74, 0xe3, 0xf5, 0x5d, 0x74, 0x4f, 0x42, 0xa0, 0x71, 0xb2, 0x95, 0x30, 0x8c,
+]);
+
+/// Maximum number of tokens in a collection, as the genesis script counts them in an 8-bit
+/// register.
+pub const COLLECTION_MAX_TOKENS: u16 = 255;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn collection_schema() -> Schema {
+    let types = standard_types();
+
+    UDC_GENESIS.verify(&[(0, INSTR_PUTA)]);
+    UDC_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDC_MATCH.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("UniqueDigitalCollection"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+            GS_ATTACH => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AttachmentType")),
+                name: fname!("attachmentTypes"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::OnceOrUpTo(COLLECTION_MAX_TOKENS),
+                GS_ATTACH => Occurrences::NoneOrOnce,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(UDC_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(UDC_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn collection_scripts() -> Scripts { SharedLibs::get().scripts(&[UDC_GENESIS, UDC_TRANSFER]) }
+
+#[derive(Default)]
+pub struct UniqueDigitalCollection;
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct CollectionWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl IssuerWrapper for UniqueDigitalCollection {
+    type Wrapper<S: ContractStateRead> = CollectionWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(collection_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(collection_scripts).clone()
+    }
+}
+
+impl UniqueDigitalCollection {
+    /// First revision of the UDC schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "UniqueDigitalCollection",
+        schema_id: COLLECTION_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Collection of unique tokens issued and allocated at genesis, and a \
+                        transfer transition moving any of them without splitting.",
+    };
+}
+
+impl IssuerInfo for UniqueDigitalCollection {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for UniqueDigitalCollection {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+impl<S: ContractStateRead> SchemaWrapper<S> for CollectionWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for CollectionWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<UniqueDigitalCollection, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> CollectionWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the UDC schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<UniqueDigitalCollection, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the UDC schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the tokens of the collection, sorted by their index.
+    pub fn tokens(&self) -> Vec<TokenData> { or_panic(self.try_tokens()) }
+
+    pub fn try_tokens(&self) -> Result<Vec<TokenData>, Error> {
+        let mut tokens =
+            globals::<_, TokenData>(&self.0, GS_TOKENS)?.collect::<Result<Vec<_>, _>>()?;
+        tokens.sort_by_key(|token| token.index);
+        Ok(tokens)
+    }
+
+    /// Returns the token of the collection with the given index, if there is one.
+    pub fn token(&self, index: TokenIndex) -> Option<TokenData> { or_panic(self.try_token(index)) }
+
+    pub fn try_token(&self, index: TokenIndex) -> Result<Option<TokenData>, Error> {
+        Ok(self
+            .try_tokens()?
+            .into_iter()
+            .find(|token| token.index == index))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Groups the allocations passing the filter by the index of the token they own.
+    ///
+    /// Tokens whose allocations are all filtered out are missing from the map.
+    pub fn allocations_by_token(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> BTreeMap<TokenIndex, Vec<DataAllocation>> {
+        or_panic(self.try_allocations_by_token(filter))
+    }
+
+    pub fn try_allocations_by_token(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> Result<BTreeMap<TokenIndex, Vec<DataAllocation>>, Error> {
+        let mut tokens = BTreeMap::<_, Vec<_>>::new();
+        for allocation in self.try_allocations(filter)? {
+            let index = Allocation::from_strict_serialized(allocation.state.clone().into())
+                .map_err(|_| Error::InvalidOwned(OS_ASSET))?
+                .token_index();
+            tokens.entry(index).or_default().push(allocation);
+        }
+        Ok(tokens)
+    }
+
+    /// Returns the allocations passing the filter which own the token with the given index.
+    pub fn token_allocations(
+        &self,
+        index: TokenIndex,
+        filter: impl AssignmentsFilter,
+    ) -> Vec<DataAllocation> {
+        or_panic(self.try_token_allocations(index, filter))
+    }
+
+    pub fn try_token_allocations(
+        &self,
+        index: TokenIndex,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Vec<DataAllocation>, Error> {
+        Ok(self
+            .try_allocations_by_token(filter)?
+            .remove(&index)
+            .unwrap_or_default())
+    }
+
+    /// Builds an invoice for receiving the token with the given index, failing if the collection
+    /// has no such token.
+    pub fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary, index))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+    ) -> Result<RgbInvoice, Error> {
+        if self.try_token(index)?.is_none() {
+            return Err(Error::UnknownToken(index));
+        }
+        allocation_invoice(&self.0, beneficiary, Allocation::with(index, 1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::Wrapper;
+    use rgbstd::contract::FilterIncludeAll;
+    use rgbstd::invoice::Precision;
+    use rgbstd::{Outpoint, OutputSeal};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = collection_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(COLLECTION_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<UniqueDigitalCollection>(fixtures::contract_id("collection"))
+            .unwrap();
+        assert_eq!(
+            wrapper.spec(),
+            AssetSpec::new("TEST", "Test collection", Precision::Indivisible)
+        );
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+
+        let indexes = fixtures::COLLECTION.map(TokenIndex::from_inner);
+        assert_eq!(
+            wrapper
+                .tokens()
+                .into_iter()
+                .map(|token| token.index)
+                .collect::<Vec<_>>(),
+            indexes
+        );
+        assert_eq!(wrapper.token(indexes[1]).unwrap().index, indexes[1]);
+        assert_eq!(wrapper.token(TokenIndex::from_inner(3)), None);
+
+        let by_token = wrapper.allocations_by_token(&FilterIncludeAll);
+        assert_eq!(by_token.keys().copied().collect::<Vec<_>>(), indexes);
+        for (vout, index) in (1..).zip(indexes) {
+            let allocations = wrapper.token_allocations(index, &FilterIncludeAll);
+            assert_eq!(allocations.len(), 1);
+            assert_eq!(allocations[0].seal, OutputSeal::new(Outpoint::new(testing::txid(), vout)));
+            assert_eq!(by_token[&index], allocations);
+        }
+        assert!(wrapper
+            .token_allocations(indexes[0], vec![Outpoint::new(testing::txid(), 2)])
+            .is_empty());
+    }
+}
@@ -0,0 +1,112 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by the schemata for their type system and AluVM libraries.
+
+// Helpers are unused when no schema feature is enabled
+#![cfg_attr(not(any_schema), allow(dead_code))]
+
+use std::sync::OnceLock;
+
+use aluvm::isa::{Instr, InstructionSet};
+use aluvm::library::{Lib, LibId, LibSeg};
+use amplify::confinement::SmallBlob;
+use rgbstd::persistence::MemContract;
+use rgbstd::stl::{rgb_contract_stl, StandardTypes};
+use rgbstd::vm::RgbIsa;
+
+/// Standard RGB contract types shared by all schemata.
+///
+/// Compiling the type library is expensive, so it is built once on first use and then borrowed
+/// by every schema constructor.
+pub(crate) fn standard_types() -> &'static StandardTypes {
+    static TYPES: OnceLock<StandardTypes> = OnceLock::new();
+    TYPES.get_or_init(|| StandardTypes::with(rgb_contract_stl()))
+}
+
+/// AluVM library embedded as precompiled byte code.
+///
+/// Each constant is generated from the `rgbasm!` source kept next to it, which unit tests
+/// reassemble to check both the byte code and the library id against the embedded values.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct PrecompiledLib {
+    pub code: &'static [u8],
+    pub data: &'static [u8],
+    pub id: [u8; 32],
+}
+
+impl PrecompiledLib {
+    pub fn id(self) -> LibId { LibId::from(self.id) }
+
+    pub fn to_lib(self) -> Lib {
+        Lib {
+            isae: Instr::<RgbIsa<MemContract>>::isa_ids(),
+            code: SmallBlob::from_checked(self.code.to_vec()),
+            data: SmallBlob::from_checked(self.data.to_vec()),
+            libs: LibSeg::default(),
+        }
+    }
+
+    /// Checks the constant against the library assembled from its `rgbasm!` source, printing
+    /// the up-to-date constant on mismatch.
+    #[cfg(test)]
+    pub fn verify(self, name: &str, assembled: Lib) {
+        fn bytes(data: &[u8]) -> String {
+            data.iter()
+                .map(|b| format!("0x{b:02x}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        let lib = self.to_lib();
+        assert!(
+            lib.code == assembled.code
+                && lib.data == assembled.data
+                && assembled.libs.is_empty()
+                && assembled.id() == self.id(),
+            "precompiled library {name} is outdated, replace it with:\n\npub(super) const {name}: \
+             PrecompiledLib = PrecompiledLib {{\n    code: &[{}],\n    data: &[{}],\n    id: \
+             [{}],\n}};\n",
+            bytes(assembled.code_segment()),
+            bytes(assembled.data_segment()),
+            bytes(assembled.id().as_slice()),
+        );
+        assert_eq!(lib.id(), self.id());
+    }
+}
+
+/// Asserts that the validation subroutine of `lib` starting at `offset` begins with the given
+/// instructions, provided as pairs of their opcode and their byte position relative to `offset`.
+///
+/// Schemas call this through `EntryPoint::verify` on every library offset they reference from a
+/// `LibSite`, so a change in the assembled code can't silently move a subroutine away from its
+/// entry point.
+pub(crate) fn verify_entry_point(lib: &Lib, offset: u16, opcodes: &[(u16, u8)]) {
+    let code = lib.code.as_ref();
+    for (pos, opcode) in opcodes {
+        let pos = (offset + pos) as usize;
+        assert_eq!(
+            code.get(pos),
+            Some(opcode),
+            "unexpected instruction at offset {pos} of library {}",
+            lib.id()
+        );
+    }
+}
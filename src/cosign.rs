@@ -0,0 +1,347 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Issuer side of the permissioned transfers of PFA contracts.
+//!
+//! Every PFA transfer must carry a signature of its id by the issuer key committed in the
+//! contract. [`PfaCosigner`] is meant to run in the service of the issuer receiving the transitions
+//! proposed by the payers: it checks them against the issuer policy and produces the signature
+//! the PFA validation script expects.
+
+use std::fmt::{self, Debug, Formatter};
+
+use amplify::{ByteArray, Bytes64};
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::ContractData;
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::secp256k1::{Message, Secp256k1, SecretKey, SignOnly};
+use rgbstd::{Amount, ContractId, Operation, Opout, Signature, Transition, TransitionType};
+
+use crate::reject::RejectList;
+use crate::{PfaWrapper, OS_ASSET, TS_TRANSFER};
+
+/// Reason for refusing to co-sign a transition.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum CosignError {
+    /// secret key doesn't match the issuer public key of contract {0}.
+    KeyMismatch(ContractId),
+
+    /// transition belongs to contract {actual} instead of {expected}.
+    ContractMismatch {
+        expected: ContractId,
+        actual: ContractId,
+    },
+
+    /// transition of type {0} is not a transfer.
+    NotTransfer(TransitionType),
+
+    /// transition spends the rejected output {0}.
+    Rejected(Opout),
+
+    /// transition moves {amount} while at most {limit} can be transferred at once.
+    AmountLimit { amount: Amount, limit: Amount },
+
+    /// transition refused by the issuer policy: {0}.
+    Policy(String),
+
+    /// {0}
+    #[from]
+    #[from(crate::WrongSchema)]
+    Contract(crate::Error),
+}
+
+/// Policy hook inspecting a proposed transition, returning the reason for refusing it.
+pub type CosignPolicy = Box<dyn Fn(&Transition) -> Result<(), String> + Send + Sync>;
+
+/// Issuer co-signing the transfers of a PFA contract which comply with its policy.
+///
+/// Before signing, the co-signer checks that the transition is a transfer of its contract, that it
+/// doesn't spend outputs of the reject list and that the moved amount, change included, doesn't
+/// exceed the limit; custom checks are added with [`PfaCosigner::policy`].
+pub struct PfaCosigner {
+    contract_id: ContractId,
+    key: SecretKey,
+    secp: Secp256k1<SignOnly>,
+    limit: Option<Amount>,
+    reject_list: RejectList,
+    policies: Vec<CosignPolicy>,
+}
+
+impl Debug for PfaCosigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PfaCosigner")
+            .field("contract_id", &self.contract_id)
+            .field("limit", &self.limit)
+            .field("reject_list", &self.reject_list)
+            .field("policies", &self.policies.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PfaCosigner {
+    /// Creates the co-signer of the contract, failing if the key is not the issuer one.
+    pub fn new<S: ContractStateRead>(
+        contract: &PfaWrapper<S>,
+        key: SecretKey,
+    ) -> Result<Self, CosignError> {
+        let secp = Secp256k1::signing_only();
+        let contract_id = contract.contract_id();
+        if CompressedPublicKey(key.public_key(&secp)) != contract.try_pubkey()? {
+            return Err(CosignError::KeyMismatch(contract_id));
+        }
+        Ok(Self {
+            contract_id,
+            key,
+            secp,
+            limit: None,
+            reject_list: RejectList::new(),
+            policies: vec![],
+        })
+    }
+
+    /// Creates the co-signer of the contract, see [`PfaCosigner::new`].
+    pub fn with<S: ContractStateRead>(
+        data: ContractData<S>,
+        key: SecretKey,
+    ) -> Result<Self, CosignError> {
+        Self::new(&PfaWrapper::try_with(data)?, key)
+    }
+
+    pub fn contract_id(&self) -> ContractId { self.contract_id }
+
+    /// Refuses transitions moving more than `limit`.
+    pub fn amount_limit(mut self, limit: Amount) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Refuses transitions spending outputs of the reject list.
+    pub fn reject_list(mut self, reject_list: RejectList) -> Self {
+        self.reject_list = reject_list;
+        self
+    }
+
+    /// Adds a custom policy check, run after the built-in ones.
+    pub fn policy(
+        mut self,
+        check: impl Fn(&Transition) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.policies.push(Box::new(check));
+        self
+    }
+
+    /// Checks the transition against the issuer policy.
+    pub fn check(&self, transition: &Transition) -> Result<(), CosignError> {
+        if transition.contract_id != self.contract_id {
+            return Err(CosignError::ContractMismatch {
+                expected: self.contract_id,
+                actual: transition.contract_id,
+            });
+        }
+        if transition.transition_type != TS_TRANSFER {
+            return Err(CosignError::NotTransfer(transition.transition_type));
+        }
+        if let Some(opout) = transition
+            .inputs
+            .iter()
+            .find(|opout| self.reject_list.is_rejected(**opout))
+        {
+            return Err(CosignError::Rejected(*opout));
+        }
+        if let Some(limit) = self.limit {
+            let amount = transition
+                .assignments
+                .get(&OS_ASSET)
+                .map(|assigns| {
+                    assigns
+                        .as_fungible()
+                        .iter()
+                        .map(|assign| Amount::from(*assign.as_revealed_state()))
+                        .fold(Amount::ZERO, |sum, amount| sum.saturating_add(amount))
+                })
+                .unwrap_or(Amount::ZERO);
+            if amount > limit {
+                return Err(CosignError::AmountLimit { amount, limit });
+            }
+        }
+        for check in &self.policies {
+            check(transition).map_err(CosignError::Policy)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the transition against the issuer policy and returns the issuer signature of it.
+    pub fn sign(&self, transition: &Transition) -> Result<Signature, CosignError> {
+        self.check(transition)?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(opid = %transition.id(), "co-signing PFA transition");
+        let msg = Message::from_digest(transition.id().to_byte_array());
+        let sig = self.secp.sign_ecdsa(&msg, &self.key).serialize_compact();
+        Ok(Signature::from(Bytes64::from_byte_array(sig)))
+    }
+
+    /// Signs the transition, see [`PfaCosigner::sign`], and attaches the signature to it.
+    pub fn cosign(&self, transition: &mut Transition) -> Result<(), CosignError> {
+        transition.signature = Some(self.sign(transition)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::ContractBuilder;
+    use rgbstd::persistence::Stock;
+    use rgbstd::secp256k1::SecretKey;
+    use rgbstd::{Amount, Operation, Opout};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::PermissionedFungibleAsset;
+
+    /// PFA asset allocated to `seal(1)`, whose issuer key can be rotated by the holder of
+    /// `seal(2)`.
+    fn pfa(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+        testing::asset::<PermissionedFungibleAsset>(issued, allocated)
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+            .add_rights("rotateKeyRight", testing::seal(2))
+            .unwrap()
+    }
+
+    fn contract() -> (testing::TestContract, Stock) {
+        let builder = pfa(1000, 1000, testing::issuer_pubkey());
+        let (stock, contract_id) = testing::stock_with(builder.clone());
+        let contract = testing::TestContract::issue::<PermissionedFungibleAsset>(builder);
+        assert_eq!(contract.genesis.contract_id(), contract_id);
+        (contract, stock)
+    }
+
+    fn cosigner(contract: &testing::TestContract, stock: &Stock, key: SecretKey) -> PfaCosigner {
+        try_cosigner(contract, stock, key).unwrap()
+    }
+
+    fn try_cosigner(
+        contract: &testing::TestContract,
+        stock: &Stock,
+        key: SecretKey,
+    ) -> Result<PfaCosigner, CosignError> {
+        let data = stock.contract_data(contract.genesis.contract_id()).unwrap();
+        PfaCosigner::with(data, key)
+    }
+
+    fn transfer<'c>(
+        contract: &'c testing::TestContract,
+        outputs: &[u64],
+    ) -> testing::TransitionCase<'c> {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, testing::amount(1000))
+            .with(|mut builder| {
+                for (vout, value) in outputs.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("assetOwner", testing::graph_seal(vout as u32), *value)
+                        .unwrap();
+                }
+                builder
+            })
+    }
+
+    #[test]
+    fn cosigned_transfer_validates() {
+        let (contract, stock) = contract();
+        let wrapper = stock
+            .contract_wrapper::<PermissionedFungibleAsset>(contract.genesis.contract_id())
+            .unwrap();
+        assert_eq!(wrapper.pubkey(), testing::issuer_pubkey());
+        let cosigner = PfaCosigner::new(&wrapper, testing::issuer_key()).unwrap();
+        assert_eq!(cosigner.contract_id(), contract.genesis.contract_id());
+
+        let case = transfer(&contract, &[600, 400]);
+        let mut transition = case.transition();
+        cosigner.cosign(&mut transition).unwrap();
+        assert_eq!(transition.signature, Some(testing::sign(&transition)));
+        case.validate_transition(&transition, true).unwrap();
+    }
+
+    #[test]
+    fn foreign_key() {
+        let (contract, stock) = contract();
+        let key = SecretKey::from_slice(&[0x43; 32]).unwrap();
+        assert_eq!(
+            try_cosigner(&contract, &stock, key).unwrap_err(),
+            CosignError::KeyMismatch(contract.genesis.contract_id())
+        );
+    }
+
+    #[test]
+    fn policies() {
+        let (contract, stock) = contract();
+        let transition = transfer(&contract, &[600, 400]).transition();
+        let input = Opout::new(contract.genesis.id(), OS_ASSET, 0);
+
+        let limited =
+            cosigner(&contract, &stock, testing::issuer_key()).amount_limit(Amount::from(999u64));
+        assert_eq!(limited.sign(&transition).unwrap_err(), CosignError::AmountLimit {
+            amount: Amount::from(1000u64),
+            limit: Amount::from(999u64)
+        });
+
+        let mut reject_list = RejectList::new();
+        reject_list.reject_opout(input);
+        let rejecting = cosigner(&contract, &stock, testing::issuer_key()).reject_list(reject_list);
+        assert_eq!(rejecting.sign(&transition).unwrap_err(), CosignError::Rejected(input));
+
+        let split_only = cosigner(&contract, &stock, testing::issuer_key())
+            .amount_limit(Amount::from(1000u64))
+            .policy(|transition| match transition.assignments[&OS_ASSET].len_u16() {
+                1 => Err("whole allocations can't be transferred".to_owned()),
+                _ => Ok(()),
+            });
+        split_only.check(&transition).unwrap();
+        assert_eq!(
+            split_only
+                .sign(&transfer(&contract, &[1000]).transition())
+                .unwrap_err(),
+            CosignError::Policy("whole allocations can't be transferred".to_owned())
+        );
+    }
+
+    #[test]
+    fn foreign_transition() {
+        let (contract, stock) = contract();
+        let other = testing::TestContract::issue::<PermissionedFungibleAsset>(pfa(
+            2000,
+            2000,
+            testing::issuer_pubkey(),
+        ));
+        let cosigner = cosigner(&contract, &stock, testing::issuer_key());
+        assert_eq!(
+            cosigner
+                .sign(&transfer(&other, &[1000]).transition())
+                .unwrap_err(),
+            CosignError::ContractMismatch {
+                expected: cosigner.contract_id(),
+                actual: other.genesis.contract_id()
+            }
+        );
+    }
+}
@@ -0,0 +1,533 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifiable credential schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A credential issued to a single holder, who can't transfer it. The genesis commits to the
+//! public key of the issuer and assigns the credential to the holder, and a revoke right to the
+//! issuer. Claims about the holder are recorded in the global state by `attest` transitions, which
+//! spend the credential and assign it back and must be signed by the issuer: the media type and
+//! data of each claim are thus attested by the issuer key for any verifier of the contract.
+//!
+//! The issuer revokes the credential by spending its revoke right in a `revoke` transition, which
+//! records the reason of the revocation. The validation scripts reject attestations made after it,
+//! so the claims of a revoked credential are those attested before its revocation.
+
+use std::io;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_PUTA, INSTR_RET};
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, IssuerWrapper, RightsAllocation, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{ContractTerms, Details, EmbeddedMedia};
+use rgbstd::validation::Scripts;
+use rgbstd::{SchemaId, TransitionDetails};
+use strict_types::encoding::TypedWrite;
+use strict_types::{StrictEncode, StrictSerialize, StrictType, TypeName, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, CREDENTIAL_ATTEST, CREDENTIAL_RECORD};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_CLAIMS, GS_PUBKEY,
+    GS_REVOCATION_REASON, GS_TERMS, OS_ASSET, OS_REVOKE, TS_ATTEST, TS_REVOKE,
+};
+
+pub const CREDENTIAL_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x21, 0x6e, 0x6c, 0x55, 0xca, 0xb0, 0xc7, 0x0f, 0xb1, 0x2c, 0xb5, 0x20, 0x89, 0xdf, 0x4c, 0xfc,
+    0x8f, 0x2f, 0x63, 0xa8, 0x22, 0x33, 0xf7, 0x2a, 0x04, 0x33, 0x21, 0x59, 0x4b, 0x7e, 0xf5, 0xc7,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn credential_schema() -> Schema {
+    let types = standard_types();
+
+    CREDENTIAL_RECORD.verify(&[(0, INSTR_RET)]);
+    CREDENTIAL_ATTEST.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("VerifiableCredential"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+            GS_CLAIMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.EmbeddedMedia")),
+                name: fname!("claims"),
+            },
+            GS_REVOCATION_REASON => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Details")),
+                name: fname!("revocationReason"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("assetOwner"),
+                default_transition: TS_ATTEST,
+            },
+            OS_REVOKE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("revokeRight"),
+                default_transition: TS_REVOKE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_TERMS => Occurrences::Once,
+                GS_PUBKEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::Once,
+                OS_REVOKE => Occurrences::NoneOrOnce,
+            },
+            validator: Some(CREDENTIAL_RECORD.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_ATTEST => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_CLAIMS => Occurrences::OnceOrMore
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    validator: Some(CREDENTIAL_ATTEST.lib_site())
+                },
+                name: fname!("attest"),
+            },
+            TS_REVOKE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_REVOCATION_REASON => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_REVOKE => Occurrences::Once
+                    },
+                    assignments: none!(),
+                    validator: Some(CREDENTIAL_RECORD.lib_site())
+                },
+                name: fname!("revoke"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn credential_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[CREDENTIAL_RECORD, CREDENTIAL_ATTEST])
+}
+
+#[derive(Default)]
+pub struct VerifiableCredential;
+
+impl IssuerWrapper for VerifiableCredential {
+    type Wrapper<S: ContractStateRead> = CredentialWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(credential_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(credential_scripts).clone()
+    }
+}
+
+impl VerifiableCredential {
+    /// First revision of the verifiable credential schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "VerifiableCredential",
+        schema_id: CREDENTIAL_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Non-transferable credential whose claims are attested by transitions \
+                        signed by the issuer, who can revoke it.",
+    };
+
+    /// Global state value recording `media` as a claim, as stored by the `claims` global state.
+    pub fn claim(media: EmbeddedMedia) -> impl StrictSerialize { Claim(media) }
+}
+
+/// Claim of the `claims` global state, encoded as the embedded media it wraps, which the standard
+/// library doesn't allow to serialize on its own.
+struct Claim(EmbeddedMedia);
+
+impl StrictType for Claim {
+    const STRICT_LIB_NAME: &'static str = EmbeddedMedia::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { EmbeddedMedia::strict_name() }
+}
+
+impl StrictEncode for Claim {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.0.strict_encode(writer)
+    }
+}
+
+impl StrictSerialize for Claim {}
+
+impl IssuerInfo for VerifiableCredential {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for VerifiableCredential {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct CredentialWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for CredentialWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for CredentialWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<VerifiableCredential, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> CredentialWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the verifiable credential schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<VerifiableCredential, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the verifiable credential schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the public key of the issuer, which signs the attestations.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    /// Returns the claims attested by the issuer, in the order they were attested.
+    pub fn claims(&self) -> Vec<EmbeddedMedia> { or_panic(self.try_claims()) }
+
+    pub fn try_claims(&self) -> Result<Vec<EmbeddedMedia>, Error> {
+        let mut claims = globals(&self.0, GS_CLAIMS)?.collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        claims.reverse();
+        Ok(claims)
+    }
+
+    /// Returns the reason given by the issuer for revoking the credential, or `None` if it isn't
+    /// revoked.
+    pub fn revocation_reason(&self) -> Option<Details> { or_panic(self.try_revocation_reason()) }
+
+    pub fn try_revocation_reason(&self) -> Result<Option<Details>, Error> {
+        globals(&self.0, GS_REVOCATION_REASON)?.next().transpose()
+    }
+
+    /// Checks whether the issuer has revoked the credential.
+    pub fn is_revoked(&self) -> bool { or_panic(self.try_is_revoked()) }
+
+    pub fn try_is_revoked(&self) -> Result<bool, Error> {
+        Ok(self.try_revocation_reason()?.is_some())
+    }
+
+    /// Returns the seal holding the credential.
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn revoke_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_revoke_rights(filter))
+    }
+
+    pub fn try_revoke_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REVOKE, filter)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use amplify::confinement::SmallBlob;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::{Details, EmbeddedMedia, MediaType};
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = credential_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(CREDENTIAL_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<VerifiableCredential>(fixtures::contract_id("credential"))
+            .unwrap();
+        assert_eq!(wrapper.version(), VerifiableCredential::V1);
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.pubkey(), fixtures::pubkey());
+        assert_eq!(wrapper.claims(), vec![]);
+        assert_eq!(wrapper.revocation_reason(), None);
+        assert!(!wrapper.is_revoked());
+
+        let holders = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(holders, vec![fixture_seal(1)]);
+        let rights = wrapper
+            .revoke_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Attesting and revoking a credential.
+    //
+    // The issuer attests two claims about the holder, each in its own mined witness TX, and then
+    // revokes the credential; the wrapper must report the claims in the order they were attested
+    // and the reason of the revocation. Claims attested after the revocation must be rejected
+    // by the validation of the history.
+
+    /// Verifiable credential held at `seal(1)` whose claims are signed by `pubkey`, with
+    /// the revoke right at `seal(2)`.
+    fn credential(pubkey: CompressedPublicKey) -> ContractBuilder {
+        testing::builder::<VerifiableCredential>()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+            .add_rights("assetOwner", testing::seal(1))
+            .unwrap()
+            .add_rights("revokeRight", testing::seal(2))
+            .unwrap()
+    }
+
+    fn claim(data: &[u8]) -> EmbeddedMedia {
+        EmbeddedMedia {
+            ty: MediaType::with("application/json"),
+            data: SmallBlob::try_from_iter(data.iter().copied()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn attest_and_revoke() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(credential(testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<VerifiableCredential>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.pubkey(), testing::issuer_pubkey());
+        assert!(wrapper.claims().is_empty());
+        assert!(!wrapper.is_revoked());
+
+        let claims = [claim(br#"{"degree":"BSc"}"#), claim(br#"{"degree":"MSc"}"#)];
+        let mut holder = (Opout::new(genesis_id, OS_ASSET, 0), Outpoint::new(testing::txid(), 1));
+        for (height, claim) in [101, 102].into_iter().zip(&claims) {
+            let mut transition = stock
+                .transition_builder(contract_id, "attest")
+                .unwrap()
+                .add_input(holder.0, AllocatedState::Void)
+                .unwrap()
+                .add_global_state("claims", VerifiableCredential::claim(claim.clone()))
+                .unwrap()
+                .add_rights("assetOwner", testing::graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            transition.signature = Some(testing::sign(&transition));
+            let opid = transition.id();
+
+            let fascia = testing::anchor(contract_id, transition, &[holder.1], 1);
+            let witness = fascia.seal_witness.public.tx().unwrap().clone();
+            let witness_id = fascia.witness_id();
+            resolver.add_witness(witness, testing::mined_at(height));
+            stock.consume_fascia(fascia, resolver.clone()).unwrap();
+            holder = (Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness_id, 1));
+        }
+
+        let wrapper = stock
+            .contract_wrapper::<VerifiableCredential>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.claims(), claims.to_vec());
+        assert!(!wrapper.is_revoked());
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(holder.1)), "{seals:?}");
+
+        let reason = Details::from_str("Degree withdrawn").unwrap();
+        let transition = stock
+            .transition_builder(contract_id, "revoke")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_REVOKE, 0), AllocatedState::Void)
+            .unwrap()
+            .add_global_state("revocationReason", reason.clone())
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let revoke_id = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 2)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        resolver.add_witness(witness, testing::mined_at(103));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        testing::validate_history::<VerifiableCredential>(&stock, &resolver, contract_id, &[
+            holder.0.op,
+            revoke_id,
+        ])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<VerifiableCredential>(contract_id)
+            .unwrap();
+        assert!(wrapper.is_revoked());
+        assert_eq!(wrapper.revocation_reason(), Some(reason));
+        // revocation leaves the claims attested before it in place
+        assert_eq!(wrapper.claims(), claims.to_vec());
+    }
+
+    #[test]
+    fn attest_after_revoke_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(credential(testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = stock
+            .transition_builder(contract_id, "revoke")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_REVOKE, 0), AllocatedState::Void)
+            .unwrap()
+            .add_global_state("revocationReason", Details::from_str("Expired").unwrap())
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let revoke_id = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 2)],
+            101,
+        );
+
+        let mut transition = stock
+            .transition_builder(contract_id, "attest")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_ASSET, 0), AllocatedState::Void)
+            .unwrap()
+            .add_global_state("claims", VerifiableCredential::claim(claim(b"{}")))
+            .unwrap()
+            .add_rights("assetOwner", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        transition.signature = Some(testing::sign(&transition));
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            102,
+        );
+
+        let err =
+            testing::validate_history::<VerifiableCredential>(&stock, &resolver, contract_id, &[
+                revoke_id, opid,
+            ])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::CredentialRevoked));
+    }
+}
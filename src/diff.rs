@@ -0,0 +1,296 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Changes of the state of a contract between two points in time.
+//!
+//! A [`ContractSnapshot`] captures the global state and the allocations of a contract, as seen
+//! through an assignment filter. Comparing two snapshots of the same contract produces a
+//! [`ContractDiff`] listing the allocations created and spent in between, the global state added
+//! and the resulting change of the allocated amounts, which explorers and accounting systems can
+//! consume without knowing the schema of the contract.
+//!
+//! The contract state never forgets allocations, so a snapshot only tells spent allocations apart
+//! if its filter selects the unspent ones, like the filter of a wallet does.
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, FungibleAllocation, RightsAllocation,
+};
+use rgbstd::persistence::{
+    ContractStateRead, IndexProvider, StashProvider, StateProvider, Stock, StockError,
+};
+use rgbstd::{
+    AssignmentType, ContractId, GlobalStateType, Opout, OwnedStateSchema, RevealedData, SchemaId,
+};
+
+use crate::Error;
+
+/// Global state and allocations of a contract at some point in time.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ContractSnapshot {
+    pub contract_id: ContractId,
+    pub schema_id: SchemaId,
+    /// Entries of each global state type, from the newest to the oldest one.
+    pub globals: BTreeMap<GlobalStateType, Vec<RevealedData>>,
+    pub fungible: BTreeMap<Opout, FungibleAllocation>,
+    pub data: BTreeMap<Opout, DataAllocation>,
+    pub rights: BTreeMap<Opout, RightsAllocation>,
+}
+
+impl ContractSnapshot {
+    /// Captures the global state of the contract and its allocations passing the filter.
+    pub fn capture<S: ContractStateRead>(
+        data: &ContractData<S>,
+        filter: impl AssignmentsFilter,
+    ) -> Self {
+        let schema = &data.schema;
+        let mut snapshot = ContractSnapshot {
+            contract_id: data.contract_id(),
+            schema_id: schema.schema_id(),
+            globals: bmap! {},
+            fungible: bmap! {},
+            data: bmap! {},
+            rights: bmap! {},
+        };
+        // State types are taken from the schema, so the accessors below never fail
+        for ty in schema.global_types.keys() {
+            let entries = data
+                .state
+                .global(*ty)
+                .into_iter()
+                .flatten()
+                .map(|entry| entry.borrow().data().clone())
+                .collect();
+            snapshot.globals.insert(*ty, entries);
+        }
+        for (ty, details) in &schema.owned_types {
+            match details.owned_state_schema {
+                OwnedStateSchema::Fungible(_) => snapshot.fungible.extend(
+                    data.fungible_raw(*ty, &filter)
+                        .into_iter()
+                        .flatten()
+                        .map(|a| (a.opout, a)),
+                ),
+                OwnedStateSchema::Structured(_) => snapshot.data.extend(
+                    data.data_raw(*ty, &filter)
+                        .into_iter()
+                        .flatten()
+                        .map(|a| (a.opout, a)),
+                ),
+                OwnedStateSchema::Declarative => snapshot.rights.extend(
+                    data.rights_raw(*ty, &filter)
+                        .into_iter()
+                        .flatten()
+                        .map(|a| (a.opout, a)),
+                ),
+            }
+        }
+        snapshot
+    }
+
+    /// Captures the contract with the given id from a stock, see [`ContractSnapshot::capture`].
+    pub fn from_stock<S: StashProvider, H: StateProvider, P: IndexProvider>(
+        stock: &Stock<S, H, P>,
+        contract_id: ContractId,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Self, StockError<S, H, P>> {
+        Ok(Self::capture(&stock.contract_data(contract_id)?, filter))
+    }
+
+    /// Sum of the fungible allocations of the given type.
+    pub fn supply(&self, ty: AssignmentType) -> u128 {
+        self.fungible
+            .values()
+            .filter(|a| a.opout.ty == ty)
+            .map(|a| a.state.value() as u128)
+            .sum()
+    }
+
+    /// Computes the changes from this snapshot to a later snapshot of the same contract.
+    pub fn diff(&self, later: &ContractSnapshot) -> Result<ContractDiff, Error> {
+        if later.contract_id != self.contract_id {
+            return Err(Error::ContractMismatch {
+                expected: self.contract_id,
+                actual: later.contract_id,
+            });
+        }
+
+        let mut globals = BTreeMap::new();
+        for (ty, entries) in &later.globals {
+            let mut known = self.globals.get(ty).cloned().unwrap_or_default();
+            let added = entries
+                .iter()
+                .filter(|entry| match known.iter().position(|k| k == *entry) {
+                    Some(pos) => {
+                        known.remove(pos);
+                        false
+                    }
+                    None => true,
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if !added.is_empty() {
+                globals.insert(*ty, added);
+            }
+        }
+
+        let supply = self
+            .fungible
+            .values()
+            .chain(later.fungible.values())
+            .map(|a| a.opout.ty)
+            .filter_map(|ty| {
+                let change = SupplyChange {
+                    before: self.supply(ty),
+                    after: later.supply(ty),
+                };
+                (change.before != change.after).then_some((ty, change))
+            })
+            .collect();
+
+        Ok(ContractDiff {
+            contract_id: self.contract_id,
+            created: Allocations::missing_from(self, later),
+            spent: Allocations::missing_from(later, self),
+            globals,
+            supply,
+        })
+    }
+}
+
+/// Allocations of a contract, grouped by the kind of their state.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Allocations {
+    pub fungible: Vec<FungibleAllocation>,
+    pub data: Vec<DataAllocation>,
+    pub rights: Vec<RightsAllocation>,
+}
+
+impl Allocations {
+    /// Allocations of `of` which are not present in `base`.
+    fn missing_from(base: &ContractSnapshot, of: &ContractSnapshot) -> Self {
+        fn missing<T: Clone>(base: &BTreeMap<Opout, T>, of: &BTreeMap<Opout, T>) -> Vec<T> {
+            of.iter()
+                .filter(|(opout, _)| !base.contains_key(opout))
+                .map(|(_, a)| a.clone())
+                .collect()
+        }
+        Allocations {
+            fungible: missing(&base.fungible, &of.fungible),
+            data: missing(&base.data, &of.data),
+            rights: missing(&base.rights, &of.rights),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fungible.is_empty() && self.data.is_empty() && self.rights.is_empty()
+    }
+}
+
+/// Change of the sum of the fungible allocations of a type.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SupplyChange {
+    pub before: u128,
+    pub after: u128,
+}
+
+impl SupplyChange {
+    /// Signed difference between the sums.
+    pub fn delta(self) -> i128 { self.after as i128 - self.before as i128 }
+}
+
+/// Changes of the state of a contract between two snapshots.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ContractDiff {
+    pub contract_id: ContractId,
+    /// Allocations present only in the later snapshot.
+    pub created: Allocations,
+    /// Allocations of the earlier snapshot missing from the later one, i.e. spent or no longer
+    /// passing the filter.
+    pub spent: Allocations,
+    /// Global state entries added in between, from the newest to the oldest one.
+    pub globals: BTreeMap<GlobalStateType, Vec<RevealedData>>,
+    /// Types of fungible allocations whose sum has changed.
+    pub supply: BTreeMap<AssignmentType, SupplyChange>,
+}
+
+impl ContractDiff {
+    /// Checks whether the contract state didn't change.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty()
+            && self.spent.is_empty()
+            && self.globals.is_empty()
+            && self.supply.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+
+    use super::*;
+    use crate::{fixtures, GS_NOMINAL};
+
+    fn snapshot(name: &str) -> ContractSnapshot {
+        let stock = fixtures::stock();
+        ContractSnapshot::from_stock(&stock, fixtures::contract_id(name), FilterIncludeAll).unwrap()
+    }
+
+    #[test]
+    fn unchanged() {
+        for name in fixtures::FIXTURES {
+            let snapshot = snapshot(name);
+            assert!(snapshot.diff(&snapshot).unwrap().is_empty(), "{name}");
+        }
+    }
+
+    #[test]
+    fn contract_mismatch() {
+        let nia = snapshot("nia");
+        let cfa = snapshot("cfa");
+        assert_eq!(nia.diff(&cfa).unwrap_err(), Error::ContractMismatch {
+            expected: nia.contract_id,
+            actual: cfa.contract_id
+        });
+    }
+
+    #[test]
+    fn changes() {
+        let before = snapshot("nia");
+        let mut after = before.clone();
+        let spec = before.globals[&GS_NOMINAL][0].clone();
+        after
+            .globals
+            .get_mut(&GS_NOMINAL)
+            .unwrap()
+            .insert(0, spec.clone());
+        let (opout, spent) = after.fungible.pop_first().unwrap();
+
+        let diff = before.diff(&after).unwrap();
+        assert!(diff.created.is_empty());
+        assert_eq!(diff.spent.fungible, vec![spent]);
+        assert_eq!(diff.globals, bmap! { GS_NOMINAL => vec![spec] });
+        let supply = diff.supply[&opout.ty];
+        assert_eq!(supply.delta(), -(spent.state.value() as i128));
+    }
+}
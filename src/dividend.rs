@@ -0,0 +1,520 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dividend-paying asset schema.
+//!
+//! A variant of the NIA schema for securities paying dividends or coupons, whose issuer keeps a
+//! distribute right allowing it to record, once per epoch, the amount it distributes to the
+//! holders in the `dividends` global state. The payment itself happens off the contract, in the
+//! currency chosen by the issuer.
+//!
+//! Each holder is entitled to the share of an epoch dividend proportional to the units it holds
+//! at the record date of the epoch: the issuer captures a [`ContractSnapshot`] of the allocations
+//! at that date, from which [`DividendWrapper::claimable`] computes the dividend claimable by each
+//! allocation, rounded down.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, Opout, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::diff::ContractSnapshot;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_DIVIDENDS,
+    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS, OS_ASSET, OS_DISTRIBUTE, TS_DISTRIBUTE, TS_TRANSFER,
+};
+
+pub const DIVIDEND_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x0e, 0xe7, 0x03, 0x50, 0xea, 0x0f, 0x7a, 0x57, 0x0a, 0x41, 0x77, 0x61, 0xb6, 0x10, 0x52, 0x0d,
+    0x6c, 0x04, 0xfb, 0x96, 0x5d, 0xbf, 0x1c, 0xcb, 0x00, 0x04, 0x74, 0xb0, 0x99, 0x90, 0xa3, 0x97,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn dividend_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("DividendAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_DIVIDENDS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("dividends"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_DISTRIBUTE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("distributeRight"),
+                default_transition: TS_DISTRIBUTE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_DISTRIBUTE => Occurrences::Once,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_DISTRIBUTE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_DIVIDENDS => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_DISTRIBUTE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_DISTRIBUTE => Occurrences::NoneOrOnce
+                    },
+                    validator: None
+                },
+                name: fname!("distribute"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn dividend_scripts() -> Scripts { SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER]) }
+
+#[derive(Default)]
+pub struct DividendAsset;
+
+impl IssuerWrapper for DividendAsset {
+    type Wrapper<S: ContractStateRead> = DividendWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(dividend_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(dividend_scripts).clone()
+    }
+}
+
+impl DividendAsset {
+    /// First revision of the dividend-paying asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "DividendAsset",
+        schema_id: DIVIDEND_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "NIA variant whose issuer records the dividends distributed to the holders \
+                        in each epoch.",
+    };
+}
+
+impl IssuerInfo for DividendAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for DividendAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct DividendWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for DividendWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for DividendWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<DividendAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> DividendWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the dividend-paying asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<DividendAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the dividend-paying asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the dividends distributed in each epoch, starting from the first one.
+    pub fn dividends(&self) -> Vec<Amount> { or_panic(self.try_dividends()) }
+
+    pub fn try_dividends(&self) -> Result<Vec<Amount>, Error> {
+        let mut dividends = globals(&self.0, GS_DIVIDENDS)?.collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        dividends.reverse();
+        Ok(dividends)
+    }
+
+    /// Returns the share of the dividend of `epoch` due to `holding` units of the asset, rounded
+    /// down.
+    pub fn dividend_share(&self, epoch: usize, holding: Amount) -> Amount {
+        or_panic(self.try_dividend_share(epoch, holding))
+    }
+
+    pub fn try_dividend_share(&self, epoch: usize, holding: Amount) -> Result<Amount, Error> {
+        let dividend = *self
+            .try_dividends()?
+            .get(epoch)
+            .ok_or(Error::UnknownEpoch(epoch))?;
+        let issued = self.try_total_issued_supply()?;
+        Ok(share(dividend, holding, issued))
+    }
+
+    /// Computes the dividend of `epoch` claimable by each allocation of the asset in `snapshot`,
+    /// which must capture the allocations at the record date of the epoch.
+    pub fn claimable(&self, snapshot: &ContractSnapshot, epoch: usize) -> BTreeMap<Opout, Amount> {
+        or_panic(self.try_claimable(snapshot, epoch))
+    }
+
+    pub fn try_claimable(
+        &self,
+        snapshot: &ContractSnapshot,
+        epoch: usize,
+    ) -> Result<BTreeMap<Opout, Amount>, Error> {
+        if snapshot.contract_id != self.0.contract_id() {
+            return Err(Error::ContractMismatch {
+                expected: self.0.contract_id(),
+                actual: snapshot.contract_id,
+            });
+        }
+        let dividend = *self
+            .try_dividends()?
+            .get(epoch)
+            .ok_or(Error::UnknownEpoch(epoch))?;
+        let issued = self.try_total_issued_supply()?;
+        Ok(snapshot
+            .fungible
+            .values()
+            .filter(|a| a.opout.ty == OS_ASSET)
+            .map(|a| (a.opout, share(dividend, Amount::from(a.state.value()), issued)))
+            .collect())
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn distribute_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_distribute_rights(filter))
+    }
+
+    pub fn try_distribute_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_DISTRIBUTE, filter)?)
+    }
+}
+
+/// Share of `dividend` due to `holding` out of `issued` units, rounded down.
+fn share(dividend: Amount, holding: Amount, issued: Amount) -> Amount {
+    if issued == Amount::ZERO {
+        return Amount::ZERO;
+    }
+    // The share never exceeds the dividend, since the holding never exceeds the issued supply
+    let share = dividend.value() as u128 * holding.value() as u128 / issued.value() as u128;
+    Amount::from(share.min(dividend.value() as u128) as u64)
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for DividendWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        DividendWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        DividendWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        DividendWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        DividendWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = dividend_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(DIVIDEND_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn share_rounding() {
+        let amount = Amount::from;
+        assert_eq!(share(amount(1000u64), amount(250u64), amount(1000u64)), amount(250u64));
+        assert_eq!(share(amount(100u64), amount(1u64), amount(3u64)), amount(33u64));
+        assert_eq!(share(amount(u64::MAX), amount(u64::MAX), amount(u64::MAX)), amount(u64::MAX));
+        assert_eq!(share(amount(100u64), amount(1u64), Amount::ZERO), Amount::ZERO);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<DividendAsset>(fixtures::contract_id("dividend"))
+            .unwrap();
+        assert_eq!(wrapper.version(), DividendAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert!(wrapper.dividends().is_empty());
+        assert_eq!(wrapper.try_dividend_share(0, Amount::from(1u64)), Err(Error::UnknownEpoch(0)));
+
+        let snapshot = wrapper.snapshot(&FilterIncludeAll);
+        assert_eq!(wrapper.try_claimable(&snapshot, 0), Err(Error::UnknownEpoch(0)));
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .distribute_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Dividends of a dividend-paying asset.
+    //
+    // A holder splits the supply between two UTXOs, which the issuer snapshots at the record date
+    // before distributing the dividends of two epochs. Each allocation of the snapshot can claim
+    // its pro-rata share of each epoch dividend, rounded down.
+
+    /// Dividend-paying asset allocated to `seal(1)`, with the distribute right at
+    /// `seal(2)`.
+    fn dividend(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::asset::<DividendAsset>(issued, allocated)
+            .add_rights("distributeRight", testing::seal(2))
+            .unwrap()
+    }
+
+    #[test]
+    fn distribute_and_claim() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(dividend(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 2);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 250u64)
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(2), 750u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let transfer_id = transition.id();
+        let witness_id = consume(&mut stock, transition, &[Outpoint::new(testing::txid(), 1)], 100);
+
+        // the record date of both epochs
+        let wrapper = stock
+            .contract_wrapper::<DividendAsset>(contract_id)
+            .unwrap();
+        let holders = [1, 2].map(|vout| Outpoint::new(witness_id, vout)).to_vec();
+        let snapshot = wrapper.snapshot(holders);
+
+        let mut right = Opout::new(genesis_id, OS_DISTRIBUTE, 0);
+        let mut prevout = Outpoint::new(testing::txid(), 2);
+        for (dividend, height) in [(10_000u64, 101), (3, 102)] {
+            let transition = stock
+                .transition_builder(contract_id, "distribute")
+                .unwrap()
+                .add_input(right, AllocatedState::Void)
+                .unwrap()
+                .add_global_state("dividends", Amount::from(dividend))
+                .unwrap()
+                .add_rights("distributeRight", testing::graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            right = Opout::new(transition.id(), OS_DISTRIBUTE, 0);
+            let distribute_id = consume(&mut stock, transition, &[prevout], height);
+            prevout = Outpoint::new(distribute_id, 1);
+        }
+
+        testing::validate_history::<DividendAsset>(&stock, &resolver, contract_id, &[
+            transfer_id,
+            right.op,
+        ])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<DividendAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.dividends(), vec![Amount::from(10_000u64), Amount::from(3u64)]);
+        let rights = wrapper
+            .distribute_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(rights.contains(&OutputSeal::new(prevout)), "{rights:?}");
+
+        let small = Opout::new(transfer_id, OS_ASSET, 0);
+        let large = Opout::new(transfer_id, OS_ASSET, 1);
+        assert_eq!(
+            wrapper.claimable(&snapshot, 0),
+            [(small, Amount::from(2500u64)), (large, Amount::from(7500u64))].into()
+        );
+        // 0.75 and 2.25 units are rounded down
+        assert_eq!(
+            wrapper.claimable(&snapshot, 1),
+            [(small, Amount::ZERO), (large, Amount::from(2u64))].into()
+        );
+        assert_eq!(wrapper.dividend_share(1, Amount::from(1000u64)), Amount::from(3u64));
+        assert_eq!(wrapper.try_claimable(&snapshot, 2), Err(Error::UnknownEpoch(2)));
+    }
+}
@@ -0,0 +1,168 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolvers for testing and for use cases where witness TXs are never needed.
+
+use std::collections::HashMap;
+
+use rgbstd::bitcoin::Transaction as Tx;
+use rgbstd::validation::{ResolveWitness, WitnessOrdProvider, WitnessResolverError, WitnessStatus};
+use rgbstd::vm::WitnessOrd;
+use rgbstd::{ChainNet, Txid};
+
+/// Resolver which must never be called, usable only when importing data which doesn't require
+/// witness resolution (e.g. a freshly issued contract). Use [`MockResolver`] when witnesses need to
+/// be resolved.
+pub struct NoResolver;
+
+impl ResolveWitness for NoResolver {
+    fn resolve_witness(&self, _: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        unreachable!()
+    }
+
+    fn check_chain_net(&self, _: ChainNet) -> Result<(), WitnessResolverError> { unreachable!() }
+}
+
+/// Resolver returning preloaded witness TXs, for tests and examples exercising transfers.
+///
+/// TXs which have not been added to the resolver are reported as
+/// [`WitnessStatus::Unresolved`], while asking for their [`WitnessOrd`] is an error. If a
+/// [`ChainNet`] is set, any other chain-network pair is rejected, otherwise all of them are
+/// accepted.
+#[derive(Clone, Debug, Default)]
+pub struct MockResolver {
+    chain_net: Option<ChainNet>,
+    witnesses: HashMap<Txid, (Tx, WitnessOrd)>,
+}
+
+impl MockResolver {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn with_chain_net(chain_net: ChainNet) -> Self {
+        Self {
+            chain_net: Some(chain_net),
+            witnesses: none!(),
+        }
+    }
+
+    /// Add a witness TX with the given ordinal, replacing any previous entry for the same TX.
+    pub fn add_witness(&mut self, tx: Tx, ord: WitnessOrd) {
+        self.witnesses.insert(tx.compute_txid(), (tx, ord));
+    }
+
+    /// Builder-style version of [`MockResolver::add_witness`].
+    pub fn with_witness(mut self, tx: Tx, ord: WitnessOrd) -> Self {
+        self.add_witness(tx, ord);
+        self
+    }
+
+    /// Change the ordinal of an already known witness TX (e.g. to simulate its mining or a
+    /// reorg), returning `false` if the TX is unknown.
+    pub fn set_witness_ord(&mut self, txid: Txid, ord: WitnessOrd) -> bool {
+        match self.witnesses.get_mut(&txid) {
+            Some((_, old)) => {
+                *old = ord;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a witness TX, so it gets reported as unresolved.
+    pub fn remove_witness(&mut self, txid: Txid) -> Option<(Tx, WitnessOrd)> {
+        self.witnesses.remove(&txid)
+    }
+}
+
+impl ResolveWitness for MockResolver {
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        Ok(self
+            .witnesses
+            .get(&witness_id)
+            .map(|(tx, ord)| WitnessStatus::Resolved(tx.clone(), *ord))
+            .unwrap_or(WitnessStatus::Unresolved))
+    }
+
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        match self.chain_net {
+            Some(expected) if expected != chain_net => Err(WitnessResolverError::WrongChainNet),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl WitnessOrdProvider for MockResolver {
+    fn witness_ord(&self, witness_id: Txid) -> Result<WitnessOrd, WitnessResolverError> {
+        self.witnesses
+            .get(&witness_id)
+            .map(|(_, ord)| *ord)
+            .ok_or_else(|| {
+                WitnessResolverError::ResolverIssue(Some(witness_id), s!("unknown witness"))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroU32;
+
+    use rgbstd::bitcoin::absolute::LockTime;
+    use rgbstd::bitcoin::transaction::Version;
+    use rgbstd::vm::WitnessPos;
+
+    use super::*;
+
+    #[test]
+    fn mock_resolver() {
+        let tx = Tx {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let txid = tx.compute_txid();
+        let mut resolver = MockResolver::with_chain_net(ChainNet::BitcoinRegtest)
+            .with_witness(tx.clone(), WitnessOrd::Tentative);
+
+        assert_eq!(
+            resolver.resolve_witness(txid).unwrap(),
+            WitnessStatus::Resolved(tx.clone(), WitnessOrd::Tentative)
+        );
+
+        let pos = WitnessPos::bitcoin(NonZeroU32::new(100).unwrap(), 1713261744).unwrap();
+        assert!(resolver.set_witness_ord(txid, WitnessOrd::Mined(pos)));
+        assert_eq!(resolver.resolve_witness(txid).unwrap().witness_ord(), WitnessOrd::Mined(pos));
+        assert_eq!(resolver.witness_ord(txid).unwrap(), WitnessOrd::Mined(pos));
+
+        resolver.remove_witness(txid);
+        assert_eq!(resolver.resolve_witness(txid).unwrap(), WitnessStatus::Unresolved);
+        assert!(resolver.witness_ord(txid).is_err());
+        assert!(!resolver.set_witness_ord(txid, WitnessOrd::Tentative));
+
+        assert!(resolver.check_chain_net(ChainNet::BitcoinRegtest).is_ok());
+        assert_eq!(
+            resolver
+                .check_chain_net(ChainNet::BitcoinMainnet)
+                .unwrap_err(),
+            WitnessResolverError::WrongChainNet
+        );
+        assert!(MockResolver::new()
+            .check_chain_net(ChainNet::BitcoinMainnet)
+            .is_ok());
+    }
+}
@@ -0,0 +1,524 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Limited edition schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A series of unique tokens minted over time up to a cap committed by the genesis. The genesis
+//! doesn't issue any token, but assigns the issuer a mint right; each `mint` transition spends it
+//! to create a new edition, recording its token data in the global state, and may assign it
+//! again. Editions are numbered from 1 in the order they are minted, and the validation scripts
+//! reject a mint whose edition number exceeds the cap. Editions are transferred like the tokens of
+//! a UDC.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_PUTA, INSTR_RET};
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, RightsAllocation, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::{
+    Allocation, Amount, GlobalDetails, OwnedStateSchema, SchemaId, TokenIndex, TransitionDetails,
+};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, EDITION_GENESIS, EDITION_MINT, UDC_MATCH, UDC_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_MAX_EDITIONS, GS_NOMINAL, GS_TERMS,
+    GS_TOKENS, OS_ASSET, OS_MINT, TS_MINT, TS_TRANSFER,
+};
+
+pub const EDITION_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xa0, 0xe8, 0x3b, 0x74, 0xb9, 0x2c, 0xdd, 0x59, 0xcf, 0x22, 0x33, 0xc9, 0x7f, 0x9e, 0xcc, 0x87,
+    0xcf, 0xc9, 0x4a, 0xc3, 0x99, 0x56, 0xeb, 0xf0, 0xb8, 0xa6, 0x0e, 0xf6, 0x88, 0x97, 0xbc, 0x63,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn edition_schema() -> Schema {
+    let types = standard_types();
+
+    UDC_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDC_MATCH.verify(&[(0, INSTR_PUTA)]);
+    EDITION_GENESIS.verify(&[(0, INSTR_RET)]);
+    EDITION_MINT.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("LimitedEditionAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_MAX_EDITIONS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("maxEditions"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_MINT => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("mintRight"),
+                default_transition: TS_MINT,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_MAX_EDITIONS => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_MINT => Occurrences::Once,
+            },
+            validator: Some(EDITION_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(UDC_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            },
+            TS_MINT => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_TOKENS => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_MINT => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once,
+                        OS_MINT => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(EDITION_MINT.lib_site())
+                },
+                name: fname!("mint"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn edition_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[EDITION_GENESIS, UDC_TRANSFER, EDITION_MINT])
+}
+
+#[derive(Default)]
+pub struct LimitedEditionAsset;
+
+impl IssuerWrapper for LimitedEditionAsset {
+    type Wrapper<S: ContractStateRead> = EditionWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(edition_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(edition_scripts).clone()
+    }
+}
+
+impl LimitedEditionAsset {
+    /// First revision of the limited edition schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "LimitedEditionAsset",
+        schema_id: EDITION_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Series of unique tokens minted by the holder of a mint right, numbered in \
+                        order of minting up to the cap committed by the genesis.",
+    };
+}
+
+impl IssuerInfo for LimitedEditionAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for LimitedEditionAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct EditionWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for EditionWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for EditionWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<LimitedEditionAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> EditionWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the limited edition schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<LimitedEditionAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the limited edition schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the maximum number of editions of the series.
+    pub fn max_editions(&self) -> u32 { or_panic(self.try_max_editions()) }
+
+    pub fn try_max_editions(&self) -> Result<u32, Error> {
+        let max = global::<_, Amount>(&self.0, GS_MAX_EDITIONS)?;
+        // Edition numbers are token indexes, so a larger cap can never be reached
+        Ok(u32::try_from(max.value()).unwrap_or(u32::MAX))
+    }
+
+    /// Returns the editions minted so far, sorted by their number.
+    pub fn editions(&self) -> Vec<TokenData> { or_panic(self.try_editions()) }
+
+    pub fn try_editions(&self) -> Result<Vec<TokenData>, Error> {
+        let mut editions =
+            globals::<_, TokenData>(&self.0, GS_TOKENS)?.collect::<Result<Vec<_>, _>>()?;
+        editions.sort_by_key(|edition| edition.index);
+        Ok(editions)
+    }
+
+    /// Returns the number of editions minted so far.
+    pub fn minted_editions(&self) -> u32 { or_panic(self.try_minted_editions()) }
+
+    pub fn try_minted_editions(&self) -> Result<u32, Error> {
+        let minted = globals::<_, TokenData>(&self.0, GS_TOKENS)?.count();
+        u32::try_from(minted).map_err(|_| Error::InvalidGlobal(GS_TOKENS))
+    }
+
+    /// Returns the number of editions which can still be minted.
+    pub fn remaining_editions(&self) -> u32 { or_panic(self.try_remaining_editions()) }
+
+    pub fn try_remaining_editions(&self) -> Result<u32, Error> {
+        Ok(self
+            .try_max_editions()?
+            .saturating_sub(self.try_minted_editions()?))
+    }
+
+    /// Returns the token index the next mint must assign to its edition, or `None` if the series
+    /// is sold out.
+    pub fn next_edition(&self) -> Option<TokenIndex> { or_panic(self.try_next_edition()) }
+
+    pub fn try_next_edition(&self) -> Result<Option<TokenIndex>, Error> {
+        if self.try_remaining_editions()? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(TokenIndex::from(self.try_minted_editions()? + 1)))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn mint_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_mint_rights(filter))
+    }
+
+    pub fn try_mint_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_MINT, filter)?)
+    }
+
+    /// Builds an invoice for receiving the edition with the given index, failing if it isn't
+    /// minted yet.
+    pub fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary, index))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+    ) -> Result<RgbInvoice, Error> {
+        if !self
+            .try_editions()?
+            .iter()
+            .any(|edition| edition.index == index)
+        {
+            return Err(Error::UnknownToken(index));
+        }
+        allocation_invoice(&self.0, beneficiary, Allocation::with(index, 1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::{AllocatedState, ContractBuilder, FilterIncludeAll};
+    use rgbstd::invoice::Precision;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::TokenData;
+    use rgbstd::{
+        Allocation, ContractId, Operation, Opout, Outpoint, OutputSeal, TokenIndex, Transition,
+    };
+    use schemata_tools::testing;
+    use schemata_tools::testing::{seal, CHAIN_NET};
+
+    use super::*;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = edition_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(EDITION_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<LimitedEditionAsset>(fixtures::contract_id("edition"))
+            .unwrap();
+        assert_eq!(wrapper.version(), LimitedEditionAsset::V1);
+        assert_eq!(wrapper.spec(), AssetSpec::new("TEST", "Test editions", Precision::Indivisible));
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.max_editions(), fixtures::MAX_EDITIONS);
+        assert_eq!(wrapper.editions(), vec![]);
+        assert_eq!(wrapper.minted_editions(), 0);
+        assert_eq!(wrapper.remaining_editions(), fixtures::MAX_EDITIONS);
+        assert_eq!(wrapper.next_edition(), Some(TokenIndex::from(1)));
+        assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 0);
+        assert_eq!(wrapper.mint_rights(&FilterIncludeAll).count(), 1);
+        let beneficiary = XChainNet::with(CHAIN_NET, Beneficiary::from(seal(7).to_secret_seal()));
+        assert_eq!(
+            wrapper
+                .try_invoice(beneficiary, TokenIndex::from(1))
+                .unwrap_err(),
+            Error::UnknownToken(TokenIndex::from(1))
+        );
+    }
+
+    // Minting a limited edition series up to its cap.
+    //
+    // The issuer mints both editions of a two-edition series, each in its own mined witness TX,
+    // passing the mint right along. The wrapper must then report the series as sold out, while the
+    // issuer still owns the mint right. A mint past the cap must be rejected by the validation of
+    // the history.
+
+    /// Limited edition series of up to `max_editions` editions, with the mint right at
+    /// `seal(1)`.
+    fn edition(max_editions: u64) -> ContractBuilder {
+        testing::builder::<LimitedEditionAsset>()
+            .add_global_state(
+                "spec",
+                AssetSpec::new("TEST", "Test editions", Precision::Indivisible),
+            )
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("maxEditions", Amount::from(max_editions))
+            .unwrap()
+            .add_rights("mintRight", testing::seal(1))
+            .unwrap()
+    }
+
+    fn mint(stock: &Stock, contract_id: ContractId, right: Opout, edition: u32) -> Transition {
+        let index = TokenIndex::from(edition);
+        stock
+            .transition_builder(contract_id, "mint")
+            .unwrap()
+            .add_input(right, AllocatedState::Void)
+            .unwrap()
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", testing::graph_seal(2), Allocation::with(index, 1))
+            .unwrap()
+            .add_rights("mintRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap()
+    }
+
+    #[test]
+    fn mint_up_to_cap() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(edition(2));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut right = (Opout::new(genesis_id, OS_MINT, 0), Outpoint::new(testing::txid(), 1));
+        for edition in 1..=2 {
+            let wrapper = stock
+                .contract_wrapper::<LimitedEditionAsset>(contract_id)
+                .unwrap();
+            assert_eq!(wrapper.next_edition(), Some(TokenIndex::from(edition)));
+
+            let transition = mint(&stock, contract_id, right.0, edition);
+            let opid = transition.id();
+            let fascia = testing::anchor(contract_id, transition, &[right.1], 1);
+            let witness = fascia.seal_witness.public.tx().unwrap().clone();
+            let witness_id = fascia.witness_id();
+            let height = 100 + edition;
+            resolver.add_witness(witness, testing::mined_at(height));
+            stock.consume_fascia(fascia, resolver.clone()).unwrap();
+            right = (Opout::new(opid, OS_MINT, 0), Outpoint::new(witness_id, 1));
+        }
+
+        testing::validate_history::<LimitedEditionAsset>(&stock, &resolver, contract_id, &[right
+            .0
+            .op])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<LimitedEditionAsset>(contract_id)
+            .unwrap();
+        assert_eq!(
+            wrapper
+                .editions()
+                .into_iter()
+                .map(|edition| edition.index)
+                .collect::<Vec<_>>(),
+            vec![TokenIndex::from(1), TokenIndex::from(2)]
+        );
+        assert_eq!(wrapper.minted_editions(), 2);
+        assert_eq!(wrapper.remaining_editions(), 0);
+        assert_eq!(wrapper.next_edition(), None);
+        let seals = wrapper
+            .mint_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(right.1)), "{seals:?}");
+    }
+
+    #[test]
+    fn mint_over_cap_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(edition(1));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = mint(&stock, contract_id, Opout::new(genesis_id, OS_MINT, 0), 1);
+        let right = Opout::new(transition.id(), OS_MINT, 0);
+        let witness_id = testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            101,
+        );
+        let transition = mint(&stock, contract_id, right, 2);
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(witness_id, 1)],
+            102,
+        );
+
+        let err =
+            testing::validate_history::<LimitedEditionAsset>(&stock, &resolver, contract_id, &[
+                opid,
+            ])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::EditionCapExceeded));
+    }
+}
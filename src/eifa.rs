@@ -0,0 +1,675 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Epoch inflatable fungible asset (EIFA) schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the IFA schema whose inflation is also rate limited: the genesis commits to an
+//! epoch length in blocks and to the epoch cap, the maximum supply the inflations mined within a
+//! single epoch may issue, next to the maximum supply. Epochs are counted from block height zero,
+//! so that epoch `n` spans the heights from `n * epochLength` to `(n + 1) * epochLength - 1`.
+//!
+//! Each inflation commits to the lock time of its witness transaction, and belongs to the epoch
+//! of the first height the witness can be mined at, i.e. the lock time plus one. Next to the
+//! inflation rights, the owner of the inflation holds the epoch issuance state, recording the
+//! epoch of the last inflation and the supply issued within it up to that inflation. Each
+//! inflation spends it and assigns it again, adding up its issued supply within the same epoch or
+//! starting over in a later one, and the scripts reject the inflations bringing the supply issued
+//! within their epoch over the cap or belonging to an earlier epoch than the last one. Transfers
+//! can't move the epoch issuance state, since the IFA transfer validation they share doesn't check
+//! it.
+//!
+//! The lock time of the witness itself is not checked, see [`crate::height`]: witnesses mined
+//! after the epoch of their inflation may still bring the supply issued within the epoch they are
+//! mined in over the cap, and the `audit` module of `rgb-schemata-tools` reports the epochs in
+//! which they do.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use amplify::Wrapper;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, FungibleAllocation, IssuerWrapper,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Allocation, Amount, GlobalDetails, MetaDetails, SchemaId, TransitionDetails};
+use strict_types::{StrictDeserialize, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, EIFA_GENESIS, EIFA_INFLATION, IFA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_EPOCH_CAP,
+    GS_EPOCH_LENGTH, GS_ISSUED_SUPPLY, GS_LOCK_TIME, GS_MAX_SUPPLY, GS_NOMINAL, GS_TERMS,
+    MS_ALLOWED_INFLATION, OS_ASSET, OS_EPOCH_ISSUANCE, OS_INFLATION, TS_INFLATION, TS_TRANSFER,
+};
+
+pub const EIFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x38, 0xb8, 0xe9, 0xd3, 0x79, 0x8a, 0x98, 0xd2, 0xaf, 0xa4, 0x44, 0x7a, 0x63, 0x36, 0x10, 0x71,
+    0x1b, 0xe3, 0x92, 0x49, 0x37, 0x1c, 0xbf, 0x43, 0x2f, 0xf0, 0xf4, 0x5f, 0xe7, 0x75, 0xdd, 0xcf,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn eifa_schema() -> Schema {
+    let types = standard_types();
+
+    EIFA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    IFA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS), (8, INSTR_SVS)]);
+    EIFA_INFLATION.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("EpochInflatableAsset"),
+        meta_types: tiny_bmap! {
+            MS_ALLOWED_INFLATION => MetaDetails {
+                sem_id: types.get("RGBContract.Amount"),
+                name: fname!("allowedInflation"),
+            }
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_MAX_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("maxSupply"),
+            },
+            GS_EPOCH_LENGTH => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("epochLength"),
+            },
+            GS_EPOCH_CAP => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("epochCap"),
+            },
+            GS_LOCK_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("lockTime"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_INFLATION => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("inflationAllowance"),
+                default_transition: TS_TRANSFER
+            },
+            // The epoch of the last inflation is kept as the token index and the supply issued
+            // within it as the fraction
+            OS_EPOCH_ISSUANCE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("epochIssuance"),
+                default_transition: TS_INFLATION,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_MAX_SUPPLY => Occurrences::Once,
+                GS_EPOCH_LENGTH => Occurrences::Once,
+                GS_EPOCH_CAP => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_INFLATION => Occurrences::NoneOrMore,
+                OS_EPOCH_ISSUANCE => Occurrences::Once,
+            },
+            validator: Some(EIFA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    validator: Some(IFA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_INFLATION => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: tiny_bset![MS_ALLOWED_INFLATION],
+                    globals: tiny_bmap! {
+                        GS_ISSUED_SUPPLY => Occurrences::Once,
+                        GS_LOCK_TIME => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_INFLATION => Occurrences::OnceOrMore,
+                        OS_EPOCH_ISSUANCE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore,
+                        OS_EPOCH_ISSUANCE => Occurrences::Once
+                    },
+                    validator: Some(EIFA_INFLATION.lib_site())
+                },
+                name: fname!("inflate"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn eifa_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[EIFA_GENESIS, IFA_TRANSFER, EIFA_INFLATION])
+}
+
+#[derive(Default)]
+pub struct EpochInflatableAsset;
+
+impl IssuerWrapper for EpochInflatableAsset {
+    type Wrapper<S: ContractStateRead> = EifaWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(eifa_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(eifa_scripts).clone()
+    }
+}
+
+impl EpochInflatableAsset {
+    /// First revision of the EIFA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "EpochInflatableAsset",
+        schema_id: EIFA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset supporting inflation up to a maximum supply, with each \
+                        inflation limited by an epoch cap committed in the global state, which \
+                        the inflations of an epoch of blocks should not exceed altogether.",
+    };
+}
+
+impl IssuerInfo for EpochInflatableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for EpochInflatableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct EifaWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for EifaWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for EifaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<EpochInflatableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> EifaWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the EIFA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<EpochInflatableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the EIFA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    pub fn max_supply(&self) -> Amount { or_panic(self.try_max_supply()) }
+
+    pub fn try_max_supply(&self) -> Result<Amount, Error> { global(&self.0, GS_MAX_SUPPLY) }
+
+    /// Returns the number of blocks of an epoch.
+    pub fn epoch_length(&self) -> u32 { or_panic(self.try_epoch_length()) }
+
+    pub fn try_epoch_length(&self) -> Result<u32, Error> {
+        let length = global::<_, Amount>(&self.0, GS_EPOCH_LENGTH)?;
+        u32::try_from(length.value())
+            .ok()
+            .filter(|length| *length > 0)
+            .ok_or(Error::InvalidGlobal(GS_EPOCH_LENGTH))
+    }
+
+    /// Returns the maximum supply the inflations of a single epoch may issue altogether.
+    pub fn epoch_cap(&self) -> Amount { or_panic(self.try_epoch_cap()) }
+
+    pub fn try_epoch_cap(&self) -> Result<Amount, Error> { global(&self.0, GS_EPOCH_CAP) }
+
+    /// Returns the epoch in which a transition mined at the given height falls.
+    pub fn epoch_of(&self, height: u32) -> u32 { or_panic(self.try_epoch_of(height)) }
+
+    pub fn try_epoch_of(&self, height: u32) -> Result<u32, Error> {
+        Ok(height / self.try_epoch_length()?)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_inflation_allocations(filter))
+    }
+
+    pub fn try_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_INFLATION, filter)?)
+    }
+
+    pub fn epoch_issuance_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_epoch_issuance_allocations(filter))
+    }
+
+    pub fn try_epoch_issuance_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_EPOCH_ISSUANCE, filter)?)
+    }
+
+    /// Returns the epoch of the last inflation and the supply issued within it up to that
+    /// inflation, as recorded by the epoch issuance state passing the filter, or none if no epoch
+    /// issuance state does.
+    pub fn epoch_issuance(&self, filter: impl AssignmentsFilter) -> Option<(u32, Amount)> {
+        or_panic(self.try_epoch_issuance(filter))
+    }
+
+    pub fn try_epoch_issuance(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Option<(u32, Amount)>, Error> {
+        self.try_epoch_issuance_allocations(filter)?
+            .next()
+            .map(|allocation| {
+                Allocation::from_strict_serialized(allocation.state.into())
+                    .map(|issuance| {
+                        (
+                            issuance.token_index().to_inner(),
+                            Amount::from(issuance.fraction().value()),
+                        )
+                    })
+                    .map_err(|_| Error::InvalidOwned(OS_EPOCH_ISSUANCE))
+            })
+            .transpose()
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for EifaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { EifaWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        EifaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        EifaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        EifaWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::height::block_height;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = eifa_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(EIFA_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<EpochInflatableAsset>(fixtures::contract_id("eifa"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.max_supply(), Amount::from(fixtures::MAX_SUPPLY));
+        assert_eq!(wrapper.epoch_length(), fixtures::EPOCH_LENGTH);
+        assert_eq!(wrapper.epoch_cap(), Amount::from(fixtures::EPOCH_CAP));
+        assert_eq!(wrapper.epoch_of(fixtures::EPOCH_LENGTH - 1), 0);
+        assert_eq!(wrapper.epoch_of(fixtures::EPOCH_LENGTH), 1);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![fixtures::ISSUED_SUPPLY]);
+        let inflation = wrapper
+            .inflation_allocations(&FilterIncludeAll)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(inflation, vec![fixtures::MAX_SUPPLY - fixtures::ISSUED_SUPPLY]);
+        assert_eq!(wrapper.epoch_issuance(&FilterIncludeAll), Some((0, Amount::from(0u64))));
+    }
+
+    // Inflations of an epoch inflatable asset.
+    //
+    // The issuer inflates twice, each inflation committing to a lock time in its own epoch and
+    // anchored into its own mined witness TX. The scripts don't check the lock time of the
+    // witnesses, so the first inflation may be mined late, in the epoch of the second one: the
+    // audit must report this epoch, and no issue once the first one is mined in its own epoch.
+    // Inflations bringing the supply issued within their epoch over the cap, by themselves or
+    // together with an earlier one, must be rejected by the validation of the history.
+
+    const EPOCH_LENGTH: u32 = 100;
+
+    /// Epoch inflatable asset allowing to inflate up to `max`, of which at most `cap` within an
+    /// epoch of `epoch_length` blocks, with the inflation right of `inflation` units at
+    /// `seal(2)` and the epoch issuance state, recording no issuance yet, at `seal(3)`.
+    fn eifa(
+        issued: u64,
+        allocated: u64,
+        max: u64,
+        inflation: u64,
+        epoch_length: u32,
+        cap: u64,
+    ) -> ContractBuilder {
+        testing::asset::<EpochInflatableAsset>(issued, allocated)
+            .add_global_state("maxSupply", Amount::from(max))
+            .unwrap()
+            .add_global_state("epochLength", Amount::from(epoch_length as u64))
+            .unwrap()
+            .add_global_state("epochCap", Amount::from(cap))
+            .unwrap()
+            .add_fungible_state("inflationAllowance", testing::seal(2), inflation)
+            .unwrap()
+            .add_data("epochIssuance", testing::seal(3), Allocation::with(0, 0))
+            .unwrap()
+    }
+
+    /// Inflation right and epoch issuance state spent by the next inflation, with the outpoints
+    /// they are allocated to.
+    #[derive(Clone, Copy)]
+    struct InflationState {
+        allowance: (Opout, u64),
+        issuance: (Opout, (u32, u64)),
+        outpoints: [Outpoint; 2],
+    }
+
+    impl InflationState {
+        fn genesis(genesis_id: OpId, allowance: u64) -> Self {
+            InflationState {
+                allowance: (Opout::new(genesis_id, OS_INFLATION, 0), allowance),
+                issuance: (Opout::new(genesis_id, OS_EPOCH_ISSUANCE, 0), (0, 0)),
+                outpoints: [Outpoint::new(testing::txid(), 2), Outpoint::new(testing::txid(), 3)],
+            }
+        }
+    }
+
+    /// Inflates `issued` units spending `state`, in a witness TX committing to `lock_time` and
+    /// mined at `height`, and records them in the epoch issuance state. Returns the state left
+    /// and the witness id.
+    fn inflate(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        state: InflationState,
+        issued: u64,
+        lock_time: u32,
+        height: u32,
+    ) -> (InflationState, Txid) {
+        let epoch = (lock_time + 1) / EPOCH_LENGTH;
+        let (last_epoch, last_issued) = state.issuance.1;
+        let recorded = (epoch, if epoch == last_epoch { last_issued + issued } else { issued });
+        let allowed = state.allowance.1 - issued;
+        let transition = stock
+            .transition_builder(contract_id, "inflate")
+            .unwrap()
+            .add_input(
+                state.allowance.0,
+                AllocatedState::Amount(RevealedValue::from(state.allowance.1)),
+            )
+            .unwrap()
+            .add_input(state.issuance.0, data(last_epoch, last_issued))
+            .unwrap()
+            .add_global_state("issuedSupply", Amount::from(issued))
+            .unwrap()
+            .add_global_state("lockTime", block_height(lock_time))
+            .unwrap()
+            .add_metadata("allowedInflation", Amount::from(allowed))
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), issued)
+            .unwrap()
+            .add_fungible_state("inflationAllowance", testing::graph_seal(2), allowed)
+            .unwrap()
+            .add_data(
+                "epochIssuance",
+                testing::graph_seal(3),
+                Allocation::with(TokenIndex::from_inner(recorded.0), recorded.1),
+            )
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        let fascia = testing::anchor(contract_id, transition, &state.outpoints, 4);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        let state = InflationState {
+            allowance: (Opout::new(opid, OS_INFLATION, 0), allowed),
+            issuance: (Opout::new(opid, OS_EPOCH_ISSUANCE, 0), recorded),
+            outpoints: [Outpoint::new(witness_id, 2), Outpoint::new(witness_id, 3)],
+        };
+        (state, witness_id)
+    }
+
+    fn data(index: u32, fraction: u64) -> AllocatedState {
+        match testing::allocation(index, fraction) {
+            RevealedState::Structured(data) => AllocatedState::Data(data),
+            _ => unreachable!(),
+        }
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn inflations_per_epoch() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(eifa(1000, 1000, 1500, 500, EPOCH_LENGTH, 200));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<EpochInflatableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.epoch_length(), EPOCH_LENGTH);
+        assert_eq!(wrapper.epoch_cap(), Amount::from(200u64));
+        assert_eq!(wrapper.epoch_of(150), 1);
+        let state = InflationState::genesis(genesis_id, 500);
+        assert_eq!(wrapper.epoch_issuance(state.outpoints[1]), Some((0, Amount::from(0u64))));
+
+        // the first inflation belongs to epoch 1 but is mined in epoch 2, with the second one
+        let (state, first) = inflate(&mut stock, &mut resolver, contract_id, state, 150, 99, 210);
+        let (state, _) = inflate(&mut stock, &mut resolver, contract_id, state, 150, 199, 250);
+
+        testing::validate_history::<EpochInflatableAsset>(&stock, &resolver, contract_id, &[state
+            .allowance
+            .0
+            .op])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<EpochInflatableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1300u64));
+        assert_eq!(wrapper.epoch_issuance(state.outpoints[1]), Some((2, Amount::from(150u64))));
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::EpochCapExceeded {
+            epoch: 2,
+            inflated: 300,
+            cap: 200,
+        }]);
+        assert_eq!((report.issued, report.burned, report.allocated), (1300, 0, 1300));
+
+        resolver.set_witness_ord(first, testing::mined_at(150));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn inflation_over_epoch_cap_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(eifa(1000, 1000, 1500, 500, EPOCH_LENGTH, 200));
+        let contract_id = contract.contract_id();
+        let state = InflationState::genesis(contract.genesis.id(), 500);
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let (state, _) = inflate(&mut stock, &mut resolver, contract_id, state, 201, 119, 120);
+
+        let err =
+            testing::validate_history::<EpochInflatableAsset>(&stock, &resolver, contract_id, &[
+                state.allowance.0.op,
+            ])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::EpochCapExceeded));
+    }
+
+    #[test]
+    fn inflations_over_epoch_cap_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(eifa(1000, 1000, 1500, 500, EPOCH_LENGTH, 200));
+        let contract_id = contract.contract_id();
+        let state = InflationState::genesis(contract.genesis.id(), 500);
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        // both inflations are within the cap, but not together
+        let (first, _) = inflate(&mut stock, &mut resolver, contract_id, state, 150, 119, 120);
+        testing::validate_history::<EpochInflatableAsset>(&stock, &resolver, contract_id, &[first
+            .allowance
+            .0
+            .op])
+        .unwrap();
+        let (second, _) = inflate(&mut stock, &mut resolver, contract_id, first, 150, 149, 150);
+
+        let err =
+            testing::validate_history::<EpochInflatableAsset>(&stock, &resolver, contract_id, &[
+                second.allowance.0.op,
+            ])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::EpochCapExceeded));
+    }
+}
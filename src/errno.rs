@@ -0,0 +1,737 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error numbers reported by the validation scripts of the schemata.
+//!
+//! When a validation script fails, the consignment validation reports the error number the
+//! script set before failing. [`ValidationErrno`] turns it into a description which can be shown
+//! to users, together with the schemata and operations whose scripts can report it.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Error number set by a schema validation script before failing.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum ValidationErrno {
+    /// Sum of the inputs differs from the sum of the outputs.
+    NonEqualInOut = 0,
+    /// Allocated amount differs from the issued supply reported in the global state.
+    IssuedMismatch = 1,
+    /// Unique token is split into fractions.
+    NonFractional = 10,
+    /// Tokens of a collection aren't sorted by strictly increasing index.
+    UnsortedTokens = 11,
+    /// Allocation owns a token the contract doesn't declare.
+    UnknownToken = 12,
+    /// Redeemed ticket differs from the spent one.
+    TicketMismatch = 13,
+    /// Minted edition isn't numbered right after the editions minted before it.
+    EditionMismatch = 14,
+    /// Minted edition exceeds the maximum number of editions of the series.
+    EditionCapExceeded = 15,
+    /// Registered name is already bound to a token of the registry.
+    NameTaken = 16,
+    /// Registered name contains upper case letters or spaces.
+    UnnormalizedName = 17,
+    /// Registered name isn't bound to the token numbered right after the names registered before
+    /// it.
+    RegistrationMismatch = 18,
+    /// Token data published by a reattachment declares another token than the current one.
+    TokenReindexed = 19,
+    /// Issuer public key is missing from the contract global state.
+    MissingPubkey = 20,
+    /// Transition is not signed by the issuer.
+    InvalidSignature = 21,
+    /// Transition signer is not one of the keys authorized by the contract.
+    UnauthorizedSigner = 22,
+    /// Inflation allowance differs from the amount the contract still allows to inflate.
+    InflationMismatch = 30,
+    /// Inflation exceeds the allowance of the spent inflation rights.
+    InflationExceedsAllowance = 31,
+    /// Approval threshold is zero or exceeds the number of approving keys.
+    InvalidThreshold = 32,
+    /// Approval state differs from the approvals collected by the operation, the approving key
+    /// doesn't follow the last one which approved, or the approval changes the approved inflation
+    /// set by the first one.
+    ApprovalMismatch = 33,
+    /// Inflation spends fewer approvals than the threshold.
+    InsufficientApprovals = 34,
+    /// Replace rights are assigned without spending any.
+    ReplaceNoInput = 35,
+    /// Replace rights are spent without assigning them again.
+    ReplaceHiddenBurn = 36,
+    /// Supply issued within the epoch of an inflation, up to and including it, exceeds the cap
+    /// committed for a single epoch.
+    EpochCapExceeded = 37,
+    /// Inflation commits to a lock time which isn't a block height, or letting its witness be
+    /// mined in an epoch preceding the one of the last inflation.
+    EpochRegressed = 38,
+    /// Epoch issuance state differs from the epoch of the inflation and the supply issued within
+    /// it, or records an issuance in the genesis.
+    EpochIssuanceMismatch = 39,
+    /// Burned supply differs from the sum of the burned allocations.
+    BurnMismatch = 40,
+    /// Reissued supply exceeds the supply burned over the contract history.
+    ReissueExceedsBurns = 41,
+    /// Redeemed supply differs from the sum of the redeemed allocations.
+    RedeemMismatch = 42,
+    /// Redeem of a bond commits to a lock time letting its witness be mined before the maturity
+    /// height.
+    RedeemLocked = 43,
+    /// Soulbound asset is assigned to new owners.
+    Soulbound = 45,
+    /// Revocation doesn't record exactly one revoked UTXO.
+    RevocationMismatch = 46,
+    /// Vote rights differ from the issued supply divided by the voting unit.
+    VoteRightsMismatch = 50,
+    /// Vesting cliff height isn't lower than the vesting end height.
+    InvalidSchedule = 55,
+    /// Released or locked supply of a release differs from its allocations, or their sum from the
+    /// spent locked allocation.
+    ReleaseMismatch = 56,
+    /// Transfer of a timelocked asset commits to a lock time letting its witness be mined before
+    /// the lock height.
+    TransferLocked = 57,
+    /// Transfer of a freezable asset commits to spending a frozen UTXO.
+    FrozenSpend = 58,
+    /// Freeze records a UTXO which is already frozen.
+    AlreadyFrozen = 59,
+    /// Reserve attestation recorded in the global state differs from the reserve proof of the
+    /// issuance.
+    ReserveMismatch = 60,
+    /// Transfer fee rate exceeds 10 000 basis points.
+    InvalidFeeRate = 65,
+    /// Fee allocated by a transfer differs from the fee rate applied to the transferred amount.
+    FeeMismatch = 66,
+    /// Amount filled over the history of a swap order exceeds the offered amount.
+    FillExceedsOffer = 67,
+    /// Expiry set by a subscription renewal isn't later than the current one.
+    ExpiryNotExtended = 68,
+    /// Asset is transferred while the issuer has paused its transfers.
+    Paused = 69,
+    /// Paused flag recorded by a toggle doesn't negate the current one.
+    PauseNotToggled = 70,
+    /// Token is leased again while its previous lease isn't reclaimed.
+    LeaseActive = 71,
+    /// Reclaim doesn't end the current lease of the token, either because there is none or
+    /// because it records another expiry.
+    LeaseMismatch = 72,
+    /// Reclaim commits to a lock time letting its witness be mined while the lease it ends is in
+    /// force.
+    LeaseNotExpired = 75,
+    /// Exercise commits to a lock time keeping its witness from being mined up to the expiry of
+    /// the options.
+    OptionExpired = 76,
+    /// Lapse commits to a lock time letting its witness be mined up to the expiry of the options.
+    OptionNotExpired = 77,
+    /// Release commits to a lock time letting its witness be mined at a height at which the
+    /// vesting schedule unlocks less than the supply released up to it.
+    ReleaseLocked = 78,
+    /// Inflation issues a supply differing from the approved inflation its approvals committed to.
+    UnapprovedInflation = 79,
+    /// Claim is attested after the issuer revoked the credential.
+    CredentialRevoked = 73,
+    /// Rebase factor published by the issuer is zero, which would wipe out all the balances.
+    ZeroRebaseFactor = 74,
+}
+
+/// Operation of a schema whose validation script can report an error number.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ErrnoEmitter {
+    /// Name of the schema.
+    pub schema: &'static str,
+    /// Name of the operation: `genesis` or the name of a state transition.
+    pub operation: &'static str,
+}
+
+impl ErrnoEmitter {
+    const fn new(schema: &'static str, operation: &'static str) -> Self {
+        ErrnoEmitter { schema, operation }
+    }
+}
+
+const NIA: &str = "NonInflatableAsset";
+const CFA: &str = "CollectibleFungibleAsset";
+const UDA: &str = "UniqueDigitalAsset";
+const FUA: &str = "FractionalUniqueAsset";
+const UDC: &str = "UniqueDigitalCollection";
+const PFA: &str = "PermissionedFungibleAsset";
+const IFA: &str = "InflatableFungibleAsset";
+const RIA: &str = "ReissuableAsset";
+const SBA: &str = "SoulboundAsset";
+const SFA: &str = "SemiFungibleAsset";
+const STABLECOIN: &str = "StablecoinAsset";
+const BOND: &str = "BondAsset";
+const GOVERNANCE: &str = "GovernanceAsset";
+const TICKET: &str = "TicketAsset";
+const VESTING: &str = "VestingAsset";
+const TIMELOCK: &str = "TimelockAsset";
+const PFAN: &str = "MultiKeyPermissionedAsset";
+const FREEZABLE: &str = "FreezableAsset";
+const TIFA: &str = "ThresholdInflatableAsset";
+const WBTC: &str = "WrappedBtcAsset";
+const FEE: &str = "TransferFeeAsset";
+const DIVIDEND: &str = "DividendAsset";
+const ESCROW: &str = "EscrowAsset";
+const SWAP: &str = "SwapOrder";
+const CARBON: &str = "CarbonCreditAsset";
+const PROVENANCE: &str = "ProvenanceAsset";
+const SUBSCRIPTION: &str = "SubscriptionAsset";
+const EDITION: &str = "LimitedEditionAsset";
+const EIFA: &str = "EpochInflatableAsset";
+const CLAIM: &str = "ClaimableAsset";
+const REGISTRY: &str = "NameRegistry";
+const PAUSABLE: &str = "PausableAsset";
+const RENTAL: &str = "RentalAsset";
+const OPTION: &str = "OptionAsset";
+const CREDENTIAL: &str = "VerifiableCredential";
+const BURNABLE: &str = "BurnableAsset";
+const REBASING: &str = "RebasingAsset";
+
+const NIA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(NIA, "genesis");
+const NIA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(NIA, "transfer");
+const CFA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(CFA, "genesis");
+const CFA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(CFA, "transfer");
+const UDA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(UDA, "genesis");
+const UDA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(UDA, "transfer");
+const UDA_ENGRAVE: ErrnoEmitter = ErrnoEmitter::new(UDA, "engrave");
+const UDA_REATTACH: ErrnoEmitter = ErrnoEmitter::new(UDA, "reattach");
+const FUA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(FUA, "genesis");
+const FUA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(FUA, "transfer");
+const UDC_GENESIS: ErrnoEmitter = ErrnoEmitter::new(UDC, "genesis");
+const UDC_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(UDC, "transfer");
+const PFA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(PFA, "genesis");
+const PFA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(PFA, "transfer");
+const IFA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(IFA, "genesis");
+const IFA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(IFA, "transfer");
+const IFA_INFLATE: ErrnoEmitter = ErrnoEmitter::new(IFA, "inflate");
+const IFA_REPLACE: ErrnoEmitter = ErrnoEmitter::new(IFA, "replace");
+const RIA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(RIA, "genesis");
+const RIA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(RIA, "transfer");
+const RIA_BURN: ErrnoEmitter = ErrnoEmitter::new(RIA, "burn");
+const RIA_REISSUE: ErrnoEmitter = ErrnoEmitter::new(RIA, "reissue");
+const SBA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(SBA, "genesis");
+const SBA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(SBA, "transfer");
+const SBA_BURN: ErrnoEmitter = ErrnoEmitter::new(SBA, "burn");
+const SBA_REVOKE: ErrnoEmitter = ErrnoEmitter::new(SBA, "revoke");
+const SFA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(SFA, "genesis");
+const SFA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(SFA, "transfer");
+const STABLECOIN_GENESIS: ErrnoEmitter = ErrnoEmitter::new(STABLECOIN, "genesis");
+const STABLECOIN_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(STABLECOIN, "transfer");
+const STABLECOIN_INFLATE: ErrnoEmitter = ErrnoEmitter::new(STABLECOIN, "inflate");
+const STABLECOIN_REDEEM: ErrnoEmitter = ErrnoEmitter::new(STABLECOIN, "redeem");
+const BOND_GENESIS: ErrnoEmitter = ErrnoEmitter::new(BOND, "genesis");
+const BOND_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(BOND, "transfer");
+const BOND_REDEEM: ErrnoEmitter = ErrnoEmitter::new(BOND, "redeem");
+const GOVERNANCE_GENESIS: ErrnoEmitter = ErrnoEmitter::new(GOVERNANCE, "genesis");
+const GOVERNANCE_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(GOVERNANCE, "transfer");
+const TICKET_GENESIS: ErrnoEmitter = ErrnoEmitter::new(TICKET, "genesis");
+const TICKET_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(TICKET, "transfer");
+const TICKET_REDEEM: ErrnoEmitter = ErrnoEmitter::new(TICKET, "redeem");
+const VESTING_GENESIS: ErrnoEmitter = ErrnoEmitter::new(VESTING, "genesis");
+const VESTING_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(VESTING, "transfer");
+const VESTING_RELEASE: ErrnoEmitter = ErrnoEmitter::new(VESTING, "release");
+const TIMELOCK_GENESIS: ErrnoEmitter = ErrnoEmitter::new(TIMELOCK, "genesis");
+const TIMELOCK_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(TIMELOCK, "transfer");
+const PFAN_GENESIS: ErrnoEmitter = ErrnoEmitter::new(PFAN, "genesis");
+const PFAN_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(PFAN, "transfer");
+const FREEZABLE_GENESIS: ErrnoEmitter = ErrnoEmitter::new(FREEZABLE, "genesis");
+const FREEZABLE_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(FREEZABLE, "transfer");
+const FREEZABLE_FREEZE: ErrnoEmitter = ErrnoEmitter::new(FREEZABLE, "freeze");
+const TIFA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(TIFA, "genesis");
+const TIFA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(TIFA, "transfer");
+const TIFA_INFLATE: ErrnoEmitter = ErrnoEmitter::new(TIFA, "inflate");
+const TIFA_APPROVE: ErrnoEmitter = ErrnoEmitter::new(TIFA, "approve");
+const WBTC_GENESIS: ErrnoEmitter = ErrnoEmitter::new(WBTC, "genesis");
+const WBTC_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(WBTC, "transfer");
+const WBTC_BURN: ErrnoEmitter = ErrnoEmitter::new(WBTC, "burn");
+const WBTC_MINT: ErrnoEmitter = ErrnoEmitter::new(WBTC, "mint");
+const FEE_GENESIS: ErrnoEmitter = ErrnoEmitter::new(FEE, "genesis");
+const FEE_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(FEE, "transfer");
+const FEE_COLLECT: ErrnoEmitter = ErrnoEmitter::new(FEE, "collect");
+const DIVIDEND_GENESIS: ErrnoEmitter = ErrnoEmitter::new(DIVIDEND, "genesis");
+const DIVIDEND_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(DIVIDEND, "transfer");
+const ESCROW_GENESIS: ErrnoEmitter = ErrnoEmitter::new(ESCROW, "genesis");
+const ESCROW_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(ESCROW, "transfer");
+const ESCROW_ESCROW: ErrnoEmitter = ErrnoEmitter::new(ESCROW, "escrow");
+const ESCROW_RELEASE: ErrnoEmitter = ErrnoEmitter::new(ESCROW, "release");
+const ESCROW_REFUND: ErrnoEmitter = ErrnoEmitter::new(ESCROW, "refund");
+const SWAP_FILL: ErrnoEmitter = ErrnoEmitter::new(SWAP, "fill");
+const SWAP_CANCEL: ErrnoEmitter = ErrnoEmitter::new(SWAP, "cancel");
+const CARBON_GENESIS: ErrnoEmitter = ErrnoEmitter::new(CARBON, "genesis");
+const CARBON_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(CARBON, "transfer");
+const CARBON_RETIRE: ErrnoEmitter = ErrnoEmitter::new(CARBON, "retire");
+const PROVENANCE_GENESIS: ErrnoEmitter = ErrnoEmitter::new(PROVENANCE, "genesis");
+const PROVENANCE_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(PROVENANCE, "transfer");
+const PROVENANCE_CHECKPOINT: ErrnoEmitter = ErrnoEmitter::new(PROVENANCE, "checkpoint");
+const SUBSCRIPTION_GENESIS: ErrnoEmitter = ErrnoEmitter::new(SUBSCRIPTION, "genesis");
+const SUBSCRIPTION_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(SUBSCRIPTION, "transfer");
+const SUBSCRIPTION_RENEW: ErrnoEmitter = ErrnoEmitter::new(SUBSCRIPTION, "renew");
+const EDITION_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(EDITION, "transfer");
+const EDITION_MINT: ErrnoEmitter = ErrnoEmitter::new(EDITION, "mint");
+const EIFA_GENESIS: ErrnoEmitter = ErrnoEmitter::new(EIFA, "genesis");
+const EIFA_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(EIFA, "transfer");
+const EIFA_INFLATE: ErrnoEmitter = ErrnoEmitter::new(EIFA, "inflate");
+const CLAIM_GENESIS: ErrnoEmitter = ErrnoEmitter::new(CLAIM, "genesis");
+const CLAIM_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(CLAIM, "transfer");
+const CLAIM_CLAIM: ErrnoEmitter = ErrnoEmitter::new(CLAIM, "claim");
+const CLAIM_COLLECT: ErrnoEmitter = ErrnoEmitter::new(CLAIM, "collect");
+const REGISTRY_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(REGISTRY, "transfer");
+const REGISTRY_REGISTER: ErrnoEmitter = ErrnoEmitter::new(REGISTRY, "register");
+const PAUSABLE_GENESIS: ErrnoEmitter = ErrnoEmitter::new(PAUSABLE, "genesis");
+const PAUSABLE_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(PAUSABLE, "transfer");
+const PAUSABLE_TOGGLE: ErrnoEmitter = ErrnoEmitter::new(PAUSABLE, "togglePause");
+const RENTAL_GENESIS: ErrnoEmitter = ErrnoEmitter::new(RENTAL, "genesis");
+const RENTAL_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(RENTAL, "transfer");
+const RENTAL_LEASE: ErrnoEmitter = ErrnoEmitter::new(RENTAL, "lease");
+const RENTAL_RECLAIM: ErrnoEmitter = ErrnoEmitter::new(RENTAL, "reclaim");
+const OPTION_GENESIS: ErrnoEmitter = ErrnoEmitter::new(OPTION, "genesis");
+const OPTION_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(OPTION, "transfer");
+const OPTION_EXERCISE: ErrnoEmitter = ErrnoEmitter::new(OPTION, "exercise");
+const OPTION_LAPSE: ErrnoEmitter = ErrnoEmitter::new(OPTION, "lapse");
+const CREDENTIAL_ATTEST: ErrnoEmitter = ErrnoEmitter::new(CREDENTIAL, "attest");
+const BURNABLE_GENESIS: ErrnoEmitter = ErrnoEmitter::new(BURNABLE, "genesis");
+const BURNABLE_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(BURNABLE, "transfer");
+const BURNABLE_BURN: ErrnoEmitter = ErrnoEmitter::new(BURNABLE, "burn");
+const REBASING_GENESIS: ErrnoEmitter = ErrnoEmitter::new(REBASING, "genesis");
+const REBASING_TRANSFER: ErrnoEmitter = ErrnoEmitter::new(REBASING, "transfer");
+const REBASING_REBASE: ErrnoEmitter = ErrnoEmitter::new(REBASING, "rebase");
+
+impl ValidationErrno {
+    /// All the error numbers, in increasing order.
+    pub const ALL: [ValidationErrno; 53] = [
+        ValidationErrno::NonEqualInOut,
+        ValidationErrno::IssuedMismatch,
+        ValidationErrno::NonFractional,
+        ValidationErrno::UnsortedTokens,
+        ValidationErrno::UnknownToken,
+        ValidationErrno::TicketMismatch,
+        ValidationErrno::EditionMismatch,
+        ValidationErrno::EditionCapExceeded,
+        ValidationErrno::NameTaken,
+        ValidationErrno::UnnormalizedName,
+        ValidationErrno::RegistrationMismatch,
+        ValidationErrno::TokenReindexed,
+        ValidationErrno::MissingPubkey,
+        ValidationErrno::InvalidSignature,
+        ValidationErrno::UnauthorizedSigner,
+        ValidationErrno::InflationMismatch,
+        ValidationErrno::InflationExceedsAllowance,
+        ValidationErrno::InvalidThreshold,
+        ValidationErrno::ApprovalMismatch,
+        ValidationErrno::InsufficientApprovals,
+        ValidationErrno::ReplaceNoInput,
+        ValidationErrno::ReplaceHiddenBurn,
+        ValidationErrno::EpochCapExceeded,
+        ValidationErrno::EpochRegressed,
+        ValidationErrno::EpochIssuanceMismatch,
+        ValidationErrno::BurnMismatch,
+        ValidationErrno::ReissueExceedsBurns,
+        ValidationErrno::RedeemMismatch,
+        ValidationErrno::RedeemLocked,
+        ValidationErrno::Soulbound,
+        ValidationErrno::RevocationMismatch,
+        ValidationErrno::VoteRightsMismatch,
+        ValidationErrno::InvalidSchedule,
+        ValidationErrno::ReleaseMismatch,
+        ValidationErrno::TransferLocked,
+        ValidationErrno::FrozenSpend,
+        ValidationErrno::AlreadyFrozen,
+        ValidationErrno::ReserveMismatch,
+        ValidationErrno::InvalidFeeRate,
+        ValidationErrno::FeeMismatch,
+        ValidationErrno::FillExceedsOffer,
+        ValidationErrno::ExpiryNotExtended,
+        ValidationErrno::Paused,
+        ValidationErrno::PauseNotToggled,
+        ValidationErrno::LeaseActive,
+        ValidationErrno::LeaseMismatch,
+        ValidationErrno::CredentialRevoked,
+        ValidationErrno::ZeroRebaseFactor,
+        ValidationErrno::LeaseNotExpired,
+        ValidationErrno::OptionExpired,
+        ValidationErrno::OptionNotExpired,
+        ValidationErrno::ReleaseLocked,
+        ValidationErrno::UnapprovedInflation,
+    ];
+
+    /// Error number as reported by the validation.
+    pub const fn errno(self) -> u8 { self as u8 }
+
+    /// Operations of the schemata whose validation scripts can report this error number.
+    pub const fn emitters(self) -> &'static [ErrnoEmitter] {
+        match self {
+            ValidationErrno::NonEqualInOut => &[
+                NIA_TRANSFER,
+                CFA_TRANSFER,
+                UDA_GENESIS,
+                UDA_TRANSFER,
+                UDA_ENGRAVE,
+                FUA_GENESIS,
+                FUA_TRANSFER,
+                UDC_TRANSFER,
+                PFA_TRANSFER,
+                IFA_TRANSFER,
+                IFA_REPLACE,
+                RIA_TRANSFER,
+                SFA_TRANSFER,
+                STABLECOIN_TRANSFER,
+                BOND_TRANSFER,
+                GOVERNANCE_TRANSFER,
+                TICKET_TRANSFER,
+                VESTING_TRANSFER,
+                TIMELOCK_TRANSFER,
+                PFAN_TRANSFER,
+                FREEZABLE_TRANSFER,
+                TIFA_TRANSFER,
+                WBTC_TRANSFER,
+                FEE_TRANSFER,
+                FEE_COLLECT,
+                DIVIDEND_TRANSFER,
+                ESCROW_TRANSFER,
+                ESCROW_ESCROW,
+                ESCROW_RELEASE,
+                ESCROW_REFUND,
+                CARBON_TRANSFER,
+                PROVENANCE_GENESIS,
+                PROVENANCE_TRANSFER,
+                PROVENANCE_CHECKPOINT,
+                SUBSCRIPTION_GENESIS,
+                SUBSCRIPTION_TRANSFER,
+                SUBSCRIPTION_RENEW,
+                EDITION_TRANSFER,
+                EDITION_MINT,
+                EIFA_TRANSFER,
+                CLAIM_TRANSFER,
+                CLAIM_CLAIM,
+                CLAIM_COLLECT,
+                REGISTRY_TRANSFER,
+                PAUSABLE_TRANSFER,
+                RENTAL_GENESIS,
+                RENTAL_TRANSFER,
+                RENTAL_LEASE,
+                RENTAL_RECLAIM,
+                OPTION_TRANSFER,
+                BURNABLE_TRANSFER,
+                REBASING_TRANSFER,
+            ],
+            ValidationErrno::IssuedMismatch => &[
+                NIA_GENESIS,
+                CFA_GENESIS,
+                FUA_GENESIS,
+                UDC_GENESIS,
+                PFA_GENESIS,
+                IFA_GENESIS,
+                IFA_INFLATE,
+                RIA_GENESIS,
+                RIA_REISSUE,
+                SBA_GENESIS,
+                STABLECOIN_GENESIS,
+                STABLECOIN_INFLATE,
+                BOND_GENESIS,
+                GOVERNANCE_GENESIS,
+                TICKET_GENESIS,
+                VESTING_GENESIS,
+                TIMELOCK_GENESIS,
+                PFAN_GENESIS,
+                FREEZABLE_GENESIS,
+                TIFA_GENESIS,
+                TIFA_INFLATE,
+                WBTC_GENESIS,
+                WBTC_MINT,
+                FEE_GENESIS,
+                DIVIDEND_GENESIS,
+                ESCROW_GENESIS,
+                CARBON_GENESIS,
+                EIFA_GENESIS,
+                EIFA_INFLATE,
+                CLAIM_GENESIS,
+                PAUSABLE_GENESIS,
+                OPTION_GENESIS,
+                BURNABLE_GENESIS,
+                REBASING_GENESIS,
+            ],
+            ValidationErrno::NonFractional => &[
+                UDA_GENESIS,
+                UDA_TRANSFER,
+                UDA_ENGRAVE,
+                UDC_GENESIS,
+                UDC_TRANSFER,
+                TICKET_GENESIS,
+                TICKET_TRANSFER,
+                PROVENANCE_GENESIS,
+                PROVENANCE_TRANSFER,
+                PROVENANCE_CHECKPOINT,
+                SUBSCRIPTION_GENESIS,
+                SUBSCRIPTION_TRANSFER,
+                SUBSCRIPTION_RENEW,
+                EDITION_TRANSFER,
+                EDITION_MINT,
+                REGISTRY_TRANSFER,
+                REGISTRY_REGISTER,
+                RENTAL_GENESIS,
+                RENTAL_TRANSFER,
+                RENTAL_LEASE,
+                RENTAL_RECLAIM,
+            ],
+            ValidationErrno::UnsortedTokens => &[UDC_GENESIS, SFA_GENESIS, TICKET_GENESIS],
+            ValidationErrno::UnknownToken => &[SFA_GENESIS],
+            ValidationErrno::TicketMismatch => &[TICKET_REDEEM],
+            ValidationErrno::EditionMismatch => &[EDITION_MINT],
+            ValidationErrno::EditionCapExceeded => &[EDITION_MINT],
+            ValidationErrno::NameTaken => &[REGISTRY_REGISTER],
+            ValidationErrno::UnnormalizedName => &[REGISTRY_REGISTER],
+            ValidationErrno::RegistrationMismatch => &[REGISTRY_REGISTER],
+            ValidationErrno::TokenReindexed => &[UDA_REATTACH],
+            ValidationErrno::MissingPubkey => &[
+                PFA_TRANSFER,
+                STABLECOIN_INFLATE,
+                STABLECOIN_REDEEM,
+                SWAP_FILL,
+                SWAP_CANCEL,
+                SUBSCRIPTION_RENEW,
+                CLAIM_COLLECT,
+                CREDENTIAL_ATTEST,
+                REBASING_REBASE,
+            ],
+            ValidationErrno::InvalidSignature => &[
+                PFA_TRANSFER,
+                PFAN_TRANSFER,
+                STABLECOIN_INFLATE,
+                STABLECOIN_REDEEM,
+                TIFA_APPROVE,
+                SWAP_FILL,
+                SWAP_CANCEL,
+                SUBSCRIPTION_RENEW,
+                CLAIM_COLLECT,
+                CREDENTIAL_ATTEST,
+                REBASING_REBASE,
+            ],
+            ValidationErrno::UnauthorizedSigner => &[PFAN_TRANSFER, TIFA_APPROVE],
+            ValidationErrno::InflationMismatch => &[
+                IFA_GENESIS,
+                IFA_INFLATE,
+                STABLECOIN_GENESIS,
+                STABLECOIN_INFLATE,
+                TIFA_GENESIS,
+                TIFA_INFLATE,
+                EIFA_GENESIS,
+                EIFA_INFLATE,
+            ],
+            ValidationErrno::InflationExceedsAllowance => {
+                &[IFA_INFLATE, STABLECOIN_INFLATE, TIFA_INFLATE, EIFA_INFLATE]
+            }
+            ValidationErrno::InvalidThreshold => &[TIFA_GENESIS],
+            ValidationErrno::ApprovalMismatch => &[TIFA_GENESIS, TIFA_APPROVE, TIFA_INFLATE],
+            ValidationErrno::InsufficientApprovals => &[TIFA_INFLATE],
+            ValidationErrno::ReplaceNoInput | ValidationErrno::ReplaceHiddenBurn => {
+                &[IFA_TRANSFER, IFA_REPLACE]
+            }
+            ValidationErrno::EpochCapExceeded | ValidationErrno::EpochRegressed => &[EIFA_INFLATE],
+            ValidationErrno::EpochIssuanceMismatch => &[EIFA_GENESIS, EIFA_INFLATE],
+            ValidationErrno::BurnMismatch => &[
+                RIA_BURN,
+                SBA_BURN,
+                WBTC_BURN,
+                CARBON_RETIRE,
+                OPTION_EXERCISE,
+                OPTION_LAPSE,
+                BURNABLE_BURN,
+            ],
+            ValidationErrno::ReissueExceedsBurns => &[RIA_REISSUE],
+            ValidationErrno::RedeemMismatch => &[STABLECOIN_REDEEM, BOND_REDEEM],
+            ValidationErrno::RedeemLocked => &[BOND_REDEEM],
+            ValidationErrno::Soulbound => &[SBA_TRANSFER],
+            ValidationErrno::RevocationMismatch => &[SBA_REVOKE],
+            ValidationErrno::VoteRightsMismatch => &[GOVERNANCE_GENESIS],
+            ValidationErrno::InvalidSchedule => &[VESTING_GENESIS],
+            ValidationErrno::ReleaseMismatch => &[VESTING_RELEASE],
+            ValidationErrno::TransferLocked => &[TIMELOCK_TRANSFER],
+            ValidationErrno::FrozenSpend => &[FREEZABLE_TRANSFER],
+            ValidationErrno::AlreadyFrozen => &[FREEZABLE_FREEZE],
+            ValidationErrno::ReserveMismatch => &[WBTC_GENESIS, WBTC_MINT],
+            ValidationErrno::InvalidFeeRate => &[FEE_GENESIS],
+            ValidationErrno::FeeMismatch => &[FEE_TRANSFER],
+            ValidationErrno::FillExceedsOffer => &[SWAP_FILL],
+            ValidationErrno::ExpiryNotExtended => &[SUBSCRIPTION_RENEW],
+            ValidationErrno::Paused => &[PAUSABLE_TRANSFER],
+            ValidationErrno::PauseNotToggled => &[PAUSABLE_TOGGLE],
+            ValidationErrno::LeaseActive => &[RENTAL_LEASE],
+            ValidationErrno::LeaseMismatch => &[RENTAL_RECLAIM],
+            ValidationErrno::CredentialRevoked => &[CREDENTIAL_ATTEST],
+            ValidationErrno::ZeroRebaseFactor => &[REBASING_REBASE],
+            ValidationErrno::LeaseNotExpired => &[RENTAL_RECLAIM],
+            ValidationErrno::OptionExpired => &[OPTION_EXERCISE],
+            ValidationErrno::OptionNotExpired => &[OPTION_LAPSE],
+            ValidationErrno::ReleaseLocked => &[VESTING_RELEASE],
+            ValidationErrno::UnapprovedInflation => &[TIFA_INFLATE],
+        }
+    }
+}
+
+impl Display for ValidationErrno {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ValidationErrno::NonEqualInOut => "input and output sums differ",
+            ValidationErrno::IssuedMismatch => "allocated amount differs from the issued supply",
+            ValidationErrno::NonFractional => "unique token can't be split",
+            ValidationErrno::UnsortedTokens => {
+                "collection tokens aren't sorted by strictly increasing index"
+            }
+            ValidationErrno::UnknownToken => "allocated token is missing from the contract",
+            ValidationErrno::TicketMismatch => "redeemed ticket differs from the spent one",
+            ValidationErrno::EditionMismatch => {
+                "minted edition doesn't follow the editions minted before"
+            }
+            ValidationErrno::EditionCapExceeded => "minted edition exceeds the cap of the series",
+            ValidationErrno::NameTaken => "registered name is already taken",
+            ValidationErrno::UnnormalizedName => {
+                "registered name contains upper case letters or spaces"
+            }
+            ValidationErrno::RegistrationMismatch => {
+                "registered name isn't bound to the token following the registered ones"
+            }
+            ValidationErrno::TokenReindexed => "reattached token data changes the token index",
+            ValidationErrno::MissingPubkey => "contract lacks the issuer public key",
+            ValidationErrno::InvalidSignature => "transition lacks a valid issuer signature",
+            ValidationErrno::UnauthorizedSigner => "transition signer isn't an authorized key",
+            ValidationErrno::InflationMismatch => {
+                "inflation allowance differs from the remaining inflatable supply"
+            }
+            ValidationErrno::InflationExceedsAllowance => {
+                "inflation exceeds the allowance of the spent inflation rights"
+            }
+            ValidationErrno::InvalidThreshold => {
+                "approval threshold is zero or exceeds the number of approving keys"
+            }
+            ValidationErrno::ApprovalMismatch => {
+                "approval state differs from the approvals collected by the operation"
+            }
+            ValidationErrno::InsufficientApprovals => {
+                "inflation spends fewer approvals than the threshold"
+            }
+            ValidationErrno::ReplaceNoInput => "replace rights are assigned without spending any",
+            ValidationErrno::ReplaceHiddenBurn => {
+                "replace rights are spent without being reassigned"
+            }
+            ValidationErrno::EpochCapExceeded => {
+                "supply issued within the epoch exceeds the cap of a single epoch"
+            }
+            ValidationErrno::EpochRegressed => {
+                "inflation lock time precedes the epoch of the last inflation"
+            }
+            ValidationErrno::EpochIssuanceMismatch => {
+                "epoch issuance state differs from the supply issued within the epoch"
+            }
+            ValidationErrno::BurnMismatch => {
+                "burned supply differs from the sum of the burned allocations"
+            }
+            ValidationErrno::ReissueExceedsBurns => "reissued supply exceeds the burned supply",
+            ValidationErrno::RedeemMismatch => {
+                "redeemed supply differs from the sum of the redeemed allocations"
+            }
+            ValidationErrno::RedeemLocked => "redeem lock time precedes the maturity height",
+            ValidationErrno::Soulbound => "soulbound asset can't be assigned to new owners",
+            ValidationErrno::RevocationMismatch => {
+                "revocation doesn't record a single revoked UTXO"
+            }
+            ValidationErrno::VoteRightsMismatch => {
+                "vote rights differ from the issued supply divided by the voting unit"
+            }
+            ValidationErrno::InvalidSchedule => {
+                "vesting cliff height isn't lower than the vesting end height"
+            }
+            ValidationErrno::ReleaseMismatch => {
+                "released or locked supply differs from the allocations of the release"
+            }
+            ValidationErrno::TransferLocked => "transfer lock time precedes the lock height",
+            ValidationErrno::FrozenSpend => "transfer spends a frozen UTXO",
+            ValidationErrno::AlreadyFrozen => "freeze records an already frozen UTXO",
+            ValidationErrno::ReserveMismatch => {
+                "reserve attestation differs from the reserve proof of the issuance"
+            }
+            ValidationErrno::InvalidFeeRate => "transfer fee rate exceeds 10 000 basis points",
+            ValidationErrno::FeeMismatch => {
+                "allocated fee differs from the fee rate applied to the transferred amount"
+            }
+            ValidationErrno::FillExceedsOffer => "filled amount exceeds the offered amount",
+            ValidationErrno::ExpiryNotExtended => "renewed expiry isn't later than the current one",
+            ValidationErrno::Paused => "asset transfers are paused by the issuer",
+            ValidationErrno::PauseNotToggled => "paused flag doesn't negate the current one",
+            ValidationErrno::LeaseActive => "token is leased while its last lease isn't reclaimed",
+            ValidationErrno::LeaseMismatch => "reclaim doesn't end the current lease of the token",
+            ValidationErrno::CredentialRevoked => {
+                "claim is attested after the credential revocation"
+            }
+            ValidationErrno::ZeroRebaseFactor => "rebase factor is zero",
+            ValidationErrno::LeaseNotExpired => "reclaim lock time precedes the lease expiry",
+            ValidationErrno::OptionExpired => "exercise lock time isn't lower than the expiry",
+            ValidationErrno::OptionNotExpired => "lapse lock time precedes the option expiry",
+            ValidationErrno::ReleaseLocked => {
+                "release lock time precedes the unlocking of the released supply"
+            }
+            ValidationErrno::UnapprovedInflation => {
+                "inflation issues a supply differing from the approved one"
+            }
+        })
+    }
+}
+
+impl From<ValidationErrno> for u8 {
+    fn from(errno: ValidationErrno) -> Self { errno.errno() }
+}
+
+/// Error number not defined by the schemata.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display("unknown validation error number {0}")]
+pub struct UnknownErrno(pub u8);
+
+impl TryFrom<u8> for ValidationErrno {
+    type Error = UnknownErrno;
+
+    fn try_from(errno: u8) -> Result<Self, Self::Error> {
+        ValidationErrno::ALL
+            .into_iter()
+            .find(|known| known.errno() == errno)
+            .ok_or(UnknownErrno(errno))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for errno in ValidationErrno::ALL {
+            assert_eq!(ValidationErrno::try_from(u8::from(errno)), Ok(errno));
+            assert!(!errno.emitters().is_empty());
+        }
+        assert_eq!(ValidationErrno::try_from(2), Err(UnknownErrno(2)));
+        assert!(ValidationErrno::ALL.is_sorted());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(ValidationErrno::NonEqualInOut.to_string(), "input and output sums differ");
+        assert_eq!(UnknownErrno(2).to_string(), "unknown validation error number 2");
+    }
+}
@@ -0,0 +1,142 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors reported when reading contract data through the schema wrappers.
+//!
+//! Each wrapper accessor has a `try_` counterpart returning [`Error`], so that contracts which
+//! don't match their schema can be rejected without panicking. The panicking accessors remain
+//! for contracts which are known to be validated.
+
+use rgbstd::contract::ContractError;
+use rgbstd::invoice::{AmountParseError, CoinAmount};
+use rgbstd::vm::UnknownGlobalStateType;
+use rgbstd::{Amount, AssignmentType, ContractId, GlobalStateType, Outpoint, SchemaId, TokenIndex};
+
+/// Error accessing contract data through a schema wrapper.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum Error {
+    /// contract uses schema {actual}, which is not a revision of the schema family whose latest
+    /// revision is {expected}.
+    SchemaMismatch {
+        expected: SchemaId,
+        actual: SchemaId,
+    },
+
+    /// contract uses schema {0}, which is not one of the fungible asset schemata.
+    NotFungible(SchemaId),
+
+    /// contract {actual} differs from the expected contract {expected}.
+    ContractMismatch {
+        expected: ContractId,
+        actual: ContractId,
+    },
+
+    /// contract has no global state of type {0}.
+    UnknownGlobalType(GlobalStateType),
+
+    /// contract has no owned state of type {0}.
+    UnknownOwnedType(AssignmentType),
+
+    /// contract has no value for the required global state of type {0}.
+    MissingGlobal(GlobalStateType),
+
+    /// global state of type {0} contains data not matching the schema.
+    InvalidGlobal(GlobalStateType),
+
+    /// owned state of type {0} contains data not matching the schema.
+    InvalidOwned(AssignmentType),
+
+    /// contract has no token with index {0}.
+    UnknownToken(TokenIndex),
+
+    /// name '{0}' can't be registered, as it must have from 1 to 40 printable ASCII characters
+    /// and no spaces.
+    InvalidName(String),
+
+    /// registry has no name '{0}'.
+    UnknownName(String),
+
+    /// ticket with index {0} is already redeemed.
+    RedeemedTicket(TokenIndex),
+
+    /// contract has no dividend distribution for epoch {0}.
+    UnknownEpoch(usize),
+
+    /// {0}
+    #[from]
+    InvalidAmount(AmountParseError),
+
+    /// amount {0} has more decimal digits than the precision of the asset, which is {1}.
+    ExcessivePrecision(CoinAmount, u8),
+
+    /// amount {0} exceeds the maximal amount of an asset.
+    AmountOverflow(CoinAmount),
+
+    /// spendable balance {available} is lower than the required amount {required}.
+    InsufficientBalance { available: Amount, required: Amount },
+
+    /// fill of {required} units exceeds the {available} units left in the order.
+    ExcessiveFill { available: Amount, required: Amount },
+
+    /// allocations can't have a zero amount.
+    ZeroAmount,
+
+    /// {0} outputs exceed the maximal number of assignments of a transition.
+    TooManyOutputs(usize),
+
+    /// genesis has {count} allocations of the asset, while the schema requires from {min} to
+    /// {max}.
+    GenesisAllocations { count: usize, min: u16, max: u16 },
+
+    /// output {0} is assigned to seals closed with different methods.
+    ConflictingMethods(Outpoint),
+
+    /// {0}
+    #[from]
+    Contract(ContractError),
+}
+
+/// Error wrapping contract data into the wrapper of another schema family.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Display, Error)]
+#[display(
+    "contract uses schema {actual}, which is not a revision of the schema family whose latest \
+     revision is {expected}."
+)]
+pub struct WrongSchema {
+    /// Latest revision of the schema family of the wrapper.
+    pub expected: SchemaId,
+    /// Schema of the contract.
+    pub actual: SchemaId,
+}
+
+impl From<WrongSchema> for Error {
+    fn from(err: WrongSchema) -> Self {
+        Error::SchemaMismatch {
+            expected: err.expected,
+            actual: err.actual,
+        }
+    }
+}
+
+impl From<UnknownGlobalStateType> for Error {
+    fn from(err: UnknownGlobalStateType) -> Self { Error::UnknownGlobalType(err.0) }
+}
@@ -0,0 +1,404 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Escrow asset schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A fungible asset whose holders can move units into escrow with an `escrow` transition, which
+//! assigns the escrowed allocation together with a release right and a refund right. The escrow
+//! is settled by spending it with one of the rights: a `release` spending the release right pays
+//! the beneficiary, while a `refund` spending the refund right returns the units to the sender.
+//! The validation scripts require the escrowed units to be preserved by each step.
+//!
+//! Scripts can't see the seals of the outputs, so the destination of a settlement is up to the
+//! party holding the right it spends. The rights aren't bound to the escrow they were created
+//! with either, so the escrowed allocation is meant to be assigned to an output controlled by all
+//! the parties, like a multisig one, in order to settle only the intended escrow.
+//!
+//! The `escrow_transition`, `release_transition` and `refund_transition` functions of the
+//! `workflow` module of `rgb-schemata-tools` build the transitions of the three steps.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, ESCROW_LOCK, ESCROW_SETTLE, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_TERMS, OS_ASSET, OS_ESCROW, OS_REFUND, OS_RELEASE, TS_ESCROW, TS_REFUND,
+    TS_RELEASE, TS_TRANSFER,
+};
+
+pub const ESCROW_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xa3, 0xac, 0xe4, 0xa3, 0xa3, 0xd2, 0xfc, 0xa8, 0x02, 0x4d, 0x84, 0xfe, 0x5e, 0xb2, 0xf5, 0x18,
+    0xb0, 0x47, 0x25, 0x74, 0x68, 0x48, 0x67, 0x85, 0xd7, 0x3e, 0x29, 0x15, 0x01, 0xcf, 0x67, 0xce,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn escrow_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    ESCROW_LOCK.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    ESCROW_SETTLE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("EscrowAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_ESCROW => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("escrowedAsset"),
+                default_transition: TS_RELEASE,
+            },
+            OS_RELEASE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("releaseRight"),
+                default_transition: TS_RELEASE,
+            },
+            OS_REFUND => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("refundRight"),
+                default_transition: TS_REFUND,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_ESCROW => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_ESCROW => Occurrences::Once,
+                        OS_RELEASE => Occurrences::Once,
+                        OS_REFUND => Occurrences::Once
+                    },
+                    validator: Some(ESCROW_LOCK.lib_site())
+                },
+                name: fname!("escrow"),
+            },
+            TS_RELEASE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ESCROW => Occurrences::Once,
+                        OS_RELEASE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(ESCROW_SETTLE.lib_site())
+                },
+                name: fname!("release"),
+            },
+            TS_REFUND => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ESCROW => Occurrences::Once,
+                        OS_REFUND => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(ESCROW_SETTLE.lib_site())
+                },
+                name: fname!("refund"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn escrow_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, ESCROW_LOCK, ESCROW_SETTLE])
+}
+
+#[derive(Default)]
+pub struct EscrowAsset;
+
+impl IssuerWrapper for EscrowAsset {
+    type Wrapper<S: ContractStateRead> = EscrowWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(escrow_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(escrow_scripts).clone()
+    }
+}
+
+impl EscrowAsset {
+    /// First revision of the escrow asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "EscrowAsset",
+        schema_id: ESCROW_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset whose units can be escrowed, then released to the \
+                        beneficiary or refunded to the sender depending on the right spent.",
+    };
+}
+
+impl IssuerInfo for EscrowAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for EscrowAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct EscrowWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for EscrowWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for EscrowWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<EscrowAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> EscrowWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the escrow asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<EscrowAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the escrow asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the escrowed allocations, which are not spendable until released or refunded.
+    pub fn escrows<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_escrows(filter))
+    }
+
+    pub fn try_escrows<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ESCROW, filter)?)
+    }
+
+    pub fn release_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_release_rights(filter))
+    }
+
+    pub fn try_release_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_RELEASE, filter)?)
+    }
+
+    pub fn refund_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_refund_rights(filter))
+    }
+
+    pub fn try_refund_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REFUND, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for EscrowWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        EscrowWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        EscrowWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        EscrowWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        EscrowWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::*;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = escrow_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(ESCROW_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<EscrowAsset>(fixtures::contract_id("escrow"))
+            .unwrap();
+        assert_eq!(wrapper.version(), EscrowAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        assert_eq!(wrapper.escrows(&FilterIncludeAll).count(), 0);
+        assert_eq!(wrapper.release_rights(&FilterIncludeAll).count(), 0);
+        assert_eq!(wrapper.refund_rights(&FilterIncludeAll).count(), 0);
+    }
+}
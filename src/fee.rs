@@ -0,0 +1,492 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transfer-fee asset (FEE) schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A fungible asset charging a fee on each transfer, like the revenue-sharing tokens issued by
+//! exchanges. The genesis commits to a `feeRate` in basis points, at most 10 000, and each
+//! `transfer` must allocate to `feeOwner` assignments the fee rate applied to the sum of its
+//! inputs, rounded down, the asset outputs getting the rest. The fee holders turn the fee
+//! allocations into units of the asset with a `collect` transition.
+//!
+//! The validation can't check who controls the seals of the fee allocations: wallets must assign
+//! them to the seals of the issuer, and recipients should check it before accepting a transfer.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::{Amount, GlobalDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, FEE_COLLECT, FEE_GENESIS, FEE_TRANSFER};
+use crate::wrapper::{check_family, global, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_FEE_RATE,
+    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS, OS_ASSET, OS_FEE, TS_COLLECT, TS_TRANSFER,
+};
+
+pub const FEE_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xd9, 0x17, 0x81, 0x18, 0x11, 0x57, 0x29, 0x5b, 0xad, 0xf5, 0x18, 0x55, 0x70, 0xdb, 0x98, 0x12,
+    0xcd, 0x98, 0x17, 0x39, 0xdd, 0xb6, 0xcc, 0x28, 0x15, 0x2a, 0x13, 0x2d, 0x81, 0xce, 0x48, 0xc4,
+]);
+
+/// Basis points making up the whole transferred amount.
+pub const MAX_FEE_RATE: u64 = 10_000;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn fee_schema() -> Schema {
+    let types = standard_types();
+
+    FEE_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    FEE_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    FEE_COLLECT.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("TransferFeeAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_FEE_RATE => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("feeRate"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_FEE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("feeOwner"),
+                default_transition: TS_COLLECT,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_FEE_RATE => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(FEE_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_FEE => Occurrences::NoneOrMore
+                    },
+                    validator: Some(FEE_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_COLLECT => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_FEE => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(FEE_COLLECT.lib_site())
+                },
+                name: fname!("collect"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn fee_scripts() -> Scripts { SharedLibs::get().scripts(&[FEE_GENESIS, FEE_TRANSFER, FEE_COLLECT]) }
+
+#[derive(Default)]
+pub struct TransferFeeAsset;
+
+impl IssuerWrapper for TransferFeeAsset {
+    type Wrapper<S: ContractStateRead> = FeeWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(fee_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(fee_scripts).clone()
+    }
+}
+
+impl TransferFeeAsset {
+    /// First revision of the FEE schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "TransferFeeAsset",
+        schema_id: FEE_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset whose transfers allocate a basis-point fee to the issuer.",
+    };
+}
+
+impl IssuerInfo for TransferFeeAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for TransferFeeAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct FeeWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for FeeWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for FeeWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<TransferFeeAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> FeeWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the FEE schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<TransferFeeAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the FEE schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the fee charged on each transfer, in basis points of the transferred amount.
+    pub fn fee_rate(&self) -> u64 { or_panic(self.try_fee_rate()) }
+
+    pub fn try_fee_rate(&self) -> Result<u64, Error> {
+        global::<_, Amount>(&self.0, GS_FEE_RATE).map(Amount::value)
+    }
+
+    /// Returns the fee a transfer spending inputs summing to `amount` must allocate to the issuer.
+    pub fn fee(&self, amount: Amount) -> Amount { or_panic(self.try_fee(amount)) }
+
+    pub fn try_fee(&self, amount: Amount) -> Result<Amount, Error> {
+        let fee = amount.value() as u128 * self.try_fee_rate()? as u128 / MAX_FEE_RATE as u128;
+        // The fee doesn't exceed the amount as long as the rate is valid
+        Ok(Amount::from(u64::try_from(fee).unwrap_or(u64::MAX)))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the fees allocated by transfers, which the issuer can collect.
+    pub fn fee_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_fee_allocations(filter))
+    }
+
+    pub fn try_fee_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_FEE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for FeeWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { FeeWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        FeeWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        FeeWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        FeeWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = fee_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(FEE_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<TransferFeeAsset>(fixtures::contract_id("fee"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.fee_rate(), fixtures::FEE_RATE);
+        assert_eq!(wrapper.fee(Amount::from(1_000_000u64)), Amount::from(2_500u64));
+        assert_eq!(wrapper.fee(Amount::from(399u64)), Amount::ZERO);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        assert_eq!(wrapper.fee_allocations(&FilterIncludeAll).count(), 0);
+    }
+
+    // Fees of a transfer-fee asset.
+    //
+    // A holder transfers part of their allocation, allocating the fee to the issuer, who then
+    // collects it into an allocation of the asset. A transfer underpaying the fee must be rejected
+    // by the validation of the history.
+
+    /// Transfer-fee asset charging `fee_rate` basis points on each transfer.
+    fn fee(issued: u64, allocated: u64, fee_rate: u64) -> ContractBuilder {
+        testing::asset::<TransferFeeAsset>(issued, allocated)
+            .add_global_state("feeRate", Amount::from(fee_rate))
+            .unwrap()
+    }
+
+    #[test]
+    fn transfer_and_collect() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(fee(1000, 1000, 250));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 3);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+
+        // the holder sends 400 units, keeping the change, and pays the issuer 2.5% of the inputs
+        let wrapper = stock
+            .contract_wrapper::<TransferFeeAsset>(contract_id)
+            .unwrap();
+        let fee = wrapper.fee(Amount::from(1000u64));
+        assert_eq!(fee, Amount::from(25u64));
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 400u64)
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(2), 575u64)
+            .unwrap()
+            .add_fungible_state("feeOwner", testing::graph_seal(3), fee.value())
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        let witness_id = consume(&mut stock, transition, &[Outpoint::new(testing::txid(), 1)], 101);
+
+        let wrapper = stock
+            .contract_wrapper::<TransferFeeAsset>(contract_id)
+            .unwrap();
+        let fees = wrapper
+            .fee_allocations(vec![Outpoint::new(witness_id, 3)])
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(fees, vec![25]);
+
+        // the issuer turns the fee into units of the asset
+        let transition = stock
+            .transition_builder(contract_id, "collect")
+            .unwrap()
+            .add_input(
+                Opout::new(opid, OS_FEE, 0),
+                AllocatedState::Amount(RevealedValue::from(25u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 25u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let collect_id = transition.id();
+        let collected = consume(&mut stock, transition, &[Outpoint::new(witness_id, 3)], 102);
+
+        let wrapper = stock
+            .contract_wrapper::<TransferFeeAsset>(contract_id)
+            .unwrap();
+        let unspent = [1, 2].map(|vout| Outpoint::new(witness_id, vout)).to_vec();
+        let mut allocations = wrapper
+            .allocations(unspent)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        allocations.sort();
+        assert_eq!(allocations, vec![400, 575]);
+        let issuer = wrapper
+            .allocations(vec![Outpoint::new(collected, 1)])
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(issuer, vec![25]);
+
+        testing::validate_history::<TransferFeeAsset>(&stock, &resolver, contract_id, &[
+            collect_id,
+        ])
+        .unwrap();
+    }
+
+    #[test]
+    fn fee_mismatch_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(fee(1000, 1000, 250));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        // the holder pays the issuer 20 units instead of 2.5% of the inputs
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 980u64)
+            .unwrap()
+            .add_fungible_state("feeOwner", testing::graph_seal(2), 20u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            101,
+        );
+
+        let err =
+            testing::validate_history::<TransferFeeAsset>(&stock, &resolver, contract_id, &[opid])
+                .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::FeeMismatch));
+    }
+}
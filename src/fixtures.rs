@@ -0,0 +1,643 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stock fixture used by the wrapper unit tests.
+//!
+//! The stock under `tests/fixtures/stock` contains a contract for each of the [`FIXTURES`], with
+//! their ids listed in `contracts.txt`. Schemas with optional global state get two contracts, one
+//! with the optional state absent and another (`*-full`) with it present. After a schema change,
+//! regenerate the fixture with `RGB_UPDATE_STOCK=1 cargo test --lib` and commit the result.
+//!
+//! The builders of the fixtures need all the schemata; with only some of them enabled the tests
+//! can still read the stored stock.
+
+#![cfg_attr(not(all_schemas), allow(dead_code, unused_imports))]
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Once;
+
+use amplify::confinement::SmallBlob;
+use amplify::Wrapper;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::containers::ConsignmentExt;
+use rgbstd::contract::ContractBuilder;
+use rgbstd::invoice::Precision;
+use rgbstd::persistence::fs::FsBinStore;
+use rgbstd::persistence::Stock;
+use rgbstd::secp256k1::{Secp256k1, SecretKey};
+use rgbstd::stl::{
+    AssetSpec, Details, EmbeddedMedia, MediaType, Name, ProofOfReserves, RejectListUrl, TokenData,
+};
+use rgbstd::{Allocation, Amount, ChainNet, ContractId, Outpoint, OutputSeal, TokenIndex};
+use schemata_tools::testing::{
+    asset_on, builder_on, issue, seal, spec, terms, txid, MockResolver, CHAIN_NET, TEST_CHAIN_NETS,
+};
+
+#[cfg(all_schemas)]
+use crate::height::block_height;
+#[cfg(all_schemas)]
+use crate::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+    DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+    GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+    NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+    ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+    SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+    TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+    VerifiableCredential, VestingAsset, WrappedBtcAsset,
+};
+
+const UPDATE_ENV: &str = "RGB_UPDATE_STOCK";
+
+pub const ISSUED_SUPPLY: u64 = 1000;
+pub const MAX_SUPPLY: u64 = 1500;
+pub const SWAP_PRICE: u64 = 2500;
+pub const TOKEN_INDEX: u32 = 2;
+/// Fractions of the FUA fixture token, owned by `seal(1)` and `seal(2)`.
+pub const FRACTIONS: [u64; 2] = [3, 1];
+/// Token indexes of the UDC fixture, allocated to `seal(1)` and `seal(2)`.
+pub const COLLECTION: [u32; 2] = [TOKEN_INDEX, 5];
+/// Allocations of the SFA fixture as the vout of their seal, the token index and the amount. The
+/// fixture declares the tokens of [`COLLECTION`].
+pub const SFA_ALLOCATIONS: [(u32, u32, u64); 3] =
+    [(1, TOKEN_INDEX, 600), (2, TOKEN_INDEX, 400), (3, 5, 50)];
+/// Face value of each bond of the bond fixture.
+pub const FACE_VALUE: u64 = 100_000;
+/// Coupon rate of the bond fixture, in basis points.
+pub const COUPON_RATE: u64 = 450;
+/// Maturity height of the bond fixture.
+pub const MATURITY: u32 = 900_000;
+/// Amount of the asset granting a vote right in the governance fixture, whose only holder gets a
+/// single vote right. The builder keeps vote rights in a hash set, so a genesis with several of
+/// them has no stable contract id.
+pub const VOTING_UNIT: u64 = ISSUED_SUPPLY;
+/// Ticket indexes of the ticket fixture, allocated to `seal(1)` and `seal(2)`.
+pub const TICKETS: [u32; 2] = [1, 4];
+/// Supply locked by the vesting fixture in the allocation of `seal(2)`, the rest of the issued
+/// supply being owned by `seal(1)`.
+pub const VESTED_SUPPLY: u64 = 600;
+/// Cliff height of the vesting schedule of the vesting fixture.
+pub const VESTING_CLIFF: u32 = 850_000;
+/// End height of the vesting schedule of the vesting fixture.
+pub const VESTING_END: u32 = 900_000;
+/// Lock height of the timelock fixture.
+pub const LOCK_HEIGHT: u32 = 900_000;
+/// Expiry height of the subscription fixture.
+pub const EXPIRY: u32 = 880_000;
+/// Maximum number of editions of the limited edition fixture.
+pub const MAX_EDITIONS: u32 = 100;
+/// Number of blocks of an epoch of the epoch inflatable fixture, about a year of blocks.
+pub const EPOCH_LENGTH: u32 = 52_560;
+/// Maximum supply the epoch inflatable fixture may issue within an epoch.
+pub const EPOCH_CAP: u64 = 200;
+/// Fee rate of the transfer-fee fixture, in basis points.
+pub const FEE_RATE: u64 = 25;
+/// Strike price of the option fixture, in satoshis per unit of the underlying asset.
+pub const STRIKE: u64 = 3000;
+/// Expiry height of the option fixture.
+pub const OPTION_EXPIRY: u32 = 890_000;
+
+/// Names of the contracts in the fixture stock.
+pub const FIXTURES: [&str; 40] = [
+    "nia",
+    "cfa",
+    "cfa-full",
+    "uda",
+    "uda-full",
+    "fua",
+    "collection",
+    "pfa",
+    "ifa",
+    "ifa-full",
+    "ria",
+    "sba",
+    "sfa",
+    "stablecoin",
+    "bond",
+    "governance",
+    "ticket",
+    "vesting",
+    "timelock",
+    "pfan",
+    "freezable",
+    "tifa",
+    "wbtc",
+    "fee",
+    "dividend",
+    "escrow",
+    "swap",
+    "carbon",
+    "provenance",
+    "subscription",
+    "edition",
+    "eifa",
+    "claim",
+    "registry",
+    "pausable",
+    "rental",
+    "option",
+    "credential",
+    "burnable",
+    "rebasing",
+];
+
+/// Output sealed by the genesis seal `seal(vout)` of the fixtures.
+pub fn fixture_seal(vout: u32) -> OutputSeal { OutputSeal::new(Outpoint::new(txid(), vout)) }
+
+pub fn details() -> Details { Details::from_str("Asset details").unwrap() }
+
+pub fn reject_list_url() -> RejectListUrl { RejectListUrl::from("example.xyz/reject") }
+
+pub fn pubkey() -> CompressedPublicKey {
+    CompressedPublicKey::from_slice(&[
+        2, 199, 163, 211, 116, 75, 108, 119, 241, 66, 54, 236, 233, 189, 142, 108, 37, 135, 56,
+        128, 200, 176, 199, 9, 117, 132, 72, 200, 167, 185, 4, 64, 53,
+    ])
+    .unwrap()
+}
+
+/// Key authorized by the PFA-N and threshold inflatable asset fixtures next to [`pubkey`].
+pub fn cosigner_pubkey() -> CompressedPublicKey {
+    let key = SecretKey::from_slice(&[0x43; 32]).unwrap();
+    CompressedPublicKey(key.public_key(&Secp256k1::signing_only()))
+}
+
+/// Asset offered by the swap order fixture.
+pub fn offered_asset() -> ContractId { ContractId::from([0x11; 32]) }
+
+/// Asset in which the swap order fixture is priced.
+pub fn counter_asset() -> ContractId { ContractId::from([0x22; 32]) }
+
+/// Asset which the holders of the option fixture can buy.
+pub fn underlying_asset() -> ContractId { ContractId::from([0x33; 32]) }
+
+/// Reserve attestation of the wrapped BTC asset fixture.
+pub fn reserve_proof() -> ProofOfReserves {
+    let proof = SmallBlob::try_from_iter(vec![0x52; 64]).unwrap();
+    ProofOfReserves::new(Outpoint::new(txid(), 0), proof)
+}
+
+pub fn token_data(full: bool) -> TokenData {
+    TokenData {
+        index: TokenIndex::from_inner(TOKEN_INDEX),
+        preview: full.then(|| EmbeddedMedia {
+            ty: MediaType::with("image/*"),
+            data: SmallBlob::try_from_iter(vec![0xde, 0xad, 0xbe, 0xef]).unwrap(),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Builder of the fixture contract `name`, one of [`FIXTURES`].
+#[cfg(all_schemas)]
+pub fn fixture(name: &str) -> ContractBuilder { fixture_on(name, CHAIN_NET) }
+
+/// Builder of the fixture contract `name` issued on `chain_net`.
+///
+/// Only the fixtures on [`CHAIN_NET`] are stored in the fixture stock; the ones on other networks
+/// are deterministic as well and differ only by their contract id.
+#[cfg(all_schemas)]
+pub fn fixture_on(name: &str, chain_net: ChainNet) -> ContractBuilder {
+    let full = name.ends_with("-full");
+    match name.trim_end_matches("-full") {
+        "nia" => asset_on::<NonInflatableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY),
+        "cfa" => {
+            let builder = builder_on::<CollectibleFungibleAsset>(chain_net)
+                .add_global_state("name", Name::from("Test asset"))
+                .unwrap()
+                .add_global_state("precision", Precision::CentiMicro)
+                .unwrap()
+                .add_global_state("terms", terms())
+                .unwrap()
+                .add_global_state("issuedSupply", Amount::from(ISSUED_SUPPLY))
+                .unwrap()
+                .add_fungible_state("assetOwner", seal(1), ISSUED_SUPPLY)
+                .unwrap();
+            if !full {
+                return builder;
+            }
+            builder.add_global_state("details", details()).unwrap()
+        }
+        "uda" => builder_on::<UniqueDigitalAsset>(chain_net)
+            .add_global_state("spec", AssetSpec::new("TEST", "Test uda", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("tokens", token_data(full))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                seal(1),
+                Allocation::with(TokenIndex::from_inner(TOKEN_INDEX), 1),
+            )
+            .unwrap(),
+        "fua" => builder_on::<FractionalUniqueAsset>(chain_net)
+            .add_global_state("spec", AssetSpec::new("TEST", "Test fua", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("tokens", token_data(false))
+            .unwrap()
+            .add_global_state("issuedSupply", Amount::from(FRACTIONS.iter().sum::<u64>()))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                seal(1),
+                Allocation::with(TokenIndex::from_inner(TOKEN_INDEX), FRACTIONS[0]),
+            )
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                seal(2),
+                Allocation::with(TokenIndex::from_inner(TOKEN_INDEX), FRACTIONS[1]),
+            )
+            .unwrap(),
+        "collection" => {
+            let mut builder = builder_on::<UniqueDigitalCollection>(chain_net)
+                .add_global_state(
+                    "spec",
+                    AssetSpec::new("TEST", "Test collection", Precision::Indivisible),
+                )
+                .unwrap()
+                .add_global_state("terms", terms())
+                .unwrap();
+            for (vout, index) in (1..).zip(COLLECTION) {
+                let index = TokenIndex::from_inner(index);
+                builder = builder
+                    .add_global_state("tokens", TokenData {
+                        index,
+                        ..Default::default()
+                    })
+                    .unwrap()
+                    .add_data("assetOwner", seal(vout), Allocation::with(index, 1))
+                    .unwrap();
+            }
+            builder
+        }
+        "pfa" => asset_on::<PermissionedFungibleAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("pubkey", pubkey())
+            .unwrap(),
+        "ifa" => {
+            let builder =
+                asset_on::<InflatableFungibleAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+                    .add_global_state("maxSupply", Amount::from(MAX_SUPPLY))
+                    .unwrap()
+                    .add_fungible_state("inflationAllowance", seal(2), MAX_SUPPLY - ISSUED_SUPPLY)
+                    .unwrap()
+                    .add_rights("replaceRight", seal(3))
+                    .unwrap();
+            if !full {
+                return builder;
+            }
+            builder
+                .add_global_state("rejectListUrl", reject_list_url())
+                .unwrap()
+        }
+        "ria" => asset_on::<ReissuableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_rights("reissueRight", seal(2))
+            .unwrap(),
+        "sba" => asset_on::<SoulboundAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_rights("revokeRight", seal(2))
+            .unwrap(),
+        "sfa" => {
+            let mut builder = builder_on::<SemiFungibleAsset>(chain_net)
+                .add_global_state("spec", spec())
+                .unwrap()
+                .add_global_state("terms", terms())
+                .unwrap();
+            for index in COLLECTION {
+                builder = builder
+                    .add_global_state("tokens", TokenData {
+                        index: TokenIndex::from_inner(index),
+                        ..Default::default()
+                    })
+                    .unwrap();
+            }
+            for (vout, index, amount) in SFA_ALLOCATIONS {
+                let allocation = Allocation::with(TokenIndex::from_inner(index), amount);
+                builder = builder
+                    .add_data("assetOwner", seal(vout), allocation)
+                    .unwrap();
+            }
+            builder
+        }
+        "stablecoin" => asset_on::<StablecoinAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("maxSupply", Amount::from(MAX_SUPPLY))
+            .unwrap()
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_fungible_state("inflationAllowance", seal(2), MAX_SUPPLY - ISSUED_SUPPLY)
+            .unwrap(),
+        "bond" => asset_on::<BondAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("faceValue", Amount::from(FACE_VALUE))
+            .unwrap()
+            .add_global_state("couponRate", Amount::from(COUPON_RATE))
+            .unwrap()
+            .add_global_state("maturity", block_height(MATURITY))
+            .unwrap(),
+        "governance" => asset_on::<GovernanceAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("votingUnit", Amount::from(VOTING_UNIT))
+            .unwrap()
+            .add_rights("voteRight", seal(1))
+            .unwrap(),
+        "ticket" => {
+            let mut builder = builder_on::<TicketAsset>(chain_net)
+                .add_global_state(
+                    "spec",
+                    AssetSpec::new("TKT", "Test tickets", Precision::Indivisible),
+                )
+                .unwrap()
+                .add_global_state("terms", terms())
+                .unwrap();
+            for (vout, index) in (1..).zip(TICKETS) {
+                let index = TokenIndex::from_inner(index);
+                builder = builder
+                    .add_global_state("tokens", TokenData {
+                        index,
+                        ..Default::default()
+                    })
+                    .unwrap()
+                    .add_data("assetOwner", seal(vout), Allocation::with(index, 1))
+                    .unwrap();
+            }
+            builder
+        }
+        "vesting" => {
+            asset_on::<VestingAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY - VESTED_SUPPLY)
+                .add_global_state("vestedSupply", Amount::from(VESTED_SUPPLY))
+                .unwrap()
+                .add_global_state("vestingCliff", block_height(VESTING_CLIFF))
+                .unwrap()
+                .add_global_state("vestingEnd", block_height(VESTING_END))
+                .unwrap()
+                .add_fungible_state("lockedAsset", seal(2), VESTED_SUPPLY)
+                .unwrap()
+        }
+        "timelock" => asset_on::<TimelockAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("lockHeight", block_height(LOCK_HEIGHT))
+            .unwrap(),
+        "pfan" => asset_on::<MultiKeyPermissionedAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_global_state("pubkey", cosigner_pubkey())
+            .unwrap(),
+        "freezable" => asset_on::<FreezableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_rights("freezeRight", seal(2))
+            .unwrap(),
+        "tifa" => asset_on::<ThresholdInflatableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("maxSupply", Amount::from(MAX_SUPPLY))
+            .unwrap()
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_global_state("pubkey", cosigner_pubkey())
+            .unwrap()
+            .add_global_state("inflationThreshold", Amount::from(2u64))
+            .unwrap()
+            .add_fungible_state("inflationAllowance", seal(2), MAX_SUPPLY - ISSUED_SUPPLY)
+            .unwrap()
+            .add_data("inflationApproval", seal(3), Allocation::with(0, 0))
+            .unwrap(),
+        "wbtc" => asset_on::<WrappedBtcAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_metadata("reserveProof", reserve_proof())
+            .unwrap()
+            .add_global_state("reserves", reserve_proof())
+            .unwrap()
+            .add_rights("mintRight", seal(2))
+            .unwrap(),
+        "fee" => asset_on::<TransferFeeAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("feeRate", Amount::from(FEE_RATE))
+            .unwrap(),
+        "dividend" => asset_on::<DividendAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_rights("distributeRight", seal(2))
+            .unwrap(),
+        "escrow" => asset_on::<EscrowAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY),
+        "swap" => builder_on::<SwapOrder>(chain_net)
+            .add_global_state("offeredAsset", SwapOrder::asset_ref(offered_asset()))
+            .unwrap()
+            .add_global_state("offeredAmount", Amount::from(ISSUED_SUPPLY))
+            .unwrap()
+            .add_global_state("counterAsset", SwapOrder::asset_ref(counter_asset()))
+            .unwrap()
+            .add_global_state("price", Amount::from(SWAP_PRICE))
+            .unwrap()
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_rights("orderRight", seal(1))
+            .unwrap(),
+        "carbon" => builder_on::<CarbonCreditAsset>(chain_net)
+            .add_global_state("name", Name::from("Test asset"))
+            .unwrap()
+            .add_global_state("details", details())
+            .unwrap()
+            .add_global_state("precision", Precision::CentiMicro)
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("issuedSupply", Amount::from(ISSUED_SUPPLY))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(1), ISSUED_SUPPLY)
+            .unwrap(),
+        "provenance" => builder_on::<ProvenanceAsset>(chain_net)
+            .add_global_state(
+                "spec",
+                AssetSpec::new("TEST", "Test provenance", Precision::Indivisible),
+            )
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("tokens", token_data(false))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                seal(1),
+                Allocation::with(TokenIndex::from_inner(TOKEN_INDEX), 1),
+            )
+            .unwrap(),
+        "subscription" => builder_on::<SubscriptionAsset>(chain_net)
+            .add_global_state(
+                "spec",
+                AssetSpec::new("TEST", "Test subscription", Precision::Indivisible),
+            )
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("tokens", token_data(false))
+            .unwrap()
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_global_state("expiry", block_height(EXPIRY))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                seal(1),
+                Allocation::with(TokenIndex::from_inner(TOKEN_INDEX), 1),
+            )
+            .unwrap(),
+        "edition" => builder_on::<LimitedEditionAsset>(chain_net)
+            .add_global_state(
+                "spec",
+                AssetSpec::new("TEST", "Test editions", Precision::Indivisible),
+            )
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("maxEditions", Amount::from(MAX_EDITIONS as u64))
+            .unwrap()
+            .add_rights("mintRight", seal(1))
+            .unwrap(),
+        "eifa" => asset_on::<EpochInflatableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("maxSupply", Amount::from(MAX_SUPPLY))
+            .unwrap()
+            .add_global_state("epochLength", Amount::from(EPOCH_LENGTH as u64))
+            .unwrap()
+            .add_global_state("epochCap", Amount::from(EPOCH_CAP))
+            .unwrap()
+            .add_fungible_state("inflationAllowance", seal(2), MAX_SUPPLY - ISSUED_SUPPLY)
+            .unwrap()
+            .add_data("epochIssuance", seal(3), Allocation::with(0, 0))
+            .unwrap(),
+        "claim" => asset_on::<ClaimableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("pubkey", pubkey())
+            .unwrap(),
+        "registry" => builder_on::<NameRegistry>(chain_net)
+            .add_global_state("spec", AssetSpec::new("TEST", "Test names", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_rights("registerRight", seal(1))
+            .unwrap(),
+        "pausable" => asset_on::<PausableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_rights("pauseRight", seal(2))
+            .unwrap(),
+        "rental" => builder_on::<RentalAsset>(chain_net)
+            .add_global_state("spec", AssetSpec::new("TEST", "Test rental", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("tokens", token_data(false))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                seal(1),
+                Allocation::with(TokenIndex::from_inner(TOKEN_INDEX), 1),
+            )
+            .unwrap(),
+        "option" => asset_on::<OptionAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("underlyingAsset", OptionAsset::asset_ref(underlying_asset()))
+            .unwrap()
+            .add_global_state("strike", Amount::from(STRIKE))
+            .unwrap()
+            .add_global_state("expiry", block_height(OPTION_EXPIRY))
+            .unwrap(),
+        "credential" => builder_on::<VerifiableCredential>(chain_net)
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_rights("assetOwner", seal(1))
+            .unwrap()
+            .add_rights("revokeRight", seal(2))
+            .unwrap(),
+        "burnable" => asset_on::<BurnableAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY),
+        "rebasing" => asset_on::<RebasingAsset>(chain_net, ISSUED_SUPPLY, ISSUED_SUPPLY)
+            .add_global_state("pubkey", pubkey())
+            .unwrap()
+            .add_rights("rebaseRight", seal(2))
+            .unwrap(),
+        _ => unreachable!("unknown fixture {name}"),
+    }
+}
+
+fn dir() -> PathBuf { PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/stock") }
+
+#[cfg(all_schemas)]
+fn generate() {
+    let mut stock = Stock::in_memory();
+    let mut ids = String::new();
+    for name in FIXTURES {
+        let contract = issue(fixture(name));
+        ids.push_str(&format!("{name} {}\n", contract.contract_id()));
+        stock
+            .import_contract(contract, MockResolver::with_chain_net(CHAIN_NET))
+            .unwrap();
+    }
+    let provider = FsBinStore::new(dir()).unwrap();
+    stock.make_persistent(provider, false).unwrap();
+    stock.store().unwrap();
+    fs::write(dir().join("contracts.txt"), ids).unwrap();
+}
+
+/// Loads the fixture stock, regenerating it first if requested.
+pub fn stock() -> Stock {
+    #[cfg(all_schemas)]
+    static GENERATE: Once = Once::new();
+    #[cfg(all_schemas)]
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        GENERATE.call_once(generate);
+    }
+    Stock::load(FsBinStore::new(dir()).unwrap(), false).expect("missing stock fixture")
+}
+
+fn contract_ids() -> BTreeMap<String, ContractId> {
+    let ids = fs::read_to_string(dir().join("contracts.txt")).expect("missing stock fixture");
+    ids.lines()
+        .map(|line| {
+            let (name, id) = line.split_once(' ').unwrap();
+            (name.to_owned(), ContractId::from_str(id).unwrap())
+        })
+        .collect()
+}
+
+/// Id of the fixture contract with the given name.
+pub fn contract_id(name: &str) -> ContractId { contract_ids()[name] }
+
+#[test]
+#[cfg(all_schemas)]
+fn up_to_date() {
+    let ids = contract_ids();
+    for name in FIXTURES {
+        assert_eq!(
+            issue(fixture(name)).contract_id(),
+            ids[name],
+            "{name} fixture is outdated, regenerate it with {UPDATE_ENV}=1"
+        );
+    }
+}
+
+#[test]
+#[cfg(all_schemas)]
+fn other_chain_nets() {
+    for chain_net in TEST_CHAIN_NETS {
+        let mut stock = Stock::in_memory();
+        for name in FIXTURES {
+            let contract = issue(fixture_on(name, chain_net));
+            assert_eq!(contract.contract_id(), issue(fixture_on(name, chain_net)).contract_id());
+            assert_eq!(
+                contract.contract_id() == contract_id(name),
+                chain_net == CHAIN_NET,
+                "{name}"
+            );
+            stock
+                .import_contract(contract, MockResolver::with_chain_net(chain_net))
+                .unwrap();
+        }
+    }
+}
@@ -0,0 +1,490 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Freezable asset schema.
+//!
+//! A variant of the NIA schema for regulated assets, whose issuer keeps a freeze right allowing it
+//! to record UTXOs in the `frozen` global state, each of them once: the allocations assigned to
+//! them can't be transferred anymore.
+//!
+//! Validation scripts can't see the outpoints spent by a transition, so each transfer commits to
+//! the UTXOs it spends in its `spentUtxos` global state, which the scripts check against the
+//! frozen ones. Wallets must commit to the UTXOs they actually spend, which the scripts can't
+//! check, see [`crate::height`] for the same limitation about heights: the wrapper doesn't offer
+//! frozen allocations as spendable, see [`FungibleAssetWrapper::spendable`], and the `audit`
+//! module of `rgb-schemata-tools` reports the transfers spending them anyway.
+//!
+//! The freeze is therefore advisory and not an administrative control: a holder committing to
+//! other UTXOs than the ones it spends transfers frozen allocations, which validate. Marking the
+//! allocations themselves as frozen would need the issuer to spend them, while only their holders
+//! can close their seals. Regulated issuers needing to block the transfers must use a schema
+//! checking them against a signature of the issuer, like PFA.
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, Outpoint, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, FREEZABLE_FREEZE, FREEZABLE_TRANSFER, NIA_GENESIS};
+use crate::selection::Spendable;
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_FROZEN,
+    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_SPENT_UTXOS, GS_TERMS, OS_ASSET, OS_FREEZE, TS_FREEZE,
+    TS_TRANSFER,
+};
+
+pub const FREEZABLE_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xd1, 0xbc, 0x01, 0x06, 0x9a, 0x1c, 0xfe, 0x83, 0xe1, 0x09, 0x37, 0xe2, 0x8e, 0xf1, 0x8a, 0x93,
+    0x37, 0x01, 0x90, 0x2b, 0x7f, 0x8b, 0x4f, 0xe7, 0x14, 0x0c, 0x23, 0xf8, 0xfd, 0x1d, 0xb9, 0xf9,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn freezable_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    FREEZABLE_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    FREEZABLE_FREEZE.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("FreezableAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_FROZEN => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.Outpoint")),
+                name: fname!("frozen"),
+            },
+            GS_SPENT_UTXOS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.Outpoint")),
+                name: fname!("spentUtxos"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_FREEZE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("freezeRight"),
+                default_transition: TS_FREEZE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_FREEZE => Occurrences::Once,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_SPENT_UTXOS => Occurrences::OnceOrMore
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(FREEZABLE_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_FREEZE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_FROZEN => Occurrences::OnceOrMore
+                    },
+                    inputs: tiny_bmap! {
+                        OS_FREEZE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_FREEZE => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(FREEZABLE_FREEZE.lib_site())
+                },
+                name: fname!("freeze"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn freezable_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, FREEZABLE_TRANSFER, FREEZABLE_FREEZE])
+}
+
+#[derive(Default)]
+pub struct FreezableAsset;
+
+impl IssuerWrapper for FreezableAsset {
+    type Wrapper<S: ContractStateRead> = FreezableWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(freezable_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(freezable_scripts).clone()
+    }
+}
+
+impl FreezableAsset {
+    /// First revision of the freezable asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "FreezableAsset",
+        schema_id: FREEZABLE_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "NIA variant whose issuer can freeze the allocations assigned to given \
+                        UTXOs, enforced through the UTXOs committed by transfers.",
+    };
+}
+
+impl IssuerInfo for FreezableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for FreezableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct FreezableWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for FreezableWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for FreezableWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<FreezableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> FreezableWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the freezable asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<FreezableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the freezable asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the UTXOs frozen by the issuer.
+    pub fn frozen(&self) -> BTreeSet<Outpoint> { or_panic(self.try_frozen()) }
+
+    pub fn try_frozen(&self) -> Result<BTreeSet<Outpoint>, Error> {
+        globals(&self.0, GS_FROZEN)?.collect()
+    }
+
+    /// Checks whether the allocations assigned to `outpoint` are frozen.
+    pub fn is_frozen(&self, outpoint: Outpoint) -> bool { or_panic(self.try_is_frozen(outpoint)) }
+
+    pub fn try_is_frozen(&self, outpoint: Outpoint) -> Result<bool, Error> {
+        Ok(self.try_frozen()?.contains(&outpoint))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn freeze_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_freeze_rights(filter))
+    }
+
+    pub fn try_freeze_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_FREEZE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for FreezableWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        FreezableWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        FreezableWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        FreezableWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        FreezableWrapper::try_allocations(self, filter)
+    }
+
+    /// Leaves out the allocations assigned to frozen UTXOs.
+    fn try_spendable(
+        &self,
+        outpoints: impl IntoIterator<Item = Outpoint>,
+    ) -> Result<Spendable, Error> {
+        let frozen = self.try_frozen()?;
+        let outpoints = outpoints
+            .into_iter()
+            .filter(|outpoint| !frozen.contains(outpoint))
+            .collect::<Vec<_>>();
+        Ok(Spendable::new(self.try_allocations(outpoints)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(all_schemas)]
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    #[cfg(all_schemas)]
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = freezable_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(FREEZABLE_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<FreezableAsset>(fixtures::contract_id("freezable"))
+            .unwrap();
+        assert_eq!(wrapper.version(), FreezableAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert!(wrapper.frozen().is_empty());
+
+        let holder = Outpoint::new(testing::txid(), 1);
+        assert!(!wrapper.is_frozen(holder));
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .freeze_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Freezing of the allocations of a freezable asset.
+    //
+    // The issuer spends the freeze right in a mined witness TX to freeze the genesis UTXO holding
+    // the whole supply. Scripts only check the UTXOs committed by a transfer, so a later transfer
+    // of the frozen allocation committing to another UTXO is still accepted by the validation of
+    // the history: the wrapper must no longer offer it as spendable, and the audit must report its
+    // transfer once mined after the freeze.
+
+    /// Freezable asset allocated to `seal(1)`, with the freeze right at
+    /// `seal(2)`.
+    #[cfg(all_schemas)]
+    fn freezable(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::asset::<FreezableAsset>(issued, allocated)
+            .add_rights("freezeRight", testing::seal(2))
+            .unwrap()
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn freeze_holder() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(freezable(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let holder = Outpoint::new(testing::txid(), 1);
+        let wrapper = stock
+            .contract_wrapper::<FreezableAsset>(contract_id)
+            .unwrap();
+        assert!(!wrapper.is_frozen(holder));
+        assert_eq!(wrapper.spendable([holder]).balance(), Amount::from(1000u64));
+
+        let transition = stock
+            .transition_builder(contract_id, "freeze")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_FREEZE, 0), AllocatedState::Void)
+            .unwrap()
+            .add_global_state("frozen", holder)
+            .unwrap()
+            .add_rights("freezeRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let freeze_op = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 2)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let freeze_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(100));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<FreezableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.frozen(), [holder].into());
+        assert!(wrapper.is_frozen(holder));
+        assert!(wrapper.spendable([holder]).allocations.is_empty());
+        let rights = wrapper
+            .freeze_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(rights.contains(&OutputSeal::new(Outpoint::new(freeze_id, 1))), "{rights:?}");
+
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 1000u64)
+            .unwrap()
+            .add_global_state("spentUtxos", Outpoint::new(testing::txid(), 3))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        let fascia = testing::anchor(contract_id, transition, &[holder], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let transfer_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(101));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::FrozenSpend {
+            op: opid,
+            input: Opout::new(genesis_id, OS_ASSET, 0),
+            utxo: holder,
+        }]);
+        assert_eq!((report.issued, report.burned, report.allocated), (1000, 0, 1000));
+
+        resolver.set_witness_ord(transfer_id, testing::mined_at(99));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+
+        testing::validate_history::<FreezableAsset>(&stock, &resolver, contract_id, &[
+            freeze_op, opid,
+        ])
+        .unwrap();
+    }
+}
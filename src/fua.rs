@@ -0,0 +1,393 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fractional unique asset (FUA) schema.
+//!
+//! Like UDA, the contract issues a single unique token, but its [`Allocation`]s may own any
+//! fraction of it. Transfers split and merge the fractions among holders, while the validation
+//! script checks that their sum always equals the total number of fractions issued.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::{
+    Allocation, Amount, GlobalDetails, Outpoint, OutputSeal, OwnedStateSchema, SchemaId,
+    TransitionDetails,
+};
+use strict_types::{StrictDeserialize, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, FUA_GENESIS, FUA_SHARED, FUA_TRANSFER};
+use crate::wrapper::{check_family, global, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_ATTACH, GS_ISSUED_SUPPLY, GS_NOMINAL,
+    GS_TERMS, GS_TOKENS, OS_ASSET, TS_TRANSFER,
+};
+
+pub const FUA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x55, 0x1d, 0x1b, 0x97, 0x77, 0xfc, 0xf6, 0xd1, 0x5b, 0xd3, 0x11, 0x7e, 0xd8, 0x9b, 0xe7, 0x0f,
+    0xb0, 0x7f, 0x05, 0x68, 0x5e, 0x0f, 0x51, 0xed, 0xcd, 0x86, 0x5c, 0xcf, 0xa4, 0x35, 0x93, 0xff,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn fua_schema() -> Schema {
+    let types = standard_types();
+
+    FUA_GENESIS.verify(&[(0, INSTR_PUTA)]);
+    FUA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    FUA_SHARED.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("FractionalUniqueAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+            GS_ATTACH => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AttachmentType")),
+                name: fname!("attachmentTypes"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::Once,
+                GS_ATTACH => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(FUA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(FUA_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn fua_scripts() -> Scripts { SharedLibs::get().scripts(&[FUA_GENESIS, FUA_TRANSFER]) }
+
+#[derive(Default)]
+pub struct FractionalUniqueAsset;
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct FuaWrapper<S: ContractStateRead>(ContractData<S>);
+
+/// Share of the unique token owned by a holder.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display("{fraction}/{total}")]
+pub struct FractionShare {
+    /// Fractions of the token owned by the holder.
+    pub fraction: u64,
+    /// Total number of fractions issued.
+    pub total: u64,
+}
+
+impl FractionShare {
+    /// Share of the token as a number between 0 and 1.
+    pub fn ratio(self) -> f64 { self.fraction as f64 / self.total as f64 }
+}
+
+impl IssuerWrapper for FractionalUniqueAsset {
+    type Wrapper<S: ContractStateRead> = FuaWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(fua_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(fua_scripts).clone()
+    }
+}
+
+impl FractionalUniqueAsset {
+    /// First revision of the FUA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "FractionalUniqueAsset",
+        schema_id: FUA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Single unique token issued at genesis as a number of fractions owned by \
+                        its holders, and a transfer transition splitting and merging them while \
+                        preserving their sum.",
+    };
+}
+
+impl IssuerInfo for FractionalUniqueAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for FractionalUniqueAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+impl<S: ContractStateRead> SchemaWrapper<S> for FuaWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for FuaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<FractionalUniqueAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> FuaWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the FUA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<FractionalUniqueAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the FUA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn token_data(&self) -> TokenData { or_panic(self.try_token_data()) }
+
+    pub fn try_token_data(&self) -> Result<TokenData, Error> { global(&self.0, GS_TOKENS) }
+
+    /// Returns the total number of fractions the token is split into.
+    pub fn total_fractions(&self) -> u64 { or_panic(self.try_total_fractions()) }
+
+    pub fn try_total_fractions(&self) -> Result<u64, Error> {
+        global::<_, Amount>(&self.0, GS_ISSUED_SUPPLY).map(Amount::value)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Builds an invoice for receiving the given number of fractions of the token.
+    pub fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        fractions: u64,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary, fractions))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        fractions: u64,
+    ) -> Result<RgbInvoice, Error> {
+        let allocation = Allocation::with(self.try_token_data()?.index, fractions);
+        allocation_invoice(&self.0, beneficiary, allocation)
+    }
+
+    /// Returns the share of the token owned by each seal passing the filter, summing the
+    /// fractions of all the allocations assigned to the same seal.
+    pub fn holder_shares(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> BTreeMap<OutputSeal, FractionShare> {
+        or_panic(self.try_holder_shares(filter))
+    }
+
+    pub fn try_holder_shares(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> Result<BTreeMap<OutputSeal, FractionShare>, Error> {
+        let total = self.try_total_fractions()?;
+        let mut shares = BTreeMap::<_, FractionShare>::new();
+        for allocation in self.try_allocations(filter)? {
+            let fraction = Allocation::from_strict_serialized(allocation.state.into())
+                .map_err(|_| Error::InvalidOwned(OS_ASSET))?
+                .fraction()
+                .value();
+            let share = shares
+                .entry(allocation.seal)
+                .or_insert(FractionShare { fraction: 0, total });
+            // the validation keeps the sum of all fractions equal to the total
+            share.fraction += fraction;
+        }
+        Ok(shares)
+    }
+
+    /// Returns the share of the token owned by a wallet, summing the fractions assigned to all the
+    /// outpoints it controls.
+    pub fn owned_share(&self, outpoints: impl IntoIterator<Item = Outpoint>) -> FractionShare {
+        or_panic(self.try_owned_share(outpoints))
+    }
+
+    pub fn try_owned_share(
+        &self,
+        outpoints: impl IntoIterator<Item = Outpoint>,
+    ) -> Result<FractionShare, Error> {
+        let outpoints = outpoints.into_iter().collect::<Vec<_>>();
+        Ok(FractionShare {
+            fraction: self
+                .try_holder_shares(outpoints)?
+                .values()
+                .map(|share| share.fraction)
+                .sum(),
+            total: self.try_total_fractions()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+    use rgbstd::invoice::Precision;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = fua_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(FUA_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<FractionalUniqueAsset>(fixtures::contract_id("fua"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), AssetSpec::new("TEST", "Test fua", Precision::Indivisible));
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.token_data(), fixtures::token_data(false));
+        assert_eq!(wrapper.total_fractions(), 4);
+        assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 2);
+
+        let seal = |vout| OutputSeal::new(Outpoint::new(testing::txid(), vout));
+        let shares = wrapper.holder_shares(&FilterIncludeAll);
+        assert_eq!(
+            shares,
+            BTreeMap::from([
+                (seal(1), FractionShare {
+                    fraction: fixtures::FRACTIONS[0],
+                    total: 4
+                }),
+                (seal(2), FractionShare {
+                    fraction: fixtures::FRACTIONS[1],
+                    total: 4
+                }),
+            ])
+        );
+        assert_eq!(shares.values().map(|share| share.ratio()).sum::<f64>(), 1.0);
+
+        let outpoint = |vout| Outpoint::new(testing::txid(), vout);
+        assert_eq!(wrapper.owned_share([outpoint(2)]), FractionShare {
+            fraction: fixtures::FRACTIONS[1],
+            total: 4
+        });
+        assert_eq!(wrapper.owned_share([outpoint(1), outpoint(2)]).ratio(), 1.0);
+        assert_eq!(wrapper.owned_share([outpoint(3)]).fraction, 0);
+    }
+
+    #[test]
+    fn share() {
+        let share = FractionShare {
+            fraction: 1,
+            total: 4,
+        };
+        assert_eq!(share.to_string(), "1/4");
+        assert_eq!(share.ratio(), 0.25);
+    }
+}
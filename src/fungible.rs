@@ -0,0 +1,404 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schema-independent view of fungible asset contracts.
+//!
+//! [`FungibleView`] presents a contract of any fungible schema through the interface of the
+//! richest one, [`IfaWrapper`](crate::IfaWrapper), so that applications handling several assets
+//! can use a single code path. Contracts of schemata without inflation report a maximal supply
+//! equal to the issued one and no inflation allowance or replace rights; RIA contracts, which only
+//! reissue burned supply, report the supply issued by the genesis as the maximal one. CFA
+//! contracts, which have no ticker, report none.
+
+#[cfg(feature = "ifa")]
+use rgbstd::contract::RightsAllocation;
+use rgbstd::contract::{AssignmentsFilter, ContractData, FungibleAllocation, SchemaWrapper};
+use rgbstd::invoice::Precision;
+use rgbstd::persistence::ContractStateRead;
+#[cfg(feature = "ifa")]
+use rgbstd::stl::RejectListUrl;
+use rgbstd::stl::{AssetSpec, ContractTerms, Details, Name, Ticker};
+use rgbstd::{Amount, SchemaId};
+
+use crate::info::{SchemaFamily, SchemaInfo};
+use crate::wrapper::{global, globals, or_panic};
+#[cfg(feature = "nia")]
+use crate::NonInflatableAsset;
+#[cfg(feature = "pfa")]
+use crate::PermissionedFungibleAsset;
+#[cfg(feature = "ria")]
+use crate::ReissuableAsset;
+#[cfg(feature = "cfa")]
+use crate::{CollectibleFungibleAsset, GS_DETAILS, GS_NAME, GS_PRECISION};
+use crate::{
+    ContractWrapper, Error, FungibleAssetWrapper, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS, OS_ASSET,
+};
+#[cfg(feature = "ifa")]
+use crate::{InflatableFungibleAsset, GS_MAX_SUPPLY, GS_REJECT_LIST_URL, OS_INFLATION, OS_REPLACE};
+
+/// Fungible asset schema family of a contract seen through a [`FungibleView`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+pub enum FungibleSchema {
+    #[cfg(feature = "nia")]
+    #[display("NIA")]
+    Nia,
+    #[cfg(feature = "cfa")]
+    #[display("CFA")]
+    Cfa,
+    #[cfg(feature = "pfa")]
+    #[display("PFA")]
+    Pfa,
+    #[cfg(feature = "ifa")]
+    #[display("IFA")]
+    Ifa,
+    #[cfg(feature = "ria")]
+    #[display("RIA")]
+    Ria,
+}
+
+impl FungibleSchema {
+    /// Returns the family and the revision of a schema, if it is a fungible asset one.
+    pub fn detect(schema_id: SchemaId) -> Option<(Self, SchemaInfo)> {
+        #[cfg(feature = "nia")]
+        if let Some(info) = NonInflatableAsset::version_of(schema_id) {
+            return Some((Self::Nia, info));
+        }
+        #[cfg(feature = "cfa")]
+        if let Some(info) = CollectibleFungibleAsset::version_of(schema_id) {
+            return Some((Self::Cfa, info));
+        }
+        #[cfg(feature = "pfa")]
+        if let Some(info) = PermissionedFungibleAsset::version_of(schema_id) {
+            return Some((Self::Pfa, info));
+        }
+        #[cfg(feature = "ifa")]
+        if let Some(info) = InflatableFungibleAsset::version_of(schema_id) {
+            return Some((Self::Ifa, info));
+        }
+        #[cfg(feature = "ria")]
+        if let Some(info) = ReissuableAsset::version_of(schema_id) {
+            return Some((Self::Ria, info));
+        }
+        None
+    }
+
+    /// Tells whether the schema allows raising the supply of the asset above the one issued by the
+    /// genesis.
+    pub fn is_inflatable(self) -> bool {
+        #[cfg(feature = "ifa")]
+        if self == Self::Ifa {
+            return true;
+        }
+        false
+    }
+}
+
+/// Contract of any fungible asset schema, accessed with the interface of the IFA wrapper.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FungibleView<S: ContractStateRead> {
+    data: ContractData<S>,
+    schema: FungibleSchema,
+    version: SchemaInfo,
+}
+
+impl<S: ContractStateRead> SchemaWrapper<S> for FungibleView<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for FungibleView<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.data }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> { Ok(self.version) }
+}
+
+impl<S: ContractStateRead> FungibleView<S> {
+    /// Wraps the contract data, failing if it doesn't belong to a fungible asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, Error> {
+        let schema_id = data.schema.schema_id();
+        let (schema, version) =
+            FungibleSchema::detect(schema_id).ok_or(Error::NotFungible(schema_id))?;
+        Ok(Self {
+            data,
+            schema,
+            version,
+        })
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to a fungible asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    /// Returns the schema family of the contract.
+    pub fn schema(&self) -> FungibleSchema { self.schema }
+
+    /// Returns the ticker of the asset, which CFA contracts don't have.
+    pub fn ticker(&self) -> Option<Ticker> { or_panic(self.try_ticker()) }
+
+    pub fn try_ticker(&self) -> Result<Option<Ticker>, Error> {
+        Ok(self.try_spec()?.map(|spec| spec.ticker))
+    }
+
+    pub fn name(&self) -> Name { or_panic(self.try_name()) }
+
+    pub fn try_name(&self) -> Result<Name, Error> {
+        #[cfg(feature = "cfa")]
+        if self.schema == FungibleSchema::Cfa {
+            return global(&self.data, GS_NAME);
+        }
+        self.try_nominal().map(|spec| spec.name)
+    }
+
+    pub fn details(&self) -> Option<Details> { or_panic(self.try_details()) }
+
+    pub fn try_details(&self) -> Result<Option<Details>, Error> {
+        #[cfg(feature = "cfa")]
+        if self.schema == FungibleSchema::Cfa {
+            return globals(&self.data, GS_DETAILS)?.next().transpose();
+        }
+        self.try_nominal().map(|spec| spec.details)
+    }
+
+    pub fn precision(&self) -> Precision { or_panic(self.try_precision()) }
+
+    pub fn try_precision(&self) -> Result<Precision, Error> {
+        #[cfg(feature = "cfa")]
+        if self.schema == FungibleSchema::Cfa {
+            return global(&self.data, GS_PRECISION);
+        }
+        self.try_nominal().map(|spec| spec.precision)
+    }
+
+    /// Returns the asset specification, which CFA contracts don't have.
+    pub fn spec(&self) -> Option<AssetSpec> { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        #[cfg(feature = "cfa")]
+        if self.schema == FungibleSchema::Cfa {
+            return Ok(None);
+        }
+        self.try_nominal().map(Some)
+    }
+
+    fn try_nominal(&self) -> Result<AssetSpec, Error> { global(&self.data, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        global(&self.data, GS_TERMS)
+    }
+
+    /// Returns the URL of the issuer reject list, which only IFA contracts may have.
+    #[cfg(feature = "ifa")]
+    pub fn reject_list_url(&self) -> Option<RejectListUrl> { or_panic(self.try_reject_list_url()) }
+
+    #[cfg(feature = "ifa")]
+    pub fn try_reject_list_url(&self) -> Result<Option<RejectListUrl>, Error> {
+        if !self.schema.is_inflatable() {
+            return Ok(None);
+        }
+        globals(&self.data, GS_REJECT_LIST_URL)?.next().transpose()
+    }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.data, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts issued by the genesis and by each inflation, if any.
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.data, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    /// Returns the maximal supply of the asset, which is the issued one unless the schema is
+    /// inflatable or reissuable.
+    pub fn max_supply(&self) -> Amount { or_panic(self.try_max_supply()) }
+
+    pub fn try_max_supply(&self) -> Result<Amount, Error> {
+        #[cfg(feature = "ifa")]
+        if self.schema.is_inflatable() {
+            return globals::<_, Amount>(&self.data, GS_MAX_SUPPLY)?.sum();
+        }
+        #[cfg(feature = "ria")]
+        if self.schema == FungibleSchema::Ria {
+            // The genesis issuance is the oldest entry
+            return globals(&self.data, GS_ISSUED_SUPPLY)?
+                .last()
+                .unwrap_or(Err(Error::MissingGlobal(GS_ISSUED_SUPPLY)));
+        }
+        self.try_total_issued_supply()
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.data.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the inflation allowances, which only IFA contracts have.
+    #[cfg(feature = "ifa")]
+    pub fn inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_inflation_allocations(filter))
+    }
+
+    #[cfg(feature = "ifa")]
+    pub fn try_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        let allocations = if self.schema.is_inflatable() {
+            Some(self.data.fungible_raw(OS_INFLATION, filter)?)
+        } else {
+            None
+        };
+        Ok(allocations.into_iter().flatten())
+    }
+
+    /// Returns the replace rights, which only IFA contracts have.
+    #[cfg(feature = "ifa")]
+    pub fn replace_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_replace_rights(filter))
+    }
+
+    #[cfg(feature = "ifa")]
+    pub fn try_replace_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        let rights = if self.schema.is_inflatable() {
+            Some(self.data.rights_raw(OS_REPLACE, filter)?)
+        } else {
+            None
+        };
+        Ok(rights.into_iter().flatten())
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for FungibleView<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { FungibleView::try_spec(self) }
+
+    fn try_name(&self) -> Result<Name, Error> { FungibleView::try_name(self) }
+
+    fn try_precision(&self) -> Result<Precision, Error> { FungibleView::try_precision(self) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        FungibleView::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        FungibleView::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        FungibleView::try_allocations(self, filter)
+    }
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+    use rgbstd::persistence::Stock;
+
+    use super::*;
+    use crate::fixtures::{self, ISSUED_SUPPLY, MAX_SUPPLY};
+    use crate::UDA_SCHEMA_ID;
+
+    fn view<'s>(
+        stock: &'s Stock,
+        name: &str,
+    ) -> Result<FungibleView<impl ContractStateRead + 's>, Error> {
+        FungibleView::try_with(stock.contract_data(fixtures::contract_id(name)).unwrap())
+    }
+
+    #[test]
+    fn non_inflatable() {
+        let stock = fixtures::stock();
+        for (name, schema) in [
+            ("nia", FungibleSchema::Nia),
+            ("cfa", FungibleSchema::Cfa),
+            ("pfa", FungibleSchema::Pfa),
+            ("ria", FungibleSchema::Ria),
+        ] {
+            let view = view(&stock, name).unwrap();
+            assert_eq!(view.schema(), schema);
+            assert!(!schema.is_inflatable());
+            assert_eq!(view.total_issued_supply().value(), ISSUED_SUPPLY, "{name}");
+            assert_eq!(view.max_supply(), view.total_issued_supply(), "{name}");
+            assert_eq!(view.issuance_amounts().len(), 1);
+            assert_eq!(
+                view.allocations(FilterIncludeAll)
+                    .map(|a| a.state.value())
+                    .sum::<u64>(),
+                ISSUED_SUPPLY
+            );
+            assert_eq!(view.inflation_allocations(FilterIncludeAll).count(), 0);
+            assert_eq!(view.replace_rights(FilterIncludeAll).count(), 0);
+            assert_eq!(view.reject_list_url(), None);
+        }
+
+        let cfa = view(&stock, "cfa-full").unwrap();
+        assert_eq!(cfa.ticker(), None);
+        assert_eq!(cfa.spec(), None);
+        assert_eq!(cfa.details(), Some(fixtures::details()));
+        let nia = view(&stock, "nia").unwrap();
+        assert_eq!(nia.spec().map(|spec| spec.ticker), nia.ticker());
+        assert_eq!(nia.precision(), nia.spec().unwrap().precision);
+    }
+
+    #[test]
+    fn inflatable() {
+        let stock = fixtures::stock();
+        let view = view(&stock, "ifa-full").unwrap();
+        assert_eq!(view.schema(), FungibleSchema::Ifa);
+        assert_eq!(view.max_supply().value(), MAX_SUPPLY);
+        assert_eq!(view.total_issued_supply().value(), ISSUED_SUPPLY);
+        assert_eq!(view.reject_list_url(), Some(fixtures::reject_list_url()));
+        assert!(view.inflation_allocations(FilterIncludeAll).count() > 0);
+    }
+
+    #[test]
+    fn not_fungible() {
+        let stock = fixtures::stock();
+        assert_eq!(view(&stock, "uda").unwrap_err(), Error::NotFungible(UDA_SCHEMA_ID));
+    }
+}
@@ -0,0 +1,266 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Genesis with many initial allocations.
+//!
+//! Large launches assign the issued asset to many outputs at once, owned by wallets using either
+//! tapret or opret commitments. The genesis doesn't commit to the method: its seals are closed by
+//! the first opret or tapret output of the spending transaction. [`GenesisAllocations`] keeps
+//! the method of each seal for the wallets owning the outputs, checks that the allocations fit the
+//! genesis of the schema and adds them to a contract builder.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rgbstd::contract::{BuilderError, ContractBuilder};
+use rgbstd::schema::{OwnedStateSchema, Schema};
+use rgbstd::{Allocation, Amount, GenesisSeal, Method, Outpoint, RevealedData};
+
+use crate::{Error, OS_ASSET};
+
+/// State of an initial allocation.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, From)]
+pub enum GenesisState {
+    /// Amount of a fungible asset.
+    #[from]
+    Amount(Amount),
+    /// Token fraction of a UDA-like asset.
+    #[from]
+    Allocation(Allocation),
+}
+
+impl From<u64> for GenesisState {
+    fn from(amount: u64) -> Self { GenesisState::Amount(Amount::from(amount)) }
+}
+
+/// Initial allocation of the asset to a seal closed with the given method.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct GenesisAllocation {
+    pub seal: GenesisSeal,
+    pub method: Method,
+    pub state: GenesisState,
+}
+
+impl GenesisAllocation {
+    pub fn opret(seal: GenesisSeal, state: impl Into<GenesisState>) -> Self {
+        Self::with(seal, Method::OpretFirst, state)
+    }
+
+    pub fn tapret(seal: GenesisSeal, state: impl Into<GenesisState>) -> Self {
+        Self::with(seal, Method::TapretFirst, state)
+    }
+
+    pub fn with(seal: GenesisSeal, method: Method, state: impl Into<GenesisState>) -> Self {
+        GenesisAllocation {
+            seal,
+            method,
+            state: state.into(),
+        }
+    }
+
+    fn outpoint(&self) -> Outpoint { Outpoint::new(self.seal.txid, self.seal.vout.into_u32()) }
+}
+
+/// Initial allocations of a contract, checked against the genesis of its schema.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct GenesisAllocations {
+    pub allocations: Vec<GenesisAllocation>,
+}
+
+impl GenesisAllocations {
+    /// Checks that the allocations fit the genesis of the schema: their number and kind of state
+    /// must match the asset assignment, amounts must not be zero and all the seals on the same
+    /// output must be closed with the same method.
+    pub fn new(
+        schema: &Schema,
+        allocations: impl IntoIterator<Item = GenesisAllocation>,
+    ) -> Result<Self, Error> {
+        let allocations = allocations.into_iter().collect::<Vec<_>>();
+
+        let occurrences = schema
+            .genesis
+            .assignments
+            .get(&OS_ASSET)
+            .ok_or(Error::InvalidOwned(OS_ASSET))?;
+        let (min, max) = (occurrences.min_value(), occurrences.max_value());
+        let count = allocations.len();
+        if count < min as usize || count > max as usize {
+            return Err(Error::GenesisAllocations { count, min, max });
+        }
+
+        let fungible = match schema
+            .owned_types
+            .get(&OS_ASSET)
+            .map(|d| &d.owned_state_schema)
+        {
+            Some(OwnedStateSchema::Fungible(_)) => true,
+            Some(OwnedStateSchema::Structured(_)) => false,
+            _ => return Err(Error::InvalidOwned(OS_ASSET)),
+        };
+        let mut methods = BTreeMap::new();
+        for allocation in &allocations {
+            match allocation.state {
+                GenesisState::Amount(amount) if fungible => {
+                    if amount == Amount::ZERO {
+                        return Err(Error::ZeroAmount);
+                    }
+                }
+                GenesisState::Allocation(_) if !fungible => {}
+                _ => return Err(Error::InvalidOwned(OS_ASSET)),
+            }
+            let outpoint = allocation.outpoint();
+            if *methods.entry(outpoint).or_insert(allocation.method) != allocation.method {
+                return Err(Error::ConflictingMethods(outpoint));
+            }
+        }
+
+        Ok(GenesisAllocations { allocations })
+    }
+
+    /// Sum of the allocated amounts, to be declared as the issued supply of a fungible asset.
+    pub fn total(&self) -> Amount {
+        self.allocations
+            .iter()
+            .filter_map(|allocation| match allocation.state {
+                GenesisState::Amount(amount) => Some(amount),
+                GenesisState::Allocation(_) => None,
+            })
+            .fold(Amount::ZERO, |sum, amount| sum.saturating_add(amount))
+    }
+
+    /// Outputs whose seals are closed with the given method.
+    pub fn outpoints(&self, method: Method) -> BTreeSet<Outpoint> {
+        self.allocations
+            .iter()
+            .filter(|allocation| allocation.method == method)
+            .map(GenesisAllocation::outpoint)
+            .collect()
+    }
+
+    /// Adds the allocations to the builder of a contract of the schema they were checked against.
+    pub fn add_to(&self, mut builder: ContractBuilder) -> Result<ContractBuilder, BuilderError> {
+        for allocation in &self.allocations {
+            builder = match allocation.state {
+                GenesisState::Amount(amount) => {
+                    builder.add_fungible_state_raw(OS_ASSET, allocation.seal, amount)?
+                }
+                GenesisState::Allocation(state) => {
+                    builder.add_data_raw(OS_ASSET, allocation.seal, RevealedData::from(state))?
+                }
+            };
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use rgbstd::contract::{FilterIncludeAll, IssuerWrapper};
+    use schemata_tools::testing::{builder, seal, spec, stock_with, terms, txid, BLINDER};
+
+    use super::*;
+    use crate::{NonInflatableAsset, UniqueDigitalAsset};
+
+    #[test]
+    fn mixed_methods() {
+        let allocations = (1..=6).map(|vout| match vout % 2 {
+            0 => GenesisAllocation::opret(seal(vout), 100 * vout as u64),
+            _ => GenesisAllocation::tapret(seal(vout), 100 * vout as u64),
+        });
+        let allocations =
+            GenesisAllocations::new(&NonInflatableAsset::schema(), allocations).unwrap();
+        assert_eq!(allocations.total(), Amount::from(2100u64));
+        let outpoints = allocations.outpoints(Method::OpretFirst);
+        assert_eq!(outpoints.iter().map(|o| o.vout).collect::<Vec<_>>(), [2, 4, 6]);
+        assert_eq!(allocations.outpoints(Method::TapretFirst).len(), 3);
+
+        let builder = builder::<NonInflatableAsset>()
+            .add_global_state("spec", spec())
+            .unwrap()
+            .add_global_state("terms", terms())
+            .unwrap()
+            .add_global_state("issuedSupply", allocations.total())
+            .unwrap();
+        let (stock, contract_id) = stock_with(allocations.add_to(builder).unwrap());
+        let nia = stock
+            .contract_wrapper::<NonInflatableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(nia.allocations(FilterIncludeAll).count(), 6);
+    }
+
+    #[test]
+    fn schema_mismatch() {
+        let nia = NonInflatableAsset::schema();
+        let uda = UniqueDigitalAsset::schema();
+        let token = Allocation::with(2, 1);
+
+        assert_eq!(
+            GenesisAllocations::new(&nia, []),
+            Err(Error::GenesisAllocations {
+                count: 0,
+                min: 1,
+                max: u16::MAX
+            })
+        );
+        assert_eq!(
+            GenesisAllocations::new(&uda, [
+                GenesisAllocation::opret(seal(1), token),
+                GenesisAllocation::tapret(seal(2), token)
+            ]),
+            Err(Error::GenesisAllocations {
+                count: 2,
+                min: 1,
+                max: 1
+            })
+        );
+        assert!(GenesisAllocations::new(&uda, [GenesisAllocation::tapret(seal(1), token)]).is_ok());
+        assert_eq!(
+            GenesisAllocations::new(&uda, [GenesisAllocation::opret(seal(1), 1)]),
+            Err(Error::InvalidOwned(OS_ASSET))
+        );
+        assert_eq!(
+            GenesisAllocations::new(&nia, [GenesisAllocation::opret(seal(1), token)]),
+            Err(Error::InvalidOwned(OS_ASSET))
+        );
+        assert_eq!(
+            GenesisAllocations::new(&nia, [GenesisAllocation::opret(seal(1), 0)]),
+            Err(Error::ZeroAmount)
+        );
+    }
+
+    #[test]
+    fn conflicting_methods() {
+        let nia = NonInflatableAsset::schema();
+        let other = BLINDER.derive("other").genesis_seal(txid(), 1);
+        assert_eq!(
+            GenesisAllocations::new(&nia, [
+                GenesisAllocation::opret(seal(1), 1),
+                GenesisAllocation::tapret(other, 1)
+            ]),
+            Err(Error::ConflictingMethods(Outpoint::new(txid(), 1)))
+        );
+        let same = GenesisAllocations::new(&nia, [
+            GenesisAllocation::tapret(seal(1), 1),
+            GenesisAllocation::tapret(other, 1),
+        ])
+        .unwrap();
+        assert_eq!(same.outpoints(Method::TapretFirst).len(), 1);
+    }
+}
@@ -0,0 +1,474 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Governance asset schema.
+//!
+//! Besides the asset, the genesis distributes vote rights to its holders: one for each voting unit
+//! of the issued supply, which the issuer assigns to the holders in proportion to their
+//! allocations. A holder votes by spending a vote right in a cast transition, recording the vote
+//! in the global state. Vote rights can't be transferred, so the voting power stays the one of
+//! the holders at issuance, whatever transfers of the asset follow.
+//!
+//! Scripts can't tell which holder a vote right is assigned to, so the genesis validation only
+//! checks the total number of vote rights; their split among the holders is up to the issuer.
+//! Vote rights carry no state, so each of them must be assigned to a distinct output.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, Details};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, GOVERNANCE_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_TERMS, GS_VOTES, GS_VOTING_UNIT, OS_ASSET, OS_VOTE, TS_CAST, TS_TRANSFER,
+};
+
+pub const GOVERNANCE_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x9a, 0x70, 0x73, 0xc3, 0xd9, 0x95, 0x78, 0x37, 0x02, 0xd6, 0xe9, 0xb6, 0x96, 0x83, 0x2b, 0x4f,
+    0xdb, 0xe4, 0xd6, 0x72, 0xe5, 0x6a, 0xc5, 0xff, 0x34, 0xdf, 0x13, 0x6b, 0x87, 0xe2, 0x73, 0x50,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn governance_schema() -> Schema {
+    let types = standard_types();
+
+    GOVERNANCE_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("GovernanceAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_VOTING_UNIT => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("votingUnit"),
+            },
+            GS_VOTES => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Details")),
+                name: fname!("votes"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_VOTE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("voteRight"),
+                default_transition: TS_CAST,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_VOTING_UNIT => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_VOTE => Occurrences::OnceOrMore,
+            },
+            validator: Some(GOVERNANCE_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_CAST => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_VOTES => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_VOTE => Occurrences::Once,
+                    },
+                    assignments: none!(),
+                    validator: None
+                },
+                name: fname!("cast"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn governance_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[GOVERNANCE_GENESIS, NIA_TRANSFER])
+}
+
+#[derive(Default)]
+pub struct GovernanceAsset;
+
+impl IssuerWrapper for GovernanceAsset {
+    type Wrapper<S: ContractStateRead> = GovernanceWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(governance_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(governance_scripts).clone()
+    }
+}
+
+impl GovernanceAsset {
+    /// First revision of the governance asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "GovernanceAsset",
+        schema_id: GOVERNANCE_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fixed supply asset whose genesis distributes a vote right for each voting \
+                        unit, spent to cast votes recorded in the global state.",
+    };
+}
+
+impl IssuerInfo for GovernanceAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for GovernanceAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct GovernanceWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for GovernanceWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for GovernanceWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<GovernanceAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> GovernanceWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the governance asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<GovernanceAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the governance asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the amount of the asset granting one vote right at issuance.
+    pub fn voting_unit(&self) -> Amount { or_panic(self.try_voting_unit()) }
+
+    pub fn try_voting_unit(&self) -> Result<Amount, Error> { global(&self.0, GS_VOTING_UNIT) }
+
+    /// Returns the number of vote rights distributed by the genesis.
+    pub fn total_vote_rights(&self) -> u64 { or_panic(self.try_total_vote_rights()) }
+
+    pub fn try_total_vote_rights(&self) -> Result<u64, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        Ok(issued
+            .checked_div(self.try_voting_unit()?.value())
+            .unwrap_or_default())
+    }
+
+    /// Returns the votes in the order they were cast.
+    pub fn votes(&self) -> Vec<Details> { or_panic(self.try_votes()) }
+
+    pub fn try_votes(&self) -> Result<Vec<Details>, Error> {
+        let mut votes = globals(&self.0, GS_VOTES)?.collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        votes.reverse();
+        Ok(votes)
+    }
+
+    /// Counts the votes cast for each distinct vote.
+    pub fn tally(&self) -> BTreeMap<Details, u64> { or_panic(self.try_tally()) }
+
+    pub fn try_tally(&self) -> Result<BTreeMap<Details, u64>, Error> {
+        let mut tally = BTreeMap::new();
+        for vote in self.try_votes()? {
+            *tally.entry(vote).or_default() += 1;
+        }
+        Ok(tally)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn vote_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_vote_rights(filter))
+    }
+
+    pub fn try_vote_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_VOTE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for GovernanceWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        GovernanceWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        GovernanceWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        GovernanceWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        GovernanceWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::Details;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = governance_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(GOVERNANCE_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<GovernanceAsset>(fixtures::contract_id("governance"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.voting_unit(), Amount::from(fixtures::VOTING_UNIT));
+        assert_eq!(wrapper.total_vote_rights(), fixtures::ISSUED_SUPPLY / fixtures::VOTING_UNIT);
+        assert_eq!(wrapper.votes(), vec![]);
+        assert_eq!(wrapper.tally(), bmap! {});
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .vote_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(1)]);
+    }
+
+    // Votes cast with the vote rights of a governance asset.
+    //
+    // The genesis gives the only holder of the asset a vote right for each voting unit. The holder
+    // casts three of the votes, each in its own mined witness TX, and the `GovernanceWrapper` must
+    // then report the votes in the order they were cast and count them by choice, and the
+    // validation of the history must accept them.
+
+    /// Governance asset allocated to `seal(1)`, with `vote_rights` vote rights assigned to
+    /// the seals with vouts from 1 up to `vote_rights`.
+    fn governance(
+        issued: u64,
+        voting_unit: u64,
+        allocated: u64,
+        vote_rights: u32,
+    ) -> ContractBuilder {
+        let mut builder = testing::asset::<GovernanceAsset>(issued, allocated)
+            .add_global_state("votingUnit", Amount::from(voting_unit))
+            .unwrap();
+        for vout in 1..=vote_rights {
+            builder = builder
+                .add_rights("voteRight", testing::seal(vout))
+                .unwrap();
+        }
+        builder
+    }
+
+    fn vote(choice: &str) -> Details { Details::from_str(choice).unwrap() }
+
+    /// Casts a vote spending the vote right `right` in a witness TX mined at `height`, returning
+    /// the id of the `cast` transition.
+    fn cast(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        right: &RightsAllocation,
+        height: u32,
+        choice: &str,
+    ) -> OpId {
+        let transition = stock
+            .transition_builder(contract_id, "cast")
+            .unwrap()
+            .add_input(right.opout, AllocatedState::Void)
+            .unwrap()
+            .add_global_state("votes", vote(choice))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+
+        let prevout = Outpoint::new(right.seal.txid, right.seal.vout.into_u32());
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        opid
+    }
+
+    #[test]
+    fn cast_votes() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(governance(1000, 250, 1000, 4));
+        let contract_id = contract.contract_id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<GovernanceAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.total_vote_rights(), 4);
+        assert_eq!(wrapper.votes(), vec![]);
+        let rights = wrapper.vote_rights(&FilterIncludeAll).collect::<Vec<_>>();
+        assert_eq!(rights.len(), 4);
+
+        let votes = [(100, "yes"), (101, "no"), (102, "yes")]
+            .into_iter()
+            .zip(&rights)
+            .map(|((height, choice), right)| {
+                cast(&mut stock, &mut resolver, contract_id, right, height, choice)
+            })
+            .collect::<Vec<_>>();
+
+        let wrapper = stock
+            .contract_wrapper::<GovernanceAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.votes(), vec![vote("yes"), vote("no"), vote("yes")]);
+        assert_eq!(wrapper.tally(), BTreeMap::from([(vote("no"), 1), (vote("yes"), 2)]));
+
+        testing::validate_history::<GovernanceAsset>(&stock, &resolver, contract_id, &votes)
+            .unwrap();
+    }
+}
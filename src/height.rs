@@ -0,0 +1,93 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Block heights committed to by the schemata.
+//!
+//! Heights are recorded in the global state as `Bitcoin.LockTime` values, the type of the absolute
+//! lock time of a Bitcoin transaction, and read back as `u32`.
+//!
+//! # Heights and consensus
+//!
+//! Validation scripts see the operation they validate and the global state of the contract, but
+//! neither the witness transaction of the operation nor the block mining it. Whatever a schema
+//! requires of the height at which its transitions are mined is therefore not part of the RGB
+//! consensus: transitions mined too early or too late are valid, and it is up to wallets to refrain
+//! from creating them. The `audit` module of `rgb-schemata-tools`, which resolves the witnesses of
+//! a contract, reports the transitions mined at heights their schema doesn't allow.
+//!
+//! What scripts can check are heights committed to by the operations themselves. A schema can
+//! require its transitions to commit to a height, compared by the scripts with the heights of the
+//! contract state: this binds the transition, not the block mining it, so wallets still have to
+//! give the witness transaction a matching absolute lock time. Each schema documents which of its
+//! height requirements its scripts check.
+
+use std::io;
+
+use rgbstd::contract::ContractData;
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::GlobalStateType;
+use strict_types::encoding::bitcoin_types::LockTime;
+use strict_types::encoding::TypedWrite;
+use strict_types::{StrictEncode, StrictSerialize, StrictType, TypeName};
+
+use crate::wrapper::global;
+use crate::Error;
+
+/// Global state value recording the block `height`, as stored by the height global states of the
+/// schemata.
+pub fn block_height(height: u32) -> impl StrictSerialize { BlockHeight(LockTime(height)) }
+
+/// Block height encoded as the lock time it wraps, which the standard library doesn't allow to
+/// serialize on its own.
+struct BlockHeight(LockTime);
+
+impl StrictType for BlockHeight {
+    const STRICT_LIB_NAME: &'static str = LockTime::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { LockTime::strict_name() }
+}
+
+impl StrictEncode for BlockHeight {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.0.strict_encode(writer)
+    }
+}
+
+impl StrictSerialize for BlockHeight {}
+
+/// Returns the first height of the global state of type `ty`, failing if there is none.
+// Not used by the schemata which only refer to the module documentation, as epoch inflatable assets
+#[cfg_attr(
+    not(any(
+        feature = "bond",
+        feature = "vesting",
+        feature = "timelock",
+        feature = "rental",
+        feature = "option",
+        feature = "subscription"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn height<S: ContractStateRead>(
+    data: &ContractData<S>,
+    ty: GlobalStateType,
+) -> Result<u32, Error> {
+    global::<_, LockTime>(data, ty).map(|height| height.0)
+}
@@ -21,138 +21,67 @@
 
 //! Inflatable Fungible Assets (IFA) schema.
 //! (!) Not safe to use in a production environment!
+//!
+//! Burns record the burned amount in the `burnedSupply` global state, which the burn validation
+//! checks against the burned asset allocations, so the wrapper reads the burned and circulating
+//! supplies from the contract state. Contracts of the first revision of the schema don't record
+//! their burns, whose supplies are only known from a consignment of the contract history.
 
-use aluvm::isa::Instr;
-use aluvm::library::{Lib, LibSite};
-use amplify::confinement::Confined;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
 use rgbstd::contract::{
     AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
     SchemaWrapper,
 };
-use rgbstd::persistence::{ContractStateRead, MemContract};
+use rgbstd::persistence::ContractStateRead;
 use rgbstd::schema::{
     AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
     OwnedStateSchema, Schema, TransitionSchema,
 };
-use rgbstd::stl::{rgb_contract_stl, AssetSpec, ContractTerms, RejectListUrl, StandardTypes};
+use rgbstd::stl::{AssetSpec, ContractTerms, RejectListUrl};
 use rgbstd::validation::Scripts;
-use rgbstd::vm::RgbIsa;
-use rgbstd::{rgbasm, Amount, GlobalDetails, MetaDetails, SchemaId, TransitionDetails};
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, MetaDetails, SchemaId, TransitionDetails};
 use strict_types::TypeSystem;
 
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::reject::RejectList;
+use crate::scripts::{
+    SharedLibs, IFA_GENESIS, IFA_INFLATION, IFA_TRANSFER, IFA_TRANSFER_NO_REPLACE, RIA_BURN,
+};
+use crate::wrapper::{check_family, global, globals, or_panic};
 use crate::{
-    ERRNO_INFLATION_EXCEEDS_ALLOWANCE, ERRNO_INFLATION_MISMATCH, ERRNO_ISSUED_MISMATCH,
-    ERRNO_NON_EQUAL_IN_OUT, ERRNO_REPLACE_HIDDEN_BURN, ERRNO_REPLACE_NO_INPUT, GS_ISSUED_SUPPLY,
-    GS_MAX_SUPPLY, GS_NOMINAL, GS_REJECT_LIST_URL, GS_TERMS, MS_ALLOWED_INFLATION, OS_ASSET,
-    OS_INFLATION, OS_REPLACE, TS_BURN, TS_INFLATION, TS_REPLACE, TS_TRANSFER,
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_BURNED_SUPPLY,
+    GS_ISSUED_SUPPLY, GS_MAX_SUPPLY, GS_NOMINAL, GS_REJECT_LIST_URL, GS_TERMS,
+    MS_ALLOWED_INFLATION, OS_ASSET, OS_INFLATION, OS_REPLACE, TS_BURN, TS_INFLATION, TS_REPLACE,
+    TS_TRANSFER,
 };
 
+/// Id of the latest revision of the schema, used for new issuances.
+///
+/// Before the schema gained new revisions it was the id of the first revision, which is now
+/// [`IFA_V1_SCHEMA_ID`].
 pub const IFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xc3, 0xf2, 0x4e, 0x86, 0xc2, 0xc0, 0xa7, 0x55, 0x7e, 0xa7, 0x24, 0x5d, 0x02, 0x3d, 0x73, 0xbe,
+    0x4b, 0x0c, 0xde, 0x71, 0xd2, 0x50, 0x2c, 0xb6, 0xc6, 0xb4, 0x56, 0x32, 0xe9, 0x6d, 0xac, 0x67,
+]);
+
+/// Id of the first revision of the schema, whose burns leave no state behind.
+pub const IFA_V1_SCHEMA_ID: SchemaId = SchemaId::from_array([
     0x82, 0x65, 0x7f, 0x89, 0x08, 0x2f, 0x06, 0x27, 0x64, 0xdc, 0x04, 0x7c, 0xbb, 0xff, 0xad, 0x94,
     0x2a, 0x82, 0x30, 0xc0, 0x41, 0xbc, 0xa3, 0x16, 0x43, 0x05, 0xba, 0x24, 0xc5, 0x95, 0xb4, 0x60,
 ]);
 
-pub(crate) fn ifa_lib_genesis() -> Lib {
-    #[allow(clippy::diverging_sub_expression)]
-    let code = rgbasm! {
-        // Set common offsets
-        put     a8[1],0;
-        put     a16[0],0;
-
-        // Check reported issued supply against sum of asset allocations in output
-        put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
-        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
-        extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
-        sas     OS_ASSET;  // check sum of assets assignments in output equals a64[0]
-        test;
-
-        // Check that sum of inflation rights = max supply - issued supply
-        put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
-        ldg     GS_MAX_SUPPLY,a8[1],s16[1];  // read max supply global state
-        extr    s16[1],a64[1],a16[0];  // and store it in a64[1]
-        sub.uc  a64[1],a64[0];  // issued supply is still in a64[0], result overwrites a64[0]
-        test;  // fails if result is <0
-        sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
-        test;
-
-        ret;
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code)
-        .expect("wrong inflatable asset genesis valdiation script")
-}
-
-pub(crate) fn ifa_lib_transfer() -> Lib {
-    let code = rgbasm! {
-        // Checking that the sum of inputs is equal to the sum of outputs
-        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
-        svs     OS_ASSET;  // verify sum
-        test;  // check it didn't fail
-        svs     OS_INFLATION;  // verify sum
-        test;  // check it didn't fail
-
-        // Replace rights validation
-        cnp     OS_REPLACE,a16[0];  // count input replace rights
-        cns     OS_REPLACE,a16[1];  // count output replace rights
-        // Check if input count is 0
-        put     a16[2],0;  // store 0 in a16[2]
-        eq.n    a16[0],a16[2];  // check if input_count == 0
-        // TODO: fix comment
-        jif     40;  // jump to 0x28 if input_count == 0
-        // Input count > 0, check that output count >= input count
-        put     a8[0],ERRNO_REPLACE_HIDDEN_BURN;  // set errno
-        lt.u    a16[1],a16[0];  // output_count < input_count
-        inv     st0;  // output_count >= input_count
-        test;  // fail if output_count < input_count
-        ret;  // return execution flow
-        // 0x28: Input count is 0, output count must also be 0
-        put     a8[0],ERRNO_REPLACE_NO_INPUT;  // set errno
-        eq.n    a16[1],a16[0];  // check if output_count == input_count
-        test;  // fail if output_count != input_count (=0)
-        ret;  // return execution flow
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code).expect("wrong transfer validation script")
-}
-
-pub(crate) fn ifa_lib_inflation() -> Lib {
-    #[allow(clippy::diverging_sub_expression)]
-    let code = rgbasm! {
-        // Set common offsets
-        put     a8[1],0;
-        put     a16[0],0;
-
-        // Check reported issued supply equals sum of asset allocations in output
-        put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
-        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
-        extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
-        sas     OS_ASSET;  // check sum of asset allocations in output equals issued_supply
-        test;
-        cpy     a64[0],a64[1];  // store issued supply in a64[1] for later
-
-        // Check reported allowed inflation equals sum of inflation rights in output
-        put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
-        ldm     MS_ALLOWED_INFLATION,s16[0];  // read allowed inflation global state
-        extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
-        sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
-        test;
-
-        // Check that input inflation rights equals issued supply + allowed inflation
-        put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
-        add.uc  a64[1],a64[0];  // result is stored in a64[0]
-        test;  // fails in case of an overflow
-        sps     OS_INFLATION;  // check sum of inflation rights in input equals a64[0]
-        test;
-
-        ret;
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code).expect("wrong inflation validation script")
-}
-
-fn ifa_standard_types() -> StandardTypes { StandardTypes::with(rgb_contract_stl()) }
-
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
 fn ifa_schema() -> Schema {
-    let types = ifa_standard_types();
+    let types = standard_types();
 
-    let alu_id_transfer = ifa_lib_transfer().id();
+    IFA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    IFA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS), (8, INSTR_SVS)]);
+    IFA_TRANSFER_NO_REPLACE.verify(&[(0, INSTR_PUTA)]);
+    IFA_INFLATION.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    RIA_BURN.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
 
     Schema {
         ffv: zero!(),
@@ -184,6 +113,10 @@ fn ifa_schema() -> Schema {
                 global_state_schema: GlobalStateSchema::once(types.get("RGBContract.RejectListUrl")),
                 name: fname!("rejectListUrl"),
             },
+            GS_BURNED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("burnedSupply"),
+            },
         },
         owned_types: tiny_bmap! {
             OS_ASSET => AssignmentDetails {
@@ -216,7 +149,7 @@ fn ifa_schema() -> Schema {
                 OS_INFLATION => Occurrences::NoneOrMore,
                 OS_REPLACE => Occurrences::NoneOrMore,
             },
-            validator: Some(LibSite::with(0, ifa_lib_genesis().id())),
+            validator: Some(IFA_GENESIS.lib_site()),
         },
         transitions: tiny_bmap! {
             TS_TRANSFER => TransitionDetails {
@@ -233,7 +166,7 @@ fn ifa_schema() -> Schema {
                         OS_INFLATION => Occurrences::NoneOrMore,
                         OS_REPLACE => Occurrences::NoneOrMore
                     },
-                    validator: Some(LibSite::with(0, alu_id_transfer))
+                    validator: Some(IFA_TRANSFER.lib_site())
                 },
                 name: fname!("transfer"),
             },
@@ -250,21 +183,23 @@ fn ifa_schema() -> Schema {
                         OS_ASSET => Occurrences::OnceOrMore,
                         OS_INFLATION => Occurrences::NoneOrMore
                     },
-                    validator: Some(LibSite::with(0, ifa_lib_inflation().id()))
+                    validator: Some(IFA_INFLATION.lib_site())
                 },
                 name: fname!("inflate"),
             },
             TS_BURN => TransitionDetails {
                 transition_schema: TransitionSchema {
                     metadata: none!(),
-                    globals: none!(),
+                    globals: tiny_bmap! {
+                        GS_BURNED_SUPPLY => Occurrences::Once,
+                    },
                     inputs: tiny_bmap! {
                         OS_ASSET => Occurrences::NoneOrMore,
                         OS_REPLACE => Occurrences::NoneOrMore,
                         OS_INFLATION => Occurrences::NoneOrMore,
                     },
                     assignments: none!(),
-                    validator: None
+                    validator: Some(RIA_BURN.lib_site())
                 },
                 name: fname!("burn"),
             },
@@ -280,7 +215,7 @@ fn ifa_schema() -> Schema {
                         OS_ASSET => Occurrences::OnceOrMore,
                         OS_REPLACE => Occurrences::OnceOrMore,
                     },
-                    validator: Some(LibSite::with(0, alu_id_transfer))
+                    validator: Some(IFA_TRANSFER.lib_site())
                 },
                 name: fname!("replace"),
             },
@@ -289,114 +224,264 @@ fn ifa_schema() -> Schema {
     }
 }
 
+fn ifa_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[IFA_GENESIS, IFA_TRANSFER, IFA_INFLATION, RIA_BURN])
+}
+
 #[derive(Default)]
 pub struct InflatableFungibleAsset;
 
 impl IssuerWrapper for InflatableFungibleAsset {
     type Wrapper<S: ContractStateRead> = IfaWrapper<S>;
 
-    fn schema() -> Schema { ifa_schema() }
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(ifa_schema).clone()
+    }
 
-    fn types() -> TypeSystem { ifa_standard_types().type_system(ifa_schema()) }
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
 
     fn scripts() -> Scripts {
-        let alu_lib_genesis = ifa_lib_genesis();
-        let alu_id_genesis = alu_lib_genesis.id();
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(ifa_scripts).clone()
+    }
+}
 
-        let alu_lib_transfer = ifa_lib_transfer();
-        let alu_id_transfer = alu_lib_transfer.id();
+impl InflatableFungibleAsset {
+    /// First revision of the IFA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "InflatableFungibleAsset",
+        schema_id: IFA_V1_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset supporting inflation up to a maximum supply, burning, \
+                        replacement of allocations and an optional reject list URL.",
+    };
 
-        let alu_lib_inflation = ifa_lib_inflation();
-        let alu_id_inflation = alu_lib_inflation.id();
+    /// Revision recording the burns of the IFA schema in the global state.
+    pub const V2: SchemaInfo = SchemaInfo {
+        name: "InflatableFungibleAsset",
+        schema_id: IFA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 2, 0),
+        release_notes: "Burned supply global state, recording the amount of each burn, which must \
+                        equal the sum of the burned asset allocations.",
+    };
+}
 
-        Confined::from_checked(bmap! {
-            alu_id_genesis => alu_lib_genesis,
-            alu_id_transfer => alu_lib_transfer,
-            alu_id_inflation => alu_lib_inflation,
-        })
-    }
+impl IssuerInfo for InflatableFungibleAsset {
+    const INFO: SchemaInfo = Self::V2;
+}
+
+impl SchemaFamily for InflatableFungibleAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1, Self::V2];
 }
 #[derive(Clone, Eq, PartialEq, Debug, From)]
 pub struct IfaWrapper<S: ContractStateRead>(ContractData<S>);
 
 impl<S: ContractStateRead> SchemaWrapper<S> for IfaWrapper<S> {
-    fn with(data: ContractData<S>) -> Self {
-        if data.schema.schema_id() != IFA_SCHEMA_ID {
-            panic!("the provided schema is not IFA");
-        }
-        Self(data)
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for IfaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<InflatableFungibleAsset, _>(&self.0)?)
     }
 }
 
 impl<S: ContractStateRead> IfaWrapper<S> {
-    pub fn spec(&self) -> AssetSpec {
-        let strict_val = &self
-            .0
-            .global("spec")
-            .next()
-            .expect("IFA requires global state `spec` to have at least one item");
-        AssetSpec::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, failing if it doesn't belong to the IFA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<InflatableFungibleAsset, _>(&data)?;
+        Ok(Self(data))
     }
 
-    pub fn contract_terms(&self) -> ContractTerms {
-        let strict_val = &self
-            .0
-            .global("terms")
-            .next()
-            .expect("IFA requires global state `terms` to have at least one item");
-        ContractTerms::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, panicking if it doesn't belong to the IFA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn reject_list_url(&self) -> Option<RejectListUrl> { or_panic(self.try_reject_list_url()) }
+
+    pub fn try_reject_list_url(&self) -> Result<Option<RejectListUrl>, Error> {
+        globals(&self.0, GS_REJECT_LIST_URL)?.next().transpose()
+    }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
     }
 
-    pub fn reject_list_url(&self) -> Option<RejectListUrl> {
-        self.0
-            .global("rejectListUrl")
-            .next()
-            .map(|strict_val| RejectListUrl::from_strict_val_unchecked(&strict_val))
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    /// Returns the supply burned over the contract history.
+    ///
+    /// Fails for contracts of the first revision of the schema, which don't record their burns.
+    pub fn total_burned(&self) -> Amount { or_panic(self.try_total_burned()) }
+
+    pub fn try_total_burned(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_BURNED_SUPPLY)?.sum()
     }
 
-    fn issued_supply(&self) -> impl Iterator<Item = Amount> + '_ {
-        self.0
-            .global("issuedSupply")
-            .map(|amount| Amount::from_strict_val_unchecked(&amount))
+    /// Returns the amounts burned by each burn, from the latest one.
+    pub fn burn_history(&self) -> Vec<Amount> { or_panic(self.try_burn_history()) }
+
+    pub fn try_burn_history(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_BURNED_SUPPLY)?.collect()
     }
 
-    pub fn total_issued_supply(&self) -> Amount { self.issued_supply().sum() }
+    /// Returns the supply issued by the genesis and the inflations which isn't burned yet.
+    pub fn circulating_supply(&self) -> Amount { or_panic(self.try_circulating_supply()) }
+
+    pub fn try_circulating_supply(&self) -> Result<Amount, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        let burned = self.try_total_burned()?.value();
+        // Burns are checked against the spent allocations, so they never exceed the issuance
+        Ok(Amount::from(issued.saturating_sub(burned)))
+    }
 
-    pub fn issuance_amounts(&self) -> Vec<Amount> { self.issued_supply().collect::<Vec<_>>() }
+    pub fn max_supply(&self) -> Amount { or_panic(self.try_max_supply()) }
 
-    pub fn max_supply(&self) -> Amount {
-        self.0
-            .global("maxSupply")
-            .map(|amount| Amount::from_strict_val_unchecked(&amount))
-            .sum()
+    pub fn try_max_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_MAX_SUPPLY)?.sum()
     }
 
     pub fn allocations<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = FungibleAllocation> + 'c {
-        self.0.fungible_raw(OS_ASSET, filter).unwrap()
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the allocations passing the filter which are not on the reject list.
+    pub fn accepted_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_accepted_allocations(filter, reject_list))
+    }
+
+    pub fn try_accepted_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self
+            .try_allocations(filter)?
+            .filter(|a| !reject_list.is_rejected(a.opout)))
+    }
+
+    /// Returns the allocations passing the filter which are on the reject list.
+    pub fn rejected_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_rejected_allocations(filter, reject_list))
+    }
+
+    pub fn try_rejected_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self
+            .try_allocations(filter)?
+            .filter(|a| reject_list.is_rejected(a.opout)))
     }
 
     pub fn inflation_allocations<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = FungibleAllocation> + 'c {
-        self.0.fungible_raw(OS_INFLATION, filter).unwrap()
+        or_panic(self.try_inflation_allocations(filter))
+    }
+
+    pub fn try_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_INFLATION, filter)?)
     }
 
     pub fn replace_rights<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = RightsAllocation> + 'c {
-        self.0.rights_raw(OS_REPLACE, filter).unwrap()
+        or_panic(self.try_replace_rights(filter))
+    }
+
+    pub fn try_replace_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REPLACE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for IfaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { IfaWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        IfaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        IfaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        IfaWrapper::try_allocations(self, filter)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::ifa::ifa_schema;
-    use crate::IFA_SCHEMA_ID;
+    use std::str::FromStr;
+
+    use rgbstd::containers::{BuilderSeal, ConsignmentExt};
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::*;
+    use rgbstd::txout::BlindSeal;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
 
     #[test]
     fn schema_id() {
@@ -404,4 +489,113 @@ mod test {
         eprintln!("{:#04x?}", schema_id.to_byte_array());
         assert_eq!(IFA_SCHEMA_ID, schema_id);
     }
+
+    #[test]
+    fn deterministic_contract_id() {
+        let created_at = 1713261744;
+        let terms = ContractTerms {
+            text: RicardianContract::default(),
+            media: None,
+        };
+        let spec = AssetSpec {
+            ticker: Ticker::from("TICKER"),
+            name: Name::from("NAME"),
+            details: None,
+            precision: Precision::try_from(2).unwrap(),
+        };
+        let issued_supply = 999u64;
+        let max_supply = 1999u64;
+        let asset_seal: BlindSeal<Txid> = GenesisSeal::from(BlindSeal::with_blinding(
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap(),
+            0,
+            testing::BLINDER.blinding(0),
+        ));
+        let inflation_seal: BlindSeal<Txid> = GenesisSeal::from(BlindSeal::with_blinding(
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap(),
+            1,
+            testing::BLINDER.blinding(0),
+        ));
+        let replace_seal: BlindSeal<Txid> = GenesisSeal::from(BlindSeal::with_blinding(
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap(),
+            2,
+            testing::BLINDER.blinding(0),
+        ));
+
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            InflatableFungibleAsset::schema(),
+            InflatableFungibleAsset::types(),
+            InflatableFungibleAsset::scripts(),
+            ChainNet::BitcoinTestnet4,
+        )
+        .add_global_state("spec", spec)
+        .unwrap()
+        .add_global_state("terms", terms)
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(max_supply))
+        .unwrap()
+        .add_global_state("rejectListUrl", RejectListUrl::from("example.xyz/reject"))
+        .unwrap()
+        .add_fungible_state("assetOwner", BuilderSeal::from(asset_seal), issued_supply)
+        .unwrap()
+        .add_fungible_state(
+            "inflationAllowance",
+            BuilderSeal::from(inflation_seal),
+            max_supply - issued_supply,
+        )
+        .unwrap()
+        .add_rights("replaceRight", BuilderSeal::from(replace_seal))
+        .unwrap();
+
+        let contract = builder.issue_contract_raw(created_at).unwrap();
+
+        assert_eq!(
+            contract.contract_id().to_string(),
+            s!("rgb:BSccaVWp-_ERfcaF-POkYglQ-0AICRbo-orkHfM9-EpUsrL4")
+        );
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        for name in ["ifa", "ifa-full"] {
+            let wrapper = stock
+                .contract_wrapper::<InflatableFungibleAsset>(fixtures::contract_id(name))
+                .unwrap();
+            assert_eq!(wrapper.spec(), testing::spec());
+            assert_eq!(wrapper.contract_terms(), testing::terms());
+            assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+            assert_eq!(wrapper.issuance_amounts(), vec![Amount::from(fixtures::ISSUED_SUPPLY)]);
+            assert_eq!(wrapper.max_supply(), Amount::from(fixtures::MAX_SUPPLY));
+            assert_eq!(wrapper.total_burned(), Amount::ZERO);
+            assert_eq!(wrapper.burn_history(), vec![]);
+            assert_eq!(wrapper.circulating_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+            let reject_list_url = (name == "ifa-full").then(fixtures::reject_list_url);
+            assert_eq!(wrapper.reject_list_url(), reject_list_url);
+
+            let allocations = wrapper
+                .allocations(&FilterIncludeAll)
+                .map(|a| (a.seal, a.state.value()))
+                .collect::<Vec<_>>();
+            assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+            let inflation = wrapper
+                .inflation_allocations(&FilterIncludeAll)
+                .map(|a| (a.seal, a.state.value()))
+                .collect::<Vec<_>>();
+            assert_eq!(inflation, vec![(
+                fixture_seal(2),
+                fixtures::MAX_SUPPLY - fixtures::ISSUED_SUPPLY
+            )]);
+            let rights = wrapper
+                .replace_rights(&FilterIncludeAll)
+                .map(|a| a.seal)
+                .collect::<Vec<_>>();
+            assert_eq!(rights, vec![fixture_seal(3)]);
+        }
+    }
 }
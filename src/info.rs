@@ -0,0 +1,445 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Authorship and revision metadata of the schemata.
+//!
+//! A schema commits only to its structure, so its id can't tell who developed it or which
+//! revision of its family it is. [`SchemaInfo`] keeps this data next to the schema, allowing users
+//! holding a schema to check it against the revision they expect.
+//!
+//! Changing a schema changes its id, so the revisions of a schema are distinct schemata. Each
+//! issuer groups them into a [`SchemaFamily`]: it issues contracts with the latest revision, while
+//! its wrapper reads contracts of any revision of the family.
+
+use rgbstd::bitcoin::hashes::{sha256, Hash};
+use rgbstd::contract::{ContractData, IssuerWrapper, SchemaWrapper};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::SchemaId;
+
+#[cfg(feature = "bond")]
+use crate::bond::BondAsset;
+#[cfg(feature = "burnable")]
+use crate::burnable::BurnableAsset;
+#[cfg(feature = "carbon")]
+use crate::carbon::CarbonCreditAsset;
+#[cfg(feature = "cfa")]
+use crate::cfa::CollectibleFungibleAsset;
+#[cfg(feature = "claim")]
+use crate::claim::ClaimableAsset;
+#[cfg(feature = "collection")]
+use crate::collection::UniqueDigitalCollection;
+#[cfg(feature = "credential")]
+use crate::credential::VerifiableCredential;
+#[cfg(feature = "dividend")]
+use crate::dividend::DividendAsset;
+#[cfg(feature = "edition")]
+use crate::edition::LimitedEditionAsset;
+#[cfg(feature = "eifa")]
+use crate::eifa::EpochInflatableAsset;
+#[cfg(feature = "escrow")]
+use crate::escrow::EscrowAsset;
+#[cfg(feature = "fee")]
+use crate::fee::TransferFeeAsset;
+#[cfg(feature = "freezable")]
+use crate::freezable::FreezableAsset;
+#[cfg(feature = "fua")]
+use crate::fua::FractionalUniqueAsset;
+#[cfg(feature = "governance")]
+use crate::governance::GovernanceAsset;
+#[cfg(feature = "ifa")]
+use crate::ifa::InflatableFungibleAsset;
+#[cfg(feature = "nia")]
+use crate::nia::NonInflatableAsset;
+#[cfg(feature = "option")]
+use crate::option::OptionAsset;
+#[cfg(feature = "pausable")]
+use crate::pausable::PausableAsset;
+#[cfg(feature = "pfa")]
+use crate::pfa::PermissionedFungibleAsset;
+#[cfg(feature = "pfan")]
+use crate::pfan::MultiKeyPermissionedAsset;
+#[cfg(feature = "provenance")]
+use crate::provenance::ProvenanceAsset;
+#[cfg(feature = "rebasing")]
+use crate::rebasing::RebasingAsset;
+#[cfg(feature = "registry")]
+use crate::registry::NameRegistry;
+#[cfg(feature = "rental")]
+use crate::rental::RentalAsset;
+#[cfg(feature = "ria")]
+use crate::ria::ReissuableAsset;
+#[cfg(feature = "sba")]
+use crate::sba::SoulboundAsset;
+#[cfg(feature = "sfa")]
+use crate::sfa::SemiFungibleAsset;
+#[cfg(feature = "stablecoin")]
+use crate::stablecoin::StablecoinAsset;
+#[cfg(feature = "subscription")]
+use crate::subscription::SubscriptionAsset;
+#[cfg(feature = "swap")]
+use crate::swap::SwapOrder;
+#[cfg(feature = "ticket")]
+use crate::ticket::TicketAsset;
+#[cfg(feature = "tifa")]
+use crate::tifa::ThresholdInflatableAsset;
+#[cfg(feature = "timelock")]
+use crate::timelock::TimelockAsset;
+#[cfg(feature = "uda")]
+use crate::uda::UniqueDigitalAsset;
+#[cfg(feature = "vesting")]
+use crate::vesting::VestingAsset;
+#[cfg(feature = "wbtc")]
+use crate::wbtc::WrappedBtcAsset;
+use crate::wrapper::check_family;
+use crate::WrongSchema;
+
+/// Developer of the schemata maintained in this crate.
+pub const DEVELOPER: &str = "LNP/BP Standards Association";
+
+/// Semantic version of a schema.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+#[display("{major}.{minor}.{patch}")]
+pub struct SchemaVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl SchemaVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// Authorship and revision of a schema.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SchemaInfo {
+    /// Name of the schema.
+    pub name: &'static str,
+    /// Id of the schema revision described.
+    pub schema_id: SchemaId,
+    /// Developer of the schema.
+    pub developer: &'static str,
+    /// Version of the schema within its family.
+    pub version: SchemaVersion,
+    /// Changes introduced by this version.
+    pub release_notes: &'static str,
+}
+
+impl SchemaInfo {
+    /// SHA-256 hash of the release notes, to be compared with the one published by the developer.
+    pub fn release_notes_hash(&self) -> sha256::Hash {
+        sha256::Hash::hash(self.release_notes.as_bytes())
+    }
+}
+
+/// Issuer of a schema carrying its [`SchemaInfo`].
+pub trait IssuerInfo: IssuerWrapper {
+    /// Metadata of the schema issued.
+    const INFO: SchemaInfo;
+
+    /// Returns the metadata of the schema issued.
+    fn info() -> SchemaInfo { Self::INFO }
+}
+
+/// Revisions of a schema sharing the same issuer and wrapper.
+pub trait SchemaFamily: IssuerInfo {
+    /// All known revisions, from the oldest to the latest one.
+    const VERSIONS: &'static [SchemaInfo];
+
+    /// Returns the latest revision, which is the one used for new issuances.
+    fn latest() -> SchemaInfo { Self::INFO }
+
+    /// Returns the revision with the given schema id, if it belongs to the family.
+    fn version_of(schema_id: SchemaId) -> Option<SchemaInfo> {
+        Self::VERSIONS
+            .iter()
+            .find(|info| info.schema_id == schema_id)
+            .copied()
+    }
+
+    /// Wraps the contract data, failing if it doesn't belong to a revision of the family.
+    ///
+    /// Library code should prefer it, or the `try_with` constructor of the wrapper, over
+    /// [`SchemaWrapper::with`] and `Stock::contract_wrapper`, which panic on a schema mismatch.
+    fn try_wrap<S: ContractStateRead>(
+        data: ContractData<S>,
+    ) -> Result<Self::Wrapper<S>, WrongSchema>
+    where Self: Sized {
+        check_family::<Self, S>(&data)?;
+        Ok(Self::Wrapper::with(data))
+    }
+}
+
+/// Metadata of the latest revision of all enabled schemata.
+pub const SCHEMATA: &[SchemaInfo] = &[
+    #[cfg(feature = "bond")]
+    BondAsset::INFO,
+    #[cfg(feature = "cfa")]
+    CollectibleFungibleAsset::INFO,
+    #[cfg(feature = "collection")]
+    UniqueDigitalCollection::INFO,
+    #[cfg(feature = "fua")]
+    FractionalUniqueAsset::INFO,
+    #[cfg(feature = "governance")]
+    GovernanceAsset::INFO,
+    #[cfg(feature = "ifa")]
+    InflatableFungibleAsset::INFO,
+    #[cfg(feature = "nia")]
+    NonInflatableAsset::INFO,
+    #[cfg(feature = "pfa")]
+    PermissionedFungibleAsset::INFO,
+    #[cfg(feature = "ria")]
+    ReissuableAsset::INFO,
+    #[cfg(feature = "sfa")]
+    SemiFungibleAsset::INFO,
+    #[cfg(feature = "sba")]
+    SoulboundAsset::INFO,
+    #[cfg(feature = "stablecoin")]
+    StablecoinAsset::INFO,
+    #[cfg(feature = "ticket")]
+    TicketAsset::INFO,
+    #[cfg(feature = "vesting")]
+    VestingAsset::INFO,
+    #[cfg(feature = "timelock")]
+    TimelockAsset::INFO,
+    #[cfg(feature = "pfan")]
+    MultiKeyPermissionedAsset::INFO,
+    #[cfg(feature = "freezable")]
+    FreezableAsset::INFO,
+    #[cfg(feature = "tifa")]
+    ThresholdInflatableAsset::INFO,
+    #[cfg(feature = "wbtc")]
+    WrappedBtcAsset::INFO,
+    #[cfg(feature = "fee")]
+    TransferFeeAsset::INFO,
+    #[cfg(feature = "dividend")]
+    DividendAsset::INFO,
+    #[cfg(feature = "escrow")]
+    EscrowAsset::INFO,
+    #[cfg(feature = "swap")]
+    SwapOrder::INFO,
+    #[cfg(feature = "carbon")]
+    CarbonCreditAsset::INFO,
+    #[cfg(feature = "provenance")]
+    ProvenanceAsset::INFO,
+    #[cfg(feature = "subscription")]
+    SubscriptionAsset::INFO,
+    #[cfg(feature = "edition")]
+    LimitedEditionAsset::INFO,
+    #[cfg(feature = "eifa")]
+    EpochInflatableAsset::INFO,
+    #[cfg(feature = "claim")]
+    ClaimableAsset::INFO,
+    #[cfg(feature = "registry")]
+    NameRegistry::INFO,
+    #[cfg(feature = "pausable")]
+    PausableAsset::INFO,
+    #[cfg(feature = "rental")]
+    RentalAsset::INFO,
+    #[cfg(feature = "option")]
+    OptionAsset::INFO,
+    #[cfg(feature = "credential")]
+    VerifiableCredential::INFO,
+    #[cfg(feature = "burnable")]
+    BurnableAsset::INFO,
+    #[cfg(feature = "rebasing")]
+    RebasingAsset::INFO,
+    #[cfg(feature = "uda")]
+    UniqueDigitalAsset::INFO,
+];
+
+/// Revisions of all enabled schema families.
+pub const FAMILIES: &[&[SchemaInfo]] = &[
+    #[cfg(feature = "bond")]
+    BondAsset::VERSIONS,
+    #[cfg(feature = "cfa")]
+    CollectibleFungibleAsset::VERSIONS,
+    #[cfg(feature = "collection")]
+    UniqueDigitalCollection::VERSIONS,
+    #[cfg(feature = "fua")]
+    FractionalUniqueAsset::VERSIONS,
+    #[cfg(feature = "governance")]
+    GovernanceAsset::VERSIONS,
+    #[cfg(feature = "ifa")]
+    InflatableFungibleAsset::VERSIONS,
+    #[cfg(feature = "nia")]
+    NonInflatableAsset::VERSIONS,
+    #[cfg(feature = "pfa")]
+    PermissionedFungibleAsset::VERSIONS,
+    #[cfg(feature = "ria")]
+    ReissuableAsset::VERSIONS,
+    #[cfg(feature = "sfa")]
+    SemiFungibleAsset::VERSIONS,
+    #[cfg(feature = "sba")]
+    SoulboundAsset::VERSIONS,
+    #[cfg(feature = "stablecoin")]
+    StablecoinAsset::VERSIONS,
+    #[cfg(feature = "ticket")]
+    TicketAsset::VERSIONS,
+    #[cfg(feature = "vesting")]
+    VestingAsset::VERSIONS,
+    #[cfg(feature = "timelock")]
+    TimelockAsset::VERSIONS,
+    #[cfg(feature = "pfan")]
+    MultiKeyPermissionedAsset::VERSIONS,
+    #[cfg(feature = "freezable")]
+    FreezableAsset::VERSIONS,
+    #[cfg(feature = "tifa")]
+    ThresholdInflatableAsset::VERSIONS,
+    #[cfg(feature = "wbtc")]
+    WrappedBtcAsset::VERSIONS,
+    #[cfg(feature = "fee")]
+    TransferFeeAsset::VERSIONS,
+    #[cfg(feature = "dividend")]
+    DividendAsset::VERSIONS,
+    #[cfg(feature = "escrow")]
+    EscrowAsset::VERSIONS,
+    #[cfg(feature = "swap")]
+    SwapOrder::VERSIONS,
+    #[cfg(feature = "carbon")]
+    CarbonCreditAsset::VERSIONS,
+    #[cfg(feature = "provenance")]
+    ProvenanceAsset::VERSIONS,
+    #[cfg(feature = "subscription")]
+    SubscriptionAsset::VERSIONS,
+    #[cfg(feature = "edition")]
+    LimitedEditionAsset::VERSIONS,
+    #[cfg(feature = "eifa")]
+    EpochInflatableAsset::VERSIONS,
+    #[cfg(feature = "claim")]
+    ClaimableAsset::VERSIONS,
+    #[cfg(feature = "registry")]
+    NameRegistry::VERSIONS,
+    #[cfg(feature = "pausable")]
+    PausableAsset::VERSIONS,
+    #[cfg(feature = "rental")]
+    RentalAsset::VERSIONS,
+    #[cfg(feature = "option")]
+    OptionAsset::VERSIONS,
+    #[cfg(feature = "credential")]
+    VerifiableCredential::VERSIONS,
+    #[cfg(feature = "burnable")]
+    BurnableAsset::VERSIONS,
+    #[cfg(feature = "rebasing")]
+    RebasingAsset::VERSIONS,
+    #[cfg(feature = "uda")]
+    UniqueDigitalAsset::VERSIONS,
+];
+
+/// Looks up the metadata of the schema with the given id, if it is a revision of one of the
+/// enabled schema families.
+pub fn schema_info(schema_id: SchemaId) -> Option<SchemaInfo> {
+    FAMILIES
+        .iter()
+        .flat_map(|versions| versions.iter())
+        .find(|info| info.schema_id == schema_id)
+        .copied()
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use super::*;
+    use crate::kit::KITS;
+
+    #[test]
+    fn matches_kits() {
+        assert_eq!(SCHEMATA.len(), KITS.len());
+        for &(name, schema_id, kit) in KITS {
+            let info = schema_info(schema_id).unwrap();
+            assert_eq!(info.name, name);
+            assert_eq!(kit().schemata.first().unwrap().name.as_str(), name);
+        }
+    }
+
+    #[test]
+    fn issuer_info() {
+        assert_eq!(NonInflatableAsset::info(), NonInflatableAsset::INFO);
+        assert_eq!(NonInflatableAsset::info().version.to_string(), "1.0.0");
+        assert_eq!(schema_info(SchemaId::from_array([0; 32])), None);
+    }
+
+    fn check_family<F: SchemaFamily>() {
+        assert_eq!(F::latest(), *F::VERSIONS.last().unwrap());
+        assert!(F::VERSIONS
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+        for info in F::VERSIONS {
+            assert_eq!(F::version_of(info.schema_id), Some(*info));
+            assert_eq!(schema_info(info.schema_id), Some(*info));
+        }
+    }
+
+    #[test]
+    fn families() {
+        check_family::<BondAsset>();
+        check_family::<CollectibleFungibleAsset>();
+        check_family::<UniqueDigitalCollection>();
+        check_family::<FractionalUniqueAsset>();
+        check_family::<GovernanceAsset>();
+        check_family::<InflatableFungibleAsset>();
+        check_family::<NonInflatableAsset>();
+        check_family::<PermissionedFungibleAsset>();
+        check_family::<ReissuableAsset>();
+        check_family::<SemiFungibleAsset>();
+        check_family::<SoulboundAsset>();
+        check_family::<StablecoinAsset>();
+        check_family::<TicketAsset>();
+        check_family::<VestingAsset>();
+        check_family::<TimelockAsset>();
+        check_family::<MultiKeyPermissionedAsset>();
+        check_family::<FreezableAsset>();
+        check_family::<ThresholdInflatableAsset>();
+        check_family::<WrappedBtcAsset>();
+        check_family::<TransferFeeAsset>();
+        check_family::<DividendAsset>();
+        check_family::<EscrowAsset>();
+        check_family::<SwapOrder>();
+        check_family::<CarbonCreditAsset>();
+        check_family::<ProvenanceAsset>();
+        check_family::<SubscriptionAsset>();
+        check_family::<LimitedEditionAsset>();
+        check_family::<EpochInflatableAsset>();
+        check_family::<ClaimableAsset>();
+        check_family::<NameRegistry>();
+        check_family::<PausableAsset>();
+        check_family::<RentalAsset>();
+        check_family::<OptionAsset>();
+        check_family::<VerifiableCredential>();
+        check_family::<BurnableAsset>();
+        check_family::<RebasingAsset>();
+        check_family::<UniqueDigitalAsset>();
+        assert_eq!(NonInflatableAsset::version_of(UniqueDigitalAsset::V1.schema_id), None);
+    }
+
+    #[test]
+    fn distinct_release_notes() {
+        let hashes = SCHEMATA
+            .iter()
+            .map(SchemaInfo::release_notes_hash)
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(hashes.len(), SCHEMATA.len());
+    }
+}
@@ -0,0 +1,215 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Invoices for receiving the assets of a contract.
+//!
+//! The schema wrappers build invoices with the assignment type and the kind of state expected by
+//! their schema: an amount of the asset for the fungible schemata and a token allocation for the
+//! unique ones. Amounts typed by users are parsed with [`parse_amount`] according to the precision
+//! of the asset.
+
+use std::str::FromStr;
+
+use rgbstd::contract::ContractData;
+use rgbstd::invoice::{Beneficiary, CoinAmount, RgbInvoice, RgbInvoiceBuilder, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::{Allocation, Amount, Precision};
+
+use crate::{Error, OS_ASSET};
+
+/// Parses an amount given in whole units of an asset with the given precision, like `"12.5"`,
+/// into the number of its smallest units.
+///
+/// The precision may also be set explicitly, as in `"12.05~2"`; digits beyond the precision of
+/// the asset are accepted only if they are zeros.
+pub fn parse_amount(s: &str, precision: Precision) -> Result<Amount, Error> {
+    let coins = CoinAmount::from_str(s)?;
+    let from = coins.precision().decimals() as u32;
+    let to = precision.decimals() as u32;
+    let fract = if from > to {
+        let div = 10u64.pow(from - to);
+        if coins.fract() % div != 0 {
+            return Err(Error::ExcessivePrecision(coins, precision.decimals()));
+        }
+        coins.fract() / div
+    } else {
+        // the fractional part has less than `from` digits, so this doesn't overflow
+        coins.fract() * 10u64.pow(to - from)
+    };
+    coins
+        .int()
+        .checked_mul(10u64.pow(to))
+        .and_then(|int| int.checked_add(fract))
+        .map(Amount::from)
+        .ok_or(Error::AmountOverflow(coins))
+}
+
+/// Starts an invoice for the asset of the contract, failing if its schema has no asset
+/// assignments.
+fn builder<S: ContractStateRead>(
+    data: &ContractData<S>,
+    beneficiary: impl Into<XChainNet<Beneficiary>>,
+) -> Result<RgbInvoiceBuilder, Error> {
+    let schema = &data.schema;
+    let asset = schema
+        .owned_types
+        .get(&OS_ASSET)
+        .ok_or(Error::UnknownOwnedType(OS_ASSET))?;
+    Ok(RgbInvoiceBuilder::with(data.contract_id(), beneficiary)
+        .set_schema(schema.schema_id())
+        .set_assignment_name(asset.name.clone()))
+}
+
+/// Builds an invoice for an amount of a fungible asset, given in its smallest units.
+pub fn amount_invoice<S: ContractStateRead>(
+    data: &ContractData<S>,
+    beneficiary: impl Into<XChainNet<Beneficiary>>,
+    amount: Amount,
+) -> Result<RgbInvoice, Error> {
+    Ok(builder(data, beneficiary)?.set_amount_raw(amount).finish())
+}
+
+/// Builds an invoice for an allocation of a token.
+pub fn allocation_invoice<S: ContractStateRead>(
+    data: &ContractData<S>,
+    beneficiary: impl Into<XChainNet<Beneficiary>>,
+    allocation: Allocation,
+) -> Result<RgbInvoice, Error> {
+    Ok(builder(data, beneficiary)?
+        .set_allocation_raw(allocation)
+        .finish())
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use amplify::Wrapper;
+    use rgbstd::invoice::{InvoiceState, NonFungible};
+    use rgbstd::TokenIndex;
+    use schemata_tools::testing::{seal, CHAIN_NET};
+
+    use super::*;
+    use crate::{
+        fixtures, CfaWrapper, CollectionWrapper, FuaWrapper, FungibleAssetWrapper, NiaWrapper,
+        UdaWrapper,
+    };
+
+    fn beneficiary() -> XChainNet<Beneficiary> {
+        XChainNet::with(CHAIN_NET, Beneficiary::from(seal(7).to_secret_seal()))
+    }
+
+    fn check(invoice: RgbInvoice, name: &str, state: InvoiceState) {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id(name)).unwrap();
+        assert_eq!(invoice.contract, Some(data.contract_id()));
+        assert_eq!(invoice.schema, Some(data.schema.schema_id()));
+        assert_eq!(invoice.assignment_name, Some(fname!("assetOwner")));
+        assert_eq!(invoice.assignment_state, Some(state));
+        assert_eq!(RgbInvoice::from_str(&invoice.to_string()).unwrap(), invoice);
+    }
+
+    #[test]
+    fn fungible() {
+        let stock = fixtures::stock();
+        let nia = NiaWrapper::try_with(stock.contract_data(fixtures::contract_id("nia")).unwrap())
+            .unwrap();
+        let invoice = nia.invoice(beneficiary(), "1.5").unwrap();
+        check(invoice, "nia", InvoiceState::Amount(Amount::from(150_000_000u64)));
+        assert_eq!(
+            nia.invoice(beneficiary(), "1.5").unwrap(),
+            nia.invoice_raw(beneficiary(), Amount::from(150_000_000u64))
+        );
+
+        let cfa = CfaWrapper::try_with(stock.contract_data(fixtures::contract_id("cfa")).unwrap())
+            .unwrap();
+        let invoice = cfa.invoice(beneficiary(), "0.00000001").unwrap();
+        check(invoice, "cfa", InvoiceState::Amount(Amount::from(1u64)));
+        assert!(matches!(
+            cfa.invoice(beneficiary(), "0.000000001"),
+            Err(Error::ExcessivePrecision(_, 8))
+        ));
+    }
+
+    #[test]
+    fn unique() {
+        let stock = fixtures::stock();
+        let uda = UdaWrapper::try_with(stock.contract_data(fixtures::contract_id("uda")).unwrap())
+            .unwrap();
+        let allocation = Allocation::with(fixtures::TOKEN_INDEX, 1);
+        let state = InvoiceState::Data(NonFungible::FractionedToken(allocation));
+        check(uda.invoice(beneficiary()), "uda", state);
+
+        let fua = FuaWrapper::try_with(stock.contract_data(fixtures::contract_id("fua")).unwrap())
+            .unwrap();
+        let allocation = Allocation::with(fixtures::TOKEN_INDEX, 3);
+        let state = InvoiceState::Data(NonFungible::FractionedToken(allocation));
+        check(fua.invoice(beneficiary(), 3), "fua", state);
+
+        let data = stock
+            .contract_data(fixtures::contract_id("collection"))
+            .unwrap();
+        let collection = CollectionWrapper::try_with(data).unwrap();
+        let index = TokenIndex::from_inner(fixtures::COLLECTION[1]);
+        let state = InvoiceState::Data(NonFungible::FractionedToken(Allocation::with(index, 1)));
+        check(collection.invoice(beneficiary(), index), "collection", state);
+        let unknown = TokenIndex::from_inner(3);
+        assert_eq!(
+            collection.try_invoice(beneficiary(), unknown),
+            Err(Error::UnknownToken(unknown))
+        );
+    }
+
+    #[test]
+    fn no_asset() {
+        // Swap orders only assign the order right
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("swap")).unwrap();
+        assert_eq!(
+            amount_invoice(&data, beneficiary(), Amount::from(1u64)),
+            Err(Error::UnknownOwnedType(OS_ASSET))
+        );
+        assert_eq!(
+            allocation_invoice(&data, beneficiary(), Allocation::with(fixtures::TOKEN_INDEX, 1)),
+            Err(Error::UnknownOwnedType(OS_ASSET))
+        );
+    }
+
+    #[test]
+    fn amounts() {
+        let parse = |s| parse_amount(s, Precision::Centi).map(Amount::value);
+        assert_eq!(parse("12"), Ok(1200));
+        assert_eq!(parse("12.5"), Ok(1250));
+        assert_eq!(parse("12.05"), Ok(1205));
+        assert_eq!(parse("1 000.01"), Ok(100001));
+        assert_eq!(parse("12.500"), Ok(1250));
+        assert_eq!(parse("12.05~2"), Ok(1205));
+        assert_eq!(parse("12.5~1"), Ok(1250));
+        assert_eq!(
+            parse("0.001"),
+            Err(Error::ExcessivePrecision(CoinAmount::from_str("0.001").unwrap(), 2))
+        );
+        assert!(matches!(parse("184467440737095517"), Err(Error::AmountOverflow(_))));
+        assert!(matches!(parse("1,5"), Err(Error::InvalidAmount(_))));
+        assert!(matches!(parse("-1"), Err(Error::InvalidAmount(_))));
+
+        assert_eq!(parse_amount("42", Precision::Indivisible).map(Amount::value), Ok(42));
+        assert!(parse_amount("4.2", Precision::Indivisible).is_err());
+    }
+}
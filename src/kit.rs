@@ -0,0 +1,281 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schema kits, bundling each schema with its AluVM libraries and type system.
+//!
+//! The build script of `rgb-schemata-tools` generates the kit files of all schemata into its
+//! `OUT_DIR`, and its `rgb-schemas` binary publishes them into `schemata/`.
+
+use std::path::Path;
+use std::{io, panic, thread};
+
+use rgbstd::containers::{FileContent, Kit};
+use rgbstd::contract::IssuerWrapper;
+use rgbstd::SchemaId;
+
+#[cfg(feature = "bond")]
+use crate::bond::{BondAsset, BOND_SCHEMA_ID};
+#[cfg(feature = "burnable")]
+use crate::burnable::{BurnableAsset, BURNABLE_SCHEMA_ID};
+#[cfg(feature = "carbon")]
+use crate::carbon::{CarbonCreditAsset, CARBON_SCHEMA_ID};
+#[cfg(feature = "cfa")]
+use crate::cfa::{CollectibleFungibleAsset, CFA_SCHEMA_ID};
+#[cfg(feature = "claim")]
+use crate::claim::{ClaimableAsset, CLAIM_SCHEMA_ID};
+#[cfg(feature = "collection")]
+use crate::collection::{UniqueDigitalCollection, COLLECTION_SCHEMA_ID};
+#[cfg(feature = "credential")]
+use crate::credential::{VerifiableCredential, CREDENTIAL_SCHEMA_ID};
+#[cfg(feature = "dividend")]
+use crate::dividend::{DividendAsset, DIVIDEND_SCHEMA_ID};
+#[cfg(feature = "edition")]
+use crate::edition::{LimitedEditionAsset, EDITION_SCHEMA_ID};
+#[cfg(feature = "eifa")]
+use crate::eifa::{EpochInflatableAsset, EIFA_SCHEMA_ID};
+#[cfg(feature = "escrow")]
+use crate::escrow::{EscrowAsset, ESCROW_SCHEMA_ID};
+#[cfg(feature = "fee")]
+use crate::fee::{TransferFeeAsset, FEE_SCHEMA_ID};
+#[cfg(feature = "freezable")]
+use crate::freezable::{FreezableAsset, FREEZABLE_SCHEMA_ID};
+#[cfg(feature = "fua")]
+use crate::fua::{FractionalUniqueAsset, FUA_SCHEMA_ID};
+#[cfg(feature = "governance")]
+use crate::governance::{GovernanceAsset, GOVERNANCE_SCHEMA_ID};
+#[cfg(feature = "ifa")]
+use crate::ifa::{InflatableFungibleAsset, IFA_SCHEMA_ID};
+#[cfg(feature = "nia")]
+use crate::nia::{NonInflatableAsset, NIA_SCHEMA_ID};
+#[cfg(feature = "option")]
+use crate::option::{OptionAsset, OPTION_SCHEMA_ID};
+#[cfg(feature = "pausable")]
+use crate::pausable::{PausableAsset, PAUSABLE_SCHEMA_ID};
+#[cfg(feature = "pfa")]
+use crate::pfa::{PermissionedFungibleAsset, PFA_SCHEMA_ID};
+#[cfg(feature = "pfan")]
+use crate::pfan::{MultiKeyPermissionedAsset, PFAN_SCHEMA_ID};
+#[cfg(feature = "provenance")]
+use crate::provenance::{ProvenanceAsset, PROVENANCE_SCHEMA_ID};
+#[cfg(feature = "rebasing")]
+use crate::rebasing::{RebasingAsset, REBASING_SCHEMA_ID};
+#[cfg(feature = "registry")]
+use crate::registry::{NameRegistry, REGISTRY_SCHEMA_ID};
+#[cfg(feature = "rental")]
+use crate::rental::{RentalAsset, RENTAL_SCHEMA_ID};
+#[cfg(feature = "ria")]
+use crate::ria::{ReissuableAsset, RIA_SCHEMA_ID};
+#[cfg(feature = "sba")]
+use crate::sba::{SoulboundAsset, SBA_SCHEMA_ID};
+#[cfg(feature = "sfa")]
+use crate::sfa::{SemiFungibleAsset, SFA_SCHEMA_ID};
+#[cfg(feature = "stablecoin")]
+use crate::stablecoin::{StablecoinAsset, STABLECOIN_SCHEMA_ID};
+#[cfg(feature = "subscription")]
+use crate::subscription::{SubscriptionAsset, SUBSCRIPTION_SCHEMA_ID};
+#[cfg(feature = "swap")]
+use crate::swap::{SwapOrder, SWAP_SCHEMA_ID};
+#[cfg(feature = "ticket")]
+use crate::ticket::{TicketAsset, TICKET_SCHEMA_ID};
+#[cfg(feature = "tifa")]
+use crate::tifa::{ThresholdInflatableAsset, TIFA_SCHEMA_ID};
+#[cfg(feature = "timelock")]
+use crate::timelock::{TimelockAsset, TIMELOCK_SCHEMA_ID};
+#[cfg(feature = "uda")]
+use crate::uda::{UniqueDigitalAsset, UDA_SCHEMA_ID};
+#[cfg(feature = "vesting")]
+use crate::vesting::{VestingAsset, VESTING_SCHEMA_ID};
+#[cfg(feature = "wbtc")]
+use crate::wbtc::{WrappedBtcAsset, WBTC_SCHEMA_ID};
+
+/// Constructor of a schema kit.
+pub type KitConstructor = fn() -> Kit;
+
+/// File name stems of the kits of all enabled schemata, with the schema id each kit must carry and
+/// the constructor of the kit.
+pub const KITS: &[(&str, SchemaId, KitConstructor)] = &[
+    #[cfg(feature = "bond")]
+    ("BondAsset", BOND_SCHEMA_ID, kit::<BondAsset>),
+    #[cfg(feature = "cfa")]
+    ("CollectibleFungibleAsset", CFA_SCHEMA_ID, kit::<CollectibleFungibleAsset>),
+    #[cfg(feature = "collection")]
+    ("UniqueDigitalCollection", COLLECTION_SCHEMA_ID, kit::<UniqueDigitalCollection>),
+    #[cfg(feature = "fua")]
+    ("FractionalUniqueAsset", FUA_SCHEMA_ID, kit::<FractionalUniqueAsset>),
+    #[cfg(feature = "governance")]
+    ("GovernanceAsset", GOVERNANCE_SCHEMA_ID, kit::<GovernanceAsset>),
+    #[cfg(feature = "ifa")]
+    ("InflatableFungibleAsset", IFA_SCHEMA_ID, kit::<InflatableFungibleAsset>),
+    #[cfg(feature = "nia")]
+    ("NonInflatableAsset", NIA_SCHEMA_ID, kit::<NonInflatableAsset>),
+    #[cfg(feature = "pfa")]
+    ("PermissionedFungibleAsset", PFA_SCHEMA_ID, kit::<PermissionedFungibleAsset>),
+    #[cfg(feature = "ria")]
+    ("ReissuableAsset", RIA_SCHEMA_ID, kit::<ReissuableAsset>),
+    #[cfg(feature = "sfa")]
+    ("SemiFungibleAsset", SFA_SCHEMA_ID, kit::<SemiFungibleAsset>),
+    #[cfg(feature = "sba")]
+    ("SoulboundAsset", SBA_SCHEMA_ID, kit::<SoulboundAsset>),
+    #[cfg(feature = "stablecoin")]
+    ("StablecoinAsset", STABLECOIN_SCHEMA_ID, kit::<StablecoinAsset>),
+    #[cfg(feature = "ticket")]
+    ("TicketAsset", TICKET_SCHEMA_ID, kit::<TicketAsset>),
+    #[cfg(feature = "vesting")]
+    ("VestingAsset", VESTING_SCHEMA_ID, kit::<VestingAsset>),
+    #[cfg(feature = "timelock")]
+    ("TimelockAsset", TIMELOCK_SCHEMA_ID, kit::<TimelockAsset>),
+    #[cfg(feature = "pfan")]
+    ("MultiKeyPermissionedAsset", PFAN_SCHEMA_ID, kit::<MultiKeyPermissionedAsset>),
+    #[cfg(feature = "freezable")]
+    ("FreezableAsset", FREEZABLE_SCHEMA_ID, kit::<FreezableAsset>),
+    #[cfg(feature = "tifa")]
+    ("ThresholdInflatableAsset", TIFA_SCHEMA_ID, kit::<ThresholdInflatableAsset>),
+    #[cfg(feature = "wbtc")]
+    ("WrappedBtcAsset", WBTC_SCHEMA_ID, kit::<WrappedBtcAsset>),
+    #[cfg(feature = "fee")]
+    ("TransferFeeAsset", FEE_SCHEMA_ID, kit::<TransferFeeAsset>),
+    #[cfg(feature = "dividend")]
+    ("DividendAsset", DIVIDEND_SCHEMA_ID, kit::<DividendAsset>),
+    #[cfg(feature = "escrow")]
+    ("EscrowAsset", ESCROW_SCHEMA_ID, kit::<EscrowAsset>),
+    #[cfg(feature = "swap")]
+    ("SwapOrder", SWAP_SCHEMA_ID, kit::<SwapOrder>),
+    #[cfg(feature = "carbon")]
+    ("CarbonCreditAsset", CARBON_SCHEMA_ID, kit::<CarbonCreditAsset>),
+    #[cfg(feature = "provenance")]
+    ("ProvenanceAsset", PROVENANCE_SCHEMA_ID, kit::<ProvenanceAsset>),
+    #[cfg(feature = "subscription")]
+    ("SubscriptionAsset", SUBSCRIPTION_SCHEMA_ID, kit::<SubscriptionAsset>),
+    #[cfg(feature = "edition")]
+    ("LimitedEditionAsset", EDITION_SCHEMA_ID, kit::<LimitedEditionAsset>),
+    #[cfg(feature = "eifa")]
+    ("EpochInflatableAsset", EIFA_SCHEMA_ID, kit::<EpochInflatableAsset>),
+    #[cfg(feature = "claim")]
+    ("ClaimableAsset", CLAIM_SCHEMA_ID, kit::<ClaimableAsset>),
+    #[cfg(feature = "registry")]
+    ("NameRegistry", REGISTRY_SCHEMA_ID, kit::<NameRegistry>),
+    #[cfg(feature = "pausable")]
+    ("PausableAsset", PAUSABLE_SCHEMA_ID, kit::<PausableAsset>),
+    #[cfg(feature = "rental")]
+    ("RentalAsset", RENTAL_SCHEMA_ID, kit::<RentalAsset>),
+    #[cfg(feature = "option")]
+    ("OptionAsset", OPTION_SCHEMA_ID, kit::<OptionAsset>),
+    #[cfg(feature = "credential")]
+    ("VerifiableCredential", CREDENTIAL_SCHEMA_ID, kit::<VerifiableCredential>),
+    #[cfg(feature = "burnable")]
+    ("BurnableAsset", BURNABLE_SCHEMA_ID, kit::<BurnableAsset>),
+    #[cfg(feature = "rebasing")]
+    ("RebasingAsset", REBASING_SCHEMA_ID, kit::<RebasingAsset>),
+    #[cfg(feature = "uda")]
+    ("UniqueDigitalAsset", UDA_SCHEMA_ID, kit::<UniqueDigitalAsset>),
+];
+
+/// Constructs the kit of the schema issued by `I`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", fields(issuer = std::any::type_name::<I>()))
+)]
+pub fn kit<I: IssuerWrapper>() -> Kit {
+    let mut kit = Kit::default();
+    kit.schemata.push(I::schema()).expect("single schema");
+    kit.scripts
+        .extend(I::scripts().into_values())
+        .expect("schema libraries exceed kit limits");
+    kit.types = I::types();
+    kit
+}
+
+/// Schema whose id differs from the constant declared by its module.
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+#[display("{name} schema id is {actual}, while its module declares {declared}")]
+pub struct SchemaIdMismatch {
+    /// File name stem of the schema kit.
+    pub name: &'static str,
+    /// Schema id constant declared by the schema module.
+    pub declared: SchemaId,
+    /// Id of the schema built by the module.
+    pub actual: SchemaId,
+}
+
+/// Recomputes the id of every enabled schema, returning those not matching their declared
+/// constant.
+pub fn schema_id_mismatches() -> Vec<SchemaIdMismatch> {
+    KITS.iter()
+        .filter_map(|&(name, declared, kit)| {
+            let actual = kit()
+                .schemata
+                .first()
+                .expect("kit has a schema")
+                .schema_id();
+            (actual != declared).then_some(SchemaIdMismatch {
+                name,
+                declared,
+                actual,
+            })
+        })
+        .collect()
+}
+
+/// Writes the binary (`.rgb`) and armored (`.rgba`) kit of every schema into `dir`, failing if a
+/// kit doesn't carry the schema id declared by its module.
+pub fn save_kits(dir: impl AsRef<Path>) -> io::Result<()> {
+    if let Some(mismatch) = schema_id_mismatches().first() {
+        #[cfg(feature = "tracing")]
+        tracing::error!(%mismatch, "refusing to save kits");
+        return Err(io::Error::new(io::ErrorKind::InvalidData, mismatch.to_string()));
+    }
+    save_kits_unchecked(dir)
+}
+
+/// Writes the kit of every schema into `dir` like [`save_kits`], without verifying schema ids.
+///
+/// Kits are independent from each other, so each of them is generated on its own thread.
+pub fn save_kits_unchecked(dir: impl AsRef<Path>) -> io::Result<()> {
+    let dir = dir.as_ref();
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!("save_kits", dir = %dir.display());
+    thread::scope(|scope| {
+        let handles = KITS
+            .iter()
+            .map(|&(name, _, kit)| {
+                #[cfg(feature = "tracing")]
+                let span = span.clone();
+                scope.spawn(move || {
+                    #[cfg(feature = "tracing")]
+                    let _entered = span.enter();
+                    save_kit(dir, name, kit)
+                })
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().try_for_each(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|err| panic::resume_unwind(err))
+        })
+    })
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip(dir, kit), err(Display)))]
+fn save_kit(dir: &Path, name: &str, kit: KitConstructor) -> io::Result<()> {
+    let kit = kit();
+    kit.save_file(dir.join(format!("{name}.rgb")))?;
+    kit.save_armored(dir.join(format!("{name}.rgba")))
+}
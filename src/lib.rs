@@ -21,70 +21,350 @@
 
 #[macro_use]
 extern crate amplify;
+#[cfg_attr(not(any_schema), allow(unused_imports))]
 #[macro_use]
 extern crate strict_types;
 
+#[cfg(test)]
+mod asm;
+#[cfg(feature = "bond")]
+mod bond;
+#[cfg(feature = "cfa")]
 mod cfa;
+#[cfg(feature = "collection")]
+mod collection;
+mod consts;
+#[cfg(feature = "pfa")]
+pub mod cosign;
+pub mod blinding;
+pub mod capabilities;
+pub mod diff;
+pub mod genesis;
+#[cfg(any(
+    feature = "bond",
+    feature = "vesting",
+    feature = "timelock",
+    feature = "eifa",
+    feature = "rental",
+    feature = "option",
+    feature = "subscription"
+))]
+pub mod height;
+pub mod dumb;
+mod errno;
+mod error;
+#[cfg(feature = "fua")]
+mod fua;
+#[cfg(feature = "governance")]
+mod governance;
+#[cfg(any(feature = "nia", feature = "cfa", feature = "pfa", feature = "ifa", feature = "ria"))]
+mod fungible;
+#[cfg(feature = "nia")]
 mod nia;
+#[cfg(feature = "pfa")]
 mod pfa;
+pub mod prelude;
+pub mod reject;
+pub mod report;
+#[cfg(feature = "uda")]
 mod uda;
+#[cfg(feature = "ifa")]
 mod ifa;
+#[cfg(feature = "ria")]
+mod ria;
+#[cfg(feature = "sba")]
+mod sba;
+#[cfg(feature = "sfa")]
+mod sfa;
+#[cfg(feature = "stablecoin")]
+mod stablecoin;
+#[cfg(feature = "ticket")]
+mod ticket;
+#[cfg(feature = "vesting")]
+mod vesting;
+#[cfg(feature = "timelock")]
+mod timelock;
+#[cfg(feature = "pfan")]
+mod pfan;
+#[cfg(feature = "freezable")]
+mod freezable;
+#[cfg(feature = "tifa")]
+mod tifa;
+#[cfg(feature = "wbtc")]
+mod wbtc;
+#[cfg(feature = "fee")]
+mod fee;
+#[cfg(feature = "dividend")]
+mod dividend;
+#[cfg(feature = "escrow")]
+mod escrow;
+#[cfg(feature = "swap")]
+mod swap;
+#[cfg(feature = "carbon")]
+mod carbon;
+#[cfg(feature = "claim")]
+mod claim;
+#[cfg(feature = "provenance")]
+mod provenance;
+#[cfg(feature = "subscription")]
+mod subscription;
+#[cfg(feature = "edition")]
+mod edition;
+#[cfg(feature = "eifa")]
+mod eifa;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "pausable")]
+mod pausable;
+#[cfg(feature = "rental")]
+mod rental;
+#[cfg(feature = "option")]
+mod option;
+#[cfg(feature = "credential")]
+mod credential;
+#[cfg(feature = "burnable")]
+mod burnable;
+#[cfg(feature = "rebasing")]
+mod rebasing;
+pub mod info;
+pub mod invoice;
+pub mod kit;
+pub mod metrics;
+pub mod scripts;
+pub mod selection;
+pub mod spec;
+pub mod split;
+pub mod summary;
+#[cfg(any(feature = "uda", feature = "fua", feature = "collection"))]
+pub mod tokens;
+#[cfg_attr(not(any_schema), allow(dead_code))]
+mod wrapper;
+pub mod types;
+#[cfg(test)]
+mod fixtures;
 
+#[cfg(feature = "bond")]
+pub use bond::{BondAsset, BondWrapper, BOND_SCHEMA_ID};
+#[cfg(feature = "burnable")]
+pub use burnable::{BurnableAsset, BurnableWrapper, BURNABLE_SCHEMA_ID};
+#[cfg(feature = "carbon")]
+pub use carbon::{CarbonCreditAsset, CarbonWrapper, Retirement, CARBON_SCHEMA_ID};
+#[cfg(feature = "cfa")]
 pub use cfa::{CfaWrapper, CollectibleFungibleAsset, CFA_SCHEMA_ID};
-pub use ifa::{IfaWrapper, InflatableFungibleAsset, IFA_SCHEMA_ID};
+#[cfg(feature = "claim")]
+pub use claim::{ClaimWrapper, ClaimableAsset, CLAIM_SCHEMA_ID};
+#[cfg(feature = "collection")]
+pub use collection::{
+    CollectionWrapper, UniqueDigitalCollection, COLLECTION_MAX_TOKENS, COLLECTION_SCHEMA_ID,
+};
+#[cfg_attr(not(any_schema), allow(unused_imports))]
+pub(crate) use consts::{standard_types, verify_entry_point, PrecompiledLib};
+#[cfg(feature = "credential")]
+pub use credential::{CredentialWrapper, VerifiableCredential, CREDENTIAL_SCHEMA_ID};
+#[cfg(feature = "dividend")]
+pub use dividend::{DividendAsset, DividendWrapper, DIVIDEND_SCHEMA_ID};
+#[cfg(feature = "edition")]
+pub use edition::{EditionWrapper, LimitedEditionAsset, EDITION_SCHEMA_ID};
+#[cfg(feature = "eifa")]
+pub use eifa::{EifaWrapper, EpochInflatableAsset, EIFA_SCHEMA_ID};
+pub use errno::{ErrnoEmitter, UnknownErrno, ValidationErrno};
+pub use error::{Error, WrongSchema};
+#[cfg(feature = "escrow")]
+pub use escrow::{EscrowAsset, EscrowWrapper, ESCROW_SCHEMA_ID};
+#[cfg(feature = "fee")]
+pub use fee::{FeeWrapper, TransferFeeAsset, FEE_SCHEMA_ID, MAX_FEE_RATE};
+#[cfg(feature = "freezable")]
+pub use freezable::{FreezableAsset, FreezableWrapper, FREEZABLE_SCHEMA_ID};
+#[cfg(feature = "fua")]
+pub use fua::{FractionShare, FractionalUniqueAsset, FuaWrapper, FUA_SCHEMA_ID};
+#[cfg(any(
+    feature = "nia",
+    feature = "cfa",
+    feature = "pfa",
+    feature = "ifa",
+    feature = "ria"
+))]
+pub use fungible::{FungibleSchema, FungibleView};
+#[cfg(feature = "governance")]
+pub use governance::{GovernanceAsset, GovernanceWrapper, GOVERNANCE_SCHEMA_ID};
+#[cfg(feature = "ifa")]
+pub use ifa::{IfaWrapper, InflatableFungibleAsset, IFA_SCHEMA_ID, IFA_V1_SCHEMA_ID};
+#[cfg(feature = "nia")]
 pub use nia::{NiaWrapper, NonInflatableAsset, NIA_SCHEMA_ID};
-pub use pfa::{PermissionedFungibleAsset, PfaWrapper, PFA_SCHEMA_ID};
-use rgbstd::{AssignmentType, GlobalStateType, MetaType, TransitionType};
-pub use uda::{UdaWrapper, UniqueDigitalAsset, UDA_SCHEMA_ID};
+#[cfg(feature = "option")]
+pub use option::{OptionAsset, OptionTerms, OptionWrapper, OPTION_SCHEMA_ID};
+#[cfg(feature = "pausable")]
+pub use pausable::{PausableAsset, PausableWrapper, PAUSABLE_SCHEMA_ID};
+#[cfg(feature = "pfa")]
+pub use pfa::{PermissionedFungibleAsset, PfaWrapper, PFA_SCHEMA_ID, PFA_V1_SCHEMA_ID};
+#[cfg(feature = "pfan")]
+pub use pfan::{MultiKeyPermissionedAsset, PfanWrapper, PFAN_SCHEMA_ID};
+#[cfg(feature = "provenance")]
+pub use provenance::{Checkpoint, ProvenanceAsset, ProvenanceWrapper, PROVENANCE_SCHEMA_ID};
+#[cfg(feature = "rebasing")]
+pub use rebasing::{RebasingAsset, RebasingWrapper, REBASE_SCALE, REBASING_SCHEMA_ID};
+#[cfg(feature = "registry")]
+pub use registry::{normalize_name, NameRegistry, RegistryWrapper, REGISTRY_SCHEMA_ID};
+#[cfg(feature = "rental")]
+pub use rental::{RentalAsset, RentalWrapper, RENTAL_SCHEMA_ID};
+#[cfg(feature = "ria")]
+pub use ria::{ReissuableAsset, RiaWrapper, RIA_SCHEMA_ID};
+#[cfg(feature = "sba")]
+pub use sba::{HolderStatus, SbaWrapper, SoulboundAsset, SBA_SCHEMA_ID};
+#[cfg(feature = "sfa")]
+pub use sfa::{SemiFungibleAsset, SfaWrapper, SFA_MAX_TOKENS, SFA_SCHEMA_ID};
+#[cfg(feature = "stablecoin")]
+pub use stablecoin::{StablecoinAsset, StablecoinWrapper, STABLECOIN_SCHEMA_ID};
+#[cfg(feature = "subscription")]
+pub use subscription::{SubscriptionAsset, SubscriptionWrapper, SUBSCRIPTION_SCHEMA_ID};
+#[cfg(feature = "swap")]
+pub use swap::{SwapOrder, SwapOrderWrapper, SWAP_SCHEMA_ID};
+#[cfg(feature = "ticket")]
+pub use ticket::{TicketAsset, TicketWrapper, TICKET_MAX_TICKETS, TICKET_SCHEMA_ID};
+#[cfg(feature = "tifa")]
+pub use tifa::{ThresholdInflatableAsset, TifaWrapper, TIFA_SCHEMA_ID};
+#[cfg(feature = "timelock")]
+pub use timelock::{TimelockAsset, TimelockWrapper, TIMELOCK_SCHEMA_ID};
+pub use types::global::*;
+#[cfg(any(
+    feature = "ifa",
+    feature = "stablecoin",
+    feature = "pfan",
+    feature = "tifa",
+    feature = "wbtc",
+    feature = "eifa"
+))]
+pub use types::meta::*;
+pub use types::owned::*;
+pub use types::transition::*;
+#[cfg(feature = "uda")]
+pub use uda::{UdaWrapper, UniqueDigitalAsset, UDA_SCHEMA_ID, UDA_V1_SCHEMA_ID, UDA_V2_SCHEMA_ID};
+#[cfg(feature = "vesting")]
+pub use vesting::{VestingAsset, VestingSchedule, VestingWrapper, VESTING_SCHEMA_ID};
+#[cfg(feature = "wbtc")]
+pub use wbtc::{ReserveAttestation, WbtcWrapper, WrappedBtcAsset, WBTC_SCHEMA_ID};
+pub use wrapper::{ContractWrapper, FungibleAssetWrapper};
 
-pub const GS_ART: GlobalStateType = GlobalStateType::with(3000);
-pub const GS_ATTACH: GlobalStateType = GlobalStateType::with(2104);
-pub const GS_REJECT_LIST_URL: GlobalStateType = GlobalStateType::with(2012);
-pub const GS_DETAILS: GlobalStateType = GlobalStateType::with(3004);
-pub const GS_ENGRAVINGS: GlobalStateType = GlobalStateType::with(2103);
-pub const GS_ISSUED_SUPPLY: GlobalStateType = GlobalStateType::with(2010);
-pub const GS_MAX_SUPPLY: GlobalStateType = GlobalStateType::with(2011);
-pub const GS_NAME: GlobalStateType = GlobalStateType::with(3001);
-pub const GS_NOMINAL: GlobalStateType = GlobalStateType::with(2000);
-pub const GS_PRECISION: GlobalStateType = GlobalStateType::with(3005);
-pub const GS_TERMS: GlobalStateType = GlobalStateType::with(2001);
-pub const GS_TOKENS: GlobalStateType = GlobalStateType::with(2102);
-pub const GS_PUBKEY: GlobalStateType = GlobalStateType::with(3006);
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "nia")]
+    use aluvm::isa::opcodes::INSTR_PUTA;
+    use rgbstd::contract::IssuerWrapper;
+    #[cfg(feature = "nia")]
+    use rgbstd::vm::opcodes::INSTR_SVS;
+    use rgbstd::SchemaId;
 
-pub const OS_ASSET: AssignmentType = AssignmentType::with(4000);
-pub const OS_INFLATION: AssignmentType = AssignmentType::with(4010);
-pub const OS_REPLACE: AssignmentType = AssignmentType::with(4012);
+    use super::*;
+    #[cfg(feature = "nia")]
+    use crate::scripts::NIA_TRANSFER;
 
-pub const TS_INFLATION: TransitionType = TransitionType::with(8000);
-pub const TS_BURN: TransitionType = TransitionType::with(8010);
-pub const TS_REPLACE: TransitionType = TransitionType::with(8011);
-pub const TS_TRANSFER: TransitionType = TransitionType::with(10000);
+    #[test]
+    #[cfg(feature = "nia")]
+    fn entry_point() { NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]); }
 
-pub const MS_ALLOWED_INFLATION: MetaType = MetaType::with(1000);
-
-pub const ERRNO_NON_EQUAL_IN_OUT: u8 = 0;
-pub const ERRNO_ISSUED_MISMATCH: u8 = 1;
-pub const ERRNO_NON_FRACTIONAL: u8 = 10;
-pub const ERRNO_MISSING_PUBKEY: u8 = 20;
-pub const ERRNO_INVALID_SIGNATURE: u8 = 21;
-pub const ERRNO_INFLATION_MISMATCH: u8 = 30;
-pub const ERRNO_INFLATION_EXCEEDS_ALLOWANCE: u8 = 31;
-pub const ERRNO_REPLACE_NO_INPUT: u8 = 35;
-pub const ERRNO_REPLACE_HIDDEN_BURN: u8 = 36;
+    #[test]
+    #[cfg(feature = "nia")]
+    #[should_panic(expected = "unexpected instruction at offset 1")]
+    fn moved_entry_point() {
+        verify_entry_point(NIA_TRANSFER.lib(), NIA_TRANSFER.offset() + 1, &[(0, INSTR_PUTA)]);
+    }
 
-pub mod dumb {
-    use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
-    use rgbstd::{ChainNet, Txid};
+    #[test]
+    #[cfg(feature = "nia")]
+    #[should_panic(expected = "unexpected instruction")]
+    fn entry_point_out_of_code() {
+        verify_entry_point(NIA_TRANSFER.lib(), u16::MAX / 2, &[(0, INSTR_PUTA)]);
+    }
 
-    pub struct NoResolver;
+    #[cfg_attr(not(any_schema), allow(dead_code))]
+    fn check_cached<I: IssuerWrapper>(schema_id: SchemaId) {
+        let schema = I::schema();
+        assert_eq!(schema.schema_id(), schema_id);
+        assert_eq!(I::schema(), schema);
+        assert_eq!(I::scripts(), I::scripts());
+        assert_eq!(I::types().id(), I::types().id());
+    }
 
-    impl ResolveWitness for NoResolver {
-        fn resolve_witness(&self, _: Txid) -> Result<WitnessStatus, WitnessResolverError> {
-            unreachable!()
-        }
+    #[test]
+    fn cached() {
+        #[cfg(feature = "nia")]
+        check_cached::<NonInflatableAsset>(NIA_SCHEMA_ID);
+        #[cfg(feature = "cfa")]
+        check_cached::<CollectibleFungibleAsset>(CFA_SCHEMA_ID);
+        #[cfg(feature = "uda")]
+        check_cached::<UniqueDigitalAsset>(UDA_SCHEMA_ID);
+        #[cfg(feature = "fua")]
+        check_cached::<FractionalUniqueAsset>(FUA_SCHEMA_ID);
+        #[cfg(feature = "collection")]
+        check_cached::<UniqueDigitalCollection>(COLLECTION_SCHEMA_ID);
+        #[cfg(feature = "pfa")]
+        check_cached::<PermissionedFungibleAsset>(PFA_SCHEMA_ID);
+        #[cfg(feature = "ifa")]
+        check_cached::<InflatableFungibleAsset>(IFA_SCHEMA_ID);
+        #[cfg(feature = "ria")]
+        check_cached::<ReissuableAsset>(RIA_SCHEMA_ID);
+        #[cfg(feature = "sba")]
+        check_cached::<SoulboundAsset>(SBA_SCHEMA_ID);
+        #[cfg(feature = "sfa")]
+        check_cached::<SemiFungibleAsset>(SFA_SCHEMA_ID);
+        #[cfg(feature = "stablecoin")]
+        check_cached::<StablecoinAsset>(STABLECOIN_SCHEMA_ID);
+        #[cfg(feature = "bond")]
+        check_cached::<BondAsset>(BOND_SCHEMA_ID);
+        #[cfg(feature = "governance")]
+        check_cached::<GovernanceAsset>(GOVERNANCE_SCHEMA_ID);
+        #[cfg(feature = "ticket")]
+        check_cached::<TicketAsset>(TICKET_SCHEMA_ID);
+        #[cfg(feature = "vesting")]
+        check_cached::<VestingAsset>(VESTING_SCHEMA_ID);
+        #[cfg(feature = "timelock")]
+        check_cached::<TimelockAsset>(TIMELOCK_SCHEMA_ID);
+        #[cfg(feature = "pfan")]
+        check_cached::<MultiKeyPermissionedAsset>(PFAN_SCHEMA_ID);
+        #[cfg(feature = "freezable")]
+        check_cached::<FreezableAsset>(FREEZABLE_SCHEMA_ID);
+        #[cfg(feature = "tifa")]
+        check_cached::<ThresholdInflatableAsset>(TIFA_SCHEMA_ID);
+        #[cfg(feature = "wbtc")]
+        check_cached::<WrappedBtcAsset>(WBTC_SCHEMA_ID);
+        #[cfg(feature = "fee")]
+        check_cached::<TransferFeeAsset>(FEE_SCHEMA_ID);
+        #[cfg(feature = "dividend")]
+        check_cached::<DividendAsset>(DIVIDEND_SCHEMA_ID);
+        #[cfg(feature = "escrow")]
+        check_cached::<EscrowAsset>(ESCROW_SCHEMA_ID);
+        #[cfg(feature = "swap")]
+        check_cached::<SwapOrder>(SWAP_SCHEMA_ID);
+        #[cfg(feature = "carbon")]
+        check_cached::<CarbonCreditAsset>(CARBON_SCHEMA_ID);
+        #[cfg(feature = "provenance")]
+        check_cached::<ProvenanceAsset>(PROVENANCE_SCHEMA_ID);
+        #[cfg(feature = "subscription")]
+        check_cached::<SubscriptionAsset>(SUBSCRIPTION_SCHEMA_ID);
+        #[cfg(feature = "edition")]
+        check_cached::<LimitedEditionAsset>(EDITION_SCHEMA_ID);
+        #[cfg(feature = "eifa")]
+        check_cached::<EpochInflatableAsset>(EIFA_SCHEMA_ID);
+        #[cfg(feature = "claim")]
+        check_cached::<ClaimableAsset>(CLAIM_SCHEMA_ID);
+        #[cfg(feature = "registry")]
+        check_cached::<NameRegistry>(REGISTRY_SCHEMA_ID);
+        #[cfg(feature = "pausable")]
+        check_cached::<PausableAsset>(PAUSABLE_SCHEMA_ID);
+        #[cfg(feature = "rental")]
+        check_cached::<RentalAsset>(RENTAL_SCHEMA_ID);
+        #[cfg(feature = "option")]
+        check_cached::<OptionAsset>(OPTION_SCHEMA_ID);
+        #[cfg(feature = "credential")]
+        check_cached::<VerifiableCredential>(CREDENTIAL_SCHEMA_ID);
+        #[cfg(feature = "burnable")]
+        check_cached::<BurnableAsset>(BURNABLE_SCHEMA_ID);
+        #[cfg(feature = "rebasing")]
+        check_cached::<RebasingAsset>(REBASING_SCHEMA_ID);
+    }
 
-        fn check_chain_net(&self, _: ChainNet) -> Result<(), WitnessResolverError> {
-            unreachable!()
-        }
+    #[test]
+    fn shared_standard_types() {
+        assert!(std::ptr::eq(standard_types(), standard_types()));
     }
 }
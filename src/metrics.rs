@@ -0,0 +1,188 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Size metrics of a contract, for monitoring the growth of contracts and ranking them.
+//!
+//! The contract state keeps every allocation ever revealed to the stock, without tracking which
+//! of them were spent: the spent outputs are found from the transitions of the contract stored
+//! in the stash, with [`spent_outputs`].
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use rgbstd::contract::ContractData;
+use rgbstd::persistence::{
+    ContractStateRead, IndexProvider, StashProvider, StashProviderError, StateProvider, Stock,
+    StockError,
+};
+use rgbstd::vm::{GlobalStateEntry, OpOrd};
+use rgbstd::{ContractId, OpId, Opout};
+use strict_types::encoding::StrictWriter;
+use strict_types::StrictEncode;
+
+/// Counters and sizes of the state of a contract.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ContractMetrics {
+    pub contract_id: ContractId,
+    /// Operations with state known to the contract, including the genesis.
+    pub operations: usize,
+    /// Allocations which are not spent yet.
+    pub live_allocations: usize,
+    /// Allocations spent by some known transition.
+    pub spent_allocations: usize,
+    /// Entries of all the global state types.
+    pub global_entries: usize,
+    /// Size of the strict-serialized global state and allocations, in bytes.
+    pub state_size: usize,
+}
+
+impl ContractMetrics {
+    /// Measures the contract state, counting as spent the allocations whose outputs are in
+    /// `spent`.
+    pub fn capture<S: ContractStateRead>(data: &ContractData<S>, spent: &BTreeSet<Opout>) -> Self {
+        let mut metrics = ContractMetrics {
+            contract_id: data.contract_id(),
+            operations: 0,
+            live_allocations: 0,
+            spent_allocations: 0,
+            global_entries: 0,
+            state_size: 0,
+        };
+        let mut opids = BTreeSet::<OpId>::new();
+        let mut genesis = false;
+        for ty in data.schema.global_types.keys() {
+            for entry in data.state.global(*ty).into_iter().flatten() {
+                let entry: &GlobalStateEntry = entry.borrow();
+                match entry.ord().op_ord {
+                    OpOrd::Genesis => genesis = true,
+                    OpOrd::Transition { opid, .. } => {
+                        opids.insert(opid);
+                    }
+                }
+                metrics.global_entries += 1;
+                metrics.state_size += strict_len(entry.data());
+            }
+        }
+
+        let mut allocation = |opout: Opout, size: usize| {
+            opids.insert(opout.op);
+            if spent.contains(&opout) {
+                metrics.spent_allocations += 1;
+            } else {
+                metrics.live_allocations += 1;
+            }
+            metrics.state_size += size;
+        };
+        for a in data.state.fungible_all() {
+            allocation(a.opout, strict_len(a));
+        }
+        for a in data.state.data_all() {
+            allocation(a.opout, strict_len(a));
+        }
+        for a in data.state.rights_all() {
+            allocation(a.opout, strict_len(a));
+        }
+
+        // The genesis id is the contract id, so it is counted once even if it also has allocations
+        let genesis_id = OpId::from(data.contract_id().to_byte_array());
+        metrics.operations = opids.len() + usize::from(genesis && !opids.contains(&genesis_id));
+        metrics
+    }
+
+    /// Measures the contract with the given id from a stock, see [`ContractMetrics::capture`].
+    pub fn from_stock<S: StashProvider, H: StateProvider, P: IndexProvider>(
+        stock: &Stock<S, H, P>,
+        contract_id: ContractId,
+    ) -> Result<Self, StockError<S, H, P>> {
+        let spent = spent_outputs(stock, contract_id)?;
+        Ok(Self::capture(&stock.contract_data(contract_id)?, &spent))
+    }
+}
+
+/// Outputs of the contract spent by the transitions stored in the stash of the stock.
+pub fn spent_outputs<S: StashProvider, H: StateProvider, P: IndexProvider>(
+    stock: &Stock<S, H, P>,
+    contract_id: ContractId,
+) -> Result<BTreeSet<Opout>, StockError<S, H, P>> {
+    let stash = stock.as_stash_provider();
+    let mut spent = bset! {};
+    for bundle_id in stash.bundle_ids().map_err(StockError::StashRead)? {
+        let bundle = stash.bundle(bundle_id).map_err(|err| match err {
+            StashProviderError::Inconsistency(err) => StockError::from(err),
+            StashProviderError::Connectivity(err) => StockError::StashRead(err),
+        })?;
+        spent.extend(
+            bundle
+                .known_transitions
+                .iter()
+                .filter(|known| known.transition.contract_id == contract_id)
+                .flat_map(|known| known.transition.inputs.iter().copied()),
+        );
+    }
+    Ok(spent)
+}
+
+/// Length of the strict serialization of a value.
+fn strict_len(value: &impl StrictEncode) -> usize {
+    value
+        .strict_encode(StrictWriter::counter::<{ usize::MAX }>())
+        .map(|writer| writer.unbox().unconfine().count)
+        .expect("counting writer doesn't fail")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn issued() {
+        let stock = fixtures::stock();
+        for name in fixtures::FIXTURES {
+            let contract_id = fixtures::contract_id(name);
+            let data = stock.contract_data(contract_id).unwrap();
+            let allocations = data.state.fungible_all().count()
+                + data.state.data_all().count()
+                + data.state.rights_all().count();
+            let metrics = ContractMetrics::from_stock(&stock, contract_id).unwrap();
+            assert_eq!(metrics.contract_id, contract_id, "{name}");
+            assert_eq!(metrics.operations, 1, "{name}");
+            assert_eq!(metrics.live_allocations, allocations, "{name}");
+            assert_eq!(metrics.spent_allocations, 0, "{name}");
+            assert!(metrics.global_entries > 0, "{name}");
+            assert!(metrics.state_size > 0, "{name}");
+            assert!(spent_outputs(&stock, contract_id).unwrap().is_empty(), "{name}");
+        }
+    }
+
+    #[test]
+    fn spent() {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        let opout = data.state.fungible_all().next().unwrap().opout;
+        let live = ContractMetrics::capture(&data, &bset! {});
+        let spent = ContractMetrics::capture(&data, &bset! {opout});
+        assert_eq!(spent.live_allocations, live.live_allocations - 1);
+        assert_eq!(spent.spent_allocations, 1);
+        assert_eq!(spent.state_size, live.state_size);
+        assert_eq!(spent.operations, live.operations);
+    }
+}
@@ -21,28 +21,29 @@
 
 //! Non-Inflatable Assets (NIA) schema.
 
+use std::sync::OnceLock;
+
 use aluvm::isa::opcodes::INSTR_PUTA;
-use aluvm::isa::Instr;
-use aluvm::library::{Lib, LibSite};
-use amplify::confinement::Confined;
 use rgbstd::contract::{
     AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
 };
-use rgbstd::persistence::{ContractStateRead, MemContract};
+use rgbstd::persistence::ContractStateRead;
 use rgbstd::schema::{
     AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
     OwnedStateSchema, Schema, TransitionSchema,
 };
-use rgbstd::stl::{rgb_contract_stl, AssetSpec, ContractTerms, StandardTypes};
+use rgbstd::stl::{AssetSpec, ContractTerms};
 use rgbstd::validation::Scripts;
 use rgbstd::vm::opcodes::INSTR_SVS;
-use rgbstd::vm::RgbIsa;
-use rgbstd::{rgbasm, Amount, SchemaId, TransitionDetails};
+use rgbstd::{Amount, SchemaId, TransitionDetails};
 use strict_types::TypeSystem;
 
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
 use crate::{
-    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS,
-    OS_ASSET, TS_TRANSFER,
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_TERMS, OS_ASSET, TS_TRANSFER,
 };
 
 pub const NIA_SCHEMA_ID: SchemaId = SchemaId::from_array([
@@ -50,48 +51,12 @@ pub const NIA_SCHEMA_ID: SchemaId = SchemaId::from_array([
     0x00, 0x80, 0x34, 0x67, 0x89, 0xad, 0x83, 0xaa, 0x0d, 0xc2, 0x9e, 0x95, 0xa3, 0x15, 0xe3, 0x35,
 ]);
 
-pub(crate) fn nia_lib() -> Lib {
-    let code = rgbasm! {
-        // SUBROUTINE Transfer validation
-        // Set errno
-        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
-        // Checking that the sum of inputs is equal to the sum of outputs.
-        svs     OS_ASSET;
-        test;
-        ret;
-
-        // SUBROUTINE Genesis validation
-        // Checking genesis assignments amount against reported amount of issued assets present in
-        // the global state.
-        put     a8[0],ERRNO_ISSUED_MISMATCH;
-        put     a8[1],0;
-        put     a16[0],0;
-        // Read global state into s16[0]
-        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
-        // Extract 64 bits from the beginning of s16[0] into a64[0]
-        // NB: if the global state is invalid, we will fail here and fail the validation
-        extr    s16[0],a64[0],a16[0];
-        // verify sum of outputs against a64[0] value
-        sas     OS_ASSET;
-        test;
-        ret;
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code).expect("wrong non-inflatable asset script")
-}
-pub(crate) const FN_NIA_GENESIS_OFFSET: u16 = 4 + 3 + 2;
-pub(crate) const FN_NIA_TRANSFER_OFFSET: u16 = 0;
-
-fn nia_standard_types() -> StandardTypes { StandardTypes::with(rgb_contract_stl()) }
-
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
 fn nia_schema() -> Schema {
-    let types = nia_standard_types();
+    let types = standard_types();
 
-    let alu_lib = nia_lib();
-    let alu_id = alu_lib.id();
-    assert_eq!(alu_lib.code.as_ref()[FN_NIA_TRANSFER_OFFSET as usize + 4], INSTR_SVS);
-    assert_eq!(alu_lib.code.as_ref()[FN_NIA_GENESIS_OFFSET as usize], INSTR_PUTA);
-    assert_eq!(alu_lib.code.as_ref()[FN_NIA_GENESIS_OFFSET as usize + 4], INSTR_PUTA);
-    assert_eq!(alu_lib.code.as_ref()[FN_NIA_GENESIS_OFFSET as usize + 8], INSTR_PUTA);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
 
     Schema {
         ffv: zero!(),
@@ -128,7 +93,7 @@ fn nia_schema() -> Schema {
             assignments: tiny_bmap! {
                 OS_ASSET => Occurrences::OnceOrMore,
             },
-            validator: Some(LibSite::with(FN_NIA_GENESIS_OFFSET, alu_id)),
+            validator: Some(NIA_GENESIS.lib_site()),
         },
         transitions: tiny_bmap! {
             TS_TRANSFER => TransitionDetails {
@@ -141,7 +106,7 @@ fn nia_schema() -> Schema {
                     assignments: tiny_bmap! {
                         OS_ASSET => Occurrences::OnceOrMore
                     },
-                    validator: Some(LibSite::with(FN_NIA_TRANSFER_OFFSET, alu_id))
+                    validator: Some(NIA_TRANSFER.lib_site())
                 },
                 name: fname!("transfer"),
             }
@@ -150,65 +115,126 @@ fn nia_schema() -> Schema {
     }
 }
 
+fn nia_scripts() -> Scripts { SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER]) }
+
 #[derive(Default)]
 pub struct NonInflatableAsset;
 
 impl IssuerWrapper for NonInflatableAsset {
     type Wrapper<S: ContractStateRead> = NiaWrapper<S>;
 
-    fn schema() -> Schema { nia_schema() }
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(nia_schema).clone()
+    }
 
-    fn types() -> TypeSystem { nia_standard_types().type_system(nia_schema()) }
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
 
     fn scripts() -> Scripts {
-        let lib = nia_lib();
-        Confined::from_checked(bmap! { lib.id() => lib })
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(nia_scripts).clone()
     }
 }
 
+impl NonInflatableAsset {
+    /// First revision of the NIA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "NonInflatableAsset",
+        schema_id: NIA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fungible asset with a fixed supply issued at genesis and a transfer \
+                        transition conserving amounts.",
+    };
+}
+
+impl IssuerInfo for NonInflatableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for NonInflatableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, From)]
 pub struct NiaWrapper<S: ContractStateRead>(ContractData<S>);
 
 impl<S: ContractStateRead> SchemaWrapper<S> for NiaWrapper<S> {
-    fn with(data: ContractData<S>) -> Self {
-        if data.schema.schema_id() != NIA_SCHEMA_ID {
-            panic!("the provided schema is not NIA");
-        }
-        Self(data)
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for NiaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<NonInflatableAsset, _>(&self.0)?)
     }
 }
 
 impl<S: ContractStateRead> NiaWrapper<S> {
-    pub fn spec(&self) -> AssetSpec {
-        let strict_val = &self
-            .0
-            .global("spec")
-            .next()
-            .expect("NIA requires global state `spec` to have at least one item");
-        AssetSpec::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, failing if it doesn't belong to the NIA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<NonInflatableAsset, _>(&data)?;
+        Ok(Self(data))
     }
 
-    pub fn contract_terms(&self) -> ContractTerms {
-        let strict_val = &self
-            .0
-            .global("terms")
-            .next()
-            .expect("NIA requires global state `terms` to have at least one item");
-        ContractTerms::from_strict_val_unchecked(strict_val)
-    }
+    /// Wraps the contract data, panicking if it doesn't belong to the NIA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
 
-    pub fn total_issued_supply(&self) -> Amount {
-        self.0
-            .global("issuedSupply")
-            .map(|amount| Amount::from_strict_val_unchecked(&amount))
-            .sum()
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
     }
 
     pub fn allocations<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = FungibleAllocation> + 'c {
-        self.0.fungible_raw(OS_ASSET, filter).unwrap()
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for NiaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { NiaWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        NiaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        NiaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        NiaWrapper::try_allocations(self, filter)
     }
 }
 
@@ -222,8 +248,10 @@ mod test {
     use rgbstd::stl::*;
     use rgbstd::txout::BlindSeal;
     use rgbstd::*;
+    use schemata_tools::testing;
 
     use super::*;
+    use crate::fixtures::{self, fixture_seal};
 
     #[test]
     fn schema_id() {
@@ -246,12 +274,11 @@ mod test {
             precision: Precision::try_from(2).unwrap(),
         };
         let issued_supply = 999u64;
-        let seal: BlindSeal<Txid> = GenesisSeal::from(BlindSeal::with_blinding(
+        let seal: BlindSeal<Txid> = testing::BLINDER.genesis_seal(
             Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
                 .unwrap(),
             0,
-            654321,
-        ));
+        );
 
         let builder = ContractBuilder::with(
             Identity::default(),
@@ -276,4 +303,21 @@ mod test {
             s!("rgb:663wqep~-0pVYnjS-ieA0N3r-58wUTIY-zgCGO_1-QQkuMMs")
         );
     }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<NonInflatableAsset>(fixtures::contract_id("nia"))
+            .unwrap();
+        assert_eq!(wrapper.version(), NonInflatableAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+    }
 }
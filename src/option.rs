@@ -0,0 +1,641 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Option schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the NIA schema for call options, or warrants, on another RGB asset. Each unit of
+//! the option entitles its holder to buy a unit of the underlying asset, in the smallest units of
+//! both, for the strike price in satoshis, up to an expiry block height. The genesis commits to
+//! the contract id of the underlying asset, to the strike and to the expiry.
+//!
+//! Holders exercise their options with an `exercise` transition, which destroys the options it
+//! doesn't assign back as change and records the exercised supply; the delivery of the underlying
+//! asset against the strike payment happens in the contract of the underlying asset, which the
+//! option contract doesn't see. Options left after the expiry are destroyed by a `lapse`
+//! transition, recording the lapsed supply. The validation scripts require the recorded supply to
+//! match the options destroyed.
+//!
+//! Both transitions commit to the lock time of their witness transaction: an exercise must commit
+//! to a lock time lower than the expiry and a lapse to one of at least the expiry. The lock time of
+//! the witness itself is not checked, see [`crate::height`]: exercises mined after the expiry and
+//! lapses mined up to it are still valid, and the `audit` module of `rgb-schemata-tools` reports
+//! them.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, Details};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, ContractId, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::height::height;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER, OPTION_EXERCISE, OPTION_LAPSE};
+use crate::wrapper::{check_family, contract_ref, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_EXERCISED_SUPPLY,
+    GS_EXPIRY, GS_ISSUED_SUPPLY, GS_LAPSED_SUPPLY, GS_LOCK_TIME, GS_NOMINAL, GS_STRIKE, GS_TERMS,
+    GS_UNDERLYING_ASSET, OS_ASSET, TS_EXERCISE, TS_LAPSE, TS_TRANSFER,
+};
+
+pub const OPTION_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x10, 0xd6, 0xf8, 0xec, 0x07, 0x42, 0x43, 0x42, 0x94, 0x17, 0x5f, 0xd4, 0xb7, 0xe9, 0x31, 0x33,
+    0x9d, 0xf9, 0x35, 0x9c, 0xd5, 0xf3, 0x58, 0x8a, 0x36, 0x39, 0x6f, 0xf8, 0xf2, 0xd2, 0x09, 0xad,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn option_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    OPTION_EXERCISE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    OPTION_LAPSE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("OptionAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_UNDERLYING_ASSET => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Details")),
+                name: fname!("underlyingAsset"),
+            },
+            GS_STRIKE => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("strike"),
+            },
+            GS_EXPIRY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("expiry"),
+            },
+            GS_EXERCISED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("exercisedSupply"),
+            },
+            GS_LAPSED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("lapsedSupply"),
+            },
+            GS_LOCK_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("lockTime"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_UNDERLYING_ASSET => Occurrences::Once,
+                GS_STRIKE => Occurrences::Once,
+                GS_EXPIRY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_EXERCISE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_EXERCISED_SUPPLY => Occurrences::Once,
+                        GS_LOCK_TIME => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore
+                    },
+                    validator: Some(OPTION_EXERCISE.lib_site())
+                },
+                name: fname!("exercise"),
+            },
+            TS_LAPSE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_LAPSED_SUPPLY => Occurrences::Once,
+                        GS_LOCK_TIME => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore
+                    },
+                    validator: Some(OPTION_LAPSE.lib_site())
+                },
+                name: fname!("lapse"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn option_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, OPTION_EXERCISE, OPTION_LAPSE])
+}
+
+#[derive(Default)]
+pub struct OptionAsset;
+
+impl IssuerWrapper for OptionAsset {
+    type Wrapper<S: ContractStateRead> = OptionWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(option_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(option_scripts).clone()
+    }
+}
+
+impl OptionAsset {
+    /// First revision of the option schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "OptionAsset",
+        schema_id: OPTION_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "NIA variant for call options on another asset, exercised by their holders \
+                        up to an expiry height and lapsed afterwards.",
+    };
+
+    /// Global state value referencing the contract `id`, as stored by the `underlyingAsset`
+    /// global state.
+    pub fn asset_ref(id: ContractId) -> Details {
+        Details::from_str(&id.to_string()).expect("contract ids are valid details")
+    }
+}
+
+impl IssuerInfo for OptionAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for OptionAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+/// Economic terms of an option, as committed by its genesis.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct OptionTerms {
+    /// Contract id of the asset which the holders can buy.
+    pub underlying: ContractId,
+    /// Price of a unit of the underlying asset, in satoshis.
+    pub strike: Amount,
+    /// Height of the last block in which the options can be exercised.
+    pub expiry: u32,
+}
+
+impl OptionTerms {
+    /// Returns the price of exercising `amount` options, in satoshis, or `None` if it overflows.
+    pub fn exercise_price(&self, amount: Amount) -> Option<u64> {
+        self.strike.value().checked_mul(amount.value())
+    }
+
+    /// Checks whether the options can be exercised in a block at the given height.
+    pub fn is_exercisable(&self, height: u32) -> bool { height <= self.expiry }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct OptionWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for OptionWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for OptionWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<OptionAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> OptionWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the option schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<OptionAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the option schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the underlying asset, strike and expiry of the options.
+    pub fn option_terms(&self) -> OptionTerms { or_panic(self.try_option_terms()) }
+
+    pub fn try_option_terms(&self) -> Result<OptionTerms, Error> {
+        Ok(OptionTerms {
+            underlying: contract_ref(&self.0, GS_UNDERLYING_ASSET)?,
+            strike: global(&self.0, GS_STRIKE)?,
+            expiry: height(&self.0, GS_EXPIRY)?,
+        })
+    }
+
+    /// Returns the options exercised over the contract history.
+    pub fn exercised_amount(&self) -> Amount { or_panic(self.try_exercised_amount()) }
+
+    pub fn try_exercised_amount(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_EXERCISED_SUPPLY)?.sum()
+    }
+
+    /// Returns the options lapsed over the contract history.
+    pub fn lapsed_amount(&self) -> Amount { or_panic(self.try_lapsed_amount()) }
+
+    pub fn try_lapsed_amount(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_LAPSED_SUPPLY)?.sum()
+    }
+
+    /// Returns the options neither exercised nor lapsed yet.
+    pub fn outstanding_supply(&self) -> Amount { or_panic(self.try_outstanding_supply()) }
+
+    pub fn try_outstanding_supply(&self) -> Result<Amount, Error> {
+        Ok(self
+            .try_total_issued_supply()?
+            .saturating_sub(self.try_exercised_amount()?)
+            .saturating_sub(self.try_lapsed_amount()?))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for OptionWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        OptionWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        OptionWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        OptionWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        OptionWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(all_schemas)]
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    #[cfg(all_schemas)]
+    use rgbstd::persistence::Stock;
+    #[cfg(all_schemas)]
+    use rgbstd::validation::ValidationError;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+    #[cfg(all_schemas)]
+    use crate::height::block_height;
+    #[cfg(all_schemas)]
+    use crate::ValidationErrno;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = option_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(OPTION_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<OptionAsset>(fixtures::contract_id("option"))
+            .unwrap();
+        assert_eq!(wrapper.version(), OptionAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        let terms = wrapper.option_terms();
+        assert_eq!(terms, OptionTerms {
+            underlying: fixtures::underlying_asset(),
+            strike: Amount::from(fixtures::STRIKE),
+            expiry: fixtures::OPTION_EXPIRY,
+        });
+        assert!(terms.is_exercisable(fixtures::OPTION_EXPIRY));
+        assert!(!terms.is_exercisable(fixtures::OPTION_EXPIRY + 1));
+        assert_eq!(terms.exercise_price(Amount::from(10u64)), Some(10 * fixtures::STRIKE));
+        assert_eq!(terms.exercise_price(Amount::from(u64::MAX)), None);
+        assert_eq!(wrapper.exercised_amount(), Amount::ZERO);
+        assert_eq!(wrapper.lapsed_amount(), Amount::ZERO);
+        assert_eq!(wrapper.outstanding_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+    }
+
+    // Exercising and lapsing options.
+    //
+    // The holder exercises part of their options, keeping the rest as change, and the options left
+    // lapse in a later witness TX, each committing to a lock time on its side of the expiry. The
+    // scripts don't check the lock time of the witnesses, so both transitions are accepted at any
+    // height, and it is the audit which must report an exercise mined after the expiry height and
+    // a lapse mined up to it. Transitions committing to a lock time on the wrong side of the
+    // expiry must be rejected.
+
+    /// Options to buy units of the underlying asset for `strike` sats each up to the `expiry`
+    /// height, with `allocated` options at `seal(1)`.
+    #[cfg(all_schemas)]
+    fn option(issued: u64, allocated: u64, strike: u64, expiry: u32) -> ContractBuilder {
+        testing::asset::<OptionAsset>(issued, allocated)
+            .add_global_state(
+                "underlyingAsset",
+                OptionAsset::asset_ref(ContractId::from([0x33; 32])),
+            )
+            .unwrap()
+            .add_global_state("strike", Amount::from(strike))
+            .unwrap()
+            .add_global_state("expiry", block_height(expiry))
+            .unwrap()
+    }
+
+    #[cfg(all_schemas)]
+    const STRIKE: u64 = 3000;
+    #[cfg(all_schemas)]
+    const EXPIRY: u32 = 150;
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn exercise_and_lapse() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(option(1000, 1000, STRIKE, EXPIRY));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock.contract_wrapper::<OptionAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.option_terms(), OptionTerms {
+            underlying: ContractId::from([0x33; 32]),
+            strike: Amount::from(STRIKE),
+            expiry: EXPIRY,
+        });
+        assert_eq!(wrapper.option_terms().exercise_price(Amount::from(300u64)), Some(300 * STRIKE));
+
+        let transition = stock
+            .transition_builder(contract_id, "exercise")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_global_state("exercisedSupply", Amount::from(300u64))
+            .unwrap()
+            .add_global_state("lockTime", block_height(EXPIRY - 1))
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 700u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let exercise_id = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 1)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let exercise_witness = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(EXPIRY + 1));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock.contract_wrapper::<OptionAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.exercised_amount(), Amount::from(300u64));
+        assert_eq!(wrapper.outstanding_supply(), Amount::from(700u64));
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(Outpoint::new(exercise_witness, 1))), "{seals:?}");
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::LateExercise {
+            op: exercise_id,
+            height: EXPIRY + 1,
+            expiry: EXPIRY,
+        }]);
+        resolver.set_witness_ord(exercise_witness, testing::mined_at(EXPIRY));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.burned, 300);
+
+        let transition = stock
+            .transition_builder(contract_id, "lapse")
+            .unwrap()
+            .add_input(
+                Opout::new(exercise_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(700u64)),
+            )
+            .unwrap()
+            .add_global_state("lapsedSupply", Amount::from(700u64))
+            .unwrap()
+            .add_global_state("lockTime", block_height(EXPIRY))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let lapse_id = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(exercise_witness, 1)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let lapse_witness = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(EXPIRY));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock.contract_wrapper::<OptionAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.lapsed_amount(), Amount::from(700u64));
+        assert_eq!(wrapper.outstanding_supply(), Amount::ZERO);
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::PrematureLapse {
+            op: lapse_id,
+            height: EXPIRY,
+            expiry: EXPIRY,
+        }]);
+        resolver.set_witness_ord(lapse_witness, testing::mined_at(EXPIRY + 1));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.burned, 1000);
+        assert_eq!(report.allocated, 0);
+    }
+
+    /// Destroys all the options allocated by the genesis with a transition of type `name`,
+    /// recording the destroyed supply in `global` and committing to `lock_time`, and validates
+    /// its consumed history.
+    #[cfg(all_schemas)]
+    fn destroy_all(
+        name: &'static str,
+        global: &'static str,
+        lock_time: u32,
+    ) -> Result<(), ValidationError> {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(option(1000, 1000, STRIKE, EXPIRY));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = stock
+            .transition_builder(contract_id, name)
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_global_state(global, Amount::from(1000u64))
+            .unwrap()
+            .add_global_state("lockTime", block_height(lock_time))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            EXPIRY,
+        );
+        testing::validate_history::<OptionAsset>(&stock, &resolver, contract_id, &[opid])
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn late_exercise_rejected() {
+        destroy_all("exercise", "exercisedSupply", EXPIRY - 1).unwrap();
+        // a lock time of `EXPIRY` keeps the witness from being mined before the options expire
+        let err = destroy_all("exercise", "exercisedSupply", EXPIRY).unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::OptionExpired));
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn early_lapse_rejected() {
+        destroy_all("lapse", "lapsedSupply", EXPIRY).unwrap();
+        // a lock time of `EXPIRY - 1` lets the witness be mined while the options can be exercised
+        let err = destroy_all("lapse", "lapsedSupply", EXPIRY - 1).unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::OptionNotExpired));
+    }
+}
@@ -0,0 +1,481 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pausable asset schema.
+//!
+//! A variant of the NIA schema for regulated assets, whose issuer keeps a pause right allowing it
+//! to pause and resume all the transfers of the asset, e.g. while responding to an incident. Each
+//! `togglePause` transition spends the pause right and records the negation of the current
+//! `paused` flag, 1 pausing the transfers and 0 resuming them; a contract is not paused until its
+//! first toggle. The flag is recorded as an amount, since the standard types have no boolean type.
+//!
+//! Unlike the transfers of frozen allocations, the transfers made while the contract is paused
+//! are rejected by the validation scripts, which see the toggles known to the validating party.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::{Amount, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, PAUSABLE_TOGGLE, PAUSABLE_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_PAUSED, GS_TERMS, OS_ASSET, OS_PAUSE, TS_TOGGLE_PAUSE, TS_TRANSFER,
+};
+
+pub const PAUSABLE_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x28, 0x3e, 0x3f, 0x50, 0xbf, 0x11, 0xcc, 0x94, 0x84, 0x71, 0x33, 0x0f, 0x46, 0x1e, 0x6b, 0x6b,
+    0x42, 0x20, 0xe4, 0x1d, 0xf1, 0x96, 0x45, 0xf8, 0xc2, 0x68, 0x8f, 0x5e, 0x3e, 0x16, 0xc0, 0xa9,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn pausable_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    PAUSABLE_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    PAUSABLE_TOGGLE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("PausableAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_PAUSED => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("paused"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_PAUSE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("pauseRight"),
+                default_transition: TS_TOGGLE_PAUSE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_PAUSE => Occurrences::Once,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(PAUSABLE_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_TOGGLE_PAUSE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_PAUSED => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_PAUSE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_PAUSE => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(PAUSABLE_TOGGLE.lib_site())
+                },
+                name: fname!("togglePause"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn pausable_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, PAUSABLE_TRANSFER, PAUSABLE_TOGGLE])
+}
+
+#[derive(Default)]
+pub struct PausableAsset;
+
+impl IssuerWrapper for PausableAsset {
+    type Wrapper<S: ContractStateRead> = PausableWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(pausable_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(pausable_scripts).clone()
+    }
+}
+
+impl PausableAsset {
+    /// First revision of the pausable asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "PausableAsset",
+        schema_id: PAUSABLE_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "NIA variant whose issuer can pause and resume all the transfers of the \
+                        asset.",
+    };
+}
+
+impl IssuerInfo for PausableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for PausableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct PausableWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for PausableWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for PausableWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<PausableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> PausableWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the pausable asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<PausableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the pausable asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Checks whether the transfers of the asset are paused, according to the latest toggle.
+    pub fn is_paused(&self) -> bool { or_panic(self.try_is_paused()) }
+
+    pub fn try_is_paused(&self) -> Result<bool, Error> {
+        // The global state lists the most recent toggles first
+        match globals::<_, Amount>(&self.0, GS_PAUSED)?.next() {
+            None => Ok(false),
+            Some(flag) => match flag?.value() {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(Error::InvalidGlobal(GS_PAUSED)),
+            },
+        }
+    }
+
+    /// Returns the number of times the issuer paused or resumed the transfers.
+    pub fn toggles(&self) -> usize { or_panic(self.try_toggles()) }
+
+    pub fn try_toggles(&self) -> Result<usize, Error> {
+        Ok(globals::<_, Amount>(&self.0, GS_PAUSED)?.count())
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn pause_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_pause_rights(filter))
+    }
+
+    pub fn try_pause_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_PAUSE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for PausableWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        PausableWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        PausableWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        PausableWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        PausableWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = pausable_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(PAUSABLE_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<PausableAsset>(fixtures::contract_id("pausable"))
+            .unwrap();
+        assert_eq!(wrapper.version(), PausableAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert!(!wrapper.is_paused());
+        assert_eq!(wrapper.toggles(), 0);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .pause_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Pausing and resuming the transfers of a pausable asset.
+    //
+    // The issuer spends the pause right in a mined witness TX to pause the contract, then spends it
+    // again in a later one to resume it. The wrapper must report the contract as paused only
+    // between the two toggles, and follow the pause right along. A transfer while the contract
+    // is paused must be rejected by the validation of the history.
+
+    /// Pausable asset allocated to `seal(1)`, with the pause right at `seal(2)`.
+    fn pausable(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::asset::<PausableAsset>(issued, allocated)
+            .add_rights("pauseRight", testing::seal(2))
+            .unwrap()
+    }
+
+    fn toggle(stock: &Stock, contract_id: ContractId, right: Opout, paused: u64) -> Transition {
+        stock
+            .transition_builder(contract_id, "togglePause")
+            .unwrap()
+            .add_input(right, AllocatedState::Void)
+            .unwrap()
+            .add_global_state("paused", Amount::from(paused))
+            .unwrap()
+            .add_rights("pauseRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap()
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(pausable(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<PausableAsset>(contract_id)
+            .unwrap();
+        assert!(!wrapper.is_paused());
+        assert_eq!(wrapper.toggles(), 0);
+
+        let mut toggles = vec![];
+        let mut right = (Opout::new(genesis_id, OS_PAUSE, 0), Outpoint::new(testing::txid(), 2));
+        for (height, paused) in [(100, 1), (101, 0)] {
+            let transition = toggle(&stock, contract_id, right.0, paused);
+            let opid = transition.id();
+            toggles.push(opid);
+            let fascia = testing::anchor(contract_id, transition, &[right.1], 1);
+            let witness = fascia.seal_witness.public.tx().unwrap().clone();
+            let witness_id = fascia.witness_id();
+            resolver.add_witness(witness, testing::mined_at(height));
+            stock.consume_fascia(fascia, resolver.clone()).unwrap();
+            right = (Opout::new(opid, OS_PAUSE, 0), Outpoint::new(witness_id, 1));
+
+            let wrapper = stock
+                .contract_wrapper::<PausableAsset>(contract_id)
+                .unwrap();
+            assert_eq!(wrapper.is_paused(), paused == 1);
+            assert_eq!(wrapper.toggles(), height as usize - 99);
+            let rights = wrapper
+                .pause_rights(&FilterIncludeAll)
+                .map(|a| a.seal)
+                .collect::<Vec<_>>();
+            assert!(rights.contains(&OutputSeal::new(right.1)), "{rights:?}");
+        }
+
+        testing::validate_history::<PausableAsset>(&stock, &resolver, contract_id, &toggles)
+            .unwrap();
+    }
+
+    #[test]
+    fn transfer_while_paused_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(pausable(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = toggle(&stock, contract_id, Opout::new(genesis_id, OS_PAUSE, 0), 1);
+        let pause_id = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 2)],
+            100,
+        );
+
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 1000u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let transfer_id = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            101,
+        );
+
+        let err = testing::validate_history::<PausableAsset>(&stock, &resolver, contract_id, &[
+            pause_id,
+            transfer_id,
+        ])
+        .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::Paused));
+    }
+}
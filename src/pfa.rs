@@ -22,78 +22,55 @@
 //! Permissioned Fungible Assets (PFA) schema.
 //! (!) Not safe to use in a production environment!
 
-use aluvm::isa::Instr;
-use aluvm::library::{Lib, LibSite};
-use amplify::confinement::Confined;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::bitcoin::CompressedPublicKey;
 use rgbstd::contract::{
-    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
 };
-use rgbstd::persistence::{ContractStateRead, MemContract};
+use rgbstd::persistence::ContractStateRead;
 use rgbstd::schema::{
     AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
     OwnedStateSchema, Schema, TransitionSchema,
 };
-use rgbstd::stl::{rgb_contract_stl, AssetSpec, ContractTerms, StandardTypes};
+use rgbstd::stl::{AssetSpec, ContractTerms};
 use rgbstd::validation::Scripts;
-use rgbstd::vm::RgbIsa;
-use rgbstd::{rgbasm, Amount, SchemaId, TransitionDetails};
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, ContractId, SchemaId, TransitionDetails};
 use strict_types::TypeSystem;
 
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::reject::RejectList;
+use crate::scripts::{SharedLibs, PFA_GENESIS, PFA_TRANSITION};
+use crate::wrapper::{check_family, global, globals, or_panic};
 use crate::{
-    ERRNO_INVALID_SIGNATURE, ERRNO_ISSUED_MISMATCH, ERRNO_MISSING_PUBKEY, ERRNO_NON_EQUAL_IN_OUT,
-    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PUBKEY, GS_TERMS, OS_ASSET, TS_TRANSFER,
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_PUBKEY, GS_TERMS, OS_ASSET, OS_ROTATE_KEY, TS_ROTATE_KEY, TS_TRANSFER,
 };
 
+/// Id of the latest revision of the schema, used for new issuances.
+///
+/// Before the schema gained new revisions it was the id of the first revision, which is now
+/// [`PFA_V1_SCHEMA_ID`].
 pub const PFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x52, 0xaa, 0x1c, 0x1c, 0xcd, 0x40, 0x42, 0x56, 0xfe, 0x18, 0x65, 0x19, 0xce, 0x4d, 0xc0, 0x57,
+    0xbb, 0x47, 0x85, 0x73, 0x2e, 0x25, 0x10, 0xa4, 0x60, 0xf8, 0xcd, 0xc1, 0x88, 0x30, 0x69, 0x63,
+]);
+
+/// Id of the first revision of the schema, whose issuer key can't be rotated.
+pub const PFA_V1_SCHEMA_ID: SchemaId = SchemaId::from_array([
     0x62, 0xfb, 0xef, 0x43, 0x85, 0x2c, 0x1e, 0xe3, 0xd0, 0x0d, 0x3d, 0xe7, 0x21, 0x0f, 0x66, 0x9e,
     0x9b, 0x2a, 0x31, 0xba, 0xec, 0xe6, 0x56, 0x19, 0x45, 0xbc, 0xb2, 0x98, 0x75, 0x6b, 0x91, 0x8f,
 ]);
 
-pub(crate) fn pfa_lib_transition() -> Lib {
-    let code = rgbasm! {
-        // Checking that the sum of inputs is equal to the sum of outputs
-        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
-        svs     OS_ASSET;  // verify sum
-        test;  // check it didn't fail
-
-        // Check transition signature
-        put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
-        put     a32[0],0;  // set a32[0] to 0
-        ldc     GS_PUBKEY,a32[0],s16[0];  // get global pubkey
-        put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
-        vts     s16[0];  // verify signature
-        test;  // check it didn't fail
-        ret;  // return execution flow
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code).expect("wrong non-inflatable asset script")
-}
-
-pub(crate) fn pfa_lib_genesis() -> Lib {
-    let code = rgbasm! {
-        // Check genesis assignments amount against reported amount of issued assets present in the
-        // global state
-        put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
-        put     a8[1],0;  // set a8[1] to 0
-        put     a16[0],0;  // set a16[0] to 0
-        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // get global issued supply
-        extr    s16[0],a64[0],a16[0];  // extract 64 bits from the beginning of s16[0] into a64[0]
-        sas     OS_ASSET;  // verify sum of outputs against a64[0] value
-        test;  // check it didn't fail
-        ret;  // return execution flow
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code).expect("wrong non-inflatable asset script")
-}
-
-fn pfa_standard_types() -> StandardTypes { StandardTypes::with(rgb_contract_stl()) }
-
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
 fn pfa_schema() -> Schema {
-    let types = pfa_standard_types();
+    let types = standard_types();
 
-    let alu_lib_genesis = pfa_lib_genesis();
-    let alu_id_genesis = alu_lib_genesis.id();
-
-    let alu_lib_transition = pfa_lib_transition();
-    let alu_id_transition = alu_lib_transition.id();
+    PFA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    PFA_TRANSITION.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
 
     Schema {
         ffv: zero!(),
@@ -113,7 +90,7 @@ fn pfa_schema() -> Schema {
                 name: fname!("issuedSupply"),
             },
             GS_PUBKEY => GlobalDetails {
-                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.CompressedPk")),
                 name: fname!("pubkey"),
             },
         },
@@ -122,6 +99,11 @@ fn pfa_schema() -> Schema {
                 owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
                 name: fname!("assetOwner"),
                 default_transition: TS_TRANSFER,
+            },
+            OS_ROTATE_KEY => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("rotateKeyRight"),
+                default_transition: TS_ROTATE_KEY,
             }
         },
         genesis: GenesisSchema {
@@ -134,8 +116,9 @@ fn pfa_schema() -> Schema {
             },
             assignments: tiny_bmap! {
                 OS_ASSET => Occurrences::OnceOrMore,
+                OS_ROTATE_KEY => Occurrences::NoneOrOnce,
             },
-            validator: Some(LibSite::with(0, alu_id_genesis)),
+            validator: Some(PFA_GENESIS.lib_site()),
         },
         transitions: tiny_bmap! {
             TS_TRANSFER => TransitionDetails {
@@ -148,88 +131,257 @@ fn pfa_schema() -> Schema {
                     assignments: tiny_bmap! {
                         OS_ASSET => Occurrences::OnceOrMore
                     },
-                    validator: Some(LibSite::with(0, alu_id_transition))
+                    validator: Some(PFA_TRANSITION.lib_site())
                 },
                 name: fname!("transfer"),
+            },
+            TS_ROTATE_KEY => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_PUBKEY => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ROTATE_KEY => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ROTATE_KEY => Occurrences::NoneOrOnce
+                    },
+                    // The transfer script checks the signature against the key preceding the
+                    // rotation, so only the current authority can publish a new one; with no asset
+                    // spent or assigned the sum check trivially holds
+                    validator: Some(PFA_TRANSITION.lib_site())
+                },
+                name: fname!("rotateKey"),
             }
         },
         default_assignment: Some(OS_ASSET),
     }
 }
 
+fn pfa_scripts() -> Scripts { SharedLibs::get().scripts(&[PFA_GENESIS, PFA_TRANSITION]) }
+
 #[derive(Default)]
 pub struct PermissionedFungibleAsset;
 
 impl IssuerWrapper for PermissionedFungibleAsset {
     type Wrapper<S: ContractStateRead> = PfaWrapper<S>;
 
-    fn schema() -> Schema { pfa_schema() }
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(pfa_schema).clone()
+    }
 
-    fn types() -> TypeSystem { pfa_standard_types().type_system(pfa_schema()) }
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
 
     fn scripts() -> Scripts {
-        let alu_lib_genesis = pfa_lib_genesis();
-        let alu_id_genesis = alu_lib_genesis.id();
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(pfa_scripts).clone()
+    }
+}
 
-        let alu_lib_transition = pfa_lib_transition();
-        let alu_id_transition = alu_lib_transition.id();
+impl PermissionedFungibleAsset {
+    /// First revision of the PFA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "PermissionedFungibleAsset",
+        schema_id: PFA_V1_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fungible asset with a fixed supply whose transfers must be signed by the \
+                        issuer public key committed in the global state.",
+    };
 
-        Confined::from_checked(bmap! {
-            alu_id_genesis => alu_lib_genesis,
-            alu_id_transition => alu_lib_transition,
-        })
-    }
+    /// Revision adding key rotation to the PFA schema.
+    pub const V2: SchemaInfo = SchemaInfo {
+        name: "PermissionedFungibleAsset",
+        schema_id: PFA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(2, 0, 0),
+        release_notes: "Key rotation transition, allowing the holder of the rotate key right to \
+                        replace the issuer public key with a new one published in the global \
+                        state and signed by the old key.",
+    };
+}
+
+impl IssuerInfo for PermissionedFungibleAsset {
+    const INFO: SchemaInfo = Self::V2;
+}
+
+impl SchemaFamily for PermissionedFungibleAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1, Self::V2];
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, From)]
 pub struct PfaWrapper<S: ContractStateRead>(ContractData<S>);
 
 impl<S: ContractStateRead> SchemaWrapper<S> for PfaWrapper<S> {
-    fn with(data: ContractData<S>) -> Self {
-        if data.schema.schema_id() != PFA_SCHEMA_ID {
-            panic!("the provided schema is not PFA");
-        }
-        Self(data)
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for PfaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<PermissionedFungibleAsset, _>(&self.0)?)
     }
 }
 
 impl<S: ContractStateRead> PfaWrapper<S> {
-    pub fn spec(&self) -> AssetSpec {
-        let strict_val = &self
-            .0
-            .global("spec")
-            .next()
-            .expect("PFA requires global state `spec` to have at least one item");
-        AssetSpec::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, failing if it doesn't belong to the PFA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<PermissionedFungibleAsset, _>(&data)?;
+        Ok(Self(data))
     }
 
-    pub fn contract_terms(&self) -> ContractTerms {
-        let strict_val = &self
-            .0
-            .global("terms")
-            .next()
-            .expect("PFA requires global state `terms` to have at least one item");
-        ContractTerms::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, panicking if it doesn't belong to the PFA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
     }
 
-    pub fn total_issued_supply(&self) -> Amount {
-        self.0
-            .global("issuedSupply")
-            .map(|amount| Amount::from_strict_val_unchecked(&amount))
-            .sum()
+    pub fn contract_id(&self) -> ContractId { self.0.contract_id() }
+
+    /// Returns the issuer public key which must sign every transfer, that is the one published by
+    /// the latest key rotation, if any.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    /// Returns all the public keys the issuer has used, from the one committed in the genesis to
+    /// the current one.
+    pub fn pubkey_history(&self) -> Vec<CompressedPublicKey> { or_panic(self.try_pubkey_history()) }
+
+    pub fn try_pubkey_history(&self) -> Result<Vec<CompressedPublicKey>, Error> {
+        // Global state is iterated from the latest entry in consensus order
+        let mut pubkeys = globals(&self.0, GS_PUBKEY)?.collect::<Result<Vec<_>, _>>()?;
+        pubkeys.reverse();
+        Ok(pubkeys)
+    }
+
+    /// Returns the rights to rotate the issuer key passing the filter.
+    pub fn rotate_key_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_rotate_key_rights(filter))
+    }
+
+    pub fn try_rotate_key_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_ROTATE_KEY, filter)?)
     }
 
     pub fn allocations<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = FungibleAllocation> + 'c {
-        self.0.fungible_raw(OS_ASSET, filter).unwrap()
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the allocations passing the filter which are not on the reject list.
+    pub fn accepted_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_accepted_allocations(filter, reject_list))
+    }
+
+    pub fn try_accepted_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self
+            .try_allocations(filter)?
+            .filter(|a| !reject_list.is_rejected(a.opout)))
+    }
+
+    /// Returns the allocations passing the filter which are on the reject list.
+    pub fn rejected_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_rejected_allocations(filter, reject_list))
+    }
+
+    pub fn try_rejected_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self
+            .try_allocations(filter)?
+            .filter(|a| reject_list.is_rejected(a.opout)))
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for PfaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { PfaWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        PfaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        PfaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        PfaWrapper::try_allocations(self, filter)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
+    use rgbstd::bitcoin::CompressedPublicKey;
+    use rgbstd::containers::{BuilderSeal, ConsignmentExt};
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::*;
+    use rgbstd::txout::BlindSeal;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
     use super::*;
+    use crate::fixtures::fixture_seal;
+    use crate::{fixtures, ValidationErrno};
 
     #[test]
     fn schema_id() {
@@ -237,4 +389,218 @@ mod test {
         eprintln!("{:#04x?}", schema_id.to_byte_array());
         assert_eq!(PFA_SCHEMA_ID, schema_id);
     }
+
+    #[test]
+    fn deterministic_contract_id() {
+        let created_at = 1713261744;
+        let terms = ContractTerms {
+            text: RicardianContract::default(),
+            media: None,
+        };
+        let spec = AssetSpec {
+            ticker: Ticker::from("TICKER"),
+            name: Name::from("NAME"),
+            details: None,
+            precision: Precision::try_from(2).unwrap(),
+        };
+        let issued_supply = 999u64;
+        let pubkey = CompressedPublicKey::from_slice(&[
+            2, 199, 163, 211, 116, 75, 108, 119, 241, 66, 54, 236, 233, 189, 142, 108, 37, 135, 56,
+            128, 200, 176, 199, 9, 117, 132, 72, 200, 167, 185, 4, 64, 53,
+        ])
+        .unwrap();
+        let seal: BlindSeal<Txid> = testing::BLINDER.genesis_seal(
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap(),
+            0,
+        );
+
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            PermissionedFungibleAsset::schema(),
+            PermissionedFungibleAsset::types(),
+            PermissionedFungibleAsset::scripts(),
+            ChainNet::BitcoinTestnet4,
+        )
+        .add_global_state("spec", spec)
+        .unwrap()
+        .add_global_state("terms", terms)
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_fungible_state("assetOwner", BuilderSeal::from(seal), issued_supply)
+        .unwrap();
+
+        let contract = builder.issue_contract_raw(created_at).unwrap();
+
+        assert_eq!(
+            contract.contract_id().to_string(),
+            s!("rgb:qICTKc5R-kEM2BwG-06idXfI-DeNGE8l-IMN15k3-bn72SGE")
+        );
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<PermissionedFungibleAsset>(fixtures::contract_id("pfa"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        assert_eq!(wrapper.version(), PermissionedFungibleAsset::V2);
+        assert_eq!(wrapper.pubkey_history(), vec![fixtures::pubkey()]);
+    }
+
+    // Rotation of the issuer key of a permissioned fungible asset.
+    //
+    // The holder of the rotation right publishes the co-signer key with a key rotation signed by
+    // the issuer key; the next transfer must then be signed by the new key, and the
+    // `PfaWrapper` must report both keys in the order they were used. A transfer still signed
+    // by the rotated issuer key must be rejected by the validation of the history.
+    //
+    // `PfaWrapper`: schemata::PfaWrapper
+
+    /// PFA asset allocated to `seal(1)`, whose issuer key can be rotated by the holder of
+    /// `seal(2)`.
+    fn pfa(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+        testing::asset::<PermissionedFungibleAsset>(issued, allocated)
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+            .add_rights("rotateKeyRight", testing::seal(2))
+            .unwrap()
+    }
+
+    /// Publishes the co-signer key with a key rotation signed by the issuer key, mined at height
+    /// 100, returning the id of the rotation and the outpoint of the new rotation right.
+    fn rotate(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        genesis_id: OpId,
+    ) -> (OpId, Outpoint) {
+        let mut rotation = stock
+            .transition_builder(contract_id, "rotateKey")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_ROTATE_KEY, 0), AllocatedState::Void)
+            .unwrap()
+            .add_global_state("pubkey", testing::cosigner_pubkey())
+            .unwrap()
+            .add_rights("rotateKeyRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        rotation.signature = Some(testing::sign(&rotation));
+        let rotation_id = rotation.id();
+        let prevouts = [Outpoint::new(testing::txid(), 2)];
+        let witness_id = testing::consume(stock, resolver, contract_id, rotation, &prevouts, 100);
+        (rotation_id, Outpoint::new(witness_id, 1))
+    }
+
+    #[test]
+    fn rotate_then_transfer() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(pfa(1000, 1000, testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<PermissionedFungibleAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.version(), PermissionedFungibleAsset::V2);
+        assert_eq!(wrapper.pubkey(), testing::issuer_pubkey());
+        assert_eq!(wrapper.rotate_key_rights(&FilterIncludeAll).count(), 1);
+
+        let (rotation_id, right) = rotate(&mut stock, &mut resolver, contract_id, genesis_id);
+
+        let mut transfer = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 1000u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        transfer.signature = Some(testing::sign_with(&transfer, &testing::cosigner_key()));
+        let transfer_id = transfer.id();
+        let prevouts = [Outpoint::new(testing::txid(), 1)];
+        let witness_id =
+            testing::consume(&mut stock, &mut resolver, contract_id, transfer, &prevouts, 101);
+        let owner = Outpoint::new(witness_id, 1);
+
+        let wrapper = stock
+            .contract_wrapper::<PermissionedFungibleAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.pubkey(), testing::cosigner_pubkey());
+        assert_eq!(wrapper.pubkey_history(), vec![
+            testing::issuer_pubkey(),
+            testing::cosigner_pubkey()
+        ]);
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(owner)), "{seals:?}");
+        let rights = wrapper
+            .rotate_key_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(rights.contains(&OutputSeal::new(right)), "{rights:?}");
+
+        testing::validate_history::<PermissionedFungibleAsset>(&stock, &resolver, contract_id, &[
+            rotation_id,
+            transfer_id,
+        ])
+        .unwrap();
+    }
+
+    #[test]
+    fn transfer_signed_by_rotated_key_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(pfa(1000, 1000, testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let (rotation_id, _) = rotate(&mut stock, &mut resolver, contract_id, genesis_id);
+        let mut transfer = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 1000u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        transfer.signature = Some(testing::sign(&transfer));
+        let transfer_id = transfer.id();
+        let prevouts = [Outpoint::new(testing::txid(), 1)];
+        testing::consume(&mut stock, &mut resolver, contract_id, transfer, &prevouts, 101);
+
+        let err = testing::validate_history::<PermissionedFungibleAsset>(
+            &stock,
+            &resolver,
+            contract_id,
+            &[rotation_id, transfer_id],
+        )
+        .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::InvalidSignature));
+    }
 }
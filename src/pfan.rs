@@ -0,0 +1,338 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Zoe Faltibà <zoefaltiba@gmail.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi-key permissioned fungible asset (PFA-N) schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the PFA schema whose genesis commits to a set of authorized keys instead of a
+//! single one, so that issuers can operate several co-signing services: a transfer is valid when
+//! signed by any of them. Validation scripts can't try a signature against each key, so transfers
+//! also name the key they are signed with in their `signer` metadata, which must be one of the
+//! authorized keys.
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, ContractId, MetaDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::reject::RejectList;
+use crate::scripts::{SharedLibs, PFAN_TRANSITION, PFA_GENESIS};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_PUBKEY, GS_TERMS, MS_SIGNER, OS_ASSET, TS_TRANSFER,
+};
+
+pub const PFAN_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x00, 0x21, 0x8b, 0x45, 0x92, 0x10, 0xae, 0x05, 0x65, 0x58, 0xb7, 0x18, 0xe6, 0x67, 0x3b, 0xa5,
+    0x19, 0x95, 0x1c, 0x77, 0x33, 0xcc, 0x84, 0xc1, 0x56, 0x22, 0xa7, 0x9d, 0x41, 0xdf, 0xe9, 0x70,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn pfan_schema() -> Schema {
+    let types = standard_types();
+
+    PFA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    PFAN_TRANSITION.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("MultiKeyPermissionedAsset"),
+        meta_types: tiny_bmap! {
+            MS_SIGNER => MetaDetails {
+                sem_id: types.get("Bitcoin.CompressedPk"),
+                name: fname!("signer"),
+            }
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_PUBKEY => Occurrences::OnceOrMore,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(PFA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: tiny_bset![MS_SIGNER],
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(PFAN_TRANSITION.lib_site())
+                },
+                name: fname!("transfer"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn pfan_scripts() -> Scripts { SharedLibs::get().scripts(&[PFA_GENESIS, PFAN_TRANSITION]) }
+
+#[derive(Default)]
+pub struct MultiKeyPermissionedAsset;
+
+impl IssuerWrapper for MultiKeyPermissionedAsset {
+    type Wrapper<S: ContractStateRead> = PfanWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(pfan_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(pfan_scripts).clone()
+    }
+}
+
+impl MultiKeyPermissionedAsset {
+    /// First revision of the PFA-N schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "MultiKeyPermissionedAsset",
+        schema_id: PFAN_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fungible asset with a fixed supply whose transfers must be signed by any \
+                        of the authorized public keys committed in the global state.",
+    };
+}
+
+impl IssuerInfo for MultiKeyPermissionedAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for MultiKeyPermissionedAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct PfanWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for PfanWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for PfanWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<MultiKeyPermissionedAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> PfanWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the PFA-N schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<MultiKeyPermissionedAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the PFA-N schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    pub fn contract_id(&self) -> ContractId { self.0.contract_id() }
+
+    /// Returns the authorized public keys, any of which can sign a transfer.
+    pub fn pubkeys(&self) -> BTreeSet<CompressedPublicKey> { or_panic(self.try_pubkeys()) }
+
+    pub fn try_pubkeys(&self) -> Result<BTreeSet<CompressedPublicKey>, Error> {
+        globals::<_, CompressedPublicKey>(&self.0, GS_PUBKEY)?.collect()
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the allocations passing the filter which are not on the reject list.
+    pub fn accepted_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_accepted_allocations(filter, reject_list))
+    }
+
+    pub fn try_accepted_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self
+            .try_allocations(filter)?
+            .filter(|a| !reject_list.is_rejected(a.opout)))
+    }
+
+    /// Returns the allocations passing the filter which are on the reject list.
+    pub fn rejected_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_rejected_allocations(filter, reject_list))
+    }
+
+    pub fn try_rejected_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+        reject_list: &'c RejectList,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self
+            .try_allocations(filter)?
+            .filter(|a| reject_list.is_rejected(a.opout)))
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for PfanWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { PfanWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        PfanWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        PfanWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        PfanWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = pfan_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(PFAN_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<MultiKeyPermissionedAsset>(fixtures::contract_id("pfan"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.pubkeys(), bset! { fixtures::pubkey(), fixtures::cosigner_pubkey() });
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![fixtures::ISSUED_SUPPLY]);
+    }
+}
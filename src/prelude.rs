@@ -0,0 +1,121 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Items used by most integrations of the schemata, to be imported at once with
+//! `use schemata::prelude::*`.
+//!
+//! Besides the issuers, wrappers and schema ids of the enabled schemata, the prelude re-exports the
+//! `rgbstd` types needed to issue contracts, import them into a stock and read their state.
+
+pub use rgbstd::containers::{ConsignmentExt, FileContent, Kit};
+pub use rgbstd::contract::{
+    AssignmentsFilter, ContractBuilder, DataAllocation, FilterExclude, FilterIncludeAll,
+    FungibleAllocation, IssuerWrapper, RightsAllocation, SchemaWrapper,
+};
+pub use rgbstd::persistence::Stock;
+pub use rgbstd::stl::{
+    AssetSpec, Attachment, ContractTerms, Details, EmbeddedMedia, MediaType, Name, RejectListUrl,
+    RicardianContract, Ticker, TokenData,
+};
+pub use rgbstd::validation::ResolveWitness;
+pub use rgbstd::{
+    Allocation, Amount, ChainNet, ContractId, GenesisSeal, Identity, Outpoint, Precision, SchemaId,
+    TokenIndex, Txid,
+};
+
+pub use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo};
+#[cfg(feature = "bond")]
+pub use crate::{BondAsset, BondWrapper, BOND_SCHEMA_ID};
+#[cfg(feature = "burnable")]
+pub use crate::{BurnableAsset, BurnableWrapper, BURNABLE_SCHEMA_ID};
+#[cfg(feature = "carbon")]
+pub use crate::{CarbonCreditAsset, CarbonWrapper, Retirement, CARBON_SCHEMA_ID};
+#[cfg(feature = "cfa")]
+pub use crate::{CfaWrapper, CollectibleFungibleAsset, CFA_SCHEMA_ID};
+#[cfg(feature = "provenance")]
+pub use crate::{Checkpoint, ProvenanceAsset, ProvenanceWrapper, PROVENANCE_SCHEMA_ID};
+#[cfg(feature = "claim")]
+pub use crate::{ClaimWrapper, ClaimableAsset, CLAIM_SCHEMA_ID};
+#[cfg(feature = "collection")]
+pub use crate::{CollectionWrapper, UniqueDigitalCollection, COLLECTION_SCHEMA_ID};
+pub use crate::{ContractWrapper, FungibleAssetWrapper};
+#[cfg(feature = "credential")]
+pub use crate::{CredentialWrapper, VerifiableCredential, CREDENTIAL_SCHEMA_ID};
+#[cfg(feature = "dividend")]
+pub use crate::{DividendAsset, DividendWrapper, DIVIDEND_SCHEMA_ID};
+#[cfg(feature = "edition")]
+pub use crate::{EditionWrapper, LimitedEditionAsset, EDITION_SCHEMA_ID};
+#[cfg(feature = "eifa")]
+pub use crate::{EifaWrapper, EpochInflatableAsset, EIFA_SCHEMA_ID};
+#[cfg(feature = "escrow")]
+pub use crate::{EscrowAsset, EscrowWrapper, ESCROW_SCHEMA_ID};
+#[cfg(feature = "fee")]
+pub use crate::{FeeWrapper, TransferFeeAsset, FEE_SCHEMA_ID};
+#[cfg(feature = "fua")]
+pub use crate::{FractionShare, FractionalUniqueAsset, FuaWrapper, FUA_SCHEMA_ID};
+#[cfg(feature = "freezable")]
+pub use crate::{FreezableAsset, FreezableWrapper, FREEZABLE_SCHEMA_ID};
+#[cfg(feature = "governance")]
+pub use crate::{GovernanceAsset, GovernanceWrapper, GOVERNANCE_SCHEMA_ID};
+#[cfg(feature = "sba")]
+pub use crate::{HolderStatus, SbaWrapper, SoulboundAsset, SBA_SCHEMA_ID};
+#[cfg(feature = "ifa")]
+pub use crate::{IfaWrapper, InflatableFungibleAsset, IFA_SCHEMA_ID, IFA_V1_SCHEMA_ID};
+#[cfg(feature = "pfan")]
+pub use crate::{MultiKeyPermissionedAsset, PfanWrapper, PFAN_SCHEMA_ID};
+#[cfg(feature = "registry")]
+pub use crate::{NameRegistry, RegistryWrapper, REGISTRY_SCHEMA_ID};
+#[cfg(feature = "nia")]
+pub use crate::{NiaWrapper, NonInflatableAsset, NIA_SCHEMA_ID};
+#[cfg(feature = "option")]
+pub use crate::{OptionAsset, OptionTerms, OptionWrapper, OPTION_SCHEMA_ID};
+#[cfg(feature = "pausable")]
+pub use crate::{PausableAsset, PausableWrapper, PAUSABLE_SCHEMA_ID};
+#[cfg(feature = "pfa")]
+pub use crate::{PermissionedFungibleAsset, PfaWrapper, PFA_SCHEMA_ID, PFA_V1_SCHEMA_ID};
+#[cfg(feature = "rebasing")]
+pub use crate::{RebasingAsset, RebasingWrapper, REBASE_SCALE, REBASING_SCHEMA_ID};
+#[cfg(feature = "ria")]
+pub use crate::{ReissuableAsset, RiaWrapper, RIA_SCHEMA_ID};
+#[cfg(feature = "rental")]
+pub use crate::{RentalAsset, RentalWrapper, RENTAL_SCHEMA_ID};
+#[cfg(feature = "wbtc")]
+pub use crate::{ReserveAttestation, WbtcWrapper, WrappedBtcAsset, WBTC_SCHEMA_ID};
+#[cfg(feature = "sfa")]
+pub use crate::{SemiFungibleAsset, SfaWrapper, SFA_SCHEMA_ID};
+#[cfg(feature = "stablecoin")]
+pub use crate::{StablecoinAsset, StablecoinWrapper, STABLECOIN_SCHEMA_ID};
+#[cfg(feature = "subscription")]
+pub use crate::{SubscriptionAsset, SubscriptionWrapper, SUBSCRIPTION_SCHEMA_ID};
+#[cfg(feature = "swap")]
+pub use crate::{SwapOrder, SwapOrderWrapper, SWAP_SCHEMA_ID};
+#[cfg(feature = "tifa")]
+pub use crate::{ThresholdInflatableAsset, TifaWrapper, TIFA_SCHEMA_ID};
+#[cfg(feature = "ticket")]
+pub use crate::{TicketAsset, TicketWrapper, TICKET_SCHEMA_ID};
+#[cfg(feature = "timelock")]
+pub use crate::{TimelockAsset, TimelockWrapper, TIMELOCK_SCHEMA_ID};
+#[cfg(feature = "uda")]
+pub use crate::{
+    UdaWrapper, UniqueDigitalAsset, UDA_SCHEMA_ID, UDA_V1_SCHEMA_ID, UDA_V2_SCHEMA_ID,
+};
+#[cfg(feature = "vesting")]
+pub use crate::{VestingAsset, VestingSchedule, VestingWrapper, VESTING_SCHEMA_ID};
@@ -0,0 +1,489 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Supply-chain provenance schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the UDA schema whose token tracks a physical good along its supply chain. Besides
+//! the plain `transfer`, the token can be moved with a `checkpoint` transition, which records in
+//! the global state where the good is, when it got there and who handles it, so that
+//! [`ProvenanceWrapper::provenance`] can list the whole chain of custody. Both transitions are
+//! validated with the UDA scripts, preserving the token.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, Details, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_LDG;
+use rgbstd::{Allocation, Amount, GlobalDetails, OwnedStateSchema, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, UDA_GENESIS, UDA_SHARED, UDA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_CHECKPOINT_HANDLER,
+    GS_CHECKPOINT_LOCATION, GS_CHECKPOINT_TIME, GS_NOMINAL, GS_TERMS, GS_TOKENS, OS_ASSET,
+    TS_CHECKPOINT, TS_TRANSFER,
+};
+
+pub const PROVENANCE_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xbb, 0x0b, 0x6b, 0xc0, 0x33, 0x2a, 0x83, 0x36, 0xc2, 0x3e, 0xaa, 0xe1, 0x5c, 0x18, 0xf4, 0xe8,
+    0xd4, 0x70, 0x5e, 0x7d, 0x64, 0x39, 0x1b, 0x42, 0x0f, 0xe5, 0x7a, 0x47, 0x92, 0x08, 0x19, 0x45,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn provenance_schema() -> Schema {
+    let types = standard_types();
+
+    UDA_GENESIS.verify(&[(0, INSTR_PUTA), (8, INSTR_LDG)]);
+    UDA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDA_SHARED.verify(&[(0, INSTR_PUTA), (4, INSTR_EXTR)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("ProvenanceAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+            GS_CHECKPOINT_LOCATION => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Details")),
+                name: fname!("checkpointLocation"),
+            },
+            GS_CHECKPOINT_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("checkpointTime"),
+            },
+            GS_CHECKPOINT_HANDLER => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Details")),
+                name: fname!("checkpointHandler"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::Once,
+            },
+            validator: Some(UDA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    validator: Some(UDA_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            },
+            TS_CHECKPOINT => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_CHECKPOINT_LOCATION => Occurrences::Once,
+                        GS_CHECKPOINT_TIME => Occurrences::Once,
+                        GS_CHECKPOINT_HANDLER => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    // A checkpoint is a transfer recording the custody of the good
+                    validator: Some(UDA_TRANSFER.lib_site()),
+                },
+                name: fname!("checkpoint"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn provenance_scripts() -> Scripts { SharedLibs::get().scripts(&[UDA_GENESIS, UDA_TRANSFER]) }
+
+#[derive(Default)]
+pub struct ProvenanceAsset;
+
+impl IssuerWrapper for ProvenanceAsset {
+    type Wrapper<S: ContractStateRead> = ProvenanceWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(provenance_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(provenance_scripts).clone()
+    }
+}
+
+impl ProvenanceAsset {
+    /// First revision of the provenance schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "ProvenanceAsset",
+        schema_id: PROVENANCE_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "UDA variant tracking a good along its supply chain, whose transfers may \
+                        record a checkpoint with the location, time and handler of the good.",
+    };
+}
+
+impl IssuerInfo for ProvenanceAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for ProvenanceAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+/// Checkpoint of the supply chain, as recorded in the global state by a `checkpoint` transition.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Checkpoint {
+    /// Place where the good is, like a warehouse or a port.
+    pub location: Details,
+    /// Time the good reached the checkpoint, as a UNIX timestamp declared by the handler.
+    pub timestamp: i64,
+    /// Party handling the good at the checkpoint.
+    pub handler: Details,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct ProvenanceWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for ProvenanceWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for ProvenanceWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<ProvenanceAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> ProvenanceWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the provenance schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<ProvenanceAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the provenance schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn token_data(&self) -> TokenData { or_panic(self.try_token_data()) }
+
+    pub fn try_token_data(&self) -> Result<TokenData, Error> { global(&self.0, GS_TOKENS) }
+
+    /// Returns the chain of custody of the good, from the oldest checkpoint to the latest one in
+    /// the order of their witnesses.
+    pub fn provenance(&self) -> Vec<Checkpoint> { or_panic(self.try_provenance()) }
+
+    pub fn try_provenance(&self) -> Result<Vec<Checkpoint>, Error> {
+        // Each checkpoint records one entry of each global state, so their entries match one to
+        // one
+        let locations =
+            globals(&self.0, GS_CHECKPOINT_LOCATION)?.collect::<Result<Vec<Details>, _>>()?;
+        let times = globals(&self.0, GS_CHECKPOINT_TIME)?.collect::<Result<Vec<Amount>, _>>()?;
+        let handlers =
+            globals(&self.0, GS_CHECKPOINT_HANDLER)?.collect::<Result<Vec<Details>, _>>()?;
+        if times.len() != locations.len() {
+            return Err(Error::MissingGlobal(GS_CHECKPOINT_TIME));
+        }
+        if handlers.len() != locations.len() {
+            return Err(Error::MissingGlobal(GS_CHECKPOINT_HANDLER));
+        }
+        let mut provenance = locations
+            .into_iter()
+            .zip(times)
+            .zip(handlers)
+            .map(|((location, time), handler)| {
+                let timestamp = i64::try_from(time.value())
+                    .map_err(|_| Error::InvalidGlobal(GS_CHECKPOINT_TIME))?;
+                Ok(Checkpoint {
+                    location,
+                    timestamp,
+                    handler,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        // Global state is iterated from the latest entry in consensus order
+        provenance.reverse();
+        Ok(provenance)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Builds an invoice for receiving the token of the contract.
+    pub fn invoice(&self, beneficiary: impl Into<XChainNet<Beneficiary>>) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+    ) -> Result<RgbInvoice, Error> {
+        let allocation = Allocation::with(self.try_token_data()?.index, 1);
+        allocation_invoice(&self.0, beneficiary, allocation)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use amplify::confinement::SmallBlob;
+    use amplify::Wrapper;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::*;
+    use rgbstd::*;
+    use schemata_tools::testing;
+    use strict_types::StrictSerialize;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = provenance_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(PROVENANCE_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<ProvenanceAsset>(fixtures::contract_id("provenance"))
+            .unwrap();
+        assert_eq!(wrapper.version(), ProvenanceAsset::V1);
+        assert_eq!(
+            wrapper.spec(),
+            AssetSpec::new("TEST", "Test provenance", Precision::Indivisible)
+        );
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.token_data(), fixtures::token_data(false));
+        assert!(wrapper.provenance().is_empty());
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state))
+            .collect::<Vec<_>>();
+        let allocation = Allocation::with(TokenIndex::from_inner(fixtures::TOKEN_INDEX), 1)
+            .to_strict_serialized::<{ u16::MAX as usize }>()
+            .unwrap();
+        let state = RevealedData::new(SmallBlob::from_checked(allocation.release()));
+        assert_eq!(allocations, vec![(fixture_seal(1), state)]);
+    }
+
+    // Chain of custody of a provenance token.
+    //
+    // The token moves along the supply chain with two checkpoints and a plain transfer between
+    // them, each in its own mined witness TX. The `ProvenanceWrapper` must then report both
+    // checkpoints in the order of their witnesses.
+
+    /// Provenance token of index `token_index`, with `owned_index` allocated to `seal(1)`.
+    fn provenance(token_index: u32, owned_index: u32) -> ContractBuilder {
+        testing::builder::<ProvenanceAsset>()
+            .add_global_state(
+                "spec",
+                AssetSpec::new("TEST", "Test provenance", Precision::Indivisible),
+            )
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(token_index),
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::seal(1),
+                Allocation::with(TokenIndex::from_inner(owned_index), 1),
+            )
+            .unwrap()
+    }
+
+    fn checkpoint(location: &str, timestamp: i64, handler: &str) -> Checkpoint {
+        Checkpoint {
+            location: Details::from_str(location).unwrap(),
+            timestamp,
+            handler: Details::from_str(handler).unwrap(),
+        }
+    }
+
+    /// Moves the token owned by `opout` on `prevout`, recording `checkpoint` if any, and returns
+    /// the new owner opout and outpoint.
+    fn move_token(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        (opout, prevout): (Opout, Outpoint),
+        height: u32,
+        checkpoint: Option<&Checkpoint>,
+    ) -> (Opout, Outpoint) {
+        let RevealedState::Structured(data) = testing::allocation(2, 1) else {
+            unreachable!()
+        };
+        let name = if checkpoint.is_some() { "checkpoint" } else { "transfer" };
+        let mut builder = stock
+            .transition_builder(contract_id, name)
+            .unwrap()
+            .add_input(opout, AllocatedState::Data(data))
+            .unwrap();
+        if let Some(checkpoint) = checkpoint {
+            builder = builder
+                .add_global_state("checkpointLocation", checkpoint.location.clone())
+                .unwrap()
+                .add_global_state("checkpointTime", Amount::from(checkpoint.timestamp as u64))
+                .unwrap()
+                .add_global_state("checkpointHandler", checkpoint.handler.clone())
+                .unwrap();
+        }
+        let transition = builder
+            .add_data(
+                "assetOwner",
+                testing::graph_seal(1),
+                Allocation::with(TokenIndex::from_inner(2), 1),
+            )
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        (Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness_id, 1))
+    }
+
+    #[test]
+    fn provenance_chain() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(provenance(2, 2));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+        let wrapper = stock
+            .contract_wrapper::<ProvenanceAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.provenance(), vec![]);
+
+        let harvested = checkpoint("Finca La Esperanza, Huila", 1_700_000_000, "Cooperativa Huila");
+        let shipped = checkpoint("Port of Genoa", 1_702_000_000, "ACME Logistics");
+
+        let owner = (Opout::new(genesis_id, OS_ASSET, 0), Outpoint::new(testing::txid(), 1));
+        let owner =
+            move_token(&mut stock, &mut resolver, contract_id, owner, 100, Some(&harvested));
+        let owner = move_token(&mut stock, &mut resolver, contract_id, owner, 101, None);
+        let owner = move_token(&mut stock, &mut resolver, contract_id, owner, 102, Some(&shipped));
+
+        let wrapper = stock
+            .contract_wrapper::<ProvenanceAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.provenance(), vec![harvested, shipped]);
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(owner.1)), "{seals:?}");
+    }
+}
@@ -0,0 +1,528 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rebasing asset schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A fungible asset with a fixed raw supply, whose balances the issuer can scale by publishing
+//! rebase factors, as used by elastic-supply experiments. The genesis commits to the public key of
+//! the issuer and assigns it a rebase right. A `rebase` transition spends the right, records a
+//! factor in the global state and must be signed by the issuer, who can also give up rebasing by
+//! not assigning the right again.
+//!
+//! Rebases don't touch the allocations: factors are fixed-point numbers in units of
+//! [`REBASE_SCALE`], and the wrapper computes the effective balances by multiplying the raw
+//! allocations with the product of all the factors published so far.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER, REBASING_REBASE};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_PUBKEY, GS_REBASE_FACTORS, GS_TERMS, OS_ASSET, OS_REBASE, TS_REBASE,
+    TS_TRANSFER,
+};
+
+pub const REBASING_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x5b, 0xcb, 0x48, 0x13, 0xdd, 0xe3, 0xb7, 0xcd, 0xa6, 0x54, 0x42, 0x47, 0xf6, 0xe1, 0xdc, 0x57,
+    0xc5, 0x52, 0x8c, 0x5a, 0xc1, 0xca, 0x5b, 0x8a, 0x36, 0x4f, 0x3b, 0xf3, 0x1c, 0xd1, 0x51, 0x3f,
+]);
+
+/// Rebase factor leaving the balances unchanged: factors are fixed-point numbers with six decimal
+/// digits.
+pub const REBASE_SCALE: u64 = 1_000_000;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn rebasing_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    REBASING_REBASE.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("RebasingAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+            GS_REBASE_FACTORS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("rebaseFactors"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_REBASE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("rebaseRight"),
+                default_transition: TS_REBASE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_PUBKEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_REBASE => Occurrences::Once,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_REBASE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_REBASE_FACTORS => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_REBASE => Occurrences::Once,
+                    },
+                    assignments: tiny_bmap! {
+                        OS_REBASE => Occurrences::NoneOrOnce,
+                    },
+                    validator: Some(REBASING_REBASE.lib_site())
+                },
+                name: fname!("rebase"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn rebasing_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, REBASING_REBASE])
+}
+
+#[derive(Default)]
+pub struct RebasingAsset;
+
+impl IssuerWrapper for RebasingAsset {
+    type Wrapper<S: ContractStateRead> = RebasingWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(rebasing_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(rebasing_scripts).clone()
+    }
+}
+
+impl RebasingAsset {
+    /// First revision of the rebasing asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "RebasingAsset",
+        schema_id: REBASING_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset whose balances are scaled by rebase factors published in \
+                        transitions signed by the issuer.",
+    };
+}
+
+impl IssuerInfo for RebasingAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for RebasingAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+/// Multiplies `amount` by `factor` given in units of [`REBASE_SCALE`], saturating at `u128::MAX`.
+fn rebase(amount: u128, factor: u128) -> u128 {
+    amount
+        .checked_mul(factor)
+        .map_or(u128::MAX, |scaled| scaled / REBASE_SCALE as u128)
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct RebasingWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for RebasingWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for RebasingWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<RebasingAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> RebasingWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the rebasing asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<RebasingAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the rebasing asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the raw supply issued at genesis, which rebases don't change.
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the public key of the issuer, which signs the rebases.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    /// Returns the rebase factors in units of [`REBASE_SCALE`], in the order they were published.
+    pub fn rebase_factors(&self) -> Vec<Amount> { or_panic(self.try_rebase_factors()) }
+
+    pub fn try_rebase_factors(&self) -> Result<Vec<Amount>, Error> {
+        let mut factors = globals(&self.0, GS_REBASE_FACTORS)?.collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        factors.reverse();
+        Ok(factors)
+    }
+
+    /// Returns the product of all the rebase factors in units of [`REBASE_SCALE`], rounding down
+    /// after each rebase and saturating at `u128::MAX`.
+    pub fn cumulative_factor(&self) -> u128 { or_panic(self.try_cumulative_factor()) }
+
+    pub fn try_cumulative_factor(&self) -> Result<u128, Error> {
+        Ok(self
+            .try_rebase_factors()?
+            .into_iter()
+            .fold(REBASE_SCALE as u128, |cumulative, factor| {
+                rebase(cumulative, factor.value() as u128)
+            }))
+    }
+
+    /// Converts a raw amount of the asset into its effective amount after all the rebases,
+    /// rounding down.
+    pub fn effective_amount(&self, amount: Amount) -> u128 {
+        or_panic(self.try_effective_amount(amount))
+    }
+
+    pub fn try_effective_amount(&self, amount: Amount) -> Result<u128, Error> {
+        Ok(rebase(amount.value() as u128, self.try_cumulative_factor()?))
+    }
+
+    /// Returns the effective balance of the allocations matching `filter`, which is their raw sum
+    /// multiplied by the cumulative factor.
+    pub fn effective_balance(&self, filter: impl AssignmentsFilter) -> u128 {
+        or_panic(self.try_effective_balance(filter))
+    }
+
+    pub fn try_effective_balance(&self, filter: impl AssignmentsFilter) -> Result<u128, Error> {
+        let raw = self
+            .try_allocations(filter)?
+            .map(|a| a.state.value() as u128)
+            .sum::<u128>();
+        Ok(rebase(raw, self.try_cumulative_factor()?))
+    }
+
+    /// Returns the effective supply, which is the issued supply multiplied by the cumulative
+    /// factor.
+    pub fn effective_supply(&self) -> u128 { or_panic(self.try_effective_supply()) }
+
+    pub fn try_effective_supply(&self) -> Result<u128, Error> {
+        self.try_effective_amount(self.try_total_issued_supply()?)
+    }
+
+    /// Returns the raw allocations of the asset, see [`Self::effective_balance`] for their value
+    /// after the rebases.
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn rebase_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_rebase_rights(filter))
+    }
+
+    pub fn try_rebase_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REBASE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for RebasingWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        RebasingWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        RebasingWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        RebasingWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        RebasingWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = rebasing_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(REBASING_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn rebase_rounding() {
+        let scale = REBASE_SCALE as u128;
+        assert_eq!(rebase(1000, scale), 1000);
+        assert_eq!(rebase(1000, scale / 2), 500);
+        assert_eq!(rebase(3, scale / 2), 1);
+        assert_eq!(rebase(1000, 3 * scale), 3000);
+        assert_eq!(rebase(u128::MAX, 2 * scale), u128::MAX);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<RebasingAsset>(fixtures::contract_id("rebasing"))
+            .unwrap();
+        assert_eq!(wrapper.version(), RebasingAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.pubkey(), fixtures::pubkey());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.rebase_factors(), vec![]);
+        assert_eq!(wrapper.cumulative_factor(), REBASE_SCALE as u128);
+        assert_eq!(wrapper.effective_supply(), fixtures::ISSUED_SUPPLY as u128);
+        assert_eq!(wrapper.effective_balance(&FilterIncludeAll), fixtures::ISSUED_SUPPLY as u128);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .rebase_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Rebasing the balances of an asset.
+    //
+    // The holder splits their allocation, and the issuer then publishes two rebase factors, each in
+    // its own mined witness TX; the wrapper must report the factors in the order they were
+    // published and scale the raw allocations by their product, leaving the allocations
+    // themselves untouched.
+
+    /// Rebasing asset allocated to `seal(1)` whose rebases are signed by `pubkey`, with
+    /// the rebase right at `seal(2)`.
+    fn rebasing(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+        testing::asset::<RebasingAsset>(issued, allocated)
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+            .add_rights("rebaseRight", testing::seal(2))
+            .unwrap()
+    }
+
+    #[test]
+    fn transfer_and_rebase() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(rebasing(1000, 1000, testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 600u64)
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(2), 400u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 1)], 2);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let transfer_witness = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(101));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let factors = [Amount::from(2 * REBASE_SCALE), Amount::from(REBASE_SCALE * 3 / 4)];
+        let mut right = (Opout::new(genesis_id, OS_REBASE, 0), Outpoint::new(testing::txid(), 2));
+        for (height, factor) in [102, 103].into_iter().zip(factors) {
+            let mut transition = stock
+                .transition_builder(contract_id, "rebase")
+                .unwrap()
+                .add_input(right.0, AllocatedState::Void)
+                .unwrap()
+                .add_global_state("rebaseFactors", factor)
+                .unwrap()
+                .add_rights("rebaseRight", testing::graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            transition.signature = Some(testing::sign(&transition));
+            let opid = transition.id();
+
+            let fascia = testing::anchor(contract_id, transition, &[right.1], 1);
+            let witness = fascia.seal_witness.public.tx().unwrap().clone();
+            let witness_id = fascia.witness_id();
+            resolver.add_witness(witness, testing::mined_at(height));
+            stock.consume_fascia(fascia, resolver.clone()).unwrap();
+            right = (Opout::new(opid, OS_REBASE, 0), Outpoint::new(witness_id, 1));
+        }
+
+        let wrapper = stock
+            .contract_wrapper::<RebasingAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.rebase_factors(), factors.to_vec());
+        assert_eq!(wrapper.cumulative_factor(), 3 * REBASE_SCALE as u128 / 2);
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1000u64));
+        assert_eq!(wrapper.effective_supply(), 1500);
+        assert_eq!(wrapper.effective_amount(Amount::from(3u64)), 4);
+
+        // rebases leave the raw allocations in place
+        let holders = [Outpoint::new(transfer_witness, 1), Outpoint::new(transfer_witness, 2)];
+        let mut allocations = wrapper
+            .allocations(holders)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        allocations.sort();
+        assert_eq!(allocations, vec![400, 600]);
+        assert_eq!(wrapper.effective_balance(holders), 1500);
+        assert_eq!(wrapper.effective_balance([holders[0]]), 900);
+        assert_eq!(wrapper.effective_balance([holders[1]]), 600);
+    }
+}
@@ -0,0 +1,577 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Name registry schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A registry of unique names, each owned like the token of a UDC. The genesis doesn't register
+//! any name, but assigns the registrar a register right; each `register` transition spends it to
+//! record a new name in the global state, and may assign it again. The n-th registered name is
+//! bound to the token with index n, which the registration allocates to the owner of the name.
+//!
+//! Names are normalized as domain names are: the validation scripts reject names containing upper
+//! case letters or spaces, and names registered before, so that each name can be registered only
+//! once per contract. Transfers keep the allocated token, and so the name bound to it.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use amplify::Wrapper;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, RightsAllocation, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, Name};
+use rgbstd::validation::Scripts;
+use rgbstd::{
+    Allocation, GlobalDetails, Outpoint, OutputSeal, OwnedStateSchema, SchemaId, TokenIndex,
+    TransitionDetails,
+};
+use strict_types::{StrictDeserialize, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, REGISTRY_REGISTER, UDC_MATCH, UDC_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_NAMES, GS_NOMINAL, GS_TERMS, OS_ASSET,
+    OS_REGISTER, TS_REGISTER, TS_TRANSFER,
+};
+
+pub const REGISTRY_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x20, 0xf2, 0xc8, 0x3b, 0xaa, 0x42, 0x26, 0xb4, 0xda, 0xb9, 0xb5, 0xdb, 0xdc, 0xd2, 0x95, 0x39,
+    0x1e, 0x54, 0x36, 0x50, 0x44, 0x07, 0x63, 0x0a, 0x05, 0x23, 0x24, 0x2c, 0x97, 0x6f, 0x80, 0x46,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn registry_schema() -> Schema {
+    let types = standard_types();
+
+    UDC_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDC_MATCH.verify(&[(0, INSTR_PUTA)]);
+    REGISTRY_REGISTER.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("NameRegistry"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_NAMES => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Name")),
+                name: fname!("names"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_REGISTER => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("registerRight"),
+                default_transition: TS_REGISTER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_REGISTER => Occurrences::Once,
+            },
+            validator: None,
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(UDC_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            },
+            TS_REGISTER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_NAMES => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_REGISTER => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once,
+                        OS_REGISTER => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(REGISTRY_REGISTER.lib_site())
+                },
+                name: fname!("register"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn registry_scripts() -> Scripts { SharedLibs::get().scripts(&[UDC_TRANSFER, REGISTRY_REGISTER]) }
+
+/// Normalizes a name for registration, lowercasing its ASCII letters.
+///
+/// Fails if the name contains spaces, or doesn't have from 1 to 40 printable ASCII characters.
+pub fn normalize_name(name: &str) -> Result<Name, Error> {
+    if name.contains(' ') {
+        return Err(Error::InvalidName(name.to_owned()));
+    }
+    Name::from_str(&name.to_ascii_lowercase()).map_err(|_| Error::InvalidName(name.to_owned()))
+}
+
+#[derive(Default)]
+pub struct NameRegistry;
+
+impl IssuerWrapper for NameRegistry {
+    type Wrapper<S: ContractStateRead> = RegistryWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(registry_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(registry_scripts).clone()
+    }
+}
+
+impl NameRegistry {
+    /// First revision of the name registry schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "NameRegistry",
+        schema_id: REGISTRY_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Registry of unique normalized names registered by the holder of a \
+                        register right, each bound to a token transferred like the tokens of a \
+                        UDC.",
+    };
+}
+
+impl IssuerInfo for NameRegistry {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for NameRegistry {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct RegistryWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for RegistryWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for RegistryWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<NameRegistry, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> RegistryWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the name registry schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<NameRegistry, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the name registry schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the registered names in order of registration, so that the n-th of them is bound to
+    /// the token with index n.
+    pub fn names(&self) -> Vec<Name> { or_panic(self.try_names()) }
+
+    pub fn try_names(&self) -> Result<Vec<Name>, Error> {
+        // The global state lists the most recent names first
+        let mut names = globals::<_, Name>(&self.0, GS_NAMES)?.collect::<Result<Vec<_>, _>>()?;
+        names.reverse();
+        Ok(names)
+    }
+
+    /// Returns the index of the token bound to the name, once normalized, or `None` if the name
+    /// isn't registered.
+    pub fn token_of(&self, name: &str) -> Option<TokenIndex> { or_panic(self.try_token_of(name)) }
+
+    pub fn try_token_of(&self, name: &str) -> Result<Option<TokenIndex>, Error> {
+        let Ok(name) = normalize_name(name) else {
+            return Ok(None);
+        };
+        Ok(self
+            .try_names()?
+            .into_iter()
+            .position(|registered| registered == name)
+            .map(|pos| TokenIndex::from(pos as u32 + 1)))
+    }
+
+    /// Returns the name bound to the token with the given index, or `None` if there is none.
+    pub fn name_of(&self, index: TokenIndex) -> Option<Name> { or_panic(self.try_name_of(index)) }
+
+    pub fn try_name_of(&self, index: TokenIndex) -> Result<Option<Name>, Error> {
+        let Some(pos) = (index.to_inner() as usize).checked_sub(1) else {
+            return Ok(None);
+        };
+        Ok(self.try_names()?.into_iter().nth(pos))
+    }
+
+    /// Returns the token index the next registration must bind its name to.
+    pub fn next_token(&self) -> TokenIndex { or_panic(self.try_next_token()) }
+
+    pub fn try_next_token(&self) -> Result<TokenIndex, Error> {
+        let registered = globals::<_, Name>(&self.0, GS_NAMES)?.count();
+        u32::try_from(registered + 1)
+            .map(TokenIndex::from)
+            .map_err(|_| Error::InvalidGlobal(GS_NAMES))
+    }
+
+    /// Resolves the name, once normalized, to the seal owning it among the allocations passing
+    /// the filter, or `None` if the name isn't registered or its owner is filtered out.
+    ///
+    /// Since the filter may pass spent allocations too, it should only pass the outputs which are
+    /// known to be unspent.
+    pub fn resolve(&self, name: &str, filter: impl AssignmentsFilter) -> Option<OutputSeal> {
+        or_panic(self.try_resolve(name, filter))
+    }
+
+    pub fn try_resolve(
+        &self,
+        name: &str,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Option<OutputSeal>, Error> {
+        let Some(index) = self.try_token_of(name)? else {
+            return Ok(None);
+        };
+        for allocation in self.try_allocations(filter)? {
+            if token_index(&allocation)? == index {
+                return Ok(Some(allocation.seal));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the names owned by the seals closed over the given output, in order of
+    /// registration.
+    pub fn names_at(&self, outpoint: Outpoint) -> Vec<Name> {
+        or_panic(self.try_names_at(outpoint))
+    }
+
+    pub fn try_names_at(&self, outpoint: Outpoint) -> Result<Vec<Name>, Error> {
+        let mut indexes = self
+            .try_allocations(vec![outpoint])?
+            .map(|allocation| token_index(&allocation))
+            .collect::<Result<Vec<_>, _>>()?;
+        indexes.sort();
+        indexes.dedup();
+        let names = self.try_names()?;
+        Ok(indexes
+            .into_iter()
+            .filter_map(|index| {
+                let pos = (index.to_inner() as usize).checked_sub(1)?;
+                names.get(pos).cloned()
+            })
+            .collect())
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn register_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_register_rights(filter))
+    }
+
+    pub fn try_register_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REGISTER, filter)?)
+    }
+
+    /// Builds an invoice for receiving the token bound to the name, failing if the name isn't
+    /// registered.
+    pub fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        name: &str,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary, name))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        name: &str,
+    ) -> Result<RgbInvoice, Error> {
+        let index = self
+            .try_token_of(name)?
+            .ok_or_else(|| Error::UnknownName(name.to_owned()))?;
+        allocation_invoice(&self.0, beneficiary, Allocation::with(index, 1))
+    }
+}
+
+fn token_index(allocation: &DataAllocation) -> Result<TokenIndex, Error> {
+    Ok(Allocation::from_strict_serialized(allocation.state.clone().into())
+        .map_err(|_| Error::InvalidOwned(OS_ASSET))?
+        .token_index())
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::{AllocatedState, ContractBuilder, FilterIncludeAll};
+    use rgbstd::invoice::Precision;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::Name;
+    use rgbstd::{
+        Allocation, ContractId, Operation, Opout, Outpoint, OutputSeal, RevealedState, TokenIndex,
+        Transition,
+    };
+    use schemata_tools::testing;
+    use schemata_tools::testing::{seal, CHAIN_NET};
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = registry_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(REGISTRY_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize_name("Satoshi.BTC").unwrap(), Name::from("satoshi.btc"));
+        assert_eq!(normalize_name("satoshi").unwrap(), Name::from("satoshi"));
+        assert_eq!(
+            normalize_name("satoshi nakamoto").unwrap_err(),
+            Error::InvalidName(s!("satoshi nakamoto"))
+        );
+        assert_eq!(normalize_name("").unwrap_err(), Error::InvalidName(s!("")));
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<NameRegistry>(fixtures::contract_id("registry"))
+            .unwrap();
+        assert_eq!(wrapper.version(), NameRegistry::V1);
+        assert_eq!(wrapper.spec(), AssetSpec::new("TEST", "Test names", Precision::Indivisible));
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.names(), vec![]);
+        assert_eq!(wrapper.token_of("satoshi"), None);
+        assert_eq!(wrapper.name_of(TokenIndex::from(1)), None);
+        assert_eq!(wrapper.next_token(), TokenIndex::from(1));
+        assert_eq!(wrapper.resolve("satoshi", &FilterIncludeAll), None);
+        assert_eq!(wrapper.names_at(seal(1).to_outpoint()), vec![]);
+        assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 0);
+        assert_eq!(wrapper.register_rights(&FilterIncludeAll).count(), 1);
+        let beneficiary = XChainNet::with(CHAIN_NET, Beneficiary::from(seal(7).to_secret_seal()));
+        assert_eq!(
+            wrapper.try_invoice(beneficiary, "satoshi").unwrap_err(),
+            Error::UnknownName(s!("satoshi"))
+        );
+    }
+
+    // Registering names and transferring them.
+    //
+    // The registrar registers two names, each in its own mined witness TX, passing the register
+    // right along, and the owner of the first name transfers it to a new output. The wrapper
+    // must then resolve each name to its current owner, and the outputs of the owners back to
+    // their names.
+
+    /// Name registry without registered names, with the register right at `seal(1)`.
+    fn registry() -> ContractBuilder {
+        testing::builder::<NameRegistry>()
+            .add_global_state("spec", AssetSpec::new("TEST", "Test names", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_rights("registerRight", testing::seal(1))
+            .unwrap()
+    }
+
+    fn register(stock: &Stock, contract_id: ContractId, right: Opout, name: &str) -> Transition {
+        let wrapper = stock.contract_wrapper::<NameRegistry>(contract_id).unwrap();
+        stock
+            .transition_builder(contract_id, "register")
+            .unwrap()
+            .add_input(right, AllocatedState::Void)
+            .unwrap()
+            .add_global_state("names", Name::from_str(name).unwrap())
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::graph_seal(2),
+                Allocation::with(wrapper.next_token(), 1),
+            )
+            .unwrap()
+            .add_rights("registerRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap()
+    }
+
+    fn consume(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        transition: Transition,
+        prevout: Outpoint,
+        height: u32,
+    ) -> Outpoint {
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 2);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        Outpoint::new(witness_id, 0)
+    }
+
+    #[test]
+    fn register_and_resolve() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(registry());
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut right = (Opout::new(genesis_id, OS_REGISTER, 0), Outpoint::new(testing::txid(), 1));
+        let mut owners = vec![];
+        for (height, name) in [(101, "satoshi.btc"), (102, "nakamoto.btc")] {
+            let transition = register(&stock, contract_id, right.0, name);
+            let opid = transition.id();
+            let witness =
+                consume(&mut stock, &mut resolver, contract_id, transition, right.1, height);
+            right = (Opout::new(opid, OS_REGISTER, 0), Outpoint::new(witness.txid, 1));
+            owners.push((Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness.txid, 2)));
+        }
+
+        let wrapper = stock.contract_wrapper::<NameRegistry>(contract_id).unwrap();
+        assert_eq!(wrapper.names(), vec![
+            Name::from_str("satoshi.btc").unwrap(),
+            Name::from_str("nakamoto.btc").unwrap()
+        ]);
+        assert_eq!(wrapper.next_token(), TokenIndex::from(3));
+        assert_eq!(wrapper.token_of("Satoshi.BTC"), Some(TokenIndex::from(1)));
+        assert_eq!(
+            wrapper.name_of(TokenIndex::from(2)),
+            Some(Name::from_str("nakamoto.btc").unwrap())
+        );
+        assert_eq!(wrapper.token_of("hal.btc"), None);
+
+        // the owner of the first name transfers it
+        let RevealedState::Structured(data) = testing::allocation(1, 1) else {
+            unreachable!()
+        };
+        let transfer = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(owners[0].0, AllocatedState::Data(data))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::graph_seal(0),
+                Allocation::with(TokenIndex::from(1), 1),
+            )
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let new_owner = consume(&mut stock, &mut resolver, contract_id, transfer, owners[0].1, 103);
+
+        let wrapper = stock.contract_wrapper::<NameRegistry>(contract_id).unwrap();
+        let unspent = vec![new_owner, owners[1].1];
+        assert_eq!(wrapper.resolve("satoshi.btc", &unspent), Some(OutputSeal::new(new_owner)));
+        assert_eq!(wrapper.resolve("nakamoto.btc", &unspent), Some(OutputSeal::new(owners[1].1)));
+        assert_eq!(wrapper.resolve("nakamoto.btc", vec![new_owner]), None);
+        assert_eq!(wrapper.resolve("hal.btc", &unspent), None);
+        assert_eq!(wrapper.names_at(new_owner), vec![Name::from_str("satoshi.btc").unwrap()]);
+        assert_eq!(wrapper.names_at(owners[1].1), vec![Name::from_str("nakamoto.btc").unwrap()]);
+        assert_eq!(wrapper.names_at(right.1), vec![]);
+    }
+}
@@ -0,0 +1,161 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reject lists published by asset issuers.
+//!
+//! An issuer may publish a list of operations and outputs whose allocations it doesn't honor,
+//! e.g. because they were stolen. IFA contracts commit to the URL of such a list in their
+//! `rejectListUrl` global state.
+//!
+//! A reject list is a text document with one entry per line: an operation id, rejecting all the
+//! outputs of the operation, or an operation output written as `<opid>/<type>/<no>`. Empty lines
+//! and everything following a `#` are ignored. Fetching a list from its URL requires an HTTP
+//! client, so it is left to the `rgb-schemata-tools` crate.
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use rgbstd::{OpId, Opout};
+
+/// Error parsing a reject list.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum RejectListError {
+    /// line {line} of the reject list contains invalid entry '{entry}'.
+    InvalidEntry { line: usize, entry: String },
+}
+
+/// Operations and operation outputs rejected by an issuer.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct RejectList {
+    ops: BTreeSet<OpId>,
+    opouts: BTreeSet<Opout>,
+}
+
+impl RejectList {
+    pub fn new() -> Self { Self::default() }
+
+    /// Rejects all the outputs of an operation.
+    pub fn reject_op(&mut self, op: OpId) { self.ops.insert(op); }
+
+    /// Rejects a single operation output.
+    pub fn reject_opout(&mut self, opout: Opout) { self.opouts.insert(opout); }
+
+    /// Checks whether all the outputs of the operation are rejected.
+    pub fn is_op_rejected(&self, op: OpId) -> bool { self.ops.contains(&op) }
+
+    /// Checks whether the operation output is rejected, directly or with its operation.
+    pub fn is_rejected(&self, opout: Opout) -> bool {
+        self.is_op_rejected(opout.op) || self.opouts.contains(&opout)
+    }
+
+    pub fn is_empty(&self) -> bool { self.ops.is_empty() && self.opouts.is_empty() }
+}
+
+impl FromStr for RejectList {
+    type Err = RejectListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut list = RejectList::new();
+        for (no, line) in s.lines().enumerate() {
+            let entry = line.split('#').next().unwrap_or_default().trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let invalid = || RejectListError::InvalidEntry {
+                line: no + 1,
+                entry: entry.to_owned(),
+            };
+            if entry.contains('/') {
+                list.reject_opout(Opout::from_str(entry).map_err(|_| invalid())?);
+            } else {
+                list.reject_op(OpId::from_str(entry).map_err(|_| invalid())?);
+            }
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+
+    use super::*;
+    use crate::{fixtures, IfaWrapper, PfaWrapper, OS_ASSET};
+
+    #[test]
+    fn parse() {
+        let op = OpId::from([1u8; 32]);
+        let other = OpId::from([7u8; 32]);
+        let list = RejectList::from_str(&format!(
+            "# stolen in the hack\n\n{op}\n  {other}/4000/1  # partially\n"
+        ))
+        .unwrap();
+        assert!(list.is_op_rejected(op));
+        assert!(list.is_rejected(Opout::new(op, OS_ASSET, 3)));
+        assert!(!list.is_op_rejected(other));
+        assert!(list.is_rejected(Opout::new(other, OS_ASSET, 1)));
+        assert!(!list.is_rejected(Opout::new(other, OS_ASSET, 0)));
+
+        assert!(RejectList::from_str("\n# nothing\n").unwrap().is_empty());
+        assert_eq!(
+            RejectList::from_str(&format!("{op}\n{op}/x/1")),
+            Err(RejectListError::InvalidEntry {
+                line: 2,
+                entry: format!("{op}/x/1")
+            })
+        );
+        assert!(RejectList::from_str("not an op").is_err());
+    }
+
+    #[test]
+    fn wrappers() {
+        let stock = fixtures::stock();
+        let ifa = IfaWrapper::try_with(stock.contract_data(fixtures::contract_id("ifa")).unwrap())
+            .unwrap();
+        let pfa = PfaWrapper::try_with(stock.contract_data(fixtures::contract_id("pfa")).unwrap())
+            .unwrap();
+
+        let empty = RejectList::new();
+        let all = ifa.allocations(FilterIncludeAll).collect::<Vec<_>>();
+        assert!(!all.is_empty());
+        assert_eq!(
+            ifa.accepted_allocations(FilterIncludeAll, &empty)
+                .collect::<Vec<_>>(),
+            all
+        );
+        assert_eq!(ifa.rejected_allocations(FilterIncludeAll, &empty).count(), 0);
+
+        let mut list = RejectList::new();
+        list.reject_opout(all[0].opout);
+        let rejected = ifa
+            .rejected_allocations(FilterIncludeAll, &list)
+            .collect::<Vec<_>>();
+        assert_eq!(rejected, vec![all[0]]);
+        assert_eq!(ifa.accepted_allocations(FilterIncludeAll, &list).count(), all.len() - 1);
+
+        let all = pfa.allocations(FilterIncludeAll).collect::<Vec<_>>();
+        let mut list = RejectList::new();
+        list.reject_op(all[0].opout.op);
+        assert_eq!(pfa.accepted_allocations(FilterIncludeAll, &list).count(), 0);
+        assert_eq!(pfa.rejected_allocations(FilterIncludeAll, &list).count(), all.len());
+    }
+}
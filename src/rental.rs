@@ -0,0 +1,531 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rental token schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the UDA schema separating the usage of the token from its ownership. The owner
+//! leases the token up to an expiry block height with a `lease` transition, which assigns the
+//! token back to the owner together with a lease right for the lessee. Once the lease expires,
+//! the owner ends it with a `reclaim` transition, after which the token can be leased again. The
+//! ownership can be transferred while the token is leased, the lease staying in force.
+//!
+//! The validation scripts check that a lease is only granted once the previous one is reclaimed,
+//! and that a reclaim ends the current lease. A reclaim commits to the lock time of its witness
+//! transaction, which must not let it be mined before the lease expires. The lock time of the
+//! witness itself is not checked, see [`crate::height`]: a reclaim mined before the lease expires
+//! is still valid, and the `audit` module of `rgb-schemata-tools` reports it.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, RightsAllocation, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_LDG;
+use rgbstd::{Allocation, GlobalDetails, OwnedStateSchema, SchemaId, TransitionDetails};
+use strict_types::encoding::bitcoin_types::LockTime;
+use strict_types::TypeSystem;
+
+use crate::height::height;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{
+    SharedLibs, RENTAL_LEASE, RENTAL_RECLAIM, UDA_GENESIS, UDA_SHARED, UDA_TRANSFER,
+};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_LEASE_EXPIRY, GS_LOCK_TIME, GS_NOMINAL,
+    GS_RECLAIMED_LEASES, GS_TERMS, GS_TOKENS, OS_ASSET, OS_LEASE, TS_LEASE, TS_RECLAIM,
+    TS_TRANSFER,
+};
+
+pub const RENTAL_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x03, 0xf6, 0xe2, 0xa6, 0x6b, 0x2b, 0xad, 0x80, 0x7c, 0xe1, 0x64, 0xaa, 0xf7, 0xc4, 0x95, 0x9a,
+    0x69, 0xb0, 0xa3, 0xd0, 0x74, 0x69, 0xc7, 0xaf, 0x83, 0x95, 0xb0, 0x58, 0x1e, 0xce, 0x9f, 0x93,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn rental_schema() -> Schema {
+    let types = standard_types();
+
+    UDA_GENESIS.verify(&[(0, INSTR_PUTA), (8, INSTR_LDG)]);
+    UDA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDA_SHARED.verify(&[(0, INSTR_PUTA), (4, INSTR_EXTR)]);
+    RENTAL_LEASE.verify(&[(0, INSTR_PUTA)]);
+    RENTAL_RECLAIM.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("RentalAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+            GS_LEASE_EXPIRY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.LockTime")),
+                name: fname!("leaseExpiry"),
+            },
+            GS_RECLAIMED_LEASES => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.LockTime")),
+                name: fname!("reclaimedLeases"),
+            },
+            GS_LOCK_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("lockTime"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_LEASE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("leaseRight"),
+                default_transition: TS_LEASE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::Once,
+            },
+            validator: Some(UDA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    validator: Some(UDA_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            },
+            TS_LEASE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_LEASE_EXPIRY => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once,
+                        OS_LEASE => Occurrences::Once
+                    },
+                    validator: Some(RENTAL_LEASE.lib_site()),
+                },
+                name: fname!("lease"),
+            },
+            TS_RECLAIM => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_RECLAIMED_LEASES => Occurrences::Once,
+                        GS_LOCK_TIME => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    validator: Some(RENTAL_RECLAIM.lib_site()),
+                },
+                name: fname!("reclaim"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn rental_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[UDA_GENESIS, UDA_TRANSFER, RENTAL_LEASE, RENTAL_RECLAIM])
+}
+
+#[derive(Default)]
+pub struct RentalAsset;
+
+impl IssuerWrapper for RentalAsset {
+    type Wrapper<S: ContractStateRead> = RentalWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(rental_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(rental_scripts).clone()
+    }
+}
+
+impl RentalAsset {
+    /// First revision of the rental schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "RentalAsset",
+        schema_id: RENTAL_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "UDA variant whose owner leases the usage of the token up to an expiry \
+                        height and reclaims it afterwards.",
+    };
+}
+
+impl IssuerInfo for RentalAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for RentalAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct RentalWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for RentalWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for RentalWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<RentalAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> RentalWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the rental schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<RentalAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the rental schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn token_data(&self) -> TokenData { or_panic(self.try_token_data()) }
+
+    pub fn try_token_data(&self) -> Result<TokenData, Error> { global(&self.0, GS_TOKENS) }
+
+    /// Returns the number of leases of the token, including the reclaimed ones.
+    pub fn leases(&self) -> usize { or_panic(self.try_leases()) }
+
+    pub fn try_leases(&self) -> Result<usize, Error> {
+        Ok(globals::<_, LockTime>(&self.0, GS_LEASE_EXPIRY)?.count())
+    }
+
+    /// Returns the height of the last block in which the current lease is in force, or `None` if
+    /// the token isn't leased since its last lease was reclaimed.
+    ///
+    /// A lease stays current after its expiry until the owner reclaims it.
+    pub fn lease_expiry(&self) -> Option<u32> { or_panic(self.try_lease_expiry()) }
+
+    pub fn try_lease_expiry(&self) -> Result<Option<u32>, Error> {
+        let reclaims = globals::<_, LockTime>(&self.0, GS_RECLAIMED_LEASES)?.count();
+        if self.try_leases()? == reclaims {
+            return Ok(None);
+        }
+        // Leases are rejected until the previous one is reclaimed, so the latest is the current
+        height(&self.0, GS_LEASE_EXPIRY).map(Some)
+    }
+
+    /// Checks whether the lessee may use the token in a block at the given height.
+    pub fn is_leased(&self, height: u32) -> bool { or_panic(self.try_is_leased(height)) }
+
+    pub fn try_is_leased(&self, height: u32) -> Result<bool, Error> {
+        Ok(self
+            .try_lease_expiry()?
+            .is_some_and(|expiry| height <= expiry))
+    }
+
+    /// Iterates over the lease rights, including the ones of reclaimed leases, which reclaims
+    /// don't spend: only the right assigned by the latest lease is in force, and only while
+    /// [`Self::lease_expiry`] is set.
+    pub fn lease_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_lease_rights(filter))
+    }
+
+    pub fn try_lease_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_LEASE, filter)?)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Builds an invoice for receiving the ownership of the token.
+    pub fn invoice(&self, beneficiary: impl Into<XChainNet<Beneficiary>>) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+    ) -> Result<RgbInvoice, Error> {
+        let allocation = Allocation::with(self.try_token_data()?.index, 1);
+        allocation_invoice(&self.0, beneficiary, allocation)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallBlob;
+    use amplify::Wrapper;
+    #[cfg(all_schemas)]
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    #[cfg(all_schemas)]
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::*;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+    use strict_types::StrictSerialize;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+    #[cfg(all_schemas)]
+    use crate::height::block_height;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = rental_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(RENTAL_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<RentalAsset>(fixtures::contract_id("rental"))
+            .unwrap();
+        assert_eq!(wrapper.version(), RentalAsset::V1);
+        assert_eq!(wrapper.spec(), AssetSpec::new("TEST", "Test rental", Precision::Indivisible));
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.token_data(), fixtures::token_data(false));
+        assert_eq!(wrapper.leases(), 0);
+        assert_eq!(wrapper.lease_expiry(), None);
+        assert!(!wrapper.is_leased(0));
+        assert_eq!(wrapper.lease_rights(&FilterIncludeAll).count(), 0);
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state))
+            .collect::<Vec<_>>();
+        let allocation = Allocation::with(TokenIndex::from_inner(fixtures::TOKEN_INDEX), 1)
+            .to_strict_serialized::<{ u16::MAX as usize }>()
+            .unwrap();
+        let state = RevealedData::new(SmallBlob::from_checked(allocation.release()));
+        assert_eq!(allocations, vec![(fixture_seal(1), state)]);
+    }
+
+    // Leasing and reclaiming a rental token.
+    //
+    // The owner leases the token up to an expiry height, keeping its ownership while the lessee
+    // receives the lease right, then reclaims it in a later witness TX committing to the expiry as
+    // its lock time. The scripts don't check the lock time of the witness, so the reclaim is
+    // accepted even if it is mined before the expiry, and it is the audit which must report it as
+    // premature until the witness is mined after the expiry height.
+
+    /// Rental token of index `token_index`, with `owned_index` allocated to `seal(1)`.
+    #[cfg(all_schemas)]
+    fn rental(token_index: u32, owned_index: u32) -> ContractBuilder {
+        testing::builder::<RentalAsset>()
+            .add_global_state("spec", AssetSpec::new("TEST", "Test rental", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(token_index),
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::seal(1),
+                Allocation::with(TokenIndex::from_inner(owned_index), 1),
+            )
+            .unwrap()
+    }
+
+    #[cfg(all_schemas)]
+    const EXPIRY: u32 = 150;
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn lease_and_reclaim() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(rental(2, 2));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let RevealedState::Structured(data) = testing::allocation(2, 1) else {
+            unreachable!()
+        };
+        let owned = Allocation::with(TokenIndex::from_inner(2), 1);
+
+        let transition = stock
+            .transition_builder(contract_id, "lease")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_ASSET, 0), AllocatedState::Data(data.clone()))
+            .unwrap()
+            .add_global_state("leaseExpiry", block_height(EXPIRY))
+            .unwrap()
+            .add_data("assetOwner", testing::graph_seal(1), owned)
+            .unwrap()
+            .add_rights("leaseRight", testing::graph_seal(2))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let lease_id = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 1)], 2);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let lease_witness = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(100));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock.contract_wrapper::<RentalAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.leases(), 1);
+        assert_eq!(wrapper.lease_expiry(), Some(EXPIRY));
+        assert!(wrapper.is_leased(EXPIRY));
+        assert!(!wrapper.is_leased(EXPIRY + 1));
+        let rights = wrapper
+            .lease_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![OutputSeal::new(Outpoint::new(lease_witness, 2))]);
+
+        let transition = stock
+            .transition_builder(contract_id, "reclaim")
+            .unwrap()
+            .add_input(Opout::new(lease_id, OS_ASSET, 0), AllocatedState::Data(data))
+            .unwrap()
+            .add_global_state("reclaimedLeases", block_height(EXPIRY))
+            .unwrap()
+            .add_global_state("lockTime", block_height(EXPIRY))
+            .unwrap()
+            .add_data("assetOwner", testing::graph_seal(1), owned)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let reclaim_id = transition.id();
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(lease_witness, 1)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let reclaim_witness = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(EXPIRY));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock.contract_wrapper::<RentalAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.leases(), 1);
+        assert_eq!(wrapper.lease_expiry(), None);
+        assert!(!wrapper.is_leased(EXPIRY));
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(Outpoint::new(reclaim_witness, 1))), "{seals:?}");
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::PrematureReclaim {
+            op: reclaim_id,
+            height: EXPIRY,
+            expiry: EXPIRY,
+        }]);
+
+        resolver.set_witness_ord(reclaim_witness, testing::mined_at(EXPIRY + 1));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+    }
+}
@@ -0,0 +1,372 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Human-readable summaries of validated consignments.
+//!
+//! A [`ConsignmentReport`] collects what a reviewer needs to check before accepting a contract or
+//! a transfer: the schema and the identity of the asset, the operations and witnesses carried by
+//! the consignment, the issued supply and the allocations assigned to the terminal seals. The
+//! report is rendered as markdown or plain text with [`ConsignmentReport::render`].
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+
+use rgbstd::containers::{ConsignmentExt, ValidConsignment};
+use rgbstd::stl::AssetSpec;
+#[cfg(any(feature = "cfa", feature = "carbon"))]
+use rgbstd::stl::Name;
+use rgbstd::vm::WitnessOrd;
+use rgbstd::{
+    Allocation, Amount, ContractId, ExposedSeal, GlobalState, GlobalStateType, Opout, Precision,
+    RevealedData, SchemaId, SecretSeal, Txid, TypedAssigns,
+};
+use strict_types::{StrictDecode, StrictDeserialize};
+
+use crate::info::{schema_info, SchemaInfo};
+use crate::{GS_ISSUED_SUPPLY, GS_NOMINAL};
+
+/// Output format of [`ConsignmentReport::render`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    PlainText,
+}
+
+/// State assigned to a terminal seal of a consignment.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum TerminalState {
+    Amount(Amount),
+    /// Fraction of a token of a UDA-like contract.
+    Allocation(Allocation),
+    /// Structured state not holding an allocation.
+    Data(RevealedData),
+    Right,
+}
+
+/// Allocation of a consignment assigned to one of its terminal seals.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TerminalAllocation {
+    pub opout: Opout,
+    pub seal: SecretSeal,
+    pub state: TerminalState,
+}
+
+/// Summary of a validated consignment, see the [module documentation](self).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ConsignmentReport {
+    pub contract_id: ContractId,
+    pub schema_id: SchemaId,
+    /// Release of the schema, if it is one of the schemata of this crate.
+    pub schema: Option<SchemaInfo>,
+    pub transfer: bool,
+    pub ticker: Option<String>,
+    pub name: Option<String>,
+    pub precision: Option<Precision>,
+    /// Sum of the supply issued by the operations of the consignment, if the schema declares one.
+    pub issued: Option<u128>,
+    pub bundles: usize,
+    /// Number of transitions of each type, by the name the schema gives to it.
+    pub transitions: BTreeMap<String, usize>,
+    /// Witnesses of the bundles with their ordering at the time of validation.
+    pub witnesses: BTreeMap<Txid, Option<WitnessOrd>>,
+    pub terminals: Vec<TerminalAllocation>,
+    pub warnings: Vec<String>,
+}
+
+impl ConsignmentReport {
+    pub fn new<const TRANSFER: bool>(consignment: &ValidConsignment<TRANSFER>) -> Self {
+        let schema_id = consignment.schema_id();
+        let genesis = consignment.genesis();
+        let (ticker, name, precision) = asset_identity(&genesis.globals);
+
+        let mut issued = consignment
+            .schema
+            .global_types
+            .contains_key(&GS_ISSUED_SUPPLY)
+            .then(|| issued_supply(&genesis.globals));
+        let mut transitions = BTreeMap::new();
+        let mut witnesses = BTreeMap::new();
+        let mut terminals = vec![];
+        let status = consignment.validation_status();
+        for bundle in &consignment.bundles {
+            let witness_id = bundle.witness_id();
+            witnesses.insert(witness_id, status.tx_ord_map.get(&witness_id).copied());
+            let terminal_seals = consignment.terminals.get(&bundle.bundle.bundle_id());
+            for known in &bundle.bundle.known_transitions {
+                let transition = &known.transition;
+                let ty = transition.transition_type;
+                let ty_name = consignment
+                    .schema
+                    .transitions
+                    .get(&ty)
+                    .map(|details| details.name.to_string())
+                    .unwrap_or_else(|| ty.to_string());
+                *transitions.entry(ty_name).or_default() += 1;
+                if let Some(issued) = &mut issued {
+                    *issued += issued_supply(&transition.globals);
+                }
+                let Some(seals) = terminal_seals else {
+                    continue;
+                };
+                for (ty, assigns) in transition.assignments.iter() {
+                    for (no, seal) in assigns.to_confidential_seals().into_iter().enumerate() {
+                        if seals.contains(&seal) {
+                            terminals.push(TerminalAllocation {
+                                opout: Opout::new(known.opid, *ty, no as u16),
+                                seal,
+                                state: terminal_state(assigns, no),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        ConsignmentReport {
+            contract_id: consignment.contract_id(),
+            schema_id,
+            schema: schema_info(schema_id),
+            transfer: TRANSFER,
+            ticker,
+            name,
+            precision,
+            issued,
+            bundles: consignment.bundles.len(),
+            transitions,
+            witnesses,
+            terminals,
+            warnings: status.warnings.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// Formats an amount of the asset with its precision, if known.
+    pub fn format_amount(&self, amount: u128) -> String {
+        let Some(precision) = self.precision.filter(|p| p.decimals() > 0) else {
+            return amount.to_string();
+        };
+        let multiplier = precision.multiplier() as u128;
+        let decimals = precision.decimals() as usize;
+        format!("{}.{:0decimals$}", amount / multiplier, amount % multiplier)
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        let mut out = Renderer {
+            format,
+            text: String::new(),
+        };
+        let kind = if self.transfer { "Transfer" } else { "Contract" };
+        out.title(format_args!("{kind} consignment of {}", self.contract_id));
+
+        out.section("Schema");
+        out.field("Schema id", self.schema_id);
+        match &self.schema {
+            Some(info) => {
+                out.field("Schema", format_args!("{} {}", info.name, info.version));
+                out.field("Developer", info.developer);
+            }
+            None => out.field("Schema", "unknown to this library"),
+        }
+
+        out.section("Asset");
+        out.field("Ticker", self.ticker.as_deref().unwrap_or("-"));
+        out.field("Name", self.name.as_deref().unwrap_or("-"));
+        match self.precision {
+            Some(precision) => out.field("Precision", precision.decimals()),
+            None => out.field("Precision", "-"),
+        }
+        match self.issued {
+            Some(issued) => out.field("Issued supply", self.format_amount(issued)),
+            None => out.field("Issued supply", "-"),
+        }
+
+        out.section("Operations");
+        out.field("Bundles", self.bundles);
+        for (name, count) in &self.transitions {
+            out.field(format_args!("Transitions {name}"), count);
+        }
+        for (txid, ord) in &self.witnesses {
+            let status = match ord {
+                Some(WitnessOrd::Mined(pos)) => format!("mined at height {}", pos.height()),
+                Some(ord) => ord.to_string(),
+                None => "unresolved".to_owned(),
+            };
+            out.field(format_args!("Witness {txid}"), status);
+        }
+
+        out.section("Terminal allocations");
+        if self.terminals.is_empty() {
+            out.item("none");
+        }
+        for terminal in &self.terminals {
+            let state = match &terminal.state {
+                TerminalState::Amount(amount) => self.format_amount(amount.value() as u128),
+                TerminalState::Allocation(allocation) => format!(
+                    "token {}, fraction {}",
+                    allocation.token_index(),
+                    allocation.fraction().value()
+                ),
+                TerminalState::Data(data) => format!("data {data}"),
+                TerminalState::Right => "right".to_owned(),
+            };
+            out.field(format_args!("{} to {}", terminal.opout, terminal.seal), state);
+        }
+
+        out.section("Validation warnings");
+        if self.warnings.is_empty() {
+            out.item("none");
+        }
+        for warning in &self.warnings {
+            out.item(warning);
+        }
+        out.text
+    }
+}
+
+fn first<T: StrictDecode + StrictDeserialize>(
+    globals: &GlobalState,
+    ty: GlobalStateType,
+) -> Option<T> {
+    let data = globals.get(&ty)?.iter().next()?;
+    T::from_strict_serialized(data.clone().into()).ok()
+}
+
+/// Ticker, name and precision of the asset, from the global state of the genesis.
+fn asset_identity(globals: &GlobalState) -> (Option<String>, Option<String>, Option<Precision>) {
+    if let Some(spec) = first::<AssetSpec>(globals, GS_NOMINAL) {
+        return (Some(spec.ticker.to_string()), Some(spec.name.to_string()), Some(spec.precision));
+    }
+    #[cfg(any(feature = "cfa", feature = "carbon"))]
+    if let Some(name) = first::<Name>(globals, crate::GS_NAME) {
+        return (None, Some(name.to_string()), first(globals, crate::GS_PRECISION));
+    }
+    (None, None, None)
+}
+
+fn issued_supply(globals: &GlobalState) -> u128 {
+    globals
+        .get(&GS_ISSUED_SUPPLY)
+        .into_iter()
+        .flat_map(|values| values.iter())
+        .filter_map(|data| Amount::from_strict_serialized(data.clone().into()).ok())
+        .map(|amount| amount.value() as u128)
+        .sum()
+}
+
+fn terminal_state<Seal: ExposedSeal>(assigns: &TypedAssigns<Seal>, no: usize) -> TerminalState {
+    match assigns {
+        TypedAssigns::Fungible(assigns) => {
+            TerminalState::Amount(Amount::from(*assigns[no].as_revealed_state()))
+        }
+        TypedAssigns::Structured(assigns) => {
+            let data = assigns[no].as_revealed_state().clone();
+            match Allocation::from_strict_serialized(data.clone().into()) {
+                Ok(allocation) => TerminalState::Allocation(allocation),
+                Err(_) => TerminalState::Data(data),
+            }
+        }
+        TypedAssigns::Declarative(_) => TerminalState::Right,
+    }
+}
+
+struct Renderer {
+    format: ReportFormat,
+    text: String,
+}
+
+impl Renderer {
+    fn title(&mut self, title: impl Display) {
+        let title = title.to_string();
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.text, "# {title}"),
+            ReportFormat::PlainText => {
+                writeln!(self.text, "{title}\n{}", "=".repeat(title.chars().count()))
+            }
+        }
+        .expect("writing to a string never fails");
+    }
+
+    fn section(&mut self, name: &str) {
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.text, "\n## {name}\n"),
+            ReportFormat::PlainText => {
+                writeln!(self.text, "\n{name}\n{}", "-".repeat(name.chars().count()))
+            }
+        }
+        .expect("writing to a string never fails");
+    }
+
+    fn field(&mut self, name: impl Display, value: impl Display) {
+        self.item(format_args!("{name}: {value}"));
+    }
+
+    fn item(&mut self, item: impl Display) {
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.text, "- {item}"),
+            ReportFormat::PlainText => writeln!(self.text, "  {item}"),
+        }
+        .expect("writing to a string never fails");
+    }
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use schemata_tools::testing::issue;
+
+    use super::*;
+    use crate::fixtures::{self, fixture, ISSUED_SUPPLY};
+    use crate::NIA_SCHEMA_ID;
+
+    #[test]
+    fn contracts() {
+        for name in fixtures::FIXTURES {
+            let report = ConsignmentReport::new(&issue(fixture(name)));
+            assert_eq!(report.contract_id, fixtures::contract_id(name), "{name}");
+            assert!(report.schema.is_some(), "{name}");
+            assert!(!report.transfer);
+            assert_eq!(report.bundles, 0);
+            assert!(report.terminals.is_empty());
+            // swap orders trade other assets and credentials aren't assets, so neither carries an
+            // asset identity of its own
+            assert_eq!(report.name.is_some(), !["swap", "credential"].contains(&name), "{name}");
+        }
+    }
+
+    #[test]
+    fn nia() {
+        let report = ConsignmentReport::new(&issue(fixture("nia")));
+        assert_eq!(report.schema_id, NIA_SCHEMA_ID);
+        assert_eq!(report.issued, Some(ISSUED_SUPPLY as u128));
+        assert_eq!(report.format_amount(1000), "0.00001000");
+
+        let markdown = report.render(ReportFormat::Markdown);
+        assert!(
+            markdown.starts_with(&format!("# Contract consignment of {}\n", report.contract_id))
+        );
+        assert!(markdown.contains("\n## Asset\n\n- Ticker: TEST\n"));
+        assert!(markdown.contains("- Issued supply: 0.00001000\n"));
+        assert!(markdown.contains("## Terminal allocations\n\n- none\n"));
+
+        let text = report.render(ReportFormat::PlainText);
+        assert!(text.contains("\nAsset\n-----\n  Ticker: TEST\n"));
+        assert!(!text.contains("\n## "));
+    }
+}
@@ -0,0 +1,394 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reissuable assets (RIA) schema.
+//!
+//! The supply of the asset can only be reissued after provable burns: each burn records the
+//! burned amount in the global state, and the reissuance validation checks that the supply
+//! reissued over the contract history never exceeds the burned one. Reissues are recorded as
+//! issued supply, like the genesis issuance.
+//!
+//! Only the burns known to the validator count, so a reissue is accepted by a receiver only if
+//! the burns it relies on are part of the validated contract history.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, NIA_TRANSFER, RIA_BURN, RIA_REISSUE};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_BURNED_SUPPLY,
+    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS, OS_ASSET, OS_REISSUE, TS_BURN, TS_REISSUE, TS_TRANSFER,
+};
+
+pub const RIA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x7a, 0xa8, 0x7b, 0x02, 0x21, 0xf4, 0xdf, 0x4e, 0x05, 0xe7, 0x82, 0xf4, 0xe4, 0x8b, 0xed, 0x8d,
+    0xa4, 0x82, 0xd4, 0x54, 0xa4, 0xac, 0xd6, 0x4b, 0xea, 0x43, 0xa9, 0x3f, 0x58, 0x3c, 0x6b, 0xdb,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn ria_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    RIA_BURN.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    RIA_REISSUE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("ReissuableAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_BURNED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("burnedSupply"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_REISSUE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("reissueRight"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_REISSUE => Occurrences::NoneOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_REISSUE => Occurrences::NoneOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_REISSUE => Occurrences::NoneOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_BURN => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_BURNED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                    },
+                    assignments: none!(),
+                    validator: Some(RIA_BURN.lib_site())
+                },
+                name: fname!("burn"),
+            },
+            TS_REISSUE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_ISSUED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_REISSUE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_REISSUE => Occurrences::NoneOrMore
+                    },
+                    validator: Some(RIA_REISSUE.lib_site())
+                },
+                name: fname!("reissue"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn ria_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, NIA_TRANSFER, RIA_BURN, RIA_REISSUE])
+}
+
+#[derive(Default)]
+pub struct ReissuableAsset;
+
+impl IssuerWrapper for ReissuableAsset {
+    type Wrapper<S: ContractStateRead> = RiaWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(ria_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(ria_scripts).clone()
+    }
+}
+
+impl ReissuableAsset {
+    /// First revision of the RIA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "ReissuableAsset",
+        schema_id: RIA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fungible asset whose supply can be reissued by the holders of reissue \
+                        rights up to the supply burned over the contract history.",
+    };
+}
+
+impl IssuerInfo for ReissuableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for ReissuableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct RiaWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for RiaWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for RiaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<ReissuableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> RiaWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the RIA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<ReissuableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the RIA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the supply issued by the genesis and all reissues.
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts issued by each reissue, from the latest one, followed by the amount
+    /// issued by the genesis.
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    /// Returns the supply reissued after the genesis.
+    pub fn reissued_supply(&self) -> Amount { or_panic(self.try_reissued_supply()) }
+
+    pub fn try_reissued_supply(&self) -> Result<Amount, Error> {
+        let mut amounts = self.try_issuance_amounts()?;
+        // The genesis issuance is the oldest entry
+        amounts.pop();
+        Ok(amounts.into_iter().sum())
+    }
+
+    /// Returns the supply burned over the contract history.
+    pub fn burned_supply(&self) -> Amount { or_panic(self.try_burned_supply()) }
+
+    pub fn try_burned_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_BURNED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts burned by each burn, from the latest one.
+    pub fn burn_amounts(&self) -> Vec<Amount> { or_panic(self.try_burn_amounts()) }
+
+    pub fn try_burn_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_BURNED_SUPPLY)?.collect()
+    }
+
+    /// Returns the supply which can still be reissued, i.e. the burned supply not reissued yet.
+    pub fn reissue_allowance(&self) -> Amount { or_panic(self.try_reissue_allowance()) }
+
+    pub fn try_reissue_allowance(&self) -> Result<Amount, Error> {
+        let burned = self.try_burned_supply()?.value();
+        let reissued = self.try_reissued_supply()?.value();
+        Ok(Amount::from(burned.saturating_sub(reissued)))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn reissue_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_reissue_rights(filter))
+    }
+
+    pub fn try_reissue_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REISSUE, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for RiaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { RiaWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        RiaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        RiaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        RiaWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::*;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = ria_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(RIA_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<ReissuableAsset>(fixtures::contract_id("ria"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.issuance_amounts(), vec![Amount::from(fixtures::ISSUED_SUPPLY)]);
+        assert_eq!(wrapper.reissued_supply(), Amount::ZERO);
+        assert_eq!(wrapper.burned_supply(), Amount::ZERO);
+        assert_eq!(wrapper.reissue_allowance(), Amount::ZERO);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .reissue_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+}
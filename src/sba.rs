@@ -0,0 +1,460 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Soulbound assets (SBA) schema.
+//!
+//! Soulbound assets are non-transferable credentials or badges: the genesis allocates them to
+//! their holders, who can only burn them, while transfers can't assign them to anyone. A burn
+//! records the burned supply in the global state, checked against the burned allocations. The
+//! issuer can revoke a credential by spending a revoke right and recording the UTXO holding the
+//! credential in the global state, one UTXO per revocation; the credential stays allocated, but is
+//! no longer valid.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::{Amount, GlobalDetails, Outpoint, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, RIA_BURN, SBA_REVOKE, SBA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_BURNED_SUPPLY, GS_ISSUED_SUPPLY,
+    GS_NOMINAL, GS_REVOCATIONS, GS_TERMS, OS_ASSET, OS_REVOKE, TS_BURN, TS_REVOKE, TS_TRANSFER,
+};
+
+pub const SBA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x82, 0x70, 0xf1, 0x16, 0xbe, 0x9c, 0x20, 0xd3, 0x34, 0xc6, 0x5a, 0x1a, 0xc6, 0x51, 0xca, 0x14,
+    0x6b, 0x8f, 0x13, 0x89, 0x75, 0xe7, 0x94, 0x2c, 0xe6, 0xd4, 0xad, 0xb0, 0xa6, 0xa3, 0xd9, 0x43,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn sba_schema() -> Schema {
+    let types = standard_types();
+
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    SBA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    SBA_REVOKE.verify(&[(0, INSTR_PUTA)]);
+    RIA_BURN.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("SoulboundAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_BURNED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("burnedSupply"),
+            },
+            GS_REVOCATIONS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.Outpoint")),
+                name: fname!("revocations"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_REVOKE => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("revokeRight"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_REVOKE => Occurrences::NoneOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_REVOKE => Occurrences::NoneOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_REVOKE => Occurrences::NoneOrMore
+                    },
+                    validator: Some(SBA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_BURN => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_BURNED_SUPPLY => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: none!(),
+                    validator: Some(RIA_BURN.lib_site())
+                },
+                name: fname!("burn"),
+            },
+            TS_REVOKE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_REVOCATIONS => Occurrences::OnceOrMore
+                    },
+                    inputs: tiny_bmap! {
+                        OS_REVOKE => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_REVOKE => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(SBA_REVOKE.lib_site())
+                },
+                name: fname!("revoke"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn sba_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[NIA_GENESIS, SBA_TRANSFER, SBA_REVOKE, RIA_BURN])
+}
+
+#[derive(Default)]
+pub struct SoulboundAsset;
+
+impl IssuerWrapper for SoulboundAsset {
+    type Wrapper<S: ContractStateRead> = SbaWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(sba_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(sba_scripts).clone()
+    }
+}
+
+impl SoulboundAsset {
+    /// First revision of the SBA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "SoulboundAsset",
+        schema_id: SBA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Non-transferable credential which its holders can only burn and the \
+                        issuer can revoke.",
+    };
+}
+
+impl IssuerInfo for SoulboundAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for SoulboundAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+/// Status of a UTXO with respect to the credentials of a soulbound asset contract.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+pub enum HolderStatus {
+    /// The UTXO holds a credential which is not revoked.
+    #[display("active")]
+    Active,
+    /// The credential held by the UTXO is revoked.
+    #[display("revoked")]
+    Revoked,
+    /// The UTXO holds no credential.
+    #[display("not a holder")]
+    NotHolder,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct SbaWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for SbaWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for SbaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<SoulboundAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> SbaWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the SBA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<SoulboundAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the SBA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the supply burned over the contract history.
+    pub fn total_burned(&self) -> Amount { or_panic(self.try_total_burned()) }
+
+    pub fn try_total_burned(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_BURNED_SUPPLY)?.sum()
+    }
+
+    /// Returns the credentials, including the revoked ones.
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the UTXOs whose credentials are revoked, in the order they were revoked.
+    pub fn revocations(&self) -> Vec<Outpoint> { or_panic(self.try_revocations()) }
+
+    pub fn try_revocations(&self) -> Result<Vec<Outpoint>, Error> {
+        let mut revocations = globals(&self.0, GS_REVOCATIONS)?.collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        revocations.reverse();
+        Ok(revocations)
+    }
+
+    /// Checks whether the credential held by `outpoint` is revoked.
+    pub fn is_revoked(&self, outpoint: Outpoint) -> bool { or_panic(self.try_is_revoked(outpoint)) }
+
+    pub fn try_is_revoked(&self, outpoint: Outpoint) -> Result<bool, Error> {
+        Ok(self.try_revocations()?.contains(&outpoint))
+    }
+
+    /// Returns the status of `outpoint` as a credential holder.
+    pub fn holder_status(&self, outpoint: Outpoint) -> HolderStatus {
+        or_panic(self.try_holder_status(outpoint))
+    }
+
+    pub fn try_holder_status(&self, outpoint: Outpoint) -> Result<HolderStatus, Error> {
+        if self.try_allocations(vec![outpoint])?.next().is_none() {
+            return Ok(HolderStatus::NotHolder);
+        }
+        Ok(if self.try_is_revoked(outpoint)? {
+            HolderStatus::Revoked
+        } else {
+            HolderStatus::Active
+        })
+    }
+
+    pub fn revoke_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_revoke_rights(filter))
+    }
+
+    pub fn try_revoke_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REVOKE, filter)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroU32;
+
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::vm::{WitnessOrd, WitnessPos};
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = sba_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(SBA_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<SoulboundAsset>(fixtures::contract_id("sba"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.revocations(), vec![]);
+        assert_eq!(wrapper.total_burned(), Amount::ZERO);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let holder = Outpoint::new(testing::txid(), 1);
+        assert_eq!(wrapper.holder_status(holder), HolderStatus::Active);
+        assert!(!wrapper.is_revoked(holder));
+        assert_eq!(
+            wrapper.holder_status(Outpoint::new(testing::txid(), 2)),
+            HolderStatus::NotHolder
+        );
+        let rights = wrapper
+            .revoke_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Revocation of a soulbound credential.
+    //
+    // The issuer spends the revoke right in a mined witness TX to revoke the credential held by the
+    // genesis UTXO. The `SbaWrapper` must then report the credential as
+    // revoked, while the issuer keeps the revoke right.
+
+    fn sba(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::asset::<SoulboundAsset>(issued, allocated)
+            .add_rights("revokeRight", testing::seal(2))
+            .unwrap()
+    }
+
+    #[test]
+    fn revoke_credential() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(sba(1, 1));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let holder = Outpoint::new(testing::txid(), 1);
+        let wrapper = stock
+            .contract_wrapper::<SoulboundAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.holder_status(holder), HolderStatus::Active);
+
+        let transition = stock
+            .transition_builder(contract_id, "revoke")
+            .unwrap()
+            .add_input(Opout::new(genesis_id, OS_REVOKE, 0), AllocatedState::Void)
+            .unwrap()
+            .add_global_state("revocations", holder)
+            .unwrap()
+            .add_rights("revokeRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 2)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        let pos = WitnessPos::bitcoin(NonZeroU32::new(100).unwrap(), testing::CREATED_AT + 60_000)
+            .unwrap();
+        resolver.add_witness(witness, WitnessOrd::Mined(pos));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<SoulboundAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.revocations(), vec![holder]);
+        assert!(wrapper.is_revoked(holder));
+        assert_eq!(wrapper.holder_status(holder), HolderStatus::Revoked);
+        let rights = wrapper
+            .revoke_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(rights.contains(&OutputSeal::new(Outpoint::new(witness_id, 1))), "{rights:?}");
+    }
+}
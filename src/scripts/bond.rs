@@ -0,0 +1,101 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the bond schema.
+//!
+//! A redeem commits to the absolute lock time of its witness transaction, which must be a block
+//! height keeping the witness from being mined before the maturity height, like the transfers of
+//! timelocked assets, and must record the redeemed supply. The lock time of the witness itself is
+//! not checked, see [`crate::height`].
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_BOND_REDEEM_OFFSET: u16 = 0;
+
+pub(super) const BOND_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0x0a, 0x08,
+        0x01, 0x39, 0x20, 0x01, 0x0b, 0x02, 0x04, 0x00, 0xc9, 0xe2, 0x07, 0x10, 0x39, 0x21, 0x02,
+        0x0b, 0x1a, 0x08, 0x00, 0x18, 0x09, 0x43, 0x01, 0x0b, 0x1a, 0x0c, 0x00, 0x20, 0x08, 0x43,
+        0x18, 0x19, 0x42, 0x1f, 0x01, 0x0b, 0x00, 0x10, 0x00, 0xc8, 0xdf, 0x07, 0x01, 0x39, 0x30,
+        0x00, 0xd2, 0xa0, 0x0f, 0x01, 0x07,
+    ],
+    data: &[
+        0x2b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x65, 0xcd, 0x1d, 0x01, 0x00, 0x00,
+        0x00, 0x2a,
+    ],
+    id: [
+        0x7b, 0xaa, 0x1a, 0xca, 0x41, 0x9a, 0xbc, 0xea, 0xd9, 0x7a, 0xf9, 0x68, 0xff, 0xbc, 0xe6,
+        0x2a, 0x30, 0xdf, 0x3e, 0x78, 0xd5, 0x9d, 0x37, 0xf8, 0x3b, 0xd3, 0xe5, 0x80, 0xab, 0x82,
+        0xb6, 0x4f,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_bond_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_REDEEM_LOCKED, ERRNO_REDEEM_MISMATCH};
+    use crate::{GS_LOCK_TIME, GS_MATURITY, GS_REDEEMED_SUPPLY, OS_ASSET};
+
+    crate::asm::assemble("bond", |_| {
+        vec![("FN_BOND_REDEEM_OFFSET", rgbasm! {
+            // Check that the witness can't be mined before the maturity height
+            put     a8[0],ERRNO_REDEEM_LOCKED;  // set errno
+            put     a8[1],0;
+            put     a16[0],0;
+            ldg     GS_LOCK_TIME,a8[1],s16[0];  // read the lock time of the redeem
+            extr    s16[0],a32[1],a16[0];  // and store it in a32[1]
+            put     a32[0],0;  // depth of the contract global state entry to read
+            ldc     GS_MATURITY,a32[0],s16[1];  // read the maturity height
+            extr    s16[1],a32[2],a16[0];  // and store it in a32[2]
+            put     a32[3],500000000;  // lock times from it on are timestamps
+            lt.u    a32[1],a32[3];
+            test;
+            put     a32[3],1;
+            add.uc  a32[1],a32[3];  // first height the witness can be mined at, into a32[3]
+            lt.u    a32[3],a32[2];  // first height < maturity height
+            inv     st0;  // first height >= maturity height
+            test;
+
+            // Check reported redeemed supply equals sum of asset allocations in input
+            put     a8[0],ERRNO_REDEEM_MISMATCH;  // set errno
+            ldg     GS_REDEEMED_SUPPLY,a8[1],s16[0];  // read redeemed supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sps     OS_ASSET;  // check sum of asset allocations in input equals a64[0]
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_bond_lib();
+        assembled.verify_offsets(&[("FN_BOND_REDEEM_OFFSET", FN_BOND_REDEEM_OFFSET)]);
+        BOND_LIB.verify("BOND_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,114 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the carbon credit schema.
+//!
+//! A retirement destroys the credits of its inputs, except for those assigned back as change: the
+//! asset inputs must equal the retired supply recorded in the global state and the change outputs
+//! together.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_CARBON_RETIRE_OFFSET: u16 = 0;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_CARBON_CHANGE_LOOP_OFFSET: u16 = 26;
+#[cfg(test)]
+const FN_CARBON_CHANGE_NEXT_OFFSET: u16 = 37;
+
+pub(super) const CARBON_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0xf5, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xc1, 0xa0, 0x0f, 0x01, 0x02, 0x25, 0x00, 0xc6, 0xa0, 0x0f, 0x30,
+        0x20, 0x18, 0x60, 0x01, 0x24, 0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0x1a, 0x00, 0x1f, 0xd2,
+        0xa0, 0x0f, 0x01, 0x07,
+    ],
+    data: &[0x28, 0x00, 0x00, 0x00],
+    id: [
+        0x64, 0xdb, 0xd5, 0xd6, 0xa4, 0x78, 0x67, 0x77, 0xad, 0x2e, 0x9c, 0xf5, 0xf3, 0xc9, 0x36,
+        0xc1, 0xbd, 0x69, 0x39, 0xed, 0x13, 0xe4, 0xa5, 0xb6, 0x4e, 0xec, 0x38, 0x84, 0xe5, 0x1f,
+        0xa5, 0xe3,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_carbon_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ldf, ERRNO_BURN_MISMATCH};
+    use crate::{GS_RETIRED_SUPPLY, OS_ASSET};
+
+    crate::asm::assemble("carbon credit", |labels| {
+        let change_loop = labels.offset("FN_CARBON_CHANGE_LOOP_OFFSET");
+        let change_next = labels.offset("FN_CARBON_CHANGE_NEXT_OFFSET");
+        vec![
+            ("FN_CARBON_RETIRE_OFFSET", rgbasm! {
+                // Read the retired supply into a64[0]
+                put     a8[0],ERRNO_BURN_MISMATCH;  // set errno
+                put     a8[1],0;
+                put     a16[0],0;
+                ldg     GS_RETIRED_SUPPLY,a8[1],s16[0];  // read retired supply global state
+                extr    s16[0],a64[0],a16[0];
+
+                // Add the change allocations in output to a64[0]
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     change_next;
+            }),
+            ("FN_CARBON_CHANGE_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[0];  // add it to the sum in a64[0]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_CARBON_CHANGE_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     change_loop;
+
+                // Check sum of asset allocations in input equals a64[0]
+                inv     st0;  // the loop exits with st0 false, which `sps` doesn't reset
+                sps     OS_ASSET;
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_carbon_lib();
+        assembled.verify_offsets(&[
+            ("FN_CARBON_RETIRE_OFFSET", FN_CARBON_RETIRE_OFFSET),
+            ("FN_CARBON_CHANGE_LOOP_OFFSET", FN_CARBON_CHANGE_LOOP_OFFSET),
+            ("FN_CARBON_CHANGE_NEXT_OFFSET", FN_CARBON_CHANGE_NEXT_OFFSET),
+        ]);
+        CARBON_LIB.verify("CARBON_LIB", assembled.lib);
+    }
+}
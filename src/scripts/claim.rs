@@ -0,0 +1,192 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the claimable asset schema.
+//!
+//! A claim requires the asset inputs to equal the claimed and change outputs together. A
+//! collection turns the claimed inputs into asset outputs of the same sum and must be signed by
+//! the issuer key committed in the genesis, so that only the issuer can spend the claims.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_CLAIM_LOCK_OFFSET: u16 = 0;
+pub(super) const FN_CLAIM_COLLECT_OFFSET: u16 = 80;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_CLAIM_CLAIMED_LOOP_OFFSET: u16 = 19;
+#[cfg(test)]
+const FN_CLAIM_CLAIMED_NEXT_OFFSET: u16 = 30;
+#[cfg(test)]
+const FN_CLAIM_CHANGE_LOOP_OFFSET: u16 = 51;
+#[cfg(test)]
+const FN_CLAIM_CHANGE_NEXT_OFFSET: u16 = 62;
+#[cfg(test)]
+const FN_CLAIM_COLLECTED_LOOP_OFFSET: u16 = 99;
+#[cfg(test)]
+const FN_CLAIM_COLLECTED_NEXT_OFFSET: u16 = 110;
+
+pub(super) const CLAIM_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x0b, 0x01, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc1, 0xb9, 0x0f,
+        0x01, 0x02, 0x1e, 0x00, 0xc6, 0xb9, 0x0f, 0x30, 0x20, 0x18, 0x61, 0x01, 0x24, 0x01, 0x01,
+        0x18, 0x01, 0x21, 0x03, 0x13, 0x00, 0x0b, 0x13, 0x00, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc1,
+        0xa0, 0x0f, 0x01, 0x02, 0x3e, 0x00, 0xc6, 0xa0, 0x0f, 0x30, 0x20, 0x18, 0x62, 0x01, 0x24,
+        0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0x33, 0x00, 0x11, 0x13, 0x03, 0x20, 0x08, 0x60, 0x01,
+        0xd2, 0xa0, 0x0f, 0x01, 0x07, 0x0b, 0x00, 0x00, 0x00, 0x0b, 0x03, 0x00, 0x00, 0x0b, 0x01,
+        0x00, 0x00, 0xc1, 0xa0, 0x0f, 0x01, 0x02, 0x6e, 0x00, 0xc6, 0xa0, 0x0f, 0x30, 0x20, 0x18,
+        0x60, 0x01, 0x24, 0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0x63, 0x00, 0x1f, 0xd2, 0xb9, 0x0f,
+        0x01, 0x0b, 0x00, 0x09, 0x00, 0x0b, 0x02, 0x00, 0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00,
+        0x0a, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x15],
+    id: [
+        0x5a, 0x65, 0x35, 0xe8, 0x27, 0x56, 0xd1, 0xd2, 0xc6, 0x0d, 0xe1, 0x22, 0xea, 0xa1, 0xd9,
+        0x5e, 0x3b, 0x0a, 0xd9, 0xb0, 0xb9, 0x4e, 0xa1, 0x34, 0x3b, 0x58, 0xb4, 0xa1, 0xfe, 0xdb,
+        0xb3, 0x3b,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_claim_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ldf, ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY, ERRNO_NON_EQUAL_IN_OUT};
+    use crate::{GS_PUBKEY, OS_ASSET, OS_CLAIM};
+
+    crate::asm::assemble("claimable asset", |labels| {
+        let claimed_loop = labels.offset("FN_CLAIM_CLAIMED_LOOP_OFFSET");
+        let claimed_next = labels.offset("FN_CLAIM_CLAIMED_NEXT_OFFSET");
+        let change_loop = labels.offset("FN_CLAIM_CHANGE_LOOP_OFFSET");
+        let change_next = labels.offset("FN_CLAIM_CHANGE_NEXT_OFFSET");
+        let collected_loop = labels.offset("FN_CLAIM_COLLECTED_LOOP_OFFSET");
+        let collected_next = labels.offset("FN_CLAIM_COLLECTED_NEXT_OFFSET");
+        vec![
+            ("FN_CLAIM_LOCK_OFFSET", rgbasm! {
+                // Sum the claimed allocations in output into a64[1]
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a64[1],0;
+                put     a16[0],0;  // index of the output allocation to read
+                cns     OS_CLAIM,a16[1];  // count claimed allocations in output
+                jmp     claimed_next;
+            }),
+            ("FN_CLAIM_CLAIMED_LOOP_OFFSET", {
+                // Read a claimed allocation into a64[3]
+                let mut code = vec![ldf(OS_CLAIM, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[1];  // add it to the sum in a64[1]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_CLAIM_CLAIMED_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining claimed allocations
+                jif     claimed_loop;
+
+                // Sum the change allocations in output into a64[2]
+                put     a64[2],0;
+                put     a16[0],0;
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     change_next;
+            }),
+            ("FN_CLAIM_CHANGE_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[2];  // add it to the sum in a64[2]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_CLAIM_CHANGE_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     change_loop;
+
+                // Check sum of asset allocations in input equals the sum of all outputs
+                cpy     a64[2],a64[0];
+                add.uc  a64[1],a64[0];  // claimed and change outputs, in a64[0]
+                test;  // fails in case of an overflow
+                sps     OS_ASSET;  // check sum of asset allocations in input equals a64[0]
+                test;
+                ret;
+            }),
+            ("FN_CLAIM_COLLECT_OFFSET", rgbasm! {
+                // Sum the asset allocations in output into a64[0]
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a64[0],0;
+                put     a16[0],0;
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     collected_next;
+            }),
+            ("FN_CLAIM_COLLECTED_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[0];  // add it to the sum in a64[0]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_CLAIM_COLLECTED_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     collected_loop;
+
+                // Check sum of claimed allocations in input equals a64[0]
+                inv     st0;  // the loop exits with st0 false, which `sps` doesn't reset
+                sps     OS_CLAIM;
+                test;
+
+                // Check transition signature against the issuer key
+                put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+                put     a32[0],0;
+                ldc     GS_PUBKEY,a32[0],s16[0];  // read issuer key contract global state
+                put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+                vts     s16[0];  // verify signature
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_claim_lib();
+        assembled.verify_offsets(&[
+            ("FN_CLAIM_LOCK_OFFSET", FN_CLAIM_LOCK_OFFSET),
+            ("FN_CLAIM_CLAIMED_LOOP_OFFSET", FN_CLAIM_CLAIMED_LOOP_OFFSET),
+            ("FN_CLAIM_CLAIMED_NEXT_OFFSET", FN_CLAIM_CLAIMED_NEXT_OFFSET),
+            ("FN_CLAIM_CHANGE_LOOP_OFFSET", FN_CLAIM_CHANGE_LOOP_OFFSET),
+            ("FN_CLAIM_CHANGE_NEXT_OFFSET", FN_CLAIM_CHANGE_NEXT_OFFSET),
+            ("FN_CLAIM_COLLECT_OFFSET", FN_CLAIM_COLLECT_OFFSET),
+            ("FN_CLAIM_COLLECTED_LOOP_OFFSET", FN_CLAIM_COLLECTED_LOOP_OFFSET),
+            ("FN_CLAIM_COLLECTED_NEXT_OFFSET", FN_CLAIM_COLLECTED_NEXT_OFFSET),
+        ]);
+        CLAIM_LIB.verify("CLAIM_LIB", assembled.lib);
+    }
+}
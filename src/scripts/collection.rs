@@ -0,0 +1,224 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the unique digital collection schema.
+//!
+//! Both genesis and transfer validation match a list of token indexes (the collection tokens or
+//! the inputs) against the outputs, requiring each index to be allocated by exactly one output
+//! owning the whole token and the outputs to allocate no other index.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_UDC_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_UDC_GENESIS_OFFSET: u16 = 59;
+pub(super) const FN_UDC_MATCH_OFFSET: u16 = 148;
+// Loop heads and branches, only targeted by jumps within the library
+#[cfg(test)]
+const FN_UDC_TRANSFER_LOOP_OFFSET: u16 = 40;
+#[cfg(test)]
+const FN_UDC_TRANSFER_NEXT_OFFSET: u16 = 50;
+#[cfg(test)]
+const FN_UDC_GENESIS_NEXT_OFFSET: u16 = 113;
+#[cfg(test)]
+const FN_UDC_END_OFFSET: u16 = 122;
+#[cfg(test)]
+const FN_UDC_GENESIS_LOOP_OFFSET: u16 = 130;
+#[cfg(test)]
+const FN_UDC_MATCH_LOOP_OFFSET: u16 = 156;
+#[cfg(test)]
+const FN_UDC_MATCH_NEXT_OFFSET: u16 = 169;
+#[cfg(test)]
+const FN_UDC_MATCHED_OFFSET: u16 = 197;
+
+pub(super) const UDC_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x01, 0x00, 0x00, 0x0b, 0x11, 0x02, 0x00, 0x0b, 0x0b, 0x04, 0x00, 0x0b, 0x31, 0x04,
+        0x00, 0x0b, 0x10, 0x00, 0x00, 0x0b, 0x28, 0x04, 0x00, 0x0b, 0x30, 0x04, 0x00, 0xc1, 0xa0,
+        0x0f, 0x03, 0xc0, 0xa0, 0x0f, 0x07, 0x0b, 0x21, 0x00, 0x00, 0xc4, 0xa0, 0x0f, 0x04, 0x39,
+        0x20, 0x00, 0x02, 0x94, 0x00, 0x18, 0x21, 0x27, 0x03, 0x28, 0x00, 0x02, 0x7a, 0x00, 0x0b,
+        0x01, 0x00, 0x00, 0x0b, 0x11, 0x02, 0x00, 0x0b, 0x0b, 0x04, 0x00, 0x0b, 0x31, 0x04, 0x00,
+        0x0b, 0x10, 0x04, 0x00, 0x0b, 0x28, 0x00, 0x00, 0x0b, 0x30, 0x04, 0x00, 0xc1, 0xa0, 0x0f,
+        0x03, 0xc2, 0x36, 0x08, 0x03, 0x0b, 0x21, 0x00, 0x00, 0x0b, 0x08, 0x00, 0x00, 0xc8, 0x36,
+        0x08, 0x01, 0x39, 0x20, 0x00, 0x02, 0x94, 0x00, 0x24, 0x08, 0x01, 0x18, 0x09, 0x03, 0x03,
+        0x82, 0x00, 0x11, 0x10, 0x00, 0x19, 0x21, 0x23, 0x01, 0x07, 0x11, 0x02, 0x12, 0xc8, 0x36,
+        0x08, 0x01, 0x39, 0x20, 0x00, 0x0b, 0x00, 0x0c, 0x00, 0x18, 0x11, 0x40, 0x01, 0x0b, 0x09,
+        0x00, 0x00, 0x0b, 0x29, 0x00, 0x00, 0xc5, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01,
+        0x41, 0x03, 0xc5, 0x00, 0x24, 0x09, 0x01, 0x18, 0x09, 0x23, 0x03, 0x9c, 0x00, 0x11, 0x10,
+        0x00, 0x19, 0x29, 0x26, 0x01, 0x24, 0x21, 0x01, 0x19, 0x29, 0x06, 0x03, 0x32, 0x00, 0x02,
+        0x71, 0x00, 0x24, 0x29, 0x01, 0x0b, 0x00, 0x0d, 0x00, 0x39, 0x31, 0x20, 0x19, 0x01, 0x61,
+        0x01, 0x02, 0xa9, 0x00,
+    ],
+    data: &[0x00, 0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b, 0x0a],
+    id: [
+        0x56, 0x3d, 0x7c, 0xec, 0xea, 0xad, 0x62, 0x4a, 0xf2, 0x89, 0x0b, 0x07, 0xf6, 0x14, 0xe6,
+        0x85, 0xc8, 0x15, 0x2a, 0x4e, 0xef, 0x22, 0x71, 0x3d, 0xed, 0x5a, 0x8c, 0xa3, 0x27, 0x84,
+        0x2f, 0xd3,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_udc_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, ERRNO_NON_FRACTIONAL, ERRNO_UNSORTED_TOKENS,
+    };
+    use crate::{GS_TOKENS, OS_ASSET};
+
+    crate::asm::assemble("unique digital collection", |labels| {
+        let transfer_loop = labels.offset("FN_UDC_TRANSFER_LOOP_OFFSET");
+        let transfer_next = labels.offset("FN_UDC_TRANSFER_NEXT_OFFSET");
+        let genesis_loop = labels.offset("FN_UDC_GENESIS_LOOP_OFFSET");
+        let genesis_next = labels.offset("FN_UDC_GENESIS_NEXT_OFFSET");
+        let end = labels.offset("FN_UDC_END_OFFSET");
+        let matching = labels.offset("FN_UDC_MATCH_OFFSET");
+        let match_loop = labels.offset("FN_UDC_MATCH_LOOP_OFFSET");
+        let match_next = labels.offset("FN_UDC_MATCH_NEXT_OFFSET");
+        let matched = labels.offset("FN_UDC_MATCHED_OFFSET");
+        vec![
+            ("FN_UDC_TRANSFER_OFFSET", rgbasm! {
+                // SUBROUTINE 2: Transfer validation, matching the inputs against the outputs
+                put     a16[0],0;  // offset of the token index in an allocation
+                put     a16[2],4;  // offset of the fraction in an allocation
+                put     a64[1],1;  // fraction of a whole token
+                put     a16[6],1;  // number of outputs each index must be allocated by
+                put     a8[2],ERRNO_NON_EQUAL_IN_OUT;  // errno for a count mismatch
+                put     a8[5],1;  // return into the transfer loop after matching
+                put     a8[6],1;
+                cns     OS_ASSET,a16[3];
+                cnp     OS_ASSET,a16[7];
+                // Number of matched inputs, also the index of the next input
+                put     a16[4],0;
+            }),
+            ("FN_UDC_TRANSFER_LOOP_OFFSET", rgbasm! {
+                // Read the token index of the input into a32[0] and match it
+                ldp     OS_ASSET,a16[4],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                jmp     matching;
+            }),
+            ("FN_UDC_TRANSFER_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[4],a16[7];  // loop over the remaining inputs
+                jif     transfer_loop;
+                jmp     end;
+            }),
+            ("FN_UDC_GENESIS_OFFSET", rgbasm! {
+                // SUBROUTINE 1: Genesis validation, matching the tokens against the outputs
+                put     a16[0],0;  // offset of the token index in an allocation
+                put     a16[2],4;  // offset of the fraction in an allocation
+                put     a64[1],1;  // fraction of a whole token
+                put     a16[6],1;  // number of outputs each index must be allocated by
+                put     a8[2],ERRNO_ISSUED_MISMATCH;  // errno for a count mismatch
+                put     a8[5],0;  // return into the genesis loop after matching
+                put     a8[6],1;
+                cns     OS_ASSET,a16[3];
+                cng     GS_TOKENS,a8[3];
+                // Number of matched tokens
+                put     a16[4],0;
+                // Read the index of the first token into a32[0] and match it
+                put     a8[1],0;
+                ldg     GS_TOKENS,a8[1],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                jmp     matching;
+            }),
+            ("FN_UDC_GENESIS_NEXT_OFFSET", rgbasm! {
+                inc     a8[1];
+                lt.u    a8[1],a8[3];  // loop over the remaining tokens
+                jif     genesis_loop;
+            }),
+            ("FN_UDC_END_OFFSET", rgbasm! {
+                // All outputs must be matched, so none allocates an unknown index
+                cpy     a8[2],a8[0];  // set errno
+                eq.n    a16[4],a16[3];
+                test;
+                ret;
+            }),
+            ("FN_UDC_GENESIS_LOOP_OFFSET", rgbasm! {
+                // Read the index of the next token into a32[0], keeping the previous one in a32[2]
+                cpy     a32[0],a32[2];
+                ldg     GS_TOKENS,a8[1],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                // Token indexes must strictly increase, so that they are unique
+                put     a8[0],ERRNO_UNSORTED_TOKENS;
+                lt.u    a32[2],a32[0];
+                test;
+            }),
+            ("FN_UDC_MATCH_OFFSET", rgbasm! {
+                // SUBROUTINE 3: Shared code, counting into a16[5] the outputs allocating the
+                // token index in a32[0] and returning into the loop selected by a8[5]
+                put     a16[1],0;
+                put     a16[5],0;
+            }),
+            ("FN_UDC_MATCH_LOOP_OFFSET", rgbasm! {
+                lds     OS_ASSET,a16[1],s16[1];  // read the output allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];
+                jif     matched;
+            }),
+            ("FN_UDC_MATCH_NEXT_OFFSET", rgbasm! {
+                inc     a16[1];
+                lt.u    a16[1],a16[3];  // loop over the remaining outputs
+                jif     match_loop;
+                // Check that exactly one output allocates the token
+                cpy     a8[2],a8[0];  // set errno
+                eq.n    a16[5],a16[6];
+                test;
+                inc     a16[4];
+                eq.n    a8[5],a8[6];
+                jif     transfer_next;
+                jmp     genesis_next;
+            }),
+            ("FN_UDC_MATCHED_OFFSET", rgbasm! {
+                inc     a16[5];
+                // Check that the output owns the whole token
+                put     a8[0],ERRNO_NON_FRACTIONAL;  // set errno
+                extr    s16[1],a64[0],a16[2];
+                eq.n    a64[0],a64[1];
+                test;
+                jmp     match_next;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_udc_lib();
+        assembled.verify_offsets(&[
+            ("FN_UDC_TRANSFER_OFFSET", FN_UDC_TRANSFER_OFFSET),
+            ("FN_UDC_TRANSFER_LOOP_OFFSET", FN_UDC_TRANSFER_LOOP_OFFSET),
+            ("FN_UDC_TRANSFER_NEXT_OFFSET", FN_UDC_TRANSFER_NEXT_OFFSET),
+            ("FN_UDC_GENESIS_OFFSET", FN_UDC_GENESIS_OFFSET),
+            ("FN_UDC_GENESIS_NEXT_OFFSET", FN_UDC_GENESIS_NEXT_OFFSET),
+            ("FN_UDC_END_OFFSET", FN_UDC_END_OFFSET),
+            ("FN_UDC_GENESIS_LOOP_OFFSET", FN_UDC_GENESIS_LOOP_OFFSET),
+            ("FN_UDC_MATCH_OFFSET", FN_UDC_MATCH_OFFSET),
+            ("FN_UDC_MATCH_LOOP_OFFSET", FN_UDC_MATCH_LOOP_OFFSET),
+            ("FN_UDC_MATCH_NEXT_OFFSET", FN_UDC_MATCH_NEXT_OFFSET),
+            ("FN_UDC_MATCHED_OFFSET", FN_UDC_MATCHED_OFFSET),
+        ]);
+        UDC_LIB.verify("UDC_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,119 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the verifiable credential schema.
+//!
+//! An attestation must be signed by the issuer, and is rejected once the issuer has revoked the
+//! credential. The contract global state doesn't contain the operation being validated, so any
+//! revocation reason it holds comes from an earlier revocation.
+//!
+//! The signature check comes last, since code following `vts` isn't byte-aligned.
+//!
+//! The genesis and the revocations have no rules beyond the schema ones, yet they need a validator:
+//! the consignment validator adds the global state of an operation to the contract state only once
+//! its validator succeeds, so without one the attestations couldn't read the issuer pubkey nor
+//! see the revocations. Their validator lives in a library of its own, which doesn't verify
+//! signatures.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_CREDENTIAL_ATTEST_OFFSET: u16 = 0;
+
+pub(super) const FN_CREDENTIAL_RECORD_OFFSET: u16 = 0;
+
+pub(super) const CREDENTIAL_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xc3, 0x08, 0x08, 0x00, 0x0b, 0x0a, 0x01, 0x00, 0x19, 0x01, 0x41,
+        0x01, 0x0b, 0x00, 0x05, 0x00, 0x0b, 0x02, 0x01, 0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00,
+        0x06, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[0x49, 0x00, 0x00, 0x00, 0x00, 0x14, 0x15],
+    id: [
+        0x06, 0x04, 0x13, 0x8a, 0xab, 0x8d, 0xc4, 0x54, 0xe7, 0xed, 0x02, 0xf7, 0x58, 0x70, 0x72,
+        0xb9, 0x27, 0xdc, 0x28, 0xd8, 0x41, 0xa7, 0xc3, 0x2a, 0x70, 0xd1, 0x76, 0xe6, 0x3f, 0x90,
+        0x4b, 0x0b,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_credential_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_CREDENTIAL_REVOKED, ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY};
+    use crate::{GS_PUBKEY, GS_REVOCATION_REASON};
+
+    crate::asm::assemble("verifiable credential", |_| {
+        vec![("FN_CREDENTIAL_ATTEST_OFFSET", rgbasm! {
+            // Check that the credential isn't revoked
+            put     a8[0],ERRNO_CREDENTIAL_REVOKED;  // set errno
+            cnc     GS_REVOCATION_REASON,a16[0];  // count revocations into a32[0]
+            put     a32[1],0;
+            eq.n    a32[0],a32[1];
+            test;
+
+            // Check transition signature
+            put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+            put     a32[0],0;
+            ldc     GS_PUBKEY,a32[0],s16[0];  // read the issuer pubkey
+            put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+            vts     s16[0];  // verify signature
+            test;
+            ret;
+        })]
+    })
+}
+
+pub(super) const CREDENTIAL_LIB_RECORD: PrecompiledLib = PrecompiledLib {
+    code: &[0x07],
+    data: &[],
+    id: [
+        0x32, 0xad, 0x9f, 0x4a, 0x39, 0x3c, 0x83, 0xf5, 0x08, 0xdd, 0xa2, 0x3e, 0xe1, 0x9f, 0xf4,
+        0x71, 0x73, 0x40, 0xa2, 0xc0, 0x7e, 0xa4, 0x8b, 0xa4, 0x2c, 0xf6, 0x28, 0x32, 0x28, 0x1a,
+        0x99, 0x72,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_credential_lib_record() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    crate::asm::assemble("verifiable credential record", |_| {
+        vec![("FN_CREDENTIAL_RECORD_OFFSET", rgbasm! {
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_credential_lib();
+        assembled.verify_offsets(&[("FN_CREDENTIAL_ATTEST_OFFSET", FN_CREDENTIAL_ATTEST_OFFSET)]);
+        CREDENTIAL_LIB.verify("CREDENTIAL_LIB", assembled.lib);
+
+        let record = assemble_credential_lib_record();
+        record.verify_offsets(&[("FN_CREDENTIAL_RECORD_OFFSET", FN_CREDENTIAL_RECORD_OFFSET)]);
+        CREDENTIAL_LIB_RECORD.verify("CREDENTIAL_LIB_RECORD", record.lib);
+    }
+}
@@ -0,0 +1,134 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the limited edition schema.
+//!
+//! Each mint spends a mint right and creates a single edition, whose token data is recorded in the
+//! global state. The contract global state doesn't contain the mint being validated yet, so the
+//! editions it counts are the ones minted before: the new edition must be numbered right after
+//! them, starting from 1, which keeps the editions unique and lets the cap be checked against the
+//! edition number.
+//!
+//! The genesis has no rules beyond the schema ones, yet it needs a validator: the consignment
+//! validator adds the global state of an operation to the contract state only once its validator
+//! succeeds, so without one the mints couldn't read the maximum number of editions.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_EDITION_MINT_OFFSET: u16 = 0;
+pub(super) const FN_EDITION_GENESIS_OFFSET: u16 = 92;
+
+pub(super) const EDITION_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x08, 0x01, 0x00, 0xc8, 0x36, 0x08,
+        0x01, 0x39, 0x20, 0x00, 0xc3, 0x36, 0x08, 0x01, 0x24, 0x0a, 0x01, 0x19, 0x01, 0x41, 0x01,
+        0x0b, 0x00, 0x03, 0x00, 0x0b, 0x12, 0x04, 0x00, 0xc9, 0xfc, 0x07, 0x12, 0x39, 0x31, 0x00,
+        0x12, 0x02, 0x0b, 0x18, 0x01, 0x61, 0x1f, 0x01, 0x0b, 0x00, 0x01, 0x00, 0x0b, 0x09, 0x01,
+        0x00, 0xc5, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x01, 0x0b, 0x00, 0x08,
+        0x00, 0x0b, 0x11, 0x09, 0x00, 0x39, 0x31, 0x20, 0x0b, 0x0b, 0x0b, 0x00, 0x19, 0x01, 0x61,
+        0x01, 0x07, 0x07,
+    ],
+    data: &[
+        0x0e, 0x00, 0x00, 0x0f, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ],
+    id: [
+        0x57, 0xa6, 0x1a, 0x36, 0xe5, 0x83, 0x77, 0x3d, 0x4e, 0x66, 0x6b, 0xb2, 0xce, 0x30, 0xf6,
+        0x01, 0x98, 0xd2, 0x30, 0xed, 0xd4, 0x9f, 0x39, 0x5a, 0x63, 0x4c, 0xe0, 0x75, 0x17, 0x8b,
+        0xe3, 0xc4,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_edition_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_EDITION_CAP_EXCEEDED, ERRNO_EDITION_MISMATCH, ERRNO_NON_EQUAL_IN_OUT,
+        ERRNO_NON_FRACTIONAL,
+    };
+    use crate::{GS_MAX_EDITIONS, GS_TOKENS, OS_ASSET};
+
+    crate::asm::assemble("limited edition", |_| {
+        vec![
+            ("FN_EDITION_MINT_OFFSET", rgbasm! {
+                // Check that the new edition is numbered right after the minted ones
+                put     a8[0],ERRNO_EDITION_MISMATCH;  // set errno
+                put     a16[0],0;
+                put     a8[1],0;
+                ldg     GS_TOKENS,a8[1],s16[0];  // read the token data of the new edition
+                extr    s16[0],a32[0],a16[0];  // extract its token index
+                // Count the minted editions: the assembler names the destination a16[1], while the
+                // count is put into a32[1]
+                cnc     GS_TOKENS,a16[1];
+                inc     a32[1];
+                eq.n    a32[0],a32[1];
+                test;
+
+                // Check that the new edition doesn't exceed the cap
+                put     a8[0],ERRNO_EDITION_CAP_EXCEEDED;  // set errno
+                put     a32[2],0;  // depth of the contract global state entry to read
+                ldc     GS_MAX_EDITIONS,a32[2],s16[1];  // read the maximum number of editions
+                extr    s16[1],a64[0],a16[0];
+                // Widen the edition number, which follows the minted count and so never reaches the
+                // sign bit the conversion extends
+                cnv     a32[0],a64[1];
+                lt.u    a64[0],a64[1];  // cap < edition
+                inv     st0;  // cap >= edition
+                test;
+
+                // Check that the output allocates the whole new edition
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a16[1],0;
+                lds     OS_ASSET,a16[1],s16[1];  // read the output allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];
+                test;
+                put     a8[0],ERRNO_NON_FRACTIONAL;  // set errno
+                put     a16[2],4;  // offset of the fraction in an allocation
+                extr    s16[1],a64[0],a16[2];
+                put     a64[1],1;
+                eq.n    a64[0],a64[1];
+                test;
+                ret;
+            }),
+            ("FN_EDITION_GENESIS_OFFSET", rgbasm! {
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_edition_lib();
+        assembled.verify_offsets(&[
+            ("FN_EDITION_MINT_OFFSET", FN_EDITION_MINT_OFFSET),
+            ("FN_EDITION_GENESIS_OFFSET", FN_EDITION_GENESIS_OFFSET),
+        ]);
+        EDITION_LIB.verify("EDITION_LIB", assembled.lib);
+    }
+}
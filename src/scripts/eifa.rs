@@ -0,0 +1,227 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the epoch inflatable asset schema.
+//!
+//! The genesis validation performs the checks of the IFA one and also requires the epoch issuance
+//! state to record no issuance yet. The epoch issuance state holds the epoch of the last inflation
+//! and the supply issued within it up to that inflation.
+//!
+//! An inflation performs the checks of the IFA one and also commits to the absolute lock time of
+//! its witness transaction, which must be a block height. The inflation belongs to the epoch of the
+//! first height the witness can be mined at, i.e. the lock time plus one, which must not precede
+//! the epoch of the spent epoch issuance state. The supply issued within the epoch, adding up the
+//! inflation to the supply recorded by the spent state if it's in the same epoch, must not exceed
+//! the epoch cap, and the epoch issuance state assigned by the inflation must record it. The lock
+//! time of the witness itself is not checked, see [`crate::height`].
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_EIFA_GENESIS_OFFSET: u16 = 0;
+pub(super) const FN_EIFA_INFLATION_OFFSET: u16 = 77;
+// Jump target, only reached from within the library
+#[cfg(test)]
+const FN_EIFA_SAME_EPOCH_OFFSET: u16 = 206;
+
+pub(super) const EIFA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x09, 0x03, 0x00, 0x0b, 0x00, 0x05,
+        0x00, 0xc8, 0xda, 0x07, 0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x06,
+        0x00, 0xc8, 0xdb, 0x07, 0x11, 0x39, 0x31, 0x01, 0x21, 0x08, 0x60, 0x01, 0xd1, 0xaa, 0x0f,
+        0x01, 0x0b, 0x00, 0x07, 0x00, 0xc5, 0xc0, 0x0f, 0x20, 0x39, 0x22, 0x00, 0x0b, 0x0a, 0x08,
+        0x00, 0x19, 0x01, 0x41, 0x01, 0x39, 0x32, 0x12, 0x0b, 0x1b, 0x0c, 0x00, 0x19, 0x11, 0x63,
+        0x01, 0x07, 0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x00, 0x00, 0x0b, 0x09, 0x03, 0x00, 0x0b,
+        0x00, 0x05, 0x00, 0xc8, 0xda, 0x07, 0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x11,
+        0x03, 0x0b, 0x0b, 0x00, 0x06, 0x00, 0xca, 0xe8, 0x03, 0x00, 0x39, 0x30, 0x00, 0xd1, 0xaa,
+        0x0f, 0x01, 0x0b, 0x00, 0x14, 0x00, 0x20, 0x08, 0x60, 0x01, 0xd2, 0xaa, 0x0f, 0x01, 0x0b,
+        0x00, 0x15, 0x00, 0xc8, 0x0a, 0x08, 0x11, 0x39, 0x21, 0x01, 0x0b, 0x12, 0x16, 0x00, 0x18,
+        0x09, 0x42, 0x01, 0x0b, 0x12, 0x1a, 0x00, 0x20, 0x08, 0x42, 0x12, 0x12, 0x13, 0x0b, 0x02,
+        0x08, 0x00, 0xc9, 0xfd, 0x07, 0x20, 0x39, 0x32, 0x03, 0x23, 0x10, 0x63, 0x01, 0xc4, 0xc0,
+        0x0f, 0x30, 0x39, 0x23, 0x03, 0x12, 0x1a, 0x23, 0x18, 0x19, 0x64, 0x1f, 0x01, 0x39, 0x33,
+        0x15, 0x19, 0x19, 0x64, 0x03, 0xce, 0x00, 0x0b, 0x2b, 0x08, 0x00, 0x0b, 0x00, 0x1e, 0x00,
+        0x20, 0x08, 0x65, 0x01, 0xc9, 0xfe, 0x07, 0x20, 0x39, 0x32, 0x06, 0x18, 0x31, 0x65, 0x1f,
+        0x01, 0x0b, 0x00, 0x07, 0x00, 0xc5, 0xc0, 0x0f, 0x40, 0x39, 0x24, 0x04, 0x12, 0x22, 0x3b,
+        0x19, 0x19, 0x67, 0x01, 0x39, 0x34, 0x17, 0x19, 0x29, 0x67, 0x01, 0x07,
+    ],
+    data: &[
+        0x00, 0x00, 0x00, 0x04, 0x00, 0x01, 0x1e, 0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x26, 0x00, 0x65, 0xcd, 0x1d, 0x01, 0x00, 0x00, 0x00,
+        0x25,
+    ],
+    id: [
+        0xb2, 0x58, 0x01, 0x2c, 0x24, 0xf1, 0xfc, 0x64, 0x6e, 0xa0, 0x45, 0x58, 0x1c, 0x36, 0x03,
+        0x7d, 0xf2, 0xd1, 0x35, 0xcd, 0x84, 0xce, 0xa0, 0x1e, 0x18, 0x3a, 0xef, 0xb0, 0x0f, 0xb3,
+        0x06, 0x0d,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_eifa_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_EPOCH_CAP_EXCEEDED, ERRNO_EPOCH_ISSUANCE_MISMATCH, ERRNO_EPOCH_REGRESSED,
+        ERRNO_INFLATION_EXCEEDS_ALLOWANCE, ERRNO_INFLATION_MISMATCH, ERRNO_ISSUED_MISMATCH,
+    };
+    use crate::{
+        GS_EPOCH_CAP, GS_EPOCH_LENGTH, GS_ISSUED_SUPPLY, GS_LOCK_TIME, GS_MAX_SUPPLY,
+        MS_ALLOWED_INFLATION, OS_ASSET, OS_EPOCH_ISSUANCE, OS_INFLATION,
+    };
+
+    crate::asm::assemble("epoch inflatable asset", |labels| {
+        let same_epoch = labels.offset("FN_EIFA_SAME_EPOCH_OFFSET");
+        vec![
+            ("FN_EIFA_GENESIS_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+                put     a16[1],4;  // offset of the issued supply in the epoch issuance state
+
+                // Check reported issued supply against sum of asset allocations in output
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of assets assignments in output equals a64[0]
+                test;
+
+                // Check that sum of inflation rights = max supply - issued supply
+                put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+                ldg     GS_MAX_SUPPLY,a8[1],s16[1];  // read max supply global state
+                extr    s16[1],a64[1],a16[0];  // and store it in a64[1]
+                sub.uc  a64[1],a64[0];  // issued supply is still in a64[0], result overwrites a64[0]
+                test;  // fails if result is <0
+                sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+                test;
+
+                // Check that no supply is recorded as issued within an epoch yet
+                put     a8[0],ERRNO_EPOCH_ISSUANCE_MISMATCH;  // set errno
+                lds     OS_EPOCH_ISSUANCE,a16[0],s16[2];  // read output epoch issuance state
+                extr    s16[2],a32[0],a16[0];  // epoch of the last inflation
+                put     a32[1],0;
+                eq.n    a32[0],a32[1];
+                test;
+                extr    s16[2],a64[2],a16[1];  // supply issued within the epoch
+                put     a64[3],0;
+                eq.n    a64[2],a64[3];
+                test;
+
+                ret;
+            }),
+            ("FN_EIFA_INFLATION_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+                put     a16[1],4;  // offset of the issued supply in the epoch issuance state
+
+                // Check reported issued supply equals sum of asset allocations in output
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of asset allocations in output equals issued_supply
+                test;
+                cpy     a64[0],a64[1];  // store issued supply in a64[1] for later
+
+                // Check reported allowed inflation equals sum of inflation rights in output
+                put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+                ldm     MS_ALLOWED_INFLATION,s16[0];  // read allowed inflation metadata
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+                test;
+
+                // Check that input inflation rights equals issued supply + allowed inflation
+                put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+                add.uc  a64[1],a64[0];  // result is stored in a64[0]
+                test;  // fails in case of an overflow
+                sps     OS_INFLATION;  // check sum of inflation rights in input equals a64[0]
+                test;
+
+                // Compute into a64[3] the epoch of the first height the witness can be mined at
+                put     a8[0],ERRNO_EPOCH_REGRESSED;  // set errno
+                ldg     GS_LOCK_TIME,a8[1],s16[1];  // read the lock time of the inflation
+                extr    s16[1],a32[1],a16[0];  // and store it in a32[1]
+                put     a32[2],500000000;  // lock times from it on are timestamps
+                lt.u    a32[1],a32[2];
+                test;
+                put     a32[2],1;
+                add.uc  a32[1],a32[2];  // can't overflow, the lock time is a height
+                cnv     a32[2],a64[2];
+                put     a32[0],0;  // depth of the contract global state entry to read
+                ldc     GS_EPOCH_LENGTH,a32[0],s16[2];  // read the epoch length
+                extr    s16[2],a64[3],a16[0];  // and store it in a64[3]
+                div.uc  a64[2],a64[3];  // epoch, stored in a64[3]
+                test;  // fails if the epoch length is zero
+
+                // Check that the epoch doesn't precede the one of the spent epoch issuance state
+                ldp     OS_EPOCH_ISSUANCE,a16[0],s16[3];  // read input epoch issuance state
+                extr    s16[3],a32[3],a16[0];  // epoch of the last inflation
+                cnv     a32[3],a64[4];  // and store it in a64[4]
+                lt.u    a64[3],a64[4];  // epoch < epoch of the last inflation
+                inv     st0;
+                test;
+                extr    s16[3],a64[5],a16[1];  // supply issued within the epoch of the last one
+                eq.n    a64[3],a64[4];
+                jif     same_epoch;
+                put     a64[5],0;  // nothing is issued yet within a later epoch
+            }),
+            ("FN_EIFA_SAME_EPOCH_OFFSET", rgbasm! {
+                // Check that the supply issued within the epoch doesn't exceed the epoch cap
+                put     a8[0],ERRNO_EPOCH_CAP_EXCEEDED;  // set errno
+                add.uc  a64[1],a64[5];  // issued supply is still in a64[1], result overwrites a64[5]
+                test;  // fails in case of an overflow
+                ldc     GS_EPOCH_CAP,a32[0],s16[2];  // read the epoch cap
+                extr    s16[2],a64[6],a16[0];  // and store it in a64[6]
+                lt.u    a64[6],a64[5];  // cap < supply issued within the epoch
+                inv     st0;  // cap >= supply issued within the epoch
+                test;
+
+                // Check that the output epoch issuance state records the inflation
+                put     a8[0],ERRNO_EPOCH_ISSUANCE_MISMATCH;  // set errno
+                lds     OS_EPOCH_ISSUANCE,a16[0],s16[4];  // read output epoch issuance state
+                extr    s16[4],a32[4],a16[0];
+                cnv     a32[4],a64[7];
+                eq.n    a64[3],a64[7];
+                test;
+                extr    s16[4],a64[7],a16[1];
+                eq.n    a64[5],a64[7];
+                test;
+
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_eifa_lib();
+        assembled.verify_offsets(&[
+            ("FN_EIFA_GENESIS_OFFSET", FN_EIFA_GENESIS_OFFSET),
+            ("FN_EIFA_INFLATION_OFFSET", FN_EIFA_INFLATION_OFFSET),
+            ("FN_EIFA_SAME_EPOCH_OFFSET", FN_EIFA_SAME_EPOCH_OFFSET),
+        ]);
+        EIFA_LIB.verify("EIFA_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,184 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the escrow asset schema.
+//!
+//! Moving units into escrow requires the asset inputs to equal the escrowed and change outputs
+//! together. Both a release and a refund of an escrow turn the escrowed inputs into asset outputs
+//! of the same sum, so they share the same subroutine: the schema tells them apart by the right
+//! they spend.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_ESCROW_LOCK_OFFSET: u16 = 0;
+pub(super) const FN_ESCROW_SETTLE_OFFSET: u16 = 80;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_ESCROW_ESCROWED_LOOP_OFFSET: u16 = 19;
+#[cfg(test)]
+const FN_ESCROW_ESCROWED_NEXT_OFFSET: u16 = 30;
+#[cfg(test)]
+const FN_ESCROW_CHANGE_LOOP_OFFSET: u16 = 51;
+#[cfg(test)]
+const FN_ESCROW_CHANGE_NEXT_OFFSET: u16 = 62;
+#[cfg(test)]
+const FN_ESCROW_SETTLED_LOOP_OFFSET: u16 = 99;
+#[cfg(test)]
+const FN_ESCROW_SETTLED_NEXT_OFFSET: u16 = 110;
+
+pub(super) const ESCROW_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x0b, 0x01, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc1, 0xb5, 0x0f,
+        0x01, 0x02, 0x1e, 0x00, 0xc6, 0xb5, 0x0f, 0x30, 0x20, 0x18, 0x61, 0x01, 0x24, 0x01, 0x01,
+        0x18, 0x01, 0x21, 0x03, 0x13, 0x00, 0x0b, 0x13, 0x00, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc1,
+        0xa0, 0x0f, 0x01, 0x02, 0x3e, 0x00, 0xc6, 0xa0, 0x0f, 0x30, 0x20, 0x18, 0x62, 0x01, 0x24,
+        0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0x33, 0x00, 0x11, 0x13, 0x03, 0x20, 0x08, 0x60, 0x01,
+        0xd2, 0xa0, 0x0f, 0x01, 0x07, 0x0b, 0x00, 0x00, 0x00, 0x0b, 0x03, 0x00, 0x00, 0x0b, 0x01,
+        0x00, 0x00, 0xc1, 0xa0, 0x0f, 0x01, 0x02, 0x6e, 0x00, 0xc6, 0xa0, 0x0f, 0x30, 0x20, 0x18,
+        0x60, 0x01, 0x24, 0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0x63, 0x00, 0x1f, 0xd2, 0xb5, 0x0f,
+        0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    id: [
+        0x4f, 0x95, 0xa6, 0x8b, 0x38, 0x6b, 0x01, 0xd6, 0x10, 0x6f, 0x79, 0x46, 0xf5, 0x44, 0x7b,
+        0xa5, 0x62, 0x37, 0xff, 0x49, 0x5d, 0x8b, 0x8f, 0xc3, 0x15, 0x18, 0x49, 0xd8, 0xb7, 0x86,
+        0xcf, 0x7a,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_escrow_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ldf, ERRNO_NON_EQUAL_IN_OUT};
+    use crate::{OS_ASSET, OS_ESCROW};
+
+    crate::asm::assemble("escrow asset", |labels| {
+        let escrowed_loop = labels.offset("FN_ESCROW_ESCROWED_LOOP_OFFSET");
+        let escrowed_next = labels.offset("FN_ESCROW_ESCROWED_NEXT_OFFSET");
+        let change_loop = labels.offset("FN_ESCROW_CHANGE_LOOP_OFFSET");
+        let change_next = labels.offset("FN_ESCROW_CHANGE_NEXT_OFFSET");
+        let settled_loop = labels.offset("FN_ESCROW_SETTLED_LOOP_OFFSET");
+        let settled_next = labels.offset("FN_ESCROW_SETTLED_NEXT_OFFSET");
+        vec![
+            ("FN_ESCROW_LOCK_OFFSET", rgbasm! {
+                // Sum the escrowed allocations in output into a64[1]
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a64[1],0;
+                put     a16[0],0;  // index of the output allocation to read
+                cns     OS_ESCROW,a16[1];  // count escrowed allocations in output
+                jmp     escrowed_next;
+            }),
+            ("FN_ESCROW_ESCROWED_LOOP_OFFSET", {
+                // Read an escrowed allocation into a64[3]
+                let mut code = vec![ldf(OS_ESCROW, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[1];  // add it to the sum in a64[1]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_ESCROW_ESCROWED_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining escrowed allocations
+                jif     escrowed_loop;
+
+                // Sum the change allocations in output into a64[2]
+                put     a64[2],0;
+                put     a16[0],0;
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     change_next;
+            }),
+            ("FN_ESCROW_CHANGE_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[2];  // add it to the sum in a64[2]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_ESCROW_CHANGE_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     change_loop;
+
+                // Check sum of asset allocations in input equals the sum of all outputs
+                cpy     a64[2],a64[0];
+                add.uc  a64[1],a64[0];  // escrowed and change outputs, in a64[0]
+                test;  // fails in case of an overflow
+                sps     OS_ASSET;  // check sum of asset allocations in input equals a64[0]
+                test;
+                ret;
+            }),
+            ("FN_ESCROW_SETTLE_OFFSET", rgbasm! {
+                // Sum the asset allocations in output into a64[0]
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a64[0],0;
+                put     a16[0],0;
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     settled_next;
+            }),
+            ("FN_ESCROW_SETTLED_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[0];  // add it to the sum in a64[0]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_ESCROW_SETTLED_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     settled_loop;
+
+                // Check sum of escrowed allocations in input equals a64[0]
+                inv     st0;  // the loop exits with st0 false, which `sps` doesn't reset
+                sps     OS_ESCROW;
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_escrow_lib();
+        assembled.verify_offsets(&[
+            ("FN_ESCROW_LOCK_OFFSET", FN_ESCROW_LOCK_OFFSET),
+            ("FN_ESCROW_ESCROWED_LOOP_OFFSET", FN_ESCROW_ESCROWED_LOOP_OFFSET),
+            ("FN_ESCROW_ESCROWED_NEXT_OFFSET", FN_ESCROW_ESCROWED_NEXT_OFFSET),
+            ("FN_ESCROW_CHANGE_LOOP_OFFSET", FN_ESCROW_CHANGE_LOOP_OFFSET),
+            ("FN_ESCROW_CHANGE_NEXT_OFFSET", FN_ESCROW_CHANGE_NEXT_OFFSET),
+            ("FN_ESCROW_SETTLE_OFFSET", FN_ESCROW_SETTLE_OFFSET),
+            ("FN_ESCROW_SETTLED_LOOP_OFFSET", FN_ESCROW_SETTLED_LOOP_OFFSET),
+            ("FN_ESCROW_SETTLED_NEXT_OFFSET", FN_ESCROW_SETTLED_NEXT_OFFSET),
+        ]);
+        ESCROW_LIB.verify("ESCROW_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,245 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the transfer-fee asset schema.
+//!
+//! The transfer validation sums the asset and fee allocations of the outputs, requiring together
+//! the sum of the asset inputs, and checks the fee against the fee rate applied to the input sum.
+//! The fee is computed as `q * rate + r * rate / 10 000`, where `q` and `r` are the quotient and
+//! the remainder of the division of the input sum by 10 000, so that the products can't overflow
+//! 64 bits with the rate bounded by the genesis validation.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_FEE_GENESIS_OFFSET: u16 = 0;
+pub(super) const FN_FEE_TRANSFER_OFFSET: u16 = 44;
+pub(super) const FN_FEE_COLLECT_OFFSET: u16 = 183;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_FEE_FEES_LOOP_OFFSET: u16 = 63;
+#[cfg(test)]
+const FN_FEE_FEES_NEXT_OFFSET: u16 = 74;
+#[cfg(test)]
+const FN_FEE_ASSETS_LOOP_OFFSET: u16 = 95;
+#[cfg(test)]
+const FN_FEE_ASSETS_NEXT_OFFSET: u16 = 106;
+#[cfg(test)]
+const FN_FEE_COLLECTED_LOOP_OFFSET: u16 = 202;
+#[cfg(test)]
+const FN_FEE_COLLECTED_NEXT_OFFSET: u16 = 213;
+
+pub(super) const FEE_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x04, 0x00, 0xc8, 0xee, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0x0b, 0x0b, 0x05, 0x00, 0x18, 0x09, 0x60, 0x1f, 0x01, 0x07, 0x0b,
+        0x00, 0x00, 0x00, 0x0b, 0x0b, 0x0d, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc1, 0xb3, 0x0f, 0x01,
+        0x02, 0x4a, 0x00, 0xc6, 0xb3, 0x0f, 0x30, 0x20, 0x18, 0x61, 0x01, 0x24, 0x01, 0x01, 0x18,
+        0x01, 0x21, 0x03, 0x3f, 0x00, 0x0b, 0x13, 0x07, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc1, 0xa0,
+        0x0f, 0x01, 0x02, 0x6a, 0x00, 0xc6, 0xa0, 0x0f, 0x30, 0x20, 0x18, 0x62, 0x01, 0x24, 0x01,
+        0x01, 0x18, 0x01, 0x21, 0x03, 0x5f, 0x00, 0x11, 0x13, 0x03, 0x20, 0x08, 0x60, 0x01, 0xd2,
+        0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x15, 0x00, 0x0b, 0x02, 0x07, 0x00, 0x0b, 0x01, 0x00, 0x00,
+        0xc9, 0xee, 0x07, 0x00, 0x39, 0x30, 0x04, 0x0b, 0x33, 0x05, 0x00, 0x23, 0x00, 0x66, 0x0b,
+        0x3b, 0x05, 0x00, 0x22, 0x30, 0x67, 0x21, 0x00, 0x67, 0x22, 0x20, 0x66, 0x01, 0x22, 0x20,
+        0x67, 0x01, 0x0b, 0x2b, 0x05, 0x00, 0x23, 0x38, 0x65, 0x20, 0x30, 0x65, 0x01, 0x19, 0x09,
+        0x65, 0x01, 0x07, 0x0b, 0x00, 0x00, 0x00, 0x0b, 0x03, 0x07, 0x00, 0x0b, 0x01, 0x00, 0x00,
+        0xc1, 0xa0, 0x0f, 0x01, 0x02, 0xd5, 0x00, 0xc6, 0xa0, 0x0f, 0x30, 0x20, 0x18, 0x60, 0x01,
+        0x24, 0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0xca, 0x00, 0x1f, 0xd2, 0xb3, 0x0f, 0x01, 0x07,
+    ],
+    data: &[
+        0x00, 0x00, 0x00, 0x01, 0x41, 0x10, 0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x42,
+    ],
+    id: [
+        0x9b, 0x62, 0x7d, 0xe9, 0xc3, 0xaa, 0x45, 0x6e, 0x24, 0x1d, 0xf1, 0x2a, 0xba, 0x0d, 0x37,
+        0xa7, 0xee, 0x23, 0x39, 0x4c, 0x87, 0x1e, 0x5b, 0x24, 0xab, 0x3c, 0x8b, 0x58, 0xd3, 0xe6,
+        0x94, 0xd5,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_fee_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ldf, ERRNO_FEE_MISMATCH, ERRNO_INVALID_FEE_RATE, ERRNO_ISSUED_MISMATCH,
+        ERRNO_NON_EQUAL_IN_OUT,
+    };
+    use crate::{GS_FEE_RATE, GS_ISSUED_SUPPLY, OS_ASSET, OS_FEE};
+
+    crate::asm::assemble("transfer-fee asset", |labels| {
+        let fees_loop = labels.offset("FN_FEE_FEES_LOOP_OFFSET");
+        let fees_next = labels.offset("FN_FEE_FEES_NEXT_OFFSET");
+        let assets_loop = labels.offset("FN_FEE_ASSETS_LOOP_OFFSET");
+        let assets_next = labels.offset("FN_FEE_ASSETS_NEXT_OFFSET");
+        let collected_loop = labels.offset("FN_FEE_COLLECTED_LOOP_OFFSET");
+        let collected_next = labels.offset("FN_FEE_COLLECTED_NEXT_OFFSET");
+        vec![
+            ("FN_FEE_GENESIS_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+
+                // Check reported issued supply equals sum of asset allocations in output
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of asset allocations in output equals a64[0]
+                test;
+
+                // Check the fee rate doesn't exceed 10 000 basis points
+                put     a8[0],ERRNO_INVALID_FEE_RATE;  // set errno
+                ldg     GS_FEE_RATE,a8[1],s16[0];  // read fee rate global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                put     a64[1],10000;
+                lt.u    a64[1],a64[0];  // 10 000 < rate
+                inv     st0;  // rate <= 10 000
+                test;
+                ret;
+            }),
+            ("FN_FEE_TRANSFER_OFFSET", rgbasm! {
+                // Sum the fee allocations in output into a64[1]
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a64[1],0;
+                put     a16[0],0;  // index of the output allocation to read
+                cns     OS_FEE,a16[1];  // count fee allocations in output
+                jmp     fees_next;
+            }),
+            ("FN_FEE_FEES_LOOP_OFFSET", {
+                // Read a fee allocation into a64[3]
+                let mut code = vec![ldf(OS_FEE, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[1];  // add it to the sum in a64[1]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_FEE_FEES_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining fee allocations
+                jif     fees_loop;
+
+                // Sum the asset allocations in output into a64[2]
+                put     a64[2],0;
+                put     a16[0],0;
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     assets_next;
+            }),
+            ("FN_FEE_ASSETS_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[2];  // add it to the sum in a64[2]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_FEE_ASSETS_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     assets_loop;
+
+                // Check sum of asset allocations in input equals the sum of all outputs
+                cpy     a64[2],a64[0];
+                add.uc  a64[1],a64[0];  // asset and fee outputs, in a64[0]
+                test;  // fails in case of an overflow
+                sps     OS_ASSET;  // check sum of asset allocations in input equals a64[0]
+                test;
+
+                // Compute into a64[5] the fee owed on the input sum
+                put     a8[0],ERRNO_FEE_MISMATCH;  // set errno
+                put     a32[0],0;
+                put     a16[0],0;
+                ldc     GS_FEE_RATE,a32[0],s16[0];  // read fee rate contract global state
+                extr    s16[0],a64[4],a16[0];  // and store it in a64[4]
+                put     a64[6],10000;
+                div.uc  a64[0],a64[6];  // quotient of the input sum, in a64[6]
+                put     a64[7],10000;
+                mul.uc  a64[6],a64[7];
+                sub.uc  a64[0],a64[7];  // remainder of the input sum, in a64[7]
+                mul.uc  a64[4],a64[6];  // quotient * rate, in a64[6]
+                test;
+                mul.uc  a64[4],a64[7];  // remainder * rate, in a64[7]
+                test;
+                put     a64[5],10000;
+                div.uc  a64[7],a64[5];  // remainder * rate / 10 000, in a64[5]
+                add.uc  a64[6],a64[5];  // fee, in a64[5]
+                test;
+
+                // Check the sum of fee allocations equals the fee
+                eq.n    a64[1],a64[5];
+                test;
+                ret;
+            }),
+            ("FN_FEE_COLLECT_OFFSET", rgbasm! {
+                // Sum the asset allocations in output into a64[0]
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a64[0],0;
+                put     a16[0],0;
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     collected_next;
+            }),
+            ("FN_FEE_COLLECTED_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[0];  // add it to the sum in a64[0]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_FEE_COLLECTED_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     collected_loop;
+
+                // Check sum of fee allocations in input equals a64[0]
+                inv     st0;  // the loop exits with st0 false, which `sps` doesn't reset
+                sps     OS_FEE;
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_fee_lib();
+        assembled.verify_offsets(&[
+            ("FN_FEE_GENESIS_OFFSET", FN_FEE_GENESIS_OFFSET),
+            ("FN_FEE_TRANSFER_OFFSET", FN_FEE_TRANSFER_OFFSET),
+            ("FN_FEE_FEES_LOOP_OFFSET", FN_FEE_FEES_LOOP_OFFSET),
+            ("FN_FEE_FEES_NEXT_OFFSET", FN_FEE_FEES_NEXT_OFFSET),
+            ("FN_FEE_ASSETS_LOOP_OFFSET", FN_FEE_ASSETS_LOOP_OFFSET),
+            ("FN_FEE_ASSETS_NEXT_OFFSET", FN_FEE_ASSETS_NEXT_OFFSET),
+            ("FN_FEE_COLLECT_OFFSET", FN_FEE_COLLECT_OFFSET),
+            ("FN_FEE_COLLECTED_LOOP_OFFSET", FN_FEE_COLLECTED_LOOP_OFFSET),
+            ("FN_FEE_COLLECTED_NEXT_OFFSET", FN_FEE_COLLECTED_NEXT_OFFSET),
+        ]);
+        FEE_LIB.verify("FEE_LIB", assembled.lib);
+    }
+}
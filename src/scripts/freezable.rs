@@ -0,0 +1,185 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the freezable asset schema.
+//!
+//! Scripts can't see the UTXOs spent by a transition, so each transfer commits to them in its
+//! global state: the transfer validation checks that none of them is among the UTXOs frozen by the
+//! contract global state, and that the transfer keeps the sum of the inputs. The freeze validation
+//! checks that the UTXOs recorded by a freeze are not frozen yet. Whether a transfer commits to the
+//! UTXOs it actually spends is not checked, see [`crate::height`] for the same limitation about
+//! heights.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_FREEZABLE_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_FREEZABLE_FREEZE_OFFSET: u16 = 66;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_FREEZABLE_SPENT_LOOP_OFFSET: u16 = 27;
+#[cfg(test)]
+const FN_FREEZABLE_SPENT_FROZEN_LOOP_OFFSET: u16 = 38;
+#[cfg(test)]
+const FN_FREEZABLE_SPENT_FROZEN_NEXT_OFFSET: u16 = 49;
+#[cfg(test)]
+const FN_FREEZABLE_SPENT_NEXT_OFFSET: u16 = 58;
+#[cfg(test)]
+const FN_FREEZABLE_FROZEN_LOOP_OFFSET: u16 = 85;
+#[cfg(test)]
+const FN_FREEZABLE_FROZEN_FROZEN_LOOP_OFFSET: u16 = 96;
+#[cfg(test)]
+const FN_FREEZABLE_FROZEN_FROZEN_NEXT_OFFSET: u16 = 107;
+#[cfg(test)]
+const FN_FREEZABLE_FROZEN_NEXT_OFFSET: u16 = 116;
+
+pub(super) const FREEZABLE_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xd0, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x01, 0x00, 0xc2, 0x0b, 0x08,
+        0x01, 0x0b, 0x10, 0x00, 0x00, 0xc3, 0xeb, 0x07, 0x01, 0x02, 0x3a, 0x00, 0xc8, 0x0b, 0x08,
+        0x02, 0x0b, 0x02, 0x02, 0x00, 0x02, 0x31, 0x00, 0xc9, 0xeb, 0x07, 0x10, 0x36, 0x10, 0x1f,
+        0x01, 0x24, 0x02, 0x01, 0x18, 0x01, 0x41, 0x03, 0x26, 0x00, 0x24, 0x10, 0x01, 0x18, 0x11,
+        0x01, 0x03, 0x1b, 0x00, 0x1f, 0x07, 0x0b, 0x00, 0x06, 0x00, 0xc2, 0xeb, 0x07, 0x01, 0x0b,
+        0x10, 0x00, 0x00, 0xc3, 0xeb, 0x07, 0x01, 0x02, 0x74, 0x00, 0xc8, 0xeb, 0x07, 0x02, 0x0b,
+        0x02, 0x02, 0x00, 0x02, 0x6b, 0x00, 0xc9, 0xeb, 0x07, 0x10, 0x36, 0x10, 0x1f, 0x01, 0x24,
+        0x02, 0x01, 0x18, 0x01, 0x41, 0x03, 0x60, 0x00, 0x24, 0x10, 0x01, 0x18, 0x11, 0x01, 0x03,
+        0x55, 0x00, 0x1f, 0x07,
+    ],
+    data: &[0x00, 0x3a, 0x00, 0x00, 0x00, 0x00, 0x3b],
+    id: [
+        0xe4, 0x66, 0x8c, 0xed, 0xcc, 0x55, 0x01, 0xc3, 0x79, 0xec, 0x00, 0x7e, 0xda, 0xb8, 0x58,
+        0x73, 0xc0, 0x5b, 0x20, 0x60, 0xf4, 0x3e, 0x00, 0x7c, 0xf3, 0x46, 0x45, 0xe0, 0xa5, 0x1e,
+        0x0c, 0x92,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_freezable_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_ALREADY_FROZEN, ERRNO_FROZEN_SPEND, ERRNO_NON_EQUAL_IN_OUT};
+    use crate::{GS_FROZEN, GS_SPENT_UTXOS, OS_ASSET};
+
+    crate::asm::assemble("freezable asset", |labels| {
+        let spent_loop = labels.offset("FN_FREEZABLE_SPENT_LOOP_OFFSET");
+        let spent_frozen_loop = labels.offset("FN_FREEZABLE_SPENT_FROZEN_LOOP_OFFSET");
+        let spent_frozen_next = labels.offset("FN_FREEZABLE_SPENT_FROZEN_NEXT_OFFSET");
+        let spent_next = labels.offset("FN_FREEZABLE_SPENT_NEXT_OFFSET");
+        let frozen_loop = labels.offset("FN_FREEZABLE_FROZEN_LOOP_OFFSET");
+        let frozen_frozen_loop = labels.offset("FN_FREEZABLE_FROZEN_FROZEN_LOOP_OFFSET");
+        let frozen_frozen_next = labels.offset("FN_FREEZABLE_FROZEN_FROZEN_NEXT_OFFSET");
+        let frozen_next = labels.offset("FN_FREEZABLE_FROZEN_NEXT_OFFSET");
+        vec![
+            ("FN_FREEZABLE_TRANSFER_OFFSET", rgbasm! {
+                // Check that the sum of inputs is equal to the sum of outputs
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                svs     OS_ASSET;
+                test;
+
+                // Check that none of the spent UTXOs is frozen: the assembler names the count
+                // destination of the frozen UTXOs a16[1], while the count is put into a32[1]
+                put     a8[0],ERRNO_FROZEN_SPEND;  // set errno
+                cng     GS_SPENT_UTXOS,a8[1];  // count the spent UTXOs
+                put     a8[2],0;  // index of the spent UTXO to check
+                cnc     GS_FROZEN,a16[1];  // count the frozen UTXOs
+                jmp     spent_next;
+            }),
+            ("FN_FREEZABLE_SPENT_LOOP_OFFSET", rgbasm! {
+                ldg     GS_SPENT_UTXOS,a8[2],s16[0];  // read a spent UTXO
+                put     a32[0],0;  // depth of the frozen UTXO to compare
+                jmp     spent_frozen_next;
+            }),
+            ("FN_FREEZABLE_SPENT_FROZEN_LOOP_OFFSET", rgbasm! {
+                ldc     GS_FROZEN,a32[0],s16[1];  // read a frozen UTXO
+                eq      s16[0],s16[1];
+                inv     st0;
+                test;
+                inc     a32[0];
+            }),
+            ("FN_FREEZABLE_SPENT_FROZEN_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining frozen UTXOs
+                jif     spent_frozen_loop;
+                inc     a8[2];
+            }),
+            ("FN_FREEZABLE_SPENT_NEXT_OFFSET", rgbasm! {
+                lt.u    a8[2],a8[1];  // loop over the remaining spent UTXOs
+                jif     spent_loop;
+                inv     st0;  // the loop exits with st0 false
+                ret;
+            }),
+            ("FN_FREEZABLE_FREEZE_OFFSET", rgbasm! {
+                // Check that none of the UTXOs to freeze is already frozen: the assembler names the
+                // count destination of the frozen UTXOs a16[1], while the count is put into a32[1]
+                put     a8[0],ERRNO_ALREADY_FROZEN;  // set errno
+                cng     GS_FROZEN,a8[1];  // count the UTXOs to freeze
+                put     a8[2],0;  // index of the UTXO to check
+                cnc     GS_FROZEN,a16[1];  // count the frozen UTXOs
+                jmp     frozen_next;
+            }),
+            ("FN_FREEZABLE_FROZEN_LOOP_OFFSET", rgbasm! {
+                ldg     GS_FROZEN,a8[2],s16[0];  // read a UTXO to freeze
+                put     a32[0],0;  // depth of the frozen UTXO to compare
+                jmp     frozen_frozen_next;
+            }),
+            ("FN_FREEZABLE_FROZEN_FROZEN_LOOP_OFFSET", rgbasm! {
+                ldc     GS_FROZEN,a32[0],s16[1];  // read a frozen UTXO
+                eq      s16[0],s16[1];
+                inv     st0;
+                test;
+                inc     a32[0];
+            }),
+            ("FN_FREEZABLE_FROZEN_FROZEN_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining frozen UTXOs
+                jif     frozen_frozen_loop;
+                inc     a8[2];
+            }),
+            ("FN_FREEZABLE_FROZEN_NEXT_OFFSET", rgbasm! {
+                lt.u    a8[2],a8[1];  // loop over the remaining UTXOs to freeze
+                jif     frozen_loop;
+                inv     st0;  // the loop exits with st0 false
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_freezable_lib();
+        assembled.verify_offsets(&[
+            ("FN_FREEZABLE_TRANSFER_OFFSET", FN_FREEZABLE_TRANSFER_OFFSET),
+            ("FN_FREEZABLE_SPENT_LOOP_OFFSET", FN_FREEZABLE_SPENT_LOOP_OFFSET),
+            ("FN_FREEZABLE_SPENT_FROZEN_LOOP_OFFSET", FN_FREEZABLE_SPENT_FROZEN_LOOP_OFFSET),
+            ("FN_FREEZABLE_SPENT_FROZEN_NEXT_OFFSET", FN_FREEZABLE_SPENT_FROZEN_NEXT_OFFSET),
+            ("FN_FREEZABLE_SPENT_NEXT_OFFSET", FN_FREEZABLE_SPENT_NEXT_OFFSET),
+            ("FN_FREEZABLE_FREEZE_OFFSET", FN_FREEZABLE_FREEZE_OFFSET),
+            ("FN_FREEZABLE_FROZEN_LOOP_OFFSET", FN_FREEZABLE_FROZEN_LOOP_OFFSET),
+            ("FN_FREEZABLE_FROZEN_FROZEN_LOOP_OFFSET", FN_FREEZABLE_FROZEN_FROZEN_LOOP_OFFSET),
+            ("FN_FREEZABLE_FROZEN_FROZEN_NEXT_OFFSET", FN_FREEZABLE_FROZEN_FROZEN_NEXT_OFFSET),
+            ("FN_FREEZABLE_FROZEN_NEXT_OFFSET", FN_FREEZABLE_FROZEN_NEXT_OFFSET),
+        ]);
+        FREEZABLE_LIB.verify("FREEZABLE_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,156 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the fractional unique asset schema.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_FUA_GENESIS_OFFSET: u16 = 65;
+pub(super) const FN_FUA_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_FUA_SHARED_OFFSET: u16 = 99;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_FUA_INPUTS_LOOP_OFFSET: u16 = 31;
+#[cfg(test)]
+const FN_FUA_OUTPUTS_LOOP_OFFSET: u16 = 111;
+
+pub(super) const FUA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x01, 0x00, 0x00, 0x0b, 0x11, 0x02, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x0b, 0x09, 0x00,
+        0x00, 0xc4, 0xa0, 0x0f, 0x01, 0x39, 0x20, 0x00, 0x0b, 0x13, 0x04, 0x00, 0xc0, 0xa0, 0x0f,
+        0x03, 0xc4, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x01, 0x39, 0x31, 0x21,
+        0x20, 0x08, 0x62, 0x01, 0x24, 0x09, 0x01, 0x18, 0x09, 0x23, 0x03, 0x1f, 0x00, 0x0b, 0x10,
+        0x00, 0x00, 0x02, 0x63, 0x00, 0x0b, 0x01, 0x00, 0x00, 0x0b, 0x11, 0x02, 0x00, 0x0b, 0x00,
+        0x00, 0x00, 0x0b, 0x08, 0x00, 0x00, 0xc8, 0x36, 0x08, 0x01, 0x39, 0x20, 0x00, 0xc8, 0xda,
+        0x07, 0x01, 0x39, 0x30, 0x02, 0x0b, 0x10, 0x0c, 0x00, 0x0b, 0x09, 0x00, 0x00, 0x0b, 0x03,
+        0x03, 0x00, 0xc1, 0xa0, 0x0f, 0x03, 0xc5, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01,
+        0x41, 0x01, 0x39, 0x31, 0x21, 0x20, 0x08, 0x60, 0x01, 0x24, 0x09, 0x01, 0x18, 0x09, 0x23,
+        0x03, 0x6f, 0x00, 0x11, 0x10, 0x00, 0x19, 0x01, 0x62, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+    id: [
+        0xb2, 0xd0, 0xdf, 0x73, 0x41, 0x5d, 0x19, 0x13, 0xa8, 0x13, 0x61, 0x58, 0x36, 0x68, 0x16,
+        0xa9, 0xbd, 0x10, 0x6b, 0x91, 0xd5, 0x03, 0x23, 0x88, 0xf4, 0x7d, 0x03, 0x61, 0x63, 0xc2,
+        0x3e, 0x85,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_fua_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT};
+    use crate::{GS_ISSUED_SUPPLY, GS_TOKENS, OS_ASSET};
+
+    crate::asm::assemble("fractional unique asset", |labels| {
+        let inputs_loop = labels.offset("FN_FUA_INPUTS_LOOP_OFFSET");
+        let shared = labels.offset("FN_FUA_SHARED_OFFSET");
+        let outputs_loop = labels.offset("FN_FUA_OUTPUTS_LOOP_OFFSET");
+        vec![
+            ("FN_FUA_TRANSFER_OFFSET", rgbasm! {
+                // SUBROUTINE 2: Transfer validation
+                put     a16[0],0;  // offset of the token index in an allocation
+                put     a16[2],4;  // offset of the fraction in an allocation
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                // Read the token index of the first input into a32[0]
+                put     a16[1],0;
+                ldp     OS_ASSET,a16[1],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                // Sum input fractions into a64[2], the schema requires at least one input
+                put     a64[2],0;
+                cnp     OS_ASSET,a16[3];
+            }),
+            ("FN_FUA_INPUTS_LOOP_OFFSET", rgbasm! {
+                ldp     OS_ASSET,a16[1],s16[1];  // read the input allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];  // check that all inputs allocate the same token
+                test;
+                extr    s16[1],a64[1],a16[2];  // extract its fraction
+                add.uc  a64[1],a64[2];  // add it to the sum in a64[2]
+                test;  // fails in case of an overflow
+                inc     a16[1];
+                lt.u    a16[1],a16[3];  // loop over the remaining inputs
+                jif     inputs_loop;
+                // Outputs must keep the sum of input fractions
+                put     a8[2],ERRNO_NON_EQUAL_IN_OUT;
+                jmp     shared;
+            }),
+            ("FN_FUA_GENESIS_OFFSET", rgbasm! {
+                // SUBROUTINE 1: Genesis validation
+                put     a16[0],0;  // offset of the token index in an allocation
+                put     a16[2],4;  // offset of the fraction in an allocation
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                // Read the index of the issued token into a32[0]
+                put     a8[1],0;
+                ldg     GS_TOKENS,a8[1],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                // Outputs must allocate the issued fractions, read into a64[2]
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+                extr    s16[0],a64[2],a16[0];
+                put     a8[2],ERRNO_ISSUED_MISMATCH;
+            }),
+            ("FN_FUA_SHARED_OFFSET", rgbasm! {
+                // SUBROUTINE 3: Shared code, expecting the token index in a32[0], the expected
+                // sum of fractions in a64[2] and the errno for a sum mismatch in a8[2]
+                // Sum output fractions into a64[0], the schema requires at least one output
+                put     a16[1],0;
+                put     a64[0],0;
+                cns     OS_ASSET,a16[3];
+            }),
+            ("FN_FUA_OUTPUTS_LOOP_OFFSET", rgbasm! {
+                lds     OS_ASSET,a16[1],s16[1];  // read the output allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];  // check that it allocates the expected token
+                test;
+                extr    s16[1],a64[1],a16[2];  // extract its fraction
+                add.uc  a64[1],a64[0];  // add it to the sum in a64[0]
+                test;  // fails in case of an overflow
+                inc     a16[1];
+                lt.u    a16[1],a16[3];  // loop over the remaining outputs
+                jif     outputs_loop;
+                // Check that the sum of output fractions is the expected one
+                cpy     a8[2],a8[0];  // set errno
+                eq.n    a64[0],a64[2];
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_fua_lib();
+        assembled.verify_offsets(&[
+            ("FN_FUA_TRANSFER_OFFSET", FN_FUA_TRANSFER_OFFSET),
+            ("FN_FUA_INPUTS_LOOP_OFFSET", FN_FUA_INPUTS_LOOP_OFFSET),
+            ("FN_FUA_GENESIS_OFFSET", FN_FUA_GENESIS_OFFSET),
+            ("FN_FUA_SHARED_OFFSET", FN_FUA_SHARED_OFFSET),
+            ("FN_FUA_OUTPUTS_LOOP_OFFSET", FN_FUA_OUTPUTS_LOOP_OFFSET),
+        ]);
+        FUA_LIB.verify("FUA_LIB", assembled.lib);
+    }
+}
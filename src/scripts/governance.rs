@@ -0,0 +1,94 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the governance asset schema.
+//!
+//! The genesis validation checks the issued supply like the NIA one, and then that a vote right is
+//! assigned for each voting unit of the issued supply. Scripts can't tell which holder a vote
+//! right is assigned to, so only the total number of vote rights is checked. The number is
+//! converted to a 64-bit register as a signed integer, limiting a genesis to 32767 vote rights.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_GOVERNANCE_GENESIS_OFFSET: u16 = 0;
+
+pub(super) const GOVERNANCE_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x04, 0x00, 0xc8, 0xe3, 0x07,
+        0x01, 0x39, 0x30, 0x01, 0xc1, 0xae, 0x0f, 0x01, 0x12, 0x09, 0x13, 0x22, 0x10, 0x61, 0x01,
+        0x19, 0x01, 0x61, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x01, 0x32],
+    id: [
+        0x99, 0xbd, 0xea, 0x3d, 0x47, 0xce, 0xda, 0x25, 0x0e, 0xe2, 0xa5, 0x71, 0x13, 0x19, 0x49,
+        0xe9, 0xe4, 0xec, 0x34, 0x5e, 0x32, 0x38, 0xf0, 0xcc, 0x2c, 0x7d, 0xba, 0x42, 0x1c, 0x57,
+        0x4b, 0x00,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_governance_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_ISSUED_MISMATCH, ERRNO_VOTE_RIGHTS_MISMATCH};
+    use crate::{GS_ISSUED_SUPPLY, GS_VOTING_UNIT, OS_ASSET, OS_VOTE};
+
+    crate::asm::assemble("governance asset", |_| {
+        vec![("FN_GOVERNANCE_GENESIS_OFFSET", rgbasm! {
+            // Set common offsets
+            put     a8[1],0;
+            put     a16[0],0;
+
+            // Check reported issued supply equals sum of asset allocations in output
+            put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+            ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_ASSET;  // check sum of asset allocations in output equals issued_supply
+            test;
+
+            // Check that a vote right is assigned for each voting unit of the issued supply
+            put     a8[0],ERRNO_VOTE_RIGHTS_MISMATCH;  // set errno
+            ldg     GS_VOTING_UNIT,a8[1],s16[0];  // read voting unit global state
+            extr    s16[0],a64[1],a16[0];  // and store it in a64[1]
+            cns     OS_VOTE,a16[1];  // count output vote rights
+            cnv     a16[1],a64[2];  // and store the count in a64[2]
+            mul.uc  a64[2],a64[1];  // result is stored in a64[1]
+            test;  // fails in case of an overflow
+            eq.n    a64[0],a64[1];  // check that the vote rights cover the issued supply
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_governance_lib();
+        assembled.verify_offsets(&[("FN_GOVERNANCE_GENESIS_OFFSET", FN_GOVERNANCE_GENESIS_OFFSET)]);
+        GOVERNANCE_LIB.verify("GOVERNANCE_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,224 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation libraries of the inflatable fungible asset schema.
+
+use crate::PrecompiledLib;
+
+#[cfg(any(feature = "ifa", feature = "stablecoin"))]
+pub(super) const FN_IFA_GENESIS_OFFSET: u16 = 0;
+pub(super) const FN_IFA_TRANSFER_OFFSET: u16 = 0;
+#[cfg(feature = "ifa")]
+pub(super) const FN_IFA_INFLATION_OFFSET: u16 = 0;
+/// Offset of the transfer validation branch handling transitions without input replace rights.
+pub(super) const FN_IFA_TRANSFER_NO_REPLACE_OFFSET: u16 = 40;
+
+#[cfg(any(feature = "ifa", feature = "stablecoin"))]
+pub(super) const IFA_LIB_GENESIS: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x04, 0x00, 0xc8, 0xdb, 0x07,
+        0x11, 0x39, 0x31, 0x01, 0x21, 0x08, 0x60, 0x01, 0xd1, 0xaa, 0x0f, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x01, 0x1e],
+    id: [
+        0xf1, 0x9a, 0x0a, 0xff, 0x79, 0x88, 0xda, 0xf6, 0xba, 0x1a, 0x21, 0x34, 0x41, 0x68, 0xaf,
+        0x80, 0x39, 0x58, 0x48, 0xc5, 0x29, 0x01, 0x38, 0x8b, 0x57, 0xb0, 0x6e, 0x6d, 0xde, 0x6b,
+        0xe5, 0xce,
+    ],
+};
+
+#[cfg(all(test, any(feature = "ifa", feature = "stablecoin")))]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_ifa_lib_genesis() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_INFLATION_MISMATCH, ERRNO_ISSUED_MISMATCH};
+    use crate::{GS_ISSUED_SUPPLY, GS_MAX_SUPPLY, OS_ASSET, OS_INFLATION};
+
+    crate::asm::assemble("inflatable asset genesis", |_| {
+        vec![("FN_IFA_GENESIS_OFFSET", rgbasm! {
+            // Set common offsets
+            put     a8[1],0;
+            put     a16[0],0;
+
+            // Check reported issued supply against sum of asset allocations in output
+            put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+            ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_ASSET;  // check sum of assets assignments in output equals a64[0]
+            test;
+
+            // Check that sum of inflation rights = max supply - issued supply
+            put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+            ldg     GS_MAX_SUPPLY,a8[1],s16[1];  // read max supply global state
+            extr    s16[1],a64[1],a16[0];  // and store it in a64[1]
+            sub.uc  a64[1],a64[0];  // issued supply is still in a64[0], result overwrites a64[0]
+            test;  // fails if result is <0
+            sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+            test;
+
+            ret;
+        })]
+    })
+}
+
+pub(super) const IFA_LIB_TRANSFER: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xd0, 0xa0, 0x0f, 0x01, 0xd0, 0xaa, 0x0f, 0x01, 0xc0, 0xac, 0x0f,
+        0x00, 0xc1, 0xac, 0x0f, 0x01, 0x0b, 0x11, 0x01, 0x00, 0x19, 0x01, 0x22, 0x03, 0x28, 0x00,
+        0x0b, 0x00, 0x03, 0x00, 0x18, 0x09, 0x20, 0x1f, 0x01, 0x07, 0x0b, 0x00, 0x04, 0x00, 0x19,
+        0x09, 0x20, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x24, 0x23],
+    id: [
+        0x0b, 0x07, 0x9a, 0x8e, 0x17, 0xab, 0x01, 0x06, 0x81, 0xed, 0x50, 0xec, 0x8b, 0x10, 0xe6,
+        0xb5, 0x5a, 0xbc, 0x89, 0x8e, 0x3c, 0x25, 0x89, 0x38, 0xf7, 0xb0, 0x6f, 0xb1, 0x41, 0xea,
+        0x72, 0x39,
+    ],
+};
+
+// Replace rights are only declared with the IFA schema, whose tests verify the shared libraries
+#[cfg(all(test, feature = "ifa"))]
+fn assemble_ifa_lib_transfer() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_NON_EQUAL_IN_OUT, ERRNO_REPLACE_HIDDEN_BURN, ERRNO_REPLACE_NO_INPUT};
+    use crate::{OS_ASSET, OS_INFLATION, OS_REPLACE};
+
+    crate::asm::assemble("inflatable asset transfer", |labels| {
+        let no_replace = labels.offset("FN_IFA_TRANSFER_NO_REPLACE_OFFSET");
+        vec![
+            ("FN_IFA_TRANSFER_OFFSET", rgbasm! {
+                // Checking that the sum of inputs is equal to the sum of outputs
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                svs     OS_ASSET;  // verify sum
+                test;  // check it didn't fail
+                svs     OS_INFLATION;  // verify sum
+                test;  // check it didn't fail
+
+                // Replace rights validation
+                cnp     OS_REPLACE,a16[0];  // count input replace rights
+                cns     OS_REPLACE,a16[1];  // count output replace rights
+                // Check if input count is 0
+                put     a16[2],0;  // store 0 in a16[2]
+                eq.n    a16[0],a16[2];  // check if input_count == 0
+                jif     no_replace;  // jump if input_count == 0
+                // Input count > 0, check that output count >= input count
+                put     a8[0],ERRNO_REPLACE_HIDDEN_BURN;  // set errno
+                lt.u    a16[1],a16[0];  // output_count < input_count
+                inv     st0;  // output_count >= input_count
+                test;  // fail if output_count < input_count
+                ret;  // return execution flow
+            }),
+            ("FN_IFA_TRANSFER_NO_REPLACE_OFFSET", rgbasm! {
+                // Input count is 0, output count must also be 0
+                put     a8[0],ERRNO_REPLACE_NO_INPUT;  // set errno
+                eq.n    a16[1],a16[0];  // check if output_count == input_count
+                test;  // fail if output_count != input_count (=0)
+                ret;  // return execution flow
+            }),
+        ]
+    })
+}
+
+#[cfg(feature = "ifa")]
+pub(super) const IFA_LIB_INFLATION: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x11, 0x03, 0x0b, 0x0b, 0x00, 0x04, 0x00,
+        0xca, 0xe8, 0x03, 0x00, 0x39, 0x30, 0x00, 0xd1, 0xaa, 0x0f, 0x01, 0x0b, 0x00, 0x05, 0x00,
+        0x20, 0x08, 0x60, 0x01, 0xd2, 0xaa, 0x0f, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x01, 0x1e, 0x1f],
+    id: [
+        0xf2, 0x3b, 0x7f, 0x69, 0xf6, 0x7f, 0xc3, 0x6c, 0x73, 0x00, 0x67, 0x9c, 0x7a, 0xb2, 0x43,
+        0x0c, 0xe8, 0x22, 0x08, 0x83, 0x61, 0xe1, 0x48, 0x73, 0x8e, 0x82, 0xb8, 0xf7, 0x5a, 0xb1,
+        0xdc, 0xaa,
+    ],
+};
+
+#[cfg(all(test, feature = "ifa"))]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_ifa_lib_inflation() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_INFLATION_EXCEEDS_ALLOWANCE, ERRNO_INFLATION_MISMATCH, ERRNO_ISSUED_MISMATCH,
+    };
+    use crate::{GS_ISSUED_SUPPLY, MS_ALLOWED_INFLATION, OS_ASSET, OS_INFLATION};
+
+    crate::asm::assemble("inflatable asset inflation", |_| {
+        vec![("FN_IFA_INFLATION_OFFSET", rgbasm! {
+            // Set common offsets
+            put     a8[1],0;
+            put     a16[0],0;
+
+            // Check reported issued supply equals sum of asset allocations in output
+            put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+            ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_ASSET;  // check sum of asset allocations in output equals issued_supply
+            test;
+            cpy     a64[0],a64[1];  // store issued supply in a64[1] for later
+
+            // Check reported allowed inflation equals sum of inflation rights in output
+            put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+            ldm     MS_ALLOWED_INFLATION,s16[0];  // read allowed inflation global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+            test;
+
+            // Check that input inflation rights equals issued supply + allowed inflation
+            put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+            add.uc  a64[1],a64[0];  // result is stored in a64[0]
+            test;  // fails in case of an overflow
+            sps     OS_INFLATION;  // check sum of inflation rights in input equals a64[0]
+            test;
+
+            ret;
+        })]
+    })
+}
+
+#[cfg(all(test, any(feature = "ifa", feature = "stablecoin")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let genesis = assemble_ifa_lib_genesis();
+        genesis.verify_offsets(&[("FN_IFA_GENESIS_OFFSET", FN_IFA_GENESIS_OFFSET)]);
+        IFA_LIB_GENESIS.verify("IFA_LIB_GENESIS", genesis.lib);
+        #[cfg(feature = "ifa")]
+        {
+            let transfer = assemble_ifa_lib_transfer();
+            transfer.verify_offsets(&[
+                ("FN_IFA_TRANSFER_OFFSET", FN_IFA_TRANSFER_OFFSET),
+                ("FN_IFA_TRANSFER_NO_REPLACE_OFFSET", FN_IFA_TRANSFER_NO_REPLACE_OFFSET),
+            ]);
+            IFA_LIB_TRANSFER.verify("IFA_LIB_TRANSFER", transfer.lib);
+            let inflation = assemble_ifa_lib_inflation();
+            inflation.verify_offsets(&[("FN_IFA_INFLATION_OFFSET", FN_IFA_INFLATION_OFFSET)]);
+            IFA_LIB_INFLATION.verify("IFA_LIB_INFLATION", inflation.lib);
+        }
+    }
+}
@@ -0,0 +1,817 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Canonical AluVM validation libraries of the schemata.
+//!
+//! Every library is defined once, in the submodule of the schema it was written for, and schemas
+//! reference its subroutines through named [`EntryPoint`]s. A schema reusing the validation code
+//! of another one, like CFA does with the NIA library, thus embeds the very same library rather
+//! than a copy of it. [`SharedLibs`] owns the libraries of all enabled schemata and collects the
+//! ones referenced by a schema into its scripts.
+
+// Entry points are unused when no schema feature is enabled
+#![cfg_attr(not(any_schema), allow(dead_code, unused_imports))]
+
+#[cfg(feature = "bond")]
+mod bond;
+#[cfg(feature = "carbon")]
+mod carbon;
+#[cfg(feature = "claim")]
+mod claim;
+#[cfg(any(feature = "collection", feature = "ticket", feature = "edition", feature = "registry"))]
+mod collection;
+#[cfg(feature = "credential")]
+mod credential;
+#[cfg(feature = "edition")]
+mod edition;
+#[cfg(feature = "escrow")]
+mod escrow;
+#[cfg(feature = "eifa")]
+mod eifa;
+#[cfg(feature = "fee")]
+mod fee;
+#[cfg(feature = "freezable")]
+mod freezable;
+#[cfg(feature = "fua")]
+mod fua;
+#[cfg(feature = "governance")]
+mod governance;
+#[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+mod ifa;
+#[cfg(any(
+    feature = "nia",
+    feature = "cfa",
+    feature = "ria",
+    feature = "sba",
+    feature = "bond",
+    feature = "governance",
+    feature = "vesting",
+    feature = "timelock",
+    feature = "freezable",
+    feature = "wbtc",
+    feature = "dividend",
+    feature = "escrow",
+    feature = "carbon",
+    feature = "claim",
+    feature = "pausable",
+    feature = "option",
+    feature = "burnable",
+    feature = "rebasing"
+))]
+mod nia;
+#[cfg(any(feature = "pfa", feature = "pfan"))]
+mod pfa;
+#[cfg(feature = "option")]
+mod option;
+#[cfg(feature = "pausable")]
+mod pausable;
+#[cfg(feature = "pfan")]
+mod pfan;
+#[cfg(feature = "uda")]
+mod reattach;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "rebasing")]
+mod rebasing;
+#[cfg(feature = "rental")]
+mod rental;
+#[cfg(any(
+    feature = "ifa",
+    feature = "ria",
+    feature = "sba",
+    feature = "wbtc",
+    feature = "burnable"
+))]
+mod ria;
+#[cfg(feature = "sba")]
+mod sba;
+#[cfg(feature = "sfa")]
+mod sfa;
+#[cfg(feature = "stablecoin")]
+mod stablecoin;
+#[cfg(feature = "subscription")]
+mod subscription;
+#[cfg(feature = "swap")]
+mod swap;
+#[cfg(feature = "ticket")]
+mod ticket;
+#[cfg(feature = "tifa")]
+mod tifa;
+#[cfg(feature = "timelock")]
+mod timelock;
+#[cfg(feature = "vesting")]
+mod vesting;
+#[cfg(feature = "wbtc")]
+mod wbtc;
+#[cfg(any(feature = "uda", feature = "provenance", feature = "subscription", feature = "rental"))]
+mod uda;
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use aluvm::library::{Lib, LibId, LibSite};
+use amplify::confinement::Confined;
+use rgbstd::validation::Scripts;
+
+use crate::{verify_entry_point, PrecompiledLib};
+
+/// Named entry point of a validation subroutine in one of the [`SharedLibs`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct EntryPoint {
+    name: &'static str,
+    lib: PrecompiledLib,
+    offset: u16,
+}
+
+impl EntryPoint {
+    const fn new(name: &'static str, lib: PrecompiledLib, offset: u16) -> Self {
+        EntryPoint { name, lib, offset }
+    }
+
+    /// Name of the entry point, matching the name of its constant.
+    pub fn name(self) -> &'static str { self.name }
+
+    /// Id of the library containing the subroutine.
+    pub fn lib_id(self) -> LibId { self.lib.id() }
+
+    /// Offset of the subroutine in the code segment of its library.
+    pub fn offset(self) -> u16 { self.offset }
+
+    /// Library site to use as a validator in a schema.
+    pub fn lib_site(self) -> LibSite { LibSite::with(self.offset, self.lib_id()) }
+
+    /// Library containing the subroutine, owned by the [`SharedLibs`] registry.
+    pub fn lib(self) -> &'static Lib {
+        SharedLibs::get()
+            .lib(self.lib_id())
+            .expect("entry point library is registered")
+    }
+
+    /// Asserts that the subroutine begins with the given instructions, see
+    /// [`verify_entry_point`].
+    pub(crate) fn verify(self, opcodes: &[(u16, u8)]) {
+        verify_entry_point(self.lib(), self.offset, opcodes)
+    }
+}
+
+/// NIA genesis validation, also used by CFA, RIA, SBA, bonds, governance, timelocked, freezable,
+/// dividend-paying, escrow, carbon credit, claimable, pausable, burnable and rebasing assets, and
+/// by options.
+#[cfg(any(
+    feature = "nia",
+    feature = "cfa",
+    feature = "ria",
+    feature = "sba",
+    feature = "bond",
+    feature = "governance",
+    feature = "timelock",
+    feature = "freezable",
+    feature = "dividend",
+    feature = "escrow",
+    feature = "carbon",
+    feature = "claim",
+    feature = "pausable",
+    feature = "option",
+    feature = "burnable",
+    feature = "rebasing"
+))]
+pub const NIA_GENESIS: EntryPoint =
+    EntryPoint::new("NIA_GENESIS", nia::NIA_LIB, nia::FN_NIA_GENESIS_OFFSET);
+/// NIA transfer validation, also used by CFA, RIA, bonds, governance, vesting, wrapped BTC,
+/// dividend-paying, escrow, carbon credit, claimable, burnable and rebasing assets, and by options.
+#[cfg(any(
+    feature = "nia",
+    feature = "cfa",
+    feature = "ria",
+    feature = "sba",
+    feature = "bond",
+    feature = "governance",
+    feature = "vesting",
+    feature = "wbtc",
+    feature = "dividend",
+    feature = "escrow",
+    feature = "carbon",
+    feature = "claim",
+    feature = "option",
+    feature = "burnable",
+    feature = "rebasing"
+))]
+pub const NIA_TRANSFER: EntryPoint =
+    EntryPoint::new("NIA_TRANSFER", nia::NIA_LIB, nia::FN_NIA_TRANSFER_OFFSET);
+/// UDA genesis validation, also used by provenance, subscription and rental tokens.
+#[cfg(any(feature = "uda", feature = "provenance", feature = "subscription", feature = "rental"))]
+pub const UDA_GENESIS: EntryPoint =
+    EntryPoint::new("UDA_GENESIS", uda::UDA_LIB, uda::FN_GENESIS_OFFSET);
+/// UDA transfer validation, also used by provenance, subscription and rental tokens.
+#[cfg(any(feature = "uda", feature = "provenance", feature = "subscription", feature = "rental"))]
+pub const UDA_TRANSFER: EntryPoint =
+    EntryPoint::new("UDA_TRANSFER", uda::UDA_LIB, uda::FN_TRANSFER_OFFSET);
+/// Token checks shared by UDA genesis and transfer validation, which jump into it.
+#[cfg(any(feature = "uda", feature = "provenance", feature = "subscription", feature = "rental"))]
+pub const UDA_SHARED: EntryPoint =
+    EntryPoint::new("UDA_SHARED", uda::UDA_LIB, uda::FN_SHARED_OFFSET);
+/// UDA validation of reattachments, checking that the published token data keeps the token index.
+#[cfg(feature = "uda")]
+pub const UDA_REATTACH: EntryPoint =
+    EntryPoint::new("UDA_REATTACH", reattach::REATTACH_LIB, reattach::FN_REATTACH_OFFSET);
+/// FUA genesis validation.
+#[cfg(feature = "fua")]
+pub const FUA_GENESIS: EntryPoint =
+    EntryPoint::new("FUA_GENESIS", fua::FUA_LIB, fua::FN_FUA_GENESIS_OFFSET);
+/// FUA transfer validation.
+#[cfg(feature = "fua")]
+pub const FUA_TRANSFER: EntryPoint =
+    EntryPoint::new("FUA_TRANSFER", fua::FUA_LIB, fua::FN_FUA_TRANSFER_OFFSET);
+/// Fraction checks shared by FUA genesis and transfer validation, which jump into it.
+#[cfg(feature = "fua")]
+pub const FUA_SHARED: EntryPoint =
+    EntryPoint::new("FUA_SHARED", fua::FUA_LIB, fua::FN_FUA_SHARED_OFFSET);
+/// UDC genesis validation, also used by the ticket schema.
+#[cfg(any(feature = "collection", feature = "ticket", feature = "edition", feature = "registry"))]
+pub const UDC_GENESIS: EntryPoint =
+    EntryPoint::new("UDC_GENESIS", collection::UDC_LIB, collection::FN_UDC_GENESIS_OFFSET);
+/// UDC transfer validation, also used by the ticket and limited edition schemata.
+#[cfg(any(feature = "collection", feature = "ticket", feature = "edition", feature = "registry"))]
+pub const UDC_TRANSFER: EntryPoint =
+    EntryPoint::new("UDC_TRANSFER", collection::UDC_LIB, collection::FN_UDC_TRANSFER_OFFSET);
+/// Token matching shared by UDC genesis and transfer validation, which jump into it.
+#[cfg(any(feature = "collection", feature = "ticket", feature = "edition", feature = "registry"))]
+pub const UDC_MATCH: EntryPoint =
+    EntryPoint::new("UDC_MATCH", collection::UDC_LIB, collection::FN_UDC_MATCH_OFFSET);
+/// PFA genesis validation, also used by multi-key permissioned assets.
+#[cfg(any(feature = "pfa", feature = "pfan"))]
+pub const PFA_GENESIS: EntryPoint =
+    EntryPoint::new("PFA_GENESIS", pfa::PFA_LIB_GENESIS, pfa::FN_PFA_GENESIS_OFFSET);
+/// PFA transfer validation, checking the issuer signature.
+#[cfg(feature = "pfa")]
+pub const PFA_TRANSITION: EntryPoint =
+    EntryPoint::new("PFA_TRANSITION", pfa::PFA_LIB_TRANSITION, pfa::FN_PFA_TRANSITION_OFFSET);
+/// IFA genesis validation, also used by the stablecoin schema.
+#[cfg(any(feature = "ifa", feature = "stablecoin"))]
+pub const IFA_GENESIS: EntryPoint =
+    EntryPoint::new("IFA_GENESIS", ifa::IFA_LIB_GENESIS, ifa::FN_IFA_GENESIS_OFFSET);
+/// IFA transfer, burn and replace validation, also used by the stablecoin, threshold and epoch
+/// inflatable asset schemata.
+#[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+pub const IFA_TRANSFER: EntryPoint =
+    EntryPoint::new("IFA_TRANSFER", ifa::IFA_LIB_TRANSFER, ifa::FN_IFA_TRANSFER_OFFSET);
+/// Branch of the IFA transfer validation handling transitions without input replace rights.
+#[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+pub const IFA_TRANSFER_NO_REPLACE: EntryPoint = EntryPoint::new(
+    "IFA_TRANSFER_NO_REPLACE",
+    ifa::IFA_LIB_TRANSFER,
+    ifa::FN_IFA_TRANSFER_NO_REPLACE_OFFSET,
+);
+/// IFA inflation validation.
+#[cfg(feature = "ifa")]
+pub const IFA_INFLATION: EntryPoint =
+    EntryPoint::new("IFA_INFLATION", ifa::IFA_LIB_INFLATION, ifa::FN_IFA_INFLATION_OFFSET);
+/// RIA burn validation, also used by IFA, soulbound, wrapped BTC and burnable assets.
+#[cfg(any(
+    feature = "ifa",
+    feature = "ria",
+    feature = "sba",
+    feature = "wbtc",
+    feature = "burnable"
+))]
+pub const RIA_BURN: EntryPoint = EntryPoint::new("RIA_BURN", ria::RIA_LIB, ria::FN_RIA_BURN_OFFSET);
+/// RIA reissue validation.
+#[cfg(feature = "ria")]
+pub const RIA_REISSUE: EntryPoint =
+    EntryPoint::new("RIA_REISSUE", ria::RIA_LIB, ria::FN_RIA_REISSUE_OFFSET);
+/// SBA transfer validation, rejecting any asset assignment.
+#[cfg(feature = "sba")]
+pub const SBA_TRANSFER: EntryPoint =
+    EntryPoint::new("SBA_TRANSFER", sba::SBA_LIB, sba::FN_SBA_TRANSFER_OFFSET);
+/// SBA revocation validation, checking that a single UTXO is revoked.
+#[cfg(feature = "sba")]
+pub const SBA_REVOKE: EntryPoint =
+    EntryPoint::new("SBA_REVOKE", sba::SBA_LIB, sba::FN_SBA_REVOKE_OFFSET);
+/// SFA genesis validation.
+#[cfg(feature = "sfa")]
+pub const SFA_GENESIS: EntryPoint =
+    EntryPoint::new("SFA_GENESIS", sfa::SFA_LIB, sfa::FN_SFA_GENESIS_OFFSET);
+/// SFA transfer validation.
+#[cfg(feature = "sfa")]
+pub const SFA_TRANSFER: EntryPoint =
+    EntryPoint::new("SFA_TRANSFER", sfa::SFA_LIB, sfa::FN_SFA_TRANSFER_OFFSET);
+/// Per-token balance check of SFA transfer validation, which jumps into it.
+#[cfg(feature = "sfa")]
+pub const SFA_BALANCE: EntryPoint =
+    EntryPoint::new("SFA_BALANCE", sfa::SFA_LIB, sfa::FN_SFA_BALANCE_OFFSET);
+/// Stablecoin inflation validation, checking the oracle signature.
+#[cfg(feature = "stablecoin")]
+pub const STABLECOIN_INFLATION: EntryPoint = EntryPoint::new(
+    "STABLECOIN_INFLATION",
+    stablecoin::STABLECOIN_LIB_INFLATION,
+    stablecoin::FN_STABLECOIN_INFLATION_OFFSET,
+);
+/// Stablecoin redeem validation, checking the oracle signature.
+#[cfg(feature = "stablecoin")]
+pub const STABLECOIN_REDEEM: EntryPoint = EntryPoint::new(
+    "STABLECOIN_REDEEM",
+    stablecoin::STABLECOIN_LIB_REDEEM,
+    stablecoin::FN_STABLECOIN_REDEEM_OFFSET,
+);
+
+/// Bond redeem validation.
+#[cfg(feature = "bond")]
+pub const BOND_REDEEM: EntryPoint =
+    EntryPoint::new("BOND_REDEEM", bond::BOND_LIB, bond::FN_BOND_REDEEM_OFFSET);
+
+/// Governance asset genesis validation.
+#[cfg(feature = "governance")]
+pub const GOVERNANCE_GENESIS: EntryPoint = EntryPoint::new(
+    "GOVERNANCE_GENESIS",
+    governance::GOVERNANCE_LIB,
+    governance::FN_GOVERNANCE_GENESIS_OFFSET,
+);
+/// Ticket redeem validation, checking the redeemed ticket is the spent one.
+#[cfg(feature = "ticket")]
+pub const TICKET_REDEEM: EntryPoint =
+    EntryPoint::new("TICKET_REDEEM", ticket::TICKET_LIB, ticket::FN_TICKET_REDEEM_OFFSET);
+
+/// Vesting asset genesis validation.
+#[cfg(feature = "vesting")]
+pub const VESTING_GENESIS: EntryPoint =
+    EntryPoint::new("VESTING_GENESIS", vesting::VESTING_LIB, vesting::FN_VESTING_GENESIS_OFFSET);
+/// Vesting asset release validation.
+#[cfg(feature = "vesting")]
+pub const VESTING_RELEASE: EntryPoint =
+    EntryPoint::new("VESTING_RELEASE", vesting::VESTING_LIB, vesting::FN_VESTING_RELEASE_OFFSET);
+/// Timelocked asset transfer validation, checking the lock time committed by the transfer.
+#[cfg(feature = "timelock")]
+pub const TIMELOCK_TRANSFER: EntryPoint = EntryPoint::new(
+    "TIMELOCK_TRANSFER",
+    timelock::TIMELOCK_LIB,
+    timelock::FN_TIMELOCK_TRANSFER_OFFSET,
+);
+/// Multi-key permissioned asset transfer validation, checking the signature of any authorized key.
+#[cfg(feature = "pfan")]
+pub const PFAN_TRANSITION: EntryPoint =
+    EntryPoint::new("PFAN_TRANSITION", pfan::PFAN_LIB, pfan::FN_PFAN_TRANSITION_OFFSET);
+/// Freezable asset transfer validation, checking that the spent UTXOs are not frozen.
+#[cfg(feature = "freezable")]
+pub const FREEZABLE_TRANSFER: EntryPoint = EntryPoint::new(
+    "FREEZABLE_TRANSFER",
+    freezable::FREEZABLE_LIB,
+    freezable::FN_FREEZABLE_TRANSFER_OFFSET,
+);
+/// Freezable asset freeze validation, checking that the frozen UTXOs are not frozen yet.
+#[cfg(feature = "freezable")]
+pub const FREEZABLE_FREEZE: EntryPoint = EntryPoint::new(
+    "FREEZABLE_FREEZE",
+    freezable::FREEZABLE_LIB,
+    freezable::FN_FREEZABLE_FREEZE_OFFSET,
+);
+/// Threshold inflatable asset genesis validation, checking the approval threshold.
+#[cfg(feature = "tifa")]
+pub const TIFA_GENESIS: EntryPoint =
+    EntryPoint::new("TIFA_GENESIS", tifa::TIFA_LIB, tifa::FN_TIFA_GENESIS_OFFSET);
+/// Threshold inflatable asset approval validation, checking the signature of the next approving
+/// key.
+#[cfg(feature = "tifa")]
+pub const TIFA_APPROVE: EntryPoint =
+    EntryPoint::new("TIFA_APPROVE", tifa::TIFA_LIB, tifa::FN_TIFA_APPROVE_OFFSET);
+/// Threshold inflatable asset inflation validation, checking the collected approvals.
+#[cfg(feature = "tifa")]
+pub const TIFA_INFLATION: EntryPoint =
+    EntryPoint::new("TIFA_INFLATION", tifa::TIFA_LIB, tifa::FN_TIFA_INFLATION_OFFSET);
+/// Wrapped BTC asset genesis and mint validation, checking the reserve attestation.
+#[cfg(feature = "wbtc")]
+pub const WBTC_ISSUANCE: EntryPoint =
+    EntryPoint::new("WBTC_ISSUANCE", wbtc::WBTC_LIB, wbtc::FN_WBTC_ISSUANCE_OFFSET);
+/// Transfer-fee asset genesis validation, checking the fee rate.
+#[cfg(feature = "fee")]
+pub const FEE_GENESIS: EntryPoint =
+    EntryPoint::new("FEE_GENESIS", fee::FEE_LIB, fee::FN_FEE_GENESIS_OFFSET);
+/// Transfer-fee asset transfer validation, checking the fee allocated by the transfer.
+#[cfg(feature = "fee")]
+pub const FEE_TRANSFER: EntryPoint =
+    EntryPoint::new("FEE_TRANSFER", fee::FEE_LIB, fee::FN_FEE_TRANSFER_OFFSET);
+/// Transfer-fee asset collection validation, turning fee allocations into the asset.
+#[cfg(feature = "fee")]
+pub const FEE_COLLECT: EntryPoint =
+    EntryPoint::new("FEE_COLLECT", fee::FEE_LIB, fee::FN_FEE_COLLECT_OFFSET);
+/// Escrow asset validation of the units moved into escrow.
+#[cfg(feature = "escrow")]
+pub const ESCROW_LOCK: EntryPoint =
+    EntryPoint::new("ESCROW_LOCK", escrow::ESCROW_LIB, escrow::FN_ESCROW_LOCK_OFFSET);
+/// Escrow asset validation of releases and refunds, paying out the escrowed units.
+#[cfg(feature = "escrow")]
+pub const ESCROW_SETTLE: EntryPoint =
+    EntryPoint::new("ESCROW_SETTLE", escrow::ESCROW_LIB, escrow::FN_ESCROW_SETTLE_OFFSET);
+/// Swap order genesis validation, letting the validator add the order terms to the contract
+/// state.
+#[cfg(feature = "swap")]
+pub const SWAP_GENESIS: EntryPoint =
+    EntryPoint::new("SWAP_GENESIS", swap::SWAP_LIB, swap::FN_SWAP_GENESIS_OFFSET);
+/// Swap order validation of fills, checking the maker signature and the filled amount.
+#[cfg(feature = "swap")]
+pub const SWAP_FILL: EntryPoint =
+    EntryPoint::new("SWAP_FILL", swap::SWAP_LIB, swap::FN_SWAP_FILL_OFFSET);
+/// Swap order validation of cancellations, checking the maker signature.
+#[cfg(feature = "swap")]
+pub const SWAP_CANCEL: EntryPoint =
+    EntryPoint::new("SWAP_CANCEL", swap::SWAP_LIB, swap::FN_SWAP_CANCEL_OFFSET);
+/// Carbon credit validation of retirements, checking the retired supply.
+#[cfg(feature = "carbon")]
+pub const CARBON_RETIRE: EntryPoint =
+    EntryPoint::new("CARBON_RETIRE", carbon::CARBON_LIB, carbon::FN_CARBON_RETIRE_OFFSET);
+/// Subscription token validation of renewals, checking the issuer signature and the new expiry.
+#[cfg(feature = "subscription")]
+pub const SUBSCRIPTION_RENEW: EntryPoint = EntryPoint::new(
+    "SUBSCRIPTION_RENEW",
+    subscription::SUBSCRIPTION_LIB,
+    subscription::FN_SUBSCRIPTION_RENEW_OFFSET,
+);
+/// Limited edition genesis validation, letting the validator add the edition cap to the contract
+/// state.
+#[cfg(feature = "edition")]
+pub const EDITION_GENESIS: EntryPoint =
+    EntryPoint::new("EDITION_GENESIS", edition::EDITION_LIB, edition::FN_EDITION_GENESIS_OFFSET);
+/// Limited edition validation of mints, checking the edition number against the cap.
+#[cfg(feature = "edition")]
+pub const EDITION_MINT: EntryPoint =
+    EntryPoint::new("EDITION_MINT", edition::EDITION_LIB, edition::FN_EDITION_MINT_OFFSET);
+/// Epoch inflatable asset genesis validation, also checking that no supply is recorded as issued
+/// within an epoch yet.
+#[cfg(feature = "eifa")]
+pub const EIFA_GENESIS: EntryPoint =
+    EntryPoint::new("EIFA_GENESIS", eifa::EIFA_LIB, eifa::FN_EIFA_GENESIS_OFFSET);
+/// Epoch inflatable asset inflation validation, also checking the supply issued within the epoch of
+/// the inflation against the epoch cap.
+#[cfg(feature = "eifa")]
+pub const EIFA_INFLATION: EntryPoint =
+    EntryPoint::new("EIFA_INFLATION", eifa::EIFA_LIB, eifa::FN_EIFA_INFLATION_OFFSET);
+/// Claimable asset validation of claims, moving units of the asset into claims on the issuer.
+#[cfg(feature = "claim")]
+pub const CLAIM_LOCK: EntryPoint =
+    EntryPoint::new("CLAIM_LOCK", claim::CLAIM_LIB, claim::FN_CLAIM_LOCK_OFFSET);
+/// Claimable asset validation of collections, checking the issuer signature and turning claims
+/// into units of the asset.
+#[cfg(feature = "claim")]
+pub const CLAIM_COLLECT: EntryPoint =
+    EntryPoint::new("CLAIM_COLLECT", claim::CLAIM_LIB, claim::FN_CLAIM_COLLECT_OFFSET);
+/// Name registry validation of registrations, checking that the name is normalized and not taken
+/// and that it's bound to the next token.
+#[cfg(feature = "registry")]
+pub const REGISTRY_REGISTER: EntryPoint = EntryPoint::new(
+    "REGISTRY_REGISTER",
+    registry::REGISTRY_LIB,
+    registry::FN_REGISTRY_REGISTER_OFFSET,
+);
+/// Pausable asset transfer validation, rejecting transfers while the contract is paused.
+#[cfg(feature = "pausable")]
+pub const PAUSABLE_TRANSFER: EntryPoint = EntryPoint::new(
+    "PAUSABLE_TRANSFER",
+    pausable::PAUSABLE_LIB,
+    pausable::FN_PAUSABLE_TRANSFER_OFFSET,
+);
+/// Pausable asset validation of pause toggles, checking that they negate the paused flag.
+#[cfg(feature = "pausable")]
+pub const PAUSABLE_TOGGLE: EntryPoint =
+    EntryPoint::new("PAUSABLE_TOGGLE", pausable::PAUSABLE_LIB, pausable::FN_PAUSABLE_TOGGLE_OFFSET);
+/// Rental token validation of leases, checking that the previous lease was reclaimed.
+#[cfg(feature = "rental")]
+pub const RENTAL_LEASE: EntryPoint =
+    EntryPoint::new("RENTAL_LEASE", rental::RENTAL_LIB, rental::FN_RENTAL_LEASE_OFFSET);
+/// Rental token validation of reclaims, checking that they end the current lease.
+#[cfg(feature = "rental")]
+pub const RENTAL_RECLAIM: EntryPoint =
+    EntryPoint::new("RENTAL_RECLAIM", rental::RENTAL_LIB, rental::FN_RENTAL_RECLAIM_OFFSET);
+/// Option validation of exercises, checking that the exercised supply matches the options
+/// destroyed.
+#[cfg(feature = "option")]
+pub const OPTION_EXERCISE: EntryPoint =
+    EntryPoint::new("OPTION_EXERCISE", option::OPTION_LIB, option::FN_OPTION_EXERCISE_OFFSET);
+/// Option validation of lapses, checking that the lapsed supply matches the options destroyed.
+#[cfg(feature = "option")]
+pub const OPTION_LAPSE: EntryPoint =
+    EntryPoint::new("OPTION_LAPSE", option::OPTION_LIB, option::FN_OPTION_LAPSE_OFFSET);
+/// Verifiable credential genesis and revocation validation, letting the validator add the issuer
+/// pubkey and the revocations to the contract state.
+#[cfg(feature = "credential")]
+pub const CREDENTIAL_RECORD: EntryPoint = EntryPoint::new(
+    "CREDENTIAL_RECORD",
+    credential::CREDENTIAL_LIB_RECORD,
+    credential::FN_CREDENTIAL_RECORD_OFFSET,
+);
+/// Verifiable credential validation of attestations, checking the issuer signature and that the
+/// credential isn't revoked.
+#[cfg(feature = "credential")]
+pub const CREDENTIAL_ATTEST: EntryPoint = EntryPoint::new(
+    "CREDENTIAL_ATTEST",
+    credential::CREDENTIAL_LIB,
+    credential::FN_CREDENTIAL_ATTEST_OFFSET,
+);
+/// Rebasing asset validation of rebases, checking the issuer signature and that the factor isn't
+/// zero.
+#[cfg(feature = "rebasing")]
+pub const REBASING_REBASE: EntryPoint =
+    EntryPoint::new("REBASING_REBASE", rebasing::REBASING_LIB, rebasing::FN_REBASING_REBASE_OFFSET);
+
+/// Entry points of all enabled schemata.
+pub const ENTRY_POINTS: &[EntryPoint] = &[
+    #[cfg(any(
+        feature = "nia",
+        feature = "cfa",
+        feature = "ria",
+        feature = "sba",
+        feature = "bond",
+        feature = "governance",
+        feature = "timelock",
+        feature = "freezable",
+        feature = "dividend",
+        feature = "escrow",
+        feature = "carbon",
+        feature = "claim",
+        feature = "pausable",
+        feature = "option",
+        feature = "burnable",
+        feature = "rebasing"
+    ))]
+    NIA_GENESIS,
+    #[cfg(any(
+        feature = "nia",
+        feature = "cfa",
+        feature = "ria",
+        feature = "sba",
+        feature = "bond",
+        feature = "governance",
+        feature = "vesting",
+        feature = "wbtc",
+        feature = "dividend",
+        feature = "escrow",
+        feature = "carbon",
+        feature = "claim",
+        feature = "option",
+        feature = "burnable",
+        feature = "rebasing"
+    ))]
+    NIA_TRANSFER,
+    #[cfg(any(
+        feature = "uda",
+        feature = "provenance",
+        feature = "subscription",
+        feature = "rental"
+    ))]
+    UDA_GENESIS,
+    #[cfg(any(
+        feature = "uda",
+        feature = "provenance",
+        feature = "subscription",
+        feature = "rental"
+    ))]
+    UDA_TRANSFER,
+    #[cfg(any(
+        feature = "uda",
+        feature = "provenance",
+        feature = "subscription",
+        feature = "rental"
+    ))]
+    UDA_SHARED,
+    #[cfg(feature = "uda")]
+    UDA_REATTACH,
+    #[cfg(feature = "fua")]
+    FUA_GENESIS,
+    #[cfg(feature = "fua")]
+    FUA_TRANSFER,
+    #[cfg(feature = "fua")]
+    FUA_SHARED,
+    #[cfg(any(
+        feature = "collection",
+        feature = "ticket",
+        feature = "edition",
+        feature = "registry"
+    ))]
+    UDC_GENESIS,
+    #[cfg(any(
+        feature = "collection",
+        feature = "ticket",
+        feature = "edition",
+        feature = "registry"
+    ))]
+    UDC_TRANSFER,
+    #[cfg(any(
+        feature = "collection",
+        feature = "ticket",
+        feature = "edition",
+        feature = "registry"
+    ))]
+    UDC_MATCH,
+    #[cfg(any(feature = "pfa", feature = "pfan"))]
+    PFA_GENESIS,
+    #[cfg(feature = "pfa")]
+    PFA_TRANSITION,
+    #[cfg(any(feature = "ifa", feature = "stablecoin"))]
+    IFA_GENESIS,
+    #[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+    IFA_TRANSFER,
+    #[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+    IFA_TRANSFER_NO_REPLACE,
+    #[cfg(feature = "ifa")]
+    IFA_INFLATION,
+    #[cfg(any(
+        feature = "ifa",
+        feature = "ria",
+        feature = "sba",
+        feature = "wbtc",
+        feature = "burnable"
+    ))]
+    RIA_BURN,
+    #[cfg(feature = "ria")]
+    RIA_REISSUE,
+    #[cfg(feature = "sba")]
+    SBA_TRANSFER,
+    #[cfg(feature = "sba")]
+    SBA_REVOKE,
+    #[cfg(feature = "sfa")]
+    SFA_GENESIS,
+    #[cfg(feature = "sfa")]
+    SFA_TRANSFER,
+    #[cfg(feature = "sfa")]
+    SFA_BALANCE,
+    #[cfg(feature = "stablecoin")]
+    STABLECOIN_INFLATION,
+    #[cfg(feature = "stablecoin")]
+    STABLECOIN_REDEEM,
+    #[cfg(feature = "bond")]
+    BOND_REDEEM,
+    #[cfg(feature = "governance")]
+    GOVERNANCE_GENESIS,
+    #[cfg(feature = "ticket")]
+    TICKET_REDEEM,
+    #[cfg(feature = "vesting")]
+    VESTING_GENESIS,
+    #[cfg(feature = "vesting")]
+    VESTING_RELEASE,
+    #[cfg(feature = "timelock")]
+    TIMELOCK_TRANSFER,
+    #[cfg(feature = "pfan")]
+    PFAN_TRANSITION,
+    #[cfg(feature = "freezable")]
+    FREEZABLE_TRANSFER,
+    #[cfg(feature = "freezable")]
+    FREEZABLE_FREEZE,
+    #[cfg(feature = "tifa")]
+    TIFA_GENESIS,
+    #[cfg(feature = "tifa")]
+    TIFA_APPROVE,
+    #[cfg(feature = "tifa")]
+    TIFA_INFLATION,
+    #[cfg(feature = "wbtc")]
+    WBTC_ISSUANCE,
+    #[cfg(feature = "fee")]
+    FEE_GENESIS,
+    #[cfg(feature = "fee")]
+    FEE_TRANSFER,
+    #[cfg(feature = "fee")]
+    FEE_COLLECT,
+    #[cfg(feature = "escrow")]
+    ESCROW_LOCK,
+    #[cfg(feature = "escrow")]
+    ESCROW_SETTLE,
+    #[cfg(feature = "swap")]
+    SWAP_GENESIS,
+    #[cfg(feature = "swap")]
+    SWAP_FILL,
+    #[cfg(feature = "swap")]
+    SWAP_CANCEL,
+    #[cfg(feature = "carbon")]
+    CARBON_RETIRE,
+    #[cfg(feature = "subscription")]
+    SUBSCRIPTION_RENEW,
+    #[cfg(feature = "edition")]
+    EDITION_GENESIS,
+    #[cfg(feature = "edition")]
+    EDITION_MINT,
+    #[cfg(feature = "eifa")]
+    EIFA_GENESIS,
+    #[cfg(feature = "eifa")]
+    EIFA_INFLATION,
+    #[cfg(feature = "claim")]
+    CLAIM_LOCK,
+    #[cfg(feature = "claim")]
+    CLAIM_COLLECT,
+    #[cfg(feature = "registry")]
+    REGISTRY_REGISTER,
+    #[cfg(feature = "pausable")]
+    PAUSABLE_TRANSFER,
+    #[cfg(feature = "pausable")]
+    PAUSABLE_TOGGLE,
+    #[cfg(feature = "rental")]
+    RENTAL_LEASE,
+    #[cfg(feature = "rental")]
+    RENTAL_RECLAIM,
+    #[cfg(feature = "option")]
+    OPTION_EXERCISE,
+    #[cfg(feature = "option")]
+    OPTION_LAPSE,
+    #[cfg(feature = "credential")]
+    CREDENTIAL_RECORD,
+    #[cfg(feature = "credential")]
+    CREDENTIAL_ATTEST,
+    #[cfg(feature = "rebasing")]
+    REBASING_REBASE,
+];
+
+/// Registry of the canonical validation libraries of all enabled schemata.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SharedLibs {
+    libs: BTreeMap<LibId, Lib>,
+}
+
+impl SharedLibs {
+    /// Returns the registry, building the libraries from their byte code on first use.
+    pub fn get() -> &'static Self {
+        static LIBS: OnceLock<SharedLibs> = OnceLock::new();
+        LIBS.get_or_init(Self::build)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+    fn build() -> Self {
+        SharedLibs {
+            libs: ENTRY_POINTS
+                .iter()
+                .map(|entry_point| (entry_point.lib_id(), entry_point.lib.to_lib()))
+                .collect(),
+        }
+    }
+
+    /// Returns the library with the given id, if it is one of the registered libraries.
+    pub fn lib(&self, id: LibId) -> Option<&Lib> { self.libs.get(&id) }
+
+    /// Iterates over all registered libraries.
+    pub fn libs(&self) -> impl Iterator<Item = &Lib> { self.libs.values() }
+
+    /// Looks up an entry point by its name.
+    pub fn entry_point(&self, name: &str) -> Option<EntryPoint> {
+        ENTRY_POINTS
+            .iter()
+            .copied()
+            .find(|entry_point| entry_point.name == name)
+    }
+
+    /// Collects the libraries containing the given entry points into the scripts of a schema,
+    /// each of them once.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(entry_points = entry_points.len()))
+    )]
+    pub fn scripts(&self, entry_points: &[EntryPoint]) -> Scripts {
+        Confined::from_checked(
+            entry_points
+                .iter()
+                .map(|entry_point| (entry_point.lib_id(), entry_point.lib().clone()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        let libs = SharedLibs::get();
+        assert!(std::ptr::eq(libs, SharedLibs::get()));
+        #[cfg(all_schemas)]
+        assert_eq!(libs.libs().count(), 39);
+        for entry_point in ENTRY_POINTS {
+            assert_eq!(libs.entry_point(entry_point.name()), Some(*entry_point));
+            assert_eq!(entry_point.lib().id(), entry_point.lib_id());
+        }
+        assert_eq!(libs.entry_point("UNKNOWN"), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "nia", feature = "ifa"))]
+    fn scripts_deduplicate_libs() {
+        let libs = SharedLibs::get();
+        let scripts = libs.scripts(&[NIA_GENESIS, NIA_TRANSFER]);
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts.get(&NIA_GENESIS.lib_id()), Some(NIA_TRANSFER.lib()));
+        let scripts = libs.scripts(&[IFA_GENESIS, IFA_TRANSFER, IFA_INFLATION]);
+        assert_eq!(scripts.len(), 3);
+    }
+}
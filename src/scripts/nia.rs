@@ -0,0 +1,141 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the non-inflatable asset schema, also used by the collectible fungible,
+//! reissuable, soulbound, bond, governance, dividend-paying, escrow, option, burnable and rebasing
+//! schemata, by the vesting and wrapped BTC ones for transfers, and by the pausable, timelocked and
+//! freezable ones for their genesis.
+
+use crate::PrecompiledLib;
+
+// Not used when the library is only shared for its transfer validation, as by vesting assets
+#[cfg_attr(
+    not(any(
+        feature = "nia",
+        feature = "cfa",
+        feature = "ria",
+        feature = "sba",
+        feature = "bond",
+        feature = "governance",
+        feature = "timelock",
+        feature = "freezable",
+        feature = "dividend",
+        feature = "escrow",
+        feature = "carbon",
+        feature = "claim",
+        feature = "pausable",
+        feature = "option",
+        feature = "burnable",
+        feature = "rebasing"
+    )),
+    allow(dead_code)
+)]
+pub(super) const FN_NIA_GENESIS_OFFSET: u16 = 9;
+// Not used when the library is only shared for its genesis validation, as by pausable assets
+#[cfg_attr(
+    not(any(
+        feature = "nia",
+        feature = "cfa",
+        feature = "ria",
+        feature = "sba",
+        feature = "bond",
+        feature = "governance",
+        feature = "vesting",
+        feature = "wbtc",
+        feature = "dividend",
+        feature = "escrow",
+        feature = "carbon",
+        feature = "claim",
+        feature = "option",
+        feature = "burnable",
+        feature = "rebasing"
+    )),
+    allow(dead_code)
+)]
+pub(super) const FN_NIA_TRANSFER_OFFSET: u16 = 0;
+
+pub(super) const NIA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xd0, 0xa0, 0x0f, 0x01, 0x07, 0x0b, 0x00, 0x01, 0x00, 0x0b, 0x08,
+        0x00, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0xda, 0x07, 0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0,
+        0x0f, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x01, 0x00, 0x00],
+    id: [
+        0xab, 0xf0, 0x99, 0xd2, 0x8b, 0xed, 0x50, 0xdf, 0x5e, 0x06, 0x57, 0x15, 0x32, 0x7f, 0x3a,
+        0x9b, 0x32, 0x9f, 0x77, 0x7c, 0xb0, 0xb9, 0xfe, 0xff, 0xf6, 0x34, 0xc1, 0x93, 0xa0, 0x3c,
+        0xb6, 0x26,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_nia_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT};
+    use crate::{GS_ISSUED_SUPPLY, OS_ASSET};
+
+    crate::asm::assemble("non-inflatable asset", |_| {
+        vec![
+            ("FN_NIA_TRANSFER_OFFSET", rgbasm! {
+                // SUBROUTINE Transfer validation
+                // Set errno
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+                // Checking that the sum of inputs is equal to the sum of outputs.
+                svs     OS_ASSET;
+                test;
+                ret;
+            }),
+            ("FN_NIA_GENESIS_OFFSET", rgbasm! {
+                // SUBROUTINE Genesis validation
+                // Checking genesis assignments amount against reported amount of issued assets present in
+                // the global state.
+                put     a8[0],ERRNO_ISSUED_MISMATCH;
+                put     a8[1],0;
+                put     a16[0],0;
+                // Read global state into s16[0]
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+                // Extract 64 bits from the beginning of s16[0] into a64[0]
+                // NB: if the global state is invalid, we will fail here and fail the validation
+                extr    s16[0],a64[0],a16[0];
+                // verify sum of outputs against a64[0] value
+                sas     OS_ASSET;
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_nia_lib();
+        assembled.verify_offsets(&[
+            ("FN_NIA_TRANSFER_OFFSET", FN_NIA_TRANSFER_OFFSET),
+            ("FN_NIA_GENESIS_OFFSET", FN_NIA_GENESIS_OFFSET),
+        ]);
+        NIA_LIB.verify("NIA_LIB", assembled.lib);
+    }
+}
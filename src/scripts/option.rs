@@ -0,0 +1,168 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the option schema.
+//!
+//! Exercises and lapses both destroy the options of their inputs, except for those assigned back
+//! as change: the asset inputs must equal the exercised or lapsed supply recorded in the global
+//! state and the change outputs together.
+//!
+//! Both also commit to the absolute lock time of their witness transaction, which must be a block
+//! height: an exercise must commit to a lock time lower than the expiry, letting its witness be
+//! mined up to it, and a lapse to one of at least the expiry, keeping its witness from being mined
+//! up to it. The lock time of the witness itself is not checked, see [`crate::height`].
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_OPTION_EXERCISE_OFFSET: u16 = 0;
+pub(super) const FN_OPTION_LAPSE_OFFSET: u16 = 56;
+// Jump targets, only reached from within the library
+#[cfg(test)]
+const FN_OPTION_SUM_OFFSET: u16 = 110;
+#[cfg(test)]
+const FN_OPTION_CHANGE_LOOP_OFFSET: u16 = 117;
+#[cfg(test)]
+const FN_OPTION_CHANGE_NEXT_OFFSET: u16 = 128;
+
+pub(super) const OPTION_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0x0a, 0x08,
+        0x01, 0x39, 0x20, 0x01, 0x0b, 0x02, 0x04, 0x00, 0xc9, 0xfb, 0x07, 0x10, 0x39, 0x21, 0x02,
+        0x0b, 0x1a, 0x08, 0x00, 0x18, 0x09, 0x43, 0x01, 0x18, 0x09, 0x42, 0x01, 0x0b, 0x00, 0x0c,
+        0x00, 0xc8, 0x05, 0x08, 0x01, 0x39, 0x30, 0x00, 0x02, 0x6e, 0x00, 0x0b, 0x00, 0x0d, 0x00,
+        0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x01, 0x00, 0xc8, 0x0a, 0x08, 0x01, 0x39, 0x20, 0x01,
+        0x0b, 0x02, 0x01, 0x00, 0xc9, 0xfb, 0x07, 0x10, 0x39, 0x21, 0x02, 0x0b, 0x1a, 0x08, 0x00,
+        0x18, 0x09, 0x43, 0x01, 0x18, 0x09, 0x42, 0x1f, 0x01, 0x0b, 0x00, 0x0c, 0x00, 0xc8, 0x06,
+        0x08, 0x01, 0x39, 0x30, 0x00, 0xc1, 0xa0, 0x0f, 0x01, 0x02, 0x80, 0x00, 0xc6, 0xa0, 0x0f,
+        0x30, 0x20, 0x18, 0x60, 0x01, 0x24, 0x01, 0x01, 0x18, 0x01, 0x21, 0x03, 0x75, 0x00, 0x1f,
+        0xd2, 0xa0, 0x0f, 0x01, 0x07,
+    ],
+    data: &[0x4c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x65, 0xcd, 0x1d, 0x28, 0x4d],
+    id: [
+        0x63, 0xe1, 0x26, 0x56, 0x1a, 0x5a, 0x70, 0x3b, 0x38, 0x59, 0x96, 0x73, 0x4c, 0xfc, 0xb7,
+        0xa9, 0x37, 0x2f, 0x85, 0x9a, 0x9d, 0x37, 0x0b, 0xf4, 0x1e, 0xff, 0xef, 0xd6, 0x6d, 0xe0,
+        0x01, 0xe6,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_option_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ldf, ERRNO_BURN_MISMATCH, ERRNO_OPTION_EXPIRED, ERRNO_OPTION_NOT_EXPIRED};
+    use crate::{GS_EXERCISED_SUPPLY, GS_EXPIRY, GS_LAPSED_SUPPLY, GS_LOCK_TIME, OS_ASSET};
+
+    crate::asm::assemble("option", |labels| {
+        let sum = labels.offset("FN_OPTION_SUM_OFFSET");
+        let change_loop = labels.offset("FN_OPTION_CHANGE_LOOP_OFFSET");
+        let change_next = labels.offset("FN_OPTION_CHANGE_NEXT_OFFSET");
+        vec![
+            ("FN_OPTION_EXERCISE_OFFSET", rgbasm! {
+                // Check that the witness can be mined up to the expiry
+                put     a8[0],ERRNO_OPTION_EXPIRED;  // set errno
+                put     a8[1],0;
+                put     a16[0],0;
+                ldg     GS_LOCK_TIME,a8[1],s16[0];  // read the lock time of the exercise
+                extr    s16[0],a32[1],a16[0];  // and store it in a32[1]
+                put     a32[0],0;  // depth of the contract global state entry to read
+                ldc     GS_EXPIRY,a32[0],s16[1];  // read the expiry
+                extr    s16[1],a32[2],a16[0];  // and store it in a32[2]
+                put     a32[3],500000000;  // lock times from it on are timestamps
+                lt.u    a32[1],a32[3];
+                test;
+                lt.u    a32[1],a32[2];  // lock time < expiry
+                test;
+
+                // Read the exercised supply into a64[0]
+                put     a8[0],ERRNO_BURN_MISMATCH;  // set errno
+                ldg     GS_EXERCISED_SUPPLY,a8[1],s16[0];  // read exercised supply global state
+                extr    s16[0],a64[0],a16[0];
+                jmp     sum;
+            }),
+            ("FN_OPTION_LAPSE_OFFSET", rgbasm! {
+                // Check that the witness can't be mined up to the expiry
+                put     a8[0],ERRNO_OPTION_NOT_EXPIRED;  // set errno
+                put     a8[1],0;
+                put     a16[0],0;
+                ldg     GS_LOCK_TIME,a8[1],s16[0];  // read the lock time of the lapse
+                extr    s16[0],a32[1],a16[0];  // and store it in a32[1]
+                put     a32[0],0;  // depth of the contract global state entry to read
+                ldc     GS_EXPIRY,a32[0],s16[1];  // read the expiry
+                extr    s16[1],a32[2],a16[0];  // and store it in a32[2]
+                put     a32[3],500000000;  // lock times from it on are timestamps
+                lt.u    a32[1],a32[3];
+                test;
+                lt.u    a32[1],a32[2];  // lock time < expiry
+                inv     st0;  // lock time >= expiry
+                test;
+
+                // Read the lapsed supply into a64[0]
+                put     a8[0],ERRNO_BURN_MISMATCH;  // set errno
+                ldg     GS_LAPSED_SUPPLY,a8[1],s16[0];  // read lapsed supply global state
+                extr    s16[0],a64[0],a16[0];
+            }),
+            ("FN_OPTION_SUM_OFFSET", rgbasm! {
+                // Add the change allocations in output to a64[0]
+                cns     OS_ASSET,a16[1];  // count asset allocations in output
+                jmp     change_next;
+            }),
+            ("FN_OPTION_CHANGE_LOOP_OFFSET", {
+                // Read an asset allocation into a64[3]
+                let mut code = vec![ldf(OS_ASSET, 0, 3)];
+                code.extend(rgbasm! {
+                    add.uc  a64[3],a64[0];  // add it to the sum in a64[0]
+                    test;  // fails in case of an overflow
+                    inc     a16[0];
+                });
+                code
+            }),
+            ("FN_OPTION_CHANGE_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[0],a16[1];  // loop over the remaining asset allocations
+                jif     change_loop;
+
+                // Check sum of asset allocations in input equals a64[0]
+                inv     st0;  // the loop exits with st0 false, which `sps` doesn't reset
+                sps     OS_ASSET;
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_option_lib();
+        assembled.verify_offsets(&[
+            ("FN_OPTION_EXERCISE_OFFSET", FN_OPTION_EXERCISE_OFFSET),
+            ("FN_OPTION_LAPSE_OFFSET", FN_OPTION_LAPSE_OFFSET),
+            ("FN_OPTION_SUM_OFFSET", FN_OPTION_SUM_OFFSET),
+            ("FN_OPTION_CHANGE_LOOP_OFFSET", FN_OPTION_CHANGE_LOOP_OFFSET),
+            ("FN_OPTION_CHANGE_NEXT_OFFSET", FN_OPTION_CHANGE_NEXT_OFFSET),
+        ]);
+        OPTION_LIB.verify("OPTION_LIB", assembled.lib);
+    }
+}
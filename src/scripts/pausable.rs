@@ -0,0 +1,136 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the pausable asset schema.
+//!
+//! The `paused` global state holds 1 while transfers are paused and 0 while they aren't, the
+//! latest value being the current one; a contract whose issuer never toggled it isn't paused. A
+//! transfer is rejected while the contract is paused, and must otherwise keep the sum of the
+//! inputs. A toggle must record the negation of the current value.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_PAUSABLE_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_PAUSABLE_TOGGLE_OFFSET: u16 = 46;
+// Jump targets, only reached from within the library
+#[cfg(test)]
+const FN_PAUSABLE_TRANSFER_SUM_OFFSET: u16 = 37;
+#[cfg(test)]
+const FN_PAUSABLE_TOGGLE_CHECK_OFFSET: u16 = 79;
+
+pub(super) const PAUSABLE_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xc3, 0x00, 0x08, 0x00, 0x0b, 0x0a, 0x01, 0x00, 0x19, 0x01, 0x41,
+        0x03, 0x25, 0x00, 0xc9, 0x00, 0x08, 0x01, 0x0b, 0x01, 0x01, 0x00, 0x39, 0x30, 0x00, 0x0b,
+        0x0b, 0x05, 0x00, 0x19, 0x01, 0x61, 0x01, 0x0b, 0x00, 0x01, 0x00, 0xd0, 0xa0, 0x0f, 0x01,
+        0x07, 0x0b, 0x00, 0x0d, 0x00, 0x0b, 0x0b, 0x01, 0x00, 0x0b, 0x01, 0x01, 0x00, 0xc3, 0x00,
+        0x08, 0x00, 0x0b, 0x0a, 0x01, 0x00, 0x19, 0x01, 0x41, 0x03, 0x4f, 0x00, 0xc9, 0x00, 0x08,
+        0x01, 0x39, 0x30, 0x01, 0x0b, 0x08, 0x01, 0x00, 0xc8, 0x00, 0x08, 0x11, 0x39, 0x31, 0x00,
+        0x20, 0x00, 0x61, 0x01, 0x0b, 0x03, 0x0e, 0x00, 0x19, 0x01, 0x61, 0x01, 0x07,
+    ],
+    data: &[
+        0x45, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46, 0x01,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    id: [
+        0x7f, 0xc0, 0x60, 0xc4, 0x6a, 0xe5, 0x68, 0x92, 0x01, 0x59, 0xd9, 0xbe, 0x16, 0x27, 0xc3,
+        0xf8, 0x7e, 0x97, 0xd3, 0x4d, 0xd9, 0x2a, 0x39, 0x21, 0x11, 0x6a, 0x87, 0xc9, 0x1d, 0xca,
+        0xbb, 0xe0,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_pausable_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_NON_EQUAL_IN_OUT, ERRNO_PAUSED, ERRNO_PAUSE_NOT_TOGGLED};
+    use crate::{GS_PAUSED, OS_ASSET};
+
+    crate::asm::assemble("pausable asset", |labels| {
+        let transfer_sum = labels.offset("FN_PAUSABLE_TRANSFER_SUM_OFFSET");
+        let toggle_check = labels.offset("FN_PAUSABLE_TOGGLE_CHECK_OFFSET");
+        vec![
+            ("FN_PAUSABLE_TRANSFER_OFFSET", rgbasm! {
+                // Check that the contract isn't paused
+                put     a8[0],ERRNO_PAUSED;  // set errno
+                cnc     GS_PAUSED,a16[0];  // count the toggles into a32[0]
+                put     a32[1],0;
+                eq.n    a32[0],a32[1];
+                jif     transfer_sum;  // never toggled, so not paused
+                ldc     GS_PAUSED,a32[1],s16[0];  // read the latest paused flag
+                put     a16[0],0;
+                extr    s16[0],a64[0],a16[0];
+                put     a64[1],0;
+                eq.n    a64[0],a64[1];
+                test;
+            }),
+            ("FN_PAUSABLE_TRANSFER_SUM_OFFSET", rgbasm! {
+                // Check that the sum of inputs is equal to the sum of outputs
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                svs     OS_ASSET;
+                test;
+                ret;
+            }),
+            ("FN_PAUSABLE_TOGGLE_OFFSET", rgbasm! {
+                // Read the current paused flag into a64[1], which is 0 before any toggle
+                put     a8[0],ERRNO_PAUSE_NOT_TOGGLED;  // set errno
+                put     a64[1],0;
+                put     a16[0],0;
+                cnc     GS_PAUSED,a16[0];  // count the toggles into a32[0]
+                put     a32[1],0;
+                eq.n    a32[0],a32[1];
+                jif     toggle_check;
+                ldc     GS_PAUSED,a32[1],s16[0];  // read the latest paused flag
+                extr    s16[0],a64[1],a16[0];
+            }),
+            ("FN_PAUSABLE_TOGGLE_CHECK_OFFSET", rgbasm! {
+                // Check that the new flag and the current one are 0 and 1, in any order
+                put     a8[1],0;
+                ldg     GS_PAUSED,a8[1],s16[1];  // read the new paused flag
+                extr    s16[1],a64[0],a16[0];
+                add.uc  a64[0],a64[1];  // fails in case of an overflow
+                test;
+                put     a64[0],1;
+                eq.n    a64[0],a64[1];
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_pausable_lib();
+        assembled.verify_offsets(&[
+            ("FN_PAUSABLE_TRANSFER_OFFSET", FN_PAUSABLE_TRANSFER_OFFSET),
+            ("FN_PAUSABLE_TRANSFER_SUM_OFFSET", FN_PAUSABLE_TRANSFER_SUM_OFFSET),
+            ("FN_PAUSABLE_TOGGLE_OFFSET", FN_PAUSABLE_TOGGLE_OFFSET),
+            ("FN_PAUSABLE_TOGGLE_CHECK_OFFSET", FN_PAUSABLE_TOGGLE_CHECK_OFFSET),
+        ]);
+        PAUSABLE_LIB.verify("PAUSABLE_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,121 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation libraries of the permissioned fungible asset schema, whose genesis validation is
+//! also used by the multi-key permissioned asset one.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_PFA_GENESIS_OFFSET: u16 = 0;
+#[cfg_attr(not(feature = "pfa"), allow(dead_code))]
+pub(super) const FN_PFA_TRANSITION_OFFSET: u16 = 0;
+
+#[cfg_attr(not(feature = "pfa"), allow(dead_code))]
+pub(super) const PFA_LIB_TRANSITION: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xd0, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x01, 0x00, 0x0b, 0x02, 0x02,
+        0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00, 0x06, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x15],
+    id: [
+        0x0c, 0xf4, 0x84, 0x06, 0x3a, 0x9f, 0xc4, 0xe8, 0xf8, 0x4c, 0x46, 0xa0, 0x19, 0xa6, 0x20,
+        0xa6, 0x5a, 0x1b, 0x57, 0x2c, 0x55, 0x61, 0x87, 0xe6, 0xf3, 0x85, 0x38, 0xbd, 0x33, 0xa2,
+        0xec, 0xec,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_pfa_lib_transition() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY, ERRNO_NON_EQUAL_IN_OUT};
+    use crate::{GS_PUBKEY, OS_ASSET};
+
+    crate::asm::assemble("permissioned asset transition", |_| {
+        vec![("FN_PFA_TRANSITION_OFFSET", rgbasm! {
+            // Checking that the sum of inputs is equal to the sum of outputs
+            put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+            svs     OS_ASSET;  // verify sum
+            test;  // check it didn't fail
+
+            // Check transition signature against the latest key, which excludes the one published by
+            // a key rotation being validated
+            put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+            put     a32[0],0;  // set a32[0] to 0
+            ldc     GS_PUBKEY,a32[0],s16[0];  // get latest global pubkey
+            put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+            vts     s16[0];  // verify signature
+            test;  // check it didn't fail
+            ret;  // return execution flow
+        })]
+    })
+}
+
+pub(super) const PFA_LIB_GENESIS: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x07,
+    ],
+    data: &[0x01, 0x00, 0x00, 0x00],
+    id: [
+        0x3e, 0x12, 0x87, 0xd2, 0x01, 0xaf, 0xda, 0xac, 0xd3, 0xab, 0x5f, 0x6a, 0x07, 0x51, 0x68,
+        0x31, 0x74, 0x62, 0xea, 0xd0, 0x27, 0x04, 0xf1, 0x26, 0x6f, 0xb6, 0xa3, 0x9f, 0xc8, 0x03,
+        0x3e, 0xa6,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_pfa_lib_genesis() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::ERRNO_ISSUED_MISMATCH;
+    use crate::{GS_ISSUED_SUPPLY, OS_ASSET};
+
+    crate::asm::assemble("permissioned asset genesis", |_| {
+        vec![("FN_PFA_GENESIS_OFFSET", rgbasm! {
+            // Check genesis assignments amount against reported amount of issued assets present in the
+            // global state
+            put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+            put     a8[1],0;  // set a8[1] to 0
+            put     a16[0],0;  // set a16[0] to 0
+            ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // get global issued supply
+            extr    s16[0],a64[0],a16[0];  // extract 64 bits from the beginning of s16[0] into a64[0]
+            sas     OS_ASSET;  // verify sum of outputs against a64[0] value
+            test;  // check it didn't fail
+            ret;  // return execution flow
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let genesis = assemble_pfa_lib_genesis();
+        genesis.verify_offsets(&[("FN_PFA_GENESIS_OFFSET", FN_PFA_GENESIS_OFFSET)]);
+        PFA_LIB_GENESIS.verify("PFA_LIB_GENESIS", genesis.lib);
+        let transition = assemble_pfa_lib_transition();
+        transition.verify_offsets(&[("FN_PFA_TRANSITION_OFFSET", FN_PFA_TRANSITION_OFFSET)]);
+        PFA_LIB_TRANSITION.verify("PFA_LIB_TRANSITION", transition.lib);
+    }
+}
@@ -0,0 +1,118 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the multi-key permissioned asset schema.
+//!
+//! The verification of a signature halts the script when it fails, so it can't be tried against
+//! each of the authorized keys in turn. Instead, a transfer names the key it is signed with in its
+//! signer metadata: the validation looks it up among the keys of the contract global state and
+//! verifies the signature against it.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_PFAN_TRANSITION_OFFSET: u16 = 0;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_PFAN_SIGNER_LOOP_OFFSET: u16 = 27;
+#[cfg(test)]
+const FN_PFAN_SIGNER_NEXT_OFFSET: u16 = 39;
+#[cfg(test)]
+const FN_PFAN_SIGNED_OFFSET: u16 = 46;
+
+pub(super) const PFAN_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xd0, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x01, 0x00, 0xca, 0xe9, 0x03,
+        0x01, 0x0b, 0x02, 0x02, 0x00, 0xc3, 0xbe, 0x0b, 0x01, 0x02, 0x27, 0x00, 0xc9, 0xbe, 0x0b,
+        0x00, 0x36, 0x10, 0x03, 0x2e, 0x00, 0x24, 0x02, 0x01, 0x18, 0x01, 0x41, 0x03, 0x1b, 0x00,
+        0x01, 0x0b, 0x00, 0x06, 0x00, 0xd3, 0x11, 0x70,
+    ],
+    data: &[0x00, 0x16, 0x00, 0x00, 0x00, 0x00, 0x15],
+    id: [
+        0xb9, 0x4c, 0xf5, 0x10, 0x75, 0x1e, 0x8f, 0xc8, 0x07, 0x7f, 0x12, 0x4e, 0x78, 0x0c, 0xd8,
+        0x9c, 0x16, 0x2e, 0x7c, 0x4a, 0xbe, 0x7b, 0x5b, 0x5d, 0x89, 0xc8, 0xf3, 0xec, 0x1c, 0xa1,
+        0x7d, 0xee,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_pfan_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_INVALID_SIGNATURE, ERRNO_NON_EQUAL_IN_OUT, ERRNO_UNAUTHORIZED_SIGNER};
+    use crate::{GS_PUBKEY, MS_SIGNER, OS_ASSET};
+
+    crate::asm::assemble("multi-key permissioned asset", |labels| {
+        let signer_loop = labels.offset("FN_PFAN_SIGNER_LOOP_OFFSET");
+        let signer_next = labels.offset("FN_PFAN_SIGNER_NEXT_OFFSET");
+        let signed = labels.offset("FN_PFAN_SIGNED_OFFSET");
+        vec![
+            ("FN_PFAN_TRANSITION_OFFSET", rgbasm! {
+                // Checking that the sum of inputs is equal to the sum of outputs
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                svs     OS_ASSET;  // verify sum
+                test;  // check it didn't fail
+
+                // Look the signer up among the authorized keys
+                put     a8[0],ERRNO_UNAUTHORIZED_SIGNER;  // set errno
+                ldm     MS_SIGNER,s16[1];  // read signer metadata
+                put     a32[0],0;  // depth of the contract global state entry to read
+                cnc     GS_PUBKEY,a16[1];  // count authorized keys into a32[1]
+                jmp     signer_next;
+            }),
+            ("FN_PFAN_SIGNER_LOOP_OFFSET", rgbasm! {
+                ldc     GS_PUBKEY,a32[0],s16[0];  // read an authorized key
+                eq      s16[0],s16[1];  // compare it with the signer
+                jif     signed;
+                inc     a32[0];
+            }),
+            ("FN_PFAN_SIGNER_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining keys
+                jif     signer_loop;
+                test;  // fails as no key matched the signer
+            }),
+            ("FN_PFAN_SIGNED_OFFSET", rgbasm! {
+                // Check transition signature
+                put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+                vts     s16[1];  // verify signature
+                test;  // check it didn't fail
+                ret;  // return execution flow
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_pfan_lib();
+        assembled.verify_offsets(&[
+            ("FN_PFAN_TRANSITION_OFFSET", FN_PFAN_TRANSITION_OFFSET),
+            ("FN_PFAN_SIGNER_LOOP_OFFSET", FN_PFAN_SIGNER_LOOP_OFFSET),
+            ("FN_PFAN_SIGNER_NEXT_OFFSET", FN_PFAN_SIGNER_NEXT_OFFSET),
+            ("FN_PFAN_SIGNED_OFFSET", FN_PFAN_SIGNED_OFFSET),
+        ]);
+        PFAN_LIB.verify("PFAN_LIB", assembled.lib);
+    }
+}
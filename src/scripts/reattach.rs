@@ -0,0 +1,83 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the UDA reattachments.
+//!
+//! It is kept apart from the UDA library, which provenance and subscription tokens share, so that
+//! their schemata don't change with it. A reattachment publishes new token data, replacing the
+//! media and attachments of the token, which must declare the same token index as the current
+//! token data.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_REATTACH_OFFSET: u16 = 0;
+
+pub(super) const REATTACH_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x02, 0x01, 0x00, 0xc9, 0x36, 0x08, 0x00, 0x0b, 0x01, 0x01,
+        0x00, 0x39, 0x20, 0x01, 0x0b, 0x08, 0x01, 0x00, 0xc8, 0x36, 0x08, 0x11, 0x39, 0x21, 0x02,
+        0x19, 0x09, 0x42, 0x01, 0x07,
+    ],
+    data: &[0x13, 0x00, 0x00, 0x00, 0x00],
+    id: [
+        0x80, 0x3f, 0x84, 0xb9, 0x1b, 0x96, 0x17, 0x26, 0x79, 0x44, 0x65, 0x62, 0x0a, 0x29, 0x3c,
+        0xba, 0x39, 0x7e, 0x80, 0x5b, 0xd5, 0xd7, 0xab, 0x1d, 0xf8, 0xea, 0xa8, 0x88, 0xac, 0x2b,
+        0xb3, 0x63,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_reattach_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::ERRNO_TOKEN_REINDEXED;
+    use crate::GS_TOKENS;
+
+    crate::asm::assemble("uda reattachment", |_| {
+        vec![("FN_REATTACH_OFFSET", rgbasm! {
+            // Check that the new token data keeps the index of the current one
+            put     a8[0],ERRNO_TOKEN_REINDEXED;  // set errno
+            put     a32[0],0;
+            ldc     GS_TOKENS,a32[0],s16[0];  // read the current token data
+            put     a16[0],0;
+            extr    s16[0],a32[1],a16[0];  // extract the current token index
+            put     a8[1],0;
+            ldg     GS_TOKENS,a8[1],s16[1];  // read the published token data
+            extr    s16[1],a32[2],a16[0];  // extract the published token index
+            eq.n    a32[1],a32[2];
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_reattach_lib();
+        assembled.verify_offsets(&[("FN_REATTACH_OFFSET", FN_REATTACH_OFFSET)]);
+        REATTACH_LIB.verify("REATTACH_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,90 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the rebasing asset schema.
+//!
+//! A rebase must record a non-zero factor, since it would otherwise wipe out all the balances
+//! irreversibly, and be signed by the issuer. The factor isn't bounded otherwise: it is up to the
+//! issuer to publish sensible values.
+//!
+//! The signature check comes last, since code following `vts` isn't byte-aligned.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_REBASING_REBASE_OFFSET: u16 = 0;
+
+pub(super) const REBASING_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0x09, 0x08,
+        0x01, 0x39, 0x30, 0x00, 0x0b, 0x0b, 0x04, 0x00, 0x18, 0x09, 0x60, 0x01, 0x0b, 0x00, 0x0c,
+        0x00, 0x0b, 0x02, 0x01, 0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00, 0x0d, 0x00, 0xd3, 0x10,
+        0x70,
+    ],
+    data: &[0x4a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x15],
+    id: [
+        0x38, 0xc1, 0x36, 0x0a, 0x70, 0xcb, 0x60, 0xef, 0xa5, 0x0f, 0x83, 0x7c, 0xb7, 0x16, 0xcf,
+        0x09, 0xde, 0xe7, 0x80, 0x83, 0x1b, 0xd4, 0x42, 0xc9, 0x62, 0xf9, 0xa2, 0x11, 0xe5, 0xd8,
+        0x77, 0xdd,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_rebasing_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY, ERRNO_ZERO_REBASE_FACTOR};
+    use crate::{GS_PUBKEY, GS_REBASE_FACTORS};
+
+    crate::asm::assemble("rebasing asset", |_| {
+        vec![("FN_REBASING_REBASE_OFFSET", rgbasm! {
+            // Check that the rebase factor isn't zero
+            put     a8[0],ERRNO_ZERO_REBASE_FACTOR;  // set errno
+            put     a8[1],0;
+            put     a16[0],0;
+            ldg     GS_REBASE_FACTORS,a8[1],s16[0];  // read the new factor
+            extr    s16[0],a64[0],a16[0];
+            put     a64[1],0;
+            lt.u    a64[1],a64[0];  // 0 < factor
+            test;
+
+            // Check transition signature
+            put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+            put     a32[0],0;
+            ldc     GS_PUBKEY,a32[0],s16[0];  // read the issuer pubkey
+            put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+            vts     s16[0];  // verify signature
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_rebasing_lib();
+        assembled.verify_offsets(&[("FN_REBASING_REBASE_OFFSET", FN_REBASING_REBASE_OFFSET)]);
+        REBASING_LIB.verify("REBASING_LIB", assembled.lib);
+    }
+}
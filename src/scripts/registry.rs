@@ -0,0 +1,168 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the name registry schema.
+//!
+//! Each registration spends a register right and records a new name in the global state. The name
+//! must be normalized, i.e. contain neither upper case letters nor spaces, and differ from each of
+//! the names registered before, which the contract global state holds since it doesn't contain the
+//! registration being validated yet. The registered name is bound to the token numbered right after
+//! them, starting from 1, which the registration must allocate whole.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_REGISTRY_REGISTER_OFFSET: u16 = 0;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_REGISTRY_CHAR_LOOP_OFFSET: u16 = 29;
+#[cfg(test)]
+const FN_REGISTRY_CHAR_NEXT_OFFSET: u16 = 60;
+#[cfg(test)]
+const FN_REGISTRY_NAME_LOOP_OFFSET: u16 = 81;
+#[cfg(test)]
+const FN_REGISTRY_NAME_NEXT_OFFSET: u16 = 92;
+
+pub(super) const REGISTRY_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0xc8, 0xff, 0x07, 0x01, 0x0b, 0x01, 0x02,
+        0x00, 0x39, 0x00, 0x01, 0x12, 0x08, 0x11, 0x0b, 0x09, 0x01, 0x00, 0x02, 0x3c, 0x00, 0x24,
+        0x09, 0x01, 0x39, 0x00, 0x12, 0x0b, 0x18, 0x04, 0x00, 0x21, 0x14, 0x03, 0x0b, 0x20, 0x05,
+        0x00, 0x18, 0x19, 0x04, 0x1f, 0x01, 0x0b, 0x18, 0x06, 0x00, 0x19, 0x11, 0x03, 0x1f, 0x01,
+        0x18, 0x09, 0x22, 0x03, 0x1d, 0x00, 0x0b, 0x00, 0x07, 0x00, 0xc3, 0xff, 0x07, 0x03, 0x0b,
+        0x22, 0x08, 0x00, 0x02, 0x5c, 0x00, 0xc9, 0xff, 0x07, 0x14, 0x36, 0x10, 0x1f, 0x01, 0x24,
+        0x22, 0x01, 0x18, 0x21, 0x43, 0x03, 0x51, 0x00, 0x0b, 0x00, 0x0c, 0x00, 0x24, 0x1a, 0x01,
+        0x0b, 0x21, 0x01, 0x00, 0xc5, 0xa0, 0x0f, 0x14, 0x39, 0x21, 0x05, 0x19, 0x29, 0x43, 0x01,
+        0x0b, 0x00, 0x0d, 0x00, 0x0b, 0x29, 0x0e, 0x00, 0x39, 0x31, 0x50, 0x0b, 0x0b, 0x10, 0x00,
+        0x19, 0x01, 0x61, 0x01, 0x07,
+    ],
+    data: &[
+        0x11, 0x00, 0x00, 0x00, 0x41, 0x1a, 0x20, 0x10, 0x00, 0x00, 0x00, 0x00, 0x12, 0x0a, 0x04,
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    id: [
+        0x5d, 0xf9, 0x92, 0xc3, 0x5b, 0xb8, 0x4b, 0x58, 0xf7, 0xb7, 0xe1, 0x62, 0x55, 0xcf, 0xb5,
+        0x1e, 0xfc, 0x3f, 0x03, 0x96, 0xeb, 0xcd, 0x5a, 0xc6, 0x01, 0x6d, 0xb3, 0xfa, 0xe9, 0x4c,
+        0x0b, 0x5e,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_registry_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_NAME_TAKEN, ERRNO_NON_FRACTIONAL, ERRNO_REGISTRATION_MISMATCH,
+        ERRNO_UNNORMALIZED_NAME,
+    };
+    use crate::{GS_NAMES, OS_ASSET};
+
+    crate::asm::assemble("name registry", |labels| {
+        let char_loop = labels.offset("FN_REGISTRY_CHAR_LOOP_OFFSET");
+        let char_next = labels.offset("FN_REGISTRY_CHAR_NEXT_OFFSET");
+        let name_loop = labels.offset("FN_REGISTRY_NAME_LOOP_OFFSET");
+        let name_next = labels.offset("FN_REGISTRY_NAME_NEXT_OFFSET");
+        vec![
+            ("FN_REGISTRY_REGISTER_OFFSET", rgbasm! {
+                // Read the registered name into s16[0] and its length into a16[2]
+                put     a8[0],ERRNO_UNNORMALIZED_NAME;  // set errno
+                put     a8[1],0;
+                ldg     GS_NAMES,a8[1],s16[0];  // read the registered name
+                put     a16[0],0;
+                extr    s16[0],a8[1],a16[0];  // extract the length prefix of the name
+                cnv     a8[1],a16[2];  // names are at most 40 characters long
+                put     a16[1],0;  // offset of the last checked character
+                jmp     char_next;
+            }),
+            ("FN_REGISTRY_CHAR_LOOP_OFFSET", rgbasm! {
+                // Check that the next character is neither an upper case letter nor a space
+                inc     a16[1];
+                extr    s16[0],a8[2],a16[1];  // read the character
+                put     a8[3],0x41;  // 'A'
+                sub.uw  a8[2],a8[3];  // position of the character after 'A', wrapping below it
+                put     a8[4],26;
+                lt.u    a8[3],a8[4];  // the character is within 'A'..='Z'
+                inv     st0;
+                test;
+                put     a8[3],0x20;  // ' '
+                eq.n    a8[2],a8[3];
+                inv     st0;
+                test;
+            }),
+            ("FN_REGISTRY_CHAR_NEXT_OFFSET", rgbasm! {
+                lt.u    a16[1],a16[2];  // loop over the remaining characters
+                jif     char_loop;
+
+                // Check that the name differs from the registered ones: the assembler names the
+                // count destination a16[3], while the count is put into a32[3]
+                put     a8[0],ERRNO_NAME_TAKEN;  // set errno
+                cnc     GS_NAMES,a16[3];  // count the registered names
+                put     a32[4],0;  // depth of the registered name to compare
+                jmp     name_next;
+            }),
+            ("FN_REGISTRY_NAME_LOOP_OFFSET", rgbasm! {
+                ldc     GS_NAMES,a32[4],s16[1];  // read a registered name
+                eq      s16[0],s16[1];
+                inv     st0;
+                test;
+                inc     a32[4];
+            }),
+            ("FN_REGISTRY_NAME_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[4],a32[3];  // loop over the remaining registered names
+                jif     name_loop;
+
+                // Check that the output allocates the token following the registered names
+                put     a8[0],ERRNO_REGISTRATION_MISMATCH;  // set errno
+                inc     a32[3];
+                put     a16[4],0;
+                lds     OS_ASSET,a16[4],s16[1];  // read the output allocation
+                extr    s16[1],a32[5],a16[0];  // extract its token index
+                eq.n    a32[5],a32[3];
+                test;
+                put     a8[0],ERRNO_NON_FRACTIONAL;  // set errno
+                put     a16[5],4;  // offset of the fraction in an allocation
+                extr    s16[1],a64[0],a16[5];
+                put     a64[1],1;
+                eq.n    a64[0],a64[1];
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_registry_lib();
+        assembled.verify_offsets(&[
+            ("FN_REGISTRY_REGISTER_OFFSET", FN_REGISTRY_REGISTER_OFFSET),
+            ("FN_REGISTRY_CHAR_LOOP_OFFSET", FN_REGISTRY_CHAR_LOOP_OFFSET),
+            ("FN_REGISTRY_CHAR_NEXT_OFFSET", FN_REGISTRY_CHAR_NEXT_OFFSET),
+            ("FN_REGISTRY_NAME_LOOP_OFFSET", FN_REGISTRY_NAME_LOOP_OFFSET),
+            ("FN_REGISTRY_NAME_NEXT_OFFSET", FN_REGISTRY_NAME_NEXT_OFFSET),
+        ]);
+        REGISTRY_LIB.verify("REGISTRY_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,160 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the rental token schema.
+//!
+//! Every lease records its expiry in the `leaseExpiry` global state and every reclaim records the
+//! expiry of the lease it ends in the `reclaimedLeases` one, so the token is leased while the
+//! former has one more entry than the latter. A lease is rejected while the token is leased, and a
+//! reclaim must end the current lease, recording its expiry. Both must preserve the token like a
+//! UDA transfer.
+//!
+//! A reclaim also commits to the absolute lock time of its witness transaction, which must be a
+//! block height keeping the witness from being mined while the lease is in force, i.e. at least
+//! the lease expiry. The lock time of the witness itself is not checked, see [`crate::height`].
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_RENTAL_LEASE_OFFSET: u16 = 0;
+pub(super) const FN_RENTAL_RECLAIM_OFFSET: u16 = 19;
+// Jump target, only reached from within the library
+#[cfg(test)]
+const FN_RENTAL_TOKEN_OFFSET: u16 = 97;
+
+pub(super) const RENTAL_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xc3, 0x01, 0x08, 0x00, 0xc3, 0x02, 0x08, 0x01, 0x19, 0x01, 0x41,
+        0x01, 0x02, 0x61, 0x00, 0x0b, 0x00, 0x01, 0x00, 0xc3, 0x01, 0x08, 0x00, 0xc3, 0x02, 0x08,
+        0x01, 0x0b, 0x12, 0x02, 0x00, 0x20, 0x10, 0x41, 0x01, 0x19, 0x01, 0x41, 0x01, 0x0b, 0x02,
+        0x06, 0x00, 0xc9, 0x01, 0x08, 0x00, 0x0b, 0x01, 0x03, 0x00, 0x39, 0x20, 0x00, 0x0b, 0x08,
+        0x03, 0x00, 0xc8, 0x02, 0x08, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x01, 0x0b, 0x00,
+        0x0a, 0x00, 0xc8, 0x0a, 0x08, 0x21, 0x39, 0x22, 0x01, 0x0b, 0x1a, 0x0b, 0x00, 0x18, 0x09,
+        0x43, 0x01, 0x18, 0x09, 0x40, 0x1f, 0x01, 0x0b, 0x00, 0x03, 0x00, 0x0b, 0x01, 0x03, 0x00,
+        0xc4, 0xa0, 0x0f, 0x00, 0x39, 0x20, 0x00, 0xc5, 0xa0, 0x0f, 0x10, 0x39, 0x21, 0x01, 0x19,
+        0x01, 0x41, 0x01, 0x0b, 0x00, 0x0f, 0x00, 0x0b, 0x11, 0x10, 0x00, 0x39, 0x31, 0x20, 0x0b,
+        0x0b, 0x02, 0x00, 0x19, 0x01, 0x61, 0x01, 0x07,
+    ],
+    data: &[
+        0x47, 0x48, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4b, 0x00, 0x65, 0xcd, 0x1d,
+        0x0a, 0x04, 0x00,
+    ],
+    id: [
+        0x12, 0x8f, 0xd6, 0xc3, 0x1b, 0x2e, 0x58, 0xbf, 0x2f, 0x92, 0xac, 0xd3, 0xe0, 0xe8, 0x7b,
+        0x21, 0x44, 0x26, 0xce, 0xfb, 0x78, 0xe4, 0xb1, 0x66, 0xd3, 0x64, 0x56, 0x15, 0x22, 0x0e,
+        0xb0, 0xe7,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_rental_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_LEASE_ACTIVE, ERRNO_LEASE_MISMATCH, ERRNO_LEASE_NOT_EXPIRED, ERRNO_NON_EQUAL_IN_OUT,
+        ERRNO_NON_FRACTIONAL,
+    };
+    use crate::{GS_LEASE_EXPIRY, GS_LOCK_TIME, GS_RECLAIMED_LEASES, OS_ASSET};
+
+    crate::asm::assemble("rental token", |labels| {
+        let token = labels.offset("FN_RENTAL_TOKEN_OFFSET");
+        vec![
+            ("FN_RENTAL_LEASE_OFFSET", rgbasm! {
+                // Check that every previous lease was reclaimed
+                put     a8[0],ERRNO_LEASE_ACTIVE;  // set errno
+                cnc     GS_LEASE_EXPIRY,a16[0];  // count the leases into a32[0]
+                cnc     GS_RECLAIMED_LEASES,a16[1];  // count the reclaims into a32[1]
+                eq.n    a32[0],a32[1];
+                test;
+                jmp     token;
+            }),
+            ("FN_RENTAL_RECLAIM_OFFSET", rgbasm! {
+                // Check that the token is leased
+                put     a8[0],ERRNO_LEASE_MISMATCH;  // set errno
+                cnc     GS_LEASE_EXPIRY,a16[0];  // count the leases into a32[0]
+                cnc     GS_RECLAIMED_LEASES,a16[1];  // count the reclaims into a32[1]
+                put     a32[2],1;
+                add.uc  a32[2],a32[1];  // fails in case of an overflow
+                test;
+                eq.n    a32[0],a32[1];
+                test;
+
+                // Check that the reclaim records the expiry of the current lease
+                put     a32[0],0;
+                ldc     GS_LEASE_EXPIRY,a32[0],s16[0];  // read the latest lease expiry
+                put     a16[0],0;
+                extr    s16[0],a32[0],a16[0];
+                put     a8[1],0;
+                ldg     GS_RECLAIMED_LEASES,a8[1],s16[1];  // read the reclaimed expiry
+                extr    s16[1],a32[1],a16[0];
+                eq.n    a32[0],a32[1];
+                test;
+
+                // Check that the witness can't be mined while the lease is in force
+                put     a8[0],ERRNO_LEASE_NOT_EXPIRED;  // set errno
+                ldg     GS_LOCK_TIME,a8[1],s16[2];  // read the lock time of the reclaim
+                extr    s16[2],a32[1],a16[0];  // and store it in a32[1]
+                put     a32[3],500000000;  // lock times from it on are timestamps
+                lt.u    a32[1],a32[3];
+                test;
+                lt.u    a32[1],a32[0];  // lock time < lease expiry
+                inv     st0;  // lock time >= lease expiry
+                test;
+            }),
+            ("FN_RENTAL_TOKEN_OFFSET", rgbasm! {
+                // Check that the token index of the input matches the output one
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a16[0],0;
+                ldp     OS_ASSET,a16[0],s16[0];  // read the input allocation
+                extr    s16[0],a32[0],a16[0];  // extract its token index
+                lds     OS_ASSET,a16[0],s16[1];  // read the output allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];
+                test;
+
+                // Check that the output owns the whole token
+                put     a8[0],ERRNO_NON_FRACTIONAL;  // set errno
+                put     a16[2],4;  // offset of the fraction in an allocation
+                extr    s16[1],a64[0],a16[2];
+                put     a64[1],1;
+                eq.n    a64[0],a64[1];
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_rental_lib();
+        assembled.verify_offsets(&[
+            ("FN_RENTAL_LEASE_OFFSET", FN_RENTAL_LEASE_OFFSET),
+            ("FN_RENTAL_RECLAIM_OFFSET", FN_RENTAL_RECLAIM_OFFSET),
+            ("FN_RENTAL_TOKEN_OFFSET", FN_RENTAL_TOKEN_OFFSET),
+        ]);
+        RENTAL_LIB.verify("RENTAL_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,170 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the reissuable asset schema, also used by the soulbound, wrapped BTC and
+//! burnable asset ones for burns.
+//!
+//! Reissuance is bounded by the burns recorded over the contract history: the reissue validation
+//! sums the issued supply of all previous reissues and the burned supply of all burns from the
+//! contract global state, which lists entries from the latest one and keeps the genesis issuance
+//! as the deepest entry of the issued supply.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_RIA_BURN_OFFSET: u16 = 0;
+#[cfg(any(test, feature = "ria"))]
+pub(super) const FN_RIA_REISSUE_OFFSET: u16 = 24;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_RIA_REISSUED_LOOP_OFFSET: u16 = 68;
+#[cfg(test)]
+const FN_RIA_REISSUED_NEXT_OFFSET: u16 = 82;
+#[cfg(test)]
+const FN_RIA_BURNED_LOOP_OFFSET: u16 = 103;
+#[cfg(test)]
+const FN_RIA_BURNED_NEXT_OFFSET: u16 = 117;
+
+pub(super) const RIA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0xdd, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd2, 0xa0, 0x0f, 0x01, 0x07, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01,
+        0x01, 0x00, 0x0b, 0x00, 0x04, 0x00, 0xc8, 0xda, 0x07, 0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0,
+        0x0f, 0x01, 0x0b, 0x00, 0x05, 0x00, 0x11, 0x03, 0x0b, 0x0b, 0x02, 0x06, 0x00, 0xc3, 0xda,
+        0x07, 0x01, 0x24, 0x0a, 0xff, 0x02, 0x52, 0x00, 0xc9, 0xda, 0x07, 0x00, 0x39, 0x30, 0x00,
+        0x20, 0x00, 0x61, 0x01, 0x24, 0x02, 0x01, 0x18, 0x01, 0x41, 0x03, 0x44, 0x00, 0x0b, 0x13,
+        0x0a, 0x00, 0x0b, 0x02, 0x06, 0x00, 0xc3, 0xdd, 0x07, 0x01, 0x02, 0x75, 0x00, 0xc9, 0xdd,
+        0x07, 0x00, 0x39, 0x30, 0x00, 0x20, 0x00, 0x62, 0x01, 0x24, 0x02, 0x01, 0x18, 0x01, 0x41,
+        0x03, 0x67, 0x00, 0x18, 0x11, 0x61, 0x1f, 0x01, 0x07,
+    ],
+    data: &[
+        0x28, 0x00, 0x00, 0x00, 0x01, 0x29, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00,
+    ],
+    id: [
+        0xdd, 0x0d, 0x7e, 0xe2, 0x34, 0x90, 0x6f, 0xe4, 0xf8, 0xf2, 0x6d, 0x6e, 0xd8, 0x81, 0xc0,
+        0xac, 0xb0, 0x84, 0xe4, 0xc8, 0x89, 0xce, 0x0b, 0xf3, 0x6e, 0x8f, 0x78, 0x9b, 0x1d, 0x2e,
+        0xf9, 0x9b,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_ria_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_BURN_MISMATCH, ERRNO_ISSUED_MISMATCH, ERRNO_REISSUE_EXCEEDS_BURNS};
+    use crate::{GS_BURNED_SUPPLY, GS_ISSUED_SUPPLY, OS_ASSET};
+
+    crate::asm::assemble("reissuable asset", |labels| {
+        let reissued_loop = labels.offset("FN_RIA_REISSUED_LOOP_OFFSET");
+        let reissued_next = labels.offset("FN_RIA_REISSUED_NEXT_OFFSET");
+        let burned_loop = labels.offset("FN_RIA_BURNED_LOOP_OFFSET");
+        let burned_next = labels.offset("FN_RIA_BURNED_NEXT_OFFSET");
+        vec![
+            ("FN_RIA_BURN_OFFSET", rgbasm! {
+                // Check reported burned supply equals sum of asset allocations in input
+                put     a8[0],ERRNO_BURN_MISMATCH;  // set errno
+                put     a8[1],0;
+                put     a16[0],0;
+                ldg     GS_BURNED_SUPPLY,a8[1],s16[0];  // read burned supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sps     OS_ASSET;  // check sum of asset allocations in input equals a64[0]
+                test;
+                ret;
+            }),
+            ("FN_RIA_REISSUE_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+
+                // Check reported issued supply equals sum of asset allocations in output
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of asset allocations in output equals a64[0]
+                test;
+
+                // Sum the supply reissued up to this reissue into a64[1]
+                put     a8[0],ERRNO_REISSUE_EXCEEDS_BURNS;  // set errno
+                cpy     a64[0],a64[1];
+                put     a32[0],0;  // depth of the contract global state entry to read
+                // Count issued supply entries: the assembler names the destination a16[1], while
+                // the count is put into a32[1]
+                cnc     GS_ISSUED_SUPPLY,a16[1];
+                dec     a32[1];  // skipping the deepest one, issued by the genesis
+                jmp     reissued_next;
+            }),
+            ("FN_RIA_REISSUED_LOOP_OFFSET", rgbasm! {
+                ldc     GS_ISSUED_SUPPLY,a32[0],s16[0];  // read a previous reissue
+                extr    s16[0],a64[0],a16[0];
+                add.uc  a64[0],a64[1];  // add it to the sum in a64[1]
+                test;  // fails in case of an overflow
+                inc     a32[0];
+            }),
+            ("FN_RIA_REISSUED_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining reissues
+                jif     reissued_loop;
+
+                // Sum the supply burned over the contract history into a64[2]
+                put     a64[2],0;
+                put     a32[0],0;
+                cnc     GS_BURNED_SUPPLY,a16[1];  // count burned supply entries into a32[1]
+                jmp     burned_next;
+            }),
+            ("FN_RIA_BURNED_LOOP_OFFSET", rgbasm! {
+                ldc     GS_BURNED_SUPPLY,a32[0],s16[0];  // read a burn
+                extr    s16[0],a64[0],a16[0];
+                add.uc  a64[0],a64[2];  // add it to the sum in a64[2]
+                test;  // fails in case of an overflow
+                inc     a32[0];
+            }),
+            ("FN_RIA_BURNED_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining burns
+                jif     burned_loop;
+
+                // Check that the reissued supply doesn't exceed the burned one
+                lt.u    a64[2],a64[1];  // burned < reissued
+                inv     st0;  // burned >= reissued
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_ria_lib();
+        assembled.verify_offsets(&[
+            ("FN_RIA_BURN_OFFSET", FN_RIA_BURN_OFFSET),
+            ("FN_RIA_REISSUE_OFFSET", FN_RIA_REISSUE_OFFSET),
+            ("FN_RIA_REISSUED_LOOP_OFFSET", FN_RIA_REISSUED_LOOP_OFFSET),
+            ("FN_RIA_REISSUED_NEXT_OFFSET", FN_RIA_REISSUED_NEXT_OFFSET),
+            ("FN_RIA_BURNED_LOOP_OFFSET", FN_RIA_BURNED_LOOP_OFFSET),
+            ("FN_RIA_BURNED_NEXT_OFFSET", FN_RIA_BURNED_NEXT_OFFSET),
+        ]);
+        RIA_LIB.verify("RIA_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,90 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the soulbound asset schema.
+//!
+//! Besides keeping transfers from assigning the asset, the library validates revocations, which
+//! must record exactly one revoked UTXO each.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_SBA_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_SBA_REVOKE_OFFSET: u16 = 17;
+
+pub(super) const SBA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0xc1, 0xa0, 0x0f, 0x00, 0x0b, 0x09, 0x01, 0x00, 0x19, 0x01, 0x21,
+        0x01, 0x07, 0x0b, 0x00, 0x03, 0x00, 0xc2, 0xde, 0x07, 0x01, 0x0b, 0x10, 0x04, 0x00, 0x19,
+        0x09, 0x02, 0x01, 0x07,
+    ],
+    data: &[0x2d, 0x00, 0x00, 0x2e, 0x01],
+    id: [
+        0x87, 0xda, 0x4d, 0x5b, 0x25, 0x89, 0xca, 0x99, 0x6a, 0xe9, 0x84, 0x16, 0xde, 0x17, 0x61,
+        0x4b, 0x19, 0xd5, 0xa7, 0xdb, 0x83, 0x86, 0x1b, 0x92, 0x51, 0x05, 0x84, 0x74, 0x96, 0xf9,
+        0x71, 0x59,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_sba_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_REVOCATION_MISMATCH, ERRNO_SOULBOUND};
+    use crate::{GS_REVOCATIONS, OS_ASSET};
+
+    crate::asm::assemble("soulbound asset", |_| {
+        vec![
+            ("FN_SBA_TRANSFER_OFFSET", rgbasm! {
+                // Check that no asset is assigned, so that it can only be burned
+                put     a8[0],ERRNO_SOULBOUND;  // set errno
+                cns     OS_ASSET,a16[0];  // count output asset allocations
+                put     a16[1],0;
+                eq.n    a16[0],a16[1];  // check that there are none
+                test;
+                ret;
+            }),
+            ("FN_SBA_REVOKE_OFFSET", rgbasm! {
+                // Check that the revocation records a single revoked UTXO
+                put     a8[0],ERRNO_REVOCATION_MISMATCH;  // set errno
+                cng     GS_REVOCATIONS,a8[1];  // count the revoked UTXOs
+                put     a8[2],1;
+                eq.n    a8[1],a8[2];  // check that there is exactly one
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_sba_lib();
+        assembled.verify_offsets(&[
+            ("FN_SBA_TRANSFER_OFFSET", FN_SBA_TRANSFER_OFFSET),
+            ("FN_SBA_REVOKE_OFFSET", FN_SBA_REVOKE_OFFSET),
+        ]);
+        SBA_LIB.verify("SBA_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,305 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the semi-fungible asset schema.
+//!
+//! Genesis validation checks that the contract declares its tokens sorted by strictly increasing
+//! index and that each output allocates one of them. Transfer validation checks the balance of
+//! every token allocated by an input or an output, requiring the outputs to allocate the same
+//! amount of the token as the inputs.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_SFA_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_SFA_GENESIS_OFFSET: u16 = 82;
+pub(super) const FN_SFA_BALANCE_OFFSET: u16 = 189;
+// Loop heads and branches, only targeted by jumps within the library
+#[cfg(test)]
+const FN_SFA_INPUTS_LOOP_OFFSET: u16 = 32;
+#[cfg(test)]
+const FN_SFA_INPUTS_NEXT_OFFSET: u16 = 42;
+#[cfg(test)]
+const FN_SFA_OUTPUTS_LOOP_OFFSET: u16 = 59;
+#[cfg(test)]
+const FN_SFA_OUTPUTS_NEXT_OFFSET: u16 = 69;
+#[cfg(test)]
+const FN_SFA_END_OFFSET: u16 = 81;
+#[cfg(test)]
+const FN_SFA_GENESIS_NEXT_OFFSET: u16 = 105;
+#[cfg(test)]
+const FN_SFA_GENESIS_OUTPUTS_LOOP_OFFSET: u16 = 126;
+#[cfg(test)]
+const FN_SFA_GENESIS_LOOKUP_OFFSET: u16 = 137;
+#[cfg(test)]
+const FN_SFA_GENESIS_DECLARED_OFFSET: u16 = 160;
+#[cfg(test)]
+const FN_SFA_GENESIS_LOOP_OFFSET: u16 = 172;
+#[cfg(test)]
+const FN_SFA_BALANCE_INPUTS_LOOP_OFFSET: u16 = 197;
+#[cfg(test)]
+const FN_SFA_BALANCE_INPUTS_NEXT_OFFSET: u16 = 210;
+#[cfg(test)]
+const FN_SFA_BALANCE_OUTPUTS_LOOP_OFFSET: u16 = 227;
+#[cfg(test)]
+const FN_SFA_BALANCE_OUTPUTS_NEXT_OFFSET: u16 = 240;
+#[cfg(test)]
+const FN_SFA_INPUT_MATCHED_OFFSET: u16 = 262;
+#[cfg(test)]
+const FN_SFA_OUTPUT_MATCHED_OFFSET: u16 = 272;
+
+pub(super) const SFA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x01, 0x00, 0x00, 0x0b, 0x11, 0x02, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x0b, 0x28, 0x00,
+        0x00, 0x0b, 0x30, 0x00, 0x00, 0xc0, 0xa0, 0x0f, 0x07, 0xc1, 0xa0, 0x0f, 0x03, 0x0b, 0x21,
+        0x00, 0x00, 0xc4, 0xa0, 0x0f, 0x04, 0x39, 0x20, 0x00, 0x02, 0xbd, 0x00, 0x24, 0x21, 0x01,
+        0x18, 0x21, 0x27, 0x03, 0x20, 0x00, 0x0b, 0x28, 0x04, 0x00, 0x0b, 0x21, 0x00, 0x00, 0xc5,
+        0xa0, 0x0f, 0x04, 0x39, 0x20, 0x00, 0x02, 0xbd, 0x00, 0x24, 0x21, 0x01, 0x19, 0x21, 0x23,
+        0x03, 0x51, 0x00, 0x02, 0x3b, 0x00, 0x07, 0x0b, 0x01, 0x00, 0x00, 0x0b, 0x00, 0x05, 0x00,
+        0xc2, 0x36, 0x08, 0x03, 0x0b, 0x08, 0x00, 0x00, 0xc8, 0x36, 0x08, 0x01, 0x39, 0x20, 0x00,
+        0x24, 0x08, 0x01, 0x18, 0x09, 0x03, 0x03, 0xac, 0x00, 0x0b, 0x00, 0x06, 0x00, 0xc1, 0xa0,
+        0x0f, 0x03, 0x0b, 0x21, 0x00, 0x00, 0xc5, 0xa0, 0x0f, 0x14, 0x39, 0x21, 0x00, 0x0b, 0x08,
+        0x00, 0x00, 0xc8, 0x36, 0x08, 0x01, 0x39, 0x20, 0x01, 0x19, 0x01, 0x41, 0x03, 0xa0, 0x00,
+        0x24, 0x08, 0x01, 0x18, 0x09, 0x03, 0x03, 0x89, 0x00, 0x00, 0x24, 0x21, 0x01, 0x19, 0x21,
+        0x23, 0x03, 0x51, 0x00, 0x02, 0x7e, 0x00, 0x11, 0x02, 0x12, 0xc8, 0x36, 0x08, 0x01, 0x39,
+        0x20, 0x00, 0x18, 0x11, 0x40, 0x01, 0x02, 0x69, 0x00, 0x0b, 0x13, 0x07, 0x00, 0x0b, 0x09,
+        0x00, 0x00, 0xc4, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x03, 0x06, 0x01,
+        0x24, 0x09, 0x01, 0x18, 0x09, 0x27, 0x03, 0xc5, 0x00, 0x0b, 0x03, 0x07, 0x00, 0x0b, 0x09,
+        0x00, 0x00, 0xc5, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x03, 0x10, 0x01,
+        0x24, 0x09, 0x01, 0x18, 0x09, 0x23, 0x03, 0xe3, 0x00, 0x19, 0x01, 0x62, 0x01, 0x19, 0x29,
+        0x06, 0x03, 0x2a, 0x00, 0x02, 0x45, 0x00, 0x39, 0x31, 0x21, 0x20, 0x08, 0x62, 0x01, 0x02,
+        0xd2, 0x00, 0x39, 0x31, 0x21, 0x20, 0x08, 0x60, 0x01, 0x02, 0xf0, 0x00,
+    ],
+    data: &[
+        0x00, 0x00, 0x04, 0x00, 0x01, 0x0b, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    id: [
+        0x83, 0x8b, 0x83, 0x63, 0x4d, 0x57, 0x27, 0x34, 0x0b, 0xc9, 0x67, 0x80, 0x0f, 0x97, 0x8d,
+        0x9b, 0x42, 0xf4, 0xdf, 0x23, 0xc1, 0xd7, 0x8d, 0xcf, 0x56, 0xf1, 0xf2, 0xe0, 0x48, 0x53,
+        0x22, 0x19,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_sfa_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_NON_EQUAL_IN_OUT, ERRNO_UNKNOWN_TOKEN, ERRNO_UNSORTED_TOKENS};
+    use crate::{GS_TOKENS, OS_ASSET};
+
+    crate::asm::assemble("semi-fungible asset", |labels| {
+        let inputs_loop = labels.offset("FN_SFA_INPUTS_LOOP_OFFSET");
+        let inputs_next = labels.offset("FN_SFA_INPUTS_NEXT_OFFSET");
+        let outputs_loop = labels.offset("FN_SFA_OUTPUTS_LOOP_OFFSET");
+        let outputs_next = labels.offset("FN_SFA_OUTPUTS_NEXT_OFFSET");
+        let end = labels.offset("FN_SFA_END_OFFSET");
+        let genesis_loop = labels.offset("FN_SFA_GENESIS_LOOP_OFFSET");
+        let genesis_next = labels.offset("FN_SFA_GENESIS_NEXT_OFFSET");
+        let genesis_outputs_loop = labels.offset("FN_SFA_GENESIS_OUTPUTS_LOOP_OFFSET");
+        let genesis_lookup = labels.offset("FN_SFA_GENESIS_LOOKUP_OFFSET");
+        let genesis_declared = labels.offset("FN_SFA_GENESIS_DECLARED_OFFSET");
+        let balance = labels.offset("FN_SFA_BALANCE_OFFSET");
+        let balance_inputs_loop = labels.offset("FN_SFA_BALANCE_INPUTS_LOOP_OFFSET");
+        let balance_inputs_next = labels.offset("FN_SFA_BALANCE_INPUTS_NEXT_OFFSET");
+        let balance_outputs_loop = labels.offset("FN_SFA_BALANCE_OUTPUTS_LOOP_OFFSET");
+        let balance_outputs_next = labels.offset("FN_SFA_BALANCE_OUTPUTS_NEXT_OFFSET");
+        let input_matched = labels.offset("FN_SFA_INPUT_MATCHED_OFFSET");
+        let output_matched = labels.offset("FN_SFA_OUTPUT_MATCHED_OFFSET");
+        vec![
+            ("FN_SFA_TRANSFER_OFFSET", rgbasm! {
+                // SUBROUTINE 2: Transfer validation, balancing the token of each input and output
+                put     a16[0],0;  // offset of the token index in an allocation
+                put     a16[2],4;  // offset of the amount in an allocation
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+                put     a8[5],0;  // return into the inputs loop after balancing
+                put     a8[6],0;
+                cnp     OS_ASSET,a16[7];
+                cns     OS_ASSET,a16[3];
+                // Number of balanced inputs, also the index of the next input; the schema
+                // requires at least one input
+                put     a16[4],0;
+            }),
+            ("FN_SFA_INPUTS_LOOP_OFFSET", rgbasm! {
+                // Read the token index of the input into a32[0] and balance it
+                ldp     OS_ASSET,a16[4],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                jmp     balance;
+            }),
+            ("FN_SFA_INPUTS_NEXT_OFFSET", rgbasm! {
+                inc     a16[4];
+                lt.u    a16[4],a16[7];  // loop over the remaining inputs
+                jif     inputs_loop;
+                // Balance the tokens of the outputs as well, so that none of them allocates a
+                // token missing from the inputs; the schema requires at least one output
+                put     a8[5],1;  // return into the outputs loop after balancing
+                put     a16[4],0;
+            }),
+            ("FN_SFA_OUTPUTS_LOOP_OFFSET", rgbasm! {
+                // Read the token index of the output into a32[0] and balance it
+                lds     OS_ASSET,a16[4],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                jmp     balance;
+            }),
+            ("FN_SFA_OUTPUTS_NEXT_OFFSET", rgbasm! {
+                inc     a16[4];
+                eq.n    a16[4],a16[3];  // stop once all the outputs are balanced
+                jif     end;
+                jmp     outputs_loop;
+            }),
+            ("FN_SFA_END_OFFSET", rgbasm! {
+                ret;
+            }),
+            ("FN_SFA_GENESIS_OFFSET", rgbasm! {
+                // SUBROUTINE 1: Genesis validation
+                put     a16[0],0;  // offset of the token index in an allocation
+                put     a8[0],ERRNO_UNSORTED_TOKENS;  // set errno
+                cng     GS_TOKENS,a8[3];
+                // Read the index of the first token into a32[0]
+                put     a8[1],0;
+                ldg     GS_TOKENS,a8[1],s16[0];
+                extr    s16[0],a32[0],a16[0];
+            }),
+            ("FN_SFA_GENESIS_NEXT_OFFSET", rgbasm! {
+                inc     a8[1];
+                lt.u    a8[1],a8[3];  // loop over the remaining tokens
+                jif     genesis_loop;
+                // Each output must allocate one of the tokens, the schema requires at least one
+                // output
+                put     a8[0],ERRNO_UNKNOWN_TOKEN;  // set errno
+                cns     OS_ASSET,a16[3];
+                put     a16[4],0;
+            }),
+            ("FN_SFA_GENESIS_OUTPUTS_LOOP_OFFSET", rgbasm! {
+                // Read the token index of the output into a32[0] and look it up among the tokens
+                lds     OS_ASSET,a16[4],s16[1];
+                extr    s16[1],a32[0],a16[0];
+                put     a8[1],0;
+            }),
+            ("FN_SFA_GENESIS_LOOKUP_OFFSET", rgbasm! {
+                ldg     GS_TOKENS,a8[1],s16[0];
+                extr    s16[0],a32[1],a16[0];
+                eq.n    a32[0],a32[1];
+                jif     genesis_declared;
+                inc     a8[1];
+                lt.u    a8[1],a8[3];  // loop over the remaining tokens
+                jif     genesis_lookup;
+                fail;  // the output allocates an unknown token
+            }),
+            ("FN_SFA_GENESIS_DECLARED_OFFSET", rgbasm! {
+                inc     a16[4];
+                eq.n    a16[4],a16[3];  // stop once all the outputs are checked
+                jif     end;
+                jmp     genesis_outputs_loop;
+            }),
+            ("FN_SFA_GENESIS_LOOP_OFFSET", rgbasm! {
+                // Read the index of the next token into a32[0], keeping the previous one in a32[2]
+                cpy     a32[0],a32[2];
+                ldg     GS_TOKENS,a8[1],s16[0];
+                extr    s16[0],a32[0],a16[0];
+                // Token indexes must strictly increase, so that each token is declared once
+                lt.u    a32[2],a32[0];
+                test;
+                jmp     genesis_next;
+            }),
+            ("FN_SFA_BALANCE_OFFSET", rgbasm! {
+                // SUBROUTINE 3: Shared code, summing into a64[2] the inputs and into a64[0] the
+                // outputs allocating the token index in a32[0], checking that the sums are equal
+                // and returning into the loop selected by a8[5]
+                put     a64[2],0;
+                put     a16[1],0;
+            }),
+            ("FN_SFA_BALANCE_INPUTS_LOOP_OFFSET", rgbasm! {
+                ldp     OS_ASSET,a16[1],s16[1];  // read the input allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];
+                jif     input_matched;
+            }),
+            ("FN_SFA_BALANCE_INPUTS_NEXT_OFFSET", rgbasm! {
+                inc     a16[1];
+                lt.u    a16[1],a16[7];  // loop over the remaining inputs
+                jif     balance_inputs_loop;
+                put     a64[0],0;
+                put     a16[1],0;
+            }),
+            ("FN_SFA_BALANCE_OUTPUTS_LOOP_OFFSET", rgbasm! {
+                lds     OS_ASSET,a16[1],s16[1];  // read the output allocation
+                extr    s16[1],a32[1],a16[0];  // extract its token index
+                eq.n    a32[0],a32[1];
+                jif     output_matched;
+            }),
+            ("FN_SFA_BALANCE_OUTPUTS_NEXT_OFFSET", rgbasm! {
+                inc     a16[1];
+                lt.u    a16[1],a16[3];  // loop over the remaining outputs
+                jif     balance_outputs_loop;
+                // Check that the outputs allocate as much of the token as the inputs
+                eq.n    a64[0],a64[2];
+                test;
+                eq.n    a8[5],a8[6];
+                jif     inputs_next;
+                jmp     outputs_next;
+            }),
+            ("FN_SFA_INPUT_MATCHED_OFFSET", rgbasm! {
+                extr    s16[1],a64[1],a16[2];  // extract the input amount
+                add.uc  a64[1],a64[2];  // add it to the sum in a64[2]
+                test;  // fails in case of an overflow
+                jmp     balance_inputs_next;
+            }),
+            ("FN_SFA_OUTPUT_MATCHED_OFFSET", rgbasm! {
+                extr    s16[1],a64[1],a16[2];  // extract the output amount
+                add.uc  a64[1],a64[0];  // add it to the sum in a64[0]
+                test;  // fails in case of an overflow
+                jmp     balance_outputs_next;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_sfa_lib();
+        assembled.verify_offsets(&[
+            ("FN_SFA_TRANSFER_OFFSET", FN_SFA_TRANSFER_OFFSET),
+            ("FN_SFA_INPUTS_LOOP_OFFSET", FN_SFA_INPUTS_LOOP_OFFSET),
+            ("FN_SFA_INPUTS_NEXT_OFFSET", FN_SFA_INPUTS_NEXT_OFFSET),
+            ("FN_SFA_OUTPUTS_LOOP_OFFSET", FN_SFA_OUTPUTS_LOOP_OFFSET),
+            ("FN_SFA_OUTPUTS_NEXT_OFFSET", FN_SFA_OUTPUTS_NEXT_OFFSET),
+            ("FN_SFA_END_OFFSET", FN_SFA_END_OFFSET),
+            ("FN_SFA_GENESIS_OFFSET", FN_SFA_GENESIS_OFFSET),
+            ("FN_SFA_GENESIS_NEXT_OFFSET", FN_SFA_GENESIS_NEXT_OFFSET),
+            ("FN_SFA_GENESIS_OUTPUTS_LOOP_OFFSET", FN_SFA_GENESIS_OUTPUTS_LOOP_OFFSET),
+            ("FN_SFA_GENESIS_LOOKUP_OFFSET", FN_SFA_GENESIS_LOOKUP_OFFSET),
+            ("FN_SFA_GENESIS_DECLARED_OFFSET", FN_SFA_GENESIS_DECLARED_OFFSET),
+            ("FN_SFA_GENESIS_LOOP_OFFSET", FN_SFA_GENESIS_LOOP_OFFSET),
+            ("FN_SFA_BALANCE_OFFSET", FN_SFA_BALANCE_OFFSET),
+            ("FN_SFA_BALANCE_INPUTS_LOOP_OFFSET", FN_SFA_BALANCE_INPUTS_LOOP_OFFSET),
+            ("FN_SFA_BALANCE_INPUTS_NEXT_OFFSET", FN_SFA_BALANCE_INPUTS_NEXT_OFFSET),
+            ("FN_SFA_BALANCE_OUTPUTS_LOOP_OFFSET", FN_SFA_BALANCE_OUTPUTS_LOOP_OFFSET),
+            ("FN_SFA_BALANCE_OUTPUTS_NEXT_OFFSET", FN_SFA_BALANCE_OUTPUTS_NEXT_OFFSET),
+            ("FN_SFA_INPUT_MATCHED_OFFSET", FN_SFA_INPUT_MATCHED_OFFSET),
+            ("FN_SFA_OUTPUT_MATCHED_OFFSET", FN_SFA_OUTPUT_MATCHED_OFFSET),
+        ]);
+        SFA_LIB.verify("SFA_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,160 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation libraries of the stablecoin schema.
+//!
+//! The inflation validation follows the inflatable asset one, while the redeem validation checks
+//! the redeemed supply like the reissuable asset burn. Both end by checking the transition
+//! signature against the oracle public key from the contract global state, the same way the
+//! permissioned fungible asset transfer does. The signature check leaves the code unaligned to
+//! bytes, so each validation is a library of its own.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_STABLECOIN_INFLATION_OFFSET: u16 = 0;
+pub(super) const FN_STABLECOIN_REDEEM_OFFSET: u16 = 0;
+
+pub(super) const STABLECOIN_LIB_INFLATION: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x11, 0x03, 0x0b, 0x0b, 0x00, 0x04, 0x00,
+        0xca, 0xe8, 0x03, 0x00, 0x39, 0x30, 0x00, 0xd1, 0xaa, 0x0f, 0x01, 0x0b, 0x00, 0x05, 0x00,
+        0x20, 0x08, 0x60, 0x01, 0xd2, 0xaa, 0x0f, 0x01, 0x0b, 0x00, 0x06, 0x00, 0x0b, 0x02, 0x07,
+        0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00, 0x0b, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x01, 0x1e, 0x1f, 0x14, 0x00, 0x00, 0x00, 0x00, 0x15],
+    id: [
+        0xcf, 0xb3, 0x57, 0x2e, 0x9c, 0xbe, 0x1f, 0x6b, 0xc0, 0x27, 0x0c, 0x1e, 0x2b, 0x61, 0xa6,
+        0x9c, 0x84, 0xaf, 0x11, 0xf7, 0xd6, 0x17, 0xc8, 0x4b, 0x4f, 0x54, 0x69, 0xdc, 0xf6, 0x1d,
+        0xe1, 0x6f,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_stablecoin_lib_inflation() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_INFLATION_EXCEEDS_ALLOWANCE, ERRNO_INFLATION_MISMATCH, ERRNO_INVALID_SIGNATURE,
+        ERRNO_ISSUED_MISMATCH, ERRNO_MISSING_PUBKEY,
+    };
+    use crate::{GS_ISSUED_SUPPLY, GS_PUBKEY, MS_ALLOWED_INFLATION, OS_ASSET, OS_INFLATION};
+
+    crate::asm::assemble("stablecoin inflation", |_| {
+        vec![("FN_STABLECOIN_INFLATION_OFFSET", rgbasm! {
+            // Set common offsets
+            put     a8[1],0;
+            put     a16[0],0;
+
+            // Check reported issued supply equals sum of asset allocations in output
+            put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+            ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_ASSET;  // check sum of asset allocations in output equals issued_supply
+            test;
+            cpy     a64[0],a64[1];  // store issued supply in a64[1] for later
+
+            // Check reported allowed inflation equals sum of inflation rights in output
+            put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+            ldm     MS_ALLOWED_INFLATION,s16[0];  // read allowed inflation metadata
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+            test;
+
+            // Check that input inflation rights equals issued supply + allowed inflation
+            put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+            add.uc  a64[1],a64[0];  // result is stored in a64[0]
+            test;  // fails in case of an overflow
+            sps     OS_INFLATION;  // check sum of inflation rights in input equals a64[0]
+            test;
+
+            // Check transition signature
+            put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+            put     a32[0],0;  // set a32[0] to 0
+            ldc     GS_PUBKEY,a32[0],s16[0];  // get global oracle pubkey
+            put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+            vts     s16[0];  // verify signature
+            test;  // check it didn't fail
+            ret;  // return execution flow
+        })]
+    })
+}
+
+pub(super) const STABLECOIN_LIB_REDEEM: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0xdf, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd2, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x04, 0x00, 0x0b, 0x02, 0x05,
+        0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00, 0x09, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[0x2a, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x15],
+    id: [
+        0xf6, 0xef, 0x99, 0xf9, 0x53, 0xb7, 0x23, 0x2d, 0xcb, 0x47, 0x1e, 0x1a, 0xef, 0xcd, 0x00,
+        0x59, 0xbd, 0x60, 0xa5, 0xc0, 0x4b, 0xb1, 0x8c, 0x1a, 0x83, 0x08, 0x37, 0x9b, 0xf0, 0x08,
+        0x56, 0x9d,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_stablecoin_lib_redeem() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY, ERRNO_REDEEM_MISMATCH};
+    use crate::{GS_PUBKEY, GS_REDEEMED_SUPPLY, OS_ASSET};
+
+    crate::asm::assemble("stablecoin redeem", |_| {
+        vec![("FN_STABLECOIN_REDEEM_OFFSET", rgbasm! {
+            // Check reported redeemed supply equals sum of asset allocations in input
+            put     a8[0],ERRNO_REDEEM_MISMATCH;  // set errno
+            put     a8[1],0;
+            put     a16[0],0;
+            ldg     GS_REDEEMED_SUPPLY,a8[1],s16[0];  // read redeemed supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sps     OS_ASSET;  // check sum of asset allocations in input equals a64[0]
+            test;
+
+            // Check transition signature
+            put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+            put     a32[0],0;  // set a32[0] to 0
+            ldc     GS_PUBKEY,a32[0],s16[0];  // get global oracle pubkey
+            put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+            vts     s16[0];  // verify signature
+            test;  // check it didn't fail
+            ret;  // return execution flow
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let inflation = assemble_stablecoin_lib_inflation();
+        inflation
+            .verify_offsets(&[("FN_STABLECOIN_INFLATION_OFFSET", FN_STABLECOIN_INFLATION_OFFSET)]);
+        STABLECOIN_LIB_INFLATION.verify("STABLECOIN_LIB_INFLATION", inflation.lib);
+        let redeem = assemble_stablecoin_lib_redeem();
+        redeem.verify_offsets(&[("FN_STABLECOIN_REDEEM_OFFSET", FN_STABLECOIN_REDEEM_OFFSET)]);
+        STABLECOIN_LIB_REDEEM.verify("STABLECOIN_LIB_REDEEM", redeem.lib);
+    }
+}
@@ -0,0 +1,116 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the subscription token schema.
+//!
+//! A renewal must preserve the token like a UDA transfer, set an expiry later than the current one
+//! and be signed by the issuer. The contract global state doesn't contain the renewal being
+//! validated yet, so its latest expiry is the current one.
+//!
+//! The signature check comes last, since code following `vts` isn't byte-aligned.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_SUBSCRIPTION_RENEW_OFFSET: u16 = 0;
+
+pub(super) const SUBSCRIPTION_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0xc4, 0xa0, 0x0f, 0x00, 0x39, 0x20, 0x00,
+        0xc5, 0xa0, 0x0f, 0x10, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x01, 0x0b, 0x00, 0x03, 0x00,
+        0x0b, 0x11, 0x04, 0x00, 0x39, 0x31, 0x20, 0x0b, 0x0b, 0x06, 0x00, 0x19, 0x01, 0x61, 0x01,
+        0x0b, 0x00, 0x0e, 0x00, 0x0b, 0x08, 0x00, 0x00, 0xc8, 0xfb, 0x07, 0x01, 0x39, 0x20, 0x01,
+        0x0b, 0x02, 0x07, 0x00, 0xc9, 0xfb, 0x07, 0x10, 0x39, 0x21, 0x02, 0x18, 0x11, 0x41, 0x01,
+        0x0b, 0x00, 0x0f, 0x00, 0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00, 0x10, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[
+        0x00, 0x00, 0x00, 0x0a, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44,
+        0x14, 0x15,
+    ],
+    id: [
+        0x05, 0xdf, 0xe5, 0xd7, 0xcf, 0xff, 0x0e, 0xac, 0xd9, 0x2c, 0x0e, 0xa0, 0x34, 0x09, 0xb5,
+        0xae, 0x21, 0x08, 0x79, 0x08, 0xc0, 0x05, 0x31, 0x99, 0x1f, 0x6a, 0x34, 0xa3, 0x73, 0x40,
+        0x42, 0x42,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_subscription_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_EXPIRY_NOT_EXTENDED, ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY,
+        ERRNO_NON_EQUAL_IN_OUT, ERRNO_NON_FRACTIONAL,
+    };
+    use crate::{GS_EXPIRY, GS_PUBKEY, OS_ASSET};
+
+    crate::asm::assemble("subscription token", |_| {
+        vec![("FN_SUBSCRIPTION_RENEW_OFFSET", rgbasm! {
+            // Check that the token index of the input matches the output one
+            put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+            put     a16[0],0;
+            ldp     OS_ASSET,a16[0],s16[0];  // read the input allocation
+            extr    s16[0],a32[0],a16[0];  // extract its token index
+            lds     OS_ASSET,a16[0],s16[1];  // read the output allocation
+            extr    s16[1],a32[1],a16[0];  // extract its token index
+            eq.n    a32[0],a32[1];
+            test;
+
+            // Check that the output owns the whole token
+            put     a8[0],ERRNO_NON_FRACTIONAL;  // set errno
+            put     a16[2],4;  // offset of the fraction in an allocation
+            extr    s16[1],a64[0],a16[2];
+            put     a64[1],1;
+            eq.n    a64[0],a64[1];
+            test;
+
+            // Check that the new expiry is later than the current one
+            put     a8[0],ERRNO_EXPIRY_NOT_EXTENDED;  // set errno
+            put     a8[1],0;
+            ldg     GS_EXPIRY,a8[1],s16[0];  // read the new expiry
+            extr    s16[0],a32[1],a16[0];
+            put     a32[0],0;
+            ldc     GS_EXPIRY,a32[0],s16[1];  // read the latest expiry of the contract
+            extr    s16[1],a32[2],a16[0];
+            lt.u    a32[2],a32[1];  // current < new
+            test;
+
+            // Check transition signature
+            put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+            ldc     GS_PUBKEY,a32[0],s16[0];  // read the issuer pubkey
+            put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+            vts     s16[0];  // verify signature
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_subscription_lib();
+        assembled.verify_offsets(&[("FN_SUBSCRIPTION_RENEW_OFFSET", FN_SUBSCRIPTION_RENEW_OFFSET)]);
+        SUBSCRIPTION_LIB.verify("SUBSCRIPTION_LIB", assembled.lib);
+    }
+}
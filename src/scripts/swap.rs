@@ -0,0 +1,142 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the swap order schema.
+//!
+//! Both fills and cancellations must be signed by the maker of the order. A fill also requires the
+//! amount filled over the contract history, including the one it records, not to exceed the
+//! offered amount; the contract global state doesn't contain the fill being validated yet.
+//!
+//! The genesis has no rules beyond the schema ones, yet it needs a validator: the consignment
+//! validator adds the global state of an operation to the contract state only once its validator
+//! succeeds, so without one the fills and cancellations couldn't read the offered amount and the
+//! maker pubkey.
+//!
+//! The fill subroutine falls through to the signature check, which is the cancel subroutine: code
+//! following `vts` isn't byte-aligned, so no subroutine can start after it.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_SWAP_GENESIS_OFFSET: u16 = 0;
+pub(super) const FN_SWAP_FILL_OFFSET: u16 = 1;
+pub(super) const FN_SWAP_CANCEL_OFFSET: u16 = 67;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_SWAP_FILLED_LOOP_OFFSET: u16 = 31;
+#[cfg(test)]
+const FN_SWAP_FILLED_NEXT_OFFSET: u16 = 45;
+
+pub(super) const SWAP_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x07, 0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0xf4,
+        0x07, 0x01, 0x39, 0x30, 0x01, 0x0b, 0x02, 0x04, 0x00, 0xc3, 0xf4, 0x07, 0x01, 0x02, 0x2d,
+        0x00, 0xc9, 0xf4, 0x07, 0x00, 0x39, 0x30, 0x00, 0x20, 0x00, 0x61, 0x01, 0x24, 0x02, 0x01,
+        0x18, 0x01, 0x41, 0x03, 0x1f, 0x00, 0x0b, 0x02, 0x01, 0x00, 0xc9, 0xf1, 0x07, 0x00, 0x39,
+        0x30, 0x02, 0x18, 0x11, 0x61, 0x1f, 0x01, 0x0b, 0x00, 0x08, 0x00, 0x0b, 0x02, 0x01, 0x00,
+        0xc9, 0xbe, 0x0b, 0x00, 0x0b, 0x00, 0x09, 0x00, 0xd3, 0x10, 0x70,
+    ],
+    data: &[0x43, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x15],
+    id: [
+        0x2e, 0x1f, 0x45, 0xa3, 0x61, 0xd1, 0x15, 0x54, 0x6a, 0xb2, 0x3f, 0x32, 0x8b, 0xa0, 0x2b,
+        0x20, 0x5f, 0x98, 0x4a, 0xdc, 0x59, 0x09, 0x93, 0xac, 0x59, 0x3f, 0x48, 0x97, 0x39, 0x6e,
+        0x59, 0x1d,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_swap_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_FILL_EXCEEDS_OFFER, ERRNO_INVALID_SIGNATURE, ERRNO_MISSING_PUBKEY};
+    use crate::{GS_FILLED, GS_OFFERED_AMOUNT, GS_PUBKEY};
+
+    crate::asm::assemble("swap order", |labels| {
+        let filled_loop = labels.offset("FN_SWAP_FILLED_LOOP_OFFSET");
+        let filled_next = labels.offset("FN_SWAP_FILLED_NEXT_OFFSET");
+        vec![
+            ("FN_SWAP_GENESIS_OFFSET", rgbasm! {
+                ret;
+            }),
+            ("FN_SWAP_FILL_OFFSET", rgbasm! {
+                // Read the amount filled by this transition into a64[1]
+                put     a8[0],ERRNO_FILL_EXCEEDS_OFFER;  // set errno
+                put     a8[1],0;
+                put     a16[0],0;
+                ldg     GS_FILLED,a8[1],s16[0];  // read filled amount global state
+                extr    s16[0],a64[1],a16[0];
+
+                // Add the amounts filled by the previous fills to a64[1]
+                put     a32[0],0;  // depth of the contract global state entry to read
+                cnc     GS_FILLED,a16[1];  // count previous fills into a32[1]
+                jmp     filled_next;
+            }),
+            ("FN_SWAP_FILLED_LOOP_OFFSET", rgbasm! {
+                ldc     GS_FILLED,a32[0],s16[0];  // read a previous fill
+                extr    s16[0],a64[0],a16[0];
+                add.uc  a64[0],a64[1];  // add it to the sum in a64[1]
+                test;  // fails in case of an overflow
+                inc     a32[0];
+            }),
+            ("FN_SWAP_FILLED_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining fills
+                jif     filled_loop;
+
+                // Check that the filled amount doesn't exceed the offered one
+                put     a32[0],0;
+                ldc     GS_OFFERED_AMOUNT,a32[0],s16[0];  // read the offered amount
+                extr    s16[0],a64[2],a16[0];
+                lt.u    a64[2],a64[1];  // offered < filled
+                inv     st0;  // offered >= filled
+                test;
+                // Fall through to the signature check
+            }),
+            ("FN_SWAP_CANCEL_OFFSET", rgbasm! {
+                // Check transition signature
+                put     a8[0],ERRNO_MISSING_PUBKEY;  // set errno
+                put     a32[0],0;
+                ldc     GS_PUBKEY,a32[0],s16[0];  // read the maker pubkey
+                put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+                vts     s16[0];  // verify signature
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_swap_lib();
+        assembled.verify_offsets(&[
+            ("FN_SWAP_GENESIS_OFFSET", FN_SWAP_GENESIS_OFFSET),
+            ("FN_SWAP_FILL_OFFSET", FN_SWAP_FILL_OFFSET),
+            ("FN_SWAP_FILLED_LOOP_OFFSET", FN_SWAP_FILLED_LOOP_OFFSET),
+            ("FN_SWAP_FILLED_NEXT_OFFSET", FN_SWAP_FILLED_NEXT_OFFSET),
+            ("FN_SWAP_CANCEL_OFFSET", FN_SWAP_CANCEL_OFFSET),
+        ]);
+        SWAP_LIB.verify("SWAP_LIB", assembled.lib);
+    }
+}
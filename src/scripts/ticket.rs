@@ -0,0 +1,81 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the event ticket schema.
+//!
+//! Tickets are issued and transferred with the UDC library, so this library only validates their
+//! redemption: the redeem transition spends a single ticket without assigning it again, and must
+//! record the redeemed ticket in the global state.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_TICKET_REDEEM_OFFSET: u16 = 0;
+
+pub(super) const TICKET_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc4, 0xa0, 0x0f,
+        0x00, 0x39, 0x20, 0x00, 0xc8, 0x3a, 0x08, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x01,
+        0x07,
+    ],
+    data: &[0x0d, 0x00, 0x00, 0x00],
+    id: [
+        0x72, 0xd4, 0xce, 0xe7, 0x76, 0x2e, 0xe0, 0x66, 0x46, 0x7e, 0x5e, 0xa8, 0x06, 0x90, 0xbf,
+        0x0b, 0xae, 0x9d, 0xab, 0x95, 0xcf, 0x8f, 0x7a, 0xb2, 0x38, 0x42, 0x38, 0x2e, 0x47, 0x6b,
+        0xba, 0xe2,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_ticket_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::ERRNO_TICKET_MISMATCH;
+    use crate::{GS_REDEEMED_TICKETS, OS_ASSET};
+
+    crate::asm::assemble("ticket", |_| {
+        vec![("FN_TICKET_REDEEM_OFFSET", rgbasm! {
+            // Check the redeemed ticket reported in the global state is the spent one
+            put     a8[0],ERRNO_TICKET_MISMATCH;  // set errno
+            put     a8[1],0;
+            put     a16[0],0;
+            ldp     OS_ASSET,a16[0],s16[0];  // read the spent allocation
+            extr    s16[0],a32[0],a16[0];  // and store its token index in a32[0]
+            ldg     GS_REDEEMED_TICKETS,a8[1],s16[1];  // read the redeemed ticket global state
+            extr    s16[1],a32[1],a16[0];  // and store its token index in a32[1]
+            eq.n    a32[0],a32[1];
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_ticket_lib();
+        assembled.verify_offsets(&[("FN_TICKET_REDEEM_OFFSET", FN_TICKET_REDEEM_OFFSET)]);
+        TICKET_LIB.verify("TICKET_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,301 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the threshold inflatable asset schema.
+//!
+//! A transition carries a single signature, so the approvals of the keys are collected one by one
+//! in the approval state: it holds the position following the last approving key, in the order
+//! the keys are read from the contract global state, and the number of approvals. An approval
+//! looks its signer up among the keys like the multi-key permissioned asset validation does,
+//! requires it to follow the last approving key, which rules out approving twice with the same
+//! key, and verifies its signature. Next to the approval state, each approval assigns the approved
+//! inflation, the supply the approvals allow to issue: the first approval sets it, and the later
+//! ones must keep it, so that every signature commits to it. An inflation spends both, requiring
+//! at least the threshold of approvals and issuing exactly the approved inflation, and resets the
+//! approval state; the approved inflation, which can't be zero, is only assigned again by the
+//! next first approval.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_TIFA_GENESIS_OFFSET: u16 = 0;
+pub(super) const FN_TIFA_INFLATION_OFFSET: u16 = 116;
+pub(super) const FN_TIFA_APPROVE_OFFSET: u16 = 242;
+// Loop heads, only targeted by jumps within the library
+#[cfg(test)]
+const FN_TIFA_SIGNER_LOOP_OFFSET: u16 = 261;
+#[cfg(test)]
+const FN_TIFA_SIGNER_NEXT_OFFSET: u16 = 273;
+#[cfg(test)]
+const FN_TIFA_SIGNED_OFFSET: u16 = 280;
+#[cfg(test)]
+const FN_TIFA_APPROVED_OFFSET: u16 = 346;
+
+pub(super) const TIFA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x09, 0x03, 0x00, 0x0b, 0x00, 0x05,
+        0x00, 0xc8, 0xda, 0x07, 0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x06,
+        0x00, 0xc8, 0xdb, 0x07, 0x11, 0x39, 0x31, 0x01, 0x21, 0x08, 0x60, 0x01, 0xd1, 0xaa, 0x0f,
+        0x01, 0x0b, 0x00, 0x07, 0x00, 0xc8, 0xec, 0x07, 0x21, 0x39, 0x32, 0x02, 0x0b, 0x1b, 0x08,
+        0x00, 0x18, 0x11, 0x63, 0x1f, 0x01, 0x0b, 0x1b, 0x10, 0x00, 0x18, 0x10, 0x63, 0x1f, 0x01,
+        0x39, 0x02, 0x02, 0x24, 0x10, 0xff, 0xc8, 0xbe, 0x0b, 0x32, 0x0b, 0x00, 0x18, 0x00, 0xc5,
+        0xb1, 0x0f, 0x40, 0x39, 0x24, 0x00, 0x0b, 0x0a, 0x09, 0x00, 0x19, 0x01, 0x41, 0x01, 0x39,
+        0x34, 0x14, 0x0b, 0x2b, 0x19, 0x00, 0x19, 0x21, 0x65, 0x01, 0x07, 0x0b, 0x08, 0x00, 0x00,
+        0x0b, 0x01, 0x00, 0x00, 0x0b, 0x09, 0x03, 0x00, 0x0b, 0x00, 0x05, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x11, 0x03, 0x0b, 0x0b, 0x00, 0x06, 0x00,
+        0xca, 0xe8, 0x03, 0x00, 0x39, 0x30, 0x00, 0xd1, 0xaa, 0x0f, 0x01, 0x0b, 0x00, 0x21, 0x00,
+        0x20, 0x08, 0x60, 0x01, 0xd2, 0xaa, 0x0f, 0x01, 0x0b, 0x00, 0x22, 0x00, 0x0b, 0x02, 0x09,
+        0x00, 0xc9, 0xec, 0x07, 0x10, 0x39, 0x31, 0x02, 0xc4, 0xb1, 0x0f, 0x20, 0x39, 0x32, 0x13,
+        0x18, 0x19, 0x62, 0x1f, 0x01, 0x0b, 0x00, 0x23, 0x00, 0x11, 0x0b, 0x03, 0xd2, 0xc1, 0x0f,
+        0x01, 0x0b, 0x00, 0x18, 0x00, 0xc5, 0xb1, 0x0f, 0x30, 0x39, 0x23, 0x01, 0x0b, 0x12, 0x09,
+        0x00, 0x19, 0x09, 0x42, 0x01, 0x39, 0x33, 0x14, 0x0b, 0x2b, 0x19, 0x00, 0x19, 0x21, 0x65,
+        0x01, 0x07, 0x0b, 0x00, 0x24, 0x00, 0xca, 0xe9, 0x03, 0x01, 0x0b, 0x02, 0x09, 0x00, 0xc3,
+        0xbe, 0x0b, 0x01, 0x02, 0x11, 0x01, 0xc9, 0xbe, 0x0b, 0x00, 0x36, 0x10, 0x03, 0x18, 0x01,
+        0x24, 0x02, 0x01, 0x18, 0x01, 0x41, 0x03, 0x05, 0x01, 0x01, 0x0b, 0x00, 0x18, 0x00, 0x0b,
+        0x01, 0x00, 0x00, 0x0b, 0x09, 0x03, 0x00, 0xc4, 0xb1, 0x0f, 0x20, 0x39, 0x22, 0x02, 0x39,
+        0x32, 0x10, 0x18, 0x01, 0x42, 0x1f, 0x01, 0x24, 0x02, 0x01, 0x24, 0x03, 0x01, 0xc5, 0xb1,
+        0x0f, 0x30, 0x39, 0x23, 0x03, 0x19, 0x01, 0x43, 0x01, 0x39, 0x33, 0x11, 0x19, 0x01, 0x61,
+        0x01, 0x0b, 0x13, 0x08, 0x00, 0x19, 0x01, 0x62, 0x03, 0x5a, 0x01, 0x1f, 0xd0, 0xc1, 0x0f,
+        0x01, 0x0b, 0x00, 0x25, 0x00, 0xd3, 0x11, 0x70,
+    ],
+    data: &[
+        0x00, 0x00, 0x00, 0x04, 0x00, 0x01, 0x1e, 0x20, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x1f, 0x22, 0x4f, 0x16, 0x15,
+    ],
+    id: [
+        0xc5, 0x83, 0x99, 0xab, 0x15, 0xd7, 0x10, 0xd9, 0xa2, 0x0a, 0x1a, 0x7b, 0x6c, 0x45, 0x98,
+        0x5f, 0x4c, 0xcd, 0xf1, 0x8e, 0x6a, 0x1f, 0xe5, 0x47, 0x65, 0x8d, 0x57, 0x85, 0x60, 0xe3,
+        0x3a, 0x80,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_tifa_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_APPROVAL_MISMATCH, ERRNO_INFLATION_EXCEEDS_ALLOWANCE, ERRNO_INFLATION_MISMATCH,
+        ERRNO_INSUFFICIENT_APPROVALS, ERRNO_INVALID_SIGNATURE, ERRNO_INVALID_THRESHOLD,
+        ERRNO_ISSUED_MISMATCH, ERRNO_UNAPPROVED_INFLATION, ERRNO_UNAUTHORIZED_SIGNER,
+    };
+    use crate::{
+        GS_INFLATION_THRESHOLD, GS_ISSUED_SUPPLY, GS_MAX_SUPPLY, GS_PUBKEY, MS_ALLOWED_INFLATION,
+        MS_SIGNER, OS_APPROVAL, OS_APPROVED_INFLATION, OS_ASSET, OS_INFLATION,
+    };
+
+    crate::asm::assemble("threshold inflatable asset", |labels| {
+        let signer_loop = labels.offset("FN_TIFA_SIGNER_LOOP_OFFSET");
+        let signer_next = labels.offset("FN_TIFA_SIGNER_NEXT_OFFSET");
+        let signed = labels.offset("FN_TIFA_SIGNED_OFFSET");
+        let approved = labels.offset("FN_TIFA_APPROVED_OFFSET");
+        vec![
+            ("FN_TIFA_GENESIS_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+                put     a16[1],4;  // offset of the approval count in the approval state
+
+                // Check reported issued supply against sum of asset allocations in output
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of assets assignments in output equals a64[0]
+                test;
+
+                // Check that sum of inflation rights = max supply - issued supply
+                put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+                ldg     GS_MAX_SUPPLY,a8[1],s16[1];  // read max supply global state
+                extr    s16[1],a64[1],a16[0];  // and store it in a64[1]
+                sub.uc  a64[1],a64[0];  // issued supply is still in a64[0], result overwrites a64[0]
+                test;  // fails if result is <0
+                sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+                test;
+
+                // Check that 0 < threshold <= number of keys
+                put     a8[0],ERRNO_INVALID_THRESHOLD;  // set errno
+                ldg     GS_INFLATION_THRESHOLD,a8[1],s16[2];  // read threshold global state
+                extr    s16[2],a64[2],a16[0];  // and store it in a64[2]
+                put     a64[3],1;
+                lt.u    a64[2],a64[3];  // threshold < 1
+                inv     st0;
+                test;
+                put     a64[3],255;
+                gt.u    a64[2],a64[3];  // threshold > 255, beyond the keys the script can index
+                inv     st0;
+                test;
+                extr    s16[2],a8[2],a16[0];  // store the threshold, which fits a byte, in a8[2]
+                dec     a8[2];  // index of the last key the threshold needs
+                ldg     GS_PUBKEY,a8[2],s16[3];  // fails if there are fewer keys
+
+                // Check that no key approved yet
+                put     a8[0],ERRNO_APPROVAL_MISMATCH;  // set errno
+                lds     OS_APPROVAL,a16[0],s16[4];  // read output approval state
+                extr    s16[4],a32[0],a16[0];  // position following the last approving key
+                put     a32[1],0;
+                eq.n    a32[0],a32[1];
+                test;
+                extr    s16[4],a64[4],a16[1];  // number of approvals
+                put     a64[5],0;
+                eq.n    a64[4],a64[5];
+                test;
+
+                ret;
+            }),
+            ("FN_TIFA_INFLATION_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+                put     a16[1],4;  // offset of the approval count in the approval state
+
+                // Check reported issued supply equals sum of asset allocations in output
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of asset allocations in output equals issued_supply
+                test;
+                cpy     a64[0],a64[1];  // store issued supply in a64[1] for later
+
+                // Check reported allowed inflation equals sum of inflation rights in output
+                put     a8[0],ERRNO_INFLATION_MISMATCH;  // set errno
+                ldm     MS_ALLOWED_INFLATION,s16[0];  // read allowed inflation metadata
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_INFLATION;  // check sum of inflation rights in output equals a64[0]
+                test;
+
+                // Check that input inflation rights equals issued supply + allowed inflation
+                put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+                add.uc  a64[1],a64[0];  // result is stored in a64[0]
+                test;  // fails in case of an overflow
+                sps     OS_INFLATION;  // check sum of inflation rights in input equals a64[0]
+                test;
+
+                // Check that the spent approvals reach the threshold
+                put     a8[0],ERRNO_INSUFFICIENT_APPROVALS;  // set errno
+                put     a32[0],0;
+                ldc     GS_INFLATION_THRESHOLD,a32[0],s16[1];  // read threshold global state
+                extr    s16[1],a64[2],a16[0];  // and store it in a64[2]
+                ldp     OS_APPROVAL,a16[0],s16[2];  // read input approval state
+                extr    s16[2],a64[3],a16[1];  // number of approvals
+                lt.u    a64[3],a64[2];  // approvals < threshold
+                inv     st0;
+                test;
+
+                // Check that the inflation issues the approved inflation
+                put     a8[0],ERRNO_UNAPPROVED_INFLATION;  // set errno
+                cpy     a64[1],a64[0];  // issued supply is still in a64[1]
+                sps     OS_APPROVED_INFLATION;  // check the spent approved inflation equals a64[0]
+                test;
+
+                // Check that the output approval state is reset
+                put     a8[0],ERRNO_APPROVAL_MISMATCH;  // set errno
+                lds     OS_APPROVAL,a16[0],s16[3];  // read output approval state
+                extr    s16[3],a32[1],a16[0];  // position following the last approving key
+                put     a32[2],0;
+                eq.n    a32[1],a32[2];
+                test;
+                extr    s16[3],a64[4],a16[1];  // number of approvals
+                put     a64[5],0;
+                eq.n    a64[4],a64[5];
+                test;
+
+                ret;
+            }),
+            ("FN_TIFA_APPROVE_OFFSET", rgbasm! {
+                // Look the signer up among the approving keys
+                put     a8[0],ERRNO_UNAUTHORIZED_SIGNER;  // set errno
+                ldm     MS_SIGNER,s16[1];  // read signer metadata
+                put     a32[0],0;  // depth of the contract global state entry to read
+                cnc     GS_PUBKEY,a16[1];  // count approving keys into a32[1]
+                jmp     signer_next;
+            }),
+            ("FN_TIFA_SIGNER_LOOP_OFFSET", rgbasm! {
+                ldc     GS_PUBKEY,a32[0],s16[0];  // read an approving key
+                eq      s16[0],s16[1];  // compare it with the signer
+                jif     signed;
+                inc     a32[0];
+            }),
+            ("FN_TIFA_SIGNER_NEXT_OFFSET", rgbasm! {
+                lt.u    a32[0],a32[1];  // loop over the remaining keys
+                jif     signer_loop;
+                test;  // fails as no key matched the signer
+            }),
+            ("FN_TIFA_SIGNED_OFFSET", rgbasm! {
+                // Check that the signer follows the last approving key
+                put     a8[0],ERRNO_APPROVAL_MISMATCH;  // set errno
+                put     a16[0],0;
+                put     a16[1],4;  // offset of the approval count in the approval state
+                ldp     OS_APPROVAL,a16[0],s16[2];  // read input approval state
+                extr    s16[2],a32[2],a16[0];  // position following the last approving key
+                extr    s16[2],a64[0],a16[1];  // number of approvals
+                lt.u    a32[0],a32[2];  // signer position < position following the last one
+                inv     st0;
+                test;
+
+                // Check that the output approval state records the approval
+                inc     a32[0];  // position following the signer
+                inc     a64[0];  // number of approvals including the signer
+                lds     OS_APPROVAL,a16[0],s16[3];  // read output approval state
+                extr    s16[3],a32[3],a16[0];
+                eq.n    a32[0],a32[3];
+                test;
+                extr    s16[3],a64[1],a16[1];
+                eq.n    a64[0],a64[1];
+                test;
+
+                // Check that only the first approval sets the approved inflation
+                put     a64[2],1;
+                eq.n    a64[0],a64[2];  // the signer is the first to approve
+                jif     approved;
+                inv     st0;  // the comparison leaves st0 false, which `svs` doesn't reset
+                svs     OS_APPROVED_INFLATION;  // later ones keep the approved inflation
+                test;
+            }),
+            ("FN_TIFA_APPROVED_OFFSET", rgbasm! {
+                // Check transition signature
+                put     a8[0],ERRNO_INVALID_SIGNATURE;  // set errno
+                vts     s16[1];  // verify signature
+                test;  // check it didn't fail
+                ret;  // return execution flow
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_tifa_lib();
+        assembled.verify_offsets(&[
+            ("FN_TIFA_GENESIS_OFFSET", FN_TIFA_GENESIS_OFFSET),
+            ("FN_TIFA_INFLATION_OFFSET", FN_TIFA_INFLATION_OFFSET),
+            ("FN_TIFA_APPROVE_OFFSET", FN_TIFA_APPROVE_OFFSET),
+            ("FN_TIFA_SIGNER_LOOP_OFFSET", FN_TIFA_SIGNER_LOOP_OFFSET),
+            ("FN_TIFA_SIGNER_NEXT_OFFSET", FN_TIFA_SIGNER_NEXT_OFFSET),
+            ("FN_TIFA_SIGNED_OFFSET", FN_TIFA_SIGNED_OFFSET),
+            ("FN_TIFA_APPROVED_OFFSET", FN_TIFA_APPROVED_OFFSET),
+        ]);
+        TIFA_LIB.verify("TIFA_LIB", assembled.lib);
+    }
+}
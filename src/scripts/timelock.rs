@@ -0,0 +1,98 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the timelocked asset schema.
+//!
+//! A transfer commits to the absolute lock time of its witness transaction, which must be a block
+//! height keeping the witness from being mined before the lock height committed by the genesis,
+//! i.e. at least the lock height minus one, and must otherwise keep the sum of the inputs. The lock
+//! time of the witness itself is not checked, see [`crate::height`].
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_TIMELOCK_TRANSFER_OFFSET: u16 = 0;
+
+pub(super) const TIMELOCK_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x01, 0x00, 0x0b, 0x01, 0x02, 0x00, 0xc8, 0x0a, 0x08,
+        0x01, 0x39, 0x20, 0x01, 0x0b, 0x02, 0x04, 0x00, 0xc9, 0xea, 0x07, 0x10, 0x39, 0x21, 0x02,
+        0x0b, 0x1a, 0x08, 0x00, 0x18, 0x09, 0x43, 0x01, 0x0b, 0x1a, 0x0c, 0x00, 0x20, 0x08, 0x43,
+        0x18, 0x19, 0x42, 0x1f, 0x01, 0x0b, 0x00, 0x01, 0x00, 0xd0, 0xa0, 0x0f, 0x01, 0x07,
+    ],
+    data: &[
+        0x39, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x65, 0xcd, 0x1d, 0x01, 0x00, 0x00,
+        0x00,
+    ],
+    id: [
+        0xb7, 0x7b, 0x82, 0xaf, 0x21, 0x5f, 0xed, 0x2b, 0x11, 0x32, 0xc9, 0x7d, 0x99, 0x82, 0x10,
+        0x21, 0xe8, 0xf9, 0xef, 0xf8, 0xdc, 0xeb, 0x5d, 0x76, 0xff, 0xa5, 0x15, 0x3a, 0xa3, 0xf6,
+        0xc7, 0xe8,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_timelock_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_NON_EQUAL_IN_OUT, ERRNO_TRANSFER_LOCKED};
+    use crate::{GS_LOCK_HEIGHT, GS_LOCK_TIME, OS_ASSET};
+
+    crate::asm::assemble("timelocked asset", |_| {
+        vec![("FN_TIMELOCK_TRANSFER_OFFSET", rgbasm! {
+            // Check that the witness can't be mined before the lock height
+            put     a8[0],ERRNO_TRANSFER_LOCKED;  // set errno
+            put     a8[1],0;
+            put     a16[0],0;
+            ldg     GS_LOCK_TIME,a8[1],s16[0];  // read the lock time of the transfer
+            extr    s16[0],a32[1],a16[0];  // and store it in a32[1]
+            put     a32[0],0;  // depth of the contract global state entry to read
+            ldc     GS_LOCK_HEIGHT,a32[0],s16[1];  // read the lock height
+            extr    s16[1],a32[2],a16[0];  // and store it in a32[2]
+            put     a32[3],500000000;  // lock times from it on are timestamps
+            lt.u    a32[1],a32[3];
+            test;
+            put     a32[3],1;
+            add.uc  a32[1],a32[3];  // first height the witness can be mined at, into a32[3]
+            lt.u    a32[3],a32[2];  // first height < lock height
+            inv     st0;  // first height >= lock height
+            test;
+
+            // Check that the sum of inputs is equal to the sum of outputs
+            put     a8[0],ERRNO_NON_EQUAL_IN_OUT;  // set errno
+            svs     OS_ASSET;
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_timelock_lib();
+        assembled.verify_offsets(&[("FN_TIMELOCK_TRANSFER_OFFSET", FN_TIMELOCK_TRANSFER_OFFSET)]);
+        TIMELOCK_LIB.verify("TIMELOCK_LIB", assembled.lib);
+    }
+}
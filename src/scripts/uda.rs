@@ -0,0 +1,121 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the unique digital asset schema.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_GENESIS_OFFSET: u16 = 11;
+pub(super) const FN_TRANSFER_OFFSET: u16 = 0;
+pub(super) const FN_SHARED_OFFSET: u16 = 23;
+
+pub(super) const UDA_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x01, 0x00, 0x00, 0xc4, 0xa0, 0x0f, 0x00, 0x02, 0x17, 0x00, 0x0b, 0x01, 0x00, 0x00,
+        0x0b, 0x08, 0x00, 0x00, 0xc8, 0x36, 0x08, 0x01, 0x0b, 0x00, 0x00, 0x00, 0x39, 0x20, 0x00,
+        0x0b, 0x09, 0x00, 0x00, 0xc5, 0xa0, 0x0f, 0x11, 0x39, 0x21, 0x01, 0x19, 0x01, 0x41, 0x01,
+        0x0b, 0x00, 0x02, 0x00, 0x0b, 0x11, 0x03, 0x00, 0x39, 0x31, 0x20, 0x0b, 0x0b, 0x05, 0x00,
+        0x19, 0x01, 0x61, 0x01,
+    ],
+    data: &[0x00, 0x00, 0x0a, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    id: [
+        0x36, 0x16, 0x71, 0x75, 0xc9, 0x44, 0xd3, 0x8d, 0xb5, 0xb3, 0x6f, 0x0c, 0x1d, 0x1f, 0xe4,
+        0xcf, 0xf4, 0x9a, 0x75, 0x11, 0xa3, 0xbe, 0x25, 0x04, 0x95, 0xf2, 0xa9, 0xe8, 0x38, 0x84,
+        0xd4, 0xa5,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_uda_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_NON_EQUAL_IN_OUT, ERRNO_NON_FRACTIONAL};
+    use crate::{GS_TOKENS, OS_ASSET};
+
+    crate::asm::assemble("unique digital asset", |labels| {
+        let shared = labels.offset("FN_SHARED_OFFSET");
+        vec![
+            ("FN_TRANSFER_OFFSET", rgbasm! {
+                // SUBROUTINE 2: Transfer validation
+                // Put 0 to a16[0]
+                put     a16[0],0;
+                // Read previous state into s16[0]
+                ldp     OS_ASSET,a16[0],s16[0];
+                // jump into SUBROUTINE 3 to reuse the code
+                jmp     shared;
+            }),
+            ("FN_GENESIS_OFFSET", rgbasm! {
+                // SUBROUTINE 1: Genesis validation
+                // Set offset to read state from strings
+                put     a16[0],0x00;
+                // Set which state index to read
+                put     a8[1],0x00;
+                // Read global state into s16[0]
+                ldg     GS_TOKENS,a8[1],s16[0];
+            }),
+            ("FN_SHARED_OFFSET", rgbasm! {
+                // SUBROUTINE 3: Shared code
+                // Set errno
+                put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+                // Extract 128 bits from the beginning of s16[0] into a32[0]
+                extr    s16[0],a32[0],a16[0];
+                // Set which state index to read
+                put     a16[1],0x00;
+                // Read owned state into s16[1]
+                lds     OS_ASSET,a16[1],s16[1];
+                // Extract 128 bits from the beginning of s16[1] into a32[1]
+                extr    s16[1],a32[1],a16[0];
+                // Check that token indexes match
+                eq.n    a32[0],a32[1];
+                // Fail if they don't
+                test;
+
+                // Set errno
+                put     a8[0],ERRNO_NON_FRACTIONAL;
+                // Put offset for the data into a16[2]
+                put     a16[2],4;
+                // Extract 128 bits starting from the fifth byte of s16[1] into a64[0]
+                extr    s16[1],a64[0],a16[2];
+                // Check that owned fraction == 1
+                put     a64[1],1;
+                eq.n    a64[0],a64[1];
+                // Fail if not
+                test;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_uda_lib();
+        assembled.verify_offsets(&[
+            ("FN_TRANSFER_OFFSET", FN_TRANSFER_OFFSET),
+            ("FN_GENESIS_OFFSET", FN_GENESIS_OFFSET),
+            ("FN_SHARED_OFFSET", FN_SHARED_OFFSET),
+        ]);
+        UDA_LIB.verify("UDA_LIB", assembled.lib);
+    }
+}
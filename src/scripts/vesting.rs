@@ -0,0 +1,216 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the vesting asset schema.
+//!
+//! The genesis validation checks that the vesting cliff precedes the vesting end, that the locked
+//! allocation holds the vested supply and that the asset allocations hold the rest of the issued
+//! supply. The release validation checks that the asset allocations hold the released supply, the
+//! locked allocation the locked supply left, and that both add up to the spent locked allocation.
+//!
+//! A release also commits to the absolute lock time of its witness transaction, which must be a
+//! block height. The supply released up to the release, i.e. the vested supply minus the locked
+//! supply left, must not exceed the supply which the vesting schedule unlocks at the first height
+//! the witness can be mined at: none before the cliff, then linearly more up to the whole vested
+//! supply at the end. The lock time of the witness itself is not checked, see [`crate::height`].
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_VESTING_GENESIS_OFFSET: u16 = 0;
+pub(super) const FN_VESTING_RELEASE_OFFSET: u16 = 61;
+// Jump targets, only reached from within the library
+#[cfg(test)]
+const FN_VESTING_AFTER_CLIFF_OFFSET: u16 = 159;
+#[cfg(test)]
+const FN_VESTING_BEFORE_END_OFFSET: u16 = 168;
+
+pub(super) const VESTING_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xe6, 0x07,
+        0x01, 0x39, 0x20, 0x02, 0xc8, 0xe7, 0x07, 0x01, 0x39, 0x20, 0x03, 0x18, 0x11, 0x43, 0x01,
+        0x0b, 0x00, 0x04, 0x00, 0xc8, 0xe5, 0x07, 0x01, 0x39, 0x30, 0x00, 0xd1, 0xaf, 0x0f, 0x01,
+        0xc8, 0xda, 0x07, 0x01, 0x39, 0x30, 0x01, 0x21, 0x08, 0x60, 0x01, 0xd1, 0xa0, 0x0f, 0x01,
+        0x07, 0x0b, 0x00, 0x05, 0x00, 0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x00, 0x00, 0xc8, 0xe8,
+        0x07, 0x11, 0x39, 0x31, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0xc8, 0xe9, 0x07, 0x01, 0x39, 0x30,
+        0x00, 0xd1, 0xaf, 0x0f, 0x01, 0x39, 0x31, 0x01, 0x20, 0x08, 0x60, 0x01, 0xd2, 0xaf, 0x0f,
+        0x01, 0x0b, 0x00, 0x06, 0x00, 0xc8, 0x0a, 0x08, 0x21, 0x39, 0x22, 0x01, 0x0b, 0x1a, 0x07,
+        0x00, 0x18, 0x09, 0x43, 0x01, 0x0b, 0x1a, 0x0b, 0x00, 0x20, 0x08, 0x43, 0x0b, 0x02, 0x0f,
+        0x00, 0xc9, 0xe6, 0x07, 0x20, 0x39, 0x22, 0x04, 0xc9, 0xe7, 0x07, 0x20, 0x39, 0x22, 0x05,
+        0x18, 0x21, 0x43, 0x03, 0x9f, 0x00, 0x11, 0x22, 0x1a, 0x18, 0x19, 0x45, 0x03, 0xa8, 0x00,
+        0x11, 0x2a, 0x1a, 0x11, 0x22, 0x32, 0x21, 0x28, 0x46, 0x21, 0x18, 0x44, 0x12, 0x22, 0x04,
+        0xc9, 0xe5, 0x07, 0x20, 0x39, 0x32, 0x02, 0x12, 0x13, 0x0c, 0x22, 0x00, 0x81, 0xc8, 0xe9,
+        0x07, 0x21, 0x39, 0x32, 0x03, 0x21, 0x10, 0x63, 0x01, 0x12, 0x1b, 0x14, 0x12, 0x32, 0x1c,
+        0x22, 0x10, 0x83, 0x18, 0x09, 0x83, 0x1f, 0x01, 0x07,
+    ],
+    data: &[
+        0x00, 0x00, 0x00, 0x37, 0x01, 0x38, 0x4e, 0x00, 0x65, 0xcd, 0x1d, 0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ],
+    id: [
+        0xf5, 0x81, 0xa4, 0xe3, 0x83, 0xc9, 0xe0, 0xf4, 0xa7, 0x76, 0x81, 0x95, 0xba, 0x17, 0xe9,
+        0xbc, 0x28, 0xd4, 0x25, 0x58, 0x1e, 0x48, 0xd4, 0x76, 0x33, 0x58, 0x4d, 0xb2, 0x05, 0x1c,
+        0xce, 0x1c,
+    ],
+};
+
+#[cfg(test)]
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_vesting_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{
+        ERRNO_INVALID_SCHEDULE, ERRNO_ISSUED_MISMATCH, ERRNO_RELEASE_LOCKED, ERRNO_RELEASE_MISMATCH,
+    };
+    use crate::{
+        GS_ISSUED_SUPPLY, GS_LOCKED_SUPPLY, GS_LOCK_TIME, GS_RELEASED_SUPPLY, GS_VESTED_SUPPLY,
+        GS_VESTING_CLIFF, GS_VESTING_END, OS_ASSET, OS_LOCKED,
+    };
+
+    crate::asm::assemble("vesting asset", |labels| {
+        let after_cliff = labels.offset("FN_VESTING_AFTER_CLIFF_OFFSET");
+        let before_end = labels.offset("FN_VESTING_BEFORE_END_OFFSET");
+        vec![
+            ("FN_VESTING_GENESIS_OFFSET", rgbasm! {
+                // Set common offsets
+                put     a8[1],0;
+                put     a16[0],0;
+
+                // Check that the vesting cliff precedes the vesting end
+                put     a8[0],ERRNO_INVALID_SCHEDULE;  // set errno
+                ldg     GS_VESTING_CLIFF,a8[1],s16[0];  // read vesting cliff global state
+                extr    s16[0],a32[2],a16[0];  // and store it in a32[2]
+                ldg     GS_VESTING_END,a8[1],s16[0];  // read vesting end global state
+                extr    s16[0],a32[3],a16[0];  // and store it in a32[3]
+                lt.u    a32[2],a32[3];
+                test;
+
+                // Check that the locked allocation holds the vested supply
+                put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+                ldg     GS_VESTED_SUPPLY,a8[1],s16[0];  // read vested supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_LOCKED;  // check sum of locked allocations in output equals a64[0]
+                test;
+
+                // Check that the asset allocations hold the rest of the issued supply
+                ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+                extr    s16[0],a64[1],a16[0];  // and store it in a64[1]
+                sub.uc  a64[1],a64[0];  // vested supply is still in a64[0], result overwrites it
+                test;  // fails if result is <0
+                sas     OS_ASSET;  // check sum of asset allocations in output equals a64[0]
+                test;
+                ret;
+            }),
+            ("FN_VESTING_RELEASE_OFFSET", rgbasm! {
+                // Check that the asset allocations hold the released supply
+                put     a8[0],ERRNO_RELEASE_MISMATCH;  // set errno
+                put     a8[1],0;
+                put     a16[0],0;
+                ldg     GS_RELEASED_SUPPLY,a8[1],s16[1];  // read released supply global state
+                extr    s16[1],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_ASSET;  // check sum of asset allocations in output equals a64[0]
+                test;
+
+                // Check that the locked allocation, if any, holds the locked supply
+                ldg     GS_LOCKED_SUPPLY,a8[1],s16[0];  // read locked supply global state
+                extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+                sas     OS_LOCKED;  // check sum of locked allocations in output equals a64[0]
+                test;
+
+                // Check that the spent locked allocation holds both
+                extr    s16[1],a64[1],a16[0];  // store released supply in a64[1]
+                add.uc  a64[1],a64[0];  // result is stored in a64[0]
+                test;  // fails in case of an overflow
+                sps     OS_LOCKED;  // check sum of locked allocations in input equals a64[0]
+                test;
+
+                // Compute into a32[3] the first height the witness can be mined at
+                put     a8[0],ERRNO_RELEASE_LOCKED;  // set errno
+                ldg     GS_LOCK_TIME,a8[1],s16[2];  // read the lock time of the release
+                extr    s16[2],a32[1],a16[0];  // and store it in a32[1]
+                put     a32[3],500000000;  // lock times from it on are timestamps
+                lt.u    a32[1],a32[3];
+                test;
+                put     a32[3],1;
+                add.uc  a32[1],a32[3];  // can't overflow, the lock time is a height
+
+                // Bring the height within the vesting schedule
+                put     a32[0],0;  // depth of the contract global state entry to read
+                ldc     GS_VESTING_CLIFF,a32[0],s16[2];  // read the vesting cliff
+                extr    s16[2],a32[4],a16[0];  // and store it in a32[4]
+                ldc     GS_VESTING_END,a32[0],s16[2];  // read the vesting end
+                extr    s16[2],a32[5],a16[0];  // and store it in a32[5]
+                lt.u    a32[4],a32[3];  // cliff < height
+                jif     after_cliff;
+                cpy     a32[4],a32[3];  // nothing is unlocked before the cliff
+            }),
+            ("FN_VESTING_AFTER_CLIFF_OFFSET", rgbasm! {
+                lt.u    a32[3],a32[5];  // height < end
+                jif     before_end;
+                cpy     a32[5],a32[3];  // everything is unlocked from the end on
+            }),
+            ("FN_VESTING_BEFORE_END_OFFSET", rgbasm! {
+                // Compute into a128[1] the vested supply times the blocks elapsed since the cliff
+                cpy     a32[4],a32[6];  // store the cliff in a32[6]
+                sub.uc  a32[5],a32[6];  // blocks from the cliff to the end, in a32[6]
+                sub.uc  a32[3],a32[4];  // blocks elapsed since the cliff, in a32[4]
+                cnv     a32[4],a128[0];
+                ldc     GS_VESTED_SUPPLY,a32[0],s16[2];  // read the vested supply
+                extr    s16[2],a64[2],a16[0];  // and store it in a64[2]
+                cnv     a64[2],a128[1];
+                mul.uc  a128[0],a128[1];  // can't overflow, the factors have 64 and 32 bits
+
+                // Compute into a128[3] the released supply times the blocks of the schedule
+                ldg     GS_LOCKED_SUPPLY,a8[1],s16[2];  // read the locked supply left
+                extr    s16[2],a64[3],a16[0];  // and store it in a64[3]
+                sub.uc  a64[2],a64[3];  // supply released up to the release, in a64[3]
+                test;  // fails if the locked supply left exceeds the vested one
+                cnv     a64[3],a128[2];
+                cnv     a32[6],a128[3];
+                mul.uc  a128[2],a128[3];
+
+                // Check the released supply doesn't exceed the unlocked one, which is the vested
+                // supply times the blocks elapsed divided by the blocks of the schedule, rounded
+                // down
+                lt.u    a128[1],a128[3];  // vested * elapsed < released * blocks
+                inv     st0;  // vested * elapsed >= released * blocks
+                test;
+                ret;
+            }),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_vesting_lib();
+        assembled.verify_offsets(&[
+            ("FN_VESTING_GENESIS_OFFSET", FN_VESTING_GENESIS_OFFSET),
+            ("FN_VESTING_RELEASE_OFFSET", FN_VESTING_RELEASE_OFFSET),
+            ("FN_VESTING_AFTER_CLIFF_OFFSET", FN_VESTING_AFTER_CLIFF_OFFSET),
+            ("FN_VESTING_BEFORE_END_OFFSET", FN_VESTING_BEFORE_END_OFFSET),
+        ]);
+        VESTING_LIB.verify("VESTING_LIB", assembled.lib);
+    }
+}
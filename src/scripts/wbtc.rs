@@ -0,0 +1,87 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation library of the wrapped BTC asset schema.
+//!
+//! Metadata is not kept in the contract state, so the genesis and each mint record the reserve
+//! proof they carry in the global state as well: the issuance validation requires the recorded
+//! reserve attestation to be byte-for-byte equal to the proof.
+
+use crate::PrecompiledLib;
+
+pub(super) const FN_WBTC_ISSUANCE_OFFSET: u16 = 0;
+
+pub(super) const WBTC_LIB: PrecompiledLib = PrecompiledLib {
+    code: &[
+        0x0b, 0x08, 0x00, 0x00, 0x0b, 0x01, 0x01, 0x00, 0x0b, 0x00, 0x03, 0x00, 0xc8, 0xda, 0x07,
+        0x01, 0x39, 0x30, 0x00, 0xd1, 0xa0, 0x0f, 0x01, 0x0b, 0x00, 0x04, 0x00, 0xca, 0xea, 0x03,
+        0x00, 0xc8, 0xed, 0x07, 0x11, 0x36, 0x10, 0x01, 0x07,
+    ],
+    data: &[0x00, 0x00, 0x00, 0x01, 0x3c],
+    id: [
+        0xb8, 0xa1, 0xe2, 0xc6, 0xe0, 0x65, 0xde, 0xd1, 0x18, 0x6c, 0xca, 0x73, 0x9e, 0xcd, 0x71,
+        0xb2, 0x11, 0x2c, 0x6a, 0x09, 0xa2, 0xfb, 0x5a, 0x22, 0xb7, 0x4f, 0xac, 0x3d, 0x5a, 0x01,
+        0xb9, 0xae,
+    ],
+};
+
+#[cfg(test)]
+fn assemble_wbtc_lib() -> crate::asm::Assembled {
+    use rgbstd::rgbasm;
+
+    use crate::asm::{ERRNO_ISSUED_MISMATCH, ERRNO_RESERVE_MISMATCH};
+    use crate::{GS_ISSUED_SUPPLY, GS_RESERVES, MS_RESERVE_PROOF, OS_ASSET};
+
+    crate::asm::assemble("wrapped BTC asset", |_| {
+        vec![("FN_WBTC_ISSUANCE_OFFSET", rgbasm! {
+            // Set common offsets
+            put     a8[1],0;
+            put     a16[0],0;
+
+            // Check reported issued supply equals sum of asset allocations in output
+            put     a8[0],ERRNO_ISSUED_MISMATCH;  // set errno
+            ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];  // read issued supply global state
+            extr    s16[0],a64[0],a16[0];  // and store it in a64[0]
+            sas     OS_ASSET;  // check sum of asset allocations in output equals a64[0]
+            test;
+
+            // Check the recorded reserve attestation equals the reserve proof metadata
+            put     a8[0],ERRNO_RESERVE_MISMATCH;  // set errno
+            ldm     MS_RESERVE_PROOF,s16[0];  // read reserve proof metadata
+            ldg     GS_RESERVES,a8[1],s16[1];  // read reserve attestation global state
+            eq      s16[0],s16[1];
+            test;
+            ret;
+        })]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precompiled_lib() {
+        let assembled = assemble_wbtc_lib();
+        assembled.verify_offsets(&[("FN_WBTC_ISSUANCE_OFFSET", FN_WBTC_ISSUANCE_OFFSET)]);
+        WBTC_LIB.verify("WBTC_LIB", assembled.lib);
+    }
+}
@@ -0,0 +1,196 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Selection of the allocations a wallet spends to pay an amount of a fungible asset.
+//!
+//! The allocations of a wallet are those assigned to the outpoints it controls, e.g. the UTXOs
+//! found by a descriptor scan. Spending an outpoint spends all the allocations assigned to it, so
+//! selection works on outpoints rather than on single allocations.
+
+use std::collections::BTreeMap;
+
+use rgbstd::contract::FungibleAllocation;
+use rgbstd::{Amount, Outpoint};
+
+use crate::Error;
+
+/// Allocations of a fungible asset which a wallet can spend.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Spendable {
+    pub allocations: Vec<FungibleAllocation>,
+}
+
+impl Spendable {
+    pub fn new(allocations: impl IntoIterator<Item = FungibleAllocation>) -> Self {
+        Self {
+            allocations: allocations.into_iter().collect(),
+        }
+    }
+
+    /// Total amount of the spendable allocations.
+    pub fn balance(&self) -> Amount { self.allocations.iter().map(|a| a.state).sum() }
+
+    /// Amounts assigned to each of the spendable outpoints.
+    pub fn outpoints(&self) -> BTreeMap<Outpoint, Amount> {
+        let mut outpoints = BTreeMap::<_, Amount>::new();
+        for allocation in &self.allocations {
+            outpoints
+                .entry(allocation.seal.to_outpoint())
+                .or_insert(Amount::ZERO)
+                .saturating_add_assign(allocation.state);
+        }
+        outpoints
+    }
+
+    /// Selects the outpoints to spend for paying the target amount.
+    ///
+    /// An outpoint holding exactly the target amount is preferred, avoiding change; otherwise the
+    /// largest outpoints are selected first, minimizing the number of inputs.
+    pub fn select(&self, target: Amount) -> Result<Selection, Error> {
+        let outpoints = self.outpoints();
+        if let Some((outpoint, _)) = outpoints.iter().find(|(_, amount)| **amount == target) {
+            return Ok(self.selection(vec![*outpoint], target));
+        }
+
+        let mut candidates = outpoints.into_iter().collect::<Vec<_>>();
+        candidates.sort_by(|(o1, a1), (o2, a2)| a2.cmp(a1).then(o1.cmp(o2)));
+        let mut selected = vec![];
+        let mut total = Amount::ZERO;
+        for (outpoint, amount) in candidates {
+            if total >= target {
+                break;
+            }
+            selected.push(outpoint);
+            total.saturating_add_assign(amount);
+        }
+        if total < target {
+            return Err(Error::InsufficientBalance {
+                available: total,
+                required: target,
+            });
+        }
+        Ok(self.selection(selected, target))
+    }
+
+    fn selection(&self, outpoints: Vec<Outpoint>, target: Amount) -> Selection {
+        let allocations = self
+            .allocations
+            .iter()
+            .filter(|a| outpoints.contains(&a.seal.to_outpoint()))
+            .copied()
+            .collect::<Vec<_>>();
+        let total = allocations.iter().map(|a| a.state).sum::<Amount>();
+        Selection {
+            outpoints,
+            allocations,
+            total,
+            change: total.saturating_sub(target),
+        }
+    }
+}
+
+/// Outpoints selected by [`Spendable::select`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Selection {
+    pub outpoints: Vec<Outpoint>,
+    /// Allocations spent with the selected outpoints.
+    pub allocations: Vec<FungibleAllocation>,
+    /// Total amount of the spent allocations.
+    pub total: Amount,
+    /// Amount exceeding the target, which has to be assigned back to the wallet.
+    pub change: Amount,
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rgbstd::{OpId, Opout, OutputSeal, Txid};
+
+    use super::*;
+    use crate::OS_ASSET;
+
+    fn outpoint(vout: u32) -> Outpoint {
+        let txid =
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap();
+        Outpoint::new(txid, vout)
+    }
+
+    fn allocation(no: u16, vout: u32, amount: u64) -> FungibleAllocation {
+        FungibleAllocation {
+            opout: Opout::new(OpId::from([1u8; 32]), OS_ASSET, no),
+            seal: OutputSeal::new(outpoint(vout)),
+            state: Amount::from(amount),
+            witness: None,
+            bundle_id: None,
+        }
+    }
+
+    fn spendable() -> Spendable {
+        Spendable::new([
+            allocation(0, 1, 300),
+            allocation(1, 1, 200),
+            allocation(2, 2, 400),
+            allocation(3, 3, 100),
+        ])
+    }
+
+    #[test]
+    fn balance() {
+        let spendable = spendable();
+        assert_eq!(spendable.balance(), Amount::from(1000u64));
+        assert_eq!(spendable.outpoints(), bmap! {
+            outpoint(1) => Amount::from(500u64),
+            outpoint(2) => Amount::from(400u64),
+            outpoint(3) => Amount::from(100u64),
+        });
+        assert_eq!(Spendable::default().balance(), Amount::ZERO);
+    }
+
+    #[test]
+    fn select() {
+        let spendable = spendable();
+        let select = |target: u64| spendable.select(Amount::from(target));
+
+        let exact = select(400).unwrap();
+        assert_eq!(exact.outpoints, vec![outpoint(2)]);
+        assert_eq!((exact.total, exact.change), (Amount::from(400u64), Amount::ZERO));
+
+        let largest = select(450).unwrap();
+        assert_eq!(largest.outpoints, vec![outpoint(1)]);
+        assert_eq!(largest.allocations.len(), 2);
+        assert_eq!(largest.change, Amount::from(50u64));
+
+        let all = select(950).unwrap();
+        assert_eq!(all.outpoints, vec![outpoint(1), outpoint(2), outpoint(3)]);
+        assert_eq!(all.allocations.len(), 4);
+        assert_eq!((all.total, all.change), (Amount::from(1000u64), Amount::from(50u64)));
+
+        assert_eq!(
+            select(1001),
+            Err(Error::InsufficientBalance {
+                available: Amount::from(1000u64),
+                required: Amount::from(1001u64),
+            })
+        );
+    }
+}
@@ -0,0 +1,391 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Semi-fungible asset (SFA) schema.
+//!
+//! A single contract issues several fungible tokens, up to 255, in the style of ERC-1155: the
+//! genesis declares the tokens sorted by strictly increasing index, and each [`Allocation`] owns an
+//! amount of one of them. The genesis may allocate only the declared tokens, while transfers must
+//! keep the amount of each token: for every token index found among the inputs or the outputs,
+//! the outputs must allocate as much of it as the inputs.
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::{
+    Allocation, Amount, GlobalDetails, OwnedStateSchema, SchemaId, TokenIndex, TransitionDetails,
+};
+use strict_types::{StrictDeserialize, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, SFA_BALANCE, SFA_GENESIS, SFA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_NOMINAL, GS_TERMS, GS_TOKENS, OS_ASSET,
+    TS_TRANSFER,
+};
+
+pub const SFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x11, 0x8f, 0x8e, 0x70, 0xc4, 0xaa, 0x0a, 0x51, 0x71, 0x2a, 0xff, 0x95, 0xac, 0x61, 0x79, 0x63,
+    0xac, 0x96, 0xfe, 0x84, 0x39, 0x79, 0x93, 0x95, 0x90, 0x99, 0xe8, 0x22, 0x68, 0x2e, 0xe0, 0xc4,
+]);
+
+/// Maximum number of tokens issued by a contract, as the genesis script counts them in an 8-bit
+/// register.
+pub const SFA_MAX_TOKENS: u16 = 255;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn sfa_schema() -> Schema {
+    let types = standard_types();
+
+    SFA_GENESIS.verify(&[(0, INSTR_PUTA)]);
+    SFA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    SFA_BALANCE.verify(&[(0, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("SemiFungibleAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::OnceOrUpTo(SFA_MAX_TOKENS),
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(SFA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(SFA_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn sfa_scripts() -> Scripts { SharedLibs::get().scripts(&[SFA_GENESIS, SFA_TRANSFER]) }
+
+#[derive(Default)]
+pub struct SemiFungibleAsset;
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct SfaWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl IssuerWrapper for SemiFungibleAsset {
+    type Wrapper<S: ContractStateRead> = SfaWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(sfa_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(sfa_scripts).clone()
+    }
+}
+
+impl SemiFungibleAsset {
+    /// First revision of the SFA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "SemiFungibleAsset",
+        schema_id: SFA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Several fungible tokens issued at genesis, and a transfer transition \
+                        keeping the amount of each of them.",
+    };
+}
+
+impl IssuerInfo for SemiFungibleAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for SemiFungibleAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+impl<S: ContractStateRead> SchemaWrapper<S> for SfaWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for SfaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<SemiFungibleAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> SfaWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the SFA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<SemiFungibleAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the SFA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the tokens issued by the contract, sorted by their index.
+    pub fn tokens(&self) -> Vec<TokenData> { or_panic(self.try_tokens()) }
+
+    pub fn try_tokens(&self) -> Result<Vec<TokenData>, Error> {
+        let mut tokens =
+            globals::<_, TokenData>(&self.0, GS_TOKENS)?.collect::<Result<Vec<_>, _>>()?;
+        tokens.sort_by_key(|token| token.index);
+        Ok(tokens)
+    }
+
+    /// Returns the token with the given index, if the contract issues it.
+    pub fn token(&self, index: TokenIndex) -> Option<TokenData> { or_panic(self.try_token(index)) }
+
+    pub fn try_token(&self, index: TokenIndex) -> Result<Option<TokenData>, Error> {
+        Ok(self
+            .try_tokens()?
+            .into_iter()
+            .find(|token| token.index == index))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the amount of each token owned by the allocations passing the filter.
+    ///
+    /// Tokens whose allocations are all filtered out are missing from the map.
+    pub fn balances(&self, filter: impl AssignmentsFilter) -> BTreeMap<TokenIndex, Amount> {
+        or_panic(self.try_balances(filter))
+    }
+
+    pub fn try_balances(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> Result<BTreeMap<TokenIndex, Amount>, Error> {
+        let mut balances = BTreeMap::<_, Amount>::new();
+        for allocation in self.try_allocations(filter)? {
+            let allocation = Allocation::from_strict_serialized(allocation.state.into())
+                .map_err(|_| Error::InvalidOwned(OS_ASSET))?;
+            balances
+                .entry(allocation.token_index())
+                .or_insert(Amount::ZERO)
+                .saturating_add_assign(allocation.fraction().value());
+        }
+        Ok(balances)
+    }
+
+    /// Returns the amount of the token with the given index owned by the allocations passing the
+    /// filter.
+    pub fn balance_of(&self, index: TokenIndex, filter: impl AssignmentsFilter) -> Amount {
+        or_panic(self.try_balance_of(index, filter))
+    }
+
+    pub fn try_balance_of(
+        &self,
+        index: TokenIndex,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Amount, Error> {
+        Ok(self
+            .try_balances(filter)?
+            .remove(&index)
+            .unwrap_or(Amount::ZERO))
+    }
+
+    /// Builds an invoice for receiving an amount of the token with the given index, failing if
+    /// the contract doesn't issue it.
+    pub fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+        amount: u64,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary, index, amount))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+        amount: u64,
+    ) -> Result<RgbInvoice, Error> {
+        if self.try_token(index)?.is_none() {
+            return Err(Error::UnknownToken(index));
+        }
+        allocation_invoice(&self.0, beneficiary, Allocation::with(index, amount))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::Wrapper;
+    use rgbstd::contract::FilterIncludeAll;
+    use rgbstd::invoice::{InvoiceState, NonFungible};
+    use rgbstd::Outpoint;
+    use schemata_tools::testing;
+    use schemata_tools::testing::{seal, CHAIN_NET};
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = sfa_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(SFA_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<SemiFungibleAsset>(fixtures::contract_id("sfa"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+
+        let indexes = fixtures::COLLECTION.map(TokenIndex::from_inner);
+        assert_eq!(
+            wrapper
+                .tokens()
+                .into_iter()
+                .map(|token| token.index)
+                .collect::<Vec<_>>(),
+            indexes
+        );
+        assert_eq!(wrapper.token(TokenIndex::from_inner(3)), None);
+        assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 3);
+
+        assert_eq!(
+            wrapper.balances(&FilterIncludeAll),
+            BTreeMap::from([
+                (indexes[0], Amount::from(1000u64)),
+                (indexes[1], Amount::from(50u64))
+            ])
+        );
+        let outpoint = |vout| Outpoint::new(testing::txid(), vout);
+        assert_eq!(wrapper.balance_of(indexes[0], vec![outpoint(1)]), Amount::from(600u64));
+        assert_eq!(wrapper.balance_of(indexes[0], vec![outpoint(2)]), Amount::from(400u64));
+        assert_eq!(
+            wrapper.balance_of(indexes[1], vec![outpoint(1), outpoint(3)]),
+            Amount::from(50u64)
+        );
+        assert_eq!(wrapper.balance_of(indexes[1], vec![outpoint(2)]), Amount::ZERO);
+        assert_eq!(wrapper.balance_of(TokenIndex::from_inner(3), &FilterIncludeAll), Amount::ZERO);
+    }
+
+    #[test]
+    fn invoice() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<SemiFungibleAsset>(fixtures::contract_id("sfa"))
+            .unwrap();
+        let beneficiary = XChainNet::with(CHAIN_NET, Beneficiary::from(seal(7).to_secret_seal()));
+        let index = TokenIndex::from_inner(fixtures::COLLECTION[1]);
+        let invoice = wrapper.invoice(beneficiary, index, 20);
+        assert_eq!(invoice.assignment_name, Some(fname!("assetOwner")));
+        assert_eq!(
+            invoice.assignment_state,
+            Some(InvoiceState::Data(NonFungible::FractionedToken(Allocation::with(index, 20))))
+        );
+        let unknown = TokenIndex::from_inner(3);
+        assert_eq!(
+            wrapper.try_invoice(beneficiary, unknown, 20),
+            Err(Error::UnknownToken(unknown))
+        );
+    }
+}
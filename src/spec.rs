@@ -0,0 +1,200 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks of the asset specification typed by users.
+//!
+//! The ticker, name and details of an asset are restricted strings, and the issued supply of a
+//! fungible asset, counted in its smallest units, must fit 64 bits. The functions of this module
+//! check user input against these rules before it reaches the contract builder, explaining which
+//! rule is broken. NIA, PFA, IFA, UDA and FUA contracts commit to a whole [`AssetSpec`], checked
+//! with [`validate_spec`]; CFA contracts have a name, details and precision without ticker.
+
+use rgbstd::stl::{AssetSpec, Details, Name, Ticker};
+use rgbstd::{Amount, Precision};
+use strict_types::InvalidRString;
+
+/// Maximal number of characters of a ticker.
+pub const TICKER_MAX_LEN: usize = 8;
+/// Maximal number of characters of an asset name.
+pub const NAME_MAX_LEN: usize = 40;
+/// Maximal number of bytes of the asset details.
+pub const DETAILS_MAX_LEN: usize = u8::MAX as usize;
+
+/// Asset specification not fitting the rules of the schemata.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum SpecError {
+    /// ticker '{0}' must have from 1 to 8 characters.
+    TickerLength(String),
+
+    /// ticker '{0}' must start with a letter and contain only letters and digits: {1}
+    InvalidTicker(String, InvalidRString),
+
+    /// asset name '{0}' must have from 1 to 40 characters.
+    NameLength(String),
+
+    /// asset name '{0}' must contain only ASCII characters: {1}
+    InvalidName(String, InvalidRString),
+
+    /// asset details have {0} bytes, while they must have from 1 to 255.
+    DetailsLength(usize),
+
+    /// asset details are invalid: {0}
+    InvalidDetails(InvalidRString),
+
+    /// supply of {supply} whole units can't have {precision} decimal digits, since its smallest
+    /// units would exceed the maximal amount of an asset; at most {max} digits are possible.
+    PrecisionForSupply { supply: u64, precision: u8, max: u8 },
+}
+
+/// Checks and parses the ticker of an asset.
+pub fn validate_ticker(ticker: &str) -> Result<Ticker, SpecError> {
+    if !(1..=TICKER_MAX_LEN).contains(&ticker.chars().count()) {
+        return Err(SpecError::TickerLength(ticker.to_owned()));
+    }
+    ticker
+        .parse()
+        .map_err(|err| SpecError::InvalidTicker(ticker.to_owned(), err))
+}
+
+/// Checks and parses the name of an asset.
+pub fn validate_name(name: &str) -> Result<Name, SpecError> {
+    if !(1..=NAME_MAX_LEN).contains(&name.chars().count()) {
+        return Err(SpecError::NameLength(name.to_owned()));
+    }
+    name.parse()
+        .map_err(|err| SpecError::InvalidName(name.to_owned(), err))
+}
+
+/// Checks and parses the details of an asset.
+pub fn validate_details(details: &str) -> Result<Details, SpecError> {
+    if !(1..=DETAILS_MAX_LEN).contains(&details.len()) {
+        return Err(SpecError::DetailsLength(details.len()));
+    }
+    details.parse().map_err(SpecError::InvalidDetails)
+}
+
+/// Returns the highest precision which a supply given in whole units can have.
+pub fn max_precision_for_supply(supply: u64) -> Precision {
+    let mut max = Precision::Indivisible;
+    for decimals in 1..=Precision::Atto.decimals() {
+        let precision = Precision::try_from(decimals).expect("all precisions up to atto exist");
+        if supply.checked_mul(precision.multiplier()).is_none() {
+            break;
+        }
+        max = precision;
+    }
+    max
+}
+
+/// Checks that a supply given in whole units can have the precision, returning it in the smallest
+/// units of the asset.
+pub fn validate_precision_for_supply(
+    precision: Precision,
+    supply: u64,
+) -> Result<Amount, SpecError> {
+    supply
+        .checked_mul(precision.multiplier())
+        .map(Amount::from)
+        .ok_or_else(|| SpecError::PrecisionForSupply {
+            supply,
+            precision: precision.decimals(),
+            max: max_precision_for_supply(supply).decimals(),
+        })
+}
+
+/// Checks and builds the specification of an asset.
+pub fn validate_spec(
+    ticker: &str,
+    name: &str,
+    details: Option<&str>,
+    precision: Precision,
+) -> Result<AssetSpec, SpecError> {
+    Ok(AssetSpec {
+        ticker: validate_ticker(ticker)?,
+        name: validate_name(name)?,
+        details: details.map(validate_details).transpose()?,
+        precision,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ticker() {
+        assert_eq!(validate_ticker("TEST1").unwrap(), Ticker::from("TEST1"));
+        assert_eq!(validate_ticker(""), Err(SpecError::TickerLength(s!(""))));
+        assert_eq!(validate_ticker("TOOLONGTK"), Err(SpecError::TickerLength(s!("TOOLONGTK"))));
+        assert!(matches!(
+            validate_ticker("1TEST"),
+            Err(SpecError::InvalidTicker(_, InvalidRString::DisallowedFirst(_, '1')))
+        ));
+        assert!(matches!(
+            validate_ticker("TE-ST"),
+            Err(SpecError::InvalidTicker(_, InvalidRString::InvalidChar(_, '-', _)))
+        ));
+        assert!(matches!(validate_ticker("TÉST"), Err(SpecError::InvalidTicker(..))));
+    }
+
+    #[test]
+    fn name_and_details() {
+        assert_eq!(validate_name("Test asset").unwrap(), Name::from("Test asset"));
+        assert_eq!(validate_name(&"x".repeat(41)), Err(SpecError::NameLength("x".repeat(41))));
+        assert!(matches!(validate_name("Tést asset"), Err(SpecError::InvalidName(..))));
+        assert!(validate_details("Asset details").is_ok());
+        assert_eq!(validate_details(""), Err(SpecError::DetailsLength(0)));
+        assert_eq!(validate_details(&"x".repeat(256)), Err(SpecError::DetailsLength(256)));
+    }
+
+    #[test]
+    fn precision_for_supply() {
+        assert_eq!(max_precision_for_supply(0), Precision::Atto);
+        assert_eq!(max_precision_for_supply(18), Precision::Atto);
+        assert_eq!(max_precision_for_supply(19), Precision::CentiFemto);
+        assert_eq!(max_precision_for_supply(21_000_000), Precision::CentiNano);
+        assert_eq!(max_precision_for_supply(u64::MAX), Precision::Indivisible);
+
+        assert_eq!(
+            validate_precision_for_supply(Precision::CentiMicro, 21_000_000).unwrap(),
+            Amount::from(2_100_000_000_000_000u64)
+        );
+        assert_eq!(
+            validate_precision_for_supply(Precision::Femto, 21_000_000),
+            Err(SpecError::PrecisionForSupply {
+                supply: 21_000_000,
+                precision: 15,
+                max: 11
+            })
+        );
+    }
+
+    #[test]
+    fn spec() {
+        let spec = validate_spec("TEST", "Test asset", None, Precision::CentiMicro).unwrap();
+        assert_eq!(spec, AssetSpec::new("TEST", "Test asset", Precision::CentiMicro));
+        assert!(matches!(
+            validate_spec("TEST", "", Some("details"), Precision::Indivisible),
+            Err(SpecError::NameLength(_))
+        ));
+    }
+}
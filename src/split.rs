@@ -0,0 +1,188 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Splitting of fungible allocations into fixed denominations.
+//!
+//! Payment channels are funded with allocations of given sizes. A [`SplitPlan`] describes a
+//! transfer of some allocations of a wallet back to itself which splits their amount into
+//! allocations of the requested denominations, assigning the rest as change. The plan keeps the
+//! sum of inputs and outputs equal and never assigns a zero amount, as required by the transfer
+//! validators of all the fungible schemata; PFA transitions still have to be signed by the issuer
+//! once completed.
+
+use rgbstd::containers::BuilderSeal;
+use rgbstd::contract::{AllocatedState, BuilderError, FungibleAllocation, TransitionBuilder};
+use rgbstd::{Amount, GraphSeal, RevealedValue};
+
+use crate::{Error, OS_ASSET};
+
+/// Maximal number of assignments of a type in a single transition.
+pub const MAX_SPLIT_OUTPUTS: usize = u16::MAX as usize;
+
+/// Transfer splitting fungible allocations into allocations of fixed denominations.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SplitPlan {
+    pub inputs: Vec<FungibleAllocation>,
+    pub denominations: Vec<Amount>,
+    /// Amount exceeding the sum of the denominations; zero if there is no change.
+    pub change: Amount,
+}
+
+impl SplitPlan {
+    /// Plans splitting the inputs into allocations of the given denominations.
+    pub fn new(
+        inputs: impl IntoIterator<Item = FungibleAllocation>,
+        denominations: impl IntoIterator<Item = Amount>,
+    ) -> Result<Self, Error> {
+        let inputs = inputs.into_iter().collect::<Vec<_>>();
+        let denominations = denominations.into_iter().collect::<Vec<_>>();
+        if denominations.contains(&Amount::ZERO) {
+            return Err(Error::ZeroAmount);
+        }
+        let available = inputs.iter().map(|a| a.state).sum::<Amount>();
+        let required = denominations
+            .iter()
+            .fold(Amount::ZERO, |sum, amount| sum.saturating_add(*amount));
+        let change = available
+            .checked_sub(required)
+            .ok_or(Error::InsufficientBalance {
+                available,
+                required,
+            })?;
+        let plan = SplitPlan {
+            inputs,
+            denominations,
+            change,
+        };
+        if plan.outputs().count() > MAX_SPLIT_OUTPUTS {
+            return Err(Error::TooManyOutputs(plan.outputs().count()));
+        }
+        Ok(plan)
+    }
+
+    /// Plans splitting the inputs into `count` allocations of the same denomination.
+    pub fn uniform(
+        inputs: impl IntoIterator<Item = FungibleAllocation>,
+        denomination: Amount,
+        count: usize,
+    ) -> Result<Self, Error> {
+        Self::new(inputs, std::iter::repeat_n(denomination, count))
+    }
+
+    /// Amounts of the planned outputs: the denominations followed by the change, if any.
+    pub fn outputs(&self) -> impl Iterator<Item = Amount> + '_ {
+        self.denominations
+            .iter()
+            .copied()
+            .chain(Some(self.change).filter(|change| *change != Amount::ZERO))
+    }
+
+    /// Adds the inputs of the plan to a transition.
+    pub fn add_inputs(
+        &self,
+        mut builder: TransitionBuilder,
+    ) -> Result<TransitionBuilder, BuilderError> {
+        for input in &self.inputs {
+            let state = AllocatedState::Amount(RevealedValue::from(input.state));
+            builder = builder.add_input(input.opout, state)?;
+        }
+        Ok(builder)
+    }
+
+    /// Adds the outputs of the plan to a transition, assigning the output with index `no` of
+    /// [`SplitPlan::outputs`] to `seal(no)`.
+    pub fn add_outputs(
+        &self,
+        mut builder: TransitionBuilder,
+        mut seal: impl FnMut(usize) -> BuilderSeal<GraphSeal>,
+    ) -> Result<TransitionBuilder, BuilderError> {
+        for (no, amount) in self.outputs().enumerate() {
+            builder = builder.add_fungible_state_raw(OS_ASSET, seal(no), amount)?;
+        }
+        Ok(builder)
+    }
+
+    /// Adds both the inputs and the outputs of the plan to a transfer transition.
+    pub fn build(
+        &self,
+        builder: TransitionBuilder,
+        seal: impl FnMut(usize) -> BuilderSeal<GraphSeal>,
+    ) -> Result<TransitionBuilder, BuilderError> {
+        self.add_outputs(self.add_inputs(builder)?, seal)
+    }
+}
+
+#[cfg(all(test, feature = "nia"))]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+    use schemata_tools::testing::graph_seal;
+
+    use super::*;
+    use crate::{fixtures, NiaWrapper};
+
+    fn amounts(values: &[u64]) -> Vec<Amount> { values.iter().copied().map(Amount::from).collect() }
+
+    #[test]
+    fn plan() {
+        let stock = fixtures::stock();
+        let contract_id = fixtures::contract_id("nia");
+        let nia = NiaWrapper::try_with(stock.contract_data(contract_id).unwrap()).unwrap();
+        let inputs = nia.allocations(FilterIncludeAll).collect::<Vec<_>>();
+
+        let plan = SplitPlan::uniform(inputs.clone(), Amount::from(300u64), 3).unwrap();
+        assert_eq!(plan.outputs().collect::<Vec<_>>(), amounts(&[300, 300, 300, 100]));
+        let exact = SplitPlan::new(inputs.clone(), amounts(&[600, 400])).unwrap();
+        assert_eq!(exact.change, Amount::ZERO);
+        assert_eq!(exact.outputs().collect::<Vec<_>>(), amounts(&[600, 400]));
+
+        assert_eq!(
+            SplitPlan::uniform(inputs.clone(), Amount::from(300u64), 4),
+            Err(Error::InsufficientBalance {
+                available: Amount::from(1000u64),
+                required: Amount::from(1200u64)
+            })
+        );
+        assert_eq!(SplitPlan::new(inputs.clone(), amounts(&[600, 0])), Err(Error::ZeroAmount));
+        assert!(matches!(
+            SplitPlan::new(inputs.clone(), amounts(&[u64::MAX, 1])),
+            Err(Error::InsufficientBalance { .. })
+        ));
+        assert_eq!(
+            SplitPlan::uniform(inputs, Amount::ZERO, 0).map(|plan| plan.outputs().count()),
+            Ok(1)
+        );
+
+        let builder = stock.transition_builder(contract_id, "transfer").unwrap();
+        let transition = plan
+            .build(builder, |no| BuilderSeal::from(graph_seal(no as u32)))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let input_opouts = transition.inputs.iter().copied().collect::<Vec<_>>();
+        assert_eq!(input_opouts, vec![plan.inputs[0].opout]);
+        let outputs = transition.assignments[&OS_ASSET]
+            .as_fungible()
+            .iter()
+            .map(|assign| Amount::from(*assign.as_revealed_state()))
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, plan.outputs().collect::<Vec<_>>());
+    }
+}
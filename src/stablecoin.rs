@@ -0,0 +1,421 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stablecoin schema.
+//!
+//! Fungible asset inflatable up to a maximum supply like IFA, whose supply changes are gated by an
+//! oracle key committed in the genesis like the PFA issuer key: every inflation and every redeem
+//! must be signed with it. This lets a fiat-backed issuer mint new units only against an
+//! off-chain attestation of the reserves, and retire the units redeemed for fiat.
+//!
+//! A redeem spends asset allocations without assigning any, recording the redeemed amount in the
+//! global state; holders redeeming part of an allocation transfer the rest to themselves first.
+//! Transfers need no signature.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, MetaDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{
+    SharedLibs, IFA_GENESIS, IFA_TRANSFER, STABLECOIN_INFLATION, STABLECOIN_REDEEM,
+};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_MAX_SUPPLY, GS_NOMINAL, GS_PUBKEY, GS_REDEEMED_SUPPLY, GS_TERMS, MS_ALLOWED_INFLATION,
+    OS_ASSET, OS_INFLATION, TS_INFLATION, TS_REDEEM, TS_TRANSFER,
+};
+
+pub const STABLECOIN_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x1b, 0x24, 0xc7, 0xbc, 0xf0, 0x55, 0x23, 0x6f, 0x27, 0x4f, 0xe4, 0xb1, 0x2b, 0x53, 0xf0, 0xf0,
+    0x4b, 0xe4, 0x35, 0x88, 0xa6, 0x2a, 0x5d, 0x08, 0x5e, 0x5f, 0x4c, 0x2a, 0x51, 0x12, 0xa3, 0xd8,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn stablecoin_schema() -> Schema {
+    let types = standard_types();
+
+    IFA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    IFA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS), (8, INSTR_SVS)]);
+    STABLECOIN_INFLATION.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA)]);
+    STABLECOIN_REDEEM.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("StablecoinAsset"),
+        meta_types: tiny_bmap! {
+            MS_ALLOWED_INFLATION => MetaDetails {
+                sem_id: types.get("RGBContract.Amount"),
+                name: fname!("allowedInflation"),
+            }
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_MAX_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("maxSupply"),
+            },
+            GS_REDEEMED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("redeemedSupply"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_INFLATION => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("inflationAllowance"),
+                default_transition: TS_TRANSFER
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_MAX_SUPPLY => Occurrences::Once,
+                GS_PUBKEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_INFLATION => Occurrences::NoneOrMore,
+            },
+            validator: Some(IFA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    validator: Some(IFA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_INFLATION => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: tiny_bset![MS_ALLOWED_INFLATION],
+                    globals: tiny_bmap! {
+                        GS_ISSUED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_INFLATION => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    validator: Some(STABLECOIN_INFLATION.lib_site())
+                },
+                name: fname!("inflate"),
+            },
+            TS_REDEEM => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_REDEEMED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                    },
+                    assignments: none!(),
+                    validator: Some(STABLECOIN_REDEEM.lib_site())
+                },
+                name: fname!("redeem"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn stablecoin_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[IFA_GENESIS, IFA_TRANSFER, STABLECOIN_INFLATION, STABLECOIN_REDEEM])
+}
+
+#[derive(Default)]
+pub struct StablecoinAsset;
+
+impl IssuerWrapper for StablecoinAsset {
+    type Wrapper<S: ContractStateRead> = StablecoinWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(stablecoin_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(stablecoin_scripts).clone()
+    }
+}
+
+impl StablecoinAsset {
+    /// First revision of the stablecoin schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "StablecoinAsset",
+        schema_id: STABLECOIN_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fungible asset inflatable up to a maximum supply, whose inflations and \
+                        redeems must be signed by the oracle key declared in the genesis.",
+    };
+}
+
+impl IssuerInfo for StablecoinAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for StablecoinAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct StablecoinWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for StablecoinWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for StablecoinWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<StablecoinAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> StablecoinWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the stablecoin schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<StablecoinAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the stablecoin schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the oracle public key which must sign every inflation and redeem.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    /// Returns the supply issued by the genesis and all inflations.
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts issued by each inflation, from the latest one, followed by the amount
+    /// issued by the genesis.
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    pub fn max_supply(&self) -> Amount { or_panic(self.try_max_supply()) }
+
+    pub fn try_max_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_MAX_SUPPLY)?.sum()
+    }
+
+    /// Returns the supply redeemed over the contract history.
+    pub fn redeemed_supply(&self) -> Amount { or_panic(self.try_redeemed_supply()) }
+
+    pub fn try_redeemed_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_REDEEMED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts retired by each redeem, from the latest one.
+    pub fn redeem_amounts(&self) -> Vec<Amount> { or_panic(self.try_redeem_amounts()) }
+
+    pub fn try_redeem_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_REDEEMED_SUPPLY)?.collect()
+    }
+
+    /// Returns the supply issued and not redeemed yet.
+    pub fn circulating_supply(&self) -> Amount { or_panic(self.try_circulating_supply()) }
+
+    pub fn try_circulating_supply(&self) -> Result<Amount, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        let redeemed = self.try_redeemed_supply()?.value();
+        Ok(Amount::from(issued.saturating_sub(redeemed)))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_inflation_allocations(filter))
+    }
+
+    pub fn try_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_INFLATION, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for StablecoinWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        StablecoinWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        StablecoinWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        StablecoinWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        StablecoinWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::contract::*;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = stablecoin_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(STABLECOIN_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<StablecoinAsset>(fixtures::contract_id("stablecoin"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.pubkey(), fixtures::pubkey());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.issuance_amounts(), vec![Amount::from(fixtures::ISSUED_SUPPLY)]);
+        assert_eq!(wrapper.max_supply(), Amount::from(fixtures::MAX_SUPPLY));
+        assert_eq!(wrapper.redeemed_supply(), Amount::ZERO);
+        assert_eq!(wrapper.redeem_amounts(), vec![]);
+        assert_eq!(wrapper.circulating_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let inflation = wrapper
+            .inflation_allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(inflation, vec![(
+            fixture_seal(2),
+            fixtures::MAX_SUPPLY - fixtures::ISSUED_SUPPLY
+        )]);
+    }
+}
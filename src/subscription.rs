@@ -0,0 +1,461 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subscription token schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the UDA schema whose token is a subscription valid up to an expiry block height.
+//! The genesis commits to the issuer public key and to the first expiry, which the subscriber can
+//! extend with a `renew` transition signed by the issuer, spending the token and assigning it back.
+//! The validation scripts require each renewal to set an expiry later than the current one, so
+//! that the latest expiry recorded in the global state is the one in force. The expiry is a block
+//! height, see [`crate::height`], which the scripts only compare with the previous expiries: a
+//! token used or renewed after it expires is valid, and it is up to the verifier to check the
+//! height with [`SubscriptionWrapper::is_active`].
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_LDG;
+use rgbstd::{Allocation, GlobalDetails, OwnedStateSchema, SchemaId, TransitionDetails};
+use strict_types::encoding::bitcoin_types::LockTime;
+use strict_types::TypeSystem;
+
+use crate::height::height;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, SUBSCRIPTION_RENEW, UDA_GENESIS, UDA_SHARED, UDA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_EXPIRY, GS_NOMINAL, GS_PUBKEY,
+    GS_TERMS, GS_TOKENS, OS_ASSET, TS_RENEW, TS_TRANSFER,
+};
+
+pub const SUBSCRIPTION_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x6f, 0x55, 0x4c, 0x72, 0x04, 0x29, 0x5b, 0xdc, 0xfd, 0xdf, 0xc6, 0x38, 0x83, 0x90, 0x4c, 0x87,
+    0xdd, 0xff, 0xdf, 0xc9, 0x54, 0xf8, 0x37, 0x0c, 0x59, 0xc9, 0x8b, 0x30, 0xb5, 0xe6, 0xed, 0xbc,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn subscription_schema() -> Schema {
+    let types = standard_types();
+
+    UDA_GENESIS.verify(&[(0, INSTR_PUTA), (8, INSTR_LDG)]);
+    UDA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDA_SHARED.verify(&[(0, INSTR_PUTA), (4, INSTR_EXTR)]);
+    SUBSCRIPTION_RENEW.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("SubscriptionAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+            GS_EXPIRY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.LockTime")),
+                name: fname!("expiry"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::Once,
+                GS_PUBKEY => Occurrences::Once,
+                GS_EXPIRY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::Once,
+            },
+            validator: Some(UDA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    validator: Some(UDA_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            },
+            TS_RENEW => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_EXPIRY => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    validator: Some(SUBSCRIPTION_RENEW.lib_site()),
+                },
+                name: fname!("renew"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn subscription_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[UDA_GENESIS, UDA_TRANSFER, SUBSCRIPTION_RENEW])
+}
+
+#[derive(Default)]
+pub struct SubscriptionAsset;
+
+impl IssuerWrapper for SubscriptionAsset {
+    type Wrapper<S: ContractStateRead> = SubscriptionWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(subscription_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(subscription_scripts).clone()
+    }
+}
+
+impl SubscriptionAsset {
+    /// First revision of the subscription schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "SubscriptionAsset",
+        schema_id: SUBSCRIPTION_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "UDA variant for subscriptions valid up to an expiry height, which the \
+                        issuer extends by signing renew transitions.",
+    };
+}
+
+impl IssuerInfo for SubscriptionAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for SubscriptionAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct SubscriptionWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for SubscriptionWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for SubscriptionWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<SubscriptionAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> SubscriptionWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the subscription schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<SubscriptionAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the subscription schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn token_data(&self) -> TokenData { or_panic(self.try_token_data()) }
+
+    pub fn try_token_data(&self) -> Result<TokenData, Error> { global(&self.0, GS_TOKENS) }
+
+    /// Returns the public key of the issuer, which signs the renewals.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    /// Returns the height of the last block in which the subscription is active, as set by the
+    /// latest renewal or by the genesis if it was never renewed.
+    pub fn valid_until(&self) -> u32 { or_panic(self.try_valid_until()) }
+
+    pub fn try_valid_until(&self) -> Result<u32, Error> {
+        // Renewals only extend the expiry, so the latest entry is the one in force
+        height(&self.0, GS_EXPIRY)
+    }
+
+    /// Checks whether the subscription is active in a block at the given height.
+    pub fn is_active(&self, height: u32) -> bool { or_panic(self.try_is_active(height)) }
+
+    pub fn try_is_active(&self, height: u32) -> Result<bool, Error> {
+        Ok(height <= self.try_valid_until()?)
+    }
+
+    /// Returns the number of renewals of the subscription.
+    pub fn renewals(&self) -> usize { or_panic(self.try_renewals()) }
+
+    pub fn try_renewals(&self) -> Result<usize, Error> {
+        // The genesis records the first expiry
+        Ok(globals::<_, LockTime>(&self.0, GS_EXPIRY)?
+            .count()
+            .saturating_sub(1))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Builds an invoice for receiving the token of the contract.
+    pub fn invoice(&self, beneficiary: impl Into<XChainNet<Beneficiary>>) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+    ) -> Result<RgbInvoice, Error> {
+        let allocation = Allocation::with(self.try_token_data()?.index, 1);
+        allocation_invoice(&self.0, beneficiary, allocation)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallBlob;
+    use amplify::Wrapper;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::*;
+    use rgbstd::*;
+    use schemata_tools::testing;
+    use strict_types::StrictSerialize;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+    use crate::height::block_height;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = subscription_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(SUBSCRIPTION_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<SubscriptionAsset>(fixtures::contract_id("subscription"))
+            .unwrap();
+        assert_eq!(wrapper.version(), SubscriptionAsset::V1);
+        assert_eq!(
+            wrapper.spec(),
+            AssetSpec::new("TEST", "Test subscription", Precision::Indivisible)
+        );
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.token_data(), fixtures::token_data(false));
+        assert_eq!(wrapper.pubkey(), fixtures::pubkey());
+        assert_eq!(wrapper.valid_until(), fixtures::EXPIRY);
+        assert!(wrapper.is_active(fixtures::EXPIRY));
+        assert!(!wrapper.is_active(fixtures::EXPIRY + 1));
+        assert_eq!(wrapper.renewals(), 0);
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state))
+            .collect::<Vec<_>>();
+        let allocation = Allocation::with(TokenIndex::from_inner(fixtures::TOKEN_INDEX), 1)
+            .to_strict_serialized::<{ u16::MAX as usize }>()
+            .unwrap();
+        let state = RevealedData::new(SmallBlob::from_checked(allocation.release()));
+        assert_eq!(allocations, vec![(fixture_seal(1), state)]);
+    }
+
+    // Renewals of a subscription token.
+    //
+    // The subscriber renews the subscription twice, each time with a renewal signed by the issuer
+    // in its own mined witness TX; the wrapper must report the expiry of the latest renewal.
+
+    /// Subscription token of index `token_index` issued by `pubkey` and valid until the `expiry`
+    /// height, with `owned_index` allocated to `seal(1)`.
+    fn subscription(
+        token_index: u32,
+        owned_index: u32,
+        pubkey: CompressedPublicKey,
+        expiry: u32,
+    ) -> ContractBuilder {
+        testing::builder::<SubscriptionAsset>()
+            .add_global_state(
+                "spec",
+                AssetSpec::new("TEST", "Test subscription", Precision::Indivisible),
+            )
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(token_index),
+                ..Default::default()
+            })
+            .unwrap()
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+            .add_global_state("expiry", block_height(expiry))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::seal(1),
+                Allocation::with(TokenIndex::from_inner(owned_index), 1),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn renewals() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(subscription(2, 2, testing::issuer_pubkey(), 900_000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<SubscriptionAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.pubkey(), testing::issuer_pubkey());
+        assert_eq!(wrapper.valid_until(), 900_000);
+        assert!(wrapper.is_active(900_000));
+        assert!(!wrapper.is_active(900_001));
+        assert_eq!(wrapper.renewals(), 0);
+
+        let RevealedState::Structured(data) = testing::allocation(2, 1) else {
+            unreachable!()
+        };
+        let mut owner = (Opout::new(genesis_id, OS_ASSET, 0), Outpoint::new(testing::txid(), 1));
+        for (height, expiry) in [(101, 950_000), (102, 1_000_000)] {
+            let mut transition = stock
+                .transition_builder(contract_id, "renew")
+                .unwrap()
+                .add_input(owner.0, AllocatedState::Data(data.clone()))
+                .unwrap()
+                .add_global_state("expiry", block_height(expiry))
+                .unwrap()
+                .add_data(
+                    "assetOwner",
+                    testing::graph_seal(1),
+                    Allocation::with(TokenIndex::from_inner(2), 1),
+                )
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            transition.signature = Some(testing::sign(&transition));
+            let opid = transition.id();
+
+            let fascia = testing::anchor(contract_id, transition, &[owner.1], 1);
+            let witness = fascia.seal_witness.public.tx().unwrap().clone();
+            let witness_id = fascia.witness_id();
+            resolver.add_witness(witness, testing::mined_at(height));
+            stock.consume_fascia(fascia, resolver.clone()).unwrap();
+            owner = (Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness_id, 1));
+        }
+
+        let wrapper = stock
+            .contract_wrapper::<SubscriptionAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.valid_until(), 1_000_000);
+        assert!(wrapper.is_active(950_001));
+        assert!(!wrapper.is_active(1_000_001));
+        assert_eq!(wrapper.renewals(), 2);
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(owner.1)), "{seals:?}");
+    }
+}
@@ -0,0 +1,290 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serializable summaries of the state of contracts.
+//!
+//! A [`ContractSummary`] collects the identity of the asset, its supplies and the allocations
+//! passing an assignment filter into plain values, which wallets and explorers can display or,
+//! with the `serde` feature, dump to JSON without walking the iterators of the wrappers. Every
+//! wrapper emits one with its `summary` method.
+//!
+//! Identifiers, seals and data are serialized with their display form, like `rgb:` contract ids
+//! and `txid:vout` seals, so the output doesn't depend on the serialization of `rgbstd` types.
+
+use std::collections::BTreeMap;
+#[cfg(feature = "serde")]
+use std::fmt::Display;
+
+use rgbstd::contract::{AssignmentsFilter, ContractData};
+use rgbstd::persistence::{
+    ContractStateRead, IndexProvider, StashProvider, StateProvider, Stock, StockError,
+};
+use rgbstd::stl::AssetSpec;
+#[cfg(any(feature = "cfa", feature = "carbon"))]
+use rgbstd::stl::Name;
+#[cfg(any(feature = "cfa", feature = "carbon"))]
+use rgbstd::Precision;
+use rgbstd::{
+    Amount, ContractId, GlobalStateType, Opout, OutputSeal, OwnedStateSchema, RevealedData,
+    SchemaId,
+};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+use strict_types::StrictDecode;
+
+use crate::info::schema_info;
+use crate::wrapper::globals;
+use crate::GS_NOMINAL;
+
+/// Serializes a value with its display form.
+#[cfg(feature = "serde")]
+fn display<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// State of an allocation listed by a [`ContractSummary`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+pub enum SummaryState {
+    Amount(u64),
+    Data(#[cfg_attr(feature = "serde", serde(serialize_with = "display"))] RevealedData),
+    Right,
+}
+
+/// Allocation listed by a [`ContractSummary`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct SummaryAllocation {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "display"))]
+    pub opout: Opout,
+    /// Name of the assignment type, like `assetOwner`.
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "display"))]
+    pub seal: OutputSeal,
+    pub state: SummaryState,
+}
+
+/// Identity, supplies and allocations of a contract, see the [module documentation](self).
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct ContractSummary {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "display"))]
+    pub contract_id: ContractId,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "display"))]
+    pub schema_id: SchemaId,
+    /// Name of the schema family, if it is one of the schemata of this crate.
+    pub schema: Option<&'static str>,
+    pub ticker: Option<String>,
+    pub name: Option<String>,
+    /// Number of decimal digits of the amounts, if the asset declares a precision.
+    pub precision: Option<u8>,
+    /// Sum of each global state type whose name ends with `Supply`, like `issuedSupply` or
+    /// `burnedSupply`, by its name.
+    pub supplies: BTreeMap<String, u128>,
+    pub allocations: Vec<SummaryAllocation>,
+}
+
+impl ContractSummary {
+    /// Summarizes the contract, listing its allocations passing the filter.
+    pub fn capture<S: ContractStateRead>(
+        data: &ContractData<S>,
+        filter: impl AssignmentsFilter,
+    ) -> Self {
+        let schema = &data.schema;
+        let schema_id = schema.schema_id();
+        let mut summary = ContractSummary {
+            contract_id: data.contract_id(),
+            schema_id,
+            schema: schema_info(schema_id).map(|info| info.name),
+            ticker: None,
+            name: None,
+            precision: None,
+            supplies: bmap! {},
+            allocations: vec![],
+        };
+
+        if let Some(spec) = first::<_, AssetSpec>(data, GS_NOMINAL) {
+            summary.ticker = Some(spec.ticker.to_string());
+            summary.name = Some(spec.name.to_string());
+            summary.precision = Some(spec.precision.decimals());
+        }
+        #[cfg(any(feature = "cfa", feature = "carbon"))]
+        if let Some(name) = first::<_, Name>(data, crate::GS_NAME) {
+            summary.name = Some(name.to_string());
+            summary.precision =
+                first::<_, Precision>(data, crate::GS_PRECISION).map(Precision::decimals);
+        }
+
+        for (ty, details) in &schema.global_types {
+            if !details.name.as_str().ends_with("Supply") {
+                continue;
+            }
+            let supply = globals::<_, Amount>(data, *ty)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|amount| amount.value() as u128)
+                .sum();
+            summary.supplies.insert(details.name.to_string(), supply);
+        }
+
+        // State types are taken from the schema, so the accessors below never fail
+        for (ty, details) in &schema.owned_types {
+            let name = details.name.to_string();
+            let allocation = |opout, seal, state| SummaryAllocation {
+                opout,
+                name: name.clone(),
+                seal,
+                state,
+            };
+            match details.owned_state_schema {
+                OwnedStateSchema::Fungible(_) => summary.allocations.extend(
+                    data.fungible_raw(*ty, &filter)
+                        .into_iter()
+                        .flatten()
+                        .map(|a| {
+                            allocation(a.opout, a.seal, SummaryState::Amount(a.state.value()))
+                        }),
+                ),
+                OwnedStateSchema::Structured(_) => summary.allocations.extend(
+                    data.data_raw(*ty, &filter)
+                        .into_iter()
+                        .flatten()
+                        .map(|a| allocation(a.opout, a.seal, SummaryState::Data(a.state))),
+                ),
+                OwnedStateSchema::Declarative => summary.allocations.extend(
+                    data.rights_raw(*ty, &filter)
+                        .into_iter()
+                        .flatten()
+                        .map(|a| allocation(a.opout, a.seal, SummaryState::Right)),
+                ),
+            }
+        }
+        summary
+    }
+
+    /// Summarizes the contract with the given id from a stock, see [`ContractSummary::capture`].
+    pub fn from_stock<S: StashProvider, H: StateProvider, P: IndexProvider>(
+        stock: &Stock<S, H, P>,
+        contract_id: ContractId,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Self, StockError<S, H, P>> {
+        Ok(Self::capture(&stock.contract_data(contract_id)?, filter))
+    }
+
+    /// Sum of the fungible allocations listed by the summary.
+    pub fn allocated(&self) -> u128 {
+        self.allocations
+            .iter()
+            .filter_map(|a| match a.state {
+                SummaryState::Amount(amount) => Some(amount as u128),
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+/// Decodes the first entry of a global state type, if the schema has it and it is valid.
+fn first<S: ContractStateRead, T: StrictDecode>(
+    data: &ContractData<S>,
+    ty: GlobalStateType,
+) -> Option<T> {
+    globals(data, ty).ok()?.next()?.ok()
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+
+    use super::*;
+    use crate::fixtures;
+
+    fn summary(name: &str) -> ContractSummary {
+        let stock = fixtures::stock();
+        ContractSummary::from_stock(&stock, fixtures::contract_id(name), FilterIncludeAll).unwrap()
+    }
+
+    #[test]
+    fn identity() {
+        let nia = summary("nia");
+        assert_eq!(nia.schema, Some("NonInflatableAsset"));
+        assert!(nia.ticker.is_some());
+        assert!(nia.name.is_some());
+        assert!(nia.precision.is_some());
+
+        let cfa = summary("cfa");
+        assert_eq!(cfa.ticker, None);
+        assert!(cfa.name.is_some());
+        assert!(cfa.precision.is_some());
+    }
+
+    #[test]
+    fn supplies() {
+        for name in ["nia", "cfa", "pfa", "ifa"] {
+            let summary = summary(name);
+            assert_eq!(
+                summary.supplies.get("issuedSupply"),
+                Some(&(fixtures::ISSUED_SUPPLY as u128)),
+                "{name}"
+            );
+            let owned = summary
+                .allocations
+                .iter()
+                .filter(|a| a.name == "assetOwner")
+                .map(|a| match a.state {
+                    SummaryState::Amount(amount) => amount,
+                    _ => panic!("{name}: non-fungible asset allocation"),
+                })
+                .sum::<u64>();
+            assert_eq!(owned, fixtures::ISSUED_SUPPLY, "{name}");
+        }
+        // Inflation allowances are fungible allocations too
+        assert!(summary("ifa").allocated() > fixtures::ISSUED_SUPPLY as u128);
+    }
+
+    #[test]
+    fn all_states() {
+        for name in fixtures::FIXTURES {
+            let summary = summary(name);
+            assert_eq!(summary.contract_id, fixtures::contract_id(name), "{name}");
+            assert!(summary.schema.is_some(), "{name}");
+        }
+        let uda = summary("uda");
+        assert!(uda
+            .allocations
+            .iter()
+            .all(|a| matches!(a.state, SummaryState::Data(_)) == (a.name == "assetOwner")));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json() {
+        let nia = summary("nia");
+        let json = serde_json::to_value(&nia).unwrap();
+        assert_eq!(json["contractId"], nia.contract_id.to_string());
+        assert_eq!(json["schemaId"], nia.schema_id.to_string());
+        assert_eq!(json["supplies"]["issuedSupply"], fixtures::ISSUED_SUPPLY);
+        let allocation = &json["allocations"][0];
+        assert_eq!(allocation["name"], "assetOwner");
+        assert_eq!(allocation["seal"], nia.allocations[0].seal.to_string());
+        assert_eq!(allocation["state"]["amount"], fixtures::ISSUED_SUPPLY);
+    }
+}
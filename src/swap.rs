@@ -0,0 +1,468 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Swap order schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A contract holding an offer to swap an amount of an asset for a price paid in a counter-asset,
+//! giving DEX prototypes a common order format. The genesis records the contract ids of both
+//! assets, the offered amount, the price of the whole offered amount in the smallest units of the
+//! counter-asset and the public key of the maker, while the maker keeps the order right.
+//!
+//! A `fill` spending the order right records the amount it fills, and may assign the right again
+//! to keep the rest of the order open; the validation scripts require the fills not to exceed the
+//! offered amount. A `cancel` spends the order right without assigning it, closing the order. Both
+//! transitions must be signed by the maker key. The swap of the assets happens in their own
+//! contracts, which the order contract doesn't see.
+//!
+//! The strict types of the standard library have no contract id, so the ids of the assets are
+//! stored as their string form in [`Details`] global state.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_PUTA, INSTR_RET};
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, IssuerWrapper, RightsAllocation, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::Details;
+use rgbstd::validation::Scripts;
+use rgbstd::{Amount, ContractId, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, SWAP_CANCEL, SWAP_FILL, SWAP_GENESIS};
+use crate::wrapper::{check_family, contract_ref, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_COUNTER_ASSET, GS_FILLED,
+    GS_OFFERED_AMOUNT, GS_OFFERED_ASSET, GS_PRICE, GS_PUBKEY, OS_ORDER, TS_CANCEL, TS_FILL,
+};
+
+pub const SWAP_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x5d, 0xcc, 0x20, 0x58, 0x35, 0x1e, 0x18, 0x4f, 0x32, 0x76, 0x64, 0x1f, 0xa0, 0x17, 0xc4, 0x32,
+    0x30, 0xea, 0x20, 0xad, 0x3a, 0x4c, 0x25, 0xdb, 0x32, 0x63, 0x02, 0x20, 0x24, 0xcd, 0x26, 0x9b,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn swap_schema() -> Schema {
+    let types = standard_types();
+
+    SWAP_GENESIS.verify(&[(0, INSTR_RET)]);
+    SWAP_FILL.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA)]);
+    SWAP_CANCEL.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("SwapOrder"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_OFFERED_ASSET => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Details")),
+                name: fname!("offeredAsset"),
+            },
+            GS_OFFERED_AMOUNT => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("offeredAmount"),
+            },
+            GS_COUNTER_ASSET => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Details")),
+                name: fname!("counterAsset"),
+            },
+            GS_PRICE => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("price"),
+            },
+            GS_FILLED => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("filledAmount"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ORDER => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("orderRight"),
+                default_transition: TS_FILL,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_OFFERED_ASSET => Occurrences::Once,
+                GS_OFFERED_AMOUNT => Occurrences::Once,
+                GS_COUNTER_ASSET => Occurrences::Once,
+                GS_PRICE => Occurrences::Once,
+                GS_PUBKEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ORDER => Occurrences::Once,
+            },
+            validator: Some(SWAP_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_FILL => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_FILLED => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ORDER => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ORDER => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(SWAP_FILL.lib_site())
+                },
+                name: fname!("fill"),
+            },
+            TS_CANCEL => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ORDER => Occurrences::Once
+                    },
+                    assignments: none!(),
+                    validator: Some(SWAP_CANCEL.lib_site())
+                },
+                name: fname!("cancel"),
+            },
+        },
+        default_assignment: None,
+    }
+}
+
+fn swap_scripts() -> Scripts { SharedLibs::get().scripts(&[SWAP_GENESIS, SWAP_FILL, SWAP_CANCEL]) }
+
+#[derive(Default)]
+pub struct SwapOrder;
+
+impl IssuerWrapper for SwapOrder {
+    type Wrapper<S: ContractStateRead> = SwapOrderWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(swap_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(swap_scripts).clone()
+    }
+}
+
+impl SwapOrder {
+    /// First revision of the swap order schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "SwapOrder",
+        schema_id: SWAP_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Offer to swap an amount of an asset for a counter-asset, filled or \
+                        cancelled by transitions signed by the maker.",
+    };
+
+    /// Global state value referencing the contract `id`, as stored by the `offeredAsset` and
+    /// `counterAsset` global state.
+    pub fn asset_ref(id: ContractId) -> Details {
+        Details::from_str(&id.to_string()).expect("contract ids are valid details")
+    }
+}
+
+impl IssuerInfo for SwapOrder {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for SwapOrder {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct SwapOrderWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for SwapOrderWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for SwapOrderWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<SwapOrder, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> SwapOrderWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the swap order schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<SwapOrder, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the swap order schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn contract_id(&self) -> ContractId { self.0.contract_id() }
+
+    /// Returns the contract id of the offered asset.
+    pub fn offered_asset(&self) -> ContractId { or_panic(self.try_offered_asset()) }
+
+    pub fn try_offered_asset(&self) -> Result<ContractId, Error> {
+        contract_ref(&self.0, GS_OFFERED_ASSET)
+    }
+
+    pub fn offered_amount(&self) -> Amount { or_panic(self.try_offered_amount()) }
+
+    pub fn try_offered_amount(&self) -> Result<Amount, Error> { global(&self.0, GS_OFFERED_AMOUNT) }
+
+    /// Returns the contract id of the asset in which the price is paid.
+    pub fn counter_asset(&self) -> ContractId { or_panic(self.try_counter_asset()) }
+
+    pub fn try_counter_asset(&self) -> Result<ContractId, Error> {
+        contract_ref(&self.0, GS_COUNTER_ASSET)
+    }
+
+    /// Returns the price of the whole offered amount, in units of the counter-asset.
+    pub fn price(&self) -> Amount { or_panic(self.try_price()) }
+
+    pub fn try_price(&self) -> Result<Amount, Error> { global(&self.0, GS_PRICE) }
+
+    /// Returns the public key of the maker, which signs fills and cancellations.
+    pub fn pubkey(&self) -> CompressedPublicKey { or_panic(self.try_pubkey()) }
+
+    pub fn try_pubkey(&self) -> Result<CompressedPublicKey, Error> { global(&self.0, GS_PUBKEY) }
+
+    /// Returns the amounts filled by each fill, starting from the first one.
+    pub fn fills(&self) -> Vec<Amount> { or_panic(self.try_fills()) }
+
+    pub fn try_fills(&self) -> Result<Vec<Amount>, Error> {
+        let mut fills = globals(&self.0, GS_FILLED)?.collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        fills.reverse();
+        Ok(fills)
+    }
+
+    /// Returns the part of the offered amount not filled yet.
+    pub fn remaining_amount(&self) -> Amount { or_panic(self.try_remaining_amount()) }
+
+    pub fn try_remaining_amount(&self) -> Result<Amount, Error> {
+        let filled = self.try_fills()?.into_iter().sum::<Amount>();
+        Ok(self.try_offered_amount()?.saturating_sub(filled))
+    }
+
+    /// Returns the price of filling `amount` units of the order, rounded up.
+    pub fn cost(&self, amount: Amount) -> Amount { or_panic(self.try_cost(amount)) }
+
+    pub fn try_cost(&self, amount: Amount) -> Result<Amount, Error> {
+        let remaining = self.try_remaining_amount()?;
+        if amount > remaining {
+            return Err(Error::ExcessiveFill {
+                available: remaining,
+                required: amount,
+            });
+        }
+        let offered = self.try_offered_amount()?.value() as u128;
+        if offered == 0 {
+            return Ok(Amount::ZERO);
+        }
+        // Can't overflow as `amount` doesn't exceed the offered amount
+        let cost = (self.try_price()?.value() as u128 * amount.value() as u128).div_ceil(offered);
+        Ok(Amount::from(cost as u64))
+    }
+
+    /// Returns the order rights, whose holder can fill or cancel the order.
+    pub fn order_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_order_rights(filter))
+    }
+
+    pub fn try_order_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_ORDER, filter)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::{Amount, ContractId, Operation, Opout, Outpoint, OutputSeal, Transition};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = swap_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(SWAP_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<SwapOrder>(fixtures::contract_id("swap"))
+            .unwrap();
+        assert_eq!(wrapper.version(), SwapOrder::V1);
+        assert_eq!(wrapper.offered_asset(), fixtures::offered_asset());
+        assert_eq!(wrapper.counter_asset(), fixtures::counter_asset());
+        assert_eq!(wrapper.offered_amount(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.price(), Amount::from(fixtures::SWAP_PRICE));
+        assert_eq!(wrapper.pubkey(), fixtures::pubkey());
+        assert_eq!(wrapper.fills(), vec![]);
+        assert_eq!(wrapper.remaining_amount(), Amount::from(fixtures::ISSUED_SUPPLY));
+        // 2500 units of the counter-asset for 1000 offered units
+        assert_eq!(wrapper.cost(Amount::from(3u64)), Amount::from(8u64));
+        assert_eq!(
+            wrapper.try_cost(Amount::from(1001u64)),
+            Err(Error::ExcessiveFill {
+                available: Amount::from(1000u64),
+                required: Amount::from(1001u64)
+            })
+        );
+
+        let rights = wrapper
+            .order_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![OutputSeal::new(Outpoint::new(testing::txid(), 1))]);
+    }
+
+    // Fills and cancellation of a swap order.
+    //
+    // The maker fills the order twice, in part, and then cancels it; the wrapper tracks the fills
+    // and prices the amount left in the order.
+
+    /// Swap order offering `offered` units for `price` units of the counter-asset, signed by the
+    /// `pubkey` maker, with the order right at `seal(1)`.
+    fn swap_order(offered: u64, price: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+        testing::builder::<SwapOrder>()
+            .add_global_state("offeredAsset", SwapOrder::asset_ref(ContractId::from([0x11; 32])))
+            .unwrap()
+            .add_global_state("offeredAmount", Amount::from(offered))
+            .unwrap()
+            .add_global_state("counterAsset", SwapOrder::asset_ref(ContractId::from([0x22; 32])))
+            .unwrap()
+            .add_global_state("price", Amount::from(price))
+            .unwrap()
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+            .add_rights("orderRight", testing::seal(1))
+            .unwrap()
+    }
+
+    #[test]
+    fn fill_and_cancel() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(swap_order(1000, 2500, testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 1);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+
+        let wrapper = stock.contract_wrapper::<SwapOrder>(contract_id).unwrap();
+        assert_eq!(wrapper.offered_asset(), ContractId::from([0x11; 32]));
+        assert_eq!(wrapper.counter_asset(), ContractId::from([0x22; 32]));
+        assert_eq!(wrapper.remaining_amount(), Amount::from(1000u64));
+        assert_eq!(wrapper.cost(Amount::from(1000u64)), Amount::from(2500u64));
+
+        let mut right = (Opout::new(genesis_id, OS_ORDER, 0), Outpoint::new(testing::txid(), 1));
+        for (height, filled) in [(101, 300u64), (102, 500)] {
+            let mut transition = stock
+                .transition_builder(contract_id, "fill")
+                .unwrap()
+                .add_input(right.0, AllocatedState::Void)
+                .unwrap()
+                .add_global_state("filledAmount", Amount::from(filled))
+                .unwrap()
+                .add_rights("orderRight", testing::graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            transition.signature = Some(testing::sign(&transition));
+            let opid = transition.id();
+            let witness_id = consume(&mut stock, transition, &[right.1], height);
+            right = (Opout::new(opid, OS_ORDER, 0), Outpoint::new(witness_id, 1));
+        }
+
+        let wrapper = stock.contract_wrapper::<SwapOrder>(contract_id).unwrap();
+        assert_eq!(wrapper.fills(), vec![Amount::from(300u64), Amount::from(500u64)]);
+        assert_eq!(wrapper.remaining_amount(), Amount::from(200u64));
+        assert_eq!(wrapper.cost(Amount::from(200u64)), Amount::from(500u64));
+        assert_eq!(wrapper.cost(Amount::from(1u64)), Amount::from(3u64));
+        assert!(matches!(wrapper.try_cost(Amount::from(201u64)), Err(Error::ExcessiveFill { .. })));
+        assert_eq!(wrapper.order_rights(vec![right.1]).count(), 1);
+
+        // the maker cancels the rest of the order
+        let mut transition = stock
+            .transition_builder(contract_id, "cancel")
+            .unwrap()
+            .add_input(right.0, AllocatedState::Void)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        transition.signature = Some(testing::sign(&transition));
+        consume(&mut stock, transition, &[right.1], 103);
+
+        let wrapper = stock.contract_wrapper::<SwapOrder>(contract_id).unwrap();
+        // the fills made before the cancellation remain recorded
+        assert_eq!(wrapper.fills().len(), 2);
+        assert_eq!(wrapper.remaining_amount(), Amount::from(200u64));
+    }
+}
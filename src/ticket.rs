@@ -0,0 +1,472 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Event ticket schema.
+//!
+//! Each ticket is a unique token of a collection issued in a single genesis, allocated and
+//! transferred like the UDC ones. A ticket is used by redeeming it: the redeem transition spends
+//! the ticket without assigning it to a new owner and records it in the `redeemedTickets` global
+//! state, so a redeemed ticket can't be transferred again.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, TokenData};
+use rgbstd::validation::Scripts;
+use rgbstd::{
+    Allocation, GlobalDetails, OwnedStateSchema, SchemaId, TokenIndex, TransitionDetails,
+};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, TICKET_REDEEM, UDC_GENESIS, UDC_MATCH, UDC_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, WrongSchema, GS_NOMINAL, GS_REDEEMED_TICKETS, GS_TERMS,
+    GS_TOKENS, OS_ASSET, TS_REDEEM, TS_TRANSFER,
+};
+
+pub const TICKET_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xab, 0xb2, 0x30, 0x11, 0x97, 0xe0, 0x1b, 0x46, 0xf6, 0x0b, 0x9a, 0x3d, 0x7a, 0x16, 0xce, 0x2a,
+    0x07, 0xcc, 0x1b, 0xd1, 0x58, 0xec, 0xea, 0xa5, 0x4d, 0x7d, 0x79, 0xc0, 0x29, 0x2b, 0xff, 0x41,
+]);
+
+/// Maximum number of tickets of a contract, as the UDC genesis script counts them in an 8-bit
+/// register.
+pub const TICKET_MAX_TICKETS: u16 = 255;
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn ticket_schema() -> Schema {
+    let types = standard_types();
+
+    UDC_GENESIS.verify(&[(0, INSTR_PUTA)]);
+    UDC_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDC_MATCH.verify(&[(0, INSTR_PUTA)]);
+    TICKET_REDEEM.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("TicketAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_TOKENS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.TokenData")),
+                name: fname!("tokens"),
+            },
+            GS_REDEEMED_TICKETS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Allocation")),
+                name: fname!("redeemedTickets"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::OnceOrUpTo(TICKET_MAX_TICKETS),
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(UDC_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(UDC_TRANSFER.lib_site()),
+                },
+                name: fname!("transfer"),
+            },
+            TS_REDEEM => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_REDEEMED_TICKETS => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once,
+                    },
+                    assignments: none!(),
+                    validator: Some(TICKET_REDEEM.lib_site())
+                },
+                name: fname!("redeem"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn ticket_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[UDC_GENESIS, UDC_TRANSFER, TICKET_REDEEM])
+}
+
+#[derive(Default)]
+pub struct TicketAsset;
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct TicketWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl IssuerWrapper for TicketAsset {
+    type Wrapper<S: ContractStateRead> = TicketWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(ticket_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(ticket_scripts).clone()
+    }
+}
+
+impl TicketAsset {
+    /// First revision of the ticket schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "TicketAsset",
+        schema_id: TICKET_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Event tickets issued as unique tokens at genesis, a transfer transition \
+                        moving them and a redeem transition using each of them once.",
+    };
+}
+
+impl IssuerInfo for TicketAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for TicketAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+impl<S: ContractStateRead> SchemaWrapper<S> for TicketWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for TicketWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<TicketAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> TicketWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the ticket schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<TicketAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the ticket schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the tickets issued by the contract, sorted by their index.
+    pub fn tickets(&self) -> Vec<TokenData> { or_panic(self.try_tickets()) }
+
+    pub fn try_tickets(&self) -> Result<Vec<TokenData>, Error> {
+        let mut tickets =
+            globals::<_, TokenData>(&self.0, GS_TOKENS)?.collect::<Result<Vec<_>, _>>()?;
+        tickets.sort_by_key(|ticket| ticket.index);
+        Ok(tickets)
+    }
+
+    /// Returns the indexes of the redeemed tickets, in the order they were redeemed.
+    pub fn redeemed_tickets(&self) -> Vec<TokenIndex> { or_panic(self.try_redeemed_tickets()) }
+
+    pub fn try_redeemed_tickets(&self) -> Result<Vec<TokenIndex>, Error> {
+        let mut redeemed = globals::<_, Allocation>(&self.0, GS_REDEEMED_TICKETS)?
+            .map(|allocation| allocation.map(|allocation| allocation.token_index()))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Global state lists the latest entries first
+        redeemed.reverse();
+        Ok(redeemed)
+    }
+
+    /// Returns the indexes of the tickets not redeemed yet, sorted.
+    pub fn outstanding_tickets(&self) -> Vec<TokenIndex> {
+        or_panic(self.try_outstanding_tickets())
+    }
+
+    pub fn try_outstanding_tickets(&self) -> Result<Vec<TokenIndex>, Error> {
+        let redeemed = self
+            .try_redeemed_tickets()?
+            .into_iter()
+            .collect::<HashSet<_>>();
+        Ok(self
+            .try_tickets()?
+            .into_iter()
+            .map(|ticket| ticket.index)
+            .filter(|index| !redeemed.contains(index))
+            .collect())
+    }
+
+    /// Tells whether the ticket with the given index is redeemed, failing if the contract has no
+    /// such ticket.
+    pub fn is_redeemed(&self, index: TokenIndex) -> bool { or_panic(self.try_is_redeemed(index)) }
+
+    pub fn try_is_redeemed(&self, index: TokenIndex) -> Result<bool, Error> {
+        if !self
+            .try_tickets()?
+            .iter()
+            .any(|ticket| ticket.index == index)
+        {
+            return Err(Error::UnknownToken(index));
+        }
+        Ok(self.try_redeemed_tickets()?.contains(&index))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Builds an invoice for receiving the ticket with the given index, failing if the contract
+    /// has no such ticket or it is already redeemed.
+    pub fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary, index))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        index: TokenIndex,
+    ) -> Result<RgbInvoice, Error> {
+        if self.try_is_redeemed(index)? {
+            return Err(Error::RedeemedTicket(index));
+        }
+        allocation_invoice(&self.0, beneficiary, Allocation::with(index, 1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::Wrapper;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::{AllocatedState, ContractBuilder, DataAllocation, FilterIncludeAll};
+    use rgbstd::invoice::{Beneficiary, Precision, XChainNet};
+    use rgbstd::persistence::Stock;
+    use rgbstd::{Allocation, ContractId, Outpoint, TokenIndex};
+    use schemata_tools::testing;
+    use strict_types::StrictDeserialize;
+
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = ticket_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(TICKET_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<TicketAsset>(fixtures::contract_id("ticket"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), AssetSpec::new("TKT", "Test tickets", Precision::Indivisible));
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+
+        let indexes = fixtures::TICKETS.map(TokenIndex::from_inner);
+        assert_eq!(
+            wrapper
+                .tickets()
+                .into_iter()
+                .map(|ticket| ticket.index)
+                .collect::<Vec<_>>(),
+            indexes
+        );
+        assert_eq!(wrapper.redeemed_tickets(), vec![]);
+        assert_eq!(wrapper.outstanding_tickets(), indexes);
+        assert!(!wrapper.is_redeemed(indexes[0]));
+        assert_eq!(
+            wrapper.try_is_redeemed(TokenIndex::from_inner(3)),
+            Err(Error::UnknownToken(TokenIndex::from_inner(3)))
+        );
+        assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), indexes.len());
+    }
+
+    // Tickets used by redeeming them.
+    //
+    // The genesis allocates three tickets to different holders. Two of them are redeemed, each in
+    // its own mined witness TX, and the `TicketWrapper` must then report them as redeemed in
+    // the order they were used, leaving the third one outstanding.
+
+    /// Event tickets with the given indexes, each allocated whole to consecutive seals starting
+    /// from `seal(1)`.
+    fn ticket(tickets: &[u32]) -> ContractBuilder {
+        let mut builder = testing::builder::<TicketAsset>()
+            .add_global_state("spec", AssetSpec::new("TKT", "Test tickets", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap();
+        for (vout, index) in (1..).zip(tickets) {
+            let index = TokenIndex::from_inner(*index);
+            builder = builder
+                .add_global_state("tokens", TokenData {
+                    index,
+                    ..Default::default()
+                })
+                .unwrap()
+                .add_data("assetOwner", testing::seal(vout), Allocation::with(index, 1))
+                .unwrap();
+        }
+        builder
+    }
+
+    fn ticket_of(allocation: &DataAllocation) -> Allocation {
+        Allocation::from_strict_serialized(allocation.state.clone().into()).unwrap()
+    }
+
+    /// Redeems the ticket allocated by `allocation` in a witness TX mined at `height`.
+    fn redeem(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        allocation: &DataAllocation,
+        height: u32,
+    ) {
+        let ticket = ticket_of(allocation);
+        let transition = stock
+            .transition_builder(contract_id, "redeem")
+            .unwrap()
+            .add_input(allocation.opout, AllocatedState::Data(allocation.state.clone()))
+            .unwrap()
+            .add_global_state("redeemedTickets", ticket)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+
+        let prevout = Outpoint::new(allocation.seal.txid, allocation.seal.vout.into_u32());
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+    }
+
+    #[test]
+    fn redeem_tickets() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(ticket(&[1, 2, 5]));
+        let contract_id = contract.contract_id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let indexes = [1, 2, 5].map(TokenIndex::from_inner);
+        let wrapper = stock.contract_wrapper::<TicketAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.redeemed_tickets(), vec![]);
+        assert_eq!(wrapper.outstanding_tickets(), indexes);
+        let allocations = wrapper.allocations(&FilterIncludeAll).collect::<Vec<_>>();
+        let ticket_allocation = |index: TokenIndex| {
+            allocations
+                .iter()
+                .find(|allocation| ticket_of(allocation).token_index() == index)
+                .unwrap()
+        };
+
+        redeem(&mut stock, &mut resolver, contract_id, ticket_allocation(indexes[2]), 100);
+        redeem(&mut stock, &mut resolver, contract_id, ticket_allocation(indexes[0]), 101);
+
+        let wrapper = stock.contract_wrapper::<TicketAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.redeemed_tickets(), vec![indexes[2], indexes[0]]);
+        assert_eq!(wrapper.outstanding_tickets(), vec![indexes[1]]);
+        assert!(wrapper.is_redeemed(indexes[0]));
+        assert!(!wrapper.is_redeemed(indexes[1]));
+
+        let beneficiary = XChainNet::with(
+            testing::CHAIN_NET,
+            Beneficiary::from(testing::seal(7).to_secret_seal()),
+        );
+        wrapper.invoice(beneficiary, indexes[1]);
+        assert_eq!(
+            wrapper.try_invoice(beneficiary, indexes[0]).unwrap_err(),
+            Error::RedeemedTicket(indexes[0])
+        );
+    }
+}
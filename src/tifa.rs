@@ -0,0 +1,783 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Threshold inflatable fungible asset (TIFA) schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A variant of the IFA schema whose inflation must be approved by `m` of the `n` keys committed
+//! in the genesis global state, next to the threshold `m`, instead of by the owner of the
+//! inflation rights alone. A transition carries a single signature, so each key approves in its
+//! own `approve` transition, naming itself in the `signer` metadata and spending the approval
+//! state, which counts the approvals. Keys approve in the order [`TifaWrapper::pubkeys`] lists
+//! them, skipping any, so none approves twice. An inflation spends the approval state once it
+//! counts at least `m` approvals and resets it.
+//!
+//! Each approval also assigns the approved inflation, the supply the approvals allow to issue.
+//! The first approval sets it and the later ones spend it and must keep it, so every approving key
+//! signs the amount. The inflation spends it and must issue exactly this supply, leaving the next
+//! first approval to assign it again.
+//!
+//! # Trust model
+//!
+//! The approvals bind the supply an inflation issues, but not its beneficiaries: validation
+//! scripts can't read the seals of the assignments, so the holder of the inflation rights and of
+//! the approval state, who builds the inflation, chooses where the issued supply goes. The
+//! approving keys trust it to assign the supply as agreed, and must only approve while it holds the
+//! approval state. What the holder can't do is issue more or less than the supply the `m` keys
+//! signed, or issue it twice with the same approvals.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use amplify::Wrapper;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, DataAllocation, FungibleAllocation, IssuerWrapper,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Allocation, Amount, GlobalDetails, MetaDetails, SchemaId, TransitionDetails};
+use strict_types::{StrictDeserialize, TypeSystem};
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, IFA_TRANSFER, TIFA_APPROVE, TIFA_GENESIS, TIFA_INFLATION};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema,
+    GS_INFLATION_THRESHOLD, GS_ISSUED_SUPPLY, GS_MAX_SUPPLY, GS_NOMINAL, GS_PUBKEY, GS_TERMS,
+    MS_ALLOWED_INFLATION, MS_SIGNER, OS_APPROVAL, OS_APPROVED_INFLATION, OS_ASSET, OS_INFLATION,
+    TS_APPROVE, TS_INFLATION, TS_TRANSFER,
+};
+
+pub const TIFA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x73, 0x5f, 0x51, 0x39, 0x74, 0x31, 0x75, 0x3d, 0x1e, 0xd1, 0xa5, 0x2d, 0x34, 0x5d, 0x55, 0xbb,
+    0x6e, 0x1b, 0x82, 0xbe, 0xdc, 0xdc, 0xa8, 0xde, 0x46, 0xac, 0x03, 0x56, 0xc3, 0xe5, 0x43, 0xf1,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn tifa_schema() -> Schema {
+    let types = standard_types();
+
+    TIFA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    IFA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS), (8, INSTR_SVS)]);
+    TIFA_APPROVE.verify(&[(0, INSTR_PUTA)]);
+    TIFA_INFLATION.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("ThresholdInflatableAsset"),
+        meta_types: tiny_bmap! {
+            MS_ALLOWED_INFLATION => MetaDetails {
+                sem_id: types.get("RGBContract.Amount"),
+                name: fname!("allowedInflation"),
+            },
+            MS_SIGNER => MetaDetails {
+                sem_id: types.get("Bitcoin.CompressedPk"),
+                name: fname!("signer"),
+            }
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_MAX_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("maxSupply"),
+            },
+            GS_INFLATION_THRESHOLD => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("inflationThreshold"),
+            },
+            GS_PUBKEY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.CompressedPk")),
+                name: fname!("pubkey"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_INFLATION => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("inflationAllowance"),
+                default_transition: TS_TRANSFER
+            },
+            // The position following the last approving key is kept as the token index and the
+            // number of approvals as the fraction
+            OS_APPROVAL => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
+                name: fname!("inflationApproval"),
+                default_transition: TS_APPROVE,
+            },
+            OS_APPROVED_INFLATION => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("approvedInflation"),
+                default_transition: TS_APPROVE,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_MAX_SUPPLY => Occurrences::Once,
+                GS_INFLATION_THRESHOLD => Occurrences::Once,
+                GS_PUBKEY => Occurrences::OnceOrMore,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_INFLATION => Occurrences::NoneOrMore,
+                OS_APPROVAL => Occurrences::Once,
+            },
+            validator: Some(TIFA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore
+                    },
+                    validator: Some(IFA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_INFLATION => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: tiny_bset![MS_ALLOWED_INFLATION],
+                    globals: tiny_bmap! {
+                        GS_ISSUED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_INFLATION => Occurrences::OnceOrMore,
+                        OS_APPROVAL => Occurrences::Once,
+                        OS_APPROVED_INFLATION => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_INFLATION => Occurrences::NoneOrMore,
+                        OS_APPROVAL => Occurrences::Once
+                    },
+                    validator: Some(TIFA_INFLATION.lib_site())
+                },
+                name: fname!("inflate"),
+            },
+            TS_APPROVE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: tiny_bset![MS_SIGNER],
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_APPROVAL => Occurrences::Once,
+                        OS_APPROVED_INFLATION => Occurrences::NoneOrOnce
+                    },
+                    assignments: tiny_bmap! {
+                        OS_APPROVAL => Occurrences::Once,
+                        OS_APPROVED_INFLATION => Occurrences::Once
+                    },
+                    validator: Some(TIFA_APPROVE.lib_site())
+                },
+                name: fname!("approve"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn tifa_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[TIFA_GENESIS, IFA_TRANSFER, TIFA_APPROVE, TIFA_INFLATION])
+}
+
+#[derive(Default)]
+pub struct ThresholdInflatableAsset;
+
+impl IssuerWrapper for ThresholdInflatableAsset {
+    type Wrapper<S: ContractStateRead> = TifaWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(tifa_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(tifa_scripts).clone()
+    }
+}
+
+impl ThresholdInflatableAsset {
+    /// First revision of the TIFA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "ThresholdInflatableAsset",
+        schema_id: TIFA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset supporting inflation up to a maximum supply once approved \
+                        by a threshold of the public keys committed in the global state.",
+    };
+}
+
+impl IssuerInfo for ThresholdInflatableAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for ThresholdInflatableAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct TifaWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for TifaWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for TifaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<ThresholdInflatableAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> TifaWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the TIFA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<ThresholdInflatableAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the TIFA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    pub fn max_supply(&self) -> Amount { or_panic(self.try_max_supply()) }
+
+    pub fn try_max_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_MAX_SUPPLY)?.sum()
+    }
+
+    /// Returns the number of approvals an inflation needs.
+    pub fn inflation_threshold(&self) -> u64 { or_panic(self.try_inflation_threshold()) }
+
+    pub fn try_inflation_threshold(&self) -> Result<u64, Error> {
+        Ok(global::<_, Amount>(&self.0, GS_INFLATION_THRESHOLD)?.value())
+    }
+
+    /// Returns the approving public keys, in the order they must approve an inflation.
+    pub fn pubkeys(&self) -> Vec<CompressedPublicKey> { or_panic(self.try_pubkeys()) }
+
+    pub fn try_pubkeys(&self) -> Result<Vec<CompressedPublicKey>, Error> {
+        globals(&self.0, GS_PUBKEY)?.collect()
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_inflation_allocations(filter))
+    }
+
+    pub fn try_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_INFLATION, filter)?)
+    }
+
+    pub fn approval_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        or_panic(self.try_approval_allocations(filter))
+    }
+
+    pub fn try_approval_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_APPROVAL, filter)?)
+    }
+
+    /// Returns the number of approvals collected by the approval state passing the filter, or
+    /// zero if none does.
+    pub fn approvals(&self, filter: impl AssignmentsFilter) -> u64 {
+        or_panic(self.try_approvals(filter))
+    }
+
+    pub fn try_approvals(&self, filter: impl AssignmentsFilter) -> Result<u64, Error> {
+        Ok(self
+            .try_approval(filter)?
+            .map(|approval| approval.fraction().value())
+            .unwrap_or_default())
+    }
+
+    pub fn approved_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_approved_inflation_allocations(filter))
+    }
+
+    pub fn try_approved_inflation_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_APPROVED_INFLATION, filter)?)
+    }
+
+    /// Returns the supply the approvals passing the filter allow the next inflation to issue, or
+    /// zero if no approved inflation passes the filter.
+    pub fn approved_inflation(&self, filter: impl AssignmentsFilter) -> Amount {
+        or_panic(self.try_approved_inflation(filter))
+    }
+
+    pub fn try_approved_inflation(&self, filter: impl AssignmentsFilter) -> Result<Amount, Error> {
+        Ok(self
+            .try_approved_inflation_allocations(filter)?
+            .map(|allocation| allocation.state)
+            .sum())
+    }
+
+    /// Returns the keys which can still approve the approval state passing the filter, in the
+    /// order they must approve, or none if no approval state passes the filter.
+    pub fn pending_approvers(&self, filter: impl AssignmentsFilter) -> Vec<CompressedPublicKey> {
+        or_panic(self.try_pending_approvers(filter))
+    }
+
+    pub fn try_pending_approvers(
+        &self,
+        filter: impl AssignmentsFilter,
+    ) -> Result<Vec<CompressedPublicKey>, Error> {
+        let Some(approval) = self.try_approval(filter)? else {
+            return Ok(vec![]);
+        };
+        let next = approval.token_index().to_inner() as usize;
+        Ok(self.try_pubkeys()?.into_iter().skip(next).collect())
+    }
+
+    fn try_approval(&self, filter: impl AssignmentsFilter) -> Result<Option<Allocation>, Error> {
+        self.try_approval_allocations(filter)?
+            .next()
+            .map(|allocation| {
+                Allocation::from_strict_serialized(allocation.state.into())
+                    .map_err(|_| Error::InvalidOwned(OS_APPROVAL))
+            })
+            .transpose()
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for TifaWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { TifaWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        TifaWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        TifaWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        TifaWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use amplify::Wrapper;
+    use rgbstd::bitcoin::CompressedPublicKey;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::secp256k1::SecretKey;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::{fixtures, ValidationErrno};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = tifa_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(TIFA_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<ThresholdInflatableAsset>(fixtures::contract_id("tifa"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.max_supply(), Amount::from(fixtures::MAX_SUPPLY));
+        assert_eq!(wrapper.inflation_threshold(), 2);
+        let pubkeys = wrapper.pubkeys();
+        assert_eq!(pubkeys.iter().copied().collect::<BTreeSet<_>>(), bset! {
+            fixtures::pubkey(),
+            fixtures::cosigner_pubkey()
+        });
+        assert_eq!(wrapper.approvals(&FilterIncludeAll), 0);
+        assert_eq!(wrapper.pending_approvers(&FilterIncludeAll), pubkeys);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![fixtures::ISSUED_SUPPLY]);
+        let inflation = wrapper
+            .inflation_allocations(&FilterIncludeAll)
+            .map(|a| a.state.value())
+            .collect::<Vec<_>>();
+        assert_eq!(inflation, vec![fixtures::MAX_SUPPLY - fixtures::ISSUED_SUPPLY]);
+    }
+
+    // Approval of the inflation of a threshold inflatable asset.
+    //
+    // Both keys committed in the genesis approve one after the other, in the order reported by the
+    // wrapper, each approval being anchored into its own mined witness TX and committing to the
+    // supply the inflation may issue; the inflation then spends the approvals collected and resets
+    // the approval state for the next one. An inflation issuing another supply than the approved
+    // one must be rejected by the validation of the history.
+
+    /// Threshold inflatable asset allocated to `seal(1)`, with the inflation allowance up
+    /// to a max supply of 1500 at `seal(2)` and the approval state, holding the
+    /// position following the last approving key and the number of approvals, at
+    /// `seal(3)`.
+    fn tifa(
+        issued: u64,
+        allocated: u64,
+        threshold: u64,
+        pubkeys: &[CompressedPublicKey],
+        approval: (u32, u64),
+    ) -> ContractBuilder {
+        let mut builder = testing::asset::<ThresholdInflatableAsset>(issued, allocated)
+            .add_global_state("maxSupply", Amount::from(1500u64))
+            .unwrap()
+            .add_global_state("inflationThreshold", Amount::from(threshold))
+            .unwrap();
+        for pubkey in pubkeys {
+            builder = builder.add_global_state("pubkey", *pubkey).unwrap();
+        }
+        builder
+            .add_fungible_state("inflationAllowance", testing::seal(2), 1500 - issued)
+            .unwrap()
+            .add_data(
+                "inflationApproval",
+                testing::seal(3),
+                Allocation::with(TokenIndex::from_inner(approval.0), approval.1),
+            )
+            .unwrap()
+    }
+
+    fn approval(index: u32, count: u64) -> Allocation {
+        Allocation::with(TokenIndex::from_inner(index), count)
+    }
+
+    fn data(index: u32, count: u64) -> AllocatedState {
+        match testing::allocation(index, count) {
+            RevealedState::Structured(data) => AllocatedState::Data(data),
+            _ => unreachable!(),
+        }
+    }
+
+    fn signer_key(pubkey: CompressedPublicKey) -> SecretKey {
+        [testing::issuer_key(), testing::cosigner_key()]
+            .into_iter()
+            .find(|key| pubkey == CompressedPublicKey(key.public_key(&Default::default())))
+            .expect("unknown approver")
+    }
+
+    /// Anchors `transition` spending `prevouts` into a witness TX with four outputs mined at
+    /// `height` and consumes it into the stock, returning the witness id.
+    fn consume(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        transition: Transition,
+        prevouts: &[Outpoint],
+        height: u32,
+    ) -> Txid {
+        let fascia = testing::anchor(contract_id, transition, prevouts, 4);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        witness_id
+    }
+
+    /// Issues a threshold inflatable asset requiring both test keys to approve, and collects
+    /// their approvals of an inflation issuing `approved` units. Returns the stock, the resolver,
+    /// the contract and genesis ids, the last approval and the outpoint holding its state.
+    fn collect_approvals(
+        approved: u64,
+    ) -> (Stock, testing::MockResolver, ContractId, OpId, OpId, Outpoint) {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(tifa(
+            1000,
+            1000,
+            2,
+            &[testing::issuer_pubkey(), testing::cosigner_pubkey()],
+            (0, 0),
+        ));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<ThresholdInflatableAsset>(contract_id)
+            .unwrap();
+        let approvers = wrapper.pubkeys();
+        assert_eq!(approvers, vec![testing::cosigner_pubkey(), testing::issuer_pubkey()]);
+        let mut holder = Outpoint::new(testing::txid(), 3);
+        assert_eq!(wrapper.approvals(holder), 0);
+        assert_eq!(wrapper.approved_inflation(holder), Amount::from(0u64));
+        assert_eq!(wrapper.pending_approvers(holder), approvers);
+
+        let mut input = genesis_id;
+        let mut state = (0, 0);
+        for (pos, signer) in approvers.iter().enumerate() {
+            let approved_state = (pos as u32 + 1, pos as u64 + 1);
+            let mut builder = stock
+                .transition_builder(contract_id, "approve")
+                .unwrap()
+                .add_input(Opout::new(input, OS_APPROVAL, 0), data(state.0, state.1))
+                .unwrap();
+            // the first approval assigns the approved inflation, the later ones spend it
+            if pos > 0 {
+                builder = builder
+                    .add_input(
+                        Opout::new(input, OS_APPROVED_INFLATION, 0),
+                        AllocatedState::Amount(RevealedValue::from(approved)),
+                    )
+                    .unwrap();
+            }
+            let mut transition = builder
+                .add_metadata("signer", *signer)
+                .unwrap()
+                .add_data(
+                    "inflationApproval",
+                    testing::graph_seal(1),
+                    approval(approved_state.0, approved_state.1),
+                )
+                .unwrap()
+                .add_fungible_state("approvedInflation", testing::graph_seal(1), approved)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            transition.signature = Some(testing::sign_with(&transition, &signer_key(*signer)));
+            input = transition.id();
+            let witness_id = consume(
+                &mut stock,
+                &mut resolver,
+                contract_id,
+                transition,
+                &[holder],
+                100 + pos as u32,
+            );
+            holder = Outpoint::new(witness_id, 1);
+            state = approved_state;
+
+            let wrapper = stock
+                .contract_wrapper::<ThresholdInflatableAsset>(contract_id)
+                .unwrap();
+            assert_eq!(wrapper.approvals(holder), pos as u64 + 1);
+            assert_eq!(wrapper.approved_inflation(holder), Amount::from(approved));
+            assert_eq!(wrapper.pending_approvers(holder), approvers[pos + 1..]);
+        }
+        (stock, resolver, contract_id, genesis_id, input, holder)
+    }
+
+    /// Inflates `issued` units spending the inflation allowance of the genesis and the approvals
+    /// of `approved` units collected up to the `last_approval`, returning the inflation.
+    fn inflate(
+        stock: &Stock,
+        contract_id: ContractId,
+        genesis_id: OpId,
+        last_approval: OpId,
+        approved: u64,
+        issued: u64,
+    ) -> Transition {
+        stock
+            .transition_builder(contract_id, "inflate")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_INFLATION, 0),
+                AllocatedState::Amount(RevealedValue::from(500u64)),
+            )
+            .unwrap()
+            .add_input(Opout::new(last_approval, OS_APPROVAL, 0), data(2, 2))
+            .unwrap()
+            .add_input(
+                Opout::new(last_approval, OS_APPROVED_INFLATION, 0),
+                AllocatedState::Amount(RevealedValue::from(approved)),
+            )
+            .unwrap()
+            .add_global_state("issuedSupply", Amount::from(issued))
+            .unwrap()
+            .add_metadata("allowedInflation", Amount::from(500 - issued))
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), issued)
+            .unwrap()
+            .add_fungible_state("inflationAllowance", testing::graph_seal(2), 500 - issued)
+            .unwrap()
+            .add_data("inflationApproval", testing::graph_seal(3), approval(0, 0))
+            .unwrap()
+            .complete_transition()
+            .unwrap()
+    }
+
+    #[test]
+    fn approve_and_inflate() {
+        let (mut stock, mut resolver, contract_id, genesis_id, last_approval, holder) =
+            collect_approvals(200);
+        let approvers = stock
+            .contract_wrapper::<ThresholdInflatableAsset>(contract_id)
+            .unwrap()
+            .pubkeys();
+
+        let transition = inflate(&stock, contract_id, genesis_id, last_approval, 200, 200);
+        let opid = transition.id();
+        let witness_id = consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 2), holder],
+            102,
+        );
+        testing::validate_history::<ThresholdInflatableAsset>(&stock, &resolver, contract_id, &[
+            opid,
+        ])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<ThresholdInflatableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1200u64));
+        let holder = Outpoint::new(witness_id, 3);
+        assert_eq!(wrapper.approvals(holder), 0);
+        assert_eq!(wrapper.approved_inflation(holder), Amount::from(0u64));
+        assert_eq!(wrapper.pending_approvers(holder), approvers);
+    }
+
+    #[test]
+    fn unapproved_inflation_rejected() {
+        let (mut stock, mut resolver, contract_id, genesis_id, last_approval, holder) =
+            collect_approvals(200);
+
+        let transition = inflate(&stock, contract_id, genesis_id, last_approval, 200, 300);
+        let opid = transition.id();
+        consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 2), holder],
+            102,
+        );
+
+        let err = testing::validate_history::<ThresholdInflatableAsset>(
+            &stock,
+            &resolver,
+            contract_id,
+            &[opid],
+        )
+        .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::UnapprovedInflation));
+    }
+}
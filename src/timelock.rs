@@ -0,0 +1,402 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timelocked asset schema.
+//!
+//! A variant of the NIA schema whose genesis also commits to a lock height, before which the
+//! allocations of the asset can't be transferred: a simple lockup of the whole supply, without a
+//! separate contract holding it.
+//!
+//! Each transfer commits to the absolute lock time of its witness transaction, which the
+//! validation scripts require to keep the witness from being mined before the lock height, see
+//! [`TimelockWrapper::min_lock_time`]. The scripts can't check the witness itself, see
+//! [`crate::height`]: wallets must give it the committed lock time, and the `audit` module of
+//! `rgb-schemata-tools` reports transfers mined before the lock height anyway.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalDetails, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::{Amount, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::height::height;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_GENESIS, TIMELOCK_TRANSFER};
+use crate::wrapper::{check_family, global, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_LOCK_HEIGHT, GS_LOCK_TIME, GS_NOMINAL, GS_TERMS, OS_ASSET, TS_TRANSFER,
+};
+
+pub const TIMELOCK_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xf2, 0xeb, 0x94, 0x94, 0x25, 0x51, 0xdf, 0x59, 0x63, 0x0b, 0xf0, 0xbc, 0x9b, 0x22, 0x1c, 0xee,
+    0x8f, 0x44, 0x53, 0x85, 0x5c, 0x1a, 0x6a, 0x64, 0x45, 0x48, 0xf7, 0x00, 0x81, 0xfd, 0xb2, 0x99,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn timelock_schema() -> Schema {
+    let types = standard_types();
+
+    TIMELOCK_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("TimelockAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_LOCK_HEIGHT => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("lockHeight"),
+            },
+            GS_LOCK_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("Bitcoin.LockTime")),
+                name: fname!("lockTime"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_LOCK_HEIGHT => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            validator: Some(NIA_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_LOCK_TIME => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(TIMELOCK_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            }
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn timelock_scripts() -> Scripts { SharedLibs::get().scripts(&[NIA_GENESIS, TIMELOCK_TRANSFER]) }
+
+#[derive(Default)]
+pub struct TimelockAsset;
+
+impl IssuerWrapper for TimelockAsset {
+    type Wrapper<S: ContractStateRead> = TimelockWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(timelock_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(timelock_scripts).clone()
+    }
+}
+
+impl TimelockAsset {
+    /// First revision of the timelocked asset schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "TimelockAsset",
+        schema_id: TIMELOCK_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "NIA variant with a lock height before which its allocations can't be \
+                        transferred, enforced through the lock time committed by transfers.",
+    };
+}
+
+impl IssuerInfo for TimelockAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for TimelockAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct TimelockWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for TimelockWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for TimelockWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<TimelockAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> TimelockWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the timelocked asset schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<TimelockAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the timelocked asset schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the block height from which the allocations of the asset can be transferred.
+    pub fn lock_height(&self) -> u32 { or_panic(self.try_lock_height()) }
+
+    pub fn try_lock_height(&self) -> Result<u32, Error> { height(&self.0, GS_LOCK_HEIGHT) }
+
+    /// Returns the lowest lock time a transfer can commit to, which keeps its witness transaction
+    /// from being mined before the lock height.
+    pub fn min_lock_time(&self) -> u32 { or_panic(self.try_min_lock_time()) }
+
+    pub fn try_min_lock_time(&self) -> Result<u32, Error> {
+        Ok(self.try_lock_height()?.saturating_sub(1))
+    }
+
+    /// Checks whether the allocations of the asset can be transferred in a block at the given
+    /// height.
+    pub fn is_unlocked(&self, height: u32) -> bool { or_panic(self.try_is_unlocked(height)) }
+
+    pub fn try_is_unlocked(&self, height: u32) -> Result<bool, Error> {
+        Ok(height >= self.try_lock_height()?)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for TimelockWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        TimelockWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        TimelockWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        TimelockWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        TimelockWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(all_schemas)]
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    #[cfg(all_schemas)]
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures;
+    #[cfg(all_schemas)]
+    use crate::height::block_height;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = timelock_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(TIMELOCK_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<TimelockAsset>(fixtures::contract_id("timelock"))
+            .unwrap();
+        assert_eq!(wrapper.version(), TimelockAsset::V1);
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.lock_height(), fixtures::LOCK_HEIGHT);
+        assert!(!wrapper.is_unlocked(fixtures::LOCK_HEIGHT - 1));
+        assert!(wrapper.is_unlocked(fixtures::LOCK_HEIGHT));
+        assert_eq!(wrapper.min_lock_time(), fixtures::LOCK_HEIGHT - 1);
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(
+            OutputSeal::new(Outpoint::new(testing::txid(), 1)),
+            fixtures::ISSUED_SUPPLY
+        )]);
+    }
+
+    // Transfer of timelocked assets.
+    //
+    // The holder transfers the whole supply in a mined witness TX, committing to the lowest lock
+    // time the scripts accept. Scripts can't see the witness, so the transfer is accepted even if
+    // it is mined before the lock height, and it is the audit which must report it as premature
+    // until the witness is mined at the lock height.
+
+    /// Timelocked asset allocated to `seal(1)`, locked up to `lock_height`.
+    #[cfg(all_schemas)]
+    fn timelock(issued: u64, allocated: u64, lock_height: u32) -> ContractBuilder {
+        testing::asset::<TimelockAsset>(issued, allocated)
+            .add_global_state("lockHeight", block_height(lock_height))
+            .unwrap()
+    }
+
+    #[cfg(all_schemas)]
+    const LOCK_HEIGHT: u32 = 100;
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn transfer_at_lock_height() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(timelock(1000, 1000, LOCK_HEIGHT));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<TimelockAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.lock_height(), LOCK_HEIGHT);
+        assert!(!wrapper.is_unlocked(LOCK_HEIGHT - 1));
+        assert!(wrapper.is_unlocked(LOCK_HEIGHT));
+
+        let transition = stock
+            .transition_builder(contract_id, "transfer")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(0), 1000u64)
+            .unwrap()
+            .add_global_state("lockTime", block_height(wrapper.min_lock_time()))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+
+        let fascia =
+            testing::anchor(contract_id, transition, &[Outpoint::new(testing::txid(), 1)], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(LOCK_HEIGHT - 1));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::PrematureTransfer {
+            op: opid,
+            height: LOCK_HEIGHT - 1,
+            lock_height: LOCK_HEIGHT,
+        }]);
+        assert_eq!((report.issued, report.burned, report.allocated), (1000, 0, 1000));
+
+        resolver.set_witness_ord(witness_id, testing::mined_at(LOCK_HEIGHT));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+    }
+}
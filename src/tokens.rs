@@ -0,0 +1,212 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token indexes used by the UDA, FUA and UDC contracts of a collection.
+//!
+//! Each UDA and FUA contract issues a single token, identified by the index of its token data,
+//! while a UDC contract issues several tokens with distinct indexes. Nothing prevents an issuer
+//! from issuing several contracts with the same index, which wallets and marketplaces would then
+//! show as the same token. [`TokenRegistry`] tracks the indexes used
+//! by the contracts of a collection, allowing to check a new index before the issuance.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use amplify::Wrapper;
+use rgbstd::persistence::{IndexProvider, StashProvider, StateProvider, Stock, StockError};
+use rgbstd::{ContractId, Identity, TokenIndex};
+
+use crate::info::SchemaFamily;
+#[cfg(feature = "fua")]
+use crate::FractionalUniqueAsset;
+#[cfg(feature = "uda")]
+use crate::UniqueDigitalAsset;
+#[cfg(feature = "collection")]
+use crate::UniqueDigitalCollection;
+
+/// Token index clashing with the collection.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum TokenIndexError {
+    /// token index {index} is already used by contract {contract_id}.
+    Used {
+        index: TokenIndex,
+        contract_id: ContractId,
+    },
+}
+
+/// Token indexes used by the contracts of a collection.
+///
+/// The registry also keeps duplicated indexes, which may already exist among issued contracts,
+/// to report them with [`TokenRegistry::duplicates`].
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct TokenRegistry {
+    tokens: BTreeMap<TokenIndex, BTreeSet<ContractId>>,
+}
+
+impl TokenRegistry {
+    pub fn new() -> Self { Self::default() }
+
+    /// Collects the token indexes of the UDA, FUA and UDC contracts known to the stock,
+    /// restricted to the contracts of an issuer if one is given.
+    pub fn from_stock<S: StashProvider, H: StateProvider, P: IndexProvider>(
+        stock: &Stock<S, H, P>,
+        issuer: Option<&Identity>,
+    ) -> Result<Self, StockError<S, H, P>> {
+        let mut registry = Self::new();
+        for info in stock.contracts()? {
+            if issuer.is_some_and(|issuer| *issuer != info.issuer) {
+                continue;
+            }
+            #[cfg(feature = "uda")]
+            if UniqueDigitalAsset::version_of(info.schema_id).is_some() {
+                let token = stock
+                    .contract_wrapper::<UniqueDigitalAsset>(info.id)?
+                    .token_data();
+                registry.insert(token.index, info.id);
+            }
+            #[cfg(feature = "fua")]
+            if FractionalUniqueAsset::version_of(info.schema_id).is_some() {
+                let token = stock
+                    .contract_wrapper::<FractionalUniqueAsset>(info.id)?
+                    .token_data();
+                registry.insert(token.index, info.id);
+            }
+            #[cfg(feature = "collection")]
+            if UniqueDigitalCollection::version_of(info.schema_id).is_some() {
+                for token in stock
+                    .contract_wrapper::<UniqueDigitalCollection>(info.id)?
+                    .tokens()
+                {
+                    registry.insert(token.index, info.id);
+                }
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Records the token index used by a contract, returning whether the index was free.
+    pub fn insert(&mut self, index: TokenIndex, contract_id: ContractId) -> bool {
+        let contracts = self.tokens.entry(index).or_default();
+        contracts.insert(contract_id);
+        contracts.len() == 1
+    }
+
+    pub fn is_used(&self, index: TokenIndex) -> bool { self.tokens.contains_key(&index) }
+
+    /// Returns the contracts using the token index.
+    pub fn contracts(&self, index: TokenIndex) -> impl Iterator<Item = ContractId> + '_ {
+        self.tokens.get(&index).into_iter().flatten().copied()
+    }
+
+    /// Checks that no contract of the collection uses the token index, before issuing a new
+    /// contract with it.
+    pub fn check(&self, index: TokenIndex) -> Result<(), TokenIndexError> {
+        match self.contracts(index).next() {
+            Some(contract_id) => Err(TokenIndexError::Used { index, contract_id }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the lowest token index not used by the collection.
+    ///
+    /// Returns `None` only if all the indexes are used.
+    pub fn next_free(&self) -> Option<TokenIndex> {
+        let mut next = 0u32;
+        for index in self.tokens.keys() {
+            if index.to_inner() != next {
+                break;
+            }
+            next = next.checked_add(1)?;
+        }
+        Some(TokenIndex::from_inner(next))
+    }
+
+    /// Returns the token indexes used by more than one contract, with the contracts using them.
+    pub fn duplicates(&self) -> impl Iterator<Item = (TokenIndex, &BTreeSet<ContractId>)> {
+        self.tokens
+            .iter()
+            .filter(|(_, contracts)| contracts.len() > 1)
+            .map(|(index, contracts)| (*index, contracts))
+    }
+
+    /// Returns the used token indexes, in ascending order.
+    pub fn indexes(&self) -> impl Iterator<Item = TokenIndex> + '_ { self.tokens.keys().copied() }
+
+    pub fn len(&self) -> usize { self.tokens.len() }
+
+    pub fn is_empty(&self) -> bool { self.tokens.is_empty() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures;
+
+    fn index(no: u32) -> TokenIndex { TokenIndex::from_inner(no) }
+
+    #[test]
+    #[cfg(all(feature = "uda", feature = "fua", feature = "collection"))]
+    fn fixture_collection() {
+        let stock = fixtures::stock();
+        let registry = TokenRegistry::from_stock(&stock, None).unwrap();
+        let index = index(fixtures::TOKEN_INDEX);
+        let contracts = ["uda", "uda-full", "fua", "collection"]
+            .map(fixtures::contract_id)
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let collected = TokenIndex::from_inner(fixtures::COLLECTION[1]);
+        assert_eq!(registry.indexes().collect::<Vec<_>>(), vec![index, collected]);
+        assert_eq!(registry.contracts(index).collect::<BTreeSet<_>>(), contracts);
+        assert_eq!(registry.contracts(collected).collect::<Vec<_>>(), vec![fixtures::contract_id(
+            "collection"
+        )]);
+        assert_eq!(registry.duplicates().collect::<Vec<_>>(), vec![(index, &contracts)]);
+        assert!(matches!(registry.check(index), Err(TokenIndexError::Used { .. })));
+
+        let issued = TokenRegistry::from_stock(&stock, Some(&Identity::default())).unwrap();
+        assert_eq!(issued, registry);
+        let foreign =
+            TokenRegistry::from_stock(&stock, Some(&Identity::from("ssi:other"))).unwrap();
+        assert!(foreign.is_empty());
+    }
+
+    #[test]
+    fn next_free() {
+        let mut registry = TokenRegistry::new();
+        assert_eq!(registry.next_free(), Some(index(0)));
+        let contract_id = fixtures::contract_id("uda");
+        assert!(registry.insert(index(0), contract_id));
+        assert!(registry.insert(index(2), contract_id));
+        assert_eq!(registry.next_free(), Some(index(1)));
+        assert!(registry.check(index(1)).is_ok());
+        assert!(registry.insert(index(1), contract_id));
+        assert_eq!(registry.next_free(), Some(index(3)));
+        assert!(!registry.insert(index(1), fixtures::contract_id("fua")));
+        assert_eq!(
+            registry.check(index(1)),
+            Err(TokenIndexError::Used {
+                index: index(1),
+                contract_id: contract_id.min(fixtures::contract_id("fua"))
+            })
+        );
+        assert_eq!(registry.len(), 3);
+    }
+}
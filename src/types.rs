@@ -0,0 +1,458 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! State, transition and metadata type ids used by the schemata, grouped by kind.
+//!
+//! Each kind has its own submodule, whose constants are also re-exported from the crate root.
+//! Schemata using the same type id give it the same name, so [`name_of`] can tell the name of any
+//! type id of the enabled schemata, e.g. when printing their disassembled scripts. Type ids used
+//! by a single schema are only available with the feature of that schema.
+
+// Type ids are unused when no schema feature is enabled
+#![cfg_attr(not(any_schema), allow(dead_code))]
+
+/// Type id whose name can be looked up with [`name_of`].
+pub trait NamedType: Copy + Eq + 'static {
+    /// Type ids of this kind defined by the enabled schemata, with their names.
+    const NAMES: &'static [(Self, &'static str)];
+}
+
+/// Returns the name the schemata give to a type id, if any of the enabled schemata uses it.
+pub fn name_of<T: NamedType>(ty: T) -> Option<&'static str> {
+    T::NAMES
+        .iter()
+        .find(|(known, _)| *known == ty)
+        .map(|(_, name)| *name)
+}
+
+macro_rules! named_types {
+    (
+        $(#[$doc:meta])*
+        $module:ident: $ty:ident {
+            $( $(#[cfg($cfg:meta)])? $name:ident = $id:literal => $field:literal, )*
+        }
+    ) => {
+        $(#[$doc])*
+        pub mod $module {
+            use rgbstd::$ty;
+
+            $(
+                $(#[cfg($cfg)])?
+                #[doc = concat!("Type id of `", $field, "`.")]
+                pub const $name: $ty = $ty::with($id);
+            )*
+
+            impl super::NamedType for $ty {
+                const NAMES: &'static [(Self, &'static str)] = &[
+                    $( $(#[cfg($cfg)])? ($name, $field), )*
+                ];
+            }
+        }
+    };
+}
+
+named_types! {
+    /// Global state types.
+    global: GlobalStateType {
+        GS_NOMINAL = 2000 => "spec",
+        GS_TERMS = 2001 => "terms",
+        GS_ISSUED_SUPPLY = 2010 => "issuedSupply",
+        #[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+        GS_MAX_SUPPLY = 2011 => "maxSupply",
+        #[cfg(feature = "ifa")]
+        GS_REJECT_LIST_URL = 2012 => "rejectListUrl",
+        #[cfg(any(
+            feature = "ifa",
+            feature = "ria",
+            feature = "sba",
+            feature = "wbtc",
+            feature = "burnable"
+        ))]
+        GS_BURNED_SUPPLY = 2013 => "burnedSupply",
+        #[cfg(feature = "sba")]
+        GS_REVOCATIONS = 2014 => "revocations",
+        #[cfg(any(feature = "stablecoin", feature = "bond"))]
+        GS_REDEEMED_SUPPLY = 2015 => "redeemedSupply",
+        #[cfg(feature = "bond")]
+        GS_FACE_VALUE = 2016 => "faceValue",
+        #[cfg(feature = "bond")]
+        GS_COUPON_RATE = 2017 => "couponRate",
+        #[cfg(feature = "bond")]
+        GS_MATURITY = 2018 => "maturity",
+        #[cfg(feature = "governance")]
+        GS_VOTING_UNIT = 2019 => "votingUnit",
+        #[cfg(feature = "governance")]
+        GS_VOTES = 2020 => "votes",
+        #[cfg(feature = "vesting")]
+        GS_VESTED_SUPPLY = 2021 => "vestedSupply",
+        #[cfg(feature = "vesting")]
+        GS_VESTING_CLIFF = 2022 => "vestingCliff",
+        #[cfg(feature = "vesting")]
+        GS_VESTING_END = 2023 => "vestingEnd",
+        #[cfg(feature = "vesting")]
+        GS_RELEASED_SUPPLY = 2024 => "releasedSupply",
+        #[cfg(feature = "vesting")]
+        GS_LOCKED_SUPPLY = 2025 => "lockedSupply",
+        #[cfg(feature = "timelock")]
+        GS_LOCK_HEIGHT = 2026 => "lockHeight",
+        #[cfg(feature = "freezable")]
+        GS_FROZEN = 2027 => "frozen",
+        #[cfg(feature = "tifa")]
+        GS_INFLATION_THRESHOLD = 2028 => "inflationThreshold",
+        #[cfg(feature = "wbtc")]
+        GS_RESERVES = 2029 => "reserves",
+        #[cfg(feature = "fee")]
+        GS_FEE_RATE = 2030 => "feeRate",
+        #[cfg(feature = "dividend")]
+        GS_DIVIDENDS = 2031 => "dividends",
+        #[cfg(feature = "swap")]
+        GS_OFFERED_ASSET = 2032 => "offeredAsset",
+        #[cfg(feature = "swap")]
+        GS_OFFERED_AMOUNT = 2033 => "offeredAmount",
+        #[cfg(feature = "swap")]
+        GS_COUNTER_ASSET = 2034 => "counterAsset",
+        #[cfg(feature = "swap")]
+        GS_PRICE = 2035 => "price",
+        #[cfg(feature = "swap")]
+        GS_FILLED = 2036 => "filledAmount",
+        #[cfg(feature = "carbon")]
+        GS_RETIRED_SUPPLY = 2037 => "retiredSupply",
+        #[cfg(feature = "carbon")]
+        GS_RETIREMENT_BENEFICIARY = 2038 => "retirementBeneficiary",
+        #[cfg(feature = "carbon")]
+        GS_RETIREMENT_PURPOSE = 2039 => "retirementPurpose",
+        #[cfg(feature = "provenance")]
+        GS_CHECKPOINT_LOCATION = 2040 => "checkpointLocation",
+        #[cfg(feature = "provenance")]
+        GS_CHECKPOINT_TIME = 2041 => "checkpointTime",
+        #[cfg(feature = "provenance")]
+        GS_CHECKPOINT_HANDLER = 2042 => "checkpointHandler",
+        #[cfg(any(feature = "subscription", feature = "option"))]
+        GS_EXPIRY = 2043 => "expiry",
+        #[cfg(feature = "edition")]
+        GS_MAX_EDITIONS = 2044 => "maxEditions",
+        #[cfg(feature = "eifa")]
+        GS_EPOCH_LENGTH = 2045 => "epochLength",
+        #[cfg(feature = "eifa")]
+        GS_EPOCH_CAP = 2046 => "epochCap",
+        #[cfg(feature = "registry")]
+        GS_NAMES = 2047 => "names",
+        #[cfg(feature = "pausable")]
+        GS_PAUSED = 2048 => "paused",
+        #[cfg(feature = "rental")]
+        GS_LEASE_EXPIRY = 2049 => "leaseExpiry",
+        #[cfg(feature = "rental")]
+        GS_RECLAIMED_LEASES = 2050 => "reclaimedLeases",
+        #[cfg(feature = "option")]
+        GS_UNDERLYING_ASSET = 2051 => "underlyingAsset",
+        #[cfg(feature = "option")]
+        GS_STRIKE = 2052 => "strike",
+        #[cfg(feature = "option")]
+        GS_EXERCISED_SUPPLY = 2053 => "exercisedSupply",
+        #[cfg(feature = "option")]
+        GS_LAPSED_SUPPLY = 2054 => "lapsedSupply",
+        #[cfg(feature = "credential")]
+        GS_CLAIMS = 2055 => "claims",
+        #[cfg(feature = "credential")]
+        GS_REVOCATION_REASON = 2056 => "revocationReason",
+        #[cfg(feature = "rebasing")]
+        GS_REBASE_FACTORS = 2057 => "rebaseFactors",
+        #[cfg(any(
+            feature = "bond",
+            feature = "vesting",
+            feature = "timelock",
+            feature = "rental",
+            feature = "option",
+            feature = "eifa"
+        ))]
+        GS_LOCK_TIME = 2058 => "lockTime",
+        #[cfg(feature = "freezable")]
+        GS_SPENT_UTXOS = 2059 => "spentUtxos",
+        #[cfg(any(
+            feature = "uda",
+            feature = "fua",
+            feature = "collection",
+            feature = "sfa",
+            feature = "ticket",
+            feature = "provenance",
+            feature = "subscription",
+            feature = "edition",
+            feature = "rental"
+        ))]
+        GS_TOKENS = 2102 => "tokens",
+        #[cfg(feature = "uda")]
+        GS_ENGRAVINGS = 2103 => "engravings",
+        #[cfg(any(feature = "uda", feature = "fua", feature = "collection"))]
+        GS_ATTACH = 2104 => "attachmentTypes",
+        #[cfg(feature = "ticket")]
+        GS_REDEEMED_TICKETS = 2106 => "redeemedTickets",
+        #[cfg(feature = "cfa")]
+        GS_ART = 3000 => "art",
+        #[cfg(any(feature = "cfa", feature = "carbon"))]
+        GS_NAME = 3001 => "name",
+        #[cfg(any(feature = "cfa", feature = "carbon"))]
+        GS_DETAILS = 3004 => "details",
+        #[cfg(any(feature = "cfa", feature = "carbon"))]
+        GS_PRECISION = 3005 => "precision",
+        #[cfg(any(
+            feature = "pfa",
+            feature = "stablecoin",
+            feature = "pfan",
+            feature = "tifa",
+            feature = "swap",
+            feature = "subscription",
+            feature = "claim",
+            feature = "credential",
+            feature = "rebasing"
+        ))]
+        GS_PUBKEY = 3006 => "pubkey",
+    }
+}
+
+named_types! {
+    /// Owned state (assignment) types.
+    owned: AssignmentType {
+        OS_ASSET = 4000 => "assetOwner",
+        #[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+        OS_INFLATION = 4010 => "inflationAllowance",
+        #[cfg(feature = "ria")]
+        OS_REISSUE = 4011 => "reissueRight",
+        #[cfg(feature = "ifa")]
+        OS_REPLACE = 4012 => "replaceRight",
+        #[cfg(any(feature = "sba", feature = "credential"))]
+        OS_REVOKE = 4013 => "revokeRight",
+        #[cfg(feature = "governance")]
+        OS_VOTE = 4014 => "voteRight",
+        #[cfg(feature = "vesting")]
+        OS_LOCKED = 4015 => "lockedAsset",
+        #[cfg(feature = "freezable")]
+        OS_FREEZE = 4016 => "freezeRight",
+        #[cfg(feature = "tifa")]
+        OS_APPROVAL = 4017 => "inflationApproval",
+        #[cfg(any(feature = "wbtc", feature = "edition"))]
+        OS_MINT = 4018 => "mintRight",
+        #[cfg(feature = "fee")]
+        OS_FEE = 4019 => "feeOwner",
+        #[cfg(feature = "dividend")]
+        OS_DISTRIBUTE = 4020 => "distributeRight",
+        #[cfg(feature = "escrow")]
+        OS_ESCROW = 4021 => "escrowedAsset",
+        #[cfg(feature = "escrow")]
+        OS_RELEASE = 4022 => "releaseRight",
+        #[cfg(feature = "escrow")]
+        OS_REFUND = 4023 => "refundRight",
+        #[cfg(feature = "swap")]
+        OS_ORDER = 4024 => "orderRight",
+        #[cfg(feature = "claim")]
+        OS_CLAIM = 4025 => "issuerClaim",
+        #[cfg(feature = "registry")]
+        OS_REGISTER = 4026 => "registerRight",
+        #[cfg(feature = "pausable")]
+        OS_PAUSE = 4027 => "pauseRight",
+        #[cfg(feature = "uda")]
+        OS_REATTACH = 4028 => "reattachRight",
+        #[cfg(feature = "rental")]
+        OS_LEASE = 4029 => "leaseRight",
+        #[cfg(feature = "rebasing")]
+        OS_REBASE = 4030 => "rebaseRight",
+        #[cfg(feature = "pfa")]
+        OS_ROTATE_KEY = 4031 => "rotateKeyRight",
+        #[cfg(feature = "eifa")]
+        OS_EPOCH_ISSUANCE = 4032 => "epochIssuance",
+        #[cfg(feature = "tifa")]
+        OS_APPROVED_INFLATION = 4033 => "approvedInflation",
+    }
+}
+
+named_types! {
+    /// State transition types.
+    transition: TransitionType {
+        #[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+        TS_INFLATION = 8000 => "inflate",
+        #[cfg(feature = "ria")]
+        TS_REISSUE = 8001 => "reissue",
+        #[cfg(any(
+            feature = "ifa",
+            feature = "ria",
+            feature = "sba",
+            feature = "wbtc",
+            feature = "burnable"
+        ))]
+        TS_BURN = 8010 => "burn",
+        #[cfg(feature = "ifa")]
+        TS_REPLACE = 8011 => "replace",
+        #[cfg(any(feature = "sba", feature = "credential"))]
+        TS_REVOKE = 8012 => "revoke",
+        #[cfg(any(feature = "stablecoin", feature = "bond", feature = "ticket"))]
+        TS_REDEEM = 8013 => "redeem",
+        #[cfg(feature = "governance")]
+        TS_CAST = 8014 => "cast",
+        #[cfg(any(feature = "vesting", feature = "escrow"))]
+        TS_RELEASE = 8015 => "release",
+        #[cfg(feature = "freezable")]
+        TS_FREEZE = 8016 => "freeze",
+        #[cfg(feature = "tifa")]
+        TS_APPROVE = 8017 => "approve",
+        #[cfg(any(feature = "wbtc", feature = "edition"))]
+        TS_MINT = 8018 => "mint",
+        #[cfg(any(feature = "fee", feature = "claim"))]
+        TS_COLLECT = 8019 => "collect",
+        #[cfg(feature = "dividend")]
+        TS_DISTRIBUTE = 8020 => "distribute",
+        #[cfg(feature = "escrow")]
+        TS_ESCROW = 8021 => "escrow",
+        #[cfg(feature = "escrow")]
+        TS_REFUND = 8022 => "refund",
+        #[cfg(feature = "swap")]
+        TS_FILL = 8023 => "fill",
+        #[cfg(feature = "swap")]
+        TS_CANCEL = 8024 => "cancel",
+        #[cfg(feature = "carbon")]
+        TS_RETIRE = 8025 => "retire",
+        #[cfg(feature = "subscription")]
+        TS_RENEW = 8026 => "renew",
+        #[cfg(feature = "pfa")]
+        TS_ROTATE_KEY = 8027 => "rotateKey",
+        #[cfg(feature = "claim")]
+        TS_CLAIM = 8028 => "claim",
+        #[cfg(feature = "registry")]
+        TS_REGISTER = 8029 => "register",
+        #[cfg(feature = "pausable")]
+        TS_TOGGLE_PAUSE = 8030 => "togglePause",
+        #[cfg(feature = "rental")]
+        TS_LEASE = 8031 => "lease",
+        #[cfg(feature = "rental")]
+        TS_RECLAIM = 8032 => "reclaim",
+        #[cfg(feature = "option")]
+        TS_EXERCISE = 8033 => "exercise",
+        #[cfg(feature = "option")]
+        TS_LAPSE = 8034 => "lapse",
+        #[cfg(feature = "credential")]
+        TS_ATTEST = 8035 => "attest",
+        #[cfg(feature = "rebasing")]
+        TS_REBASE = 8036 => "rebase",
+        TS_TRANSFER = 10000 => "transfer",
+        #[cfg(feature = "uda")]
+        TS_ENGRAVE = 10100 => "engrave",
+        #[cfg(feature = "provenance")]
+        TS_CHECKPOINT = 10101 => "checkpoint",
+        #[cfg(feature = "uda")]
+        TS_REATTACH = 10102 => "reattach",
+    }
+}
+
+named_types! {
+    /// Metadata types.
+    meta: MetaType {
+        #[cfg(any(feature = "ifa", feature = "stablecoin", feature = "tifa", feature = "eifa"))]
+        MS_ALLOWED_INFLATION = 1000 => "allowedInflation",
+        #[cfg(any(feature = "pfan", feature = "tifa"))]
+        MS_SIGNER = 1001 => "signer",
+        #[cfg(feature = "wbtc")]
+        MS_RESERVE_PROOF = 1002 => "reserveProof",
+    }
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use rgbstd::contract::IssuerWrapper;
+    use rgbstd::{AssignmentType, GlobalStateType};
+
+    use super::*;
+    use crate::{
+        BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+        DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+        GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+        NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+        ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+        SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+        TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+        VerifiableCredential, VestingAsset, WrappedBtcAsset, GS_ISSUED_SUPPLY,
+        MS_ALLOWED_INFLATION, OS_REPLACE, TS_TRANSFER,
+    };
+
+    #[test]
+    fn lookup() {
+        assert_eq!(name_of(GS_ISSUED_SUPPLY), Some("issuedSupply"));
+        assert_eq!(name_of(OS_REPLACE), Some("replaceRight"));
+        assert_eq!(name_of(TS_TRANSFER), Some("transfer"));
+        assert_eq!(name_of(MS_ALLOWED_INFLATION), Some("allowedInflation"));
+        assert_eq!(name_of(GlobalStateType::with(2105)), None);
+        assert_eq!(name_of(AssignmentType::with(0)), None);
+    }
+
+    fn check_names<I: IssuerWrapper>() {
+        let schema = I::schema();
+        for (ty, details) in &schema.global_types {
+            assert_eq!(name_of(*ty), Some(details.name.as_str()));
+        }
+        for (ty, details) in &schema.owned_types {
+            assert_eq!(name_of(*ty), Some(details.name.as_str()));
+        }
+        for (ty, details) in &schema.transitions {
+            assert_eq!(name_of(*ty), Some(details.name.as_str()));
+        }
+        for (ty, details) in &schema.meta_types {
+            assert_eq!(name_of(*ty), Some(details.name.as_str()));
+        }
+    }
+
+    #[test]
+    fn schema_names() {
+        check_names::<NonInflatableAsset>();
+        check_names::<CollectibleFungibleAsset>();
+        check_names::<UniqueDigitalAsset>();
+        check_names::<FractionalUniqueAsset>();
+        check_names::<UniqueDigitalCollection>();
+        check_names::<PermissionedFungibleAsset>();
+        check_names::<InflatableFungibleAsset>();
+        check_names::<ReissuableAsset>();
+        check_names::<SoulboundAsset>();
+        check_names::<SemiFungibleAsset>();
+        check_names::<StablecoinAsset>();
+        check_names::<BondAsset>();
+        check_names::<GovernanceAsset>();
+        check_names::<TicketAsset>();
+        check_names::<VestingAsset>();
+        check_names::<TimelockAsset>();
+        check_names::<MultiKeyPermissionedAsset>();
+        check_names::<FreezableAsset>();
+        check_names::<ThresholdInflatableAsset>();
+        check_names::<WrappedBtcAsset>();
+        check_names::<TransferFeeAsset>();
+        check_names::<DividendAsset>();
+        check_names::<EscrowAsset>();
+        check_names::<SwapOrder>();
+        check_names::<CarbonCreditAsset>();
+        check_names::<ProvenanceAsset>();
+        check_names::<SubscriptionAsset>();
+        check_names::<LimitedEditionAsset>();
+        check_names::<EpochInflatableAsset>();
+        check_names::<ClaimableAsset>();
+        check_names::<NameRegistry>();
+        check_names::<PausableAsset>();
+        check_names::<RentalAsset>();
+        check_names::<OptionAsset>();
+        check_names::<VerifiableCredential>();
+        check_names::<BurnableAsset>();
+        check_names::<RebasingAsset>();
+    }
+}
@@ -21,100 +21,64 @@
 
 //! Unique digital asset (UDA) schema.
 
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::OnceLock;
+
 use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
-use aluvm::isa::Instr;
-use aluvm::library::{Lib, LibSite};
-use amplify::confinement::Confined;
 use rgbstd::contract::{
-    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, SchemaWrapper,
+    AssignmentsFilter, ContractData, DataAllocation, IssuerWrapper, RightsAllocation, SchemaWrapper,
 };
-use rgbstd::persistence::{ContractStateRead, MemContract};
+use rgbstd::invoice::{Beneficiary, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
 use rgbstd::schema::{
     AssignmentDetails, GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema,
 };
-use rgbstd::stl::{rgb_contract_stl, AssetSpec, ContractTerms, StandardTypes, TokenData};
+use rgbstd::stl::{AssetSpec, Attachment, AttachmentType, ContractTerms, EmbeddedMedia, TokenData};
 use rgbstd::validation::Scripts;
 use rgbstd::vm::opcodes::INSTR_LDG;
-use rgbstd::vm::RgbIsa;
-use rgbstd::{rgbasm, GlobalDetails, OwnedStateSchema, SchemaId, TransitionDetails};
-use strict_types::TypeSystem;
+use rgbstd::{Allocation, GlobalDetails, OwnedStateSchema, SchemaId, TransitionDetails};
+use strict_types::encoding::TypedWrite;
+use strict_types::{StrictEncode, StrictSerialize, StrictType, TypeName, TypeSystem};
 
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::invoice::allocation_invoice;
+use crate::scripts::{SharedLibs, UDA_GENESIS, UDA_REATTACH, UDA_SHARED, UDA_TRANSFER};
+use crate::wrapper::{check_family, global, globals, or_panic};
 use crate::{
-    ERRNO_NON_EQUAL_IN_OUT, ERRNO_NON_FRACTIONAL, GS_ATTACH, GS_NOMINAL, GS_TERMS, GS_TOKENS,
-    OS_ASSET, TS_TRANSFER,
+    standard_types, ContractWrapper, Error, WrongSchema, GS_ATTACH, GS_ENGRAVINGS, GS_NOMINAL,
+    GS_TERMS, GS_TOKENS, OS_ASSET, OS_REATTACH, TS_ENGRAVE, TS_REATTACH, TS_TRANSFER,
 };
 
+/// Id of the latest revision of the schema, used for new issuances.
+///
+/// Before the schema gained new revisions it was the id of the first revision, which is now
+/// [`UDA_V1_SCHEMA_ID`].
 pub const UDA_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x17, 0xec, 0xa3, 0xde, 0x8b, 0x2a, 0x45, 0x43, 0x42, 0x7d, 0xbd, 0x6a, 0x40, 0x21, 0xb5, 0xaf,
+    0x4b, 0x4c, 0xcf, 0x4e, 0xee, 0xb2, 0x96, 0xbc, 0xa8, 0x1d, 0xfe, 0x18, 0xf8, 0x32, 0x09, 0x01,
+]);
+
+/// Id of the first revision of the schema, which has no engravings.
+pub const UDA_V1_SCHEMA_ID: SchemaId = SchemaId::from_array([
     0xff, 0xaa, 0xe3, 0xca, 0x67, 0xf7, 0x19, 0x31, 0x3c, 0xe3, 0x49, 0x5b, 0xe4, 0x9a, 0x17, 0x9b,
     0x66, 0x85, 0xc0, 0x4f, 0x1e, 0x58, 0x29, 0x37, 0x98, 0x28, 0xce, 0x7f, 0xe9, 0x94, 0xce, 0xd1,
 ]);
 
-pub const FN_GENESIS_OFFSET: u16 = 4 + 4 + 3;
-pub const FN_TRANSFER_OFFSET: u16 = 0;
-pub const FN_SHARED_OFFSET: u16 = FN_GENESIS_OFFSET + 4 + 4 + 4;
-
-fn uda_standard_types() -> StandardTypes { StandardTypes::with(rgb_contract_stl()) }
-
-fn uda_lib() -> Lib {
-    let code = rgbasm! {
-        // SUBROUTINE 2: Transfer validation
-        // Put 0 to a16[0]
-        put     a16[0],0;
-        // Read previous state into s16[0]
-        ldp     OS_ASSET,a16[0],s16[0];
-        // jump into SUBROUTINE 3 to reuse the code
-        jmp     FN_SHARED_OFFSET;
-
-        // SUBROUTINE 1: Genesis validation
-        // Set offset to read state from strings
-        put     a16[0],0x00;
-        // Set which state index to read
-        put     a8[1],0x00;
-        // Read global state into s16[0]
-        ldg     GS_TOKENS,a8[1],s16[0];
-
-        // SUBROUTINE 3: Shared code
-        // Set errno
-        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
-        // Extract 128 bits from the beginning of s16[0] into a32[0]
-        extr    s16[0],a32[0],a16[0];
-        // Set which state index to read
-        put     a16[1],0x00;
-        // Read owned state into s16[1]
-        lds     OS_ASSET,a16[1],s16[1];
-        // Extract 128 bits from the beginning of s16[1] into a32[1]
-        extr    s16[1],a32[1],a16[0];
-        // Check that token indexes match
-        eq.n    a32[0],a32[1];
-        // Fail if they don't
-        test;
-
-        // Set errno
-        put     a8[0],ERRNO_NON_FRACTIONAL;
-        // Put offset for the data into a16[2]
-        put     a16[2],4;
-        // Extract 128 bits starting from the fifth byte of s16[1] into a64[0]
-        extr    s16[1],a64[0],a16[2];
-        // Check that owned fraction == 1
-        put     a64[1],1;
-        eq.n    a64[0],a64[1];
-        // Fail if not
-        test;
-    };
-    Lib::assemble::<Instr<RgbIsa<MemContract>>>(&code).expect("wrong unique digital asset script")
-}
+/// Id of the second revision of the schema, whose attachments can't be replaced.
+pub const UDA_V2_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x71, 0xf4, 0x38, 0x6f, 0x4c, 0x8a, 0xc7, 0x74, 0x16, 0x8e, 0x8f, 0x3e, 0xfc, 0x97, 0x07, 0x30,
+    0xb9, 0xdd, 0x67, 0x47, 0x3c, 0x41, 0x4a, 0x3f, 0x24, 0x88, 0xa7, 0xf6, 0xca, 0x8a, 0xbc, 0x16,
+]);
 
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
 fn uda_schema() -> Schema {
-    let types = uda_standard_types();
+    let types = standard_types();
 
-    let alu_lib = uda_lib();
-    let alu_id = alu_lib.id();
-    let code = alu_lib.code.as_ref();
-    assert_eq!(code[FN_GENESIS_OFFSET as usize], INSTR_PUTA);
-    assert_eq!(code[FN_GENESIS_OFFSET as usize + 8], INSTR_LDG);
-    assert_eq!(code[FN_TRANSFER_OFFSET as usize], INSTR_PUTA);
-    assert_eq!(code[FN_SHARED_OFFSET as usize], INSTR_PUTA);
-    assert_eq!(code[FN_SHARED_OFFSET as usize + 4], INSTR_EXTR);
+    UDA_GENESIS.verify(&[(0, INSTR_PUTA), (8, INSTR_LDG)]);
+    UDA_TRANSFER.verify(&[(0, INSTR_PUTA)]);
+    UDA_SHARED.verify(&[(0, INSTR_PUTA), (4, INSTR_EXTR)]);
+    UDA_REATTACH.verify(&[(0, INSTR_PUTA)]);
 
     Schema {
         ffv: zero!(),
@@ -130,19 +94,28 @@ fn uda_schema() -> Schema {
                 name: fname!("terms"),
             },
             GS_TOKENS => GlobalDetails {
-                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.TokenData")),
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.TokenData")),
                 name: fname!("tokens"),
             },
             GS_ATTACH => GlobalDetails {
                 global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AttachmentType")),
                 name: fname!("attachmentTypes"),
             },
+            GS_ENGRAVINGS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Attachment")),
+                name: fname!("engravings"),
+            },
         },
         owned_types: tiny_bmap! {
             OS_ASSET => AssignmentDetails {
                 owned_state_schema: OwnedStateSchema::Structured(types.get("RGBContract.Allocation")),
                 name: fname!("assetOwner"),
                 default_transition: TS_TRANSFER,
+            },
+            OS_REATTACH => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("reattachRight"),
+                default_transition: TS_REATTACH,
             }
         },
         genesis: GenesisSchema {
@@ -155,8 +128,9 @@ fn uda_schema() -> Schema {
             },
             assignments: tiny_bmap! {
                 OS_ASSET => Occurrences::Once,
+                OS_REATTACH => Occurrences::NoneOrOnce,
             },
-            validator: Some(LibSite::with(FN_GENESIS_OFFSET, alu_id)),
+            validator: Some(UDA_GENESIS.lib_site()),
         },
         transitions: tiny_bmap! {
             TS_TRANSFER => TransitionDetails {
@@ -169,15 +143,52 @@ fn uda_schema() -> Schema {
                     assignments: tiny_bmap! {
                         OS_ASSET => Occurrences::Once
                     },
-                    validator: Some(LibSite::with(FN_TRANSFER_OFFSET, alu_id)),
+                    validator: Some(UDA_TRANSFER.lib_site()),
                 },
                 name: fname!("transfer"),
+            },
+            TS_ENGRAVE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_ENGRAVINGS => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::Once
+                    },
+                    // Engraving leaves the token untouched, so it is validated as a transfer
+                    validator: Some(UDA_TRANSFER.lib_site()),
+                },
+                name: fname!("engrave"),
+            },
+            TS_REATTACH => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_TOKENS => Occurrences::Once
+                    },
+                    inputs: tiny_bmap! {
+                        OS_REATTACH => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_REATTACH => Occurrences::NoneOrOnce
+                    },
+                    validator: Some(UDA_REATTACH.lib_site()),
+                },
+                name: fname!("reattach"),
             }
         },
         default_assignment: Some(OS_ASSET),
     }
 }
 
+fn uda_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[UDA_GENESIS, UDA_TRANSFER, UDA_REATTACH])
+}
+
 #[derive(Default)]
 pub struct UniqueDigitalAsset;
 
@@ -187,64 +198,252 @@ pub struct UdaWrapper<S: ContractStateRead>(ContractData<S>);
 impl IssuerWrapper for UniqueDigitalAsset {
     type Wrapper<S: ContractStateRead> = UdaWrapper<S>;
 
-    fn schema() -> Schema { uda_schema() }
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(uda_schema).clone()
+    }
 
-    fn types() -> TypeSystem { uda_standard_types().type_system(uda_schema()) }
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
 
     fn scripts() -> Scripts {
-        let lib = uda_lib();
-        Confined::from_checked(bmap! { lib.id() => lib })
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(uda_scripts).clone()
     }
 }
 
+impl UniqueDigitalAsset {
+    /// First revision of the UDA schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "UniqueDigitalAsset",
+        schema_id: UDA_V1_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Single non-fungible token issued at genesis with its token data and \
+                        attachments, and a transfer transition preserving the token.",
+    };
+
+    /// Revision adding engravings to the UDA schema.
+    pub const V2: SchemaInfo = SchemaInfo {
+        name: "UniqueDigitalAsset",
+        schema_id: UDA_V2_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(2, 0, 0),
+        release_notes: "Engrave transition, allowing the owner of the token to append an \
+                        engraving to the global state while keeping the token.",
+    };
+
+    /// Revision adding attachment replacement to the UDA schema.
+    pub const V3: SchemaInfo = SchemaInfo {
+        name: "UniqueDigitalAsset",
+        schema_id: UDA_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(3, 0, 0),
+        release_notes: "Reattach transition, allowing the holder of the reattach right to publish \
+                        new token data replacing the media and attachments of the token.",
+    };
+
+    /// Global state value declaring the attachment type `ty`, as stored by the `attachmentTypes`
+    /// global state.
+    pub fn attachment_type(ty: AttachmentType) -> impl StrictSerialize { AttachmentTypeState(ty) }
+}
+
+/// Attachment type of the `attachmentTypes` global state, encoded as the attachment type it wraps,
+/// which the standard library doesn't allow to serialize on its own.
+struct AttachmentTypeState(AttachmentType);
+
+impl StrictType for AttachmentTypeState {
+    const STRICT_LIB_NAME: &'static str = AttachmentType::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { AttachmentType::strict_name() }
+}
+
+impl StrictEncode for AttachmentTypeState {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.0.strict_encode(writer)
+    }
+}
+
+impl StrictSerialize for AttachmentTypeState {}
+
+impl IssuerInfo for UniqueDigitalAsset {
+    const INFO: SchemaInfo = Self::V3;
+}
+
+impl SchemaFamily for UniqueDigitalAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1, Self::V2, Self::V3];
+}
+
 impl<S: ContractStateRead> SchemaWrapper<S> for UdaWrapper<S> {
-    fn with(data: ContractData<S>) -> Self {
-        if data.schema.schema_id() != UDA_SCHEMA_ID {
-            panic!("the provided schema is not UDA");
-        }
-        Self(data)
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for UdaWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<UniqueDigitalAsset, _>(&self.0)?)
     }
 }
 
 impl<S: ContractStateRead> UdaWrapper<S> {
-    pub fn spec(&self) -> AssetSpec {
-        let strict_val = &self
-            .0
-            .global("spec")
-            .next()
-            .expect("UDA requires global state `spec` to have at least one item");
-        AssetSpec::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, failing if it doesn't belong to the UDA schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<UniqueDigitalAsset, _>(&data)?;
+        Ok(Self(data))
     }
 
-    pub fn contract_terms(&self) -> ContractTerms {
-        let strict_val = &self
-            .0
-            .global("terms")
-            .next()
-            .expect("UDA requires global state `terms` to have at least one item");
-        ContractTerms::from_strict_val_unchecked(strict_val)
+    /// Wraps the contract data, panicking if it doesn't belong to the UDA schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the current token data, that is the one published by the latest reattachment, if
+    /// any.
+    pub fn token_data(&self) -> TokenData { or_panic(self.try_token_data()) }
+
+    pub fn try_token_data(&self) -> Result<TokenData, Error> { global(&self.0, GS_TOKENS) }
+
+    /// Returns the attachment type declared by the contract, naming the attachments of the token
+    /// with its id.
+    pub fn attachment_type(&self) -> Option<AttachmentType> { or_panic(self.try_attachment_type()) }
+
+    pub fn try_attachment_type(&self) -> Result<Option<AttachmentType>, Error> {
+        globals(&self.0, GS_ATTACH)?.next().transpose()
     }
 
-    pub fn token_data(&self) -> TokenData {
-        let strict_val = &self
-            .0
-            .global("tokens")
-            .next()
-            .expect("UDA requires global state `tokens` to have at least one item");
-        TokenData::from_strict_val_unchecked(strict_val)
+    /// Returns the preview of the token embedded in its current token data.
+    pub fn preview(&self) -> Option<EmbeddedMedia> { or_panic(self.try_preview()) }
+
+    pub fn try_preview(&self) -> Result<Option<EmbeddedMedia>, Error> {
+        Ok(self.try_token_data()?.preview)
+    }
+
+    /// Returns the reference to the full media of the token, committing to it by its digest.
+    pub fn media(&self) -> Option<Attachment> { or_panic(self.try_media()) }
+
+    pub fn try_media(&self) -> Result<Option<Attachment>, Error> {
+        Ok(self.try_token_data()?.media)
+    }
+
+    /// Returns the attachments of the token by their attachment type id.
+    pub fn attachments(&self) -> BTreeMap<u8, Attachment> { or_panic(self.try_attachments()) }
+
+    pub fn try_attachments(&self) -> Result<BTreeMap<u8, Attachment>, Error> {
+        Ok(self.try_token_data()?.attachments.release())
+    }
+
+    /// Returns the attachment of the token of the type with the given id, if any.
+    pub fn attachment(&self, id: u8) -> Option<Attachment> { or_panic(self.try_attachment(id)) }
+
+    pub fn try_attachment(&self, id: u8) -> Result<Option<Attachment>, Error> {
+        Ok(self.try_attachments()?.remove(&id))
+    }
+
+    /// Returns all the token data published for the token, from the one committed in the genesis
+    /// to the current one, each of them carrying the media and attachments of the token at the
+    /// time.
+    pub fn token_data_history(&self) -> Vec<TokenData> { or_panic(self.try_token_data_history()) }
+
+    pub fn try_token_data_history(&self) -> Result<Vec<TokenData>, Error> {
+        // Global state is iterated from the latest entry in consensus order
+        let mut history = globals(&self.0, GS_TOKENS)?.collect::<Result<Vec<_>, _>>()?;
+        history.reverse();
+        Ok(history)
+    }
+
+    /// Returns the chain of engravings of the token, from the oldest to the latest one, each of
+    /// them committing to the engraved media by its digest.
+    ///
+    /// Contracts of the first revision of the schema can't be engraved and always return an empty
+    /// chain.
+    pub fn engravings(&self) -> Vec<Attachment> { or_panic(self.try_engravings()) }
+
+    pub fn try_engravings(&self) -> Result<Vec<Attachment>, Error> {
+        if !self.0.schema.global_types.contains_key(&GS_ENGRAVINGS) {
+            return Ok(vec![]);
+        }
+        // Global state is iterated from the latest entry in consensus order
+        let mut engravings = globals(&self.0, GS_ENGRAVINGS)?.collect::<Result<Vec<_>, _>>()?;
+        engravings.reverse();
+        Ok(engravings)
     }
 
     pub fn allocations<'c>(
         &'c self,
         filter: impl AssignmentsFilter + 'c,
     ) -> impl Iterator<Item = DataAllocation> + 'c {
-        self.0.data_raw(OS_ASSET, filter).unwrap()
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = DataAllocation> + 'c, Error> {
+        Ok(self.0.data_raw(OS_ASSET, filter)?)
+    }
+
+    /// Returns the rights to replace the attachments of the token, which contracts of the first two
+    /// revisions of the schema lack.
+    pub fn reattach_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_reattach_rights(filter))
+    }
+
+    pub fn try_reattach_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_REATTACH, filter)?)
+    }
+
+    /// Builds an invoice for receiving the token of the contract.
+    pub fn invoice(&self, beneficiary: impl Into<XChainNet<Beneficiary>>) -> RgbInvoice {
+        or_panic(self.try_invoice(beneficiary))
+    }
+
+    pub fn try_invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+    ) -> Result<RgbInvoice, Error> {
+        let allocation = Allocation::with(self.try_token_data()?.index, 1);
+        allocation_invoice(&self.0, beneficiary, allocation)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
+    use amplify::confinement::{Confined, SmallBlob};
+    use amplify::{Bytes32, Wrapper};
+    use rgbstd::containers::{BuilderSeal, ConsignmentExt};
+    use rgbstd::contract::*;
+    use rgbstd::invoice::Precision;
+    use rgbstd::persistence::Stock;
+    use rgbstd::stl::*;
+    use rgbstd::txout::BlindSeal;
+    use rgbstd::*;
+    use schemata_tools::testing;
+    use strict_types::StrictSerialize;
+
     use super::*;
+    use crate::fixtures::{self, fixture_seal};
 
     #[test]
     fn schema_id() {
@@ -252,4 +451,332 @@ mod test {
         eprintln!("{:#04x?}", schema_id.to_byte_array());
         assert_eq!(UDA_SCHEMA_ID, schema_id);
     }
+
+    #[test]
+    fn deterministic_contract_id() {
+        let created_at = 1713261744;
+        let terms = ContractTerms {
+            text: RicardianContract::default(),
+            media: None,
+        };
+        let spec = AssetSpec {
+            ticker: Ticker::from("TICKER"),
+            name: Name::from("NAME"),
+            details: None,
+            precision: Precision::try_from(0).unwrap(),
+        };
+        let index = TokenIndex::from_inner(2);
+        let token_data = TokenData {
+            index,
+            preview: Some(EmbeddedMedia {
+                ty: MediaType::with("image/*"),
+                data: SmallBlob::try_from_iter(vec![0, 0]).unwrap(),
+            }),
+            ..Default::default()
+        };
+        let seal: BlindSeal<Txid> = testing::BLINDER.genesis_seal(
+            Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19")
+                .unwrap(),
+            0,
+        );
+
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            UniqueDigitalAsset::schema(),
+            UniqueDigitalAsset::types(),
+            UniqueDigitalAsset::scripts(),
+            ChainNet::BitcoinTestnet4,
+        )
+        .add_global_state("spec", spec)
+        .unwrap()
+        .add_global_state("terms", terms)
+        .unwrap()
+        .add_global_state("tokens", token_data)
+        .unwrap()
+        .add_data("assetOwner", BuilderSeal::from(seal), Allocation::with(index, 1))
+        .unwrap();
+
+        let contract = builder.issue_contract_raw(created_at).unwrap();
+
+        assert_eq!(
+            contract.contract_id().to_string(),
+            s!("rgb:Fy7MeZS8-dPExuQY-YatuPfg-1sO~XxB-QMPw~b_-6wBSAY0")
+        );
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        for name in ["uda", "uda-full"] {
+            let wrapper = stock
+                .contract_wrapper::<UniqueDigitalAsset>(fixtures::contract_id(name))
+                .unwrap();
+            assert_eq!(wrapper.spec(), AssetSpec::new("TEST", "Test uda", Precision::Indivisible));
+            assert_eq!(wrapper.contract_terms(), testing::terms());
+            assert_eq!(wrapper.token_data(), fixtures::token_data(name == "uda-full"));
+            assert_eq!(wrapper.preview(), fixtures::token_data(name == "uda-full").preview);
+            assert_eq!(wrapper.media(), None);
+            assert_eq!(wrapper.attachments(), bmap! {});
+            assert_eq!(wrapper.attachment_type(), None);
+            assert_eq!(wrapper.engravings(), vec![]);
+            assert_eq!(wrapper.version(), UniqueDigitalAsset::V3);
+            assert_eq!(wrapper.token_data_history(), vec![wrapper.token_data()]);
+            assert_eq!(wrapper.reattach_rights(&FilterIncludeAll).count(), 0);
+            let allocations = wrapper
+                .allocations(&FilterIncludeAll)
+                .map(|a| (a.seal, a.state))
+                .collect::<Vec<_>>();
+            let allocation = Allocation::with(TokenIndex::from_inner(fixtures::TOKEN_INDEX), 1)
+                .to_strict_serialized::<{ u16::MAX as usize }>()
+                .unwrap();
+            let state = RevealedData::new(SmallBlob::from_checked(allocation.release()));
+            assert_eq!(allocations, vec![(fixture_seal(1), state)]);
+        }
+    }
+
+    #[test]
+    fn attachments() {
+        let media = Attachment {
+            ty: MediaType::with("video/mp4"),
+            digest: [0x11; 32].into(),
+        };
+        let certificate = Attachment {
+            ty: MediaType::with("application/pdf"),
+            digest: [0x22; 32].into(),
+        };
+        let index = TokenIndex::from_inner(fixtures::TOKEN_INDEX);
+        let token_data = TokenData {
+            index,
+            media: Some(media.clone()),
+            attachments: Confined::try_from(bmap! { 1 => certificate.clone() }).unwrap(),
+            ..Default::default()
+        };
+        let (stock, contract_id) = testing::stock_with(
+            testing::builder::<UniqueDigitalAsset>()
+                .add_global_state("spec", testing::spec())
+                .unwrap()
+                .add_global_state("terms", testing::terms())
+                .unwrap()
+                .add_global_state("tokens", token_data)
+                .unwrap()
+                .add_global_state(
+                    "attachmentTypes",
+                    UniqueDigitalAsset::attachment_type(AttachmentType::with(1, "certificate")),
+                )
+                .unwrap()
+                .add_data("assetOwner", testing::seal(1), Allocation::with(index, 1))
+                .unwrap(),
+        );
+        let wrapper = stock
+            .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.attachment_type(), Some(AttachmentType::with(1, "certificate")));
+        assert_eq!(wrapper.preview(), None);
+        assert_eq!(wrapper.media(), Some(media));
+        assert_eq!(wrapper.attachments(), bmap! { 1 => certificate.clone() });
+        assert_eq!(wrapper.attachment(1), Some(certificate));
+        assert_eq!(wrapper.attachment(2), None);
+    }
+
+    // Engravings of a unique digital asset.
+    //
+    // The owner of the token engraves it twice, each engrave transition spending the token and
+    // assigning it back to the owner in its own mined witness TX. The `UdaWrapper` must then report
+    // both engravings in the order they were made.
+
+    fn uda(token_index: u32, owned_index: u32, fraction: u64) -> ContractBuilder {
+        testing::builder::<UniqueDigitalAsset>()
+            .add_global_state("spec", AssetSpec::new("TEST", "Test uda", Precision::Indivisible))
+            .unwrap()
+            .add_global_state("terms", testing::terms())
+            .unwrap()
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(token_index),
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::seal(1),
+                Allocation::with(TokenIndex::from_inner(owned_index), fraction),
+            )
+            .unwrap()
+    }
+
+    fn engraving(no: u8) -> Attachment {
+        Attachment {
+            ty: MediaType::with("image/png"),
+            digest: Bytes32::from_byte_array([no; 32]),
+        }
+    }
+
+    /// Engraves the token owned by `opout` on `prevout` with engraving `no`, returning the new
+    /// owner opout and outpoint.
+    fn engrave(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        (opout, prevout): (Opout, Outpoint),
+        height: u32,
+        no: u8,
+    ) -> (Opout, Outpoint) {
+        let RevealedState::Structured(data) = testing::allocation(2, 1) else {
+            unreachable!()
+        };
+        let transition = stock
+            .transition_builder(contract_id, "engrave")
+            .unwrap()
+            .add_input(opout, AllocatedState::Data(data))
+            .unwrap()
+            .add_global_state("engravings", engraving(no))
+            .unwrap()
+            .add_data(
+                "assetOwner",
+                testing::graph_seal(1),
+                Allocation::with(TokenIndex::from_inner(2), 1),
+            )
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        (Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness_id, 1))
+    }
+
+    #[test]
+    fn engravings_chain() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(uda(2, 2, 1));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+        let wrapper = stock
+            .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.version(), UniqueDigitalAsset::V3);
+        assert_eq!(wrapper.engravings(), vec![]);
+
+        let owner = (Opout::new(genesis_id, OS_ASSET, 0), Outpoint::new(testing::txid(), 1));
+        let owner = engrave(&mut stock, &mut resolver, contract_id, owner, 100, 1);
+        let owner = engrave(&mut stock, &mut resolver, contract_id, owner, 101, 2);
+        testing::validate_history::<UniqueDigitalAsset>(&stock, &resolver, contract_id, &[owner
+            .0
+            .op])
+        .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.engravings(), vec![engraving(1), engraving(2)]);
+        let seals = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(seals.contains(&OutputSeal::new(owner.1)), "{seals:?}");
+    }
+
+    // Replacement of the attachments of a unique digital asset.
+    //
+    // The issuer spends the reattach right in a mined witness TX to replace the media of the token,
+    // then again in a later one to add an attachment, passing the right along. The `UdaWrapper`
+    // must then report the latest token data, together with the whole history of the token data.
+
+    fn media(no: u8) -> Attachment {
+        Attachment {
+            ty: MediaType::with("image/png"),
+            digest: Bytes32::from_byte_array([no; 32]),
+        }
+    }
+
+    fn token_data(attachments: &[(u8, Attachment)]) -> TokenData {
+        TokenData {
+            index: TokenIndex::from_inner(2),
+            media: Some(media(1)),
+            attachments: Confined::from_iter_checked(attachments.iter().cloned()),
+            ..Default::default()
+        }
+    }
+
+    /// Publishes `data` spending the reattach right at `right`, returning the new reattach right.
+    fn reattach(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        (opout, prevout): (Opout, Outpoint),
+        height: u32,
+        data: TokenData,
+    ) -> (Opout, Outpoint) {
+        let transition = stock
+            .transition_builder(contract_id, "reattach")
+            .unwrap()
+            .add_input(opout, AllocatedState::Void)
+            .unwrap()
+            .add_global_state("tokens", data)
+            .unwrap()
+            .add_rights("reattachRight", testing::graph_seal(1))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 1);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+
+        (Opout::new(opid, OS_REATTACH, 0), Outpoint::new(witness_id, 1))
+    }
+
+    #[test]
+    fn reattachments_history() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(
+            uda(2, 2, 1)
+                .add_rights("reattachRight", testing::seal(2))
+                .unwrap(),
+        );
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+        let wrapper = stock
+            .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+            .unwrap();
+        let genesis_data = wrapper.token_data();
+        assert_eq!(wrapper.token_data_history(), vec![genesis_data.clone()]);
+
+        let right = (Opout::new(genesis_id, OS_REATTACH, 0), Outpoint::new(testing::txid(), 2));
+        let right = reattach(&mut stock, &mut resolver, contract_id, right, 100, token_data(&[]));
+        let right = reattach(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            right,
+            101,
+            token_data(&[(0, media(2))]),
+        );
+
+        let wrapper = stock
+            .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.token_data(), token_data(&[(0, media(2))]));
+        assert_eq!(wrapper.token_data_history(), vec![
+            genesis_data,
+            token_data(&[]),
+            token_data(&[(0, media(2))])
+        ]);
+        let rights = wrapper
+            .reattach_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert!(rights.contains(&OutputSeal::new(right.1)), "{rights:?}");
+    }
 }
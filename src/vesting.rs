@@ -0,0 +1,653 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Vesting asset schema.
+//!
+//! A fixed supply asset whose genesis locks part of the supply, the vested supply, in a single
+//! locked allocation held by the founders, and commits to the [`VestingSchedule`] unlocking it:
+//! nothing is unlocked before the cliff height, and the vested supply is then unlocked linearly up
+//! to the end height. Locked units can't be transferred; the founders turn them into the asset
+//! with the release transition, which assigns the released supply to new owners, keeps the rest
+//! locked and records both amounts in the global state.
+//!
+//! The validation scripts check that the cliff precedes the end and that releases move units out
+//! of the locked allocation without creating or destroying any. Each release commits to the lock
+//! time of its witness transaction, and must leave locked at least [`VestingSchedule::locked`] at
+//! the first height the witness can be mined at, i.e. the lock time plus one. The lock time of the
+//! witness itself is not checked, see [`crate::height`]: releases leaving less than the schedule
+//! requires at the height they are mined are still valid, and the `audit` module of
+//! `rgb-schemata-tools` reports them.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::height::height;
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_TRANSFER, VESTING_GENESIS, VESTING_RELEASE};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_ISSUED_SUPPLY,
+    GS_LOCKED_SUPPLY, GS_LOCK_TIME, GS_NOMINAL, GS_RELEASED_SUPPLY, GS_TERMS, GS_VESTED_SUPPLY,
+    GS_VESTING_CLIFF, GS_VESTING_END, OS_ASSET, OS_LOCKED, TS_RELEASE, TS_TRANSFER,
+};
+
+pub const VESTING_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0xd4, 0x67, 0xfc, 0xba, 0x70, 0x08, 0xc1, 0x4e, 0x4c, 0x98, 0x0a, 0x40, 0xea, 0xad, 0x6b, 0xf3,
+    0xf4, 0x0e, 0x47, 0xdd, 0xfe, 0x3b, 0xc4, 0xcb, 0x97, 0xd3, 0x10, 0x7b, 0x0c, 0xa5, 0x53, 0x53,
+]);
+
+/// Schedule unlocking the vested supply of a vesting asset.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct VestingSchedule {
+    /// Supply locked by the genesis.
+    pub vested: Amount,
+    /// Height of the first block in which units start being unlocked.
+    pub cliff: u32,
+    /// Height of the first block in which the whole vested supply is unlocked.
+    pub end: u32,
+}
+
+impl VestingSchedule {
+    /// Returns the part of the vested supply unlocked in a block at the given height.
+    pub fn unlocked(&self, height: u32) -> Amount {
+        if height < self.cliff {
+            return Amount::ZERO;
+        }
+        if height >= self.end {
+            return self.vested;
+        }
+        let elapsed = (height - self.cliff) as u128;
+        let duration = (self.end - self.cliff) as u128;
+        Amount::from((self.vested.value() as u128 * elapsed / duration) as u64)
+    }
+
+    /// Returns the part of the vested supply still locked in a block at the given height.
+    pub fn locked(&self, height: u32) -> Amount {
+        Amount::from(self.vested.value() - self.unlocked(height).value())
+    }
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn vesting_schema() -> Schema {
+    let types = standard_types();
+
+    VESTING_GENESIS.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    VESTING_RELEASE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("VestingAsset"),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_VESTED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.Amount")),
+                name: fname!("vestedSupply"),
+            },
+            GS_VESTING_CLIFF => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("vestingCliff"),
+            },
+            GS_VESTING_END => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("vestingEnd"),
+            },
+            GS_RELEASED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("releasedSupply"),
+            },
+            GS_LOCKED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("lockedSupply"),
+            },
+            GS_LOCK_TIME => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("Bitcoin.LockTime")),
+                name: fname!("lockTime"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_LOCKED => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("lockedAsset"),
+                default_transition: TS_RELEASE,
+            },
+        },
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_VESTED_SUPPLY => Occurrences::Once,
+                GS_VESTING_CLIFF => Occurrences::Once,
+                GS_VESTING_END => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_LOCKED => Occurrences::Once,
+            },
+            validator: Some(VESTING_GENESIS.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_RELEASE => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_RELEASED_SUPPLY => Occurrences::Once,
+                        GS_LOCKED_SUPPLY => Occurrences::Once,
+                        GS_LOCK_TIME => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_LOCKED => Occurrences::Once,
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_LOCKED => Occurrences::NoneOrOnce,
+                    },
+                    validator: Some(VESTING_RELEASE.lib_site())
+                },
+                name: fname!("release"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn vesting_scripts() -> Scripts {
+    SharedLibs::get().scripts(&[VESTING_GENESIS, NIA_TRANSFER, VESTING_RELEASE])
+}
+
+#[derive(Default)]
+pub struct VestingAsset;
+
+impl IssuerWrapper for VestingAsset {
+    type Wrapper<S: ContractStateRead> = VestingWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(vesting_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(vesting_scripts).clone()
+    }
+}
+
+impl VestingAsset {
+    /// First revision of the vesting schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "VestingAsset",
+        schema_id: VESTING_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(1, 0, 0),
+        release_notes: "Fixed supply asset locking a vested supply under a cliff and linear \
+                        vesting schedule, with a release transition unlocking it.",
+    };
+}
+
+impl IssuerInfo for VestingAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for VestingAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct VestingWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for VestingWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for VestingWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<VestingAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> VestingWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the vesting schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<VestingAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the vesting schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        global(&self.0, GS_ISSUED_SUPPLY)
+    }
+
+    /// Returns the vesting schedule committed by the genesis.
+    pub fn schedule(&self) -> VestingSchedule { or_panic(self.try_schedule()) }
+
+    pub fn try_schedule(&self) -> Result<VestingSchedule, Error> {
+        Ok(VestingSchedule {
+            vested: global(&self.0, GS_VESTED_SUPPLY)?,
+            cliff: height(&self.0, GS_VESTING_CLIFF)?,
+            end: height(&self.0, GS_VESTING_END)?,
+        })
+    }
+
+    /// Returns the part of the vested supply unlocked in a block at the given height, whether
+    /// released already or not.
+    pub fn unlocked_supply(&self, height: u32) -> Amount {
+        or_panic(self.try_unlocked_supply(height))
+    }
+
+    pub fn try_unlocked_supply(&self, height: u32) -> Result<Amount, Error> {
+        Ok(self.try_schedule()?.unlocked(height))
+    }
+
+    /// Returns the supply released over the contract history.
+    pub fn released_supply(&self) -> Amount { or_panic(self.try_released_supply()) }
+
+    pub fn try_released_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_RELEASED_SUPPLY)?.sum()
+    }
+
+    /// Returns the supply which can be released in a block at the given height: the unlocked
+    /// supply not released yet.
+    pub fn releasable_supply(&self, height: u32) -> Amount {
+        or_panic(self.try_releasable_supply(height))
+    }
+
+    pub fn try_releasable_supply(&self, height: u32) -> Result<Amount, Error> {
+        let unlocked = self.try_unlocked_supply(height)?.value();
+        let released = self.try_released_supply()?.value();
+        Ok(Amount::from(unlocked.saturating_sub(released)))
+    }
+
+    /// Returns the part of the vested supply not released yet.
+    pub fn locked_supply(&self) -> Amount { or_panic(self.try_locked_supply()) }
+
+    pub fn try_locked_supply(&self) -> Result<Amount, Error> {
+        let vested = global::<_, Amount>(&self.0, GS_VESTED_SUPPLY)?.value();
+        let released = self.try_released_supply()?.value();
+        Ok(Amount::from(vested.saturating_sub(released)))
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn locked_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_locked_allocations(filter))
+    }
+
+    pub fn try_locked_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_LOCKED, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for VestingWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> {
+        VestingWrapper::try_spec(self).map(Some)
+    }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        VestingWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        VestingWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        VestingWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(all_schemas)]
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    #[cfg(all_schemas)]
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    #[cfg(all_schemas)]
+    use schemata_tools::audit::{audit_onchain, AuditIssue};
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+    #[cfg(all_schemas)]
+    use crate::height::block_height;
+    #[cfg(all_schemas)]
+    use crate::ValidationErrno;
+
+    #[test]
+    fn schema_id() {
+        let schema_id = vesting_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(VESTING_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn schedule() {
+        let schedule = VestingSchedule {
+            vested: Amount::from(1000u64),
+            cliff: 100,
+            end: 300,
+        };
+        assert_eq!(schedule.unlocked(0), Amount::ZERO);
+        assert_eq!(schedule.unlocked(99), Amount::ZERO);
+        assert_eq!(schedule.unlocked(100), Amount::ZERO);
+        assert_eq!(schedule.unlocked(101), Amount::from(5u64));
+        assert_eq!(schedule.unlocked(200), Amount::from(500u64));
+        assert_eq!(schedule.unlocked(299), Amount::from(995u64));
+        assert_eq!(schedule.unlocked(300), Amount::from(1000u64));
+        assert_eq!(schedule.unlocked(u32::MAX), Amount::from(1000u64));
+        assert_eq!(schedule.locked(200), Amount::from(500u64));
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<VestingAsset>(fixtures::contract_id("vesting"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        let schedule = wrapper.schedule();
+        assert_eq!(schedule, VestingSchedule {
+            vested: Amount::from(fixtures::VESTED_SUPPLY),
+            cliff: fixtures::VESTING_CLIFF,
+            end: fixtures::VESTING_END,
+        });
+        assert_eq!(wrapper.unlocked_supply(fixtures::VESTING_CLIFF), Amount::ZERO);
+        assert_eq!(
+            wrapper.unlocked_supply(fixtures::VESTING_END),
+            Amount::from(fixtures::VESTED_SUPPLY)
+        );
+        assert_eq!(wrapper.released_supply(), Amount::ZERO);
+        assert_eq!(wrapper.locked_supply(), Amount::from(fixtures::VESTED_SUPPLY));
+        assert_eq!(
+            wrapper.releasable_supply(fixtures::VESTING_END),
+            Amount::from(fixtures::VESTED_SUPPLY)
+        );
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(
+            fixture_seal(1),
+            fixtures::ISSUED_SUPPLY - fixtures::VESTED_SUPPLY
+        )]);
+        let locked = wrapper
+            .locked_allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(locked, vec![(fixture_seal(2), fixtures::VESTED_SUPPLY)]);
+    }
+
+    // Release of the locked allocation of a vesting asset.
+    //
+    // The founders release the vested supply in two steps, each in its own mined witness TX
+    // committing to a lock time for which the schedule unlocks the released supply. The scripts
+    // don't check the lock time of the witnesses, so a release mined ahead of the schedule is
+    // accepted, and it is the audit which must report it as premature until its witness is mined
+    // late enough. A release committing to a lock time ahead of the schedule must be rejected.
+
+    /// Vesting asset allocating `allocated` units to `seal(1)` and locking `locked` units
+    /// in the allocation of `seal(2)`, under a vesting schedule from `cliff` to `end`.
+    #[cfg(all_schemas)]
+    fn vesting(
+        issued: u64,
+        vested: u64,
+        allocated: u64,
+        locked: u64,
+        cliff: u32,
+        end: u32,
+    ) -> ContractBuilder {
+        testing::asset::<VestingAsset>(issued, allocated)
+            .add_global_state("vestedSupply", Amount::from(vested))
+            .unwrap()
+            .add_global_state("vestingCliff", block_height(cliff))
+            .unwrap()
+            .add_global_state("vestingEnd", block_height(end))
+            .unwrap()
+            .add_fungible_state("lockedAsset", testing::seal(2), locked)
+            .unwrap()
+    }
+
+    #[cfg(all_schemas)]
+    const CLIFF: u32 = 100;
+    #[cfg(all_schemas)]
+    const END: u32 = 200;
+
+    /// Releases `released` units out of the locked allocation `locked`, keeping the rest locked,
+    /// in a witness TX committing to `lock_time` and mined at `height`.
+    #[cfg(all_schemas)]
+    fn release(
+        stock: &mut Stock,
+        resolver: &mut testing::MockResolver,
+        contract_id: ContractId,
+        locked: &FungibleAllocation,
+        released: u64,
+        lock_time: u32,
+        height: u32,
+    ) -> (OpId, Txid) {
+        let remaining = locked.state.value() - released;
+        let mut builder = stock
+            .transition_builder(contract_id, "release")
+            .unwrap()
+            .add_input(locked.opout, AllocatedState::Amount(locked.state.into()))
+            .unwrap()
+            .add_global_state("releasedSupply", Amount::from(released))
+            .unwrap()
+            .add_global_state("lockedSupply", Amount::from(remaining))
+            .unwrap()
+            .add_global_state("lockTime", block_height(lock_time))
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(0), released)
+            .unwrap();
+        if remaining > 0 {
+            builder = builder
+                .add_fungible_state("lockedAsset", testing::graph_seal(1), remaining)
+                .unwrap();
+        }
+        let transition = builder.complete_transition().unwrap();
+        let opid = transition.id();
+
+        let prevout = Outpoint::new(locked.seal.txid, locked.seal.vout.into_u32());
+        let fascia = testing::anchor(contract_id, transition, &[prevout], 2);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        let witness_id = fascia.witness_id();
+        resolver.add_witness(witness, testing::mined_at(height));
+        stock.consume_fascia(fascia, resolver.clone()).unwrap();
+        (opid, witness_id)
+    }
+
+    #[cfg(all_schemas)]
+    fn locked_allocation(stock: &Stock, contract_id: ContractId, op: OpId) -> FungibleAllocation {
+        let wrapper = stock.contract_wrapper::<VestingAsset>(contract_id).unwrap();
+        let allocation = wrapper
+            .locked_allocations(&FilterIncludeAll)
+            .find(|allocation| allocation.opout.op == op)
+            .unwrap();
+        allocation
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn release_schedule() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(vesting(1000, 600, 400, 600, CLIFF, END));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let wrapper = stock.contract_wrapper::<VestingAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.unlocked_supply(CLIFF), Amount::ZERO);
+        assert_eq!(wrapper.unlocked_supply(150), Amount::from(300u64));
+        assert_eq!(wrapper.unlocked_supply(END), Amount::from(600u64));
+        assert_eq!(wrapper.releasable_supply(150), Amount::from(300u64));
+
+        let locked = locked_allocation(&stock, contract_id, genesis_id);
+        let (first, _) = release(&mut stock, &mut resolver, contract_id, &locked, 300, 149, 150);
+
+        let wrapper = stock.contract_wrapper::<VestingAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.released_supply(), Amount::from(300u64));
+        assert_eq!(wrapper.locked_supply(), Amount::from(300u64));
+        assert_eq!(wrapper.releasable_supply(150), Amount::ZERO);
+        assert_eq!(wrapper.releasable_supply(175), Amount::from(150u64));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+
+        // releasing everything before the end of the schedule, despite the lock time
+        let locked = locked_allocation(&stock, contract_id, first);
+        let (second, witness_id) =
+            release(&mut stock, &mut resolver, contract_id, &locked, 300, END - 1, 175);
+        testing::validate_history::<VestingAsset>(&stock, &resolver, contract_id, &[first, second])
+            .unwrap();
+
+        let wrapper = stock.contract_wrapper::<VestingAsset>(contract_id).unwrap();
+        assert_eq!(wrapper.released_supply(), Amount::from(600u64));
+        assert_eq!(wrapper.locked_supply(), Amount::ZERO);
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert_eq!(report.issues, vec![AuditIssue::PrematureRelease {
+            op: second,
+            height: 175,
+            locked: 0,
+            required: 150,
+        }]);
+        assert_eq!((report.issued, report.burned, report.allocated), (1000, 0, 1000));
+
+        resolver.set_witness_ord(witness_id, testing::mined_at(END));
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+    }
+
+    #[cfg(all_schemas)]
+    #[test]
+    fn premature_release_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(vesting(1000, 600, 400, 600, CLIFF, END));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        // a lock time of 149 lets the witness be mined at height 150, which unlocks 300 units
+        let locked = locked_allocation(&stock, contract_id, genesis_id);
+        let (opid, _) = release(&mut stock, &mut resolver, contract_id, &locked, 301, 149, 150);
+
+        let err =
+            testing::validate_history::<VestingAsset>(&stock, &resolver, contract_id, &[opid])
+                .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::ReleaseLocked));
+    }
+}
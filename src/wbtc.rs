@@ -0,0 +1,523 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2025 by
+//     Stefano Pellegrini <stefano.pellegrini@bitfinex.com>
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wrapped BTC asset (WBTC) schema.
+//! (!) Not safe to use in a production environment!
+//!
+//! A fungible asset backed by BTC held in reserve by its custodian. The supply is issued by the
+//! genesis and by `mint` transitions spending a mint right, and each issuance must carry a
+//! `reserveProof` metadata field attesting the reserves backing it: the reserve UTXO, naming the
+//! txid holding the BTC, and a commitment proving control over it. The validation requires the
+//! issuance to record the same attestation in the global state, so that
+//! [`WbtcWrapper::attestations`] can list the attestation history and auditors can reconcile the
+//! circulating supply against the on-chain reserves. Holders unwrapping their BTC burn the
+//! corresponding units, recording the burned supply.
+//!
+//! The validation can't check the attestation against the chain: auditors must verify that each
+//! reserve UTXO holds enough BTC and that the commitment proves its control.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use rgbstd::contract::{
+    AssignmentsFilter, ContractData, FungibleAllocation, IssuerWrapper, RightsAllocation,
+    SchemaWrapper,
+};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::schema::{
+    AssignmentDetails, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences,
+    OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::{AssetSpec, ContractTerms, ProofOfReserves};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_SVS;
+use rgbstd::{Amount, GlobalDetails, MetaDetails, SchemaId, TransitionDetails};
+use strict_types::TypeSystem;
+
+use crate::info::{IssuerInfo, SchemaFamily, SchemaInfo, SchemaVersion, DEVELOPER};
+use crate::scripts::{SharedLibs, NIA_TRANSFER, RIA_BURN, WBTC_ISSUANCE};
+use crate::wrapper::{check_family, global, globals, or_panic};
+use crate::{
+    standard_types, ContractWrapper, Error, FungibleAssetWrapper, WrongSchema, GS_BURNED_SUPPLY,
+    GS_ISSUED_SUPPLY, GS_NOMINAL, GS_RESERVES, GS_TERMS, MS_RESERVE_PROOF, OS_ASSET, OS_MINT,
+    TS_BURN, TS_MINT, TS_TRANSFER,
+};
+
+pub const WBTC_SCHEMA_ID: SchemaId = SchemaId::from_array([
+    0x3c, 0xf3, 0xd1, 0x22, 0x02, 0x42, 0xa2, 0xc1, 0x9b, 0xb2, 0x2d, 0x10, 0x96, 0xf0, 0x3d, 0x6b,
+    0xa5, 0x88, 0xe5, 0x56, 0x09, 0xca, 0xbe, 0x78, 0x49, 0x2b, 0xf6, 0xf0, 0x62, 0xd1, 0x3f, 0x47,
+]);
+
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug"))]
+fn wbtc_schema() -> Schema {
+    let types = standard_types();
+
+    WBTC_ISSUANCE.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+    NIA_TRANSFER.verify(&[(0, INSTR_PUTA), (4, INSTR_SVS)]);
+    RIA_BURN.verify(&[(0, INSTR_PUTA), (4, INSTR_PUTA), (8, INSTR_PUTA)]);
+
+    Schema {
+        ffv: zero!(),
+        name: tn!("WrappedBtcAsset"),
+        meta_types: tiny_bmap! {
+            MS_RESERVE_PROOF => MetaDetails {
+                sem_id: types.get("RGBContract.ProofOfReserves"),
+                name: fname!("reserveProof"),
+            }
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+                name: fname!("spec"),
+            },
+            GS_TERMS => GlobalDetails {
+                global_state_schema: GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+                name: fname!("terms"),
+            },
+            GS_ISSUED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("issuedSupply"),
+            },
+            GS_BURNED_SUPPLY => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.Amount")),
+                name: fname!("burnedSupply"),
+            },
+            GS_RESERVES => GlobalDetails {
+                global_state_schema: GlobalStateSchema::many(types.get("RGBContract.ProofOfReserves")),
+                name: fname!("reserves"),
+            },
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+                name: fname!("assetOwner"),
+                default_transition: TS_TRANSFER,
+            },
+            OS_MINT => AssignmentDetails {
+                owned_state_schema: OwnedStateSchema::Declarative,
+                name: fname!("mintRight"),
+                default_transition: TS_TRANSFER,
+            }
+        },
+        genesis: GenesisSchema {
+            metadata: tiny_bset![MS_RESERVE_PROOF],
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_RESERVES => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_MINT => Occurrences::NoneOrMore,
+            },
+            validator: Some(WBTC_ISSUANCE.lib_site()),
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: none!(),
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_MINT => Occurrences::NoneOrMore
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::NoneOrMore,
+                        OS_MINT => Occurrences::NoneOrMore
+                    },
+                    validator: Some(NIA_TRANSFER.lib_site())
+                },
+                name: fname!("transfer"),
+            },
+            TS_BURN => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: none!(),
+                    globals: tiny_bmap! {
+                        GS_BURNED_SUPPLY => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                    },
+                    assignments: none!(),
+                    validator: Some(RIA_BURN.lib_site())
+                },
+                name: fname!("burn"),
+            },
+            TS_MINT => TransitionDetails {
+                transition_schema: TransitionSchema {
+                    metadata: tiny_bset![MS_RESERVE_PROOF],
+                    globals: tiny_bmap! {
+                        GS_ISSUED_SUPPLY => Occurrences::Once,
+                        GS_RESERVES => Occurrences::Once,
+                    },
+                    inputs: tiny_bmap! {
+                        OS_MINT => Occurrences::Once
+                    },
+                    assignments: tiny_bmap! {
+                        OS_ASSET => Occurrences::OnceOrMore,
+                        OS_MINT => Occurrences::NoneOrMore
+                    },
+                    validator: Some(WBTC_ISSUANCE.lib_site())
+                },
+                name: fname!("mint"),
+            },
+        },
+        default_assignment: Some(OS_ASSET),
+    }
+}
+
+fn wbtc_scripts() -> Scripts { SharedLibs::get().scripts(&[WBTC_ISSUANCE, NIA_TRANSFER, RIA_BURN]) }
+
+#[derive(Default)]
+pub struct WrappedBtcAsset;
+
+impl IssuerWrapper for WrappedBtcAsset {
+    type Wrapper<S: ContractStateRead> = WbtcWrapper<S>;
+
+    fn schema() -> Schema {
+        static SCHEMA: OnceLock<Schema> = OnceLock::new();
+        SCHEMA.get_or_init(wbtc_schema).clone()
+    }
+
+    fn types() -> TypeSystem {
+        static TYPES: OnceLock<TypeSystem> = OnceLock::new();
+        TYPES
+            .get_or_init(|| standard_types().type_system(Self::schema()))
+            .clone()
+    }
+
+    fn scripts() -> Scripts {
+        static SCRIPTS: OnceLock<Scripts> = OnceLock::new();
+        SCRIPTS.get_or_init(wbtc_scripts).clone()
+    }
+}
+
+impl WrappedBtcAsset {
+    /// First revision of the WBTC schema.
+    pub const V1: SchemaInfo = SchemaInfo {
+        name: "WrappedBtcAsset",
+        schema_id: WBTC_SCHEMA_ID,
+        developer: DEVELOPER,
+        version: SchemaVersion::new(0, 1, 0),
+        release_notes: "Fungible asset backed by BTC reserves, whose issuances record an \
+                        attestation of the reserves backing them.",
+    };
+}
+
+impl IssuerInfo for WrappedBtcAsset {
+    const INFO: SchemaInfo = Self::V1;
+}
+
+impl SchemaFamily for WrappedBtcAsset {
+    const VERSIONS: &'static [SchemaInfo] = &[Self::V1];
+}
+
+/// Reserve attestation recorded by an issuance of a wrapped BTC asset.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ReserveAttestation {
+    /// Supply issued against the attested reserves.
+    pub issued: Amount,
+    /// Reserve UTXO holding the BTC together with the commitment proving its control.
+    pub proof: ProofOfReserves,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, From)]
+pub struct WbtcWrapper<S: ContractStateRead>(ContractData<S>);
+
+impl<S: ContractStateRead> SchemaWrapper<S> for WbtcWrapper<S> {
+    /// Panics if the contract doesn't belong to the schema family. Kept for
+    /// `Stock::contract_wrapper`, while calls naming the wrapper resolve to its deprecated inherent
+    /// `with`.
+    fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+}
+
+impl<S: ContractStateRead> ContractWrapper<S> for WbtcWrapper<S> {
+    fn contract_data(&self) -> &ContractData<S> { &self.0 }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error> {
+        Ok(check_family::<WrappedBtcAsset, _>(&self.0)?)
+    }
+}
+
+impl<S: ContractStateRead> WbtcWrapper<S> {
+    /// Wraps the contract data, failing if it doesn't belong to the WBTC schema.
+    pub fn try_with(data: ContractData<S>) -> Result<Self, WrongSchema> {
+        check_family::<WrappedBtcAsset, _>(&data)?;
+        Ok(Self(data))
+    }
+
+    /// Wraps the contract data, panicking if it doesn't belong to the WBTC schema.
+    #[deprecated(since = "0.11.1", note = "panics on a schema mismatch, use `try_with` instead")]
+    pub fn with(data: ContractData<S>) -> Self { or_panic(Self::try_with(data)) }
+
+    pub fn spec(&self) -> AssetSpec { or_panic(self.try_spec()) }
+
+    pub fn try_spec(&self) -> Result<AssetSpec, Error> { global(&self.0, GS_NOMINAL) }
+
+    pub fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    pub fn try_contract_terms(&self) -> Result<ContractTerms, Error> { global(&self.0, GS_TERMS) }
+
+    /// Returns the supply issued by the genesis and all mints.
+    pub fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    pub fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_ISSUED_SUPPLY)?.sum()
+    }
+
+    /// Returns the amounts issued by each mint, from the latest one, followed by the amount
+    /// issued by the genesis.
+    pub fn issuance_amounts(&self) -> Vec<Amount> { or_panic(self.try_issuance_amounts()) }
+
+    pub fn try_issuance_amounts(&self) -> Result<Vec<Amount>, Error> {
+        globals(&self.0, GS_ISSUED_SUPPLY)?.collect()
+    }
+
+    /// Returns the supply burned by holders unwrapping their BTC.
+    pub fn burned_supply(&self) -> Amount { or_panic(self.try_burned_supply()) }
+
+    pub fn try_burned_supply(&self) -> Result<Amount, Error> {
+        globals::<_, Amount>(&self.0, GS_BURNED_SUPPLY)?.sum()
+    }
+
+    /// Returns the supply issued and not burned, which the reserves must cover.
+    pub fn circulating_supply(&self) -> Amount { or_panic(self.try_circulating_supply()) }
+
+    pub fn try_circulating_supply(&self) -> Result<Amount, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        let burned = self.try_burned_supply()?.value();
+        Ok(Amount::from(issued.saturating_sub(burned)))
+    }
+
+    /// Returns the reserve attestations in the order the issuances were made, starting from the
+    /// genesis one.
+    pub fn attestations(&self) -> Vec<ReserveAttestation> { or_panic(self.try_attestations()) }
+
+    pub fn try_attestations(&self) -> Result<Vec<ReserveAttestation>, Error> {
+        // Each issuance records one issued supply and one attestation, so the entries of both
+        // global states match one to one
+        let issued = self.try_issuance_amounts()?;
+        let proofs = globals(&self.0, GS_RESERVES)?.collect::<Result<Vec<ProofOfReserves>, _>>()?;
+        if issued.len() != proofs.len() {
+            return Err(Error::MissingGlobal(GS_RESERVES));
+        }
+        let mut attestations = issued
+            .into_iter()
+            .zip(proofs)
+            .map(|(issued, proof)| ReserveAttestation { issued, proof })
+            .collect::<Vec<_>>();
+        // Global state lists the latest entries first
+        attestations.reverse();
+        Ok(attestations)
+    }
+
+    pub fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    pub fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        Ok(self.0.fungible_raw(OS_ASSET, filter)?)
+    }
+
+    pub fn mint_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = RightsAllocation> + 'c {
+        or_panic(self.try_mint_rights(filter))
+    }
+
+    pub fn try_mint_rights<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = RightsAllocation> + 'c, Error> {
+        Ok(self.0.rights_raw(OS_MINT, filter)?)
+    }
+}
+
+impl<S: ContractStateRead> FungibleAssetWrapper<S> for WbtcWrapper<S> {
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error> { WbtcWrapper::try_spec(self).map(Some) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error> {
+        WbtcWrapper::try_contract_terms(self)
+    }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error> {
+        WbtcWrapper::try_total_issued_supply(self)
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error> {
+        WbtcWrapper::try_allocations(self, filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallBlob;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::*;
+    use rgbstd::persistence::Stock;
+    use rgbstd::*;
+    use schemata_tools::testing;
+
+    use super::*;
+    use crate::fixtures::{self, fixture_seal};
+
+    #[test]
+    fn schema_id() {
+        let schema_id = wbtc_schema().schema_id();
+        eprintln!("{:#04x?}", schema_id.to_byte_array());
+        assert_eq!(WBTC_SCHEMA_ID, schema_id);
+    }
+
+    #[test]
+    fn wrapper() {
+        let stock = fixtures::stock();
+        let wrapper = stock
+            .contract_wrapper::<WrappedBtcAsset>(fixtures::contract_id("wbtc"))
+            .unwrap();
+        assert_eq!(wrapper.spec(), testing::spec());
+        assert_eq!(wrapper.contract_terms(), testing::terms());
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.burned_supply(), Amount::ZERO);
+        assert_eq!(wrapper.circulating_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+        assert_eq!(wrapper.attestations(), vec![ReserveAttestation {
+            issued: Amount::from(fixtures::ISSUED_SUPPLY),
+            proof: fixtures::reserve_proof(),
+        }]);
+
+        let allocations = wrapper
+            .allocations(&FilterIncludeAll)
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(allocations, vec![(fixture_seal(1), fixtures::ISSUED_SUPPLY)]);
+        let rights = wrapper
+            .mint_rights(&FilterIncludeAll)
+            .map(|a| a.seal)
+            .collect::<Vec<_>>();
+        assert_eq!(rights, vec![fixture_seal(2)]);
+    }
+
+    // Reserve attestations of a wrapped BTC asset.
+    //
+    // The custodian mints twice, attesting new reserves each time, and a holder then burns part of
+    // the supply to unwrap their BTC; the wrapper lists the attestations in the order they were
+    // made and reports the supply the reserves must cover.
+
+    /// Attestation of BTC reserves held in the output `vout` of the test TX.
+    fn reserve_proof(vout: u32) -> ProofOfReserves {
+        let proof = SmallBlob::try_from_iter(vec![0x52; 64]).unwrap();
+        ProofOfReserves::new(Outpoint::new(testing::txid(), vout), proof)
+    }
+
+    /// Wrapped BTC asset issued against the reserves of [`reserve_proof`]`(0)`, recording
+    /// `reserves` as the attestation.
+    fn wbtc(issued: u64, allocated: u64, reserves: ProofOfReserves) -> ContractBuilder {
+        testing::asset::<WrappedBtcAsset>(issued, allocated)
+            .add_metadata("reserveProof", reserve_proof(0))
+            .unwrap()
+            .add_global_state("reserves", reserves)
+            .unwrap()
+            .add_rights("mintRight", testing::seal(2))
+            .unwrap()
+    }
+
+    #[test]
+    fn mint_and_burn() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(wbtc(1000, 1000, reserve_proof(0)));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 2);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+
+        let mut right = (Opout::new(genesis_id, OS_MINT, 0), Outpoint::new(testing::txid(), 2));
+        let mut minted = vec![];
+        for (vout, amount) in [(1, 500u64), (2, 300)] {
+            let transition = stock
+                .transition_builder(contract_id, "mint")
+                .unwrap()
+                .add_input(right.0, AllocatedState::Void)
+                .unwrap()
+                .add_metadata("reserveProof", reserve_proof(vout))
+                .unwrap()
+                .add_global_state("issuedSupply", Amount::from(amount))
+                .unwrap()
+                .add_global_state("reserves", reserve_proof(vout))
+                .unwrap()
+                .add_fungible_state("assetOwner", testing::graph_seal(1), amount)
+                .unwrap()
+                .add_rights("mintRight", testing::graph_seal(2))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            let opid = transition.id();
+            let witness_id = consume(&mut stock, transition, &[right.1], 100 + vout);
+            minted.push((Opout::new(opid, OS_ASSET, 0), Outpoint::new(witness_id, 1)));
+            right = (Opout::new(opid, OS_MINT, 0), Outpoint::new(witness_id, 2));
+        }
+
+        // the holder of the first mint unwraps their BTC
+        let transition = stock
+            .transition_builder(contract_id, "burn")
+            .unwrap()
+            .add_input(minted[0].0, AllocatedState::Amount(RevealedValue::from(500u64)))
+            .unwrap()
+            .add_global_state("burnedSupply", Amount::from(500u64))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        consume(&mut stock, transition, &[minted[0].1], 110);
+
+        let wrapper = stock
+            .contract_wrapper::<WrappedBtcAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1800u64));
+        assert_eq!(wrapper.burned_supply(), Amount::from(500u64));
+        assert_eq!(wrapper.circulating_supply(), Amount::from(1300u64));
+        let attestations = [(1000u64, 0), (500, 1), (300, 2)]
+            .map(|(issued, vout)| ReserveAttestation {
+                issued: Amount::from(issued),
+                proof: reserve_proof(vout),
+            })
+            .to_vec();
+        assert_eq!(wrapper.attestations(), attestations);
+    }
+}
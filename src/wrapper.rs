@@ -0,0 +1,423 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Features shared by the schema wrappers and typed access to contract global state.
+//!
+//! [`ContractWrapper`] and [`FungibleAssetWrapper`] implement once what every wrapper, or every
+//! fungible asset wrapper, offers; the schema modules only provide access to their contract data
+//! and the accessors specific to their schema.
+//!
+//! Global state values are strict-decoded directly from the bytes borrowed from the contract
+//! state, skipping the type-system driven `StrictVal` tree built by `ContractData::global`.
+//! Decoding is lazy, so accessors only pay for the entries they consume, which matters when
+//! scanning many contracts.
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use rgbstd::contract::{AssignmentsFilter, ContractData, FungibleAllocation, SchemaWrapper};
+use rgbstd::invoice::{Beneficiary, Precision, RgbInvoice, XChainNet};
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::stl::{AssetSpec, ContractTerms, Name};
+use rgbstd::vm::GlobalStateEntry;
+use rgbstd::{Amount, GlobalStateType, Opout, Outpoint};
+use strict_types::encoding::StrictReader;
+use strict_types::StrictDecode;
+
+use crate::diff::ContractSnapshot;
+use crate::info::{SchemaFamily, SchemaInfo};
+use crate::invoice::{amount_invoice, parse_amount};
+use crate::metrics::ContractMetrics;
+use crate::selection::Spendable;
+use crate::summary::ContractSummary;
+use crate::{Error, WrongSchema, GS_NOMINAL};
+
+/// Interface shared by the wrappers of all the schemata, giving access to the contract data they
+/// wrap.
+pub trait ContractWrapper<S: ContractStateRead>: SchemaWrapper<S> {
+    /// Returns the contract data wrapped.
+    fn contract_data(&self) -> &ContractData<S>;
+
+    /// Returns the revision of the schema family used by the contract.
+    fn version(&self) -> SchemaInfo { or_panic(self.try_version()) }
+
+    fn try_version(&self) -> Result<SchemaInfo, Error>;
+
+    /// Captures the current state of the contract for [`ContractSnapshot::diff`].
+    fn snapshot(&self, filter: impl AssignmentsFilter) -> ContractSnapshot {
+        ContractSnapshot::capture(self.contract_data(), filter)
+    }
+
+    /// Summarizes the identity, supplies and allocations of the contract, see [`ContractSummary`].
+    fn summary(&self, filter: impl AssignmentsFilter) -> ContractSummary {
+        ContractSummary::capture(self.contract_data(), filter)
+    }
+
+    /// Measures the size of the contract, counting as spent the allocations whose outputs are in
+    /// `spent`, see [`crate::metrics::spent_outputs`].
+    fn metrics(&self, spent: &BTreeSet<Opout>) -> ContractMetrics {
+        ContractMetrics::capture(self.contract_data(), spent)
+    }
+}
+
+/// Interface shared by the wrappers of the fungible asset schemata, letting wallets handle their
+/// contracts with a single generic code path.
+///
+/// Unlike [`FungibleView`](crate::FungibleView), which detects the schema of a contract at
+/// runtime, the trait is meant for code generic over the wrapper type. The inherent methods of the
+/// wrappers with the same names take precedence in method calls on a concrete wrapper.
+pub trait FungibleAssetWrapper<S: ContractStateRead>: ContractWrapper<S> {
+    /// Returns the asset specification, which schemata naming the asset without a ticker, like
+    /// CFA, don't have.
+    fn spec(&self) -> Option<AssetSpec> { or_panic(self.try_spec()) }
+
+    fn try_spec(&self) -> Result<Option<AssetSpec>, Error>;
+
+    /// Returns the name of the asset, taken from its specification if it has one.
+    fn name(&self) -> Name { or_panic(self.try_name()) }
+
+    fn try_name(&self) -> Result<Name, Error> {
+        self.try_spec()?
+            .map(|spec| spec.name)
+            .ok_or(Error::MissingGlobal(GS_NOMINAL))
+    }
+
+    /// Returns the number of decimal digits of the asset amounts.
+    fn precision(&self) -> Precision { or_panic(self.try_precision()) }
+
+    fn try_precision(&self) -> Result<Precision, Error> {
+        self.try_spec()?
+            .map(|spec| spec.precision)
+            .ok_or(Error::MissingGlobal(GS_NOMINAL))
+    }
+
+    fn contract_terms(&self) -> ContractTerms { or_panic(self.try_contract_terms()) }
+
+    fn try_contract_terms(&self) -> Result<ContractTerms, Error>;
+
+    fn total_issued_supply(&self) -> Amount { or_panic(self.try_total_issued_supply()) }
+
+    fn try_total_issued_supply(&self) -> Result<Amount, Error>;
+
+    fn allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> impl Iterator<Item = FungibleAllocation> + 'c {
+        or_panic(self.try_allocations(filter))
+    }
+
+    fn try_allocations<'c>(
+        &'c self,
+        filter: impl AssignmentsFilter + 'c,
+    ) -> Result<impl Iterator<Item = FungibleAllocation> + 'c, Error>;
+
+    /// Returns the sum of the allocations passing the filter.
+    fn balance(&self, filter: impl AssignmentsFilter) -> Amount {
+        or_panic(self.try_balance(filter))
+    }
+
+    fn try_balance(&self, filter: impl AssignmentsFilter) -> Result<Amount, Error> {
+        Ok(self.try_allocations(filter)?.map(|a| a.state).sum())
+    }
+
+    /// Returns the sum of the allocations assigned to the outpoint by the contract state, which
+    /// keeps the allocations once spent: wallets only query the outpoints they haven't spent yet,
+    /// otherwise see [`Self::unspent_balance_of`].
+    fn balance_of(&self, outpoint: Outpoint) -> Amount { or_panic(self.try_balance_of(outpoint)) }
+
+    fn try_balance_of(&self, outpoint: Outpoint) -> Result<Amount, Error> {
+        self.try_balance(outpoint)
+    }
+
+    /// Returns the sum of the allocations assigned to the outpoint, skipping the ones whose outputs
+    /// are in `spent`, see [`crate::metrics::spent_outputs`].
+    fn unspent_balance_of(&self, outpoint: Outpoint, spent: &BTreeSet<Opout>) -> Amount {
+        or_panic(self.try_unspent_balance_of(outpoint, spent))
+    }
+
+    fn try_unspent_balance_of(
+        &self,
+        outpoint: Outpoint,
+        spent: &BTreeSet<Opout>,
+    ) -> Result<Amount, Error> {
+        Ok(self
+            .try_allocations(outpoint)?
+            .filter(|a| !spent.contains(&a.opout))
+            .map(|a| a.state)
+            .sum())
+    }
+
+    /// Returns the allocations assigned to the outpoints controlled by a wallet, which it can
+    /// spend.
+    fn spendable(&self, outpoints: impl IntoIterator<Item = Outpoint>) -> Spendable {
+        or_panic(self.try_spendable(outpoints))
+    }
+
+    fn try_spendable(
+        &self,
+        outpoints: impl IntoIterator<Item = Outpoint>,
+    ) -> Result<Spendable, Error> {
+        let outpoints = outpoints.into_iter().collect::<Vec<_>>();
+        Ok(Spendable::new(self.try_allocations(outpoints)?))
+    }
+
+    /// Builds an invoice for receiving an amount of the asset given in its whole units, like
+    /// `"12.5"`, see [`parse_amount`].
+    fn invoice(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        amount: &str,
+    ) -> Result<RgbInvoice, Error> {
+        let amount = parse_amount(amount, self.try_precision()?)?;
+        self.try_invoice_raw(beneficiary, amount)
+    }
+
+    /// Builds an invoice for receiving an amount of the asset given in its smallest units.
+    fn invoice_raw(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        amount: Amount,
+    ) -> RgbInvoice {
+        or_panic(self.try_invoice_raw(beneficiary, amount))
+    }
+
+    fn try_invoice_raw(
+        &self,
+        beneficiary: impl Into<XChainNet<Beneficiary>>,
+        amount: Amount,
+    ) -> Result<RgbInvoice, Error> {
+        amount_invoice(self.contract_data(), beneficiary, amount)
+    }
+}
+
+/// Checks that the contract data belongs to a revision of the schema family `F`, returning it.
+pub(crate) fn check_family<F: SchemaFamily, S: ContractStateRead>(
+    data: &ContractData<S>,
+) -> Result<SchemaInfo, WrongSchema> {
+    let actual = data.schema.schema_id();
+    F::version_of(actual).ok_or_else(|| {
+        let expected = F::latest().schema_id;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%expected, %actual, "contract schema mismatch");
+        WrongSchema { expected, actual }
+    })
+}
+
+/// Iterates over the values of the global state of type `ty`, decoding each of them on demand.
+pub(crate) fn globals<S: ContractStateRead, T: StrictDecode>(
+    data: &ContractData<S>,
+    ty: GlobalStateType,
+) -> Result<impl Iterator<Item = Result<T, Error>> + '_, Error> {
+    Ok(data
+        .state
+        .global(ty)?
+        .map(move |entry| decode(entry.borrow(), ty)))
+}
+
+/// Returns the first value of the global state of type `ty`, failing if there is none.
+pub(crate) fn global<S: ContractStateRead, T: StrictDecode>(
+    data: &ContractData<S>,
+    ty: GlobalStateType,
+) -> Result<T, Error> {
+    globals(data, ty)?
+        .next()
+        .unwrap_or(Err(Error::MissingGlobal(ty)))
+}
+
+/// Returns the contract id referenced by the global state of type `ty`, stored in its string form
+/// as [`Details`](rgbstd::stl::Details), since the standard types have no contract id.
+#[cfg(any(feature = "swap", feature = "option"))]
+pub(crate) fn contract_ref<S: ContractStateRead>(
+    data: &ContractData<S>,
+    ty: GlobalStateType,
+) -> Result<rgbstd::ContractId, Error> {
+    use std::str::FromStr;
+
+    let details = global::<_, rgbstd::stl::Details>(data, ty)?;
+    rgbstd::ContractId::from_str(details.as_ref()).map_err(|_| Error::InvalidGlobal(ty))
+}
+
+/// Unwraps the result of a fallible accessor for its panicking counterpart.
+///
+/// # Panics
+///
+/// If the contract data doesn't match the schema of the wrapper.
+pub(crate) fn or_panic<T, E: Display>(res: Result<T, E>) -> T {
+    res.unwrap_or_else(|err| panic!("{err}"))
+}
+
+fn decode<T: StrictDecode>(entry: &GlobalStateEntry, ty: GlobalStateType) -> Result<T, Error> {
+    let mut reader = StrictReader::in_memory::<{ u16::MAX as usize }>(entry.data().as_slice());
+    T::strict_decode(&mut reader).map_err(|_| Error::InvalidGlobal(ty))
+}
+
+#[cfg(all(test, all_schemas))]
+mod test {
+    use rgbstd::contract::SchemaWrapper;
+    use rgbstd::stl::{AssetSpec, ContractTerms};
+    use rgbstd::Amount;
+
+    use super::*;
+    use crate::info::SchemaFamily;
+    use crate::{
+        fixtures, NiaWrapper, NonInflatableAsset, UdaWrapper, UniqueDigitalAsset, GS_ISSUED_SUPPLY,
+        GS_NOMINAL, GS_TERMS, GS_TOKENS, NIA_SCHEMA_ID, UDA_SCHEMA_ID,
+    };
+
+    #[test]
+    fn matches_strict_val() {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+
+        let spec = globals::<_, AssetSpec>(&data, GS_NOMINAL)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let spec_val = data
+            .global_raw(GS_NOMINAL)
+            .map(|val| AssetSpec::from_strict_val_unchecked(&val))
+            .collect::<Vec<_>>();
+        assert_eq!(spec, spec_val);
+
+        let terms = globals::<_, ContractTerms>(&data, GS_TERMS)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let terms_val = data
+            .global_raw(GS_TERMS)
+            .map(|val| ContractTerms::from_strict_val_unchecked(&val))
+            .collect::<Vec<_>>();
+        assert_eq!(terms, terms_val);
+
+        let supply = globals::<_, Amount>(&data, GS_ISSUED_SUPPLY)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let supply_val = data
+            .global_raw(GS_ISSUED_SUPPLY)
+            .map(|val| Amount::from_strict_val_unchecked(&val))
+            .collect::<Vec<_>>();
+        assert_eq!(supply, supply_val);
+    }
+
+    #[test]
+    fn schema_mismatch() {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        let err = UdaWrapper::try_with(data).unwrap_err();
+        assert_eq!(err, WrongSchema {
+            expected: UDA_SCHEMA_ID,
+            actual: NIA_SCHEMA_ID
+        });
+        assert_eq!(Error::from(err), Error::SchemaMismatch {
+            expected: UDA_SCHEMA_ID,
+            actual: NIA_SCHEMA_ID
+        });
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        assert!(NiaWrapper::try_with(data).is_ok());
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        assert!(UniqueDigitalAsset::try_wrap(data).is_err());
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        assert!(NonInflatableAsset::try_wrap(data).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "which is not a revision of the schema family")]
+    fn schema_mismatch_panics() {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        <UdaWrapper<_> as SchemaWrapper<_>>::with(data);
+    }
+
+    #[test]
+    fn unknown_global() {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        assert_eq!(
+            global::<_, AssetSpec>(&data, GS_TOKENS).unwrap_err(),
+            Error::UnknownGlobalType(GS_TOKENS)
+        );
+        assert_eq!(
+            globals::<_, AssetSpec>(&data, GS_TOKENS).err(),
+            Some(Error::UnknownGlobalType(GS_TOKENS))
+        );
+    }
+
+    #[test]
+    fn invalid_global() {
+        let stock = fixtures::stock();
+        let data = stock.contract_data(fixtures::contract_id("nia")).unwrap();
+        assert_eq!(
+            global::<_, AssetSpec>(&data, GS_ISSUED_SUPPLY).unwrap_err(),
+            Error::InvalidGlobal(GS_ISSUED_SUPPLY)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "nia", feature = "cfa", feature = "pfa", feature = "ifa"))]
+    fn fungible_asset_wrapper() {
+        use rgbstd::contract::FilterIncludeAll;
+        use schemata_tools::testing;
+
+        use crate::{CollectibleFungibleAsset, InflatableFungibleAsset, PermissionedFungibleAsset};
+
+        fn check<S: ContractStateRead>(wrapper: impl FungibleAssetWrapper<S>, spec: bool) {
+            assert_eq!(wrapper.spec().is_some(), spec);
+            if let Some(spec) = wrapper.spec() {
+                assert_eq!(wrapper.name(), spec.name);
+            }
+            assert_eq!(wrapper.contract_terms(), testing::terms());
+            assert_eq!(wrapper.total_issued_supply(), Amount::from(fixtures::ISSUED_SUPPLY));
+            let allocated = wrapper
+                .allocations(&FilterIncludeAll)
+                .map(|a| a.state.value())
+                .sum::<u64>();
+            assert_eq!(allocated, fixtures::ISSUED_SUPPLY);
+        }
+
+        let stock = fixtures::stock();
+        let id = fixtures::contract_id;
+        check(
+            stock
+                .contract_wrapper::<NonInflatableAsset>(id("nia"))
+                .unwrap(),
+            true,
+        );
+        check(
+            stock
+                .contract_wrapper::<PermissionedFungibleAsset>(id("pfa"))
+                .unwrap(),
+            true,
+        );
+        check(
+            stock
+                .contract_wrapper::<InflatableFungibleAsset>(id("ifa"))
+                .unwrap(),
+            true,
+        );
+        let cfa = stock
+            .contract_wrapper::<CollectibleFungibleAsset>(id("cfa"))
+            .unwrap();
+        assert_eq!(FungibleAssetWrapper::name(&cfa), cfa.name());
+        check(cfa, false);
+    }
+}
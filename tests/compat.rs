@@ -0,0 +1,99 @@
+//! Contracts issued with earlier revisions of a schema.
+//!
+//! Each test imports a contract committed under `tests/fixtures/compat`, which is the golden vector
+//! of the schema before it gained a new revision, and checks that it is still wrapped through its
+//! schema family and reports the revision it was issued with. The fixtures must never be
+//! regenerated, since the schemata can't issue contracts of their earlier revisions anymore.
+
+#![cfg(all_schemas)]
+
+use std::path::PathBuf;
+
+use rgbstd::containers::{ConsignmentExt, Contract, FileContent};
+use rgbstd::contract::{FilterIncludeAll, IssuerWrapper};
+use rgbstd::persistence::Stock;
+use rgbstd::validation::ValidationConfig;
+use rgbstd::ContractId;
+use schemata::dumb::MockResolver;
+use schemata::info::{schema_info, SchemaFamily};
+use schemata::{
+    ContractWrapper, FungibleAssetWrapper, InflatableFungibleAsset, PermissionedFungibleAsset,
+    UniqueDigitalAsset, IFA_V1_SCHEMA_ID, PFA_V1_SCHEMA_ID, UDA_V1_SCHEMA_ID, UDA_V2_SCHEMA_ID,
+};
+
+/// Validates the fixture contract `name` and imports it into a new stock.
+fn import<I: IssuerWrapper>(name: &str) -> (Stock, ContractId) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/compat")
+        .join(format!("{name}.rgb"));
+    let contract = Contract::load_file(path).unwrap();
+    let contract_id = contract.contract_id();
+    let resolver = MockResolver::with_chain_net(contract.genesis.chain_net);
+    let config = ValidationConfig {
+        chain_net: contract.genesis.chain_net,
+        trusted_typesystem: I::types(),
+        ..Default::default()
+    };
+    let contract = contract
+        .validate(&resolver, &config)
+        .expect("fixture contract is not valid");
+    let mut stock = Stock::in_memory();
+    stock.import_contract(contract, &resolver).unwrap();
+    (stock, contract_id)
+}
+
+#[test]
+fn uda_v1() {
+    let (stock, contract_id) = import::<UniqueDigitalAsset>("uda-v1");
+    let data = stock.contract_data(contract_id).unwrap();
+    assert_eq!(data.schema.schema_id(), UDA_V1_SCHEMA_ID);
+    assert_eq!(schema_info(UDA_V1_SCHEMA_ID), Some(UniqueDigitalAsset::V1));
+
+    let wrapper = UniqueDigitalAsset::try_wrap(data).unwrap();
+    assert_eq!(wrapper.version(), UniqueDigitalAsset::V1);
+    assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 1);
+    assert_eq!(wrapper.engravings(), vec![]);
+    assert!(wrapper.token_data().preview.is_some());
+}
+
+#[test]
+fn uda_v2() {
+    let (stock, contract_id) = import::<UniqueDigitalAsset>("uda-v2");
+    let data = stock.contract_data(contract_id).unwrap();
+    assert_eq!(data.schema.schema_id(), UDA_V2_SCHEMA_ID);
+    assert_eq!(schema_info(UDA_V2_SCHEMA_ID), Some(UniqueDigitalAsset::V2));
+
+    let wrapper = UniqueDigitalAsset::try_wrap(data).unwrap();
+    assert_eq!(wrapper.version(), UniqueDigitalAsset::V2);
+    assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 1);
+    assert_eq!(wrapper.engravings(), vec![]);
+}
+
+#[test]
+fn pfa_v1() {
+    let (stock, contract_id) = import::<PermissionedFungibleAsset>("pfa-v1");
+    let data = stock.contract_data(contract_id).unwrap();
+    assert_eq!(data.schema.schema_id(), PFA_V1_SCHEMA_ID);
+    assert_eq!(schema_info(PFA_V1_SCHEMA_ID), Some(PermissionedFungibleAsset::V1));
+
+    let wrapper = PermissionedFungibleAsset::try_wrap(data).unwrap();
+    assert_eq!(wrapper.version(), PermissionedFungibleAsset::V1);
+    assert_eq!(wrapper.pubkey_history(), vec![wrapper.pubkey()]);
+    assert_eq!(wrapper.rotate_key_rights(&FilterIncludeAll).count(), 0);
+    assert_eq!(wrapper.balance(&FilterIncludeAll), wrapper.total_issued_supply());
+}
+
+#[test]
+fn ifa_v1() {
+    let (stock, contract_id) = import::<InflatableFungibleAsset>("ifa-v1");
+    let data = stock.contract_data(contract_id).unwrap();
+    assert_eq!(data.schema.schema_id(), IFA_V1_SCHEMA_ID);
+    assert_eq!(schema_info(IFA_V1_SCHEMA_ID), Some(InflatableFungibleAsset::V1));
+
+    let wrapper = InflatableFungibleAsset::try_wrap(data).unwrap();
+    assert_eq!(wrapper.version(), InflatableFungibleAsset::V1);
+    assert_eq!(wrapper.balance(&FilterIncludeAll), wrapper.total_issued_supply());
+    // burns of the first revision leave no state behind
+    assert!(wrapper.try_total_burned().is_err());
+    assert!(wrapper.try_circulating_supply().is_err());
+}
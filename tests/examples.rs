@@ -0,0 +1,149 @@
+//! Runs the issuance workflows of the examples, saving the contracts into a temporary directory
+//! and resolving witnesses with a [`MockResolver`].
+
+#![cfg(all_schemas)]
+
+#[path = "../examples/workflows/mod.rs"]
+mod workflows;
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use rgbstd::containers::{ConsignmentExt, Contract, FileContent};
+use rgbstd::contract::{FilterIncludeAll, IssuerWrapper};
+use rgbstd::persistence::Stock;
+use rgbstd::validation::ValidationConfig;
+use rgbstd::{Amount, ChainNet, ContractId};
+use schemata::dumb::MockResolver;
+use schemata::{
+    CollectibleFungibleAsset, IfaWrapper, InflatableFungibleAsset, NonInflatableAsset,
+    PermissionedFungibleAsset, UniqueDigitalAsset,
+};
+use workflows::{ISSUED_SUPPLY, MAX_SUPPLY};
+
+/// Temporary directory removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir()
+            .join(format!("rgb-schemas-examples-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    fn path(&self) -> &Path { &self.0 }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+}
+
+/// Networks the examples are run on.
+const CHAIN_NETS: [ChainNet; 3] =
+    [ChainNet::BitcoinTestnet4, ChainNet::BitcoinSignet, ChainNet::BitcoinRegtest];
+
+fn resolver(chain_net: ChainNet) -> MockResolver { MockResolver::with_chain_net(chain_net) }
+
+/// Runs the `issue` workflow on `chain_net` and checks that the saved contract files match the
+/// contract imported into the stock and are valid on that network only.
+fn run<I: IssuerWrapper>(
+    name: &str,
+    chain_net: ChainNet,
+    issue: fn(&Path, ChainNet, MockResolver) -> (Stock, ContractId),
+) -> (Stock, ContractId) {
+    let dir = TempDir::new(&format!("{name}-{}", chain_net.prefix()));
+    let (stock, contract_id) = issue(dir.path(), chain_net, resolver(chain_net));
+
+    let binary = Contract::load_file(dir.path().join(format!("{name}-example.rgb"))).unwrap();
+    let armored = fs::read_to_string(dir.path().join(format!("{name}-example.rgba"))).unwrap();
+    let armored = Contract::from_str(&armored).unwrap();
+    for contract in [binary, armored] {
+        assert_eq!(contract.contract_id(), contract_id);
+        for other in CHAIN_NETS {
+            let config = ValidationConfig {
+                chain_net: other,
+                trusted_typesystem: I::types(),
+                ..Default::default()
+            };
+            let valid = contract.clone().validate(&resolver(other), &config);
+            if other == chain_net {
+                valid.expect("saved contract is not valid");
+            } else {
+                assert!(valid.is_err(), "{name} contract on {chain_net} is valid on {other}");
+            }
+        }
+    }
+
+    (stock, contract_id)
+}
+
+macro_rules! check_fungible {
+    ($name:literal, $issue:path, $issuer:ty) => {
+        check_fungible!($name, $issue, $issuer, |_| {})
+    };
+    ($name:literal, $issue:path, $issuer:ty, $check:expr) => {{
+        let mut ids = BTreeSet::new();
+        for chain_net in CHAIN_NETS {
+            let (stock, contract_id) = run::<$issuer>($name, chain_net, $issue);
+            assert!(ids.insert(contract_id));
+            let contract = stock.contract_wrapper::<$issuer>(contract_id).unwrap();
+            assert_eq!(contract.total_issued_supply(), Amount::from(ISSUED_SUPPLY));
+            let allocations = contract
+                .allocations(&FilterIncludeAll)
+                .map(|a| a.state.value())
+                .collect::<Vec<_>>();
+            assert_eq!(allocations, vec![ISSUED_SUPPLY]);
+            $check(contract);
+        }
+    }};
+}
+
+#[test]
+fn nia() {
+    check_fungible!("nia", workflows::issue_nia, NonInflatableAsset);
+}
+
+#[test]
+fn cfa() {
+    check_fungible!("cfa", workflows::issue_cfa, CollectibleFungibleAsset);
+}
+
+#[test]
+fn pfa() {
+    check_fungible!("pfa", workflows::issue_pfa, PermissionedFungibleAsset);
+}
+
+#[test]
+fn ifa() {
+    check_fungible!(
+        "ifa",
+        workflows::issue_ifa,
+        InflatableFungibleAsset,
+        |contract: IfaWrapper<_>| {
+            assert_eq!(contract.max_supply(), Amount::from(MAX_SUPPLY));
+            assert!(contract.reject_list_url().is_some());
+            let allowance = contract
+                .inflation_allocations(&FilterIncludeAll)
+                .map(|a| a.state.value())
+                .sum::<u64>();
+            assert_eq!(allowance, MAX_SUPPLY - ISSUED_SUPPLY);
+            assert_eq!(contract.replace_rights(&FilterIncludeAll).count(), 1);
+        }
+    );
+}
+
+#[test]
+fn uda() {
+    for chain_net in CHAIN_NETS {
+        let (stock, contract_id) =
+            run::<UniqueDigitalAsset>("uda", chain_net, workflows::issue_uda);
+        let contract = stock
+            .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+            .unwrap();
+        assert!(contract.contract_terms().media.is_some());
+        assert_eq!(contract.allocations(&FilterIncludeAll).count(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+//! Schema snapshot regression tests.
+//!
+//! Each schema is serialized to YAML and compared against the snapshot committed under
+//! `tests/fixtures/schemata`, whose first line records the schema id the snapshot was taken for.
+//! A schema change which is not accompanied by an update of the corresponding `*_SCHEMA_ID`
+//! constant fails the test, so the schema id can't drift unnoticed.
+//!
+//! After updating the schema id constant, regenerate the snapshots with
+//! `RGB_UPDATE_SNAPSHOTS=1 cargo test --test schema_snapshot` and commit the result.
+
+#![cfg(all_schemas)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use rgbstd::contract::IssuerWrapper;
+use rgbstd::SchemaId;
+use schemata::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+    DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+    GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+    NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+    ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+    SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+    TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+    VerifiableCredential, VestingAsset, WrappedBtcAsset, BOND_SCHEMA_ID, BURNABLE_SCHEMA_ID,
+    CARBON_SCHEMA_ID, CFA_SCHEMA_ID, CLAIM_SCHEMA_ID, COLLECTION_SCHEMA_ID, CREDENTIAL_SCHEMA_ID,
+    DIVIDEND_SCHEMA_ID, EDITION_SCHEMA_ID, EIFA_SCHEMA_ID, ESCROW_SCHEMA_ID, FEE_SCHEMA_ID,
+    FREEZABLE_SCHEMA_ID, FUA_SCHEMA_ID, GOVERNANCE_SCHEMA_ID, IFA_SCHEMA_ID, NIA_SCHEMA_ID,
+    OPTION_SCHEMA_ID, PAUSABLE_SCHEMA_ID, PFAN_SCHEMA_ID, PFA_SCHEMA_ID, PROVENANCE_SCHEMA_ID,
+    REBASING_SCHEMA_ID, REGISTRY_SCHEMA_ID, RENTAL_SCHEMA_ID, RIA_SCHEMA_ID, SBA_SCHEMA_ID,
+    SFA_SCHEMA_ID, STABLECOIN_SCHEMA_ID, SUBSCRIPTION_SCHEMA_ID, SWAP_SCHEMA_ID, TICKET_SCHEMA_ID,
+    TIFA_SCHEMA_ID, TIMELOCK_SCHEMA_ID, UDA_SCHEMA_ID, VESTING_SCHEMA_ID, WBTC_SCHEMA_ID,
+};
+
+const UPDATE_ENV: &str = "RGB_UPDATE_SNAPSHOTS";
+const ID_PREFIX: &str = "# schema id: ";
+
+fn check_snapshot<I: IssuerWrapper>(name: &str, schema_id: SchemaId) {
+    let schema = I::schema();
+    assert_eq!(
+        schema.schema_id(),
+        schema_id,
+        "{name} schema has changed, its schema id constant must be updated"
+    );
+    let snapshot = format!(
+        "{ID_PREFIX}{schema_id}\n{}",
+        serde_yaml::to_string(&schema).expect("unable to serialize schema")
+    );
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/schemata")
+        .join(format!("{name}.yaml"));
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &snapshot).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing schema snapshot {}", path.display()));
+    let expected_id = expected
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix(ID_PREFIX))
+        .unwrap_or_else(|| panic!("schema snapshot {} has no schema id", path.display()));
+    if expected_id != schema_id.to_string() {
+        panic!(
+            "{name} schema id has changed to {schema_id}, regenerate the snapshots with \
+             {UPDATE_ENV}=1"
+        );
+    }
+    assert!(
+        snapshot == expected,
+        "{name} schema serialization has changed without changing the schema id"
+    );
+}
+
+#[test]
+fn nia() { check_snapshot::<NonInflatableAsset>("nia", NIA_SCHEMA_ID); }
+
+#[test]
+fn cfa() { check_snapshot::<CollectibleFungibleAsset>("cfa", CFA_SCHEMA_ID); }
+
+#[test]
+fn uda() { check_snapshot::<UniqueDigitalAsset>("uda", UDA_SCHEMA_ID); }
+
+#[test]
+fn fua() { check_snapshot::<FractionalUniqueAsset>("fua", FUA_SCHEMA_ID); }
+
+#[test]
+fn collection() { check_snapshot::<UniqueDigitalCollection>("collection", COLLECTION_SCHEMA_ID); }
+
+#[test]
+fn pfa() { check_snapshot::<PermissionedFungibleAsset>("pfa", PFA_SCHEMA_ID); }
+
+#[test]
+fn ifa() { check_snapshot::<InflatableFungibleAsset>("ifa", IFA_SCHEMA_ID); }
+
+#[test]
+fn ria() { check_snapshot::<ReissuableAsset>("ria", RIA_SCHEMA_ID); }
+
+#[test]
+fn sba() { check_snapshot::<SoulboundAsset>("sba", SBA_SCHEMA_ID); }
+
+#[test]
+fn sfa() { check_snapshot::<SemiFungibleAsset>("sfa", SFA_SCHEMA_ID); }
+
+#[test]
+fn stablecoin() { check_snapshot::<StablecoinAsset>("stablecoin", STABLECOIN_SCHEMA_ID); }
+
+#[test]
+fn bond() { check_snapshot::<BondAsset>("bond", BOND_SCHEMA_ID); }
+
+#[test]
+fn governance() { check_snapshot::<GovernanceAsset>("governance", GOVERNANCE_SCHEMA_ID); }
+
+#[test]
+fn ticket() { check_snapshot::<TicketAsset>("ticket", TICKET_SCHEMA_ID); }
+
+#[test]
+fn vesting() { check_snapshot::<VestingAsset>("vesting", VESTING_SCHEMA_ID); }
+
+#[test]
+fn timelock() { check_snapshot::<TimelockAsset>("timelock", TIMELOCK_SCHEMA_ID); }
+
+#[test]
+fn pfan() { check_snapshot::<MultiKeyPermissionedAsset>("pfan", PFAN_SCHEMA_ID); }
+
+#[test]
+fn freezable() { check_snapshot::<FreezableAsset>("freezable", FREEZABLE_SCHEMA_ID); }
+
+#[test]
+fn tifa() { check_snapshot::<ThresholdInflatableAsset>("tifa", TIFA_SCHEMA_ID); }
+
+#[test]
+fn wbtc() { check_snapshot::<WrappedBtcAsset>("wbtc", WBTC_SCHEMA_ID); }
+
+#[test]
+fn fee() { check_snapshot::<TransferFeeAsset>("fee", FEE_SCHEMA_ID); }
+
+#[test]
+fn dividend() { check_snapshot::<DividendAsset>("dividend", DIVIDEND_SCHEMA_ID); }
+
+#[test]
+fn escrow() { check_snapshot::<EscrowAsset>("escrow", ESCROW_SCHEMA_ID); }
+
+#[test]
+fn swap() { check_snapshot::<SwapOrder>("swap", SWAP_SCHEMA_ID); }
+
+#[test]
+fn carbon() { check_snapshot::<CarbonCreditAsset>("carbon", CARBON_SCHEMA_ID); }
+
+#[test]
+fn provenance() { check_snapshot::<ProvenanceAsset>("provenance", PROVENANCE_SCHEMA_ID); }
+
+#[test]
+fn subscription() { check_snapshot::<SubscriptionAsset>("subscription", SUBSCRIPTION_SCHEMA_ID); }
+
+#[test]
+fn edition() { check_snapshot::<LimitedEditionAsset>("edition", EDITION_SCHEMA_ID); }
+
+#[test]
+fn eifa() { check_snapshot::<EpochInflatableAsset>("eifa", EIFA_SCHEMA_ID); }
+
+#[test]
+fn claim() { check_snapshot::<ClaimableAsset>("claim", CLAIM_SCHEMA_ID); }
+
+#[test]
+fn registry() { check_snapshot::<NameRegistry>("registry", REGISTRY_SCHEMA_ID); }
+
+#[test]
+fn pausable() { check_snapshot::<PausableAsset>("pausable", PAUSABLE_SCHEMA_ID); }
+
+#[test]
+fn rental() { check_snapshot::<RentalAsset>("rental", RENTAL_SCHEMA_ID); }
+
+#[test]
+fn option() { check_snapshot::<OptionAsset>("option", OPTION_SCHEMA_ID); }
+
+#[test]
+fn credential() { check_snapshot::<VerifiableCredential>("credential", CREDENTIAL_SCHEMA_ID); }
+
+#[test]
+fn burnable() { check_snapshot::<BurnableAsset>("burnable", BURNABLE_SCHEMA_ID); }
+
+#[test]
+fn rebasing() { check_snapshot::<RebasingAsset>("rebasing", REBASING_SCHEMA_ID); }
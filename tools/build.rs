@@ -0,0 +1,67 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates the kit files of the enabled schemata into `OUT_DIR`, verifying their schema ids.
+//!
+//! The kits are built by the `rgb-schemata-core` library itself, so they always match its code.
+//! The schema features of this crate enable those of its build dependency as well, so only the
+//! schemata being generated are compiled for the build script.
+//!
+//! Like the one of `rgb-schemata-core`, the script also sets the `all_schemas` cfg when all the
+//! schemata are enabled through the `default` or `all` features, gating the integration tests
+//! which need every schema.
+//! The build fails if a schema id differs from the constant declared by its module; while
+//! intentionally changing a schema, set `RGB_ALLOW_SCHEMA_ID_MISMATCH` to turn the failure into a
+//! warning until the constant is updated.
+
+use std::env;
+
+use schemata::kit;
+
+const ALLOW_MISMATCH_ENV: &str = "RGB_ALLOW_SCHEMA_ID_MISMATCH";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={ALLOW_MISMATCH_ENV}");
+    if env::var_os("CARGO_FEATURE_DEFAULT").is_some() || env::var_os("CARGO_FEATURE_ALL").is_some()
+    {
+        println!("cargo:rustc-cfg=all_schemas");
+    }
+
+    let mismatches = kit::schema_id_mismatches();
+    if env::var_os(ALLOW_MISMATCH_ENV).is_some() {
+        for mismatch in &mismatches {
+            println!("cargo:warning={mismatch}");
+        }
+    } else if !mismatches.is_empty() {
+        let list = mismatches
+            .iter()
+            .map(|mismatch| format!("  {mismatch}\n"))
+            .collect::<String>();
+        panic!(
+            "schema ids don't match their declared constants:\n{list}update the constants or set \
+             {ALLOW_MISMATCH_ENV} while developing"
+        );
+    }
+
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo");
+    kit::save_kits_unchecked(out_dir).expect("unable to generate schema kits");
+}
@@ -0,0 +1,170 @@
+use std::env;
+use std::path::PathBuf;
+
+use amplify::Wrapper;
+use bitcoincore_rpc::bitcoin::Amount as BtcAmount;
+use rgbstd::bitcoin::CompressedPublicKey;
+use schemata::prelude::*;
+use schemata_tools::resolvers::{Auth, RegtestResolver};
+
+// Issues a contract for each schema on a local regtest node, using outputs funded by the node
+// wallet as genesis seals, and imports them into a stock resolving witnesses against the node.
+//
+// Start the node with `bitcoind -regtest -txindex=1 -fallbackfee=0.0001`, create a wallet with
+// `bitcoin-cli -regtest createwallet rgb` and run the example with
+// `cargo run -p rgb-schemata-tools --example regtest --features bitcoind`. The RPC endpoint and
+// credentials can be set with the `RGB_REGTEST_URL` and `RGB_REGTEST_COOKIE` (or `RGB_REGTEST_USER`
+// and `RGB_REGTEST_PASS`) environment variables.
+fn main() {
+    let url = env::var("RGB_REGTEST_URL").unwrap_or("http://127.0.0.1:18443".to_owned());
+    let auth = match (env::var("RGB_REGTEST_USER"), env::var("RGB_REGTEST_PASS")) {
+        (Ok(user), Ok(pass)) => Auth::UserPass(user, pass),
+        _ => Auth::CookieFile(
+            env::var("RGB_REGTEST_COOKIE")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| {
+                    PathBuf::from(env::var("HOME").unwrap()).join(".bitcoin/regtest/.cookie")
+                }),
+        ),
+    };
+    let resolver = RegtestResolver::with(&url, auth).expect("unable to connect to bitcoind");
+    resolver
+        .check_chain_net(ChainNet::BitcoinRegtest)
+        .expect("bitcoind is not running on regtest");
+
+    let mut stock = Stock::in_memory();
+    for kit in [
+        "schemata/NonInflatableAsset.rgb",
+        "schemata/CollectibleFungibleAsset.rgb",
+        "schemata/UniqueDigitalAsset.rgb",
+        "schemata/PermissionedFungibleAsset.rgb",
+        "schemata/InflatableFungibleAsset.rgb",
+    ] {
+        let kit = Kit::load_file(kit).unwrap().validate().unwrap();
+        stock.import_kit(kit).expect("invalid issuer kit");
+    }
+
+    let seal = || {
+        let outpoint: Outpoint = resolver
+            .fund(resolver.mining_address(), BtcAmount::from_sat(10_000))
+            .expect("unable to fund seal output");
+        GenesisSeal::new_random(outpoint.txid, outpoint.vout)
+    };
+    let spec = AssetSpec::new("TEST", "Test asset", Precision::CentiMicro);
+    let terms = ContractTerms {
+        text: RicardianContract::default(),
+        media: None,
+    };
+    let issued_supply = Amount::from(100000u64);
+
+    let builder = |stock: &Stock, schema_id| {
+        stock
+            .contract_builder("ssi:anonymous", schema_id, ChainNet::BitcoinRegtest)
+            .unwrap()
+    };
+    let issue = |stock: &mut Stock, builder: ContractBuilder| {
+        let contract = builder
+            .issue_contract()
+            .expect("contract doesn't fit schema requirements");
+        let contract_id = contract.contract_id();
+        stock
+            .import_contract(contract, &resolver)
+            .expect("invalid contract");
+        contract_id
+    };
+
+    let nia = builder(&stock, NonInflatableAsset::schema().schema_id())
+        .add_global_state("spec", spec.clone())
+        .unwrap()
+        .add_global_state("terms", terms.clone())
+        .unwrap()
+        .add_global_state("issuedSupply", issued_supply)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(), issued_supply.value())
+        .unwrap();
+    let nia = issue(&mut stock, nia);
+    let nia = stock.contract_wrapper::<NonInflatableAsset>(nia).unwrap();
+    eprintln!("NIA issued supply: {}", nia.total_issued_supply().value());
+
+    let cfa = builder(&stock, CollectibleFungibleAsset::schema().schema_id())
+        .add_global_state("name", Name::from("Test asset"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms.clone())
+        .unwrap()
+        .add_global_state("issuedSupply", issued_supply)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(), issued_supply.value())
+        .unwrap();
+    let cfa = issue(&mut stock, cfa);
+    let cfa = stock
+        .contract_wrapper::<CollectibleFungibleAsset>(cfa)
+        .unwrap();
+    eprintln!("CFA issued supply: {}", cfa.total_issued_supply().value());
+
+    let index = TokenIndex::from_inner(0);
+    let uda = builder(&stock, UniqueDigitalAsset::schema().schema_id())
+        .add_global_state("spec", AssetSpec::new("TEST", "Test uda", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms.clone())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(), Allocation::with(index, 1))
+        .unwrap();
+    let uda = issue(&mut stock, uda);
+    let uda = stock.contract_wrapper::<UniqueDigitalAsset>(uda).unwrap();
+    eprintln!("UDA token index: {}", uda.token_data().index);
+
+    let pubkey = CompressedPublicKey::from_slice(&[
+        2, 199, 163, 211, 116, 75, 108, 119, 241, 66, 54, 236, 233, 189, 142, 108, 37, 135, 56,
+        128, 200, 176, 199, 9, 117, 132, 72, 200, 167, 185, 4, 64, 53,
+    ])
+    .unwrap();
+    let pfa = builder(&stock, PermissionedFungibleAsset::schema().schema_id())
+        .add_global_state("spec", spec.clone())
+        .unwrap()
+        .add_global_state("terms", terms.clone())
+        .unwrap()
+        .add_global_state("issuedSupply", issued_supply)
+        .unwrap()
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(), issued_supply.value())
+        .unwrap();
+    let pfa = issue(&mut stock, pfa);
+    let pfa = stock
+        .contract_wrapper::<PermissionedFungibleAsset>(pfa)
+        .unwrap();
+    eprintln!("PFA issued supply: {}", pfa.total_issued_supply().value());
+
+    let max_supply = Amount::from(150000u64);
+    let ifa = builder(&stock, InflatableFungibleAsset::schema().schema_id())
+        .add_global_state("spec", spec)
+        .unwrap()
+        .add_global_state("terms", terms)
+        .unwrap()
+        .add_global_state("issuedSupply", issued_supply)
+        .unwrap()
+        .add_global_state("maxSupply", max_supply)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(), issued_supply.value())
+        .unwrap()
+        .add_fungible_state(
+            "inflationAllowance",
+            seal(),
+            max_supply.value() - issued_supply.value(),
+        )
+        .unwrap()
+        .add_rights("replaceRight", seal())
+        .unwrap();
+    let ifa = issue(&mut stock, ifa);
+    let ifa = stock
+        .contract_wrapper::<InflatableFungibleAsset>(ifa)
+        .unwrap();
+    eprintln!("IFA max supply: {}", ifa.max_supply().value());
+}
@@ -0,0 +1,688 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! On-chain audit of the history of a fungible asset contract.
+//!
+//! [`audit_onchain`] walks all the operations of a contract known to a stock and resolves their
+//! witness transactions. It reports witnesses which are unknown, unconfirmed or excluded from the
+//! contract state, operations spending state committed after their own witness, and any
+//! inconsistency between the issued supply declared by the operations and the amounts they
+//! actually create or destroy. Supply is checked only for schemata with a fungible asset. It also
+//! reports the operations breaking requirements which their schema doesn't enforce, mostly about
+//! block heights, see [`schemata::height`]: for bonds, redeems mined before the maturity height;
+//! for vesting assets, releases leaving locked less than their vesting schedule requires at the
+//! height they are mined; for timelocked assets, transfers mined before the lock height; for
+//! freezable assets, operations spending allocations assigned to a UTXO frozen before them; for
+//! epoch inflatable assets, epochs in which the inflations mined issue more than the cap committed
+//! for a single epoch; for rental tokens, reclaims mined while the lease they end is still in
+//! force; and for options, exercises mined after the expiry height and lapses mined up to it.
+//! Exercised and lapsed options count as burned. The locked allocations of vesting assets and the
+//! claims on the issuer of claimable assets count as asset units.
+//!
+//! Consignments are validated on import, so a stock normally passes the audit once all of its
+//! witnesses are mined; the audit gives custodians a single check of this before accepting a
+//! deposit, using their own resolver.
+
+use std::collections::BTreeMap;
+
+use rgbstd::containers::{Consignment, ConsignmentExt};
+use rgbstd::persistence::{
+    ConsignError, IndexProvider, StashProvider, StateProvider, Stock, StockError,
+};
+use rgbstd::validation::{ResolveWitness, WitnessStatus};
+use rgbstd::vm::WitnessOrd;
+use rgbstd::{
+    Amount, AssignmentType, Assignments, ContractId, ExposedSeal, GlobalState, OpId, Operation,
+    Opout, OwnedStateSchema, TransitionType, Txid,
+};
+use schemata::{GS_ISSUED_SUPPLY, OS_ASSET};
+use strict_types::StrictDeserialize;
+
+use crate::history::contract_history;
+
+/// Problem found by [`audit_onchain`].
+#[derive(Clone, Eq, PartialEq, Debug, Display)]
+#[display(doc_comments)]
+pub enum AuditIssue {
+    /// witness {0} is not known to the resolver.
+    Unresolved(Txid),
+
+    /// resolver failed to look up witness {0}: {1}.
+    ResolverFailure(Txid, String),
+
+    /// witness {0} is not mined yet.
+    Unconfirmed(Txid),
+
+    /// witness {0} is excluded from the contract state.
+    Excluded(Txid),
+
+    /// operation {op} spends output {input} committed by a witness ordered after its own one.
+    OutOfOrder { op: OpId, input: Opout },
+
+    /// operation {op} spends output {input}, which is not part of the contract history.
+    UnknownInput { op: OpId, input: Opout },
+
+    /// operation {0} declares an issued supply not matching the schema.
+    InvalidSupply(OpId),
+
+    /// operation {op} creates {created} units of the asset while declaring an issue of
+    /// {declared}.
+    IssueMismatch {
+        op: OpId,
+        created: u128,
+        declared: u128,
+    },
+
+    /// operation {op} destroys {destroyed} units of the asset without being a burn.
+    UnaccountedBurn { op: OpId, destroyed: u128 },
+
+    /// unspent allocations sum to {allocated}, while the circulating supply is {circulating}.
+    SupplyMismatch { allocated: u128, circulating: u128 },
+
+    /// operation {op} redeems bonds at height {height}, before their maturity at height
+    /// {maturity}.
+    #[cfg(feature = "bond")]
+    PrematureRedeem {
+        op: OpId,
+        height: u32,
+        maturity: u32,
+    },
+
+    /// operation {op} mined at height {height} keeps {locked} units of the asset locked, while
+    /// the vesting schedule requires {required}.
+    #[cfg(feature = "vesting")]
+    PrematureRelease {
+        op: OpId,
+        height: u32,
+        locked: u64,
+        required: u64,
+    },
+
+    /// operation {op} transfers timelocked assets at height {height}, before their lock height
+    /// {lock_height}.
+    #[cfg(feature = "timelock")]
+    PrematureTransfer {
+        op: OpId,
+        height: u32,
+        lock_height: u32,
+    },
+
+    /// operation {op} spends output {input} assigned to {utxo}, frozen by an earlier operation.
+    #[cfg(feature = "freezable")]
+    FrozenSpend {
+        op: OpId,
+        input: Opout,
+        utxo: rgbstd::Outpoint,
+    },
+
+    /// inflations mined in epoch {epoch} issue {inflated} units of the asset, above the cap of
+    /// {cap} for a single epoch.
+    #[cfg(feature = "eifa")]
+    EpochCapExceeded { epoch: u32, inflated: u64, cap: u64 },
+
+    /// operation {op} reclaims a lease at height {height}, before the lease expires after height
+    /// {expiry}.
+    #[cfg(feature = "rental")]
+    PrematureReclaim { op: OpId, height: u32, expiry: u32 },
+
+    /// operation {op} exercises options at height {height}, after their expiry at height
+    /// {expiry}.
+    #[cfg(feature = "option")]
+    LateExercise { op: OpId, height: u32, expiry: u32 },
+
+    /// operation {op} lapses options at height {height}, before they expire after height
+    /// {expiry}.
+    #[cfg(feature = "option")]
+    PrematureLapse { op: OpId, height: u32, expiry: u32 },
+}
+
+/// Result of [`audit_onchain`] and [`audit_consignment`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AuditReport {
+    pub contract_id: ContractId,
+    /// Witnesses of the contract operations, with their ordering if they were resolved.
+    pub witnesses: BTreeMap<Txid, Option<WitnessOrd>>,
+    /// Asset units issued by the valid operations.
+    pub issued: u128,
+    /// Asset units destroyed by the valid burn operations.
+    pub burned: u128,
+    /// Sum of the asset allocations not spent by any valid operation.
+    pub allocated: u128,
+    pub issues: Vec<AuditIssue>,
+}
+
+impl AuditReport {
+    /// Supply of the asset which is issued and not burned.
+    pub fn circulating(&self) -> u128 { self.issued.saturating_sub(self.burned) }
+
+    /// Checks whether the audit found no problem.
+    pub fn is_clean(&self) -> bool { self.issues.is_empty() }
+}
+
+/// Operation of the contract history, as seen by the audit.
+struct AuditedOp {
+    /// Witness ordering; `None` for the genesis and unresolved witnesses.
+    ord: Option<WitnessOrd>,
+    burn: bool,
+    inputs: Vec<Opout>,
+    /// Declared issued supply, `None` if it doesn't match the schema.
+    declared: Option<u128>,
+}
+
+/// Audits the full history of a contract known to the stock against the witnesses resolved by
+/// the resolver.
+pub fn audit_onchain<S: StashProvider, H: StateProvider, P: IndexProvider>(
+    stock: &Stock<S, H, P>,
+    contract_id: ContractId,
+    resolver: &impl ResolveWitness,
+) -> Result<AuditReport, StockError<S, H, P, ConsignError>> {
+    let history = contract_history(stock, contract_id)?;
+    Ok(audit_consignment(&history, resolver))
+}
+
+/// Audits the operations of a contract consignment against the witnesses resolved by the
+/// resolver.
+///
+/// Consignments only contain the history of their terminals, so the audit covers the whole
+/// contract only for consignments of all its operations, as built by [`audit_onchain`].
+pub fn audit_consignment<const TRANSFER: bool>(
+    consignment: &Consignment<TRANSFER>,
+    resolver: &impl ResolveWitness,
+) -> AuditReport {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("audit", contract_id = %consignment.contract_id()).entered();
+
+    let mut report = AuditReport {
+        contract_id: consignment.contract_id(),
+        witnesses: bmap! {},
+        issued: 0,
+        burned: 0,
+        allocated: 0,
+        issues: vec![],
+    };
+
+    let genesis = consignment.genesis();
+    let mut ops = bmap! { genesis.id() => AuditedOp {
+        ord: None,
+        burn: false,
+        inputs: vec![],
+        declared: declared_supply(&genesis.globals),
+    }};
+    let mut outputs = asset_outputs(genesis.id(), &genesis.assignments);
+    #[cfg(feature = "bond")]
+    let maturity = global_height(&genesis.globals, schemata::GS_MATURITY);
+    #[cfg(feature = "vesting")]
+    let schedule = vesting_schedule(&genesis.globals);
+    #[cfg(feature = "timelock")]
+    let lock_height = global_height(&genesis.globals, schemata::GS_LOCK_HEIGHT);
+    #[cfg(feature = "freezable")]
+    let mut utxos = asset_utxos(genesis.id(), &genesis.assignments, None);
+    #[cfg(feature = "freezable")]
+    let mut frozen = bmap! {};
+    #[cfg(feature = "eifa")]
+    let epoch_length =
+        global_amount(&genesis.globals, schemata::GS_EPOCH_LENGTH).filter(|length| *length > 0);
+    #[cfg(feature = "eifa")]
+    let mut epochs = BTreeMap::<u32, u64>::new();
+    #[cfg(feature = "option")]
+    let expiry = global_height(&genesis.globals, schemata::GS_EXPIRY);
+
+    for bundle in &consignment.bundles {
+        let witness_id = bundle.witness_id();
+        let ord = match resolver.resolve_witness(witness_id) {
+            Ok(WitnessStatus::Resolved(_, ord)) => Some(ord),
+            Ok(WitnessStatus::Unresolved) => {
+                report.issues.push(AuditIssue::Unresolved(witness_id));
+                None
+            }
+            Err(err) => {
+                report
+                    .issues
+                    .push(AuditIssue::ResolverFailure(witness_id, err.to_string()));
+                None
+            }
+        };
+        match ord {
+            Some(WitnessOrd::Mined(_)) | None => {}
+            Some(WitnessOrd::Tentative) => report.issues.push(AuditIssue::Unconfirmed(witness_id)),
+            Some(WitnessOrd::Ignored | WitnessOrd::Archived) => {
+                report.issues.push(AuditIssue::Excluded(witness_id))
+            }
+        }
+        report.witnesses.insert(witness_id, ord);
+        if matches!(ord, Some(WitnessOrd::Ignored | WitnessOrd::Archived)) {
+            continue;
+        }
+        for known in &bundle.bundle.known_transitions {
+            let transition = &known.transition;
+            #[cfg(feature = "bond")]
+            if let (Some(maturity), Some(WitnessOrd::Mined(pos))) = (maturity, ord) {
+                let height = pos.height().get();
+                if transition.transition_type == schemata::TS_REDEEM && height < maturity {
+                    report.issues.push(AuditIssue::PrematureRedeem {
+                        op: known.opid,
+                        height,
+                        maturity,
+                    });
+                }
+            }
+            #[cfg(feature = "vesting")]
+            if let (Some(schedule), Some(WitnessOrd::Mined(pos))) = (schedule, ord) {
+                let height = pos.height().get();
+                let locked =
+                    fungible_outputs(known.opid, &transition.assignments, schemata::OS_LOCKED)
+                        .values()
+                        .sum::<u64>();
+                let required = schedule.locked(height).value();
+                if transition.transition_type == schemata::TS_RELEASE && locked < required {
+                    report.issues.push(AuditIssue::PrematureRelease {
+                        op: known.opid,
+                        height,
+                        locked,
+                        required,
+                    });
+                }
+            }
+            #[cfg(feature = "timelock")]
+            if let (Some(lock_height), Some(WitnessOrd::Mined(pos))) = (lock_height, ord) {
+                let height = pos.height().get();
+                if transition.transition_type == schemata::TS_TRANSFER && height < lock_height {
+                    report.issues.push(AuditIssue::PrematureTransfer {
+                        op: known.opid,
+                        height,
+                        lock_height,
+                    });
+                }
+            }
+            #[cfg(feature = "eifa")]
+            if let (Some(length), Some(WitnessOrd::Mined(pos))) = (epoch_length, ord) {
+                if transition.transition_type == schemata::TS_INFLATION {
+                    let epoch = (pos.height().get() as u64 / length) as u32;
+                    let issued = declared_supply(&transition.globals).unwrap_or_default();
+                    let inflated = epochs.entry(epoch).or_default();
+                    *inflated = inflated.saturating_add(issued as u64);
+                }
+            }
+            #[cfg(feature = "rental")]
+            if let Some(WitnessOrd::Mined(pos)) = ord {
+                let height = pos.height().get();
+                let expiry = global_height(&transition.globals, schemata::GS_RECLAIMED_LEASES);
+                if let (schemata::TS_RECLAIM, Some(expiry)) = (transition.transition_type, expiry) {
+                    if height <= expiry {
+                        report.issues.push(AuditIssue::PrematureReclaim {
+                            op: known.opid,
+                            height,
+                            expiry,
+                        });
+                    }
+                }
+            }
+            #[cfg(feature = "option")]
+            if let (Some(expiry), Some(WitnessOrd::Mined(pos))) = (expiry, ord) {
+                let height = pos.height().get();
+                match transition.transition_type {
+                    schemata::TS_EXERCISE if height > expiry => {
+                        report.issues.push(AuditIssue::LateExercise {
+                            op: known.opid,
+                            height,
+                            expiry,
+                        })
+                    }
+                    schemata::TS_LAPSE if height <= expiry => {
+                        report.issues.push(AuditIssue::PrematureLapse {
+                            op: known.opid,
+                            height,
+                            expiry,
+                        })
+                    }
+                    _ => {}
+                }
+            }
+            #[cfg(feature = "freezable")]
+            {
+                utxos.extend(asset_utxos(known.opid, &transition.assignments, Some(witness_id)));
+                if transition.transition_type == schemata::TS_FREEZE {
+                    for utxo in frozen_utxos(&transition.globals) {
+                        frozen.entry(utxo).or_insert(ord);
+                    }
+                }
+            }
+            outputs.extend(asset_outputs(known.opid, &transition.assignments));
+            ops.insert(known.opid, AuditedOp {
+                ord,
+                burn: is_burn(transition.transition_type),
+                inputs: transition.inputs.iter().copied().collect(),
+                declared: declared_supply(&transition.globals),
+            });
+        }
+    }
+
+    #[cfg(feature = "eifa")]
+    if let Some(cap) = global_amount(&genesis.globals, schemata::GS_EPOCH_CAP) {
+        for (epoch, inflated) in epochs {
+            if inflated > cap {
+                report.issues.push(AuditIssue::EpochCapExceeded {
+                    epoch,
+                    inflated,
+                    cap,
+                });
+            }
+        }
+    }
+
+    let fungible = matches!(
+        consignment
+            .schema
+            .owned_types
+            .get(&OS_ASSET)
+            .map(|details| &details.owned_state_schema),
+        Some(OwnedStateSchema::Fungible(_))
+    );
+    let mut spent = bset! {};
+    for (opid, op) in &ops {
+        let mut consumed = 0u128;
+        for input in &op.inputs {
+            let Some(prev) = ops.get(&input.op) else {
+                report.issues.push(AuditIssue::UnknownInput {
+                    op: *opid,
+                    input: *input,
+                });
+                continue;
+            };
+            if matches!((prev.ord, op.ord), (Some(prev), Some(ord)) if prev > ord) {
+                report.issues.push(AuditIssue::OutOfOrder {
+                    op: *opid,
+                    input: *input,
+                });
+            }
+            #[cfg(feature = "freezable")]
+            if let Some(utxo) = utxos.get(input) {
+                if matches!((frozen.get(utxo), op.ord), (Some(Some(freeze)), Some(ord)) if *freeze < ord)
+                {
+                    report.issues.push(AuditIssue::FrozenSpend {
+                        op: *opid,
+                        input: *input,
+                        utxo: *utxo,
+                    });
+                }
+            }
+            if let Some(amount) = outputs.get(input) {
+                consumed += *amount as u128;
+                spent.insert(*input);
+            }
+        }
+        let created = outputs
+            .iter()
+            .filter(|(opout, _)| opout.op == *opid)
+            .map(|(_, amount)| *amount as u128)
+            .sum::<u128>();
+
+        if !fungible {
+            continue;
+        }
+        let Some(declared) = op.declared else {
+            report.issues.push(AuditIssue::InvalidSupply(*opid));
+            continue;
+        };
+        if created >= consumed {
+            if created - consumed != declared {
+                report.issues.push(AuditIssue::IssueMismatch {
+                    op: *opid,
+                    created: created - consumed,
+                    declared,
+                });
+            }
+            report.issued += declared;
+        } else if op.burn && declared == 0 {
+            report.burned += consumed - created;
+        } else {
+            report.issues.push(AuditIssue::UnaccountedBurn {
+                op: *opid,
+                destroyed: consumed - created,
+            });
+        }
+    }
+
+    if !fungible {
+        return report;
+    }
+    report.allocated = outputs
+        .iter()
+        .filter(|(opout, _)| !spent.contains(*opout))
+        .map(|(_, amount)| *amount as u128)
+        .sum();
+    if report.allocated != report.circulating() {
+        report.issues.push(AuditIssue::SupplyMismatch {
+            allocated: report.allocated,
+            circulating: report.circulating(),
+        });
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(issues = report.issues.len(), "audit complete");
+    report
+}
+
+/// Collects the outputs of an operation holding units of the asset.
+fn asset_outputs<Seal: ExposedSeal>(
+    opid: OpId,
+    assignments: &Assignments<Seal>,
+) -> BTreeMap<Opout, u64> {
+    #[allow(unused_mut)]
+    let mut outputs = fungible_outputs(opid, assignments, OS_ASSET);
+    #[cfg(feature = "vesting")]
+    outputs.extend(fungible_outputs(opid, assignments, schemata::OS_LOCKED));
+    #[cfg(feature = "claim")]
+    outputs.extend(fungible_outputs(opid, assignments, schemata::OS_CLAIM));
+    outputs
+}
+
+fn fungible_outputs<Seal: ExposedSeal>(
+    opid: OpId,
+    assignments: &Assignments<Seal>,
+    ty: AssignmentType,
+) -> BTreeMap<Opout, u64> {
+    assignments
+        .get(&ty)
+        .map(|assigns| {
+            assigns
+                .as_fungible()
+                .iter()
+                .enumerate()
+                .map(|(no, assign)| {
+                    let amount = Amount::from(*assign.as_revealed_state());
+                    (Opout::new(opid, ty, no as u16), amount.value())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collects the UTXOs to which the outputs of an operation holding units of the asset are
+/// assigned, resolving the seals pointing to the witness of the operation, if there is one.
+#[cfg(feature = "freezable")]
+fn asset_utxos<Seal: ExposedSeal>(
+    opid: OpId,
+    assignments: &Assignments<Seal>,
+    witness_id: Option<Txid>,
+) -> BTreeMap<Opout, rgbstd::Outpoint> {
+    let Some(assigns) = assignments.get(&OS_ASSET) else {
+        return bmap! {};
+    };
+    assigns
+        .as_fungible()
+        .iter()
+        .enumerate()
+        .filter_map(|(no, assign)| {
+            let seal = assign.revealed_seal()?;
+            let utxo = match witness_id {
+                Some(witness_id) => Some(seal.outpoint_or(witness_id)),
+                None => seal.outpoint(),
+            }?;
+            Some((Opout::new(opid, OS_ASSET, no as u16), utxo))
+        })
+        .collect()
+}
+
+/// Reads the UTXOs frozen by a freeze of a freezable asset, skipping invalid entries.
+#[cfg(feature = "freezable")]
+fn frozen_utxos(globals: &GlobalState) -> Vec<rgbstd::Outpoint> {
+    let Some(values) = globals.get(&schemata::GS_FROZEN) else {
+        return vec![];
+    };
+    values
+        .iter()
+        .filter_map(|data| rgbstd::Outpoint::from_strict_serialized(data.clone().into()).ok())
+        .collect()
+}
+
+fn declared_supply(globals: &GlobalState) -> Option<u128> {
+    let Some(values) = globals.get(&GS_ISSUED_SUPPLY) else {
+        return Some(0);
+    };
+    values.iter().try_fold(0u128, |sum, data| {
+        let amount = Amount::from_strict_serialized(data.clone().into()).ok()?;
+        Some(sum + amount.value() as u128)
+    })
+}
+
+/// Reads the single amount of a global state type, if present and valid.
+#[cfg(any(feature = "vesting", feature = "eifa"))]
+fn global_amount(globals: &GlobalState, ty: rgbstd::GlobalStateType) -> Option<u64> {
+    let data = globals.get(&ty)?.iter().next()?;
+    let amount = Amount::from_strict_serialized(data.clone().into()).ok()?;
+    Some(amount.value())
+}
+
+/// Reads the vesting schedule committed by the genesis of a vesting asset.
+#[cfg(feature = "vesting")]
+fn vesting_schedule(globals: &GlobalState) -> Option<schemata::VestingSchedule> {
+    Some(schemata::VestingSchedule {
+        vested: Amount::from(global_amount(globals, schemata::GS_VESTED_SUPPLY)?),
+        cliff: global_height(globals, schemata::GS_VESTING_CLIFF)?,
+        end: global_height(globals, schemata::GS_VESTING_END)?,
+    })
+}
+
+/// Reads the single block height of a global state type, if present and valid.
+#[cfg(any(
+    feature = "bond",
+    feature = "vesting",
+    feature = "timelock",
+    feature = "rental",
+    feature = "option"
+))]
+fn global_height(globals: &GlobalState, ty: rgbstd::GlobalStateType) -> Option<u32> {
+    use strict_types::encoding::bitcoin_types::LockTime;
+    use strict_types::encoding::StrictReader;
+    use strict_types::StrictDecode;
+
+    let data = globals.get(&ty)?.iter().next()?;
+    let mut reader = StrictReader::in_memory::<{ u16::MAX as usize }>(data.as_slice());
+    LockTime::strict_decode(&mut reader)
+        .ok()
+        .map(|height| height.0)
+}
+
+/// Checks whether the transition type destroys asset units: burns, redeems, and exercises and
+/// lapses of options.
+#[cfg(any(
+    feature = "ifa",
+    feature = "ria",
+    feature = "sba",
+    feature = "stablecoin",
+    feature = "bond",
+    feature = "wbtc",
+    feature = "option",
+    feature = "burnable"
+))]
+fn is_burn(ty: TransitionType) -> bool {
+    #[cfg(any(
+        feature = "ifa",
+        feature = "ria",
+        feature = "sba",
+        feature = "wbtc",
+        feature = "burnable"
+    ))]
+    if ty == schemata::TS_BURN {
+        return true;
+    }
+    #[cfg(any(feature = "stablecoin", feature = "bond"))]
+    if ty == schemata::TS_REDEEM {
+        return true;
+    }
+    #[cfg(feature = "option")]
+    if ty == schemata::TS_EXERCISE || ty == schemata::TS_LAPSE {
+        return true;
+    }
+    false
+}
+
+#[cfg(not(any(
+    feature = "ifa",
+    feature = "ria",
+    feature = "sba",
+    feature = "stablecoin",
+    feature = "bond",
+    feature = "wbtc",
+    feature = "option",
+    feature = "burnable"
+)))]
+fn is_burn(_: TransitionType) -> bool { false }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use rgbstd::persistence::fs::FsBinStore;
+    use schemata::dumb::MockResolver;
+
+    use super::*;
+
+    /// Stock of the schema fixtures of the core crate, see its `fixtures` module.
+    fn fixtures() -> (Stock, BTreeMap<String, ContractId>) {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../tests/fixtures/stock");
+        let stock = Stock::load(FsBinStore::new(dir.clone()).unwrap(), false).unwrap();
+        let ids = std::fs::read_to_string(dir.join("contracts.txt")).unwrap();
+        let ids = ids
+            .lines()
+            .map(|line| {
+                let (name, id) = line.split_once(' ').unwrap();
+                (name.to_owned(), id.parse().unwrap())
+            })
+            .collect();
+        (stock, ids)
+    }
+
+    #[test]
+    fn genesis_only() {
+        let (stock, ids) = fixtures();
+        for (name, contract_id) in &ids {
+            let report = audit_onchain(&stock, *contract_id, &MockResolver::new()).unwrap();
+            assert!(report.is_clean(), "{name}: {:?}", report.issues);
+            assert!(report.witnesses.is_empty());
+            assert_eq!(report.allocated, report.circulating(), "{name}");
+        }
+
+        let report = audit_onchain(&stock, ids["nia"], &MockResolver::new()).unwrap();
+        assert_eq!(report.issued, 1000);
+    }
+}
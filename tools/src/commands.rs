@@ -0,0 +1,173 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subcommands of the `rgb-schemas` binary operating on a [`PersistentStock`].
+//!
+//! Contracts are issued and validated on testnet4 unless the `RGB_CHAIN_NET` environment variable
+//! selects another network by its invoice prefix, like the examples do.
+
+use std::fmt::Display;
+use std::io;
+use std::str::FromStr;
+
+use rgbstd::containers::{ConsignmentExt, Contract, FileContent};
+use rgbstd::contract::FilterIncludeAll;
+use rgbstd::invoice::Precision;
+use rgbstd::stl::{AssetSpec, ContractTerms, RicardianContract};
+use rgbstd::validation::ValidationConfig;
+use rgbstd::{Amount, ChainNet, ContractId, GenesisSeal, Outpoint};
+use schemata::diff::ContractSnapshot;
+use schemata::dumb::MockResolver;
+use schemata::info::schema_info;
+use schemata::kit::KITS;
+use schemata::types::name_of;
+use schemata::NIA_SCHEMA_ID;
+use schemata_tools::stock::PersistentStock;
+
+use crate::USAGE;
+
+pub fn run(command: &str, args: &[String]) -> io::Result<()> {
+    let (dir, args) = args.split_first().ok_or_else(usage)?;
+    let mut stock = PersistentStock::open(dir).map_err(invalid_data)?;
+    match (command, args) {
+        ("issue", [ticker, name, precision, supply, outpoint]) => {
+            issue(&mut stock, ticker, name, precision, supply, outpoint)
+        }
+        ("state", [contract_id]) => state(&stock, contract_id),
+        ("validate", [path]) => validate(&mut stock, path),
+        _ => Err(usage()),
+    }?;
+    stock.save().map_err(invalid_data)
+}
+
+fn chain_net() -> io::Result<ChainNet> {
+    match std::env::var("RGB_CHAIN_NET") {
+        Ok(prefix) => ChainNet::from_str(&prefix).map_err(invalid_input),
+        Err(_) => Ok(ChainNet::BitcoinTestnet4),
+    }
+}
+
+/// Issues a NIA contract allocating its whole supply to `outpoint`.
+fn issue(
+    stock: &mut PersistentStock,
+    ticker: &str,
+    name: &str,
+    precision: &str,
+    supply: &str,
+    outpoint: &str,
+) -> io::Result<()> {
+    let precision = u8::from_str(precision).map_err(invalid_input)?;
+    let precision = Precision::try_from(precision).map_err(invalid_input)?;
+    let spec = AssetSpec::with(ticker, name, precision, None).map_err(invalid_input)?;
+    let supply = u64::from_str(supply).map_err(invalid_input)?;
+    let outpoint = Outpoint::from_str(outpoint).map_err(invalid_input)?;
+    let terms = ContractTerms {
+        text: RicardianContract::default(),
+        media: None,
+    };
+    let chain_net = chain_net()?;
+
+    let contract = stock
+        .contract_builder("ssi:anonymous", NIA_SCHEMA_ID, chain_net)
+        .map_err(invalid_data)?
+        .add_global_state("spec", spec)
+        .and_then(|builder| builder.add_global_state("terms", terms))
+        .and_then(|builder| builder.add_global_state("issuedSupply", Amount::from(supply)))
+        .and_then(|builder| {
+            let seal = GenesisSeal::new_random(outpoint.txid, outpoint.vout);
+            builder.add_fungible_state("assetOwner", seal, supply)
+        })
+        .map_err(invalid_input)?
+        .issue_contract()
+        .map_err(invalid_input)?;
+    let contract_id = contract.contract_id();
+    stock
+        .import_contract(contract, MockResolver::with_chain_net(chain_net))
+        .map_err(invalid_data)?;
+    println!("{contract_id}");
+    Ok(())
+}
+
+/// Prints the global state and the allocations of a contract known to the stock.
+fn state(stock: &PersistentStock, contract_id: &str) -> io::Result<()> {
+    let contract_id = ContractId::from_str(contract_id).map_err(invalid_input)?;
+    let snapshot =
+        ContractSnapshot::from_stock(stock, contract_id, FilterIncludeAll).map_err(invalid_data)?;
+    match schema_info(snapshot.schema_id) {
+        Some(info) => println!("{contract_id} {} {}", info.name, info.version),
+        None => println!("{contract_id} {}", snapshot.schema_id),
+    }
+    for (ty, entries) in &snapshot.globals {
+        let name = name_of(*ty).unwrap_or("<unknown>");
+        println!("global {name:<20} {} entries", entries.len());
+    }
+    for allocation in snapshot.fungible.values() {
+        let name = name_of(allocation.opout.ty).unwrap_or("<unknown>");
+        println!("owned  {name:<20} {} {}", allocation.seal, allocation.state.value());
+    }
+    for allocation in snapshot.data.values() {
+        let name = name_of(allocation.opout.ty).unwrap_or("<unknown>");
+        println!("owned  {name:<20} {} data", allocation.seal);
+    }
+    for allocation in snapshot.rights.values() {
+        let name = name_of(allocation.opout.ty).unwrap_or("<unknown>");
+        println!("owned  {name:<20} {} right", allocation.seal);
+    }
+    Ok(())
+}
+
+/// Validates a contract file and imports it into the stock.
+///
+/// Witnesses can't be resolved yet, so only contracts without state transitions pass validation.
+fn validate(stock: &mut PersistentStock, path: &str) -> io::Result<()> {
+    let contract = Contract::load_file(path).map_err(invalid_data)?;
+    let schema_id = contract.schema_id();
+    let (_, _, kit) = KITS
+        .iter()
+        .find(|(_, id, _)| *id == schema_id)
+        .ok_or_else(|| invalid_data(format!("unknown schema {schema_id}")))?;
+    let chain_net = chain_net()?;
+    let resolver = MockResolver::with_chain_net(chain_net);
+    let config = ValidationConfig {
+        chain_net,
+        trusted_typesystem: kit().types,
+        ..Default::default()
+    };
+    let contract = contract
+        .validate(&resolver, &config)
+        .map_err(|err| invalid_data(format!("{err:?}")))?;
+    let contract_id = contract.contract_id();
+    stock
+        .import_contract(contract, resolver)
+        .map_err(invalid_data)?;
+    println!("{contract_id} is valid");
+    Ok(())
+}
+
+fn usage() -> io::Error { invalid_input(USAGE) }
+
+fn invalid_input(err: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err.to_string())
+}
+
+fn invalid_data(err: impl Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
@@ -0,0 +1,340 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! History of the state transitions of a contract.
+//!
+//! The contract state only keeps the allocations and the global state of a contract, so the
+//! transitions are read from a consignment of its history, like the one built out of a stock by
+//! [`contract_history`]. [`transitions`] decodes the inputs and outputs of each transition found
+//! in the consignment and orders them by their witnesses, which lets auditors reconstruct the
+//! history of an asset. [`TransitionHistory`] exposes it on the contract wrappers, and
+//! [`IfaHistory`] computes the supplies of the inflatable fungible assets from their burns.
+//!
+//! The contract state only records the ordering of the witnesses of transitions leaving some state
+//! behind, so the witnesses of the others, like burns, are looked up with a resolver.
+
+use std::collections::BTreeMap;
+
+use rgbstd::containers::{Consignment, ConsignmentExt, Transfer};
+use rgbstd::contract::{AllocatedState, ContractData};
+use rgbstd::persistence::{
+    ConsignError, ContractStateRead, IndexProvider, StashProvider, StashProviderError,
+    StateProvider, Stock, StockError,
+};
+use rgbstd::validation::{ResolveWitness, WitnessStatus};
+use rgbstd::vm::WitnessOrd;
+#[cfg(feature = "ifa")]
+use rgbstd::Amount;
+use rgbstd::{
+    Assignments, ContractId, ExposedSeal, OpId, Operation, Opout, OutputSeal, TransitionType, Txid,
+    TypedAssigns,
+};
+use schemata::{ContractWrapper, Error};
+#[cfg(feature = "ifa")]
+use schemata::{IfaWrapper, OS_ASSET, TS_BURN};
+
+/// Input or output of a transition listed by a [`HistoryEntry`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HistoryAllocation {
+    pub opout: Opout,
+    /// Seal of the allocation, `None` if it is concealed.
+    pub seal: Option<OutputSeal>,
+    pub state: AllocatedState,
+}
+
+/// State transition listed by [`transitions`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HistoryEntry {
+    pub opid: OpId,
+    pub transition_type: TransitionType,
+    pub witness_id: Txid,
+    /// Ordering of the witness, `None` if neither the contract state nor the resolver know it.
+    pub witness_ord: Option<WitnessOrd>,
+    /// Allocations spent by the transition. Inputs missing from the consignment are skipped.
+    pub inputs: Vec<HistoryAllocation>,
+    /// Allocations created by the transition.
+    pub outputs: Vec<HistoryAllocation>,
+}
+
+/// Builds a consignment of all the operations of a contract known to the stock.
+pub fn contract_history<S: StashProvider, H: StateProvider, P: IndexProvider>(
+    stock: &Stock<S, H, P>,
+    contract_id: ContractId,
+) -> Result<Transfer, StockError<S, H, P, ConsignError>> {
+    type Error<S, H, P> = StockError<S, H, P, ConsignError>;
+    let stash = stock.as_stash_provider();
+    let mut opids = bset! {};
+    for bundle_id in stash.bundle_ids().map_err(Error::StashRead)? {
+        let bundle = stash.bundle(bundle_id).map_err(|err| match err {
+            StashProviderError::Inconsistency(err) => Error::from(err),
+            StashProviderError::Connectivity(err) => Error::StashRead(err),
+        })?;
+        opids.extend(
+            bundle
+                .known_transitions
+                .iter()
+                .filter(|known| known.transition.contract_id == contract_id)
+                .map(|known| known.opid),
+        );
+    }
+    stock.transfer(contract_id, [], [], opids, None)
+}
+
+/// Lists the transitions of the history of a contract, or only those of the given type, ordered
+/// by their witnesses.
+///
+/// Transitions whose witness is unknown come last; transitions sharing a witness keep their order
+/// in the consignment.
+pub fn transitions<S: ContractStateRead, const TRANSFER: bool>(
+    data: &ContractData<S>,
+    history: &Consignment<TRANSFER>,
+    ty: Option<TransitionType>,
+    resolver: &impl ResolveWitness,
+) -> Result<Vec<HistoryEntry>, Error> {
+    let contract_id = data.state.contract_id();
+    if history.contract_id() != contract_id {
+        return Err(Error::ContractMismatch {
+            expected: contract_id,
+            actual: history.contract_id(),
+        });
+    }
+
+    let genesis = history.genesis();
+    let mut outputs = allocations(genesis.id(), &genesis.assignments, None)
+        .into_iter()
+        .map(|allocation| (allocation.opout, allocation))
+        .collect::<BTreeMap<_, _>>();
+    let mut entries = vec![];
+    for bundle in &history.bundles {
+        let witness_id = bundle.witness_id();
+        for known in &bundle.bundle.known_transitions {
+            let transition = &known.transition;
+            let created = allocations(known.opid, &transition.assignments, Some(witness_id));
+            outputs.extend(
+                created
+                    .iter()
+                    .map(|allocation| (allocation.opout, allocation.clone())),
+            );
+            if ty.is_some_and(|ty| ty != transition.transition_type) {
+                continue;
+            }
+            let entry = HistoryEntry {
+                opid: known.opid,
+                transition_type: transition.transition_type,
+                witness_id,
+                witness_ord: data.state.witness_ord(witness_id).or_else(|| {
+                    match resolver.resolve_witness(witness_id) {
+                        Ok(WitnessStatus::Resolved(_, ord)) => Some(ord),
+                        _ => None,
+                    }
+                }),
+                inputs: vec![],
+                outputs: created,
+            };
+            entries.push((entry, transition.inputs.iter().copied().collect::<Vec<_>>()));
+        }
+    }
+
+    // Inputs are resolved once all the outputs are known, since bundles aren't sorted
+    let mut entries = entries
+        .into_iter()
+        .map(|(mut entry, inputs)| {
+            entry.inputs = inputs
+                .iter()
+                .filter_map(|input| outputs.get(input).cloned())
+                .collect();
+            entry
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| (entry.witness_ord.is_none(), entry.witness_ord));
+    Ok(entries)
+}
+
+/// History accessors of the contract wrappers, implemented for all of them.
+pub trait TransitionHistory<S: ContractStateRead>: ContractWrapper<S> {
+    /// Lists the transitions of the given type found in a consignment of the contract history,
+    /// ordered by their witnesses, see [`transitions`].
+    fn transitions<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        ty: TransitionType,
+        resolver: &impl ResolveWitness,
+    ) -> Vec<HistoryEntry> {
+        or_panic(self.try_transitions(history, ty, resolver))
+    }
+
+    fn try_transitions<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        ty: TransitionType,
+        resolver: &impl ResolveWitness,
+    ) -> Result<Vec<HistoryEntry>, Error> {
+        transitions(self.contract_data(), history, Some(ty), resolver)
+    }
+}
+
+impl<S: ContractStateRead, W: ContractWrapper<S>> TransitionHistory<S> for W {}
+
+/// Supplies of an inflatable fungible asset computed from the burns of a consignment of the
+/// contract history, like the one built by [`contract_history`].
+///
+/// [`IfaWrapper`] reads the burned and circulating supplies from the burned supply global state,
+/// which the first revision of the schema doesn't record, so this is needed for its contracts.
+/// It also lists the burn transitions with the allocations they spend.
+#[cfg(feature = "ifa")]
+pub trait IfaHistory {
+    /// Returns the burns found in the contract history, ordered by their witnesses.
+    fn burn_transitions<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Vec<HistoryEntry> {
+        or_panic(self.try_burn_transitions(history, resolver))
+    }
+
+    fn try_burn_transitions<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Result<Vec<HistoryEntry>, Error>;
+
+    /// Returns the supply destroyed by the burns of the contract history, see
+    /// [`IfaHistory::burn_transitions`].
+    ///
+    /// Only the burned asset allocations count, not the burned inflation allowance, and burns
+    /// whose witness is excluded from the contract state are skipped.
+    fn burned_in_history<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Amount {
+        or_panic(self.try_burned_in_history(history, resolver))
+    }
+
+    fn try_burned_in_history<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Result<Amount, Error> {
+        let mut burned = Amount::ZERO;
+        for burn in self.try_burn_transitions(history, resolver)? {
+            if matches!(burn.witness_ord, Some(WitnessOrd::Ignored | WitnessOrd::Archived)) {
+                continue;
+            }
+            for input in burn
+                .inputs
+                .iter()
+                .filter(|input| input.opout.ty == OS_ASSET)
+            {
+                if let AllocatedState::Amount(value) = input.state {
+                    burned.saturating_add_assign(value.as_u64());
+                }
+            }
+        }
+        Ok(burned)
+    }
+
+    /// Returns the supply issued by the genesis and the inflations which isn't burned by the burns
+    /// of the contract history.
+    fn circulating_in_history<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Amount {
+        or_panic(self.try_circulating_in_history(history, resolver))
+    }
+
+    fn try_circulating_in_history<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Result<Amount, Error>;
+}
+
+#[cfg(feature = "ifa")]
+impl<S: ContractStateRead> IfaHistory for IfaWrapper<S> {
+    fn try_burn_transitions<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Result<Vec<HistoryEntry>, Error> {
+        self.try_transitions(history, TS_BURN, resolver)
+    }
+
+    fn try_circulating_in_history<const TRANSFER: bool>(
+        &self,
+        history: &Consignment<TRANSFER>,
+        resolver: &impl ResolveWitness,
+    ) -> Result<Amount, Error> {
+        let issued = self.try_total_issued_supply()?.value();
+        let burned = self.try_burned_in_history(history, resolver)?.value();
+        Ok(Amount::from(issued.saturating_sub(burned)))
+    }
+}
+
+fn or_panic<T>(res: Result<T, Error>) -> T { res.unwrap_or_else(|err| panic!("{err}")) }
+
+/// Decodes the outputs of an operation, resolving the seals pointing to the witness of the
+/// operation, if there is one.
+fn allocations<Seal: ExposedSeal>(
+    opid: OpId,
+    assignments: &Assignments<Seal>,
+    witness_id: Option<Txid>,
+) -> Vec<HistoryAllocation> {
+    let resolve = |seal: Option<Seal>| {
+        let seal = seal?;
+        match witness_id {
+            Some(witness_id) => Some(seal.to_output_seal_or_default(witness_id)),
+            None => seal.to_output_seal(),
+        }
+    };
+    let mut allocations = vec![];
+    for (ty, assigns) in assignments.iter() {
+        let states = match assigns {
+            TypedAssigns::Declarative(assigns) => assigns
+                .iter()
+                .map(|assign| (resolve(assign.revealed_seal()), AllocatedState::Void))
+                .collect::<Vec<_>>(),
+            TypedAssigns::Fungible(assigns) => assigns
+                .iter()
+                .map(|assign| {
+                    let state = AllocatedState::Amount(*assign.as_revealed_state());
+                    (resolve(assign.revealed_seal()), state)
+                })
+                .collect(),
+            TypedAssigns::Structured(assigns) => assigns
+                .iter()
+                .map(|assign| {
+                    let state = AllocatedState::Data(assign.as_revealed_state().clone());
+                    (resolve(assign.revealed_seal()), state)
+                })
+                .collect(),
+        };
+        allocations.extend(states.into_iter().enumerate().map(|(no, (seal, state))| {
+            HistoryAllocation {
+                opout: Opout::new(opid, *ty, no as u16),
+                seal,
+                state,
+            }
+        }));
+    }
+    allocations
+}
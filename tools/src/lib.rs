@@ -0,0 +1,42 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tooling around the RGB schemata: the reference transfer workflow, contract history and
+//! on-chain audits, witness resolvers, a file-backed stock, test helpers and the `rgb-schemas`
+//! binary.
+//!
+//! Schema definitions live in the `rgb-schemata-core` crate, which keeps only the schemata, their
+//! scripts and their wrappers; everything working with stocks, consignment histories, network
+//! clients or other heavy dependencies belongs here.
+
+#[macro_use]
+extern crate amplify;
+
+pub mod audit;
+pub mod history;
+#[cfg(feature = "reject-list")]
+pub mod reject;
+pub mod resolvers;
+#[cfg(feature = "fs")]
+pub mod stock;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod workflow;
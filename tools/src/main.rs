@@ -0,0 +1,112 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "fs")]
+mod commands;
+
+use std::io;
+use std::io::stdout;
+use std::path::Path;
+
+use amplify::Wrapper;
+use rgbstd::containers::{FileContent, Kit};
+use rgbstd::persistence::MemContract;
+use rgbstd::vm::RgbIsa;
+use schemata::info::schema_info;
+use schemata::kit::{save_kits, KITS};
+use schemata::types::{name_of, NamedType};
+
+fn main() -> io::Result<()> {
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+        .with_writer(io::stderr)
+        .init();
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        None | Some("publish") => publish(),
+        #[cfg(feature = "fs")]
+        Some(command @ ("issue" | "state" | "validate")) => commands::run(command, &args[1..]),
+        Some(command) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unknown command {command}\n\n{USAGE}"),
+        )),
+    }
+}
+
+#[cfg(feature = "fs")]
+const USAGE: &str = "usage: rgb-schemas [publish]
+       rgb-schemas issue <stock-dir> <ticker> <name> <precision> <supply> <txid:vout>
+       rgb-schemas state <stock-dir> <contract-id>
+       rgb-schemas validate <stock-dir> <contract-file>";
+
+#[cfg(not(feature = "fs"))]
+const USAGE: &str = "usage: rgb-schemas [publish]";
+
+/// Saves the kits of all the schemata to the `schemata` directory and prints their details.
+fn publish() -> io::Result<()> {
+    let dir = Path::new("schemata");
+    save_kits(dir)?;
+    for &(name, _, _) in KITS {
+        let kit = Kit::load_file(dir.join(format!("{name}.rgb")))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+        print_info(&kit);
+        print_types(&kit);
+        print_lib(&kit);
+    }
+
+    Ok(())
+}
+
+fn print_info(kit: &Kit) {
+    let schema_id = kit.schemata.first().unwrap().schema_id();
+    let Some(info) = schema_info(schema_id) else {
+        eprintln!("{schema_id} has no schema info");
+        return;
+    };
+    eprintln!("{} {} by {}", info.name, info.version, info.developer);
+    eprintln!("  schema id:     {}", info.schema_id);
+    eprintln!("  release notes: {}", info.release_notes_hash());
+}
+
+fn print_types(kit: &Kit) {
+    fn print<T: NamedType + Wrapper<Inner = u16>>(kind: &str, types: impl IntoIterator<Item = T>) {
+        for ty in types {
+            eprintln!("{kind:<10} {:>5} {}", ty.to_inner(), name_of(ty).unwrap_or("<unknown>"));
+        }
+    }
+
+    let schema = kit.schemata.first().unwrap();
+    print("global", schema.global_types.keys().copied());
+    print("owned", schema.owned_types.keys().copied());
+    print("transition", schema.transitions.keys().copied());
+    print("meta", schema.meta_types.keys().copied());
+}
+
+fn print_lib(kit: &Kit) {
+    let alu_lib = kit.scripts.first().unwrap();
+    eprintln!("{alu_lib}");
+    alu_lib
+        .print_disassemble::<RgbIsa<MemContract>>(stdout())
+        .unwrap();
+}
@@ -0,0 +1,130 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2026 by
+//     RGB schemas contributors
+//
+// Copyright (C) 2026 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fetching of the reject lists published by asset issuers.
+//!
+//! Reject list URLs come from contracts issued by third parties, so lists are only fetched over
+//! HTTPS, without following redirects, within [`FETCH_TIMEOUT`] and up to
+//! [`MAX_REJECT_LIST_SIZE`] bytes.
+
+use std::io::Read;
+
+use rgbstd::persistence::ContractStateRead;
+use rgbstd::stl::RejectListUrl;
+use schemata::reject::{RejectList, RejectListError};
+use schemata::IfaWrapper;
+
+/// Timeout of a reject list request, in seconds.
+pub const FETCH_TIMEOUT: u64 = 30;
+
+/// Maximum size of a reject list, in bytes.
+pub const MAX_REJECT_LIST_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Error fetching a reject list.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum FetchError {
+    /// reject list URL '{0}' doesn't use HTTPS.
+    NotHttps(String),
+
+    /// unable to fetch the reject list: {0}.
+    Fetch(String),
+
+    /// reject list server responded with HTTP status {0}.
+    Status(i32),
+
+    /// reject list exceeds the maximum size of {MAX_REJECT_LIST_SIZE} bytes.
+    TooLarge,
+
+    /// reject list is not a valid UTF-8 text.
+    NotUtf8,
+
+    /// {0}
+    #[from]
+    Parse(RejectListError),
+
+    /// {0}
+    #[from]
+    Contract(schemata::Error),
+}
+
+/// Returns the HTTPS URL of a reject list, adding the scheme to URLs without one, like those
+/// usually stored in contracts, and refusing URLs with any other scheme.
+pub fn https_url(url: &RejectListUrl) -> Result<String, FetchError> {
+    match url.as_str().split_once("://") {
+        None => Ok(format!("https://{}", url.as_str())),
+        Some((scheme, _)) if scheme.eq_ignore_ascii_case("https") => Ok(url.as_str().to_owned()),
+        Some(_) => Err(FetchError::NotHttps(url.as_str().to_owned())),
+    }
+}
+
+/// Fetches and parses the reject list published at the URL.
+pub fn fetch(url: &RejectListUrl) -> Result<RejectList, FetchError> {
+    let url = https_url(url)?;
+    #[cfg(feature = "tracing")]
+    tracing::debug!(%url, "fetching reject list");
+    let response = minreq::get(url)
+        .with_timeout(FETCH_TIMEOUT)
+        .with_follow_redirects(false)
+        .send_lazy()
+        .map_err(|err| FetchError::Fetch(err.to_string()))?;
+    if response.status_code != 200 {
+        return Err(FetchError::Status(response.status_code));
+    }
+    let mut body = vec![];
+    Read::take(response, MAX_REJECT_LIST_SIZE + 1)
+        .read_to_end(&mut body)
+        .map_err(|err| FetchError::Fetch(err.to_string()))?;
+    if body.len() as u64 > MAX_REJECT_LIST_SIZE {
+        return Err(FetchError::TooLarge);
+    }
+    let body = String::from_utf8(body).map_err(|_| FetchError::NotUtf8)?;
+    Ok(body.parse()?)
+}
+
+/// Fetches the reject list published by the issuer of an IFA contract, if the contract has one.
+pub fn fetch_ifa<S: ContractStateRead>(
+    contract: &IfaWrapper<S>,
+) -> Result<Option<RejectList>, FetchError> {
+    contract
+        .try_reject_list_url()?
+        .map(|url| fetch(&url))
+        .transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn url(s: &str) -> RejectListUrl { RejectListUrl::from_str(s).unwrap() }
+
+    #[test]
+    fn https_only() {
+        assert_eq!(https_url(&url("example.com/list")), Ok(s!("https://example.com/list")));
+        assert_eq!(https_url(&url("HTTPS://example.com/list")), Ok(s!("HTTPS://example.com/list")));
+        for insecure in ["http://example.com/list", "ftp://example.com/list"] {
+            assert_eq!(https_url(&url(insecure)), Err(FetchError::NotHttps(insecure.to_owned())));
+            assert_eq!(fetch(&url(insecure)), Err(FetchError::NotHttps(insecure.to_owned())));
+        }
+    }
+}
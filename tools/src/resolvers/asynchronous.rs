@@ -0,0 +1,218 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asynchronous witness resolvers and an adapter exposing them as [`ResolveWitness`].
+
+use std::future::Future;
+use std::num::NonZeroU32;
+
+use esplora_client::AsyncClient;
+pub use esplora_client::Builder as EsploraBuilder;
+use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{ChainNet, Txid};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::task;
+
+/// Asynchronous counterpart of [`ResolveWitness`].
+pub trait ResolveWitnessAsync {
+    /// Provide the [`WitnessStatus`] for a TX with the given `witness_id`.
+    fn resolve_witness(
+        &self,
+        witness_id: Txid,
+    ) -> impl Future<Output = Result<WitnessStatus, WitnessResolverError>> + Send;
+
+    /// Check that the resolver works with the expected [`ChainNet`].
+    fn check_chain_net(
+        &self,
+        chain_net: ChainNet,
+    ) -> impl Future<Output = Result<(), WitnessResolverError>> + Send;
+}
+
+/// [`ResolveWitnessAsync`] implementation backed by an Esplora server.
+pub struct EsploraAsyncResolver {
+    inner: AsyncClient,
+}
+
+impl EsploraAsyncResolver {
+    pub fn with(builder: EsploraBuilder) -> Result<Self, esplora_client::Error> {
+        Ok(Self {
+            inner: builder.build_async()?,
+        })
+    }
+}
+
+impl From<AsyncClient> for EsploraAsyncResolver {
+    fn from(inner: AsyncClient) -> Self { Self { inner } }
+}
+
+impl ResolveWitnessAsync for EsploraAsyncResolver {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err(Display)))]
+    async fn resolve_witness(&self, txid: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        let Some(tx) = self
+            .inner
+            .get_tx(&txid)
+            .await
+            .map_err(|e| WitnessResolverError::ResolverIssue(Some(txid), e.to_string()))?
+        else {
+            return Ok(WitnessStatus::Unresolved);
+        };
+        let status = self
+            .inner
+            .get_tx_status(&txid)
+            .await
+            .map_err(|e| WitnessResolverError::ResolverIssue(Some(txid), e.to_string()))?;
+        let ord = match status.block_height.zip(status.block_time) {
+            Some((height, time)) => {
+                let height =
+                    NonZeroU32::new(height).ok_or(WitnessResolverError::InvalidResolverData)?;
+                WitnessOrd::Mined(
+                    WitnessPos::bitcoin(height, time as i64)
+                        .ok_or(WitnessResolverError::InvalidResolverData)?,
+                )
+            }
+            None => WitnessOrd::Tentative,
+        };
+        Ok(WitnessStatus::Resolved(tx, ord))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err(Display)))]
+    async fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        let block_hash = self
+            .inner
+            .get_block_hash(0)
+            .await
+            .map_err(|e| WitnessResolverError::ResolverIssue(None, e.to_string()))?;
+        if chain_net.genesis_block_hash() != block_hash {
+            return Err(WitnessResolverError::WrongChainNet);
+        }
+        Ok(())
+    }
+}
+
+/// Adapter exposing a [`ResolveWitnessAsync`] implementation through the synchronous
+/// [`ResolveWitness`] trait, driving its futures on the provided tokio runtime.
+///
+/// Validation is synchronous and should be run with [`tokio::task::spawn_blocking`], so that the
+/// executor threads are not blocked while waiting for the resolver. When called from a worker
+/// thread of a multi-threaded runtime the adapter falls back to [`task::block_in_place`]; calling
+/// it from within a current-thread runtime panics.
+pub struct BlockingAdapter<R: ResolveWitnessAsync> {
+    inner: R,
+    handle: Handle,
+}
+
+impl<R: ResolveWitnessAsync> BlockingAdapter<R> {
+    /// Wrap `inner`, driving its futures on the runtime behind `handle`.
+    pub fn with(inner: R, handle: Handle) -> Self { Self { inner, handle } }
+
+    /// Wrap `inner`, driving its futures on the current runtime.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the context of a tokio runtime.
+    pub fn current(inner: R) -> Self { Self::with(inner, Handle::current()) }
+
+    pub fn inner(&self) -> &R { &self.inner }
+
+    pub fn into_inner(self) -> R { self.inner }
+
+    fn block_on<F: Future>(&self, fut: F) -> F::Output {
+        match Handle::try_current() {
+            Ok(current) if current.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                task::block_in_place(|| self.handle.block_on(fut))
+            }
+            _ => self.handle.block_on(fut),
+        }
+    }
+}
+
+impl<R: ResolveWitnessAsync> ResolveWitness for BlockingAdapter<R> {
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        self.block_on(self.inner.resolve_witness(witness_id))
+    }
+
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        self.block_on(self.inner.check_chain_net(chain_net))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::bitcoin::absolute::LockTime;
+    use rgbstd::bitcoin::transaction::Version;
+    use rgbstd::bitcoin::Transaction as Tx;
+    use schemata::dumb::MockResolver;
+    use tokio::runtime::Builder;
+
+    use super::*;
+
+    struct AsyncMock(MockResolver);
+
+    impl ResolveWitnessAsync for AsyncMock {
+        async fn resolve_witness(
+            &self,
+            witness_id: Txid,
+        ) -> Result<WitnessStatus, WitnessResolverError> {
+            task::yield_now().await;
+            self.0.resolve_witness(witness_id)
+        }
+
+        async fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+            self.0.check_chain_net(chain_net)
+        }
+    }
+
+    #[test]
+    fn blocking_adapter() {
+        let tx = Tx {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let txid = tx.compute_txid();
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(1)
+            .build()
+            .unwrap();
+        let mock = MockResolver::with_chain_net(ChainNet::BitcoinRegtest)
+            .with_witness(tx.clone(), WitnessOrd::Tentative);
+        let adapter = BlockingAdapter::with(AsyncMock(mock), runtime.handle().clone());
+
+        // from a thread outside the runtime
+        assert_eq!(
+            adapter.resolve_witness(txid).unwrap(),
+            WitnessStatus::Resolved(tx.clone(), WitnessOrd::Tentative)
+        );
+
+        // from a blocking task and from a worker thread of the runtime
+        let adapter = std::sync::Arc::new(adapter);
+        let blocking = adapter.clone();
+        runtime.block_on(async move {
+            task::spawn_blocking(move || blocking.check_chain_net(ChainNet::BitcoinRegtest))
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                adapter.check_chain_net(ChainNet::BitcoinMainnet),
+                Err(WitnessResolverError::WrongChainNet)
+            );
+        });
+    }
+}
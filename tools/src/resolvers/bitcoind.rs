@@ -0,0 +1,141 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolver backed by the JSON-RPC interface of Bitcoin Core.
+
+use std::num::NonZeroU32;
+
+use bitcoincore_rpc::jsonrpc::error::{Error as JsonRpcError, RpcError};
+pub use bitcoincore_rpc::Auth;
+use bitcoincore_rpc::{Client, Error as RpcClientError, RpcApi};
+use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{ChainNet, Txid};
+
+/// Error code returned by bitcoind when a TX is unknown (`RPC_INVALID_ADDRESS_OR_KEY`).
+const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
+
+/// [`ResolveWitness`] implementation backed by a Bitcoin Core node.
+///
+/// Witness TXs are looked up with `getrawtransaction` and their mining position is read with
+/// `getblockheader`, so the node must run with `-txindex=1` to resolve TXs which are not in its
+/// mempool or wallet.
+pub struct BitcoindResolver {
+    client: Client,
+}
+
+impl BitcoindResolver {
+    /// Connect to the JSON-RPC server at the given `url`, authenticating with either a cookie file
+    /// or a username and password.
+    pub fn with(url: &str, auth: Auth) -> Result<Self, RpcClientError> {
+        Ok(Self {
+            client: Client::new(url, auth)?,
+        })
+    }
+
+    /// Access the underlying RPC client.
+    pub fn client(&self) -> &Client { &self.client }
+}
+
+impl From<Client> for BitcoindResolver {
+    fn from(client: Client) -> Self { Self { client } }
+}
+
+impl ResolveWitness for BitcoindResolver {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err(Display)))]
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        let tx_info = match self.client.get_raw_transaction_info(&witness_id, None) {
+            Err(e) if is_unknown_tx(&e) => return Ok(WitnessStatus::Unresolved),
+            Err(e) => {
+                return Err(WitnessResolverError::ResolverIssue(Some(witness_id), e.to_string()));
+            }
+            Ok(tx_info) => tx_info,
+        };
+        let tx = tx_info
+            .transaction()
+            .map_err(|_| WitnessResolverError::InvalidResolverData)?;
+        if tx.compute_txid() != witness_id {
+            return Err(WitnessResolverError::IdMismatch {
+                actual: tx.compute_txid(),
+                expected: witness_id,
+            });
+        }
+
+        let Some(block_hash) = tx_info.blockhash else {
+            return Ok(WitnessStatus::Resolved(tx, WitnessOrd::Tentative));
+        };
+        let header = self
+            .client
+            .get_block_header_info(&block_hash)
+            .map_err(|e| WitnessResolverError::ResolverIssue(Some(witness_id), e.to_string()))?;
+        // a negative number of confirmations means the block is not in the active chain anymore
+        if header.confirmations <= 0 {
+            return Ok(WitnessStatus::Resolved(tx, WitnessOrd::Tentative));
+        }
+
+        let height = u32::try_from(header.height)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .ok_or(WitnessResolverError::InvalidResolverData)?;
+        let timestamp =
+            i64::try_from(header.time).map_err(|_| WitnessResolverError::InvalidResolverData)?;
+        let pos = WitnessPos::bitcoin(height, timestamp)
+            .ok_or(WitnessResolverError::InvalidResolverData)?;
+
+        Ok(WitnessStatus::Resolved(tx, WitnessOrd::Mined(pos)))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err(Display)))]
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        let block_hash = self
+            .client
+            .get_block_hash(0)
+            .map_err(|e| WitnessResolverError::ResolverIssue(None, e.to_string()))?;
+        if chain_net.genesis_block_hash() != block_hash {
+            return Err(WitnessResolverError::WrongChainNet);
+        }
+        Ok(())
+    }
+}
+
+fn is_unknown_tx(err: &RpcClientError) -> bool {
+    matches!(
+        err,
+        RpcClientError::JsonRpc(JsonRpcError::Rpc(RpcError { code, .. }))
+            if *code == RPC_INVALID_ADDRESS_OR_KEY
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown_tx_error() {
+        let rpc_error = |code| {
+            RpcClientError::JsonRpc(JsonRpcError::Rpc(RpcError {
+                code,
+                message: s!("No such mempool or blockchain transaction"),
+                data: None,
+            }))
+        };
+        assert!(is_unknown_tx(&rpc_error(RPC_INVALID_ADDRESS_OR_KEY)));
+        assert!(!is_unknown_tx(&rpc_error(-8)));
+        assert!(!is_unknown_tx(&RpcClientError::InvalidCookieFile));
+    }
+}
@@ -0,0 +1,276 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolver decorator memoizing witness lookups.
+
+use std::collections::HashMap;
+#[cfg(feature = "fs")]
+use std::fs;
+#[cfg(feature = "fs")]
+use std::io;
+#[cfg(feature = "fs")]
+use std::num::NonZeroU32;
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "fs")]
+use rgbstd::bitcoin::consensus;
+use rgbstd::bitcoin::Transaction as Tx;
+use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{ChainNet, Txid};
+
+/// Extension of the files storing cached witnesses on disk.
+#[cfg(feature = "fs")]
+pub const CACHE_FILE_EXT: &str = "wit";
+
+/// [`ResolveWitness`] decorator caching the witnesses resolved by the inner resolver.
+///
+/// Only mined witnesses are cached, since the status of tentative and unresolved TXs is expected to
+/// change. Cached witnesses are never refreshed: in case of a reorg the affected TXs must be
+/// removed with [`CachingResolver::invalidate`].
+///
+/// With the `fs` feature the cache can also be persisted to a directory, one file per witness, so
+/// it survives restarts. Failing to write a cache file doesn't fail the resolution.
+pub struct CachingResolver<R: ResolveWitness> {
+    inner: R,
+    cache: Mutex<HashMap<Txid, (Tx, WitnessPos)>>,
+    #[cfg(feature = "fs")]
+    dir: Option<PathBuf>,
+}
+
+impl<R: ResolveWitness> CachingResolver<R> {
+    /// Wrap `inner`, caching its results in memory only.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cache: none!(),
+            #[cfg(feature = "fs")]
+            dir: None,
+        }
+    }
+
+    /// Wrap `inner`, caching its results in memory and in the provided directory, which is created
+    /// if missing.
+    #[cfg(feature = "fs")]
+    pub fn with_dir(inner: R, dir: impl AsRef<Path>) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            inner,
+            cache: none!(),
+            dir: Some(dir.as_ref().to_path_buf()),
+        })
+    }
+
+    pub fn inner(&self) -> &R { &self.inner }
+
+    pub fn into_inner(self) -> R { self.inner }
+
+    /// Number of witnesses currently cached in memory.
+    pub fn len(&self) -> usize { self.cache().len() }
+
+    pub fn is_empty(&self) -> bool { self.cache().is_empty() }
+
+    /// Remove a witness from the cache, so it will be resolved again by the inner resolver.
+    pub fn invalidate(&self, txid: Txid) {
+        self.cache().remove(&txid);
+        #[cfg(feature = "fs")]
+        if let Some(path) = self.cache_file(txid) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Remove all witnesses from the in-memory cache. Files persisted on disk are kept.
+    pub fn clear(&self) { self.cache().clear() }
+
+    fn cache(&self) -> MutexGuard<'_, HashMap<Txid, (Tx, WitnessPos)>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[cfg(feature = "fs")]
+    fn cache_file(&self, txid: Txid) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{txid}.{CACHE_FILE_EXT}")))
+    }
+
+    #[cfg(feature = "fs")]
+    fn load(&self, txid: Txid) -> Option<(Tx, WitnessPos)> {
+        let data = fs::read(self.cache_file(txid)?).ok()?;
+        decode_entry(&data).filter(|(tx, _)| tx.compute_txid() == txid)
+    }
+
+    #[cfg(not(feature = "fs"))]
+    fn load(&self, _: Txid) -> Option<(Tx, WitnessPos)> { None }
+
+    #[cfg(feature = "fs")]
+    fn store(&self, txid: Txid, tx: &Tx, pos: WitnessPos) {
+        if let Some(path) = self.cache_file(txid) {
+            let _ = fs::write(path, encode_entry(tx, pos));
+        }
+    }
+
+    #[cfg(not(feature = "fs"))]
+    fn store(&self, _: Txid, _: &Tx, _: WitnessPos) {}
+}
+
+impl<R: ResolveWitness> ResolveWitness for CachingResolver<R> {
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        if let Some((tx, pos)) = self.cache().get(&witness_id) {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(%witness_id, "witness found in memory cache");
+            return Ok(WitnessStatus::Resolved(tx.clone(), WitnessOrd::Mined(*pos)));
+        }
+        if let Some((tx, pos)) = self.load(witness_id) {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(%witness_id, "witness loaded from cache directory");
+            self.cache().insert(witness_id, (tx.clone(), pos));
+            return Ok(WitnessStatus::Resolved(tx, WitnessOrd::Mined(pos)));
+        }
+
+        let status = self.inner.resolve_witness(witness_id)?;
+        if let WitnessStatus::Resolved(tx, WitnessOrd::Mined(pos)) = &status {
+            self.store(witness_id, tx, *pos);
+            self.cache().insert(witness_id, (tx.clone(), *pos));
+        }
+        Ok(status)
+    }
+
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        self.inner.check_chain_net(chain_net)
+    }
+}
+
+/// Serialize a cache entry as the block height (4 bytes LE), the block timestamp (8 bytes LE) and
+/// the consensus-serialized TX.
+#[cfg(feature = "fs")]
+fn encode_entry(tx: &Tx, pos: WitnessPos) -> Vec<u8> {
+    let mut data = Vec::with_capacity(12 + tx.total_size());
+    data.extend(pos.height().get().to_le_bytes());
+    data.extend(pos.timestamp().to_le_bytes());
+    data.extend(consensus::serialize(tx));
+    data
+}
+
+#[cfg(feature = "fs")]
+fn decode_entry(data: &[u8]) -> Option<(Tx, WitnessPos)> {
+    let height = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+    let timestamp = i64::from_le_bytes(data.get(4..12)?.try_into().ok()?);
+    let pos = WitnessPos::bitcoin(NonZeroU32::new(height)?, timestamp)?;
+    let tx = consensus::deserialize(&data[12..]).ok()?;
+    Some((tx, pos))
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::num::NonZeroU32;
+
+    use rgbstd::bitcoin::absolute::LockTime;
+    use rgbstd::bitcoin::transaction::Version;
+    use schemata::dumb::MockResolver;
+
+    use super::*;
+
+    struct CountingResolver {
+        inner: MockResolver,
+        calls: Cell<usize>,
+    }
+
+    impl ResolveWitness for CountingResolver {
+        fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+            self.calls.set(self.calls.get() + 1);
+            self.inner.resolve_witness(witness_id)
+        }
+
+        fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+            self.inner.check_chain_net(chain_net)
+        }
+    }
+
+    fn tx(lock_time: u32) -> Tx {
+        Tx {
+            version: Version::TWO,
+            lock_time: LockTime::from_consensus(lock_time),
+            input: vec![],
+            output: vec![],
+        }
+    }
+
+    fn pos() -> WitnessPos {
+        WitnessPos::bitcoin(NonZeroU32::new(840000).unwrap(), 1713261744).unwrap()
+    }
+
+    #[test]
+    fn caches_mined_only() {
+        let mined = tx(1);
+        let tentative = tx(2);
+        let resolver = CachingResolver::new(CountingResolver {
+            inner: MockResolver::new()
+                .with_witness(mined.clone(), WitnessOrd::Mined(pos()))
+                .with_witness(tentative.clone(), WitnessOrd::Tentative),
+            calls: Cell::new(0),
+        });
+
+        for _ in 0..3 {
+            resolver.resolve_witness(mined.compute_txid()).unwrap();
+            resolver.resolve_witness(tentative.compute_txid()).unwrap();
+        }
+        assert_eq!(resolver.inner().calls.get(), 4);
+        assert_eq!(resolver.len(), 1);
+
+        resolver.invalidate(mined.compute_txid());
+        assert!(resolver.is_empty());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn entry_encoding() {
+        let tx = tx(3);
+        let data = encode_entry(&tx, pos());
+        assert_eq!(decode_entry(&data), Some((tx, pos())));
+        assert_eq!(decode_entry(&data[..10]), None);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn persisted_cache() {
+        let dir = std::env::temp_dir().join(format!("rgb-schemas-cache-{}", std::process::id()));
+        let mined = tx(4);
+        let txid = mined.compute_txid();
+
+        let resolver = CachingResolver::with_dir(
+            MockResolver::new().with_witness(mined.clone(), WitnessOrd::Mined(pos())),
+            &dir,
+        )
+        .unwrap();
+        resolver.resolve_witness(txid).unwrap();
+
+        // a fresh resolver not knowing the TX must read it from disk
+        let resolver = CachingResolver::with_dir(MockResolver::new(), &dir).unwrap();
+        assert_eq!(
+            resolver.resolve_witness(txid).unwrap(),
+            WitnessStatus::Resolved(mined, WitnessOrd::Mined(pos()))
+        );
+        resolver.invalidate(txid);
+        assert_eq!(resolver.resolve_witness(txid).unwrap(), WitnessStatus::Unresolved);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,242 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolver speaking the Electrum protocol.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+
+use amplify::hex::FromHex;
+use electrum_client::{Batch, Client, Config, ElectrumApi, Param};
+use rgbstd::bitcoin::{consensus, Transaction as Tx};
+use rgbstd::indexers::electrum_blocking::ElectrumClient;
+use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{ChainNet, Txid};
+
+/// Maximum number of requests sent to the Electrum server in a single batch.
+pub const ELECTRUM_BATCH_SIZE: usize = 100;
+
+/// [`ResolveWitness`] implementation backed by an Electrum server.
+///
+/// Single lookups are delegated to the [`ElectrumClient`] provided by `rgb-ops`, while
+/// [`ElectrumResolver::resolve_witnesses`] allows resolving many witnesses at once using Electrum
+/// batch requests.
+pub struct ElectrumResolver {
+    inner: ElectrumClient,
+}
+
+impl ElectrumResolver {
+    /// Connect to the Electrum server at the given `url`.
+    pub fn with(url: &str, config: Option<Config>) -> Result<Self, electrum_client::Error> {
+        let inner = Client::from_config(url, config.unwrap_or_default())?;
+        Ok(Self {
+            inner: ElectrumClient { inner },
+        })
+    }
+
+    /// Resolve the status of multiple witness TXs, batching the requests to the Electrum server.
+    ///
+    /// Electrum fails a whole batch when one of its calls fails (e.g. when a TX is unknown to the
+    /// server), so the TXs of a failed batch are resolved one by one.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, err(Display)))]
+    pub fn resolve_witnesses(
+        &self,
+        txids: impl IntoIterator<Item = Txid>,
+    ) -> Result<BTreeMap<Txid, WitnessStatus>, WitnessResolverError> {
+        let txids = txids.into_iter().collect::<Vec<_>>();
+        let mut resolved = BTreeMap::new();
+        for chunk in txids.chunks(ELECTRUM_BATCH_SIZE) {
+            match self.resolve_batch(chunk)? {
+                Some(statuses) => resolved.extend(statuses),
+                None => {
+                    for txid in chunk {
+                        resolved.insert(*txid, self.inner.resolve_witness(*txid)?);
+                    }
+                }
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn resolve_batch(
+        &self,
+        txids: &[Txid],
+    ) -> Result<Option<BTreeMap<Txid, WitnessStatus>>, WitnessResolverError> {
+        let client = &self.inner.inner;
+        let tip_height = client
+            .block_headers_subscribe()
+            .map_err(|e| WitnessResolverError::ResolverIssue(None, e.to_string()))?
+            .height;
+        while client.block_headers_pop().ok().flatten().is_some() {}
+
+        let mut batch = Batch::default();
+        for txid in txids {
+            batch.raw(s!("blockchain.transaction.get"), vec![
+                Param::String(txid.to_string()),
+                Param::Bool(true),
+            ]);
+        }
+        let Ok(details) = client.batch_call(&batch) else {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(txids = txids.len(), "batch call failed, resolving one by one");
+            return Ok(None);
+        };
+        if details.len() != txids.len() {
+            return Err(WitnessResolverError::InvalidResolverData);
+        }
+
+        let mut resolved = BTreeMap::new();
+        let mut mined = Vec::new();
+        for (txid, tx_details) in txids.iter().zip(details) {
+            let (tx, confirmed) = parse_tx_details(*txid, &tx_details)?;
+            match confirmed {
+                None => {
+                    resolved.insert(*txid, WitnessStatus::Resolved(tx, WitnessOrd::Tentative));
+                }
+                Some((confirmations, block_time)) => {
+                    let height = (tip_height + 1)
+                        .checked_sub(confirmations as usize)
+                        .ok_or(WitnessResolverError::InvalidResolverData)?;
+                    mined.push((*txid, tx, height, block_time));
+                }
+            }
+        }
+
+        // Electrum may report confirmations against a tip which is out of sync with the one
+        // returned by the header subscription, so we cross-check the heights using merkle proofs.
+        let proofs = client
+            .batch_transaction_get_merkle(mined.iter().map(|(txid, _, height, _)| (*txid, *height)))
+            .unwrap_or_default();
+        for (index, (txid, tx, height, block_time)) in mined.into_iter().enumerate() {
+            let status = match proofs.get(index) {
+                Some(proof) if proof.block_height == height => {
+                    let height = u32::try_from(height)
+                        .ok()
+                        .and_then(NonZeroU32::new)
+                        .ok_or(WitnessResolverError::InvalidResolverData)?;
+                    let pos = WitnessPos::bitcoin(height, block_time)
+                        .ok_or(WitnessResolverError::InvalidResolverData)?;
+                    WitnessStatus::Resolved(tx, WitnessOrd::Mined(pos))
+                }
+                _ => self.inner.resolve_witness(txid)?,
+            };
+            resolved.insert(txid, status);
+        }
+
+        Ok(Some(resolved))
+    }
+}
+
+impl ResolveWitness for ElectrumResolver {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err(Display)))]
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        self.inner.resolve_witness(witness_id)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), err(Display)))]
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        self.inner.check_chain_net(chain_net)
+    }
+}
+
+/// Parse the verbose output of `blockchain.transaction.get`, returning the transaction and, if
+/// it has been mined, its number of confirmations and block time.
+fn parse_tx_details(
+    txid: Txid,
+    tx_details: &serde_json::Value,
+) -> Result<(Tx, Option<(u32, i64)>), WitnessResolverError> {
+    let tx_hex = tx_details
+        .get("hex")
+        .and_then(|v| v.as_str())
+        .and_then(|s| Vec::<u8>::from_hex(s).ok())
+        .ok_or(WitnessResolverError::InvalidResolverData)?;
+    let tx: Tx =
+        consensus::deserialize(&tx_hex).map_err(|_| WitnessResolverError::InvalidResolverData)?;
+    if tx.compute_txid() != txid {
+        return Err(WitnessResolverError::IdMismatch {
+            actual: tx.compute_txid(),
+            expected: txid,
+        });
+    }
+
+    let confirmations = match tx_details.get("confirmations") {
+        None => 0,
+        Some(confirmations) => confirmations
+            .as_u64()
+            .and_then(|x| u32::try_from(x).ok())
+            .ok_or(WitnessResolverError::InvalidResolverData)?,
+    };
+    if confirmations == 0 {
+        return Ok((tx, None));
+    }
+    let block_time = tx_details
+        .get("blocktime")
+        .and_then(|v| v.as_i64())
+        .ok_or(WitnessResolverError::InvalidResolverData)?;
+
+    Ok((tx, Some((confirmations, block_time))))
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rgbstd::bitcoin::absolute::LockTime;
+    use rgbstd::bitcoin::transaction::Version;
+    use rgbstd::bitcoin::{Amount, ScriptBuf, TxOut};
+
+    use super::*;
+
+    fn tx() -> Tx {
+        Tx {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![TxOut {
+                value: Amount::from_sat(1000),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_verbose_tx() {
+        let tx = tx();
+        let txid = tx.compute_txid();
+        let hex = consensus::encode::serialize_hex(&tx);
+
+        let unconfirmed = serde_json::json!({ "hex": hex });
+        assert_eq!(parse_tx_details(txid, &unconfirmed).unwrap(), (tx.clone(), None));
+
+        let mined = serde_json::json!({ "hex": hex, "confirmations": 3, "blocktime": 1713261744 });
+        assert_eq!(parse_tx_details(txid, &mined).unwrap(), (tx.clone(), Some((3, 1713261744))));
+
+        let no_time = serde_json::json!({ "hex": hex, "confirmations": 3 });
+        assert_eq!(
+            parse_tx_details(txid, &no_time).unwrap_err(),
+            WitnessResolverError::InvalidResolverData
+        );
+
+        let other = Txid::from_str(&"00".repeat(32)).unwrap();
+        assert!(matches!(
+            parse_tx_details(other, &unconfirmed).unwrap_err(),
+            WitnessResolverError::IdMismatch { .. }
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolver failing over between multiple backends.
+
+use std::sync::{Mutex, MutexGuard};
+
+use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
+use rgbstd::{ChainNet, Txid};
+
+/// Health statistics of a [`FallbackResolver`] backend.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct BackendHealth {
+    /// Number of requests successfully served by the backend.
+    pub successes: u64,
+    /// Number of requests the backend failed to serve.
+    pub failures: u64,
+    /// Number of failures since the last successful request.
+    pub consecutive_failures: u32,
+    /// Error returned by the last failed request.
+    pub last_error: Option<WitnessResolverError>,
+}
+
+impl BackendHealth {
+    /// Whether the last request to the backend was successful (or no request was made yet).
+    pub fn is_healthy(&self) -> bool { self.consecutive_failures == 0 }
+
+    fn record(&mut self, res: Result<(), &WitnessResolverError>) {
+        match res {
+            Ok(()) => {
+                self.successes += 1;
+                self.consecutive_failures = 0;
+            }
+            Err(err) => {
+                self.failures += 1;
+                self.consecutive_failures += 1;
+                self.last_error = Some(err.clone());
+            }
+        }
+    }
+}
+
+struct Backend {
+    name: String,
+    resolver: Box<dyn ResolveWitness + Send>,
+    health: Mutex<BackendHealth>,
+}
+
+impl Backend {
+    fn health(&self) -> MutexGuard<'_, BackendHealth> {
+        self.health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// [`ResolveWitness`] implementation querying a list of backends in order, failing over to the
+/// next one when a backend returns an error.
+///
+/// The first successful answer is returned, including [`WitnessStatus::Unresolved`]. Timeouts are
+/// not handled here: they must be configured on the backend clients, which report them as errors.
+///
+/// [`FallbackResolver::check_chain_net`] fails if any backend is on a different chain-network
+/// pair, while it tolerates unreachable backends as long as at least one of them answers.
+#[derive(Default)]
+pub struct FallbackResolver {
+    backends: Vec<Backend>,
+}
+
+impl FallbackResolver {
+    pub fn new() -> Self { Self::default() }
+
+    /// Append a backend, which will be queried after all the previously added ones.
+    pub fn push(&mut self, name: impl ToString, resolver: impl ResolveWitness + Send + 'static) {
+        self.backends.push(Backend {
+            name: name.to_string(),
+            resolver: Box::new(resolver),
+            health: none!(),
+        });
+    }
+
+    /// Builder-style version of [`FallbackResolver::push`].
+    pub fn with_backend(
+        mut self,
+        name: impl ToString,
+        resolver: impl ResolveWitness + Send + 'static,
+    ) -> Self {
+        self.push(name, resolver);
+        self
+    }
+
+    pub fn len(&self) -> usize { self.backends.len() }
+
+    pub fn is_empty(&self) -> bool { self.backends.is_empty() }
+
+    /// Report the health of each backend, in the order they are queried.
+    pub fn health(&self) -> Vec<(String, BackendHealth)> {
+        self.backends
+            .iter()
+            .map(|backend| (backend.name.clone(), backend.health().clone()))
+            .collect()
+    }
+
+    /// Reset the health statistics of all the backends.
+    pub fn reset_health(&self) {
+        for backend in &self.backends {
+            *backend.health() = none!();
+        }
+    }
+}
+
+impl ResolveWitness for FallbackResolver {
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        let mut last_error = None;
+        for backend in &self.backends {
+            match backend.resolver.resolve_witness(witness_id) {
+                Ok(status) => {
+                    backend.health().record(Ok(()));
+                    return Ok(status);
+                }
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(backend = %backend.name, %witness_id, %err, "resolver backend failed");
+                    backend.health().record(Err(&err));
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            WitnessResolverError::ResolverIssue(Some(witness_id), s!("no resolver backends"))
+        }))
+    }
+
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        let mut last_error = None;
+        let mut reachable = false;
+        for backend in &self.backends {
+            match backend.resolver.check_chain_net(chain_net) {
+                Ok(()) => {
+                    backend.health().record(Ok(()));
+                    reachable = true;
+                }
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(backend = %backend.name, %err, "resolver backend failed");
+                    backend.health().record(Err(&err));
+                    if err == WitnessResolverError::WrongChainNet {
+                        return Err(err);
+                    }
+                    last_error = Some(err);
+                }
+            }
+        }
+        if reachable {
+            return Ok(());
+        }
+        Err(last_error.unwrap_or_else(|| {
+            WitnessResolverError::ResolverIssue(None, s!("no resolver backends"))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::bitcoin::absolute::LockTime;
+    use rgbstd::bitcoin::transaction::Version;
+    use rgbstd::bitcoin::Transaction as Tx;
+    use rgbstd::vm::WitnessOrd;
+    use schemata::dumb::MockResolver;
+
+    use super::*;
+
+    struct FailingResolver;
+
+    impl ResolveWitness for FailingResolver {
+        fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+            Err(WitnessResolverError::ResolverIssue(Some(witness_id), s!("timeout")))
+        }
+
+        fn check_chain_net(&self, _: ChainNet) -> Result<(), WitnessResolverError> {
+            Err(WitnessResolverError::ResolverIssue(None, s!("timeout")))
+        }
+    }
+
+    #[test]
+    fn failover() {
+        let tx = Tx {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+        let txid = tx.compute_txid();
+        let resolver = FallbackResolver::new()
+            .with_backend("bitcoind", FailingResolver)
+            .with_backend(
+                "esplora",
+                MockResolver::with_chain_net(ChainNet::BitcoinRegtest)
+                    .with_witness(tx.clone(), WitnessOrd::Tentative),
+            );
+
+        assert_eq!(
+            resolver.resolve_witness(txid).unwrap(),
+            WitnessStatus::Resolved(tx, WitnessOrd::Tentative)
+        );
+        assert!(resolver.check_chain_net(ChainNet::BitcoinRegtest).is_ok());
+        assert_eq!(
+            resolver
+                .check_chain_net(ChainNet::BitcoinMainnet)
+                .unwrap_err(),
+            WitnessResolverError::WrongChainNet
+        );
+
+        let health = resolver.health();
+        assert_eq!(health[0].0, "bitcoind");
+        assert_eq!(health[0].1.failures, 3);
+        assert_eq!(health[0].1.consecutive_failures, 3);
+        assert!(!health[0].1.is_healthy());
+        assert_eq!(health[1].1.successes, 2);
+        assert_eq!(health[1].1.failures, 1);
+
+        resolver.reset_health();
+        assert!(resolver
+            .health()
+            .iter()
+            .all(|(_, h)| *h == BackendHealth::default()));
+    }
+
+    #[test]
+    fn all_backends_failing() {
+        let resolver = FallbackResolver::new().with_backend("bitcoind", FailingResolver);
+        assert!(matches!(
+            resolver.check_chain_net(ChainNet::BitcoinRegtest),
+            Err(WitnessResolverError::ResolverIssue(..))
+        ));
+        assert!(FallbackResolver::new()
+            .check_chain_net(ChainNet::BitcoinRegtest)
+            .is_err());
+    }
+}
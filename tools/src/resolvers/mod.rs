@@ -0,0 +1,47 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Witness resolvers implementing [`ResolveWitness`] for different backends.
+//!
+//! [`ResolveWitness`]: rgbstd::validation::ResolveWitness
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "bitcoind")]
+mod bitcoind;
+mod cache;
+#[cfg(feature = "electrum")]
+mod electrum;
+mod fallback;
+#[cfg(feature = "bitcoind")]
+mod regtest;
+
+#[cfg(feature = "async")]
+pub use asynchronous::{
+    BlockingAdapter, EsploraAsyncResolver, EsploraBuilder, ResolveWitnessAsync,
+};
+#[cfg(feature = "bitcoind")]
+pub use bitcoind::{Auth, BitcoindResolver};
+pub use cache::CachingResolver;
+#[cfg(feature = "fs")]
+pub use cache::CACHE_FILE_EXT;
+#[cfg(feature = "electrum")]
+pub use electrum::{ElectrumResolver, ELECTRUM_BATCH_SIZE};
+pub use fallback::{BackendHealth, FallbackResolver};
+#[cfg(feature = "bitcoind")]
+pub use regtest::RegtestResolver;
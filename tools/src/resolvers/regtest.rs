@@ -0,0 +1,156 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolver and helpers for a local regtest Bitcoin Core node.
+
+use bitcoincore_rpc::bitcoin::address::NetworkChecked;
+use bitcoincore_rpc::bitcoin::{Address, Amount, BlockHash, Network};
+use bitcoincore_rpc::{Auth, Client, Error as RpcClientError, RpcApi};
+use rgbstd::bitcoin::Transaction as Tx;
+use rgbstd::validation::{ResolveWitness, WitnessResolverError, WitnessStatus};
+use rgbstd::{ChainNet, Outpoint, Txid};
+
+use super::BitcoindResolver;
+
+/// [`ResolveWitness`] implementation for a regtest Bitcoin Core node, also providing helpers to
+/// fund outputs and to mine witness TXs, so complete issuance and transfer workflows can be run
+/// locally.
+///
+/// The node must run with `-regtest -txindex=1` and have a loaded wallet, which is used to fund
+/// outputs and, unless a different address is provided, as the destination of mined coins.
+pub struct RegtestResolver {
+    inner: BitcoindResolver,
+    mining_address: Address<NetworkChecked>,
+}
+
+impl RegtestResolver {
+    /// Connect to the regtest node, mining to a new address of its wallet.
+    pub fn with(url: &str, auth: Auth) -> Result<Self, RpcClientError> {
+        let client = Client::new(url, auth)?;
+        let mining_address = client
+            .get_new_address(None, None)?
+            .require_network(Network::Regtest)
+            .map_err(|_| RpcClientError::UnexpectedStructure)?;
+        Ok(Self {
+            inner: BitcoindResolver::from(client),
+            mining_address,
+        })
+    }
+
+    /// Connect to the regtest node, mining to the provided address.
+    pub fn with_mining_address(
+        url: &str,
+        auth: Auth,
+        mining_address: Address<NetworkChecked>,
+    ) -> Result<Self, RpcClientError> {
+        Ok(Self {
+            inner: BitcoindResolver::with(url, auth)?,
+            mining_address,
+        })
+    }
+
+    pub fn client(&self) -> &Client { self.inner.client() }
+
+    pub fn mining_address(&self) -> &Address<NetworkChecked> { &self.mining_address }
+
+    /// Mine the given number of blocks.
+    pub fn mine(&self, blocks: u64) -> Result<Vec<BlockHash>, RpcClientError> {
+        self.client()
+            .generate_to_address(blocks, &self.mining_address)
+    }
+
+    /// Make sure the wallet has mature coins to spend, mining the 101 blocks needed for the first
+    /// coinbase to mature on a fresh chain.
+    pub fn ensure_funds(&self) -> Result<(), RpcClientError> {
+        if self.client().get_balance(None, None)? == Amount::ZERO {
+            self.mine(101)?;
+        }
+        Ok(())
+    }
+
+    /// Send `amount` from the node wallet to `address` and mine a block, returning the created
+    /// output, which can then be used as a seal.
+    pub fn fund(
+        &self,
+        address: &Address<NetworkChecked>,
+        amount: Amount,
+    ) -> Result<Outpoint, RpcClientError> {
+        self.ensure_funds()?;
+        let txid = self
+            .client()
+            .send_to_address(address, amount, None, None, None, None, None, None)?;
+        let tx = self.client().get_raw_transaction(&txid, None)?;
+        let vout = tx
+            .output
+            .iter()
+            .position(|txout| txout.script_pubkey == address.script_pubkey())
+            .ok_or(RpcClientError::UnexpectedStructure)?;
+        self.mine(1)?;
+        Ok(Outpoint::new(txid, vout as u32))
+    }
+
+    /// Broadcast a witness TX, returning its ID.
+    pub fn broadcast(&self, tx: &Tx) -> Result<Txid, RpcClientError> {
+        self.client().send_raw_transaction(tx)
+    }
+
+    /// Mine blocks until the TX with the given ID has the requested number of confirmations,
+    /// returning its updated status.
+    pub fn confirm(
+        &self,
+        txid: Txid,
+        confirmations: u32,
+    ) -> Result<WitnessStatus, WitnessResolverError> {
+        let current = self
+            .client()
+            .get_raw_transaction_info(&txid, None)
+            .map_err(|e| WitnessResolverError::ResolverIssue(Some(txid), e.to_string()))?
+            .confirmations
+            .unwrap_or_default();
+        if current < confirmations {
+            self.mine((confirmations - current) as u64)
+                .map_err(|e| WitnessResolverError::ResolverIssue(Some(txid), e.to_string()))?;
+        }
+        self.resolve_witness(txid)
+    }
+
+    /// Broadcast a witness TX and mine blocks until it has the requested number of confirmations.
+    pub fn broadcast_and_confirm(
+        &self,
+        tx: &Tx,
+        confirmations: u32,
+    ) -> Result<WitnessStatus, WitnessResolverError> {
+        let txid = tx.compute_txid();
+        self.broadcast(tx)
+            .map_err(|e| WitnessResolverError::ResolverIssue(Some(txid), e.to_string()))?;
+        self.confirm(txid, confirmations)
+    }
+}
+
+impl ResolveWitness for RegtestResolver {
+    fn resolve_witness(&self, witness_id: Txid) -> Result<WitnessStatus, WitnessResolverError> {
+        self.inner.resolve_witness(witness_id)
+    }
+
+    fn check_chain_net(&self, chain_net: ChainNet) -> Result<(), WitnessResolverError> {
+        if chain_net != ChainNet::BitcoinRegtest {
+            return Err(WitnessResolverError::WrongChainNet);
+        }
+        self.inner.check_chain_net(chain_net)
+    }
+}
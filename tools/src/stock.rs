@@ -0,0 +1,195 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! File-backed [`Stock`] keeping the state of the tooling workflows across process restarts.
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use rgbstd::persistence::fs::FsBinStore;
+use rgbstd::persistence::Stock;
+use schemata::kit::KITS;
+
+/// Error opening or saving a [`PersistentStock`].
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum StockFileError {
+    /// unable to access the stock directory: {0}
+    #[from]
+    Io(io::Error),
+
+    /// unable to load or save the stock: {0}
+    Persistence(String),
+
+    /// unable to import the {0} kit: {1}
+    Kit(&'static str, String),
+}
+
+/// [`Stock`] persisted in a directory, knowing the kits of all the enabled schemata.
+///
+/// The stock is created and the built-in kits imported the first time the directory is opened. It
+/// is saved when dropped; use [`PersistentStock::save`] to handle saving errors, which are
+/// otherwise only reported to stderr.
+#[derive(Debug)]
+pub struct PersistentStock {
+    stock: Stock,
+    dir: PathBuf,
+}
+
+impl PersistentStock {
+    /// Opens the stock stored in `dir`, creating it if the directory has no stock yet.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, StockFileError> {
+        let dir = dir.as_ref().to_path_buf();
+        let provider = FsBinStore::new(dir.clone())?;
+        let stock = if provider.stash.exists() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(dir = %dir.display(), "loading stock");
+            Stock::load(provider, false).map_err(|e| StockFileError::Persistence(e.to_string()))?
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::info!(dir = %dir.display(), "creating stock");
+            let mut stock = Stock::in_memory();
+            for &(name, _, kit) in KITS {
+                let kit = kit()
+                    .validate()
+                    .map_err(|e| StockFileError::Kit(name, format!("{e:?}")))?;
+                stock
+                    .import_kit(kit)
+                    .map_err(|e| StockFileError::Kit(name, e.to_string()))?;
+            }
+            stock
+                .make_persistent(provider, false)
+                .map_err(|e| StockFileError::Persistence(e.to_string()))?;
+            let mut stock = Self { stock, dir };
+            stock.save()?;
+            return Ok(stock);
+        };
+        Ok(Self { stock, dir })
+    }
+
+    /// Directory the stock is stored in.
+    pub fn dir(&self) -> &Path { &self.dir }
+
+    /// Writes the stock to its directory.
+    pub fn save(&mut self) -> Result<(), StockFileError> {
+        self.stock
+            .store()
+            .map_err(|e| StockFileError::Persistence(e.to_string()))
+    }
+}
+
+impl Deref for PersistentStock {
+    type Target = Stock;
+
+    fn deref(&self) -> &Self::Target { &self.stock }
+}
+
+impl DerefMut for PersistentStock {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.stock }
+}
+
+impl Drop for PersistentStock {
+    fn drop(&mut self) {
+        if let Err(err) = self.save() {
+            #[cfg(feature = "tracing")]
+            tracing::error!(dir = %self.dir.display(), %err, "unable to save stock");
+            eprintln!("unable to save the stock in {}: {err}", self.dir.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::Amount;
+    use schemata::dumb::MockResolver;
+    use schemata::{NonInflatableAsset, NIA_SCHEMA_ID};
+
+    use super::*;
+    use crate::testing::{builder, issue, seal, spec, terms, CHAIN_NET};
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("rgb-schemas-stock-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+    }
+
+    #[test]
+    fn imports_kits_on_creation() {
+        let dir = TempDir::new("kits");
+        let stock = PersistentStock::open(&dir.0).unwrap();
+        assert_eq!(stock.dir(), dir.0);
+        assert_eq!(stock.schemata().unwrap().count(), KITS.len());
+        drop(stock);
+        assert!(dir.0.join("stash.dat").exists());
+
+        let stock = PersistentStock::open(&dir.0).unwrap();
+        assert_eq!(stock.schemata().unwrap().count(), KITS.len());
+    }
+
+    #[test]
+    fn survives_reopening() {
+        let dir = TempDir::new("reopen");
+        let contract = issue(
+            builder::<NonInflatableAsset>()
+                .add_global_state("spec", spec())
+                .unwrap()
+                .add_global_state("terms", terms())
+                .unwrap()
+                .add_global_state("issuedSupply", Amount::from(1000u64))
+                .unwrap()
+                .add_fungible_state("assetOwner", seal(1), 1000u64)
+                .unwrap(),
+        );
+        let contract_id = contract.contract_id();
+
+        let mut stock = PersistentStock::open(&dir.0).unwrap();
+        stock
+            .import_contract(contract, MockResolver::with_chain_net(CHAIN_NET))
+            .unwrap();
+        drop(stock);
+
+        let stock = PersistentStock::open(&dir.0).unwrap();
+        let contract = stock.contracts().unwrap().next().unwrap();
+        assert_eq!(contract.id, contract_id);
+        assert_eq!(contract.schema_id, NIA_SCHEMA_ID);
+    }
+
+    #[test]
+    fn unreadable_stock() {
+        let dir = TempDir::new("corrupt");
+        fs::create_dir_all(&dir.0).unwrap();
+        fs::write(dir.0.join("stash.dat"), b"not a stash").unwrap();
+        assert!(matches!(PersistentStock::open(&dir.0), Err(StockFileError::Persistence(_))));
+    }
+}
@@ -0,0 +1,561 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright (C) 2025 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for writing schema-level tests, available with the `testing` feature.
+//!
+//! All the data produced here is deterministic, so contracts issued from the same builder calls
+//! always get the same contract id.
+//!
+//! Transitions of a [`TestContract`] are validated directly against the schema with
+//! [`Schema::validate_state`], so the tests don't need to anchor them into witness transactions;
+//! the ones which are consumed into a stock are anchored with [`anchor`] or [`consume`].
+//!
+//! The [`audit`](crate::audit) reports the issues specific to a schema only when this crate is
+//! built with its feature, which the tests of `rgb-schemata-core` get in the workspace build only;
+//! there, tests auditing a contract are gated behind the `all_schemas` cfg.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::num::NonZeroU32;
+use std::rc::Rc;
+
+use amplify::confinement::{Confined, NonEmptyOrdMap, NonEmptyVec, SmallBlob};
+use amplify::{Bytes32, Bytes64, Wrapper};
+use rgbstd::bitcoin::absolute::LockTime;
+use rgbstd::bitcoin::hashes::Hash;
+use rgbstd::bitcoin::transaction::Version;
+use rgbstd::bitcoin::{
+    Amount as Sats, CompressedPublicKey, ScriptBuf, Sequence, Transaction as Tx, TxIn, TxOut,
+    Witness,
+};
+use rgbstd::containers::{
+    ConsignmentExt, Fascia, PubWitness, SealWitness, ValidConsignment, ValidContract,
+};
+use rgbstd::contract::{
+    AllocatedState, BuilderError, ContractBuilder, IssuerWrapper, TransitionBuilder,
+};
+use rgbstd::invoice::Precision;
+use rgbstd::persistence::{MemContract, Stock};
+use rgbstd::rgbcore::commit_verify::mpc::{self, MerkleBlock, MerkleTree, MultiSource};
+use rgbstd::rgbcore::commit_verify::{CommitId, EmbedCommitVerify, TryCommitVerify};
+use rgbstd::schema::Schema;
+use rgbstd::secp256k1::{Message, Secp256k1, SecretKey};
+use rgbstd::stl::{AssetSpec, ContractTerms, RicardianContract};
+use rgbstd::validation::{
+    DbcProof, Failure, ResolveWitness, Scripts, ValidationConfig, ValidationError,
+};
+use rgbstd::vm::{ContractStateEvolve, OrdOpRef, WitnessOrd, WitnessPos};
+use rgbstd::{
+    Allocation, Amount, AssignmentType, BundleId, ChainNet, ContractId, Genesis, GenesisSeal,
+    GraphSeal, Identity, KnownTransition, OpId, Operation, Opout, Outpoint, RevealedData,
+    RevealedState, RevealedValue, Signature, TokenIndex, Transition, TransitionBundle, Txid,
+};
+use schemata::blinding::SealBlinder;
+pub use schemata::dumb::MockResolver;
+use strict_types::{StrictSerialize, TypeSystem};
+
+/// Default chain network of the helpers of this module.
+pub const CHAIN_NET: ChainNet = ChainNet::BitcoinTestnet4;
+
+/// Bitcoin test networks which contracts can be issued on with [`builder_on`].
+pub const TEST_CHAIN_NETS: [ChainNet; 3] =
+    [ChainNet::BitcoinTestnet4, ChainNet::BitcoinSignet, ChainNet::BitcoinRegtest];
+
+/// Genesis timestamp used by [`issue`].
+pub const CREATED_AT: i64 = 1713261744;
+
+/// Blinder of the seals of [`seal`] and [`graph_seal`], also used by the test vectors of the
+/// schemata.
+pub const BLINDER: SealBlinder = SealBlinder::Offset(654321);
+
+/// Dummy txid with all bytes set to `no`, for telling apart several witness TXs.
+pub fn dummy_txid(no: u8) -> Txid { Txid::from_byte_array([no; 32]) }
+
+/// Txid of the outputs used by [`seal`].
+pub fn txid() -> Txid { dummy_txid(0x01) }
+
+/// Genesis seal on the output `vout` of [`txid`] with a blinding factor derived from `vout`.
+pub fn seal(vout: u32) -> GenesisSeal { BLINDER.genesis_seal(txid(), vout) }
+
+/// Seal on the output `vout` of the witness TX with a blinding factor derived from `vout`.
+pub fn graph_seal(vout: u32) -> GraphSeal { BLINDER.graph_seal(vout) }
+
+pub fn spec() -> AssetSpec { AssetSpec::new("TEST", "Test asset", Precision::CentiMicro) }
+
+pub fn terms() -> ContractTerms {
+    ContractTerms {
+        text: RicardianContract::default(),
+        media: None,
+    }
+}
+
+/// Contract builder for the schema of `I`, issuing on [`CHAIN_NET`].
+pub fn builder<I: IssuerWrapper>() -> ContractBuilder { builder_on::<I>(CHAIN_NET) }
+
+/// Contract builder for the schema of `I`, issuing on `chain_net`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", fields(issuer = std::any::type_name::<I>()))
+)]
+pub fn builder_on<I: IssuerWrapper>(chain_net: ChainNet) -> ContractBuilder {
+    ContractBuilder::with(Identity::default(), I::schema(), I::types(), I::scripts(), chain_net)
+}
+
+/// Issue the contract at [`CREATED_AT`], panicking if it doesn't fit the schema requirements.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn issue(builder: ContractBuilder) -> ValidContract {
+    let contract = builder
+        .issue_contract_raw(CREATED_AT)
+        .expect("contract doesn't fit schema requirements");
+    #[cfg(feature = "tracing")]
+    tracing::debug!(contract_id = %contract.contract_id(), "contract issued");
+    contract
+}
+
+/// Validates the contract as a receiver on `chain_net` would, failing if either the contract or
+/// the resolver is on another network.
+pub fn validate_on<I: IssuerWrapper>(
+    contract: ValidContract,
+    chain_net: ChainNet,
+    resolver: &impl ResolveWitness,
+) -> Result<ValidContract, ValidationError> {
+    let config = ValidationConfig {
+        chain_net,
+        trusted_typesystem: I::types(),
+        ..Default::default()
+    };
+    contract.into_consignment().validate(resolver, &config)
+}
+
+/// Builder of an asset of the schema of `I` on [`CHAIN_NET`], see [`asset_on`].
+pub fn asset<I: IssuerWrapper>(issued: u64, allocated: u64) -> ContractBuilder {
+    asset_on::<I>(CHAIN_NET, issued, allocated)
+}
+
+/// Builder of an asset of the schema of `I` on `chain_net`, with the [`spec`] and [`terms`] of the
+/// test asset, `issued` units of issued supply and `allocated` units owned by `seal(1)`.
+///
+/// Covers the genesis state shared by the fungible schemata; callers add the state specific to
+/// their schema.
+pub fn asset_on<I: IssuerWrapper>(
+    chain_net: ChainNet,
+    issued: u64,
+    allocated: u64,
+) -> ContractBuilder {
+    builder_on::<I>(chain_net)
+        .add_global_state("spec", spec())
+        .expect("schema without asset spec")
+        .add_global_state("terms", terms())
+        .expect("schema without contract terms")
+        .add_global_state("issuedSupply", Amount::from(issued))
+        .expect("schema without issued supply")
+        .add_fungible_state("assetOwner", seal(1), allocated)
+        .expect("schema without fungible asset owner")
+}
+
+/// In-memory stock with the contract issued by `builder` already imported, using a resolver on
+/// the chain network of the contract.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn stock_with(builder: ContractBuilder) -> (Stock, ContractId) {
+    let contract = issue(builder);
+    let contract_id = contract.contract_id();
+    let resolver = MockResolver::with_chain_net(contract.genesis.chain_net);
+    let mut stock = Stock::in_memory();
+    stock
+        .import_contract(contract, resolver)
+        .expect("unable to import contract");
+    (stock, contract_id)
+}
+
+/// Key of the PFA issuer, authorizing transfers, also used as the stablecoin oracle key.
+pub fn issuer_key() -> SecretKey { SecretKey::from_slice(&[0x42; 32]).unwrap() }
+
+pub fn issuer_pubkey() -> CompressedPublicKey {
+    CompressedPublicKey(issuer_key().public_key(&Secp256k1::signing_only()))
+}
+
+/// Key of a second PFA-N co-signing service, next to the issuer one.
+pub fn cosigner_key() -> SecretKey { SecretKey::from_slice(&[0x43; 32]).unwrap() }
+
+pub fn cosigner_pubkey() -> CompressedPublicKey {
+    CompressedPublicKey(cosigner_key().public_key(&Secp256k1::signing_only()))
+}
+
+/// Produces the issuer signature over the transition id.
+pub fn sign(transition: &Transition) -> Signature { sign_with(transition, &issuer_key()) }
+
+/// Produces the signature of `key` over the transition id.
+pub fn sign_with(transition: &Transition, key: &SecretKey) -> Signature {
+    let msg = Message::from_digest(transition.id().to_byte_array());
+    let sig = Secp256k1::signing_only()
+        .sign_ecdsa(&msg, key)
+        .serialize_compact();
+    Signature::from(Bytes64::from_byte_array(sig))
+}
+
+/// Error number of the script failure `err`, decoded into the errno type `E` of the schemata.
+pub fn script_errno<E>(err: ValidationError) -> Option<E>
+where
+    E: TryFrom<u8>,
+    E::Error: Debug,
+{
+    match err {
+        ValidationError::InvalidConsignment(Failure::ScriptFailure(_, errno, _)) => {
+            errno.map(|errno| E::try_from(errno).expect("known error number"))
+        }
+        err => panic!("validation failed without a script failure: {err:?}"),
+    }
+}
+
+/// Error number of the script failure rejecting a genesis, see [`script_errno`].
+pub fn genesis_errno<E>(res: Result<ValidConsignment<false>, BuilderError>) -> Option<E>
+where
+    E: TryFrom<u8>,
+    E::Error: Debug,
+{
+    match res {
+        Err(BuilderError::ContractInconsistency(err)) => script_errno(err),
+        Err(err) => panic!("unexpected builder error: {err:?}"),
+        Ok(_) => panic!("invalid genesis passed validation"),
+    }
+}
+
+/// Issued contract together with the schema data needed to validate its transitions.
+pub struct TestContract {
+    pub schema: Schema,
+    pub types: TypeSystem,
+    pub scripts: Scripts,
+    pub genesis: Genesis,
+}
+
+impl TestContract {
+    pub fn issue<I: IssuerWrapper>(builder: ContractBuilder) -> Self {
+        let genesis = builder
+            .issue_contract_raw(CREATED_AT)
+            .expect("valid genesis failed validation")
+            .genesis
+            .clone();
+        Self {
+            schema: I::schema(),
+            types: I::types(),
+            scripts: I::scripts(),
+            genesis,
+        }
+    }
+
+    pub fn transition(&self, name: &'static str) -> TransitionCase<'_> {
+        let builder = TransitionBuilder::named_transition(
+            self.genesis.contract_id(),
+            self.schema.clone(),
+            name,
+            self.types.clone(),
+        )
+        .unwrap();
+        TransitionCase {
+            contract: self,
+            builder,
+            prev_state: BTreeMap::new(),
+            history: vec![],
+        }
+    }
+}
+
+/// Transition under construction, spending (possibly non-existing) genesis outputs.
+pub struct TransitionCase<'c> {
+    contract: &'c TestContract,
+    builder: TransitionBuilder,
+    prev_state: BTreeMap<AssignmentType, Vec<RevealedState>>,
+    history: Vec<Transition>,
+}
+
+impl TransitionCase<'_> {
+    pub fn input(mut self, ty: AssignmentType, no: u16, state: RevealedState) -> Self {
+        let allocated = match &state {
+            RevealedState::Void => AllocatedState::Void,
+            RevealedState::Fungible(value) => AllocatedState::Amount(*value),
+            RevealedState::Structured(data) => AllocatedState::Data(data.clone()),
+        };
+        self.builder = self
+            .builder
+            .add_input(Opout::new(self.contract.genesis.id(), ty, no), allocated)
+            .unwrap();
+        self.prev_state.entry(ty).or_default().push(state);
+        self
+    }
+
+    /// Validates the transition after `transition`, whose global state is added to the contract
+    /// state.
+    pub fn after(mut self, transition: Transition) -> Self {
+        self.history.push(transition);
+        self
+    }
+
+    pub fn with(mut self, f: impl FnOnce(TransitionBuilder) -> TransitionBuilder) -> Self {
+        self.builder = f(self.builder);
+        self
+    }
+
+    pub fn transition(&self) -> Transition { self.builder.clone().complete_transition().unwrap() }
+
+    /// Completes the transition and signs it with the issuer key, see [`sign`].
+    pub fn signed(&self) -> Transition {
+        let mut transition = self.transition();
+        transition.signature = Some(sign(&transition));
+        transition
+    }
+
+    pub fn validate_transition(
+        &self,
+        transition: &Transition,
+        with_genesis_state: bool,
+    ) -> Result<(), ValidationError> {
+        let TestContract {
+            schema,
+            types,
+            scripts,
+            genesis,
+        } = self.contract;
+        let mut state = MemContract::init((schema, genesis.contract_id()));
+        let bundle_id = BundleId::from_inner(Bytes32::from_byte_array([0u8; 32]));
+        // like the consignment validator, only operations with a validator add their global
+        // state to the contract state
+        if with_genesis_state {
+            if schema.genesis.validator.is_some() {
+                state.evolve_state(OrdOpRef::Genesis(genesis)).unwrap();
+            }
+            for prev in &self.history {
+                if schema.transitions[&prev.transition_type]
+                    .transition_schema
+                    .validator
+                    .is_none()
+                {
+                    continue;
+                }
+                let op = OrdOpRef::Transition(prev, txid(), WitnessOrd::Tentative, bundle_id);
+                state.evolve_state(op).unwrap();
+            }
+        }
+        let op = OrdOpRef::Transition(transition, txid(), WitnessOrd::Tentative, bundle_id);
+        schema.validate_state(
+            types,
+            scripts,
+            genesis,
+            op,
+            Rc::new(RefCell::new(state)),
+            &self.prev_state,
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.validate_transition(&self.transition(), true)
+    }
+
+    pub fn errno<E>(&self) -> Option<E>
+    where
+        E: TryFrom<u8>,
+        E::Error: Debug,
+    {
+        script_errno(
+            self.validate()
+                .expect_err("invalid transition passed validation"),
+        )
+    }
+}
+
+/// Position of a witness TX mined at `height`, one block every ten minutes after [`CREATED_AT`].
+pub fn mined_at(height: u32) -> WitnessOrd {
+    let pos =
+        WitnessPos::bitcoin(NonZeroU32::new(height).unwrap(), CREATED_AT + 600 * height as i64)
+            .unwrap();
+    WitnessOrd::Mined(pos)
+}
+
+pub fn amount(value: u64) -> RevealedState { RevealedState::Fungible(RevealedValue::from(value)) }
+
+pub fn allocation(index: u32, fraction: u64) -> RevealedState {
+    let data = Allocation::with(TokenIndex::from_inner(index), fraction)
+        .to_strict_serialized::<{ u16::MAX as usize }>()
+        .unwrap();
+    RevealedState::Structured(RevealedData::new(SmallBlob::from_checked(data.release())))
+}
+
+/// Anchors `transition` spending `prevouts` into a witness TX with two outputs mined at `height`
+/// and consumes it into the stock, returning the witness id.
+pub fn consume(
+    stock: &mut Stock,
+    resolver: &mut MockResolver,
+    contract_id: ContractId,
+    transition: Transition,
+    prevouts: &[Outpoint],
+    height: u32,
+) -> Txid {
+    let fascia = anchor(contract_id, transition, prevouts, 2);
+    let witness = fascia.seal_witness.public.tx().unwrap().clone();
+    let witness_id = fascia.witness_id();
+    resolver.add_witness(witness, mined_at(height));
+    stock.consume_fascia(fascia, resolver.clone()).unwrap();
+    witness_id
+}
+
+/// Validates the history of the `opids` operations, consumed into the stock, as a transfer
+/// consignment.
+///
+/// [`Stock::consume_fascia`] imports transitions without running the schema scripts, so history
+/// tests call this to check that each transition they consume is accepted by the validators.
+pub fn validate_history<I: IssuerWrapper>(
+    stock: &Stock,
+    resolver: &MockResolver,
+    contract_id: ContractId,
+    opids: &[OpId],
+) -> Result<(), ValidationError> {
+    let transfer = stock
+        .transfer(contract_id, [], [], opids.iter().copied(), None)
+        .expect("stock is unable to export the history");
+    let config = ValidationConfig {
+        chain_net: CHAIN_NET,
+        trusted_typesystem: I::types(),
+        ..Default::default()
+    };
+    transfer.validate(resolver, &config).map(|_| ())
+}
+
+/// Bundles the transition and anchors it with an opret commitment into a witness TX spending
+/// `prevouts`.
+///
+/// The first output of the witness is the opret one, followed by `outputs` outputs which may be
+/// used by witness-relative seals (`vout` 1 and above).
+pub fn anchor(
+    contract_id: ContractId,
+    transition: Transition,
+    prevouts: &[Outpoint],
+    outputs: u32,
+) -> Fascia {
+    let opid = transition.id();
+    let input_map = transition
+        .inputs
+        .iter()
+        .map(|opout| (*opout, opid))
+        .collect::<BTreeMap<_, _>>();
+    let bundle = TransitionBundle {
+        input_map: NonEmptyOrdMap::try_from(input_map).unwrap(),
+        known_transitions: NonEmptyVec::with(KnownTransition::new(opid, transition)),
+    };
+
+    let source = MultiSource {
+        messages: Confined::from_checked(BTreeMap::from([(
+            mpc::ProtocolId::from(contract_id),
+            mpc::Message::from(bundle.bundle_id()),
+        )])),
+        static_entropy: Some(0),
+        ..MultiSource::default()
+    };
+    let tree = MerkleTree::try_commit(&source).unwrap();
+
+    let mut tx = Tx {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: prevouts
+            .iter()
+            .map(|prevout| TxIn {
+                previous_output: *prevout,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::new(),
+            })
+            .collect(),
+        output: vec![TxOut {
+            value: Sats::ZERO,
+            script_pubkey: ScriptBuf::from_bytes(vec![0x6a]),
+        }],
+    };
+    tx.output.extend((0..outputs).map(|_| TxOut {
+        value: Sats::from_sat(1000),
+        script_pubkey: ScriptBuf::from_bytes(vec![0x51]),
+    }));
+    let proof = tx.embed_commit(&tree.commit_id()).unwrap();
+
+    Fascia {
+        seal_witness: SealWitness::new(
+            PubWitness::with(tx),
+            MerkleBlock::from(tree),
+            DbcProof::Opret(proof),
+        ),
+        bundles: NonEmptyOrdMap::with_key_value(contract_id, bundle),
+    }
+}
+
+#[cfg(all(test, feature = "nia"))]
+mod test {
+    use rgbstd::contract::FilterIncludeAll;
+    use schemata::NonInflatableAsset;
+
+    use super::*;
+
+    fn nia() -> ContractBuilder { nia_on(CHAIN_NET) }
+
+    fn nia_on(chain_net: ChainNet) -> ContractBuilder {
+        asset_on::<NonInflatableAsset>(chain_net, 1000, 1000)
+    }
+
+    #[test]
+    fn deterministic_issue() {
+        assert_eq!(seal(1), seal(1));
+        assert_ne!(seal(1), seal(2));
+        assert_eq!(issue(nia()).contract_id(), issue(nia()).contract_id());
+    }
+
+    #[test]
+    fn stock_setup() {
+        let (stock, contract_id) = stock_with(nia());
+        let wrapper = stock
+            .contract_wrapper::<NonInflatableAsset>(contract_id)
+            .unwrap();
+        assert_eq!(wrapper.total_issued_supply(), Amount::from(1000u64));
+        assert_eq!(wrapper.allocations(&FilterIncludeAll).count(), 1);
+    }
+
+    #[test]
+    fn chain_nets() {
+        let ids = TEST_CHAIN_NETS.map(|chain_net| {
+            let contract = issue(nia_on(chain_net));
+            assert_eq!(contract.genesis.chain_net, chain_net);
+            assert_eq!(contract.contract_id(), issue(nia_on(chain_net)).contract_id());
+            let (stock, contract_id) = stock_with(nia_on(chain_net));
+            assert!(stock.contract_data(contract_id).is_ok());
+            contract.contract_id()
+        });
+        assert_ne!(ids[0], ids[1]);
+        assert_ne!(ids[1], ids[2]);
+        assert_ne!(ids[0], ids[2]);
+    }
+
+    #[test]
+    fn wrong_chain_net() {
+        let regtest = ChainNet::BitcoinRegtest;
+        let signet = ChainNet::BitcoinSignet;
+        let contract = issue(nia_on(regtest));
+        let resolver = |chain_net| MockResolver::with_chain_net(chain_net);
+        assert!(validate_on::<NonInflatableAsset>(contract.clone(), regtest, &resolver(regtest))
+            .is_ok());
+        assert!(
+            validate_on::<NonInflatableAsset>(contract.clone(), signet, &resolver(signet)).is_err()
+        );
+        assert!(validate_on::<NonInflatableAsset>(contract, regtest, &resolver(signet)).is_err());
+    }
+}
@@ -0,0 +1,700 @@
+// RGB schemas
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reference workflow of a transfer, from the payer stock to the beneficiary one.
+//!
+//! A transfer goes through the following steps, each provided by a function of this module:
+//!
+//! 1. the payer builds the transfer transition, spending the allocations selected with
+//!    [`Spendable::select`](schemata::selection::Spendable::select) for a fungible asset
+//!    ([`fungible_transition`]) or the token allocations of a UDA or FUA ([`data_transition`]); PFA
+//!    transitions must then be signed by the issuer;
+//! 2. the transition is anchored into the witness transaction with an opret commitment
+//!    ([`anchor`]); the witness must spend the outpoints of the spent allocations and is signed and
+//!    broadcast by the wallet of the payer;
+//! 3. the payer stock consumes the anchored transition, learning the change ([`consume`]);
+//! 4. the consignment for the beneficiary is extracted from the payer stock ([`consignment`]),
+//!    together with the operations outside the history of the transfer which its validation depends
+//!    on, like the key rotations of a PFA;
+//! 5. the beneficiary validates the consignment and accepts it into its own stock ([`accept`]),
+//!    after having stored the seal it used in the invoice.
+//!
+//! Escrow asset transfers build the transition of their first step with [`escrow_transition`],
+//! [`release_transition`] or [`refund_transition`] instead. Holders of claimable assets hand
+//! units back to the issuer with [`claim_transition`], and the issuer collects them with
+//! [`collect_transition`], signing the transition with its key.
+
+use std::collections::BTreeMap;
+
+use amplify::confinement::{Confined, NonEmptyOrdMap, NonEmptyVec};
+use rgbstd::bitcoin::Transaction as Tx;
+use rgbstd::containers::{BuilderSeal, Fascia, PubWitness, SealWitness, Transfer};
+#[cfg(any(feature = "escrow", feature = "claim"))]
+use rgbstd::contract::FungibleAllocation;
+#[cfg(feature = "escrow")]
+use rgbstd::contract::RightsAllocation;
+use rgbstd::contract::{
+    AllocatedState, BuilderError, DataAllocation, IssuerWrapper, TransitionBuilder,
+};
+use rgbstd::persistence::{
+    ConsignError, FasciaError, IndexProvider, StashProvider, StateProvider, Stock, StockError,
+};
+use rgbstd::rgbcore::commit_verify::mpc::{self, MerkleBlock, MerkleTree, MultiSource};
+use rgbstd::rgbcore::commit_verify::{CommitId, EmbedCommitVerify, TryCommitVerify};
+use rgbstd::rgbcore::dbc::opret::OpretError;
+use rgbstd::validation::{
+    DbcProof, ResolveWitness, Status, ValidationConfig, ValidationError, WitnessOrdProvider,
+};
+use rgbstd::{
+    Allocation, Amount, ChainNet, ContractId, GraphSeal, KnownTransition, OpId, Operation,
+    RevealedData, RevealedValue, SecretSeal, Transition, TransitionBundle, Txid,
+};
+use schemata::selection::Selection;
+#[cfg(feature = "claim")]
+use schemata::OS_CLAIM;
+use schemata::{Error, OS_ASSET};
+#[cfg(feature = "escrow")]
+use schemata::{OS_ESCROW, OS_REFUND, OS_RELEASE};
+
+/// Error building or anchoring a transfer transition.
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum TransferError {
+    /// {0}
+    #[from]
+    Invalid(Error),
+
+    /// {0}
+    #[from]
+    Builder(BuilderError),
+
+    /// transition spends no state.
+    NoInputs,
+
+    /// unable to commit to the transfer in the witness transaction: {0}
+    #[from]
+    Anchor(OpretError),
+}
+
+/// Error accepting a transfer consignment.
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum AcceptError<S: StashProvider, H: StateProvider, P: IndexProvider> {
+    /// transfer consignment is not valid: {0:?}
+    #[from]
+    Invalid(ValidationError),
+
+    /// {0}
+    #[from]
+    Stock(StockError<S, H, P>),
+}
+
+/// Completes a transfer of a fungible asset spending the selected allocations, paying the
+/// selected amount to the beneficiary and assigning the change, if any, to the `change` seal.
+pub fn fungible_transition(
+    mut builder: TransitionBuilder,
+    selection: &Selection,
+    beneficiary: BuilderSeal<GraphSeal>,
+    change: GraphSeal,
+) -> Result<Transition, TransferError> {
+    if selection.allocations.is_empty() {
+        return Err(TransferError::NoInputs);
+    }
+    let amount = selection.total.saturating_sub(selection.change);
+    if amount == Amount::ZERO {
+        return Err(Error::ZeroAmount.into());
+    }
+    for input in &selection.allocations {
+        let state = AllocatedState::Amount(RevealedValue::from(input.state));
+        builder = builder.add_input(input.opout, state)?;
+    }
+    builder = builder.add_fungible_state_raw(OS_ASSET, beneficiary, amount)?;
+    if selection.change != Amount::ZERO {
+        builder = builder.add_fungible_state_raw(OS_ASSET, change, selection.change)?;
+    }
+    Ok(builder.complete_transition()?)
+}
+
+/// Completes a transfer of UDA or FUA token allocations, spending `inputs` and assigning the
+/// token fractions of `outputs` to their seals.
+///
+/// The validation scripts require the fractions of each token to be preserved by the transfer.
+pub fn data_transition(
+    mut builder: TransitionBuilder,
+    inputs: &[DataAllocation],
+    outputs: impl IntoIterator<Item = (BuilderSeal<GraphSeal>, Allocation)>,
+) -> Result<Transition, TransferError> {
+    if inputs.is_empty() {
+        return Err(TransferError::NoInputs);
+    }
+    for input in inputs {
+        builder = builder.add_input(input.opout, AllocatedState::Data(input.state.clone()))?;
+    }
+    for (seal, allocation) in outputs {
+        builder = builder.add_data_raw(OS_ASSET, seal, RevealedData::from(allocation))?;
+    }
+    Ok(builder.complete_transition()?)
+}
+
+/// Completes an `escrow` transition spending the selected allocations: the selected amount is
+/// escrowed at the `escrow` seal, the change, if any, is assigned to the `change` seal, and the
+/// release and refund rights to their seals.
+#[cfg(feature = "escrow")]
+pub fn escrow_transition(
+    mut builder: TransitionBuilder,
+    selection: &Selection,
+    escrow: BuilderSeal<GraphSeal>,
+    release: BuilderSeal<GraphSeal>,
+    refund: BuilderSeal<GraphSeal>,
+    change: GraphSeal,
+) -> Result<Transition, TransferError> {
+    if selection.allocations.is_empty() {
+        return Err(TransferError::NoInputs);
+    }
+    let amount = selection.total.saturating_sub(selection.change);
+    if amount == Amount::ZERO {
+        return Err(Error::ZeroAmount.into());
+    }
+    for input in &selection.allocations {
+        let state = AllocatedState::Amount(RevealedValue::from(input.state));
+        builder = builder.add_input(input.opout, state)?;
+    }
+    builder = builder
+        .add_fungible_state_raw(OS_ESCROW, escrow, amount)?
+        .add_rights_raw(OS_RELEASE, release)?
+        .add_rights_raw(OS_REFUND, refund)?;
+    if selection.change != Amount::ZERO {
+        builder = builder.add_fungible_state_raw(OS_ASSET, change, selection.change)?;
+    }
+    Ok(builder.complete_transition()?)
+}
+
+/// Completes a `release` transition paying the escrowed allocation to the beneficiary, spending
+/// the release right.
+#[cfg(feature = "escrow")]
+pub fn release_transition(
+    builder: TransitionBuilder,
+    escrow: &FungibleAllocation,
+    right: &RightsAllocation,
+    beneficiary: BuilderSeal<GraphSeal>,
+) -> Result<Transition, TransferError> {
+    settle_transition(builder, escrow, right, beneficiary)
+}
+
+/// Completes a `refund` transition returning the escrowed allocation to the sender, spending the
+/// refund right.
+#[cfg(feature = "escrow")]
+pub fn refund_transition(
+    builder: TransitionBuilder,
+    escrow: &FungibleAllocation,
+    right: &RightsAllocation,
+    sender: BuilderSeal<GraphSeal>,
+) -> Result<Transition, TransferError> {
+    settle_transition(builder, escrow, right, sender)
+}
+
+#[cfg(feature = "escrow")]
+fn settle_transition(
+    builder: TransitionBuilder,
+    escrow: &FungibleAllocation,
+    right: &RightsAllocation,
+    seal: BuilderSeal<GraphSeal>,
+) -> Result<Transition, TransferError> {
+    let state = AllocatedState::Amount(RevealedValue::from(escrow.state));
+    Ok(builder
+        .add_input(escrow.opout, state)?
+        .add_input(right.opout, AllocatedState::Void)?
+        .add_fungible_state_raw(OS_ASSET, seal, escrow.state)?
+        .complete_transition()?)
+}
+
+/// Completes a `claim` transition spending the selected allocations: the selected amount is
+/// claimed on the issuer at the `claim` seal and the change, if any, is assigned to the `change`
+/// seal.
+#[cfg(feature = "claim")]
+pub fn claim_transition(
+    mut builder: TransitionBuilder,
+    selection: &Selection,
+    claim: BuilderSeal<GraphSeal>,
+    change: GraphSeal,
+) -> Result<Transition, TransferError> {
+    if selection.allocations.is_empty() {
+        return Err(TransferError::NoInputs);
+    }
+    let amount = selection.total.saturating_sub(selection.change);
+    if amount == Amount::ZERO {
+        return Err(Error::ZeroAmount.into());
+    }
+    for input in &selection.allocations {
+        let state = AllocatedState::Amount(RevealedValue::from(input.state));
+        builder = builder.add_input(input.opout, state)?;
+    }
+    builder = builder.add_fungible_state_raw(OS_CLAIM, claim, amount)?;
+    if selection.change != Amount::ZERO {
+        builder = builder.add_fungible_state_raw(OS_ASSET, change, selection.change)?;
+    }
+    Ok(builder.complete_transition()?)
+}
+
+/// Completes a `collect` transition spending the claims and assigning their units to the
+/// `issuer` seal.
+///
+/// The transition must then be signed by the issuer key committed in the genesis.
+#[cfg(feature = "claim")]
+pub fn collect_transition(
+    mut builder: TransitionBuilder,
+    claims: &[FungibleAllocation],
+    issuer: BuilderSeal<GraphSeal>,
+) -> Result<Transition, TransferError> {
+    if claims.is_empty() {
+        return Err(TransferError::NoInputs);
+    }
+    for claim in claims {
+        let state = AllocatedState::Amount(RevealedValue::from(claim.state));
+        builder = builder.add_input(claim.opout, state)?;
+    }
+    let amount = claims
+        .iter()
+        .fold(Amount::ZERO, |sum, claim| sum.saturating_add(claim.state));
+    Ok(builder
+        .add_fungible_state_raw(OS_ASSET, issuer, amount)?
+        .complete_transition()?)
+}
+
+/// Commits to the transition in the first `OP_RETURN` output of the witness transaction, which
+/// must carry no data yet.
+///
+/// The returned fascia contains the witness with the commitment, which has to be signed and
+/// broadcast once the fascia is consumed by the payer stock.
+pub fn anchor(
+    contract_id: ContractId,
+    transition: Transition,
+    mut witness: Tx,
+) -> Result<Fascia, TransferError> {
+    let opid = transition.id();
+    let input_map = transition
+        .inputs
+        .iter()
+        .map(|opout| (*opout, opid))
+        .collect::<BTreeMap<_, _>>();
+    let bundle = TransitionBundle {
+        input_map: NonEmptyOrdMap::try_from(input_map).expect("transitions spend some state"),
+        known_transitions: NonEmptyVec::with(KnownTransition::new(opid, transition)),
+    };
+
+    let source = MultiSource {
+        messages: Confined::from_checked(bmap! {
+            mpc::ProtocolId::from(contract_id) => mpc::Message::from(bundle.bundle_id()),
+        }),
+        ..MultiSource::default()
+    };
+    let tree = MerkleTree::try_commit(&source).expect("a single message always fits the tree");
+    let proof = witness.embed_commit(&tree.commit_id())?;
+
+    Ok(Fascia {
+        seal_witness: SealWitness::new(
+            PubWitness::with(witness),
+            MerkleBlock::from(tree),
+            DbcProof::Opret(proof),
+        ),
+        bundles: NonEmptyOrdMap::with_key_value(contract_id, bundle),
+    })
+}
+
+/// Consumes the anchored transfer into the payer stock, returning the id of the witness.
+pub fn consume<S: StashProvider, H: StateProvider, P: IndexProvider>(
+    stock: &mut Stock<S, H, P>,
+    fascia: Fascia,
+    witness_ord_provider: impl WitnessOrdProvider,
+) -> Result<Txid, StockError<S, H, P, FasciaError>> {
+    let witness_id = fascia.witness_id();
+    stock.consume_fascia(fascia, witness_ord_provider)?;
+    Ok(witness_id)
+}
+
+/// Extracts from the payer stock the consignment of the transfer with the given witness,
+/// revealing the state assigned to the blinded seals of the beneficiaries.
+///
+/// The consignment carries the history of the state assigned to the beneficiaries and the `opids`
+/// operations, which the beneficiary needs to validate the transfer without being part of that
+/// history, like the key rotations of a PFA whose scripts check the transfer signature against the
+/// latest key. The stock skips the operations which aren't anchored to the witness of the transfer,
+/// so when `opids` isn't empty the consignment also carries all the operations assigning state to
+/// the revealed seals of the payer.
+pub fn consignment<S: StashProvider, H: StateProvider, P: IndexProvider>(
+    stock: &Stock<S, H, P>,
+    contract_id: ContractId,
+    beneficiaries: impl AsRef<[SecretSeal]>,
+    opids: impl IntoIterator<Item = OpId>,
+    witness_id: Txid,
+) -> Result<Transfer, StockError<S, H, P, ConsignError>> {
+    let opids = opids.into_iter().collect::<Vec<_>>();
+    let witness_id = if opids.is_empty() { Some(witness_id) } else { None };
+    stock.transfer(contract_id, [], beneficiaries, opids, witness_id)
+}
+
+/// Validates a transfer consignment of a contract issued by `I` on `chain_net` and accepts it
+/// into the beneficiary stock, returning the validation status with its warnings.
+///
+/// The beneficiary stock must already know the seals it provided in its invoices, otherwise the
+/// state assigned to them remains concealed.
+pub fn accept<I: IssuerWrapper, S: StashProvider, H: StateProvider, P: IndexProvider>(
+    stock: &mut Stock<S, H, P>,
+    consignment: Transfer,
+    chain_net: ChainNet,
+    resolver: impl ResolveWitness,
+) -> Result<Status, AcceptError<S, H, P>> {
+    let config = ValidationConfig {
+        chain_net,
+        trusted_typesystem: I::types(),
+        ..Default::default()
+    };
+    let consignment = consignment.validate(&resolver, &config)?;
+    let status = consignment.validation_status().clone();
+    stock.accept_transfer(consignment, resolver)?;
+    Ok(status)
+}
+
+#[cfg(all(test, any(feature = "escrow", feature = "claim")))]
+mod test {
+    #[cfg(feature = "claim")]
+    use rgbstd::bitcoin::CompressedPublicKey;
+    use rgbstd::containers::ConsignmentExt;
+    use rgbstd::contract::ContractBuilder;
+    #[cfg(feature = "escrow")]
+    use rgbstd::Opout;
+    use rgbstd::{Outpoint, OutputSeal};
+    #[cfg(feature = "claim")]
+    use schemata::ClaimableAsset;
+    #[cfg(feature = "escrow")]
+    use schemata::EscrowAsset;
+    use schemata::{FungibleAssetWrapper, ValidationErrno};
+
+    use super::*;
+    #[cfg(feature = "claim")]
+    use crate::audit::audit_onchain;
+    use crate::testing;
+
+    // Escrows of an escrow asset.
+    //
+    // A holder escrows part of their allocation, which is released to the beneficiary, then escrows
+    // the change, which is refunded to them. An escrow not balancing the spent allocations must be
+    // rejected by the validation of the history.
+
+    #[cfg(feature = "escrow")]
+    fn escrow(issued: u64, allocated: u64) -> ContractBuilder {
+        testing::asset::<EscrowAsset>(issued, allocated)
+    }
+
+    #[cfg(feature = "escrow")]
+    #[test]
+    fn release_and_refund() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(escrow(1000, 1000));
+        let contract_id = contract.contract_id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 4);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+        let lock = |stock: &mut Stock, outpoint: Outpoint, amount: u64| {
+            let selection = stock
+                .contract_wrapper::<EscrowAsset>(contract_id)
+                .unwrap()
+                .spendable([outpoint])
+                .select(Amount::from(amount))
+                .unwrap();
+            let builder = stock.transition_builder(contract_id, "escrow").unwrap();
+            let [escrow, release, refund] =
+                [1, 2, 3].map(|vout| BuilderSeal::from(testing::graph_seal(vout)));
+            let transition = escrow_transition(
+                builder,
+                &selection,
+                escrow,
+                release,
+                refund,
+                testing::graph_seal(4),
+            )
+            .unwrap();
+            (transition, selection.outpoints)
+        };
+
+        // the holder escrows 600 units, keeping the change
+        let (transition, prevouts) = lock(&mut stock, Outpoint::new(testing::txid(), 1), 600);
+        let escrowed = consume(&mut stock, transition, &prevouts, 101);
+
+        let wrapper = stock.contract_wrapper::<EscrowAsset>(contract_id).unwrap();
+        let escrow = wrapper
+            .escrows(vec![Outpoint::new(escrowed, 1)])
+            .next()
+            .unwrap();
+        assert_eq!(escrow.state, Amount::from(600u64));
+        let release = wrapper
+            .release_rights(vec![Outpoint::new(escrowed, 2)])
+            .next()
+            .unwrap();
+        assert_eq!(
+            wrapper
+                .refund_rights(vec![Outpoint::new(escrowed, 3)])
+                .count(),
+            1
+        );
+        // the escrowed units can't be spent as the asset
+        let outpoints = [1, 4].map(|vout| Outpoint::new(escrowed, vout));
+        assert_eq!(wrapper.spendable(outpoints).balance(), Amount::from(400u64));
+
+        // the escrow is released to the beneficiary
+        let builder = stock.transition_builder(contract_id, "release").unwrap();
+        let beneficiary = BuilderSeal::from(testing::graph_seal(1));
+        let transition = release_transition(builder, &escrow, &release, beneficiary).unwrap();
+        let prevouts = [1, 2].map(|vout| Outpoint::new(escrowed, vout));
+        let release_id = transition.id();
+        let released = consume(&mut stock, transition, &prevouts, 102);
+
+        // the change is escrowed and refunded to the holder
+        let (transition, prevouts) = lock(&mut stock, Outpoint::new(escrowed, 4), 400);
+        let escrowed_change = consume(&mut stock, transition, &prevouts, 103);
+        let wrapper = stock.contract_wrapper::<EscrowAsset>(contract_id).unwrap();
+        let escrow = wrapper
+            .escrows(vec![Outpoint::new(escrowed_change, 1)])
+            .next()
+            .unwrap();
+        let refund = wrapper
+            .refund_rights(vec![Outpoint::new(escrowed_change, 3)])
+            .next()
+            .unwrap();
+        let builder = stock.transition_builder(contract_id, "refund").unwrap();
+        let sender = BuilderSeal::from(testing::graph_seal(1));
+        let transition = refund_transition(builder, &escrow, &refund, sender).unwrap();
+        let prevouts = [1, 3].map(|vout| Outpoint::new(escrowed_change, vout));
+        let refund_id = transition.id();
+        let refunded = consume(&mut stock, transition, &prevouts, 104);
+
+        let wrapper = stock.contract_wrapper::<EscrowAsset>(contract_id).unwrap();
+        let allocations = wrapper
+            .allocations(vec![Outpoint::new(released, 1), Outpoint::new(refunded, 1)])
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert!(allocations.contains(&(OutputSeal::new(Outpoint::new(released, 1)), 600)));
+        assert!(allocations.contains(&(OutputSeal::new(Outpoint::new(refunded, 1)), 400)));
+        assert_eq!(allocations.len(), 2);
+
+        testing::validate_history::<EscrowAsset>(&stock, &resolver, contract_id, &[
+            release_id, refund_id,
+        ])
+        .unwrap();
+    }
+
+    #[cfg(feature = "escrow")]
+    #[test]
+    fn escrow_mismatch_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(escrow(1000, 1000));
+        let contract_id = contract.contract_id();
+        let genesis_id = contract.genesis.id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        // the holder escrows 600 units but keeps only 300 units of change
+        let transition = stock
+            .transition_builder(contract_id, "escrow")
+            .unwrap()
+            .add_input(
+                Opout::new(genesis_id, OS_ASSET, 0),
+                AllocatedState::Amount(RevealedValue::from(1000u64)),
+            )
+            .unwrap()
+            .add_fungible_state("escrowedAsset", testing::graph_seal(1), 600u64)
+            .unwrap()
+            .add_rights("releaseRight", testing::graph_seal(2))
+            .unwrap()
+            .add_rights("refundRight", testing::graph_seal(2))
+            .unwrap()
+            .add_fungible_state("assetOwner", testing::graph_seal(1), 300u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[Outpoint::new(testing::txid(), 1)],
+            101,
+        );
+
+        let err = testing::validate_history::<EscrowAsset>(&stock, &resolver, contract_id, &[opid])
+            .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::NonEqualInOut));
+    }
+
+    // Claims on the issuer of a claimable asset.
+    //
+    // A holder hands part of their allocation back to the issuer with a claim, keeping the change;
+    // the issuer then collects the claim with a transition signed by its key, after which the audit
+    // must account for all the issued units. A collect signed by another key than the issuer one
+    // must be rejected by the validation of the history.
+
+    /// Claimable asset allocated to `seal(1)`, whose claims are collected by the `pubkey`
+    /// issuer.
+    #[cfg(feature = "claim")]
+    fn claim(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+        testing::asset::<ClaimableAsset>(issued, allocated)
+            .add_global_state("pubkey", pubkey)
+            .unwrap()
+    }
+
+    #[cfg(feature = "claim")]
+    #[test]
+    fn claim_and_collect() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(claim(1000, 1000, testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let mut consume =
+            |stock: &mut Stock, transition: Transition, prevouts: &[Outpoint], height| {
+                let fascia = testing::anchor(contract_id, transition, prevouts, 2);
+                let witness = fascia.seal_witness.public.tx().unwrap().clone();
+                let witness_id = fascia.witness_id();
+                resolver.add_witness(witness, testing::mined_at(height));
+                stock.consume_fascia(fascia, resolver.clone()).unwrap();
+                witness_id
+            };
+
+        // the holder claims 600 units on the issuer, keeping the change
+        let selection = stock
+            .contract_wrapper::<ClaimableAsset>(contract_id)
+            .unwrap()
+            .spendable([Outpoint::new(testing::txid(), 1)])
+            .select(Amount::from(600u64))
+            .unwrap();
+        let builder = stock.transition_builder(contract_id, "claim").unwrap();
+        let transition = claim_transition(
+            builder,
+            &selection,
+            BuilderSeal::from(testing::graph_seal(1)),
+            testing::graph_seal(2),
+        )
+        .unwrap();
+        let claimed = consume(&mut stock, transition, &selection.outpoints, 101);
+
+        let wrapper = stock
+            .contract_wrapper::<ClaimableAsset>(contract_id)
+            .unwrap();
+        let claim_outpoint = Outpoint::new(claimed, 1);
+        assert_eq!(wrapper.claimed_amount([claim_outpoint]), Amount::from(600u64));
+        // the claimed units can't be spent as the asset
+        let outpoints = [1, 2].map(|vout| Outpoint::new(claimed, vout));
+        assert_eq!(wrapper.spendable(outpoints).balance(), Amount::from(400u64));
+        let claims = wrapper.claims([claim_outpoint]).collect::<Vec<_>>();
+
+        // the issuer collects the claim
+        let builder = stock.transition_builder(contract_id, "collect").unwrap();
+        let mut transition =
+            collect_transition(builder, &claims, BuilderSeal::from(testing::graph_seal(1)))
+                .unwrap();
+        transition.signature = Some(testing::sign(&transition));
+        let opid = transition.id();
+        let collected = consume(&mut stock, transition, &[claim_outpoint], 102);
+        testing::validate_history::<ClaimableAsset>(&stock, &resolver, contract_id, &[opid])
+            .unwrap();
+
+        let wrapper = stock
+            .contract_wrapper::<ClaimableAsset>(contract_id)
+            .unwrap();
+        let allocations = wrapper
+            .allocations(vec![Outpoint::new(collected, 1), Outpoint::new(claimed, 2)])
+            .map(|a| (a.seal, a.state.value()))
+            .collect::<Vec<_>>();
+        assert!(allocations.contains(&(OutputSeal::new(Outpoint::new(collected, 1)), 600)));
+        assert!(allocations.contains(&(OutputSeal::new(Outpoint::new(claimed, 2)), 400)));
+        assert_eq!(allocations.len(), 2);
+
+        // the claims count as units of the asset for the audit
+        let report = audit_onchain(&stock, contract_id, &resolver).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!((report.issued, report.allocated), (1000, 1000));
+    }
+
+    #[cfg(feature = "claim")]
+    #[test]
+    fn collect_by_holder_rejected() {
+        let mut resolver = testing::MockResolver::with_chain_net(testing::CHAIN_NET);
+        let contract = testing::issue(claim(1000, 1000, testing::issuer_pubkey()));
+        let contract_id = contract.contract_id();
+        let mut stock = Stock::in_memory();
+        stock.import_contract(contract, &resolver).unwrap();
+
+        let selection = stock
+            .contract_wrapper::<ClaimableAsset>(contract_id)
+            .unwrap()
+            .spendable([Outpoint::new(testing::txid(), 1)])
+            .select(Amount::from(1000u64))
+            .unwrap();
+        let builder = stock.transition_builder(contract_id, "claim").unwrap();
+        let transition = claim_transition(
+            builder,
+            &selection,
+            BuilderSeal::from(testing::graph_seal(1)),
+            testing::graph_seal(2),
+        )
+        .unwrap();
+        let prevouts = selection.outpoints.clone();
+        let claimed =
+            testing::consume(&mut stock, &mut resolver, contract_id, transition, &prevouts, 101);
+
+        let claim_outpoint = Outpoint::new(claimed, 1);
+        let claims = stock
+            .contract_wrapper::<ClaimableAsset>(contract_id)
+            .unwrap()
+            .claims([claim_outpoint])
+            .collect::<Vec<_>>();
+        let builder = stock.transition_builder(contract_id, "collect").unwrap();
+        let mut transition =
+            collect_transition(builder, &claims, BuilderSeal::from(testing::graph_seal(1)))
+                .unwrap();
+        transition.signature = Some(testing::sign_with(&transition, &testing::cosigner_key()));
+        let opid = transition.id();
+        testing::consume(
+            &mut stock,
+            &mut resolver,
+            contract_id,
+            transition,
+            &[claim_outpoint],
+            102,
+        );
+
+        let err =
+            testing::validate_history::<ClaimableAsset>(&stock, &resolver, contract_id, &[opid])
+                .unwrap_err();
+        assert_eq!(testing::script_errno(err), Some(ValidationErrno::InvalidSignature));
+    }
+}
@@ -0,0 +1,557 @@
+//! Schema builders shared by the integration tests, on top of the harness of
+//! [`schemata_tools::testing`].
+//!
+//! The builders cover all the schemata, so the integration tests are only built when they are all
+//! enabled, as reported by the `all_schemas` cfg of the build script.
+
+#![allow(dead_code)]
+
+use amplify::confinement::SmallBlob;
+use amplify::Wrapper;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::contract::ContractBuilder;
+use rgbstd::invoice::Precision;
+use rgbstd::stl::{AssetSpec, Name, ProofOfReserves, TokenData};
+use rgbstd::{Allocation, Amount, ContractId, Outpoint, TokenIndex};
+use schemata::height::block_height;
+use schemata::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+    DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+    GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+    NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+    ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+    SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+    TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+    VerifiableCredential, VestingAsset, WrappedBtcAsset,
+};
+pub use schemata_tools::testing::*;
+
+pub fn nia(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<NonInflatableAsset>(issued, allocated)
+}
+
+/// Burnable asset allocated to `seal(1)`.
+pub fn burnable(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<BurnableAsset>(issued, allocated)
+}
+
+pub fn cfa(issued: u64, allocated: u64) -> ContractBuilder {
+    builder::<CollectibleFungibleAsset>()
+        .add_global_state("name", Name::from("Test asset"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), allocated)
+        .unwrap()
+}
+
+/// PFA asset allocated to `seal(1)`, whose issuer key can be rotated by the holder of `seal(2)`.
+pub fn pfa(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+    asset::<PermissionedFungibleAsset>(issued, allocated)
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_rights("rotateKeyRight", seal(2))
+        .unwrap()
+}
+
+/// PFA-N asset allocated to `seal(1)`, whose transfers can be signed by any of `pubkeys`.
+pub fn pfan(issued: u64, allocated: u64, pubkeys: &[CompressedPublicKey]) -> ContractBuilder {
+    let mut builder = asset::<MultiKeyPermissionedAsset>(issued, allocated);
+    for pubkey in pubkeys {
+        builder = builder.add_global_state("pubkey", *pubkey).unwrap();
+    }
+    builder
+}
+
+/// Freezable asset allocated to `seal(1)`, with the freeze right at `seal(2)`.
+pub fn freezable(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<FreezableAsset>(issued, allocated)
+        .add_rights("freezeRight", seal(2))
+        .unwrap()
+}
+
+/// Pausable asset allocated to `seal(1)`, with the pause right at `seal(2)`.
+pub fn pausable(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<PausableAsset>(issued, allocated)
+        .add_rights("pauseRight", seal(2))
+        .unwrap()
+}
+
+/// Threshold inflatable asset allocated to `seal(1)`, with the inflation allowance up to a max
+/// supply of 1500 at `seal(2)` and the approval state, holding the position following the last
+/// approving key and the number of approvals, at `seal(3)`.
+pub fn tifa(
+    issued: u64,
+    allocated: u64,
+    threshold: u64,
+    pubkeys: &[CompressedPublicKey],
+    approval: (u32, u64),
+) -> ContractBuilder {
+    let mut builder = asset::<ThresholdInflatableAsset>(issued, allocated)
+        .add_global_state("maxSupply", Amount::from(1500u64))
+        .unwrap()
+        .add_global_state("inflationThreshold", Amount::from(threshold))
+        .unwrap();
+    for pubkey in pubkeys {
+        builder = builder.add_global_state("pubkey", *pubkey).unwrap();
+    }
+    builder
+        .add_fungible_state("inflationAllowance", seal(2), 1500 - issued)
+        .unwrap()
+        .add_data(
+            "inflationApproval",
+            seal(3),
+            Allocation::with(TokenIndex::from_inner(approval.0), approval.1),
+        )
+        .unwrap()
+}
+
+pub fn uda(token_index: u32, owned_index: u32, fraction: u64) -> ContractBuilder {
+    builder::<UniqueDigitalAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test uda", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index: TokenIndex::from_inner(token_index),
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data(
+            "assetOwner",
+            seal(1),
+            Allocation::with(TokenIndex::from_inner(owned_index), fraction),
+        )
+        .unwrap()
+}
+
+/// FUA of token 2 issuing `issued` fractions, with genesis allocations of `(token index, fraction)`
+/// assigned to consecutive seals.
+pub fn fua(issued: u64, allocations: &[(u32, u64)]) -> ContractBuilder {
+    let mut builder = builder::<FractionalUniqueAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test fua", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index: TokenIndex::from_inner(2),
+            ..Default::default()
+        })
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued))
+        .unwrap();
+    for (vout, (index, fraction)) in allocations.iter().enumerate() {
+        builder = builder
+            .add_data(
+                "assetOwner",
+                seal(vout as u32 + 1),
+                Allocation::with(TokenIndex::from_inner(*index), *fraction),
+            )
+            .unwrap();
+    }
+    builder
+}
+
+/// UDC of the tokens with the given indexes, in the given order, with genesis allocations of
+/// `(token index, fraction)` assigned to consecutive seals.
+pub fn collection(tokens: &[u32], allocations: &[(u32, u64)]) -> ContractBuilder {
+    let mut builder = builder::<UniqueDigitalCollection>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test collection", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for index in tokens {
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(*index),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+    for (vout, (index, fraction)) in allocations.iter().enumerate() {
+        builder = builder
+            .add_data(
+                "assetOwner",
+                seal(vout as u32 + 1),
+                Allocation::with(TokenIndex::from_inner(*index), *fraction),
+            )
+            .unwrap();
+    }
+    builder
+}
+
+pub fn ifa(issued: u64, allocated: u64, max: u64, inflation: u64) -> ContractBuilder {
+    asset::<InflatableFungibleAsset>(issued, allocated)
+        .add_global_state("maxSupply", Amount::from(max))
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), inflation)
+        .unwrap()
+        .add_rights("replaceRight", seal(3))
+        .unwrap()
+}
+
+pub fn ria(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<ReissuableAsset>(issued, allocated)
+        .add_rights("reissueRight", seal(2))
+        .unwrap()
+}
+
+pub fn sba(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<SoulboundAsset>(issued, allocated)
+        .add_rights("revokeRight", seal(2))
+        .unwrap()
+}
+
+pub fn stablecoin(
+    issued: u64,
+    allocated: u64,
+    max: u64,
+    inflation: u64,
+    pubkey: CompressedPublicKey,
+) -> ContractBuilder {
+    asset::<StablecoinAsset>(issued, allocated)
+        .add_global_state("maxSupply", Amount::from(max))
+        .unwrap()
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), inflation)
+        .unwrap()
+}
+
+/// Bond maturing at height `maturity`, with a face value of 100 000 and a coupon rate of 450 basis
+/// points.
+pub fn bond(issued: u64, allocated: u64, maturity: u32) -> ContractBuilder {
+    asset::<BondAsset>(issued, allocated)
+        .add_global_state("faceValue", Amount::from(100_000u64))
+        .unwrap()
+        .add_global_state("couponRate", Amount::from(450u64))
+        .unwrap()
+        .add_global_state("maturity", block_height(maturity))
+        .unwrap()
+}
+
+/// Governance asset allocated to `seal(1)`, with `vote_rights` vote rights assigned to the seals
+/// with vouts from 1 up to `vote_rights`.
+pub fn governance(
+    issued: u64,
+    voting_unit: u64,
+    allocated: u64,
+    vote_rights: u32,
+) -> ContractBuilder {
+    let mut builder = asset::<GovernanceAsset>(issued, allocated)
+        .add_global_state("votingUnit", Amount::from(voting_unit))
+        .unwrap();
+    for vout in 1..=vote_rights {
+        builder = builder.add_rights("voteRight", seal(vout)).unwrap();
+    }
+    builder
+}
+
+/// Event tickets with the given indexes, each allocated whole to consecutive seals starting from
+/// `seal(1)`.
+pub fn ticket(tickets: &[u32]) -> ContractBuilder {
+    let mut builder = builder::<TicketAsset>()
+        .add_global_state("spec", AssetSpec::new("TKT", "Test tickets", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for (vout, index) in (1..).zip(tickets) {
+        let index = TokenIndex::from_inner(*index);
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", seal(vout), Allocation::with(index, 1))
+            .unwrap();
+    }
+    builder
+}
+
+/// Timelocked asset allocated to `seal(1)`, locked up to `lock_height`.
+pub fn timelock(issued: u64, allocated: u64, lock_height: u32) -> ContractBuilder {
+    asset::<TimelockAsset>(issued, allocated)
+        .add_global_state("lockHeight", block_height(lock_height))
+        .unwrap()
+}
+
+/// Vesting asset allocating `allocated` units to `seal(1)` and locking `locked` units in the
+/// allocation of `seal(2)`, under a vesting schedule from `cliff` to `end`.
+pub fn vesting(
+    issued: u64,
+    vested: u64,
+    allocated: u64,
+    locked: u64,
+    cliff: u32,
+    end: u32,
+) -> ContractBuilder {
+    asset::<VestingAsset>(issued, allocated)
+        .add_global_state("vestedSupply", Amount::from(vested))
+        .unwrap()
+        .add_global_state("vestingCliff", block_height(cliff))
+        .unwrap()
+        .add_global_state("vestingEnd", block_height(end))
+        .unwrap()
+        .add_fungible_state("lockedAsset", seal(2), locked)
+        .unwrap()
+}
+
+/// SFA of the tokens with the given indexes, in the given order, with genesis allocations of
+/// `(token index, amount)` assigned to consecutive seals.
+pub fn sfa(tokens: &[u32], allocations: &[(u32, u64)]) -> ContractBuilder {
+    let mut builder = builder::<SemiFungibleAsset>()
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for index in tokens {
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(*index),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+    for (vout, (index, amount)) in allocations.iter().enumerate() {
+        builder = builder
+            .add_data(
+                "assetOwner",
+                seal(vout as u32 + 1),
+                Allocation::with(TokenIndex::from_inner(*index), *amount),
+            )
+            .unwrap();
+    }
+    builder
+}
+
+/// Attestation of BTC reserves held in the output `vout` of the test TX.
+pub fn reserve_proof(vout: u32) -> ProofOfReserves {
+    let proof = SmallBlob::try_from_iter(vec![0x52; 64]).unwrap();
+    ProofOfReserves::new(Outpoint::new(txid(), vout), proof)
+}
+
+/// Wrapped BTC asset issued against the reserves of [`reserve_proof`]`(0)`, recording `reserves`
+/// as the attestation.
+pub fn wbtc(issued: u64, allocated: u64, reserves: ProofOfReserves) -> ContractBuilder {
+    asset::<WrappedBtcAsset>(issued, allocated)
+        .add_metadata("reserveProof", reserve_proof(0))
+        .unwrap()
+        .add_global_state("reserves", reserves)
+        .unwrap()
+        .add_rights("mintRight", seal(2))
+        .unwrap()
+}
+
+/// Transfer-fee asset charging `fee_rate` basis points on each transfer.
+pub fn fee(issued: u64, allocated: u64, fee_rate: u64) -> ContractBuilder {
+    asset::<TransferFeeAsset>(issued, allocated)
+        .add_global_state("feeRate", Amount::from(fee_rate))
+        .unwrap()
+}
+
+/// Dividend-paying asset allocated to `seal(1)`, with the distribute right at `seal(2)`.
+pub fn dividend(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<DividendAsset>(issued, allocated)
+        .add_rights("distributeRight", seal(2))
+        .unwrap()
+}
+
+pub fn escrow(issued: u64, allocated: u64) -> ContractBuilder {
+    asset::<EscrowAsset>(issued, allocated)
+}
+
+/// Carbon credits allocated to `seal(1)`.
+pub fn carbon(issued: u64, allocated: u64) -> ContractBuilder {
+    builder::<CarbonCreditAsset>()
+        .add_global_state("name", Name::from("Test asset"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), allocated)
+        .unwrap()
+}
+
+/// Provenance token of index `token_index`, with `owned_index` allocated to `seal(1)`.
+pub fn provenance(token_index: u32, owned_index: u32) -> ContractBuilder {
+    builder::<ProvenanceAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test provenance", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index: TokenIndex::from_inner(token_index),
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(TokenIndex::from_inner(owned_index), 1))
+        .unwrap()
+}
+
+/// Subscription token of index `token_index` issued by `pubkey` and valid until the `expiry`
+/// height, with `owned_index` allocated to `seal(1)`.
+pub fn subscription(
+    token_index: u32,
+    owned_index: u32,
+    pubkey: CompressedPublicKey,
+    expiry: u32,
+) -> ContractBuilder {
+    builder::<SubscriptionAsset>()
+        .add_global_state(
+            "spec",
+            AssetSpec::new("TEST", "Test subscription", Precision::Indivisible),
+        )
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index: TokenIndex::from_inner(token_index),
+            ..Default::default()
+        })
+        .unwrap()
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_global_state("expiry", block_height(expiry))
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(TokenIndex::from_inner(owned_index), 1))
+        .unwrap()
+}
+
+/// Limited edition series of up to `max_editions` editions, with the mint right at `seal(1)`.
+pub fn edition(max_editions: u64) -> ContractBuilder {
+    builder::<LimitedEditionAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test editions", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("maxEditions", Amount::from(max_editions))
+        .unwrap()
+        .add_rights("mintRight", seal(1))
+        .unwrap()
+}
+
+/// Epoch inflatable asset allowing to inflate up to `max`, of which at most `cap` within an
+/// epoch of `epoch_length` blocks, with the inflation right of `inflation` units at `seal(2)` and
+/// the epoch issuance state, holding the epoch of the last inflation and the supply issued within
+/// it, at `seal(3)`.
+pub fn eifa(
+    issued: u64,
+    allocated: u64,
+    max: u64,
+    inflation: u64,
+    epoch_length: u32,
+    cap: u64,
+    issuance: (u32, u64),
+) -> ContractBuilder {
+    asset::<EpochInflatableAsset>(issued, allocated)
+        .add_global_state("maxSupply", Amount::from(max))
+        .unwrap()
+        .add_global_state("epochLength", Amount::from(epoch_length as u64))
+        .unwrap()
+        .add_global_state("epochCap", Amount::from(cap))
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(2), inflation)
+        .unwrap()
+        .add_data(
+            "epochIssuance",
+            seal(3),
+            Allocation::with(TokenIndex::from_inner(issuance.0), issuance.1),
+        )
+        .unwrap()
+}
+
+/// Claimable asset allocated to `seal(1)`, whose claims are collected by the `pubkey` issuer.
+pub fn claim(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+    asset::<ClaimableAsset>(issued, allocated)
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+}
+
+/// Name registry without registered names, with the register right at `seal(1)`.
+pub fn registry() -> ContractBuilder {
+    builder::<NameRegistry>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test names", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_rights("registerRight", seal(1))
+        .unwrap()
+}
+
+/// Rental token of index `token_index`, with `owned_index` allocated to `seal(1)`.
+pub fn rental(token_index: u32, owned_index: u32) -> ContractBuilder {
+    builder::<RentalAsset>()
+        .add_global_state("spec", AssetSpec::new("TEST", "Test rental", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index: TokenIndex::from_inner(token_index),
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(TokenIndex::from_inner(owned_index), 1))
+        .unwrap()
+}
+
+/// Options to buy units of the underlying asset for `strike` sats each up to the `expiry` height,
+/// with `allocated` options at `seal(1)`.
+pub fn option(issued: u64, allocated: u64, strike: u64, expiry: u32) -> ContractBuilder {
+    asset::<OptionAsset>(issued, allocated)
+        .add_global_state("underlyingAsset", OptionAsset::asset_ref(ContractId::from([0x33; 32])))
+        .unwrap()
+        .add_global_state("strike", Amount::from(strike))
+        .unwrap()
+        .add_global_state("expiry", block_height(expiry))
+        .unwrap()
+}
+
+/// Verifiable credential held at `seal(1)` whose claims are signed by `pubkey`, with the revoke
+/// right at `seal(2)`.
+pub fn credential(pubkey: CompressedPublicKey) -> ContractBuilder {
+    builder::<VerifiableCredential>()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_rights("assetOwner", seal(1))
+        .unwrap()
+        .add_rights("revokeRight", seal(2))
+        .unwrap()
+}
+
+/// Rebasing asset allocated to `seal(1)` whose rebases are signed by `pubkey`, with the rebase
+/// right at `seal(2)`.
+pub fn rebasing(issued: u64, allocated: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+    asset::<RebasingAsset>(issued, allocated)
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_rights("rebaseRight", seal(2))
+        .unwrap()
+}
+
+/// Swap order offering `offered` units for `price` units of the counter-asset, signed by the
+/// `pubkey` maker, with the order right at `seal(1)`.
+pub fn swap_order(offered: u64, price: u64, pubkey: CompressedPublicKey) -> ContractBuilder {
+    builder::<SwapOrder>()
+        .add_global_state("offeredAsset", SwapOrder::asset_ref(ContractId::from([0x11; 32])))
+        .unwrap()
+        .add_global_state("offeredAmount", Amount::from(offered))
+        .unwrap()
+        .add_global_state("counterAsset", SwapOrder::asset_ref(ContractId::from([0x22; 32])))
+        .unwrap()
+        .add_global_state("price", Amount::from(price))
+        .unwrap()
+        .add_global_state("pubkey", pubkey)
+        .unwrap()
+        .add_rights("orderRight", seal(1))
+        .unwrap()
+}
@@ -0,0 +1,2320 @@
+#![cfg(all_schemas)]
+
+mod common;
+
+use std::str::FromStr;
+
+use amplify::confinement::SmallBlob;
+use amplify::{Bytes32, Wrapper};
+use common::*;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::secp256k1::{Secp256k1, SecretKey};
+use rgbstd::stl::{Attachment, Details, EmbeddedMedia, MediaType, Name, TokenData};
+use rgbstd::{Allocation, Amount, AssignmentType, Outpoint, RevealedState, TokenIndex, Transition};
+use schemata::height::block_height;
+use schemata::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+    DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+    GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+    NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+    ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+    SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+    TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+    ValidationErrno, VerifiableCredential, VestingAsset, WrappedBtcAsset, OS_APPROVAL,
+    OS_APPROVED_INFLATION, OS_ASSET, OS_CLAIM, OS_EPOCH_ISSUANCE, OS_ESCROW, OS_FEE, OS_FREEZE,
+    OS_INFLATION, OS_LOCKED, OS_MINT, OS_ORDER, OS_PAUSE, OS_REATTACH, OS_REBASE, OS_REFUND,
+    OS_REGISTER, OS_REISSUE, OS_RELEASE, OS_REPLACE, OS_REVOKE, OS_ROTATE_KEY, OS_VOTE,
+};
+
+#[test]
+fn nia_errnos() {
+    assert_eq!(
+        genesis_errno(nia(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<NonInflatableAsset>(nia(1000, 1000));
+    let transfer = |outputs: &[u64]| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|mut builder| {
+                for (vout, value) in outputs.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("assetOwner", graph_seal(vout as u32), *value)
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    transfer(&[600, 400]).validate().unwrap();
+    assert_eq!(transfer(&[600, 399]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(&[600, 401]).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn cfa_errnos() {
+    assert_eq!(
+        genesis_errno(cfa(1000, 1001).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<CollectibleFungibleAsset>(cfa(1000, 1000));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn pfa_errnos() {
+    let pubkey = issuer_pubkey();
+
+    assert_eq!(
+        genesis_errno(pfa(1000, 999, pubkey).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<PermissionedFungibleAsset>(pfa(1000, 1000, pubkey));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+
+    let case = transfer(1000);
+    let mut signed = case.transition();
+    signed.signature = Some(sign(&signed));
+    case.validate_transition(&signed, true).unwrap();
+
+    // without the contract global state the issuer pubkey can't be loaded
+    assert_eq!(
+        script_errno(case.validate_transition(&signed, false).unwrap_err()),
+        Some(ValidationErrno::MissingPubkey)
+    );
+
+    // missing signature
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+
+    // signature not matching the transition
+    let mut unbalanced = transfer(999).transition();
+    unbalanced.signature = Some(sign(&signed));
+    let mut forged = signed.clone();
+    forged.signature = Some(sign(&unbalanced));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+
+    // sums are checked before the signature
+    assert_eq!(
+        script_errno(case.validate_transition(&unbalanced, true).unwrap_err()),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    // a key rotation spends the rotation right and must be signed by the current key, not by the
+    // one it publishes
+    let rotate = contract
+        .transition("rotateKey")
+        .input(OS_ROTATE_KEY, 0, RevealedState::Void)
+        .with(|builder| {
+            builder
+                .add_global_state("pubkey", cosigner_pubkey())
+                .unwrap()
+                .add_rights("rotateKeyRight", graph_seal(0))
+                .unwrap()
+        });
+    let mut rotation = rotate.transition();
+    rotation.signature = Some(sign(&rotation));
+    rotate.validate_transition(&rotation, true).unwrap();
+    let mut forged = rotation.clone();
+    forged.signature = Some(sign_with(&forged, &cosigner_key()));
+    assert_eq!(
+        script_errno(rotate.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+
+    // after the rotation transfers must be signed by the new key only
+    let case = transfer(1000).after(rotation);
+    let mut transition = case.transition();
+    transition.signature = Some(sign(&transition));
+    assert_eq!(
+        script_errno(case.validate_transition(&transition, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+    transition.signature = Some(sign_with(&transition, &cosigner_key()));
+    case.validate_transition(&transition, true).unwrap();
+}
+
+#[test]
+fn uda_errnos() {
+    assert_eq!(
+        genesis_errno(uda(2, 3, 1).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    assert_eq!(
+        genesis_errno(uda(2, 2, 2).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonFractional)
+    );
+    assert_eq!(
+        genesis_errno(uda(2, 2, 0).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonFractional)
+    );
+
+    let contract = TestContract::issue::<UniqueDigitalAsset>(uda(2, 2, 1));
+    let transfer = |index: u32, fraction: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+            })
+    };
+    transfer(2, 1).validate().unwrap();
+    assert_eq!(transfer(3, 1).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(2, 2).errno(), Some(ValidationErrno::NonFractional));
+
+    let engrave = |index: u32, fraction: u64| {
+        contract
+            .transition("engrave")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .add_global_state("engravings", Attachment {
+                        ty: MediaType::with("text/plain"),
+                        digest: Bytes32::from_byte_array([1; 32]),
+                    })
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+            })
+    };
+    engrave(2, 1).validate().unwrap();
+    assert_eq!(engrave(3, 1).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(engrave(2, 2).errno(), Some(ValidationErrno::NonFractional));
+
+    let contract = TestContract::issue::<UniqueDigitalAsset>(
+        uda(2, 2, 1).add_rights("reattachRight", seal(2)).unwrap(),
+    );
+    let reattach = |index: u32| {
+        contract
+            .transition("reattach")
+            .input(OS_REATTACH, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state("tokens", TokenData {
+                        index: TokenIndex::from_inner(index),
+                        media: Some(Attachment {
+                            ty: MediaType::with("image/png"),
+                            digest: Bytes32::from_byte_array([2; 32]),
+                        }),
+                        ..Default::default()
+                    })
+                    .unwrap()
+            })
+    };
+    reattach(2).validate().unwrap();
+    // the token data of a reattachment must describe the same token
+    assert_eq!(reattach(3).errno(), Some(ValidationErrno::TokenReindexed));
+}
+
+#[test]
+fn fua_errnos() {
+    fua(4, &[(2, 3), (2, 1)])
+        .issue_contract_raw(CREATED_AT)
+        .unwrap();
+    assert_eq!(
+        genesis_errno(fua(4, &[(2, 3), (2, 2)]).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+    assert_eq!(
+        genesis_errno(fua(4, &[(2, 3), (3, 1)]).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    let contract = TestContract::issue::<FractionalUniqueAsset>(fua(4, &[(2, 3), (2, 1)]));
+    let transfer = |inputs: &[(u32, u64)], outputs: &[(u32, u64)]| {
+        let mut case = contract.transition("transfer");
+        for (no, (index, fraction)) in inputs.iter().enumerate() {
+            case = case.input(OS_ASSET, no as u16, allocation(*index, *fraction));
+        }
+        case.with(|mut builder| {
+            for (vout, (index, fraction)) in outputs.iter().enumerate() {
+                builder = builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(vout as u32),
+                        Allocation::with(TokenIndex::from_inner(*index), *fraction),
+                    )
+                    .unwrap();
+            }
+            builder
+        })
+    };
+    // split
+    transfer(&[(2, 3)], &[(2, 1), (2, 2)]).validate().unwrap();
+    // merge
+    transfer(&[(2, 3), (2, 1)], &[(2, 4)]).validate().unwrap();
+    assert_eq!(
+        transfer(&[(2, 3)], &[(2, 1), (2, 1)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    assert_eq!(
+        transfer(&[(2, 3), (2, 1)], &[(2, 5)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    assert_eq!(transfer(&[(2, 3)], &[(3, 3)]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(
+        transfer(&[(2, 3), (3, 1)], &[(2, 4)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    // fractions can't overflow to wrap around the sum
+    assert_eq!(
+        transfer(&[(2, 3)], &[(2, u64::MAX), (2, 4)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+}
+
+#[test]
+fn collection_errnos() {
+    let genesis = |tokens: &[u32], allocations: &[(u32, u64)]| {
+        genesis_errno(collection(tokens, allocations).issue_contract_raw(CREATED_AT))
+    };
+    collection(&[1, 2, 5], &[(5, 1), (1, 1), (2, 1)])
+        .issue_contract_raw(CREATED_AT)
+        .unwrap();
+    assert_eq!(genesis(&[2, 1], &[(1, 1), (2, 1)]), Some(ValidationErrno::UnsortedTokens));
+    assert_eq!(genesis(&[1, 1], &[(1, 1)]), Some(ValidationErrno::UnsortedTokens));
+    assert_eq!(genesis(&[1, 2], &[(1, 1)]), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis(&[1, 2], &[(1, 1), (1, 1)]), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis(&[1, 2], &[(1, 1), (2, 1), (3, 1)]), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis(&[1, 2], &[(1, 1), (2, 2)]), Some(ValidationErrno::NonFractional));
+
+    let contract = TestContract::issue::<UniqueDigitalCollection>(collection(&[1, 2, 5], &[
+        (1, 1),
+        (2, 1),
+        (5, 1),
+    ]));
+    let transfer = |inputs: &[u32], outputs: &[(u32, u64)]| {
+        let mut case = contract.transition("transfer");
+        for (no, index) in inputs.iter().enumerate() {
+            case = case.input(OS_ASSET, no as u16, allocation(*index, 1));
+        }
+        case.with(|mut builder| {
+            for (vout, (index, fraction)) in outputs.iter().enumerate() {
+                builder = builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(vout as u32),
+                        Allocation::with(TokenIndex::from_inner(*index), *fraction),
+                    )
+                    .unwrap();
+            }
+            builder
+        })
+    };
+    transfer(&[5], &[(5, 1)]).validate().unwrap();
+    transfer(&[1, 5], &[(5, 1), (1, 1)]).validate().unwrap();
+    assert_eq!(transfer(&[1], &[(2, 1)]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(&[1, 2], &[(1, 1)]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(&[1], &[(1, 1), (1, 1)]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(&[1], &[(1, 1), (2, 1)]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(&[1], &[(1, 2)]).errno(), Some(ValidationErrno::NonFractional));
+}
+
+#[test]
+fn ifa_genesis_errnos() {
+    assert_eq!(
+        genesis_errno(ifa(1000, 999, 1500, 500).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+    assert_eq!(
+        genesis_errno(ifa(1000, 1000, 1500, 501).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::InflationMismatch)
+    );
+    // max supply lower than issued supply
+    assert_eq!(
+        genesis_errno(ifa(1000, 1000, 900, 100).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::InflationMismatch)
+    );
+}
+
+#[test]
+fn ifa_transfer_errnos() {
+    let contract = TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500));
+    let transfer = || contract.transition("transfer");
+
+    let assets = |output: u64| {
+        transfer().input(OS_ASSET, 0, amount(1000)).with(|builder| {
+            builder
+                .add_fungible_state("assetOwner", graph_seal(0), output)
+                .unwrap()
+        })
+    };
+    assets(1000).validate().unwrap();
+    assert_eq!(assets(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let inflation = |output: u64| {
+        transfer()
+            .input(OS_INFLATION, 0, amount(500))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("inflationAllowance", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    inflation(500).validate().unwrap();
+    assert_eq!(inflation(501).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let replace = transfer()
+        .input(OS_ASSET, 0, amount(1000))
+        .input(OS_REPLACE, 0, RevealedState::Void)
+        .with(|builder| {
+            builder
+                .add_fungible_state("assetOwner", graph_seal(0), 1000u64)
+                .unwrap()
+        });
+    assert_eq!(replace.errno(), Some(ValidationErrno::ReplaceHiddenBurn));
+    replace
+        .with(|builder| builder.add_rights("replaceRight", graph_seal(1)).unwrap())
+        .validate()
+        .unwrap();
+
+    let no_input =
+        assets(1000).with(|builder| builder.add_rights("replaceRight", graph_seal(1)).unwrap());
+    assert_eq!(no_input.errno(), Some(ValidationErrno::ReplaceNoInput));
+}
+
+#[test]
+fn ifa_inflation_errnos() {
+    let contract = TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500));
+    let inflate = |issued: u64, minted: u64, allowed: u64, remaining: u64| {
+        contract
+            .transition("inflate")
+            .input(OS_INFLATION, 0, amount(500))
+            .with(|builder| {
+                let builder = builder
+                    .add_global_state("issuedSupply", Amount::from(issued))
+                    .unwrap()
+                    .add_metadata("allowedInflation", Amount::from(allowed))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), minted)
+                    .unwrap();
+                if remaining > 0 {
+                    builder
+                        .add_fungible_state("inflationAllowance", graph_seal(1), remaining)
+                        .unwrap()
+                } else {
+                    builder
+                }
+            })
+    };
+    inflate(200, 200, 300, 300).validate().unwrap();
+    assert_eq!(inflate(200, 199, 300, 300).errno(), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(inflate(200, 200, 300, 299).errno(), Some(ValidationErrno::InflationMismatch));
+    assert_eq!(
+        inflate(200, 200, 301, 301).errno(),
+        Some(ValidationErrno::InflationExceedsAllowance)
+    );
+    assert_eq!(
+        inflate(201, 201, 300, 300).errno(),
+        Some(ValidationErrno::InflationExceedsAllowance)
+    );
+}
+
+#[test]
+fn ifa_burn_errnos() {
+    let contract = TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500));
+    let burn = |ty: AssignmentType, value: u64, burned: u64| {
+        contract
+            .transition("burn")
+            .input(ty, 0, amount(value))
+            .with(|builder| {
+                builder
+                    .add_global_state("burnedSupply", Amount::from(burned))
+                    .unwrap()
+            })
+    };
+    burn(OS_ASSET, 1000, 1000).validate().unwrap();
+    assert_eq!(burn(OS_ASSET, 1000, 999).errno(), Some(ValidationErrno::BurnMismatch));
+    // the burned inflation allowance isn't part of the burned supply
+    burn(OS_INFLATION, 500, 0).validate().unwrap();
+    assert_eq!(burn(OS_INFLATION, 500, 500).errno(), Some(ValidationErrno::BurnMismatch));
+}
+
+#[test]
+fn ria_errnos() {
+    assert_eq!(
+        genesis_errno(ria(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<ReissuableAsset>(ria(1000, 1000));
+    let burn = |burned: u64, recorded: u64| {
+        contract
+            .transition("burn")
+            .input(OS_ASSET, 0, amount(burned))
+            .with(|builder| {
+                builder
+                    .add_global_state("burnedSupply", Amount::from(recorded))
+                    .unwrap()
+            })
+    };
+    burn(300, 300).validate().unwrap();
+    assert_eq!(burn(300, 299).errno(), Some(ValidationErrno::BurnMismatch));
+    assert_eq!(burn(300, 301).errno(), Some(ValidationErrno::BurnMismatch));
+
+    let reissue = |issued: u64, minted: u64, history: &[Transition]| {
+        let mut case = contract
+            .transition("reissue")
+            .input(OS_REISSUE, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state("issuedSupply", Amount::from(issued))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), minted)
+                    .unwrap()
+                    .add_rights("reissueRight", graph_seal(1))
+                    .unwrap()
+            });
+        for prev in history {
+            case = case.after(prev.clone());
+        }
+        case
+    };
+    // the genesis issuance doesn't allow reissuing
+    assert_eq!(reissue(1, 1, &[]).errno(), Some(ValidationErrno::ReissueExceedsBurns));
+    assert_eq!(reissue(200, 199, &[]).errno(), Some(ValidationErrno::IssuedMismatch));
+
+    let burns = [burn(300, 300).transition(), burn(100, 100).transition()];
+    reissue(400, 400, &burns).validate().unwrap();
+    assert_eq!(reissue(401, 401, &burns).errno(), Some(ValidationErrno::ReissueExceedsBurns));
+
+    let reissued = |issued: u64| {
+        let mut history = burns.to_vec();
+        history.push(reissue(issued, issued, &[]).transition());
+        history
+    };
+    reissue(200, 200, &reissued(200)).validate().unwrap();
+    assert_eq!(
+        reissue(200, 200, &reissued(201)).errno(),
+        Some(ValidationErrno::ReissueExceedsBurns)
+    );
+}
+
+#[test]
+fn sba_errnos() {
+    assert_eq!(
+        genesis_errno(sba(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<SoulboundAsset>(sba(1000, 1000));
+    let transfer = |outputs: &[u64]| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|mut builder| {
+                for (vout, value) in outputs.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("assetOwner", graph_seal(vout as u32), *value)
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    // a transfer without asset outputs burns the asset
+    transfer(&[]).validate().unwrap();
+    assert_eq!(transfer(&[1000]).errno(), Some(ValidationErrno::Soulbound));
+    assert_eq!(transfer(&[600, 400]).errno(), Some(ValidationErrno::Soulbound));
+
+    // the revoke right stays transferable
+    contract
+        .transition("transfer")
+        .input(OS_REVOKE, 0, RevealedState::Void)
+        .with(|builder| builder.add_rights("revokeRight", graph_seal(0)).unwrap())
+        .validate()
+        .unwrap();
+
+    let burn = |burned: u64| {
+        contract
+            .transition("burn")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_global_state("burnedSupply", Amount::from(burned))
+                    .unwrap()
+            })
+    };
+    burn(1000).validate().unwrap();
+    assert_eq!(burn(999).errno(), Some(ValidationErrno::BurnMismatch));
+
+    let revoke = |vouts: &[u32]| {
+        contract
+            .transition("revoke")
+            .input(OS_REVOKE, 0, RevealedState::Void)
+            .with(|mut builder| {
+                for vout in vouts {
+                    builder = builder
+                        .add_global_state("revocations", Outpoint::new(txid(), *vout))
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    revoke(&[1]).validate().unwrap();
+    assert_eq!(revoke(&[1, 2]).errno(), Some(ValidationErrno::RevocationMismatch));
+}
+
+#[test]
+fn sfa_errnos() {
+    let genesis = |tokens: &[u32], allocations: &[(u32, u64)]| {
+        genesis_errno(sfa(tokens, allocations).issue_contract_raw(CREATED_AT))
+    };
+    // tokens may be left unallocated or allocated several times
+    sfa(&[1, 2, 5], &[(5, 10), (1, 20), (5, 30)])
+        .issue_contract_raw(CREATED_AT)
+        .unwrap();
+    assert_eq!(genesis(&[2, 1], &[(1, 10), (2, 10)]), Some(ValidationErrno::UnsortedTokens));
+    assert_eq!(genesis(&[1, 1], &[(1, 10)]), Some(ValidationErrno::UnsortedTokens));
+    assert_eq!(genesis(&[1, 2], &[(1, 10), (3, 10)]), Some(ValidationErrno::UnknownToken));
+
+    let contract =
+        TestContract::issue::<SemiFungibleAsset>(sfa(&[1, 2, 5], &[(1, 100), (2, 100), (5, 100)]));
+    let transfer = |inputs: &[(u32, u64)], outputs: &[(u32, u64)]| {
+        let mut case = contract.transition("transfer");
+        for (no, (index, amount)) in inputs.iter().enumerate() {
+            case = case.input(OS_ASSET, no as u16, allocation(*index, *amount));
+        }
+        case.with(|mut builder| {
+            for (vout, (index, amount)) in outputs.iter().enumerate() {
+                builder = builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(vout as u32),
+                        Allocation::with(TokenIndex::from_inner(*index), *amount),
+                    )
+                    .unwrap();
+            }
+            builder
+        })
+    };
+    transfer(&[(1, 100)], &[(1, 60), (1, 40)])
+        .validate()
+        .unwrap();
+    transfer(&[(1, 100), (5, 100)], &[(5, 30), (1, 100), (5, 70)])
+        .validate()
+        .unwrap();
+    assert_eq!(
+        transfer(&[(1, 100)], &[(1, 60), (1, 30)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    assert_eq!(
+        transfer(&[(1, 100)], &[(1, 60), (1, 50)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    // amounts can't move between tokens, even if the total is kept
+    assert_eq!(
+        transfer(&[(1, 100), (2, 100)], &[(1, 150), (2, 50)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    // an output can't allocate a token missing from the inputs
+    assert_eq!(
+        transfer(&[(1, 100)], &[(1, 100), (2, 1)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    // nor can the inputs lose a token
+    assert_eq!(
+        transfer(&[(1, 100), (2, 100)], &[(1, 100)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    assert_eq!(
+        transfer(&[(1, u64::MAX), (1, 1)], &[(1, 1)]).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+}
+
+#[test]
+fn stablecoin_errnos() {
+    let pubkey = issuer_pubkey();
+    assert_eq!(
+        genesis_errno(stablecoin(1000, 999, 1500, 500, pubkey).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+    assert_eq!(
+        genesis_errno(stablecoin(1000, 1000, 1500, 499, pubkey).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::InflationMismatch)
+    );
+
+    let contract =
+        TestContract::issue::<StablecoinAsset>(stablecoin(1000, 1000, 1500, 500, pubkey));
+    let signed_errno = |case: &TransitionCase| {
+        script_errno(
+            case.validate_transition(&case.signed(), true)
+                .expect_err("invalid transition passed validation"),
+        )
+    };
+
+    // transfers need no signature
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let inflate = |issued: u64, minted: u64, allowed: u64, remaining: u64| {
+        contract
+            .transition("inflate")
+            .input(OS_INFLATION, 0, amount(500))
+            .with(|builder| {
+                builder
+                    .add_global_state("issuedSupply", Amount::from(issued))
+                    .unwrap()
+                    .add_metadata("allowedInflation", Amount::from(allowed))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), minted)
+                    .unwrap()
+                    .add_fungible_state("inflationAllowance", graph_seal(1), remaining)
+                    .unwrap()
+            })
+    };
+    let case = inflate(200, 200, 300, 300);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+    // without the contract global state the oracle pubkey can't be loaded
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), false).unwrap_err()),
+        Some(ValidationErrno::MissingPubkey)
+    );
+    // supply is checked before the signature
+    assert_eq!(signed_errno(&inflate(200, 199, 300, 300)), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(
+        signed_errno(&inflate(200, 200, 300, 299)),
+        Some(ValidationErrno::InflationMismatch)
+    );
+    assert_eq!(
+        signed_errno(&inflate(201, 201, 300, 300)),
+        Some(ValidationErrno::InflationExceedsAllowance)
+    );
+
+    let redeem = |redeemed: u64, recorded: u64| {
+        contract
+            .transition("redeem")
+            .input(OS_ASSET, 0, amount(redeemed))
+            .with(|builder| {
+                builder
+                    .add_global_state("redeemedSupply", Amount::from(recorded))
+                    .unwrap()
+            })
+    };
+    let case = redeem(300, 300);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+    assert_eq!(signed_errno(&redeem(300, 299)), Some(ValidationErrno::RedeemMismatch));
+    assert_eq!(signed_errno(&redeem(300, 301)), Some(ValidationErrno::RedeemMismatch));
+
+    // a signature over another transition doesn't authorize a redeem
+    let mut forged = case.transition();
+    forged.signature = Some(sign(&redeem(200, 200).transition()));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+}
+
+#[test]
+fn bond_errnos() {
+    assert_eq!(
+        genesis_errno(bond(1000, 999, 100).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<BondAsset>(bond(1000, 1000, 100));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let redeem = |recorded: u64, lock_time: u32| {
+        contract
+            .transition("redeem")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_global_state("redeemedSupply", Amount::from(recorded))
+                    .unwrap()
+                    .add_global_state("lockTime", block_height(lock_time))
+                    .unwrap()
+            })
+    };
+    // a lock time of 99 lets the witness be mined from the maturity height 100 on
+    redeem(1000, 99).validate().unwrap();
+    assert_eq!(redeem(999, 99).errno(), Some(ValidationErrno::RedeemMismatch));
+    assert_eq!(redeem(1001, 99).errno(), Some(ValidationErrno::RedeemMismatch));
+    assert_eq!(redeem(1000, 98).errno(), Some(ValidationErrno::RedeemLocked));
+    // lock times from 500 000 000 on are timestamps, not heights
+    assert_eq!(redeem(1000, 500_000_000).errno(), Some(ValidationErrno::RedeemLocked));
+    // the lock time is checked before the redeemed supply
+    assert_eq!(redeem(999, 98).errno(), Some(ValidationErrno::RedeemLocked));
+}
+
+#[test]
+fn governance_errnos() {
+    let genesis = |voting_unit: u64, allocated: u64, vote_rights: u32| {
+        genesis_errno(
+            governance(1000, voting_unit, allocated, vote_rights).issue_contract_raw(CREATED_AT),
+        )
+    };
+    assert_eq!(genesis(250, 999, 4), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis(250, 1000, 3), Some(ValidationErrno::VoteRightsMismatch));
+    assert_eq!(genesis(250, 1000, 5), Some(ValidationErrno::VoteRightsMismatch));
+    // the voting unit must divide the issued supply
+    assert_eq!(genesis(300, 1000, 3), Some(ValidationErrno::VoteRightsMismatch));
+    assert_eq!(genesis(300, 1000, 4), Some(ValidationErrno::VoteRightsMismatch));
+
+    let contract = TestContract::issue::<GovernanceAsset>(governance(1000, 250, 1000, 4));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    // casting a vote is only constrained by the schema
+    contract
+        .transition("cast")
+        .input(OS_VOTE, 0, RevealedState::Void)
+        .with(|builder| {
+            builder
+                .add_global_state("votes", Details::from_str("yes").unwrap())
+                .unwrap()
+        })
+        .validate()
+        .unwrap();
+}
+
+#[test]
+fn ticket_errnos() {
+    assert_eq!(
+        genesis_errno(ticket(&[2, 1]).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::UnsortedTokens)
+    );
+
+    let contract = TestContract::issue::<TicketAsset>(ticket(&[1, 2]));
+    let transfer = |input: u32, output: u32| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, allocation(input, 1))
+            .with(|builder| {
+                builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(output), 1),
+                    )
+                    .unwrap()
+            })
+    };
+    transfer(1, 1).validate().unwrap();
+    assert_eq!(transfer(1, 2).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let redeem = |spent: u32, recorded: u32| {
+        contract
+            .transition("redeem")
+            .input(OS_ASSET, 0, allocation(spent, 1))
+            .with(|builder| {
+                builder
+                    .add_global_state(
+                        "redeemedTickets",
+                        Allocation::with(TokenIndex::from_inner(recorded), 1),
+                    )
+                    .unwrap()
+            })
+    };
+    redeem(2, 2).validate().unwrap();
+    assert_eq!(redeem(2, 1).errno(), Some(ValidationErrno::TicketMismatch));
+}
+
+#[test]
+fn vesting_errnos() {
+    let genesis = |vested: u64, allocated: u64, locked: u64, cliff: u32, end: u32| {
+        genesis_errno(
+            vesting(1000, vested, allocated, locked, cliff, end).issue_contract_raw(CREATED_AT),
+        )
+    };
+    assert_eq!(genesis(600, 400, 600, 100, 100), Some(ValidationErrno::InvalidSchedule));
+    assert_eq!(genesis(600, 400, 600, 200, 100), Some(ValidationErrno::InvalidSchedule));
+    assert_eq!(genesis(600, 400, 599, 100, 200), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis(600, 399, 600, 100, 200), Some(ValidationErrno::IssuedMismatch));
+    // the vested supply can't exceed the issued one
+    assert_eq!(genesis(1100, 400, 1100, 100, 200), Some(ValidationErrno::IssuedMismatch));
+
+    let contract = TestContract::issue::<VestingAsset>(vesting(1000, 600, 400, 600, 100, 200));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(400))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(400).validate().unwrap();
+    assert_eq!(transfer(399).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let release_at = |lock_time: u32, released: u64, locked: u64, outputs: (u64, u64)| {
+        contract
+            .transition("release")
+            .input(OS_LOCKED, 0, amount(600))
+            .with(|mut builder| {
+                builder = builder
+                    .add_global_state("releasedSupply", Amount::from(released))
+                    .unwrap()
+                    .add_global_state("lockedSupply", Amount::from(locked))
+                    .unwrap()
+                    .add_global_state("lockTime", block_height(lock_time))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), outputs.0)
+                    .unwrap();
+                if outputs.1 > 0 {
+                    builder = builder
+                        .add_fungible_state("lockedAsset", graph_seal(1), outputs.1)
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    // a lock time of 199 lets the witness be mined from the end of the schedule on
+    let release = |released: u64, locked: u64, outputs: (u64, u64)| {
+        release_at(199, released, locked, outputs)
+    };
+    release(200, 400, (200, 400)).validate().unwrap();
+    release(600, 0, (600, 0)).validate().unwrap();
+    assert_eq!(release(200, 400, (199, 400)).errno(), Some(ValidationErrno::ReleaseMismatch));
+    assert_eq!(release(200, 400, (200, 399)).errno(), Some(ValidationErrno::ReleaseMismatch));
+    assert_eq!(release(200, 300, (200, 300)).errno(), Some(ValidationErrno::ReleaseMismatch));
+    assert_eq!(release(200, 500, (200, 500)).errno(), Some(ValidationErrno::ReleaseMismatch));
+
+    // height 134 unlocks 600 * 34 / 100 = 204 units, height 133 only 198
+    release_at(133, 200, 400, (200, 400)).validate().unwrap();
+    release_at(499_999_999, 600, 0, (600, 0))
+        .validate()
+        .unwrap();
+    assert_eq!(release_at(132, 200, 400, (200, 400)).errno(), Some(ValidationErrno::ReleaseLocked));
+    assert_eq!(release_at(198, 600, 0, (600, 0)).errno(), Some(ValidationErrno::ReleaseLocked));
+    // nothing is unlocked before the cliff
+    assert_eq!(release_at(0, 1, 599, (1, 599)).errno(), Some(ValidationErrno::ReleaseLocked));
+    // lock times from 500 000 000 on are timestamps, not heights
+    assert_eq!(
+        release_at(500_000_000, 200, 400, (200, 400)).errno(),
+        Some(ValidationErrno::ReleaseLocked)
+    );
+    // the released supply is checked before the lock time
+    assert_eq!(
+        release_at(132, 200, 400, (199, 400)).errno(),
+        Some(ValidationErrno::ReleaseMismatch)
+    );
+}
+
+#[test]
+fn timelock_errnos() {
+    assert_eq!(
+        genesis_errno(timelock(1000, 999, 100).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<TimelockAsset>(timelock(1000, 1000, 100));
+    let transfer = |output: u64, lock_time: u32| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+                    .add_global_state("lockTime", block_height(lock_time))
+                    .unwrap()
+            })
+    };
+    // a lock time of 99 lets the witness be mined from height 100 on
+    transfer(1000, 99).validate().unwrap();
+    transfer(1000, 499_999_999).validate().unwrap();
+    assert_eq!(transfer(1000, 98).errno(), Some(ValidationErrno::TransferLocked));
+    // lock times from 500 000 000 on are timestamps, not heights
+    assert_eq!(transfer(1000, 500_000_000).errno(), Some(ValidationErrno::TransferLocked));
+    assert_eq!(transfer(999, 99).errno(), Some(ValidationErrno::NonEqualInOut));
+    // the lock time is checked before the sums
+    assert_eq!(transfer(999, 98).errno(), Some(ValidationErrno::TransferLocked));
+}
+
+#[test]
+fn pfan_errnos() {
+    let pubkeys = [issuer_pubkey(), cosigner_pubkey()];
+    assert_eq!(
+        genesis_errno(pfan(1000, 999, &pubkeys).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<MultiKeyPermissionedAsset>(pfan(1000, 1000, &pubkeys));
+    let transfer = |output: u64, signer| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+                    .add_metadata("signer", signer)
+                    .unwrap()
+            })
+    };
+
+    // each authorized key can sign, matching the signer metadata
+    for (key, pubkey) in [(issuer_key(), issuer_pubkey()), (cosigner_key(), cosigner_pubkey())] {
+        let case = transfer(1000, pubkey);
+        let mut signed = case.transition();
+        signed.signature = Some(sign_with(&signed, &key));
+        case.validate_transition(&signed, true).unwrap();
+    }
+
+    // signature of an authorized key other than the signer
+    let case = transfer(1000, cosigner_pubkey());
+    let mut signed = case.transition();
+    signed.signature = Some(sign(&signed));
+    assert_eq!(
+        script_errno(case.validate_transition(&signed, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+
+    // signer outside of the authorized keys
+    let key = SecretKey::from_slice(&[0x44; 32]).unwrap();
+    let pubkey = CompressedPublicKey(key.public_key(&Secp256k1::signing_only()));
+    let case = transfer(1000, pubkey);
+    let mut signed = case.transition();
+    signed.signature = Some(sign_with(&signed, &key));
+    assert_eq!(
+        script_errno(case.validate_transition(&signed, true).unwrap_err()),
+        Some(ValidationErrno::UnauthorizedSigner)
+    );
+
+    // sums are checked before the signer
+    assert_eq!(transfer(999, pubkey).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn freezable_errnos() {
+    assert_eq!(
+        genesis_errno(freezable(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<FreezableAsset>(freezable(1000, 1000));
+    let transfer = |output: u64, vouts: &[u32]| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|mut builder| {
+                for vout in vouts {
+                    builder = builder
+                        .add_global_state("spentUtxos", Outpoint::new(txid(), *vout))
+                        .unwrap();
+                }
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    let freeze = |vouts: &[u32]| {
+        contract
+            .transition("freeze")
+            .input(OS_FREEZE, 0, RevealedState::Void)
+            .with(|mut builder| {
+                for vout in vouts {
+                    builder = builder
+                        .add_global_state("frozen", Outpoint::new(txid(), *vout))
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    transfer(1000, &[1]).validate().unwrap();
+    assert_eq!(transfer(999, &[1]).errno(), Some(ValidationErrno::NonEqualInOut));
+    freeze(&[1]).validate().unwrap();
+    freeze(&[1, 3]).validate().unwrap();
+
+    // the frozen UTXOs are read from the contract global state
+    let frozen = freeze(&[1]).transition();
+    transfer(1000, &[3])
+        .after(frozen.clone())
+        .validate()
+        .unwrap();
+    assert_eq!(
+        transfer(1000, &[1]).after(frozen.clone()).errno(),
+        Some(ValidationErrno::FrozenSpend)
+    );
+    assert_eq!(
+        transfer(1000, &[3, 1]).after(frozen.clone()).errno(),
+        Some(ValidationErrno::FrozenSpend)
+    );
+    freeze(&[3]).after(frozen.clone()).validate().unwrap();
+    assert_eq!(freeze(&[1]).after(frozen.clone()).errno(), Some(ValidationErrno::AlreadyFrozen));
+    assert_eq!(freeze(&[3, 1]).after(frozen.clone()).errno(), Some(ValidationErrno::AlreadyFrozen));
+    let refrozen = freeze(&[3]).after(frozen.clone()).transition();
+    assert_eq!(
+        transfer(1000, &[3]).after(frozen).after(refrozen).errno(),
+        Some(ValidationErrno::FrozenSpend)
+    );
+
+    // sums are checked before the spent UTXOs
+    assert_eq!(
+        transfer(999, &[1]).after(freeze(&[1]).transition()).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+}
+
+#[test]
+fn tifa_errnos() {
+    let pubkeys = [issuer_pubkey(), cosigner_pubkey()];
+    let issue = |allocated: u64, threshold: u64, approval: (u32, u64)| {
+        tifa(1000, allocated, threshold, &pubkeys, approval).issue_contract_raw(CREATED_AT)
+    };
+    issue(1000, 2, (0, 0)).unwrap();
+    issue(1000, 1, (0, 0)).unwrap();
+    assert_eq!(genesis_errno(issue(999, 2, (0, 0))), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis_errno(issue(1000, 0, (0, 0))), Some(ValidationErrno::InvalidThreshold));
+    assert_eq!(genesis_errno(issue(1000, 3, (0, 0))), Some(ValidationErrno::InvalidThreshold));
+    assert_eq!(genesis_errno(issue(1000, 2, (0, 1))), Some(ValidationErrno::ApprovalMismatch));
+    assert_eq!(genesis_errno(issue(1000, 2, (1, 0))), Some(ValidationErrno::ApprovalMismatch));
+
+    let contract =
+        TestContract::issue::<ThresholdInflatableAsset>(tifa(1000, 1000, 2, &pubkeys, (0, 0)));
+    // the approved inflation spent, none for the first approval, and assigned next to the
+    // approval state
+    let approve = |signer, input: (u32, u64), output: (u32, u64), approved: (u64, u64)| {
+        let case =
+            contract
+                .transition("approve")
+                .input(OS_APPROVAL, 0, allocation(input.0, input.1));
+        let case = match approved.0 {
+            0 => case,
+            spent => case.input(OS_APPROVED_INFLATION, 0, amount(spent)),
+        };
+        case.with(|builder| {
+            builder
+                .add_metadata("signer", signer)
+                .unwrap()
+                .add_data(
+                    "inflationApproval",
+                    graph_seal(0),
+                    Allocation::with(TokenIndex::from_inner(output.0), output.1),
+                )
+                .unwrap()
+                .add_fungible_state("approvedInflation", graph_seal(0), approved.1)
+                .unwrap()
+        })
+    };
+    let approve_errno = |key: &SecretKey, signer, input, output, approved| {
+        let case = approve(signer, input, output, approved);
+        let mut signed = case.transition();
+        signed.signature = Some(sign_with(&signed, key));
+        case.validate_transition(&signed, true)
+            .err()
+            .and_then(script_errno)
+    };
+
+    // the contract state lists the keys starting from the last issued one, so the cosigner
+    // key approves first
+    let cosigner = (cosigner_key(), cosigner_pubkey());
+    let issuer = (issuer_key(), issuer_pubkey());
+    assert_eq!(approve_errno(&cosigner.0, cosigner.1, (0, 0), (1, 1), (0, 200)), None);
+    assert_eq!(approve_errno(&issuer.0, issuer.1, (1, 1), (2, 2), (200, 200)), None);
+    assert_eq!(approve_errno(&issuer.0, issuer.1, (0, 0), (2, 1), (0, 200)), None);
+
+    // keys approve once and in order
+    assert_eq!(
+        approve_errno(&cosigner.0, cosigner.1, (1, 1), (1, 2), (200, 200)),
+        Some(ValidationErrno::ApprovalMismatch)
+    );
+    assert_eq!(
+        approve_errno(&issuer.0, issuer.1, (2, 1), (2, 2), (200, 200)),
+        Some(ValidationErrno::ApprovalMismatch)
+    );
+    // the output must record the signer position and the increased count
+    assert_eq!(
+        approve_errno(&cosigner.0, cosigner.1, (0, 0), (2, 1), (0, 200)),
+        Some(ValidationErrno::ApprovalMismatch)
+    );
+    assert_eq!(
+        approve_errno(&cosigner.0, cosigner.1, (0, 0), (1, 2), (0, 200)),
+        Some(ValidationErrno::ApprovalMismatch)
+    );
+    // only the first approval sets the approved inflation
+    assert_eq!(
+        approve_errno(&issuer.0, issuer.1, (1, 1), (2, 2), (200, 300)),
+        Some(ValidationErrno::ApprovalMismatch)
+    );
+
+    // signature of an authorized key other than the signer
+    assert_eq!(
+        approve_errno(&issuer.0, cosigner.1, (0, 0), (1, 1), (0, 200)),
+        Some(ValidationErrno::InvalidSignature)
+    );
+
+    // signer outside of the authorized keys
+    let key = SecretKey::from_slice(&[0x44; 32]).unwrap();
+    let pubkey = CompressedPublicKey(key.public_key(&Secp256k1::signing_only()));
+    assert_eq!(
+        approve_errno(&key, pubkey, (0, 0), (1, 1), (0, 200)),
+        Some(ValidationErrno::UnauthorizedSigner)
+    );
+
+    let inflate = |minted: u64, approvals: u64, output: (u32, u64), approved: u64| {
+        contract
+            .transition("inflate")
+            .input(OS_INFLATION, 0, amount(500))
+            .input(OS_APPROVAL, 0, allocation(2, approvals))
+            .input(OS_APPROVED_INFLATION, 0, amount(approved))
+            .with(|builder| {
+                builder
+                    .add_global_state("issuedSupply", Amount::from(200u64))
+                    .unwrap()
+                    .add_metadata("allowedInflation", Amount::from(300u64))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), minted)
+                    .unwrap()
+                    .add_fungible_state("inflationAllowance", graph_seal(1), 300u64)
+                    .unwrap()
+                    .add_data(
+                        "inflationApproval",
+                        graph_seal(2),
+                        Allocation::with(TokenIndex::from_inner(output.0), output.1),
+                    )
+                    .unwrap()
+            })
+    };
+    inflate(200, 2, (0, 0), 200).validate().unwrap();
+    assert_eq!(inflate(199, 2, (0, 0), 200).errno(), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(inflate(200, 1, (0, 0), 200).errno(), Some(ValidationErrno::InsufficientApprovals));
+    assert_eq!(inflate(200, 2, (0, 0), 300).errno(), Some(ValidationErrno::UnapprovedInflation));
+    assert_eq!(inflate(200, 2, (2, 2), 200).errno(), Some(ValidationErrno::ApprovalMismatch));
+    assert_eq!(inflate(200, 2, (0, 1), 200).errno(), Some(ValidationErrno::ApprovalMismatch));
+}
+
+#[test]
+fn wbtc_errnos() {
+    wbtc(1000, 1000, reserve_proof(0))
+        .issue_contract_raw(CREATED_AT)
+        .unwrap();
+    assert_eq!(
+        genesis_errno(wbtc(1000, 999, reserve_proof(0)).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+    assert_eq!(
+        genesis_errno(wbtc(1000, 1000, reserve_proof(1)).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::ReserveMismatch)
+    );
+
+    let contract = TestContract::issue::<WrappedBtcAsset>(wbtc(1000, 1000, reserve_proof(0)));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let burn = |burned: u64, recorded: u64| {
+        contract
+            .transition("burn")
+            .input(OS_ASSET, 0, amount(burned))
+            .with(|builder| {
+                builder
+                    .add_global_state("burnedSupply", Amount::from(recorded))
+                    .unwrap()
+            })
+    };
+    burn(300, 300).validate().unwrap();
+    assert_eq!(burn(300, 299).errno(), Some(ValidationErrno::BurnMismatch));
+
+    let mint = |minted: u64, proof: u32, recorded: u32| {
+        contract
+            .transition("mint")
+            .input(OS_MINT, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_metadata("reserveProof", reserve_proof(proof))
+                    .unwrap()
+                    .add_global_state("issuedSupply", Amount::from(500u64))
+                    .unwrap()
+                    .add_global_state("reserves", reserve_proof(recorded))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), minted)
+                    .unwrap()
+                    .add_rights("mintRight", graph_seal(1))
+                    .unwrap()
+            })
+    };
+    mint(500, 1, 1).validate().unwrap();
+    assert_eq!(mint(499, 1, 1).errno(), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(mint(500, 1, 2).errno(), Some(ValidationErrno::ReserveMismatch));
+    assert_eq!(mint(500, 2, 1).errno(), Some(ValidationErrno::ReserveMismatch));
+}
+
+#[test]
+fn fee_errnos() {
+    fee(1000, 1000, 10_000)
+        .issue_contract_raw(CREATED_AT)
+        .unwrap();
+    assert_eq!(
+        genesis_errno(fee(1000, 999, 25).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+    assert_eq!(
+        genesis_errno(fee(1000, 1000, 10_001).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::InvalidFeeRate)
+    );
+
+    // 2.5% of 1000 is 25
+    let contract = TestContract::issue::<TransferFeeAsset>(fee(1000, 1000, 250));
+    let transfer = |outputs: &[u64], fees: &[u64]| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|mut builder| {
+                for (vout, value) in outputs.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("assetOwner", graph_seal(vout as u32), *value)
+                        .unwrap();
+                }
+                for (vout, value) in fees.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("feeOwner", graph_seal(10 + vout as u32), *value)
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    transfer(&[975], &[25]).validate().unwrap();
+    transfer(&[600, 375], &[20, 5]).validate().unwrap();
+    assert_eq!(transfer(&[976], &[25]).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(&[1000], &[]).errno(), Some(ValidationErrno::FeeMismatch));
+    assert_eq!(transfer(&[976], &[24]).errno(), Some(ValidationErrno::FeeMismatch));
+    assert_eq!(transfer(&[974], &[26]).errno(), Some(ValidationErrno::FeeMismatch));
+
+    let collect = |collected: u64| {
+        contract
+            .transition("collect")
+            .input(OS_FEE, 0, amount(25))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), collected)
+                    .unwrap()
+            })
+    };
+    collect(25).validate().unwrap();
+    assert_eq!(collect(24).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn fee_rounding() {
+    // 0.3% of 333 is 0.999, rounded down to no fee
+    let contract = TestContract::issue::<TransferFeeAsset>(fee(u64::MAX, u64::MAX, 30));
+    let transfer = |input: u64, output: u64, fee: Option<u64>| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(input))
+            .with(|builder| {
+                let builder = builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap();
+                match fee {
+                    Some(fee) => builder
+                        .add_fungible_state("feeOwner", graph_seal(1), fee)
+                        .unwrap(),
+                    None => builder,
+                }
+            })
+    };
+    transfer(333, 333, None).validate().unwrap();
+    transfer(334, 333, Some(1)).validate().unwrap();
+    // The fee of the whole supply doesn't overflow
+    let fee = (u64::MAX as u128 * 30 / 10_000) as u64;
+    transfer(u64::MAX, u64::MAX - fee, Some(fee))
+        .validate()
+        .unwrap();
+    assert_eq!(
+        transfer(u64::MAX, u64::MAX - fee - 1, Some(fee + 1)).errno(),
+        Some(ValidationErrno::FeeMismatch)
+    );
+}
+
+#[test]
+fn dividend_errnos() {
+    assert_eq!(
+        genesis_errno(dividend(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<DividendAsset>(dividend(1000, 1000));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn escrow_errnos() {
+    assert_eq!(
+        genesis_errno(escrow(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<EscrowAsset>(escrow(1000, 1000));
+    let lock = |escrowed: u64, change: Option<u64>| {
+        contract
+            .transition("escrow")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                let builder = builder
+                    .add_fungible_state("escrowedAsset", graph_seal(0), escrowed)
+                    .unwrap()
+                    .add_rights("releaseRight", graph_seal(1))
+                    .unwrap()
+                    .add_rights("refundRight", graph_seal(2))
+                    .unwrap();
+                match change {
+                    Some(change) => builder
+                        .add_fungible_state("assetOwner", graph_seal(3), change)
+                        .unwrap(),
+                    None => builder,
+                }
+            })
+    };
+    lock(1000, None).validate().unwrap();
+    lock(400, Some(600)).validate().unwrap();
+    assert_eq!(lock(999, None).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(lock(400, Some(599)).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(lock(400, Some(601)).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    for (name, right) in [("release", OS_RELEASE), ("refund", OS_REFUND)] {
+        let settle = |outputs: &[u64]| {
+            contract
+                .transition(name)
+                .input(OS_ESCROW, 0, amount(400))
+                .input(right, 0, RevealedState::Void)
+                .with(|mut builder| {
+                    for (vout, output) in outputs.iter().enumerate() {
+                        builder = builder
+                            .add_fungible_state("assetOwner", graph_seal(vout as u32), *output)
+                            .unwrap();
+                    }
+                    builder
+                })
+        };
+        settle(&[400]).validate().unwrap();
+        settle(&[150, 250]).validate().unwrap();
+        assert_eq!(settle(&[399]).errno(), Some(ValidationErrno::NonEqualInOut));
+        assert_eq!(settle(&[150, 251]).errno(), Some(ValidationErrno::NonEqualInOut));
+    }
+}
+
+#[test]
+fn swap_errnos() {
+    let contract = TestContract::issue::<SwapOrder>(swap_order(1000, 2500, issuer_pubkey()));
+    let signed_errno = |case: &TransitionCase| {
+        script_errno(
+            case.validate_transition(&case.signed(), true)
+                .expect_err("invalid transition passed validation"),
+        )
+    };
+
+    let fill = |filled: u64| {
+        contract
+            .transition("fill")
+            .input(OS_ORDER, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state("filledAmount", Amount::from(filled))
+                    .unwrap()
+                    .add_rights("orderRight", graph_seal(0))
+                    .unwrap()
+            })
+    };
+    let case = fill(600);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+    let case = fill(1000);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(signed_errno(&fill(1001)), Some(ValidationErrno::FillExceedsOffer));
+
+    // previous fills count towards the offered amount
+    let prev = fill(600).signed();
+    let case = fill(400).after(prev.clone());
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(signed_errno(&fill(401).after(prev)), Some(ValidationErrno::FillExceedsOffer));
+
+    // a fill signed by someone else than the maker is rejected
+    let case = fill(600);
+    let mut forged = case.transition();
+    forged.signature = Some(sign_with(&forged, &cosigner_key()));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+
+    let case = contract
+        .transition("cancel")
+        .input(OS_ORDER, 0, RevealedState::Void);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+    // without the contract global state the maker pubkey can't be loaded
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), false).unwrap_err()),
+        Some(ValidationErrno::MissingPubkey)
+    );
+}
+
+#[test]
+fn carbon_errnos() {
+    assert_eq!(
+        genesis_errno(carbon(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<CarbonCreditAsset>(carbon(1000, 1000));
+    let retire = |retired: u64, change: &[u64]| {
+        contract
+            .transition("retire")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|mut builder| {
+                builder = builder
+                    .add_global_state("retiredSupply", Amount::from(retired))
+                    .unwrap()
+                    .add_global_state(
+                        "retirementBeneficiary",
+                        Details::from_str("ACME Corp").unwrap(),
+                    )
+                    .unwrap()
+                    .add_global_state(
+                        "retirementPurpose",
+                        Details::from_str("2025 emissions").unwrap(),
+                    )
+                    .unwrap();
+                for (vout, value) in change.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("assetOwner", graph_seal(vout as u32), *value)
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    retire(1000, &[]).validate().unwrap();
+    retire(300, &[700]).validate().unwrap();
+    retire(300, &[200, 500]).validate().unwrap();
+    assert_eq!(retire(999, &[]).errno(), Some(ValidationErrno::BurnMismatch));
+    assert_eq!(retire(300, &[699]).errno(), Some(ValidationErrno::BurnMismatch));
+    assert_eq!(retire(300, &[701]).errno(), Some(ValidationErrno::BurnMismatch));
+}
+
+#[test]
+fn provenance_errnos() {
+    assert_eq!(
+        genesis_errno(provenance(2, 3).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    let contract = TestContract::issue::<ProvenanceAsset>(provenance(2, 2));
+    let checkpoint = |index: u32, fraction: u64| {
+        contract
+            .transition("checkpoint")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .add_global_state(
+                        "checkpointLocation",
+                        Details::from_str("Port of Genoa").unwrap(),
+                    )
+                    .unwrap()
+                    .add_global_state("checkpointTime", Amount::from(1_700_000_000u64))
+                    .unwrap()
+                    .add_global_state(
+                        "checkpointHandler",
+                        Details::from_str("ACME Logistics").unwrap(),
+                    )
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+            })
+    };
+    checkpoint(2, 1).validate().unwrap();
+    assert_eq!(checkpoint(3, 1).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(checkpoint(2, 2).errno(), Some(ValidationErrno::NonFractional));
+}
+
+#[test]
+fn subscription_errnos() {
+    assert_eq!(
+        genesis_errno(subscription(2, 3, issuer_pubkey(), 900_000).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    let contract =
+        TestContract::issue::<SubscriptionAsset>(subscription(2, 2, issuer_pubkey(), 900_000));
+    let signed_errno = |case: &TransitionCase| {
+        script_errno(
+            case.validate_transition(&case.signed(), true)
+                .expect_err("invalid transition passed validation"),
+        )
+    };
+    let renew = |expiry: u32, index: u32, fraction: u64| {
+        contract
+            .transition("renew")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .add_global_state("expiry", block_height(expiry))
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+            })
+    };
+    let case = renew(950_000, 2, 1);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+    assert_eq!(signed_errno(&renew(900_000, 2, 1)), Some(ValidationErrno::ExpiryNotExtended));
+    assert_eq!(signed_errno(&renew(899_999, 2, 1)), Some(ValidationErrno::ExpiryNotExtended));
+    assert_eq!(signed_errno(&renew(950_000, 3, 1)), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(signed_errno(&renew(950_000, 2, 2)), Some(ValidationErrno::NonFractional));
+
+    // a renewal signed by someone else than the issuer is rejected
+    let case = renew(950_000, 2, 1);
+    let mut forged = case.transition();
+    forged.signature = Some(sign_with(&forged, &cosigner_key()));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+}
+
+#[test]
+fn edition_errnos() {
+    let contract = TestContract::issue::<LimitedEditionAsset>(edition(2));
+    let mint = |edition: u32, index: u32, fraction: u64| {
+        contract
+            .transition("mint")
+            .input(OS_MINT, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state("tokens", TokenData {
+                        index: TokenIndex::from_inner(edition),
+                        ..Default::default()
+                    })
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+                    .add_rights("mintRight", graph_seal(1))
+                    .unwrap()
+            })
+    };
+    mint(1, 1, 1).validate().unwrap();
+    assert_eq!(mint(0, 0, 1).errno(), Some(ValidationErrno::EditionMismatch));
+    assert_eq!(mint(2, 2, 1).errno(), Some(ValidationErrno::EditionMismatch));
+    assert_eq!(mint(1, 2, 1).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(mint(1, 1, 2).errno(), Some(ValidationErrno::NonFractional));
+
+    // editions minted before are counted from the contract global state
+    let first = mint(1, 1, 1).transition();
+    mint(2, 2, 1).after(first.clone()).validate().unwrap();
+    assert_eq!(mint(1, 1, 1).after(first.clone()).errno(), Some(ValidationErrno::EditionMismatch));
+    let second = mint(2, 2, 1).after(first.clone()).transition();
+    assert_eq!(
+        mint(3, 3, 1).after(first).after(second).errno(),
+        Some(ValidationErrno::EditionCapExceeded)
+    );
+
+    let transfer = |input: u32, output: u32| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, allocation(input, 1))
+            .with(|builder| {
+                builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(output), 1),
+                    )
+                    .unwrap()
+            })
+    };
+    transfer(1, 1).validate().unwrap();
+    assert_eq!(transfer(1, 2).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn eifa_errnos() {
+    let issue = |allocated: u64, issuance: (u32, u64)| {
+        eifa(1000, allocated, 1500, 500, 100, 200, issuance).issue_contract_raw(CREATED_AT)
+    };
+    issue(1000, (0, 0)).unwrap();
+    assert_eq!(genesis_errno(issue(999, (0, 0))), Some(ValidationErrno::IssuedMismatch));
+    assert_eq!(genesis_errno(issue(1000, (1, 0))), Some(ValidationErrno::EpochIssuanceMismatch));
+    assert_eq!(genesis_errno(issue(1000, (0, 1))), Some(ValidationErrno::EpochIssuanceMismatch));
+
+    let contract =
+        TestContract::issue::<EpochInflatableAsset>(eifa(1000, 1000, 1500, 500, 100, 200, (0, 0)));
+    let inflate = |lock_time: u32, input: (u32, u64), issued: u64, output: (u32, u64)| {
+        contract
+            .transition("inflate")
+            .input(OS_INFLATION, 0, amount(500))
+            .input(OS_EPOCH_ISSUANCE, 0, allocation(input.0, input.1))
+            .with(|builder| {
+                builder
+                    .add_global_state("issuedSupply", Amount::from(issued))
+                    .unwrap()
+                    .add_global_state("lockTime", block_height(lock_time))
+                    .unwrap()
+                    .add_metadata("allowedInflation", Amount::from(500 - issued))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), issued)
+                    .unwrap()
+                    .add_fungible_state("inflationAllowance", graph_seal(1), 500 - issued)
+                    .unwrap()
+                    .add_data(
+                        "epochIssuance",
+                        graph_seal(2),
+                        Allocation::with(TokenIndex::from_inner(output.0), output.1),
+                    )
+                    .unwrap()
+            })
+    };
+    // a lock time of 99 lets the witness be mined from height 100 on, in epoch 1
+    inflate(99, (0, 0), 200, (1, 200)).validate().unwrap();
+    inflate(149, (1, 50), 150, (1, 200)).validate().unwrap();
+    inflate(199, (1, 200), 200, (2, 200)).validate().unwrap();
+    assert_eq!(inflate(99, (0, 0), 201, (1, 201)).errno(), Some(ValidationErrno::EpochCapExceeded));
+    assert_eq!(
+        inflate(149, (1, 51), 150, (1, 201)).errno(),
+        Some(ValidationErrno::EpochCapExceeded)
+    );
+
+    // inflations can't go back to an earlier epoch, nor commit to a timestamp
+    assert_eq!(inflate(98, (1, 0), 100, (0, 100)).errno(), Some(ValidationErrno::EpochRegressed));
+    assert_eq!(
+        inflate(500_000_000, (1, 0), 100, (5_000_000, 100)).errno(),
+        Some(ValidationErrno::EpochRegressed)
+    );
+
+    // the output must record the epoch of the inflation and the supply issued within it
+    assert_eq!(
+        inflate(149, (1, 50), 100, (1, 100)).errno(),
+        Some(ValidationErrno::EpochIssuanceMismatch)
+    );
+    assert_eq!(
+        inflate(199, (1, 50), 100, (1, 150)).errno(),
+        Some(ValidationErrno::EpochIssuanceMismatch)
+    );
+
+    let overinflate = contract
+        .transition("inflate")
+        .input(OS_INFLATION, 0, amount(500))
+        .input(OS_EPOCH_ISSUANCE, 0, allocation(0, 0))
+        .with(|builder| {
+            builder
+                .add_global_state("issuedSupply", Amount::from(200u64))
+                .unwrap()
+                .add_global_state("lockTime", block_height(99))
+                .unwrap()
+                .add_metadata("allowedInflation", Amount::from(301u64))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), 200u64)
+                .unwrap()
+                .add_fungible_state("inflationAllowance", graph_seal(1), 301u64)
+                .unwrap()
+                .add_data(
+                    "epochIssuance",
+                    graph_seal(2),
+                    Allocation::with(TokenIndex::from(1), 200),
+                )
+                .unwrap()
+        });
+    assert_eq!(overinflate.errno(), Some(ValidationErrno::InflationExceedsAllowance));
+}
+
+#[test]
+fn claim_errnos() {
+    assert_eq!(
+        genesis_errno(claim(1000, 999, issuer_pubkey()).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<ClaimableAsset>(claim(1000, 1000, issuer_pubkey()));
+    let lock = |claimed: u64, change: Option<u64>| {
+        contract
+            .transition("claim")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                let builder = builder
+                    .add_fungible_state("issuerClaim", graph_seal(0), claimed)
+                    .unwrap();
+                match change {
+                    Some(change) => builder
+                        .add_fungible_state("assetOwner", graph_seal(1), change)
+                        .unwrap(),
+                    None => builder,
+                }
+            })
+    };
+    lock(1000, None).validate().unwrap();
+    lock(400, Some(600)).validate().unwrap();
+    assert_eq!(lock(999, None).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(lock(400, Some(601)).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let collect = |outputs: &[u64]| {
+        contract
+            .transition("collect")
+            .input(OS_CLAIM, 0, amount(400))
+            .with(|mut builder| {
+                for (vout, output) in outputs.iter().enumerate() {
+                    builder = builder
+                        .add_fungible_state("assetOwner", graph_seal(vout as u32), *output)
+                        .unwrap();
+                }
+                builder
+            })
+    };
+    let case = collect(&[150, 250]);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+    let case = collect(&[399]);
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), true).unwrap_err()),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    // only the issuer can collect the claims
+    let case = collect(&[400]);
+    let mut forged = case.transition();
+    forged.signature = Some(sign_with(&forged, &cosigner_key()));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+    // without the contract global state the issuer pubkey can't be loaded
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), false).unwrap_err()),
+        Some(ValidationErrno::MissingPubkey)
+    );
+}
+
+#[test]
+fn registry_errnos() {
+    let contract = TestContract::issue::<NameRegistry>(registry());
+    let register = |name: &str, index: u32, fraction: u64| {
+        let name = Name::from_str(name).unwrap();
+        contract
+            .transition("register")
+            .input(OS_REGISTER, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state("names", name)
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+                    .add_rights("registerRight", graph_seal(1))
+                    .unwrap()
+            })
+    };
+    register("satoshi.btc", 1, 1).validate().unwrap();
+    assert_eq!(register("Satoshi.btc", 1, 1).errno(), Some(ValidationErrno::UnnormalizedName));
+    assert_eq!(register("satoshiZ", 1, 1).errno(), Some(ValidationErrno::UnnormalizedName));
+    assert_eq!(register("satoshi btc", 1, 1).errno(), Some(ValidationErrno::UnnormalizedName));
+    assert_eq!(register("satoshi.btc", 0, 1).errno(), Some(ValidationErrno::RegistrationMismatch));
+    assert_eq!(register("satoshi.btc", 2, 1).errno(), Some(ValidationErrno::RegistrationMismatch));
+    assert_eq!(register("satoshi.btc", 1, 2).errno(), Some(ValidationErrno::NonFractional));
+
+    // names registered before are read from the contract global state
+    let first = register("satoshi.btc", 1, 1).transition();
+    register("nakamoto.btc", 2, 1)
+        .after(first.clone())
+        .validate()
+        .unwrap();
+    assert_eq!(
+        register("satoshi.btc", 2, 1).after(first.clone()).errno(),
+        Some(ValidationErrno::NameTaken)
+    );
+    assert_eq!(
+        register("nakamoto.btc", 1, 1).after(first.clone()).errno(),
+        Some(ValidationErrno::RegistrationMismatch)
+    );
+    let second = register("nakamoto.btc", 2, 1)
+        .after(first.clone())
+        .transition();
+    assert_eq!(
+        register("satoshi.btc", 3, 1)
+            .after(first)
+            .after(second)
+            .errno(),
+        Some(ValidationErrno::NameTaken)
+    );
+
+    let transfer = |input: u32, output: u32| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, allocation(input, 1))
+            .with(|builder| {
+                builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(output), 1),
+                    )
+                    .unwrap()
+            })
+    };
+    transfer(1, 1).validate().unwrap();
+    assert_eq!(transfer(1, 2).errno(), Some(ValidationErrno::NonEqualInOut));
+}
+
+#[test]
+fn pausable_errnos() {
+    assert_eq!(
+        genesis_errno(pausable(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<PausableAsset>(pausable(1000, 1000));
+    // toggles in the same witness are ordered by their nonces
+    let toggle = |paused: u64, nonce: u64| {
+        contract
+            .transition("togglePause")
+            .input(OS_PAUSE, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .set_nonce(nonce)
+                    .add_global_state("paused", Amount::from(paused))
+                    .unwrap()
+                    .add_rights("pauseRight", graph_seal(1))
+                    .unwrap()
+            })
+    };
+    // contracts are not paused before the first toggle
+    toggle(1, 0).validate().unwrap();
+    assert_eq!(toggle(0, 0).errno(), Some(ValidationErrno::PauseNotToggled));
+    assert_eq!(toggle(2, 0).errno(), Some(ValidationErrno::PauseNotToggled));
+    let pause = toggle(1, 0).transition();
+    toggle(0, 1).after(pause.clone()).validate().unwrap();
+    assert_eq!(toggle(1, 1).after(pause.clone()).errno(), Some(ValidationErrno::PauseNotToggled));
+    let resume = toggle(0, 1).transition();
+
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(transfer(1000).after(pause.clone()).errno(), Some(ValidationErrno::Paused));
+    // the pause is checked before the sums
+    assert_eq!(transfer(999).after(pause.clone()).errno(), Some(ValidationErrno::Paused));
+    transfer(1000)
+        .after(pause.clone())
+        .after(resume.clone())
+        .validate()
+        .unwrap();
+    assert_eq!(
+        transfer(999).after(pause).after(resume).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+}
+
+#[test]
+fn rental_errnos() {
+    assert_eq!(
+        genesis_errno(rental(2, 3).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    let contract = TestContract::issue::<RentalAsset>(rental(2, 2));
+    // leases and reclaims in the same witness are ordered by their nonces
+    let lease = |expiry: u32, index: u32, fraction: u64, nonce: u64| {
+        contract
+            .transition("lease")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .set_nonce(nonce)
+                    .add_global_state("leaseExpiry", block_height(expiry))
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+                    .add_rights("leaseRight", graph_seal(1))
+                    .unwrap()
+            })
+    };
+    let reclaim_at = |expiry: u32, lock_time: u32, index: u32, fraction: u64, nonce: u64| {
+        contract
+            .transition("reclaim")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .set_nonce(nonce)
+                    .add_global_state("reclaimedLeases", block_height(expiry))
+                    .unwrap()
+                    .add_global_state("lockTime", block_height(lock_time))
+                    .unwrap()
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(index), fraction),
+                    )
+                    .unwrap()
+            })
+    };
+    let reclaim = |expiry: u32, index: u32, fraction: u64, nonce: u64| {
+        reclaim_at(expiry, expiry, index, fraction, nonce)
+    };
+    lease(100, 2, 1, 0).validate().unwrap();
+    assert_eq!(lease(100, 3, 1, 0).errno(), Some(ValidationErrno::NonEqualInOut));
+    assert_eq!(lease(100, 2, 2, 0).errno(), Some(ValidationErrno::NonFractional));
+    // nothing to reclaim before the first lease
+    assert_eq!(reclaim(100, 2, 1, 0).errno(), Some(ValidationErrno::LeaseMismatch));
+
+    let first = lease(100, 2, 1, 0).transition();
+    assert_eq!(
+        lease(200, 2, 1, 1).after(first.clone()).errno(),
+        Some(ValidationErrno::LeaseActive)
+    );
+    reclaim(100, 2, 1, 1)
+        .after(first.clone())
+        .validate()
+        .unwrap();
+    assert_eq!(
+        reclaim(99, 2, 1, 1).after(first.clone()).errno(),
+        Some(ValidationErrno::LeaseMismatch)
+    );
+    assert_eq!(
+        reclaim(100, 3, 1, 1).after(first.clone()).errno(),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+
+    // a lock time of 100 lets the witness be mined from height 101 on, after the lease expires
+    reclaim_at(100, 499_999_999, 2, 1, 1)
+        .after(first.clone())
+        .validate()
+        .unwrap();
+    assert_eq!(
+        reclaim_at(100, 99, 2, 1, 1).after(first.clone()).errno(),
+        Some(ValidationErrno::LeaseNotExpired)
+    );
+    // lock times from 500 000 000 on are timestamps, not heights
+    assert_eq!(
+        reclaim_at(100, 500_000_000, 2, 1, 1)
+            .after(first.clone())
+            .errno(),
+        Some(ValidationErrno::LeaseNotExpired)
+    );
+
+    // once reclaimed, the token can be leased again, but not reclaimed twice
+    let reclaimed = reclaim(100, 2, 1, 1).transition();
+    lease(200, 2, 1, 2)
+        .after(first.clone())
+        .after(reclaimed.clone())
+        .validate()
+        .unwrap();
+    assert_eq!(
+        reclaim(100, 2, 1, 2).after(first).after(reclaimed).errno(),
+        Some(ValidationErrno::LeaseMismatch)
+    );
+}
+
+#[test]
+fn option_errnos() {
+    assert_eq!(
+        genesis_errno(option(1000, 999, 3000, 890_000).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<OptionAsset>(option(1000, 1000, 3000, 890_000));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    // exercises must commit to a lock time lower than the expiry, and lapses to one not lower
+    for (name, global, valid, invalid, errno) in [
+        ("exercise", "exercisedSupply", 889_999, 890_000, ValidationErrno::OptionExpired),
+        ("lapse", "lapsedSupply", 890_000, 889_999, ValidationErrno::OptionNotExpired),
+    ] {
+        let destroy_at = |lock_time: u32, destroyed: u64, change: &[u64]| {
+            contract
+                .transition(name)
+                .input(OS_ASSET, 0, amount(1000))
+                .with(|mut builder| {
+                    builder = builder
+                        .add_global_state(global, Amount::from(destroyed))
+                        .unwrap()
+                        .add_global_state("lockTime", block_height(lock_time))
+                        .unwrap();
+                    for (vout, value) in change.iter().enumerate() {
+                        builder = builder
+                            .add_fungible_state("assetOwner", graph_seal(vout as u32), *value)
+                            .unwrap();
+                    }
+                    builder
+                })
+        };
+        let destroy = |destroyed: u64, change: &[u64]| destroy_at(valid, destroyed, change);
+        destroy(1000, &[]).validate().unwrap();
+        destroy(300, &[700]).validate().unwrap();
+        destroy(300, &[200, 500]).validate().unwrap();
+        assert_eq!(destroy(999, &[]).errno(), Some(ValidationErrno::BurnMismatch));
+        assert_eq!(destroy(300, &[699]).errno(), Some(ValidationErrno::BurnMismatch));
+        assert_eq!(destroy(300, &[701]).errno(), Some(ValidationErrno::BurnMismatch));
+        assert_eq!(destroy_at(invalid, 1000, &[]).errno(), Some(errno));
+        // lock times from 500 000 000 on are timestamps, not heights
+        assert_eq!(destroy_at(500_000_000, 1000, &[]).errno(), Some(errno));
+        // the lock time is checked before the destroyed supply
+        assert_eq!(destroy_at(invalid, 999, &[]).errno(), Some(errno));
+    }
+}
+
+#[test]
+fn credential_errnos() {
+    let contract = TestContract::issue::<VerifiableCredential>(credential(issuer_pubkey()));
+    let attest = || {
+        contract
+            .transition("attest")
+            .input(OS_ASSET, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state(
+                        "claims",
+                        VerifiableCredential::claim(EmbeddedMedia {
+                            ty: MediaType::with("application/json"),
+                            data: SmallBlob::try_from_iter(*b"{}").unwrap(),
+                        }),
+                    )
+                    .unwrap()
+                    .add_rights("assetOwner", graph_seal(0))
+                    .unwrap()
+            })
+    };
+    let case = attest();
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+
+    // a claim signed by someone else than the issuer is rejected
+    let mut forged = case.transition();
+    forged.signature = Some(sign_with(&forged, &cosigner_key()));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+    // without the contract global state the issuer pubkey can't be loaded
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), false).unwrap_err()),
+        Some(ValidationErrno::MissingPubkey)
+    );
+
+    // once revoked, the issuer can't attest new claims
+    let revoke = contract
+        .transition("revoke")
+        .input(OS_REVOKE, 0, RevealedState::Void)
+        .with(|builder| {
+            builder
+                .add_global_state("revocationReason", Details::from_str("Expired").unwrap())
+                .unwrap()
+        });
+    revoke.validate().unwrap();
+    let case = attest().after(revoke.transition());
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), true).unwrap_err()),
+        Some(ValidationErrno::CredentialRevoked)
+    );
+}
+
+#[test]
+fn burnable_errnos() {
+    assert_eq!(
+        genesis_errno(burnable(1000, 999).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<BurnableAsset>(burnable(1000, 1000));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let burn = |burned: u64| {
+        contract
+            .transition("burn")
+            .input(OS_ASSET, 0, amount(600))
+            .input(OS_ASSET, 1, amount(400))
+            .with(|builder| {
+                builder
+                    .add_global_state("burnedSupply", Amount::from(burned))
+                    .unwrap()
+            })
+    };
+    burn(1000).validate().unwrap();
+    assert_eq!(burn(999).errno(), Some(ValidationErrno::BurnMismatch));
+    assert_eq!(burn(1001).errno(), Some(ValidationErrno::BurnMismatch));
+}
+
+#[test]
+fn rebasing_errnos() {
+    assert_eq!(
+        genesis_errno(rebasing(1000, 999, issuer_pubkey()).issue_contract_raw(CREATED_AT)),
+        Some(ValidationErrno::IssuedMismatch)
+    );
+
+    let contract = TestContract::issue::<RebasingAsset>(rebasing(1000, 1000, issuer_pubkey()));
+    let transfer = |output: u64| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), output)
+                    .unwrap()
+            })
+    };
+    transfer(1000).validate().unwrap();
+    assert_eq!(transfer(999).errno(), Some(ValidationErrno::NonEqualInOut));
+
+    let rebase = |factor: u64| {
+        contract
+            .transition("rebase")
+            .input(OS_REBASE, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_global_state("rebaseFactors", Amount::from(factor))
+                    .unwrap()
+                    .add_rights("rebaseRight", graph_seal(0))
+                    .unwrap()
+            })
+    };
+    let case = rebase(2_000_000);
+    case.validate_transition(&case.signed(), true).unwrap();
+    assert_eq!(case.errno(), Some(ValidationErrno::InvalidSignature));
+
+    // a rebase signed by someone else than the issuer is rejected
+    let mut forged = case.transition();
+    forged.signature = Some(sign_with(&forged, &cosigner_key()));
+    assert_eq!(
+        script_errno(case.validate_transition(&forged, true).unwrap_err()),
+        Some(ValidationErrno::InvalidSignature)
+    );
+    // without the contract global state the issuer pubkey can't be loaded
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), false).unwrap_err()),
+        Some(ValidationErrno::MissingPubkey)
+    );
+
+    // even the issuer can't wipe out all the balances
+    let case = rebase(0);
+    assert_eq!(
+        script_errno(case.validate_transition(&case.signed(), true).unwrap_err()),
+        Some(ValidationErrno::ZeroRebaseFactor)
+    );
+}
@@ -0,0 +1,833 @@
+//! Golden vectors for contract issuance.
+//!
+//! Each test deterministically issues one contract per schema and compares its serialized
+//! consignment and contract id byte-for-byte against the files committed under
+//! `tests/fixtures/golden`. A mismatch means that either the schema or one of the consensus
+//! dependencies changed the way contracts are committed to.
+//!
+//! If the change is intentional, regenerate the fixtures with
+//! `RGB_UPDATE_GOLDEN=1 cargo test -p rgb-schemata-tools --test golden` and commit the result.
+
+#![cfg(all_schemas)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use amplify::confinement::SmallBlob;
+use amplify::Wrapper;
+use rgbstd::bitcoin::CompressedPublicKey;
+use rgbstd::containers::{BuilderSeal, ConsignmentExt, FileContent};
+use rgbstd::contract::{ContractBuilder, IssuerWrapper};
+use rgbstd::invoice::Precision;
+use rgbstd::stl::{
+    AssetSpec, ContractTerms, EmbeddedMedia, MediaType, Name, ProofOfReserves, RejectListUrl,
+    RicardianContract, Ticker, TokenData,
+};
+use rgbstd::txout::BlindSeal;
+use rgbstd::{Allocation, Amount, ChainNet, ContractId, Identity, Outpoint, TokenIndex, Txid};
+use schemata::height::block_height;
+use schemata::{
+    BondAsset, BurnableAsset, CarbonCreditAsset, ClaimableAsset, CollectibleFungibleAsset,
+    DividendAsset, EpochInflatableAsset, EscrowAsset, FractionalUniqueAsset, FreezableAsset,
+    GovernanceAsset, InflatableFungibleAsset, LimitedEditionAsset, MultiKeyPermissionedAsset,
+    NameRegistry, NonInflatableAsset, OptionAsset, PausableAsset, PermissionedFungibleAsset,
+    ProvenanceAsset, RebasingAsset, ReissuableAsset, RentalAsset, SemiFungibleAsset,
+    SoulboundAsset, StablecoinAsset, SubscriptionAsset, SwapOrder, ThresholdInflatableAsset,
+    TicketAsset, TimelockAsset, TransferFeeAsset, UniqueDigitalAsset, UniqueDigitalCollection,
+    VerifiableCredential, VestingAsset, WrappedBtcAsset,
+};
+use schemata_tools::testing::BLINDER;
+
+const CREATED_AT: i64 = 1713261744;
+const UPDATE_ENV: &str = "RGB_UPDATE_GOLDEN";
+
+fn seal(vout: u32) -> BuilderSeal<BlindSeal<Txid>> {
+    let txid =
+        Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19").unwrap();
+    BuilderSeal::from(BLINDER.genesis_seal(txid, vout))
+}
+
+fn spec(precision: Precision) -> AssetSpec {
+    AssetSpec {
+        ticker: Ticker::from("TICKER"),
+        name: Name::from("NAME"),
+        details: None,
+        precision,
+    }
+}
+
+fn terms() -> ContractTerms {
+    ContractTerms {
+        text: RicardianContract::from_str("golden vector terms").unwrap(),
+        media: None,
+    }
+}
+
+fn pubkey() -> CompressedPublicKey {
+    CompressedPublicKey::from_slice(&[
+        2, 199, 163, 211, 116, 75, 108, 119, 241, 66, 54, 236, 233, 189, 142, 108, 37, 135, 56,
+        128, 200, 176, 199, 9, 117, 132, 72, 200, 167, 185, 4, 64, 53,
+    ])
+    .unwrap()
+}
+
+/// Key of a second co-signing service of the PFA-N contract.
+fn cosigner_pubkey() -> CompressedPublicKey {
+    CompressedPublicKey::from_slice(&[
+        2, 121, 190, 102, 126, 249, 220, 187, 172, 85, 160, 98, 149, 206, 135, 11, 7, 2, 155, 252,
+        219, 45, 206, 40, 217, 89, 242, 129, 91, 22, 248, 23, 152,
+    ])
+    .unwrap()
+}
+
+fn builder<I: IssuerWrapper>() -> ContractBuilder {
+    ContractBuilder::with(
+        Identity::default(),
+        I::schema(),
+        I::types(),
+        I::scripts(),
+        ChainNet::BitcoinTestnet4,
+    )
+}
+
+fn check_golden(name: &str, builder: ContractBuilder) {
+    let contract = builder
+        .issue_contract_raw(CREATED_AT)
+        .expect("golden contract doesn't fit schema requirements");
+    let mut data = vec![];
+    contract.save(&mut data).unwrap();
+    let contract_id = contract.contract_id().to_string();
+
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/golden");
+    let data_path = dir.join(format!("{name}.rgb"));
+    let id_path = dir.join(format!("{name}.id"));
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&data_path, &data).unwrap();
+        fs::write(&id_path, format!("{contract_id}\n")).unwrap();
+        return;
+    }
+
+    let golden_id = fs::read_to_string(&id_path)
+        .unwrap_or_else(|_| panic!("missing golden file {}", id_path.display()));
+    assert_eq!(contract_id, golden_id.trim(), "{name} contract id has changed");
+    let golden_data = fs::read(&data_path)
+        .unwrap_or_else(|_| panic!("missing golden file {}", data_path.display()));
+    assert!(data == golden_data, "{name} consignment serialization has changed");
+}
+
+#[test]
+fn nia() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<NonInflatableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("nia", builder);
+}
+
+#[test]
+fn cfa() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<CollectibleFungibleAsset>()
+        .add_global_state("name", Name::from("NAME"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), 600_000u64)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 400_000u64)
+        .unwrap();
+    check_golden("cfa", builder);
+}
+
+#[test]
+fn uda() {
+    let index = TokenIndex::from_inner(2);
+    let token_data = TokenData {
+        index,
+        preview: Some(EmbeddedMedia {
+            ty: MediaType::with("image/*"),
+            data: SmallBlob::try_from_iter(vec![0xde, 0xad, 0xbe, 0xef]).unwrap(),
+        }),
+        ..Default::default()
+    };
+    let builder = builder::<UniqueDigitalAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", token_data)
+        .unwrap()
+        .add_data("assetOwner", seal(0), Allocation::with(index, 1))
+        .unwrap();
+    check_golden("uda", builder);
+}
+
+#[test]
+fn fua() {
+    let index = TokenIndex::from_inner(2);
+    let token_data = TokenData {
+        index,
+        ..Default::default()
+    };
+    let builder = builder::<FractionalUniqueAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", token_data)
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(100u64))
+        .unwrap()
+        .add_data("assetOwner", seal(0), Allocation::with(index, 60))
+        .unwrap()
+        .add_data("assetOwner", seal(1), Allocation::with(index, 40))
+        .unwrap();
+    check_golden("fua", builder);
+}
+
+#[test]
+fn collection() {
+    let mut builder = builder::<UniqueDigitalCollection>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for (vout, index) in [1u32, 2, 3].into_iter().enumerate() {
+        let index = TokenIndex::from_inner(index);
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", seal(vout as u32), Allocation::with(index, 1))
+            .unwrap();
+    }
+    check_golden("collection", builder);
+}
+
+#[test]
+fn pfa() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<PermissionedFungibleAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("pfa", builder);
+}
+
+#[test]
+fn ifa() {
+    let issued_supply = 1_000_000u64;
+    let max_supply = 1_500_000u64;
+    let builder = builder::<InflatableFungibleAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(max_supply))
+        .unwrap()
+        .add_global_state("rejectListUrl", RejectListUrl::from("example.xyz/reject"))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(1), max_supply - issued_supply)
+        .unwrap()
+        .add_rights("replaceRight", seal(2))
+        .unwrap();
+    check_golden("ifa", builder);
+}
+
+#[test]
+fn ria() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<ReissuableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("reissueRight", seal(1))
+        .unwrap();
+    check_golden("ria", builder);
+}
+
+#[test]
+fn sba() {
+    let builder = builder::<SoulboundAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(2u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), 1u64)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 1u64)
+        .unwrap()
+        .add_rights("revokeRight", seal(2))
+        .unwrap();
+    check_golden("sba", builder);
+}
+
+#[test]
+fn stablecoin() {
+    let issued_supply = 1_000_000u64;
+    let max_supply = 1_500_000u64;
+    let builder = builder::<StablecoinAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(max_supply))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(1), max_supply - issued_supply)
+        .unwrap();
+    check_golden("stablecoin", builder);
+}
+
+#[test]
+fn bond() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<BondAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("faceValue", Amount::from(100_000u64))
+        .unwrap()
+        .add_global_state("couponRate", Amount::from(450u64))
+        .unwrap()
+        .add_global_state("maturity", block_height(900_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("bond", builder);
+}
+
+#[test]
+fn governance() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<GovernanceAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("votingUnit", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("voteRight", seal(0))
+        .unwrap();
+    check_golden("governance", builder);
+}
+
+#[test]
+fn ticket() {
+    let mut builder = builder::<TicketAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for (vout, index) in [1u32, 2, 3].into_iter().enumerate() {
+        let index = TokenIndex::from_inner(index);
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index,
+                ..Default::default()
+            })
+            .unwrap()
+            .add_data("assetOwner", seal(vout as u32), Allocation::with(index, 1))
+            .unwrap();
+    }
+    check_golden("ticket", builder);
+}
+
+#[test]
+fn vesting() {
+    let issued_supply = 1_000_000u64;
+    let vested_supply = 600_000u64;
+    let builder = builder::<VestingAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("vestedSupply", Amount::from(vested_supply))
+        .unwrap()
+        .add_global_state("vestingCliff", block_height(850_000))
+        .unwrap()
+        .add_global_state("vestingEnd", block_height(900_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply - vested_supply)
+        .unwrap()
+        .add_fungible_state("lockedAsset", seal(1), vested_supply)
+        .unwrap();
+    check_golden("vesting", builder);
+}
+
+#[test]
+fn timelock() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<TimelockAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("lockHeight", block_height(900_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("timelock", builder);
+}
+
+#[test]
+fn pfan() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<MultiKeyPermissionedAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_global_state("pubkey", cosigner_pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("pfan", builder);
+}
+
+#[test]
+fn freezable() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<FreezableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("freezeRight", seal(1))
+        .unwrap();
+    check_golden("freezable", builder);
+}
+
+#[test]
+fn tifa() {
+    let issued_supply = 1_000_000u64;
+    let max_supply = 1_500_000u64;
+    let builder = builder::<ThresholdInflatableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(max_supply))
+        .unwrap()
+        .add_global_state("inflationThreshold", Amount::from(2u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_global_state("pubkey", cosigner_pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(1), max_supply - issued_supply)
+        .unwrap()
+        .add_data("inflationApproval", seal(2), Allocation::with(TokenIndex::from_inner(0), 0))
+        .unwrap();
+    check_golden("tifa", builder);
+}
+
+#[test]
+fn wbtc() {
+    let issued_supply = 1_000_000u64;
+    let txid =
+        Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19").unwrap();
+    let proof = SmallBlob::try_from_iter(vec![0x52; 64]).unwrap();
+    let reserves = ProofOfReserves::new(Outpoint::new(txid, 0), proof);
+    let builder = builder::<WrappedBtcAsset>()
+        .add_metadata("reserveProof", reserves.clone())
+        .unwrap()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("reserves", reserves)
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("mintRight", seal(1))
+        .unwrap();
+    check_golden("wbtc", builder);
+}
+
+#[test]
+fn fee() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<TransferFeeAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("feeRate", Amount::from(25u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("fee", builder);
+}
+
+#[test]
+fn dividend() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<DividendAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("distributeRight", seal(1))
+        .unwrap();
+    check_golden("dividend", builder);
+}
+
+#[test]
+fn escrow() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<EscrowAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("escrow", builder);
+}
+
+#[test]
+fn swap() {
+    let builder = builder::<SwapOrder>()
+        .add_global_state("offeredAsset", SwapOrder::asset_ref(ContractId::from([0x11; 32])))
+        .unwrap()
+        .add_global_state("offeredAmount", Amount::from(1_000_000u64))
+        .unwrap()
+        .add_global_state("counterAsset", SwapOrder::asset_ref(ContractId::from([0x22; 32])))
+        .unwrap()
+        .add_global_state("price", Amount::from(2_500_000u64))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_rights("orderRight", seal(0))
+        .unwrap();
+    check_golden("swap", builder);
+}
+
+#[test]
+fn carbon() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<CarbonCreditAsset>()
+        .add_global_state("name", Name::from("NAME"))
+        .unwrap()
+        .add_global_state("precision", Precision::CentiMicro)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("carbon", builder);
+}
+
+#[test]
+fn provenance() {
+    let index = TokenIndex::from_inner(2);
+    let builder = builder::<ProvenanceAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(0), Allocation::with(index, 1))
+        .unwrap();
+    check_golden("provenance", builder);
+}
+
+#[test]
+fn subscription() {
+    let index = TokenIndex::from_inner(2);
+    let builder = builder::<SubscriptionAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_global_state("expiry", block_height(900_000))
+        .unwrap()
+        .add_data("assetOwner", seal(0), Allocation::with(index, 1))
+        .unwrap();
+    check_golden("subscription", builder);
+}
+
+#[test]
+fn edition() {
+    let builder = builder::<LimitedEditionAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("maxEditions", Amount::from(100u64))
+        .unwrap()
+        .add_rights("mintRight", seal(0))
+        .unwrap();
+    check_golden("edition", builder);
+}
+
+#[test]
+fn eifa() {
+    let issued_supply = 1_000_000u64;
+    let max_supply = 1_500_000u64;
+    let builder = builder::<EpochInflatableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(max_supply))
+        .unwrap()
+        .add_global_state("epochLength", Amount::from(52_560u64))
+        .unwrap()
+        .add_global_state("epochCap", Amount::from(100_000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_fungible_state("inflationAllowance", seal(1), max_supply - issued_supply)
+        .unwrap()
+        .add_data("epochIssuance", seal(2), Allocation::with(TokenIndex::from_inner(0), 0))
+        .unwrap();
+    check_golden("eifa", builder);
+}
+
+#[test]
+fn claim() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<ClaimableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("claim", builder);
+}
+
+#[test]
+fn registry() {
+    let builder = builder::<NameRegistry>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_rights("registerRight", seal(0))
+        .unwrap();
+    check_golden("registry", builder);
+}
+
+#[test]
+fn pausable() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<PausableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("pauseRight", seal(1))
+        .unwrap();
+    check_golden("pausable", builder);
+}
+
+#[test]
+fn rental() {
+    let index = TokenIndex::from_inner(2);
+    let builder = builder::<RentalAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("tokens", TokenData {
+            index,
+            ..Default::default()
+        })
+        .unwrap()
+        .add_data("assetOwner", seal(0), Allocation::with(index, 1))
+        .unwrap();
+    check_golden("rental", builder);
+}
+
+#[test]
+fn option() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<OptionAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("underlyingAsset", OptionAsset::asset_ref(ContractId::from([0x33; 32])))
+        .unwrap()
+        .add_global_state("strike", Amount::from(3000u64))
+        .unwrap()
+        .add_global_state("expiry", block_height(890_000))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("option", builder);
+}
+
+#[test]
+fn credential() {
+    let builder = builder::<VerifiableCredential>()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_rights("assetOwner", seal(0))
+        .unwrap()
+        .add_rights("revokeRight", seal(1))
+        .unwrap();
+    check_golden("credential", builder);
+}
+
+#[test]
+fn burnable() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<BurnableAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap();
+    check_golden("burnable", builder);
+}
+
+#[test]
+fn rebasing() {
+    let issued_supply = 1_000_000u64;
+    let builder = builder::<RebasingAsset>()
+        .add_global_state("spec", spec(Precision::CentiMicro))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(issued_supply))
+        .unwrap()
+        .add_global_state("pubkey", pubkey())
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), issued_supply)
+        .unwrap()
+        .add_rights("rebaseRight", seal(1))
+        .unwrap();
+    check_golden("rebasing", builder);
+}
+
+#[test]
+fn sfa() {
+    let mut builder = builder::<SemiFungibleAsset>()
+        .add_global_state("spec", spec(Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap();
+    for index in [1u32, 2] {
+        builder = builder
+            .add_global_state("tokens", TokenData {
+                index: TokenIndex::from_inner(index),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+    for (vout, (index, amount)) in [(1u32, 100u64), (1, 50), (2, 10)].into_iter().enumerate() {
+        builder = builder
+            .add_data(
+                "assetOwner",
+                seal(vout as u32),
+                Allocation::with(TokenIndex::from_inner(index), amount),
+            )
+            .unwrap();
+    }
+    check_golden("sfa", builder);
+}
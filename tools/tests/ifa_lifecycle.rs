@@ -0,0 +1,345 @@
+//! Inflatable asset lifecycle scenarios.
+//!
+//! A [`Scenario`] scripts the operations an IFA issuer performs over the life of the contract
+//! (inflation, burn and replacement) against an in-memory stock, each operation being anchored
+//! into its own mined witness TX. After every step the supplies reported by the [`IfaWrapper`]
+//! can be checked, so the tests here double as a description of how each operation affects them:
+//!
+//! - inflation increases the issued supply and the circulating supply by the inflated amount, while
+//!   spending the same amount of inflation allowance;
+//! - burning removes the burned allocations from circulation without touching the issued supply,
+//!   recording the burned amount in the global state, where the wrapper reads it, and the same
+//!   amount is found in the history of the contract;
+//! - replacement reissues the allocations, leaving all supplies unchanged.
+//!
+//! The history of the contract is then listed by the [`IfaWrapper`] from a consignment of all its
+//! operations, one transition type at a time.
+
+#![cfg(all_schemas)]
+
+mod common;
+
+use std::collections::BTreeSet;
+use std::num::NonZeroU32;
+
+use common::*;
+use rgbstd::contract::{AllocatedState, FilterIncludeAll, FungibleAllocation, TransitionBuilder};
+use rgbstd::persistence::{ContractStateRead, Stock};
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{Amount, ContractId, GraphSeal, Opout, Outpoint, RevealedValue};
+use schemata::{
+    IfaWrapper, InflatableFungibleAsset, OS_ASSET, OS_INFLATION, TS_BURN, TS_INFLATION, TS_REPLACE,
+};
+use schemata_tools::history::{contract_history, IfaHistory, TransitionHistory};
+
+const ISSUED: u64 = 1000;
+const MAX: u64 = 2000;
+
+/// Supplies of the contract as reported by the [`IfaWrapper`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Supply {
+    /// Sum of the issued supply of the genesis and all inflations.
+    issued: u64,
+    /// Sum of the unspent asset allocations.
+    circulating: u64,
+    /// Sum of the unspent inflation allowance.
+    allowance: u64,
+}
+
+/// IFA contract issued into a stock, evolved by anchoring one transition per step.
+struct Scenario {
+    stock: Stock,
+    resolver: MockResolver,
+    contract_id: ContractId,
+    spent: BTreeSet<Opout>,
+    height: u32,
+}
+
+impl Scenario {
+    /// Issues [`ISSUED`] assets with a [`MAX`] max supply, assigning the whole remaining allowance
+    /// and a replace right to the issuer.
+    fn issue() -> Self {
+        let resolver = MockResolver::with_chain_net(CHAIN_NET);
+        let (stock, contract_id) = stock_with(ifa(ISSUED, ISSUED, MAX, MAX - ISSUED));
+        Self {
+            stock,
+            resolver,
+            contract_id,
+            spent: BTreeSet::new(),
+            height: 100,
+        }
+    }
+
+    fn wrapper(&self) -> IfaWrapper<impl ContractStateRead + '_> {
+        self.stock
+            .contract_wrapper::<InflatableFungibleAsset>(self.contract_id)
+            .unwrap()
+    }
+
+    fn unspent(
+        &self,
+        allocations: impl Iterator<Item = FungibleAllocation>,
+    ) -> Vec<(Opout, Outpoint, u64)> {
+        allocations
+            .filter(|a| !self.spent.contains(&a.opout))
+            .map(|a| (a.opout, Outpoint::new(a.seal.txid, a.seal.vout.into_u32()), a.state.value()))
+            .collect()
+    }
+
+    fn assets(&self) -> Vec<(Opout, Outpoint, u64)> {
+        self.unspent(self.wrapper().allocations(&FilterIncludeAll))
+    }
+
+    fn allowance(&self) -> Vec<(Opout, Outpoint, u64)> {
+        self.unspent(self.wrapper().inflation_allocations(&FilterIncludeAll))
+    }
+
+    fn replace_rights(&self) -> Vec<(Opout, Outpoint)> {
+        self.wrapper()
+            .replace_rights(&FilterIncludeAll)
+            .filter(|a| !self.spent.contains(&a.opout))
+            .map(|a| (a.opout, Outpoint::new(a.seal.txid, a.seal.vout.into_u32())))
+            .collect()
+    }
+
+    fn supply(&self) -> Supply {
+        let sum = |allocations: Vec<(Opout, Outpoint, u64)>| allocations.iter().map(|a| a.2).sum();
+        Supply {
+            issued: self.wrapper().total_issued_supply().value(),
+            circulating: sum(self.assets()),
+            allowance: sum(self.allowance()),
+        }
+    }
+
+    /// Anchors the transition built by `f` into a witness TX with `outputs` non-opret outputs,
+    /// spending `inputs`, and consumes it into the stock as mined.
+    fn commit(
+        &mut self,
+        name: &'static str,
+        inputs: Vec<(Opout, Outpoint, AllocatedState)>,
+        outputs: u32,
+        f: impl FnOnce(TransitionBuilder) -> TransitionBuilder,
+    ) {
+        let mut builder = self
+            .stock
+            .transition_builder(self.contract_id, name)
+            .unwrap();
+        let mut prevouts = vec![];
+        for (opout, outpoint, state) in inputs {
+            builder = builder.add_input(opout, state).unwrap();
+            prevouts.push(outpoint);
+            self.spent.insert(opout);
+        }
+        let transition = f(builder).complete_transition().unwrap();
+
+        let fascia = anchor(self.contract_id, transition, &prevouts, outputs);
+        let witness = fascia.seal_witness.public.tx().unwrap().clone();
+        self.height += 1;
+        let pos = WitnessPos::bitcoin(
+            NonZeroU32::new(self.height).unwrap(),
+            CREATED_AT + 600 * self.height as i64,
+        )
+        .unwrap();
+        self.resolver.add_witness(witness, WitnessOrd::Mined(pos));
+        self.stock
+            .consume_fascia(fascia, self.resolver.clone())
+            .unwrap();
+    }
+
+    /// Inflates `amount` assets, spending all the inflation allowance and assigning back the rest.
+    fn inflate(mut self, amount: u64) -> Self {
+        let allowance = self.allowance();
+        let remaining = allowance.iter().map(|a| a.2).sum::<u64>() - amount;
+        let inputs = allowance
+            .into_iter()
+            .map(|(opout, outpoint, value)| {
+                (opout, outpoint, AllocatedState::Amount(RevealedValue::from(value)))
+            })
+            .collect();
+        self.commit("inflate", inputs, 2, |builder| {
+            let builder = builder
+                .add_metadata("allowedInflation", Amount::from(remaining))
+                .unwrap()
+                .add_global_state("issuedSupply", Amount::from(amount))
+                .unwrap()
+                .add_fungible_state("assetOwner", GraphSeal::new_random_vout(1), amount)
+                .unwrap();
+            if remaining == 0 {
+                return builder;
+            }
+            builder
+                .add_fungible_state("inflationAllowance", GraphSeal::new_random_vout(2), remaining)
+                .unwrap()
+        });
+        self
+    }
+
+    /// Burns the (first) unspent asset allocation of `amount`.
+    fn burn(mut self, amount: u64) -> Self {
+        let (opout, outpoint, value) = self
+            .assets()
+            .into_iter()
+            .find(|a| a.2 == amount)
+            .expect("no allocation to burn");
+        let inputs = vec![(opout, outpoint, AllocatedState::Amount(RevealedValue::from(value)))];
+        self.commit("burn", inputs, 0, |builder| {
+            builder
+                .add_global_state("burnedSupply", Amount::from(value))
+                .unwrap()
+        });
+        self
+    }
+
+    /// Replaces all unspent asset allocations with a single one, together with the replace right.
+    fn replace(mut self) -> Self {
+        let assets = self.assets();
+        let total = assets.iter().map(|a| a.2).sum::<u64>();
+        let mut inputs = assets
+            .into_iter()
+            .map(|(opout, outpoint, value)| {
+                (opout, outpoint, AllocatedState::Amount(RevealedValue::from(value)))
+            })
+            .collect::<Vec<_>>();
+        inputs.extend(
+            self.replace_rights()
+                .into_iter()
+                .map(|(opout, outpoint)| (opout, outpoint, AllocatedState::Void)),
+        );
+        self.commit("replace", inputs, 2, |builder| {
+            builder
+                .add_fungible_state("assetOwner", GraphSeal::new_random_vout(1), total)
+                .unwrap()
+                .add_rights("replaceRight", GraphSeal::new_random_vout(2))
+                .unwrap()
+        });
+        self
+    }
+
+    fn check(self, issued: u64, circulating: u64, allowance: u64) -> Self {
+        let expected = Supply {
+            issued,
+            circulating,
+            allowance,
+        };
+        assert_eq!(self.supply(), expected);
+        assert_eq!(self.wrapper().max_supply(), Amount::from(MAX));
+        assert_eq!(self.wrapper().circulating_supply().value(), expected.circulating);
+        let burned = self.wrapper().total_burned();
+        assert_eq!(burned.value(), expected.issued - expected.circulating);
+        let history = contract_history(&self.stock, self.contract_id).unwrap();
+        let circulating = self
+            .wrapper()
+            .circulating_in_history(&history, &self.resolver);
+        assert_eq!(circulating.value(), expected.circulating);
+        assert_eq!(self.wrapper().burned_in_history(&history, &self.resolver), burned);
+        self
+    }
+}
+
+#[test]
+fn issue() { Scenario::issue().check(ISSUED, ISSUED, MAX - ISSUED); }
+
+#[test]
+fn inflate_twice() {
+    let scenario = Scenario::issue()
+        .inflate(300)
+        .check(1300, 1300, 700)
+        .inflate(200)
+        .check(1500, 1500, 500);
+    // issuances are reported starting from the most recent one
+    assert_eq!(scenario.wrapper().issuance_amounts(), vec![
+        Amount::from(200u64),
+        Amount::from(300u64),
+        Amount::from(ISSUED),
+    ]);
+}
+
+#[test]
+fn inflate_whole_allowance() {
+    let scenario = Scenario::issue().inflate(MAX - ISSUED).check(MAX, MAX, 0);
+    assert!(scenario.allowance().is_empty());
+}
+
+#[test]
+fn full_lifecycle() {
+    let scenario = Scenario::issue()
+        .check(1000, 1000, 1000)
+        .inflate(300)
+        .check(1300, 1300, 700)
+        .inflate(200)
+        .check(1500, 1500, 500)
+        .burn(300)
+        .check(1500, 1200, 500)
+        .replace()
+        .check(1500, 1200, 500);
+    assert_eq!(scenario.assets().len(), 1);
+    assert_eq!(scenario.replace_rights().len(), 1);
+}
+
+#[test]
+fn history() {
+    let scenario = Scenario::issue()
+        .inflate(300)
+        .inflate(200)
+        .burn(300)
+        .replace();
+    let history = contract_history(&scenario.stock, scenario.contract_id).unwrap();
+    let wrapper = scenario.wrapper();
+    let amounts = |allocations: &[schemata_tools::history::HistoryAllocation]| {
+        allocations
+            .iter()
+            .map(|a| match a.state {
+                AllocatedState::Amount(value) => (a.opout.ty, value.as_u64()),
+                _ => (a.opout.ty, 0),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let inflations = wrapper.transitions(&history, TS_INFLATION, &scenario.resolver);
+    assert_eq!(inflations.len(), 2);
+    assert!(inflations[0].witness_ord < inflations[1].witness_ord);
+    assert_eq!(amounts(&inflations[0].inputs), vec![(OS_INFLATION, 1000)]);
+    assert_eq!(amounts(&inflations[0].outputs), vec![(OS_ASSET, 300), (OS_INFLATION, 700)]);
+    assert_eq!(amounts(&inflations[1].inputs), vec![(OS_INFLATION, 700)]);
+    assert_eq!(amounts(&inflations[1].outputs), vec![(OS_ASSET, 200), (OS_INFLATION, 500)]);
+    // seals pointing to the witness are resolved
+    for entry in &inflations {
+        let witness = Some(entry.witness_id);
+        assert!(entry
+            .outputs
+            .iter()
+            .all(|a| a.seal.map(|seal| seal.txid) == witness));
+    }
+
+    let burns = wrapper.transitions(&history, TS_BURN, &scenario.resolver);
+    assert_eq!(wrapper.burn_transitions(&history, &scenario.resolver), burns);
+    assert_eq!(burns.len(), 1);
+    assert_eq!(amounts(&burns[0].inputs), vec![(OS_ASSET, 300)]);
+    assert!(burns[0].outputs.is_empty());
+    assert_eq!(wrapper.burn_history(), vec![Amount::from(300u64)]);
+    assert_eq!(wrapper.burned_in_history(&history, &scenario.resolver), Amount::from(300u64));
+    assert!(burns[0].witness_ord > inflations[1].witness_ord);
+
+    let replaces = wrapper.transitions(&history, TS_REPLACE, &scenario.resolver);
+    assert_eq!(replaces.len(), 1);
+    assert_eq!(replaces[0].inputs.len(), 3);
+    assert_eq!(amounts(&replaces[0].outputs)[0], (OS_ASSET, 1200));
+
+    // burns of excluded witnesses don't count in the history
+    let burn_witness = burns[0].witness_id;
+    drop(wrapper);
+    let Scenario {
+        mut stock,
+        resolver,
+        contract_id,
+        ..
+    } = scenario;
+    stock
+        .upsert_witness(burn_witness, WitnessOrd::Archived)
+        .unwrap();
+    let wrapper = stock
+        .contract_wrapper::<InflatableFungibleAsset>(contract_id)
+        .unwrap();
+    assert_eq!(wrapper.burned_in_history(&history, &resolver), Amount::ZERO);
+    assert_eq!(wrapper.circulating_in_history(&history, &resolver), Amount::from(1500u64));
+}
@@ -0,0 +1,59 @@
+//! Published kit consistency tests.
+//!
+//! The build script of this crate generates the kit of every schema into `OUT_DIR`. The kits
+//! committed under `schemata/` must be identical to them; after a schema change, publish the new
+//! kits with `cargo run -p rgb-schemata-tools --features cli` and commit the result.
+
+use std::fs;
+use std::path::Path;
+
+use rgbstd::containers::{FileContent, Kit};
+use schemata::kit::{save_kits, schema_id_mismatches, KITS};
+
+#[test]
+fn published_kits_up_to_date() {
+    let out_dir = Path::new(env!("OUT_DIR"));
+    let published = Path::new(env!("CARGO_MANIFEST_DIR")).join("../schemata");
+    for &(name, _, _) in KITS {
+        for ext in ["rgb", "rgba"] {
+            let file = format!("{name}.{ext}");
+            let generated = fs::read(out_dir.join(&file)).expect("kit generated by build script");
+            let committed = fs::read(published.join(&file)).expect("published kit");
+            assert!(
+                generated == committed,
+                "schemata/{file} is outdated, run `cargo run -p rgb-schemata-tools --features cli`"
+            );
+        }
+    }
+}
+
+#[test]
+fn generated_kits_match_code() {
+    let out_dir = Path::new(env!("OUT_DIR"));
+    for &(name, schema_id, kit) in KITS {
+        let generated = Kit::load_file(out_dir.join(format!("{name}.rgb"))).unwrap();
+        let schema = generated.schemata.first().unwrap();
+        assert_eq!(schema.schema_id(), schema_id);
+        assert_eq!(generated, kit());
+    }
+}
+
+#[test]
+fn save_all_kits() {
+    let out_dir = Path::new(env!("OUT_DIR"));
+    let dir = std::env::temp_dir().join(format!("rgb-schemas-kits-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    save_kits(&dir).unwrap();
+    for &(name, _, _) in KITS {
+        for ext in ["rgb", "rgba"] {
+            let file = format!("{name}.{ext}");
+            assert_eq!(fs::read(dir.join(&file)).unwrap(), fs::read(out_dir.join(&file)).unwrap());
+        }
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn schema_ids_match() {
+    assert_eq!(schema_id_mismatches(), vec![]);
+}
@@ -0,0 +1,270 @@
+//! Mutation self-checks of the validator scripts.
+//!
+//! Each test makes a validator library skip a single instruction, re-binding the schema to the
+//! mutated library, and checks that a state transition rejected by the original script gets
+//! accepted by the mutant. This proves that the instruction is load-bearing, i.e. that the check
+//! performed by it is not dead code shadowed by some other check.
+//!
+//! The instruction is skipped by overwriting it with a jump to the next one: AluVM `nop`s don't
+//! advance the instruction pointer, so they can't be used as filler. Thus only instructions at
+//! least as long as a `jmp` (3 bytes) can be mutated.
+
+#![cfg(all_schemas)]
+
+mod common;
+
+use aluvm::isa::opcodes::INSTR_JMP;
+use aluvm::isa::{Bytecode, Instr};
+use aluvm::library::{Cursor, LibId, Read};
+use amplify::confinement::{Confined, SmallBlob};
+use amplify::Wrapper;
+use common::*;
+use rgbstd::contract::ContractBuilder;
+use rgbstd::persistence::MemContract;
+use rgbstd::schema::Schema;
+use rgbstd::vm::RgbIsa;
+use rgbstd::{Allocation, Amount, Identity, RevealedState, TokenIndex, TransitionType};
+use schemata::{
+    InflatableFungibleAsset, NonInflatableAsset, PermissionedFungibleAsset, UniqueDigitalAsset,
+    ValidationErrno, OS_ASSET, OS_INFLATION, OS_REPLACE, TS_INFLATION, TS_TRANSFER,
+};
+
+fn genesis_lib(schema: &Schema) -> LibId { schema.genesis.validator.unwrap().lib }
+
+fn transition_lib(schema: &Schema, ty: TransitionType) -> LibId {
+    schema.transitions[&ty]
+        .transition_schema
+        .validator
+        .unwrap()
+        .lib
+}
+
+/// Returns a copy of the contract in which the `no`-th `mnemonic` instruction of the `lib_id`
+/// validator library is replaced with a jump to the following instruction, so that all the other
+/// instructions keep their offsets.
+fn mutate(contract: &TestContract, lib_id: LibId, mnemonic: &str, no: usize) -> TestContract {
+    let lib = contract.scripts[&lib_id].clone();
+
+    let mut reader = Cursor::with(&lib.code, &lib.data, &lib.libs);
+    let mut found = vec![];
+    while !reader.is_eof() {
+        let start = reader.offset().0 as usize;
+        let Ok(instr) = Instr::<RgbIsa<MemContract>>::decode(&mut reader) else {
+            break;
+        };
+        if instr.to_string().split_whitespace().next() == Some(mnemonic) {
+            found.push(start..reader.offset().0 as usize);
+        }
+    }
+    let range = found
+        .get(no)
+        .unwrap_or_else(|| panic!("library {lib_id} has no {mnemonic} instruction #{no}"))
+        .clone();
+
+    assert!(range.len() >= 3, "{mnemonic} instruction is too short to be skipped");
+
+    let mut code = lib.code.to_vec();
+    code[range.start] = INSTR_JMP;
+    code[range.start + 1..range.start + 3].copy_from_slice(&(range.end as u16).to_le_bytes());
+    let mut mutant = lib;
+    mutant.code = SmallBlob::try_from(code).unwrap();
+    let mutant_id = mutant.id();
+
+    let mut scripts = contract.scripts.clone().release();
+    scripts.remove(&lib_id);
+    scripts.insert(mutant_id, mutant);
+
+    let mut schema = contract.schema.clone();
+    let validators = schema
+        .transitions
+        .values_mut()
+        .map(|details| &mut details.transition_schema.validator)
+        .chain([&mut schema.genesis.validator]);
+    for site in validators.flatten().filter(|site| site.lib == lib_id) {
+        site.lib = mutant_id;
+    }
+
+    TestContract {
+        schema,
+        types: contract.types.clone(),
+        scripts: Confined::try_from(scripts).unwrap(),
+        genesis: contract.genesis.clone(),
+    }
+}
+
+#[test]
+fn nia_genesis_sum() {
+    let contract = TestContract::issue::<NonInflatableAsset>(nia(1000, 1000));
+    let mutant = mutate(&contract, genesis_lib(&contract.schema), "sas", 0);
+    let issue = |contract: &TestContract| {
+        ContractBuilder::with(
+            Identity::default(),
+            contract.schema.clone(),
+            contract.types.clone(),
+            contract.scripts.clone(),
+            CHAIN_NET,
+        )
+        .add_global_state("spec", spec())
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(1), 999u64)
+        .unwrap()
+        .issue_contract_raw(CREATED_AT)
+    };
+    assert_eq!(genesis_errno(issue(&contract)), Some(ValidationErrno::IssuedMismatch));
+    issue(&mutant).unwrap();
+}
+
+#[test]
+fn nia_transfer_sum() {
+    let contract = TestContract::issue::<NonInflatableAsset>(nia(1000, 1000));
+    let mutant = mutate(&contract, transition_lib(&contract.schema, TS_TRANSFER), "svs", 0);
+    let transfer = |contract: &TestContract| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), 999u64)
+                    .unwrap()
+            })
+            .validate()
+    };
+    assert_eq!(
+        script_errno(transfer(&contract).unwrap_err()),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    transfer(&mutant).unwrap();
+}
+
+#[test]
+fn pfa_transfer_sum() {
+    let contract =
+        TestContract::issue::<PermissionedFungibleAsset>(pfa(1000, 1000, issuer_pubkey()));
+    let mutant = mutate(&contract, transition_lib(&contract.schema, TS_TRANSFER), "svs", 0);
+    let transfer = |contract: &TestContract| {
+        let case = contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), 999u64)
+                    .unwrap()
+            });
+        let mut transition = case.transition();
+        transition.signature = Some(sign(&transition));
+        case.validate_transition(&transition, true)
+    };
+    assert_eq!(
+        script_errno(transfer(&contract).unwrap_err()),
+        Some(ValidationErrno::NonEqualInOut)
+    );
+    transfer(&mutant).unwrap();
+}
+
+#[test]
+fn uda_fraction() {
+    let contract = TestContract::issue::<UniqueDigitalAsset>(uda(2, 2, 1));
+    // the first `eq.n` checks the token index, the second one the fraction
+    let mutant = mutate(&contract, transition_lib(&contract.schema, TS_TRANSFER), "eq.n", 1);
+    let transfer = |contract: &TestContract| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, allocation(2, 1))
+            .with(|builder| {
+                builder
+                    .add_data(
+                        "assetOwner",
+                        graph_seal(0),
+                        Allocation::with(TokenIndex::from_inner(2), 2),
+                    )
+                    .unwrap()
+            })
+            .validate()
+    };
+    assert_eq!(
+        script_errno(transfer(&contract).unwrap_err()),
+        Some(ValidationErrno::NonFractional)
+    );
+    transfer(&mutant).unwrap();
+}
+
+#[test]
+fn ifa_replace_hidden_burn() {
+    let contract = TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500));
+    let mutant = mutate(&contract, transition_lib(&contract.schema, TS_TRANSFER), "lt.u", 0);
+    let burn_right = |contract: &TestContract| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .input(OS_REPLACE, 0, RevealedState::Void)
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), 1000u64)
+                    .unwrap()
+            })
+            .validate()
+    };
+    assert_eq!(
+        script_errno(burn_right(&contract).unwrap_err()),
+        Some(ValidationErrno::ReplaceHiddenBurn)
+    );
+    burn_right(&mutant).unwrap();
+}
+
+#[test]
+fn ifa_replace_no_input() {
+    let contract = TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500));
+    // the first `eq.n` checks for replace rights in input, the second one for their absence in
+    // output
+    let mutant = mutate(&contract, transition_lib(&contract.schema, TS_TRANSFER), "eq.n", 1);
+    let mint_right = |contract: &TestContract| {
+        contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(1000))
+            .with(|builder| {
+                builder
+                    .add_fungible_state("assetOwner", graph_seal(0), 1000u64)
+                    .unwrap()
+                    .add_rights("replaceRight", graph_seal(1))
+                    .unwrap()
+            })
+            .validate()
+    };
+    assert_eq!(
+        script_errno(mint_right(&contract).unwrap_err()),
+        Some(ValidationErrno::ReplaceNoInput)
+    );
+    mint_right(&mutant).unwrap();
+}
+
+#[test]
+fn ifa_inflation_allowance() {
+    let contract = TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500));
+    let mutant = mutate(&contract, transition_lib(&contract.schema, TS_INFLATION), "sps", 0);
+    let overinflate = |contract: &TestContract| {
+        contract
+            .transition("inflate")
+            .input(OS_INFLATION, 0, amount(500))
+            .with(|builder| {
+                builder
+                    .add_global_state("issuedSupply", Amount::from(201u64))
+                    .unwrap()
+                    .add_metadata("allowedInflation", Amount::from(300u64))
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(0), 201u64)
+                    .unwrap()
+                    .add_fungible_state("inflationAllowance", graph_seal(1), 300u64)
+                    .unwrap()
+            })
+            .validate()
+    };
+    assert_eq!(
+        script_errno(overinflate(&contract).unwrap_err()),
+        Some(ValidationErrno::InflationExceedsAllowance)
+    );
+    overinflate(&mutant).unwrap();
+}
@@ -0,0 +1,66 @@
+//! Denomination splits validated against the transfer scripts of the fungible schemata.
+
+#![cfg(all_schemas)]
+
+mod common;
+
+use common::*;
+use rgbstd::containers::BuilderSeal;
+use rgbstd::contract::FungibleAllocation;
+use rgbstd::{Amount, Operation, Opout, Outpoint, OutputSeal};
+use schemata::split::SplitPlan;
+use schemata::{
+    CollectibleFungibleAsset, InflatableFungibleAsset, NonInflatableAsset,
+    PermissionedFungibleAsset, OS_ASSET,
+};
+
+/// Checks splits of the genesis allocation of `issued`; PFA transitions are `signed` by the issuer.
+fn check(contract: TestContract, issued: u64, signed: bool) {
+    let genesis_id = contract.genesis.id();
+    let input = FungibleAllocation {
+        opout: Opout::new(genesis_id, OS_ASSET, 0),
+        seal: OutputSeal::new(Outpoint::new(txid(), 0)),
+        state: Amount::from(issued),
+        witness: None,
+        bundle_id: None,
+    };
+    for denominations in [vec![250u64; 4], vec![300, 300], vec![1, 2, 3, 994]] {
+        let plan =
+            SplitPlan::new([input], denominations.iter().copied().map(Amount::from)).unwrap();
+        let case = contract
+            .transition("transfer")
+            .input(OS_ASSET, 0, amount(issued))
+            .with(|builder| {
+                plan.add_outputs(builder, |no| BuilderSeal::from(graph_seal(no as u32)))
+                    .unwrap()
+            });
+        let mut transition = case.transition();
+        if signed {
+            transition.signature = Some(sign(&transition));
+        }
+        case.validate_transition(&transition, true)
+            .unwrap_or_else(|err| panic!("split into {denominations:?} is not valid: {err}"));
+    }
+}
+
+#[test]
+fn nia_split() { check(TestContract::issue::<NonInflatableAsset>(nia(1000, 1000)), 1000, false); }
+
+#[test]
+fn cfa_split() {
+    check(TestContract::issue::<CollectibleFungibleAsset>(cfa(1000, 1000)), 1000, false);
+}
+
+#[test]
+fn pfa_split() {
+    check(
+        TestContract::issue::<PermissionedFungibleAsset>(pfa(1000, 1000, issuer_pubkey())),
+        1000,
+        true,
+    );
+}
+
+#[test]
+fn ifa_split() {
+    check(TestContract::issue::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500)), 1000, false);
+}
@@ -0,0 +1,442 @@
+//! End-to-end transfers for every schema.
+//!
+//! A contract is issued into the sender stock, a transfer transition is anchored into a mock
+//! witness transaction with an opret commitment and consumed by the sender as a fascia. The
+//! resulting transfer consignment is then validated and accepted by a second, receiver, stock,
+//! with all witnesses resolved by a [`MockResolver`].
+
+#![cfg(all_schemas)]
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+use amplify::Wrapper;
+use common::*;
+use rgbstd::containers::{BuilderSeal, ConsignmentExt, Transfer, ValidTransfer};
+use rgbstd::contract::{
+    AllocatedState, ContractBuilder, FilterIncludeAll, IssuerWrapper, TransitionBuilder,
+};
+use rgbstd::persistence::Stock;
+use rgbstd::validation::ValidationConfig;
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{
+    Allocation, Amount, ChainNet, ContractId, GraphSeal, OpId, Operation, Opout, Outpoint,
+    OutputSeal, RevealedState, RevealedValue, TokenIndex, Txid,
+};
+use schemata::diff::{ContractSnapshot, SupplyChange};
+use schemata::dumb::MockResolver;
+use schemata::metrics::spent_outputs;
+use schemata::report::{ConsignmentReport, ReportFormat, TerminalState};
+use schemata::{
+    CollectibleFungibleAsset, FungibleAssetWrapper, InflatableFungibleAsset, NonInflatableAsset,
+    PermissionedFungibleAsset, ReissuableAsset, SemiFungibleAsset, UniqueDigitalAsset,
+    UniqueDigitalCollection, OS_ASSET,
+};
+use schemata_tools::audit::{audit_onchain, AuditIssue};
+
+/// UTXO owned by the receiver, to which the transferred state is assigned with a blinded seal.
+fn receiver_outpoint() -> Outpoint {
+    let txid =
+        Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19").unwrap();
+    Outpoint::new(txid, 7)
+}
+
+struct Transferred {
+    contract_id: ContractId,
+    witness_id: Txid,
+    /// State of the sender right after the issue.
+    issued: ContractSnapshot,
+    sender: Stock,
+    receiver: Stock,
+    /// Resolver knowing the witness of the transfer, mined at height 100.
+    resolver: MockResolver,
+    /// Transfer consignment accepted by the receiver.
+    consignment: ValidTransfer,
+}
+
+/// Issues the contract, transfers the genesis allocations selected by `transfer` and accepts the
+/// transfer into the receiver stock.
+///
+/// The `transfer` closure gets the genesis id, the blinded receiver seal and a change seal
+/// pointing to the first non-opret output of the witness.
+fn transfer<I: IssuerWrapper>(
+    issue: ContractBuilder,
+    signed: bool,
+    transfer: impl FnOnce(
+        OpId,
+        TransitionBuilder,
+        BuilderSeal<GraphSeal>,
+        GraphSeal,
+    ) -> TransitionBuilder,
+) -> Transferred {
+    let mut resolver = MockResolver::with_chain_net(ChainNet::BitcoinTestnet4);
+
+    let contract = issue.issue_contract().unwrap();
+    let contract_id = contract.contract_id();
+    let genesis = contract.genesis.clone();
+    let mut sender = Stock::in_memory();
+    sender.import_contract(contract, &resolver).unwrap();
+    let issued = ContractSnapshot::from_stock(&sender, contract_id, FilterIncludeAll).unwrap();
+
+    let receiver_seal = GraphSeal::new_random(receiver_outpoint().txid, receiver_outpoint().vout);
+    let builder = sender.transition_builder(contract_id, "transfer").unwrap();
+    let mut transition = transfer(
+        genesis.id(),
+        builder,
+        BuilderSeal::Concealed(receiver_seal.to_secret_seal()),
+        graph_seal(1),
+    )
+    .complete_transition()
+    .unwrap();
+    if signed {
+        transition.signature = Some(sign(&transition));
+    }
+
+    let prevouts = transition
+        .inputs
+        .iter()
+        .map(|opout| {
+            genesis.assignments[&opout.ty]
+                .revealed_seal_at(opout.no)
+                .unwrap()
+                .expect("genesis seals are revealed")
+                .to_outpoint()
+        })
+        .collect::<Vec<_>>();
+    let fascia = anchor(contract_id, transition, &prevouts, 1);
+    let witness = fascia.seal_witness.public.tx().unwrap().clone();
+    let witness_id = fascia.witness_id();
+
+    resolver.add_witness(witness, WitnessOrd::Tentative);
+    sender.consume_fascia(fascia, resolver.clone()).unwrap();
+
+    let pos = WitnessPos::bitcoin(NonZeroU32::new(100).unwrap(), CREATED_AT + 600).unwrap();
+    assert!(resolver.set_witness_ord(witness_id, WitnessOrd::Mined(pos)));
+
+    let consignment: Transfer = sender
+        .transfer(contract_id, [], [receiver_seal.to_secret_seal()], [], Some(witness_id))
+        .unwrap();
+    let config = ValidationConfig {
+        chain_net: ChainNet::BitcoinTestnet4,
+        trusted_typesystem: I::types(),
+        ..Default::default()
+    };
+    let consignment = consignment
+        .validate(&resolver, &config)
+        .expect("transfer consignment is not valid");
+
+    let mut receiver = Stock::in_memory();
+    receiver.store_secret_seal(receiver_seal).unwrap();
+    receiver
+        .accept_transfer(consignment.clone(), &resolver)
+        .unwrap();
+
+    Transferred {
+        contract_id,
+        witness_id,
+        issued,
+        sender,
+        receiver,
+        resolver,
+        consignment,
+    }
+}
+
+impl Transferred {
+    fn receiver_outpoint(&self) -> OutputSeal { OutputSeal::new(receiver_outpoint()) }
+
+    fn change_outpoint(&self) -> OutputSeal { OutputSeal::new(Outpoint::new(self.witness_id, 1)) }
+}
+
+fn fungible_transfer(
+    genesis_id: OpId,
+    builder: TransitionBuilder,
+    receiver: BuilderSeal<GraphSeal>,
+    change: GraphSeal,
+) -> TransitionBuilder {
+    builder
+        .add_input(
+            Opout::new(genesis_id, OS_ASSET, 0),
+            AllocatedState::Amount(RevealedValue::from(1000u64)),
+        )
+        .unwrap()
+        .add_fungible_state("assetOwner", receiver, 600u64)
+        .unwrap()
+        .add_fungible_state("assetOwner", change, 400u64)
+        .unwrap()
+}
+
+macro_rules! check_fungible {
+    ($transferred:expr, $issuer:ty) => {{
+        let transferred = $transferred;
+        let balances = |stock: &Stock| {
+            stock
+                .contract_wrapper::<$issuer>(transferred.contract_id)
+                .unwrap()
+                .allocations(&FilterIncludeAll)
+                .map(|a| (a.seal, a.state.value()))
+                .collect::<Vec<_>>()
+        };
+        let receiver = balances(&transferred.receiver);
+        assert!(receiver.contains(&(transferred.receiver_outpoint(), 600)), "{receiver:?}");
+        let sender = balances(&transferred.sender);
+        assert!(sender.contains(&(transferred.change_outpoint(), 400)), "{sender:?}");
+
+        let spendable = transferred
+            .sender
+            .contract_wrapper::<$issuer>(transferred.contract_id)
+            .unwrap()
+            .spendable([transferred.change_outpoint().to_outpoint()]);
+        assert_eq!(spendable.balance().value(), 400);
+    }};
+}
+
+macro_rules! check_balance {
+    ($transferred:expr, $issuer:ty) => {{
+        let transferred = $transferred;
+        let receiver = transferred.receiver_outpoint().to_outpoint();
+        let change = transferred.change_outpoint().to_outpoint();
+        let wrapper = transferred
+            .receiver
+            .contract_wrapper::<$issuer>(transferred.contract_id)
+            .unwrap();
+        assert_eq!(wrapper.balance_of(receiver).value(), 600);
+        let spent = spent_outputs(&transferred.sender, transferred.contract_id).unwrap();
+        let wrapper = transferred
+            .sender
+            .contract_wrapper::<$issuer>(transferred.contract_id)
+            .unwrap();
+        assert_eq!(wrapper.balance_of(change).value(), 400);
+        // The receiver seal is blinded to the sender
+        assert_eq!(wrapper.balance([receiver, change]).value(), 400);
+        assert_eq!(wrapper.balance_of(Outpoint::new(txid(), 9)).value(), 0);
+        // The contract state keeps the issued allocation spent by the transfer
+        let issued = Outpoint::new(txid(), 1);
+        assert_eq!(wrapper.balance_of(issued).value(), 1000);
+        assert_eq!(wrapper.unspent_balance_of(issued, &spent).value(), 0);
+        assert_eq!(wrapper.unspent_balance_of(change, &spent).value(), 400);
+    }};
+}
+
+#[test]
+fn nia_transfer() {
+    let transferred = transfer::<NonInflatableAsset>(nia(1000, 1000), false, fungible_transfer);
+    check_balance!(&transferred, NonInflatableAsset);
+    check_fungible!(transferred, NonInflatableAsset);
+}
+
+#[test]
+fn cfa_transfer() {
+    let transferred =
+        transfer::<CollectibleFungibleAsset>(cfa(1000, 1000), false, fungible_transfer);
+    check_balance!(&transferred, CollectibleFungibleAsset);
+    check_fungible!(transferred, CollectibleFungibleAsset);
+}
+
+#[test]
+fn pfa_transfer() {
+    let transferred = transfer::<PermissionedFungibleAsset>(
+        pfa(1000, 1000, issuer_pubkey()),
+        true,
+        fungible_transfer,
+    );
+    check_balance!(&transferred, PermissionedFungibleAsset);
+    check_fungible!(transferred, PermissionedFungibleAsset);
+}
+
+#[test]
+fn ifa_transfer() {
+    let transferred =
+        transfer::<InflatableFungibleAsset>(ifa(1000, 1000, 1500, 500), false, fungible_transfer);
+    check_balance!(&transferred, InflatableFungibleAsset);
+    check_fungible!(transferred, InflatableFungibleAsset);
+}
+
+#[test]
+fn ria_transfer() {
+    let transferred = transfer::<ReissuableAsset>(ria(1000, 1000), false, fungible_transfer);
+    check_fungible!(transferred, ReissuableAsset);
+}
+
+#[test]
+fn nia_transfer_diff() {
+    let transferred = transfer::<NonInflatableAsset>(nia(1000, 1000), false, fungible_transfer);
+    let change = transferred.change_outpoint();
+    let after = ContractSnapshot::from_stock(
+        &transferred.sender,
+        transferred.contract_id,
+        change.to_outpoint(),
+    )
+    .unwrap();
+    let diff = transferred.issued.diff(&after).unwrap();
+
+    let created = diff
+        .created
+        .fungible
+        .iter()
+        .map(|a| (a.seal, a.state.value()))
+        .collect::<Vec<_>>();
+    assert_eq!(created, vec![(change, 400)]);
+    let spent = diff
+        .spent
+        .fungible
+        .iter()
+        .map(|a| a.state.value())
+        .collect::<Vec<_>>();
+    assert_eq!(spent, vec![1000]);
+    assert_eq!(diff.supply[&OS_ASSET], SupplyChange {
+        before: 1000,
+        after: 400
+    });
+    assert_eq!(diff.supply[&OS_ASSET].delta(), -600);
+    assert!(diff.globals.is_empty());
+}
+
+#[test]
+fn nia_transfer_audit() {
+    let mut transferred = transfer::<NonInflatableAsset>(nia(1000, 1000), false, fungible_transfer);
+    let witness_id = transferred.witness_id;
+    let audit = |transferred: &Transferred| {
+        audit_onchain(&transferred.sender, transferred.contract_id, &transferred.resolver).unwrap()
+    };
+
+    let report = audit(&transferred);
+    assert!(report.is_clean(), "{:?}", report.issues);
+    assert_eq!((report.issued, report.burned, report.allocated), (1000, 0, 1000));
+    assert!(matches!(report.witnesses[&witness_id], Some(WitnessOrd::Mined(_))));
+
+    transferred
+        .resolver
+        .set_witness_ord(witness_id, WitnessOrd::Tentative);
+    assert_eq!(audit(&transferred).issues, vec![AuditIssue::Unconfirmed(witness_id)]);
+
+    transferred
+        .resolver
+        .set_witness_ord(witness_id, WitnessOrd::Archived);
+    let report = audit(&transferred);
+    assert_eq!(report.issues, vec![AuditIssue::Excluded(witness_id)]);
+    assert_eq!(report.allocated, 1000);
+
+    transferred.resolver.remove_witness(witness_id);
+    let report = audit(&transferred);
+    assert_eq!(report.issues, vec![AuditIssue::Unresolved(witness_id)]);
+    assert_eq!(report.witnesses[&witness_id], None);
+}
+
+#[test]
+fn nia_transfer_report() {
+    let transferred = transfer::<NonInflatableAsset>(nia(1000, 1000), false, fungible_transfer);
+    let report = ConsignmentReport::new(&transferred.consignment);
+    assert!(report.transfer);
+    assert_eq!(report.issued, Some(1000));
+    assert_eq!(report.bundles, 1);
+    assert_eq!(report.transitions, BTreeMap::from([("transfer".to_owned(), 1)]));
+    assert!(matches!(report.witnesses[&transferred.witness_id], Some(WitnessOrd::Mined(_))));
+    assert_eq!(report.terminals.len(), 1);
+    assert_eq!(report.terminals[0].opout.ty, OS_ASSET);
+    assert_eq!(report.terminals[0].state, TerminalState::Amount(Amount::from(600u64)));
+
+    let markdown = report.render(ReportFormat::Markdown);
+    assert!(markdown.starts_with("# Transfer consignment of "));
+    assert!(markdown.contains("- Transitions transfer: 1\n"));
+    assert!(
+        markdown.contains(&format!("- Witness {}: mined at height 100\n", transferred.witness_id))
+    );
+}
+
+#[test]
+fn uda_transfer() {
+    let RevealedState::Structured(data) = allocation(2, 1) else {
+        unreachable!()
+    };
+    let transferred = transfer::<UniqueDigitalAsset>(
+        uda(2, 2, 1),
+        false,
+        |genesis_id, builder, receiver, _change| {
+            builder
+                .add_input(Opout::new(genesis_id, OS_ASSET, 0), AllocatedState::Data(data))
+                .unwrap()
+                .add_data("assetOwner", receiver, Allocation::with(TokenIndex::from_inner(2), 1))
+                .unwrap()
+        },
+    );
+    let allocations = transferred
+        .receiver
+        .contract_wrapper::<UniqueDigitalAsset>(transferred.contract_id)
+        .unwrap()
+        .allocations(&FilterIncludeAll)
+        .map(|a| a.seal)
+        .collect::<Vec<_>>();
+    assert!(allocations.contains(&transferred.receiver_outpoint()), "{allocations:?}");
+}
+
+#[test]
+fn collection_transfer() {
+    let token = |index| {
+        let RevealedState::Structured(data) = allocation(index, 1) else {
+            unreachable!()
+        };
+        AllocatedState::Data(data)
+    };
+    let transferred = transfer::<UniqueDigitalCollection>(
+        collection(&[1, 2], &[(1, 1), (2, 1)]),
+        false,
+        |genesis_id, builder, receiver, change| {
+            builder
+                .add_input(Opout::new(genesis_id, OS_ASSET, 0), token(1))
+                .unwrap()
+                .add_input(Opout::new(genesis_id, OS_ASSET, 1), token(2))
+                .unwrap()
+                .add_data("assetOwner", receiver, Allocation::with(TokenIndex::from_inner(1), 1))
+                .unwrap()
+                .add_data("assetOwner", change, Allocation::with(TokenIndex::from_inner(2), 1))
+                .unwrap()
+        },
+    );
+    let wrapper = transferred
+        .receiver
+        .contract_wrapper::<UniqueDigitalCollection>(transferred.contract_id)
+        .unwrap();
+    assert_eq!(wrapper.tokens().len(), 2);
+    let received = wrapper
+        .token_allocations(TokenIndex::from_inner(1), &FilterIncludeAll)
+        .into_iter()
+        .map(|a| a.seal)
+        .collect::<Vec<_>>();
+    assert!(received.contains(&transferred.receiver_outpoint()), "{received:?}");
+}
+
+#[test]
+fn sfa_transfer() {
+    let RevealedState::Structured(data) = allocation(1, 100) else {
+        unreachable!()
+    };
+    let transferred = transfer::<SemiFungibleAsset>(
+        sfa(&[1, 2], &[(1, 100), (2, 10)]),
+        false,
+        |genesis_id, builder, receiver, change| {
+            builder
+                .add_input(Opout::new(genesis_id, OS_ASSET, 0), AllocatedState::Data(data))
+                .unwrap()
+                .add_data("assetOwner", receiver, Allocation::with(TokenIndex::from_inner(1), 60))
+                .unwrap()
+                .add_data("assetOwner", change, Allocation::with(TokenIndex::from_inner(1), 40))
+                .unwrap()
+        },
+    );
+    let wrapper = transferred
+        .receiver
+        .contract_wrapper::<SemiFungibleAsset>(transferred.contract_id)
+        .unwrap();
+    let token = TokenIndex::from_inner(1);
+    assert_eq!(wrapper.balance_of(token, vec![receiver_outpoint()]), Amount::from(60u64));
+    let change = transferred.change_outpoint().to_outpoint();
+    assert_eq!(wrapper.balance_of(token, vec![change]), Amount::from(40u64));
+    assert_eq!(
+        wrapper.balance_of(TokenIndex::from_inner(2), vec![receiver_outpoint(), change]),
+        Amount::ZERO
+    );
+}
@@ -0,0 +1,182 @@
+//! Property-based tests of the transfer sum checks.
+//!
+//! Random sets of input and output allocations are validated against the NIA, CFA, PFA and IFA
+//! transfer scripts, which must accept a transition if and only if the input and output sums are
+//! equal, neither of them overflows and no output is zero.
+
+#![cfg(all_schemas)]
+
+mod common;
+
+use common::*;
+use proptest::prelude::*;
+use rgbstd::AssignmentType;
+use schemata::{
+    CollectibleFungibleAsset, InflatableFungibleAsset, NonInflatableAsset,
+    PermissionedFungibleAsset, OS_ASSET, OS_INFLATION,
+};
+
+const MAX_ALLOCATIONS: usize = 8;
+
+fn checked_sum(values: &[u64]) -> Option<u64> {
+    values
+        .iter()
+        .try_fold(0u64, |acc, value| acc.checked_add(*value))
+}
+
+fn is_valid(inputs: &[u64], outputs: &[u64]) -> bool {
+    !outputs.contains(&0)
+        && matches!((checked_sum(inputs), checked_sum(outputs)), (Some(i), Some(o)) if i == o)
+}
+
+fn value() -> impl Strategy<Value = u64> {
+    prop_oneof![Just(0), Just(1), Just(u64::MAX), 0..1_000_000u64, any::<u64>(),]
+}
+
+/// Merges consecutive parts whenever the matching flag is set, producing a random partition of
+/// the parts total.
+fn partition(parts: &[u64], merge: &[bool]) -> Vec<u64> {
+    let mut groups = vec![parts[0]];
+    for (part, merge) in parts[1..].iter().zip(merge) {
+        match groups.last_mut() {
+            Some(last) if *merge => *last += part,
+            _ => groups.push(*part),
+        }
+    }
+    groups
+}
+
+/// Input and output sets having the same sum.
+fn balanced() -> impl Strategy<Value = (Vec<u64>, Vec<u64>)> {
+    prop::collection::vec(1..u64::MAX / MAX_ALLOCATIONS as u64, 1..=MAX_ALLOCATIONS).prop_flat_map(
+        |parts| {
+            let len = parts.len();
+            (
+                Just(parts),
+                prop::collection::vec(any::<bool>(), len),
+                prop::collection::vec(any::<bool>(), len),
+            )
+                .prop_map(|(parts, a, b)| (partition(&parts, &a), partition(&parts, &b)))
+        },
+    )
+}
+
+/// Input and output sets with arbitrary values, which mostly won't balance and may overflow.
+fn arbitrary() -> impl Strategy<Value = (Vec<u64>, Vec<u64>)> {
+    (
+        prop::collection::vec(value(), 1..=MAX_ALLOCATIONS),
+        prop::collection::vec(value(), 1..=MAX_ALLOCATIONS),
+    )
+}
+
+/// Input and output sets where one side sums above `u64::MAX`.
+fn overflowing() -> impl Strategy<Value = (Vec<u64>, Vec<u64>)> {
+    (
+        u64::MAX / 2 + 1..=u64::MAX,
+        u64::MAX / 2 + 1..=u64::MAX,
+        prop::collection::vec(value(), 1..=MAX_ALLOCATIONS),
+        any::<bool>(),
+    )
+        .prop_map(|(a, b, other, inputs_overflow)| {
+            let overflown = vec![a, b];
+            if inputs_overflow {
+                (overflown, other)
+            } else {
+                (other, overflown)
+            }
+        })
+}
+
+fn allocations() -> impl Strategy<Value = (Vec<u64>, Vec<u64>)> {
+    prop_oneof![balanced(), arbitrary(), overflowing()]
+}
+
+fn case<'c>(
+    contract: &'c TestContract,
+    ty: AssignmentType,
+    name: &'static str,
+    inputs: &[u64],
+    outputs: &[u64],
+) -> TransitionCase<'c> {
+    let mut case = contract.transition("transfer");
+    for (no, value) in inputs.iter().enumerate() {
+        case = case.input(ty, no as u16, amount(*value));
+    }
+    case.with(|mut builder| {
+        for (vout, value) in outputs.iter().enumerate() {
+            builder = builder
+                .add_fungible_state(name, graph_seal(vout as u32), *value)
+                .unwrap();
+        }
+        builder
+    })
+}
+
+fn check(
+    contract: &TestContract,
+    ty: AssignmentType,
+    name: &'static str,
+    inputs: &[u64],
+    outputs: &[u64],
+) -> Result<(), TestCaseError> {
+    let res = case(contract, ty, name, inputs, outputs).validate();
+    prop_assert_eq!(res.is_ok(), is_valid(inputs, outputs), "validation result: {:?}", res);
+    Ok(())
+}
+
+fn config() -> ProptestConfig { ProptestConfig::with_cases(64) }
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn nia_transfer((inputs, outputs) in allocations()) {
+        thread_local! {
+            static CONTRACT: TestContract = TestContract::issue::<NonInflatableAsset>(nia(1, 1));
+        }
+        CONTRACT.with(|c| check(c, OS_ASSET, "assetOwner", &inputs, &outputs))?;
+    }
+
+    #[test]
+    fn cfa_transfer((inputs, outputs) in allocations()) {
+        thread_local! {
+            static CONTRACT: TestContract =
+                TestContract::issue::<CollectibleFungibleAsset>(cfa(1, 1));
+        }
+        CONTRACT.with(|c| check(c, OS_ASSET, "assetOwner", &inputs, &outputs))?;
+    }
+
+    #[test]
+    fn pfa_transfer((inputs, outputs) in allocations()) {
+        thread_local! {
+            static CONTRACT: TestContract =
+                TestContract::issue::<PermissionedFungibleAsset>(pfa(1, 1, issuer_pubkey()));
+        }
+        CONTRACT.with(|c| {
+            let case = case(c, OS_ASSET, "assetOwner", &inputs, &outputs);
+            let mut transition = case.transition();
+            transition.signature = Some(sign(&transition));
+            let res = case.validate_transition(&transition, true);
+            prop_assert_eq!(res.is_ok(), is_valid(&inputs, &outputs), "validation result: {:?}", res);
+            Ok(())
+        })?;
+    }
+
+    #[test]
+    fn ifa_transfer((inputs, outputs) in allocations()) {
+        thread_local! {
+            static CONTRACT: TestContract =
+                TestContract::issue::<InflatableFungibleAsset>(ifa(1, 1, 2, 1));
+        }
+        CONTRACT.with(|c| check(c, OS_ASSET, "assetOwner", &inputs, &outputs))?;
+    }
+
+    #[test]
+    fn ifa_inflation_transfer((inputs, outputs) in allocations()) {
+        thread_local! {
+            static CONTRACT: TestContract =
+                TestContract::issue::<InflatableFungibleAsset>(ifa(1, 1, 2, 1));
+        }
+        CONTRACT.with(|c| check(c, OS_INFLATION, "inflationAllowance", &inputs, &outputs))?;
+    }
+}
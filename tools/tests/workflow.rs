@@ -0,0 +1,307 @@
+//! Transfers of every schema through the reference workflow of [`schemata_tools::workflow`].
+
+#![cfg(all_schemas)]
+
+mod common;
+
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+use amplify::Wrapper;
+use common::*;
+use rgbstd::bitcoin::absolute::LockTime;
+use rgbstd::bitcoin::transaction::Version;
+use rgbstd::bitcoin::{
+    Amount as Sats, ScriptBuf, Sequence, Transaction as Tx, TxIn, TxOut, Witness,
+};
+use rgbstd::containers::{BuilderSeal, ConsignmentExt};
+use rgbstd::contract::{AllocatedState, ContractBuilder, FilterIncludeAll, IssuerWrapper};
+use rgbstd::persistence::Stock;
+use rgbstd::vm::{WitnessOrd, WitnessPos};
+use rgbstd::{
+    Allocation, Amount, ContractId, GraphSeal, Operation, Opout, Outpoint, OutputSeal, TokenIndex,
+    Transition, Txid,
+};
+use schemata::dumb::MockResolver;
+use schemata::metrics::ContractMetrics;
+use schemata::selection::Selection;
+use schemata::{
+    CollectibleFungibleAsset, Error, FungibleAssetWrapper, InflatableFungibleAsset,
+    NonInflatableAsset, PermissionedFungibleAsset, UniqueDigitalAsset, OS_ROTATE_KEY,
+};
+use schemata_tools::workflow::{
+    accept, anchor, consignment, consume, data_transition, fungible_transition, TransferError,
+};
+
+fn receiver_outpoint() -> Outpoint {
+    let txid =
+        Txid::from_str("8d54c98d4c29a1ec4fd90635f543f0f7a871a78eb6a6e706342f831d92e3ba19").unwrap();
+    Outpoint::new(txid, 7)
+}
+
+/// Unsigned witness spending `prevouts`, with an empty opret output followed by a change output.
+fn witness(prevouts: &[Outpoint]) -> Tx {
+    Tx {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: prevouts
+            .iter()
+            .map(|prevout| TxIn {
+                previous_output: *prevout,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::MAX,
+                witness: Witness::new(),
+            })
+            .collect(),
+        output: vec![
+            TxOut {
+                value: Sats::ZERO,
+                script_pubkey: ScriptBuf::from_bytes(vec![0x6a]),
+            },
+            TxOut {
+                value: Sats::from_sat(1000),
+                script_pubkey: ScriptBuf::from_bytes(vec![0x51]),
+            },
+        ],
+    }
+}
+
+/// Anchors the transition spending `prevouts`, consumes it into the stock and mines its witness at
+/// `height`, returning the witness id.
+fn settle(
+    stock: &mut Stock,
+    resolver: &mut MockResolver,
+    contract_id: ContractId,
+    transition: Transition,
+    prevouts: &[Outpoint],
+    height: u32,
+) -> Txid {
+    let fascia = anchor(contract_id, transition, witness(prevouts)).unwrap();
+    let pos = WitnessPos::bitcoin(NonZeroU32::new(height).unwrap(), CREATED_AT + 600).unwrap();
+    resolver.add_witness(fascia.seal_witness.public.tx().unwrap().clone(), WitnessOrd::Mined(pos));
+    consume(stock, fascia, resolver.clone()).unwrap()
+}
+
+struct RoundTrip {
+    contract_id: ContractId,
+    witness_id: Txid,
+    sender: Stock,
+    receiver: Stock,
+}
+
+/// Issues the contract, pays the beneficiary with the transition returned by `pay` together with
+/// the outpoints it spends and accepts the transfer into the receiver stock.
+fn round_trip<I: IssuerWrapper>(
+    issue: ContractBuilder,
+    pay: impl FnOnce(&Stock, ContractId, BuilderSeal<GraphSeal>) -> (Transition, Vec<Outpoint>),
+) -> RoundTrip {
+    let mut resolver = MockResolver::with_chain_net(CHAIN_NET);
+    let contract = issue.issue_contract().unwrap();
+    let contract_id = contract.contract_id();
+    let mut sender = Stock::in_memory();
+    sender.import_contract(contract, &resolver).unwrap();
+
+    let receiver_seal = GraphSeal::new_random(receiver_outpoint().txid, receiver_outpoint().vout);
+    let beneficiary = BuilderSeal::Concealed(receiver_seal.to_secret_seal());
+    let (transition, prevouts) = pay(&sender, contract_id, beneficiary);
+    let witness_id = settle(&mut sender, &mut resolver, contract_id, transition, &prevouts, 100);
+
+    let transfer =
+        consignment(&sender, contract_id, [receiver_seal.to_secret_seal()], [], witness_id)
+            .unwrap();
+    let mut receiver = Stock::in_memory();
+    receiver.store_secret_seal(receiver_seal).unwrap();
+    let status = accept::<I, _, _, _>(&mut receiver, transfer, CHAIN_NET, &resolver).unwrap();
+    assert!(status.tx_ord_map.contains_key(&witness_id), "{status:?}");
+
+    RoundTrip {
+        contract_id,
+        witness_id,
+        sender,
+        receiver,
+    }
+}
+
+macro_rules! fungible_round_trip {
+    ($issuer:ty, $issue:expr, $signed:expr) => {{
+        let genesis = Outpoint::new(txid(), 1);
+        let paid = round_trip::<$issuer>($issue, |stock, contract_id, beneficiary| {
+            let selection = stock
+                .contract_wrapper::<$issuer>(contract_id)
+                .unwrap()
+                .spendable([genesis])
+                .select(Amount::from(600u64))
+                .unwrap();
+            let builder = stock.transition_builder(contract_id, "transfer").unwrap();
+            let mut transition =
+                fungible_transition(builder, &selection, beneficiary, graph_seal(1)).unwrap();
+            if $signed {
+                transition.signature = Some(sign(&transition));
+            }
+            (transition, selection.outpoints)
+        });
+        let balances = |stock: &Stock| {
+            stock
+                .contract_wrapper::<$issuer>(paid.contract_id)
+                .unwrap()
+                .allocations(&FilterIncludeAll)
+                .map(|a| (a.seal, a.state.value()))
+                .collect::<Vec<_>>()
+        };
+        let received = balances(&paid.receiver);
+        assert!(received.contains(&(OutputSeal::new(receiver_outpoint()), 600)), "{received:?}");
+        let change = OutputSeal::new(Outpoint::new(paid.witness_id, 1));
+        let sent = balances(&paid.sender);
+        assert!(sent.contains(&(change, 400)), "{sent:?}");
+        let metrics = ContractMetrics::from_stock(&paid.sender, paid.contract_id).unwrap();
+        assert_eq!((metrics.operations, metrics.spent_allocations), (2, 1), "{metrics:?}");
+    }};
+}
+
+#[test]
+fn nia() { fungible_round_trip!(NonInflatableAsset, common::nia(1000, 1000), false) }
+
+#[test]
+fn cfa() { fungible_round_trip!(CollectibleFungibleAsset, common::cfa(1000, 1000), false) }
+
+#[test]
+fn pfa() {
+    fungible_round_trip!(PermissionedFungibleAsset, common::pfa(1000, 1000, issuer_pubkey()), true)
+}
+
+#[test]
+fn pfa_after_key_rotation() {
+    let mut resolver = MockResolver::with_chain_net(CHAIN_NET);
+    let contract = common::pfa(1000, 1000, issuer_pubkey())
+        .issue_contract()
+        .unwrap();
+    let contract_id = contract.contract_id();
+    let genesis_id = contract.genesis.id();
+    let mut sender = Stock::in_memory();
+    sender.import_contract(contract, &resolver).unwrap();
+
+    let mut rotation = sender
+        .transition_builder(contract_id, "rotateKey")
+        .unwrap()
+        .add_input(Opout::new(genesis_id, OS_ROTATE_KEY, 0), AllocatedState::Void)
+        .unwrap()
+        .add_global_state("pubkey", cosigner_pubkey())
+        .unwrap()
+        .add_rights("rotateKeyRight", graph_seal(1))
+        .unwrap()
+        .complete_transition()
+        .unwrap();
+    rotation.signature = Some(sign(&rotation));
+    let rotation_id = rotation.id();
+    let prevouts = [Outpoint::new(txid(), 2)];
+    settle(&mut sender, &mut resolver, contract_id, rotation, &prevouts, 100);
+
+    let receiver_seal = GraphSeal::new_random(receiver_outpoint().txid, receiver_outpoint().vout);
+    let beneficiary = receiver_seal.to_secret_seal();
+    let selection = sender
+        .contract_wrapper::<PermissionedFungibleAsset>(contract_id)
+        .unwrap()
+        .spendable([Outpoint::new(txid(), 1)])
+        .select(Amount::from(600u64))
+        .unwrap();
+    let builder = sender.transition_builder(contract_id, "transfer").unwrap();
+    let mut transfer = fungible_transition(
+        builder,
+        &selection,
+        BuilderSeal::Concealed(beneficiary),
+        graph_seal(1),
+    )
+    .unwrap();
+    transfer.signature = Some(sign_with(&transfer, &cosigner_key()));
+    let witness_id =
+        settle(&mut sender, &mut resolver, contract_id, transfer, &selection.outpoints, 101);
+
+    // the transfer is signed by the rotated key, which a receiver can't learn from its history
+    let mut receiver = Stock::in_memory();
+    receiver.store_secret_seal(receiver_seal).unwrap();
+    let partial = consignment(&sender, contract_id, [beneficiary], [], witness_id).unwrap();
+    accept::<PermissionedFungibleAsset, _, _, _>(&mut receiver, partial, CHAIN_NET, &resolver)
+        .unwrap_err();
+
+    let transfer =
+        consignment(&sender, contract_id, [beneficiary], [rotation_id], witness_id).unwrap();
+    let status =
+        accept::<PermissionedFungibleAsset, _, _, _>(&mut receiver, transfer, CHAIN_NET, &resolver)
+            .unwrap();
+    assert!(status.tx_ord_map.contains_key(&witness_id), "{status:?}");
+    let wrapper = receiver
+        .contract_wrapper::<PermissionedFungibleAsset>(contract_id)
+        .unwrap();
+    assert_eq!(wrapper.pubkey(), cosigner_pubkey());
+    let received = wrapper
+        .allocations(&FilterIncludeAll)
+        .map(|a| (a.seal, a.state.value()))
+        .collect::<Vec<_>>();
+    assert!(received.contains(&(OutputSeal::new(receiver_outpoint()), 600)), "{received:?}");
+}
+
+#[test]
+fn ifa() {
+    fungible_round_trip!(InflatableFungibleAsset, common::ifa(1000, 1000, 1500, 500), false)
+}
+
+#[test]
+fn uda() {
+    let paid = round_trip::<UniqueDigitalAsset>(
+        common::uda(2, 2, 1),
+        |stock, contract_id, beneficiary| {
+            let inputs = stock
+                .contract_wrapper::<UniqueDigitalAsset>(contract_id)
+                .unwrap()
+                .allocations(&FilterIncludeAll)
+                .collect::<Vec<_>>();
+            let builder = stock.transition_builder(contract_id, "transfer").unwrap();
+            let token = Allocation::with(TokenIndex::from_inner(2), 1);
+            let transition = data_transition(builder, &inputs, [(beneficiary, token)]).unwrap();
+            (transition, vec![Outpoint::new(txid(), 1)])
+        },
+    );
+    let received = paid
+        .receiver
+        .contract_wrapper::<UniqueDigitalAsset>(paid.contract_id)
+        .unwrap()
+        .allocations(&FilterIncludeAll)
+        .map(|a| a.seal)
+        .collect::<Vec<_>>();
+    assert!(received.contains(&OutputSeal::new(receiver_outpoint())), "{received:?}");
+}
+
+#[test]
+fn invalid_transfers() {
+    let (stock, contract_id) = stock_with(common::nia(1000, 1000));
+    let nia = stock
+        .contract_wrapper::<NonInflatableAsset>(contract_id)
+        .unwrap();
+    let beneficiary = BuilderSeal::from(graph_seal(2));
+    let builder = || stock.transition_builder(contract_id, "transfer").unwrap();
+
+    let nothing = nia.spendable([]).select(Amount::ZERO).unwrap();
+    assert_eq!(
+        fungible_transition(builder(), &nothing, beneficiary, graph_seal(1)),
+        Err(TransferError::NoInputs)
+    );
+
+    let selection = nia
+        .spendable([Outpoint::new(txid(), 1)])
+        .select(Amount::from(1000u64))
+        .unwrap();
+    let transition =
+        fungible_transition(builder(), &selection, beneficiary, graph_seal(1)).unwrap();
+    let mut no_opret = witness(&selection.outpoints);
+    no_opret.output.remove(0);
+    assert!(matches!(anchor(contract_id, transition, no_opret), Err(TransferError::Anchor(_))));
+    let no_payment = Selection {
+        change: selection.total,
+        ..selection
+    };
+    assert_eq!(
+        fungible_transition(builder(), &no_payment, beneficiary, graph_seal(1)),
+        Err(TransferError::Invalid(Error::ZeroAmount))
+    );
+    assert_eq!(data_transition(builder(), &[], []), Err(TransferError::NoInputs));
+}